@@ -10,7 +10,7 @@ use zo_sir::Sir;
 use zo_span::Span;
 use zo_token::{LiteralStore, Token};
 use zo_tree::Tree;
-use zo_ty::Annotation;
+use zo_ty::{Annotation, TyId};
 use zo_ty_checker::TyChecker;
 use zo_value::FunDef;
 
@@ -19,11 +19,16 @@ use rustc_hash::FxHashMap as HashMap;
 use std::path::PathBuf;
 
 /// Represents the result of semantic analysis.
+#[derive(Default)]
 pub struct SemanticResult {
   /// The semantic intermediate representation [`Sir`].
   pub sir: Sir,
   /// The collections of types [`Annotation`].
   pub annotations: Vec<Annotation>,
+  /// The type every value-producing node of the main pass
+  /// resolved to — what `--emit=types` prints. Empty unless
+  /// [`AnalyzerConfig::node_tys`] is set.
+  pub node_tys: Vec<Annotation>,
   /// Function definitions from the executor (carries
   /// return_type_args for ext functions).
   pub funs: Vec<FunDef>,
@@ -47,6 +52,14 @@ pub struct SemanticResult {
   pub component_bodies: Vec<ExportedComponentBody>,
   /// Maps each identifier use-span to its definition-span.
   pub use_def_map: HashMap<Span, Span>,
+  /// Maps each non-exhaustive `match` body-`{` span to the
+  /// patterns of its missing arms.
+  pub missing_arms: HashMap<Span, Vec<String>>,
+  /// Maps each generic call site to the type bound to each
+  /// `$T` there.
+  pub generic_args: HashMap<Span, Vec<TyId>>,
+  /// Maps each local's declaration span to its type.
+  pub local_tys: HashMap<Span, TyId>,
   /// Pack symbol → absolute source path for every compiled
   /// module. Populated by the compiler during module
   /// resolution so the LSP can resolve cross-file jumps.
@@ -103,6 +116,10 @@ pub struct AnalyzerConfig {
   /// resolves spans against the correct source text.
   /// `0` = entry file. `0xFFFF` = unset (default).
   pub file_id: u16,
+  /// `true` to record the type of every value-producing node
+  /// (`--emit=types`, the language server). Off by default —
+  /// a plain build never reads them.
+  pub node_tys: bool,
 }
 
 /// Represents the [`Analyzer`] phase.
@@ -177,18 +194,7 @@ impl<'a> Analyzer<'a> {
         self.tree.eof_span(),
       ));
 
-      // TODO: derive default to `SemanticResult` and return `SemanticResult::default()`
-      return SemanticResult {
-        sir: Sir::new(),
-        annotations: Vec::new(),
-        funs: Vec::new(),
-        abstract_defs: HashMap::default(),
-        abstract_impls: HashMap::default(),
-        generic_bodies: Vec::new(),
-        component_bodies: Vec::new(),
-        use_def_map: HashMap::default(),
-        pack_paths: HashMap::default(),
-      };
+      return SemanticResult::default();
     }
 
     let mut executor =
@@ -203,6 +209,7 @@ impl<'a> Analyzer<'a> {
       is_entry: _,
       test_mode: _,
       file_id,
+      node_tys,
     } = self.config;
 
     if !imports.is_empty() {
@@ -227,17 +234,25 @@ impl<'a> Analyzer<'a> {
 
     executor = executor.with_file_id(file_id);
 
+    if node_tys {
+      executor = executor.with_node_tys();
+    }
+
     let execute_result = executor.execute();
 
     SemanticResult {
       sir: execute_result.sir,
       annotations: execute_result.annotations,
+      node_tys: execute_result.node_tys,
       abstract_defs: execute_result.abstract_defs,
       abstract_impls: execute_result.abstract_impls,
       funs: execute_result.funs,
       generic_bodies: execute_result.generic_bodies,
       component_bodies: execute_result.component_bodies,
       use_def_map: execute_result.use_def_map,
+      missing_arms: execute_result.missing_arms,
+      generic_args: execute_result.generic_args,
+      local_tys: execute_result.local_tys,
       pack_paths: HashMap::default(),
     }
   }
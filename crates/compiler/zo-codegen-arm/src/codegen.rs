@@ -12,7 +12,7 @@ use zo_emitter_arm::{
   X11, X16, X17, X29, X30, XZR,
 };
 use zo_interner::{DenseMap, Interner, Sentinel, Symbol};
-use zo_module_resolver::{AbstractDef, AbstractImpl};
+use zo_module_resolver::{AbstractDef, AbstractImpl, vtable_methods};
 use zo_register_allocation::{
   AllocInput, EmitTiming, EnumPayloadFields, FnKey, IO_RESULT_FRAME_SLOTS,
   IO_SHARED_BUF_SLOTS, RegAlloc, RegisterClass, SpillKind,
@@ -1272,11 +1272,13 @@ impl<'a> ARM64Gen<'a> {
     matches!(
       insn,
       Insn::ArrayLiteral { .. }
+        | Insn::ClosureEnv { .. }
         | Insn::ArrayPush { .. }
         | Insn::ChannelCreate { .. }
         | Insn::ChannelSend { .. }
         | Insn::ChannelRecv { .. }
         | Insn::ChannelClose { .. }
+        | Insn::ClosureEnvFree { .. }
         | Insn::TaskSpawn { .. }
         | Insn::TaskAwait { .. }
         | Insn::TaskCancelled { .. }
@@ -1608,8 +1610,10 @@ impl<'a> ARM64Gen<'a> {
       // `ArrayLiteral`/`ArrayPush`/`ArrayPop` may call `_malloc`
       // / `_realloc` / `_zo_box_alloc`, but those are ABI-
       // conforming `bl`s that preserve x19..x28, so a promoted
-      // local survives them for free.
+      // local survives them for free. `ClosureEnv` is the same
+      // `_malloc` + element stores.
       Insn::ArrayLiteral { .. }
+      | Insn::ClosureEnv { .. }
       | Insn::ArrayIndex { .. }
       | Insn::ArrayStore { .. }
       | Insn::ArrayLen { .. }
@@ -2883,9 +2887,11 @@ impl<'a> ARM64Gen<'a> {
   /// concrete value carries through the AAPCS); slots
   /// 1..N are zero-initialised placeholders that the
   /// linker fills with `method_addr − vtable_addr`
-  /// offsets via `vtable_fixups`. Method order tracks
-  /// `impl_entry.methods`, keeping the per-slot index
-  /// aligned with `Insn::DynDispatch.method_index`.
+  /// offsets via `vtable_fixups`. Method order is the
+  /// abstract's `vtable_methods` — the supers' methods
+  /// first, each implemented by its own `apply` — keeping
+  /// the per-slot index aligned with
+  /// `Insn::DynDispatch.method_index`.
   ///
   /// Vtable bytes ride the TEXT-trailing pattern (same
   /// as string / template data); code-side ADR loaders
@@ -2895,11 +2901,13 @@ impl<'a> ARM64Gen<'a> {
     abstract_defs: &HashMap<Symbol, AbstractDef>,
     abstract_impls: &HashMap<(Symbol, Symbol), AbstractImpl>,
   ) {
-    for ((abs_sym, _concrete_sym), impl_entry) in abstract_impls {
-      let Some(def) = abstract_defs.get(abs_sym) else {
+    for (&(abs_sym, concrete_sym), impl_entry) in abstract_impls {
+      if !abstract_defs.contains_key(&abs_sym) {
         continue;
-      };
-      let n = def.methods.len();
+      }
+
+      let methods = vtable_methods(abstract_defs, abs_sym);
+      let n = methods.len();
       let blob_len = 8 * (1 + n);
       let mut blob = Vec::with_capacity(blob_len);
 
@@ -2912,13 +2920,28 @@ impl<'a> ARM64Gen<'a> {
       blob.extend_from_slice(&8u64.to_le_bytes());
       blob.resize(blob_len, 0);
 
-      // Slots 1..=N: per-method fixups. `impl_entry
-      // .methods[i]` matches the abstract's `methods[i]`
-      // by construction (the apply-block parser slices
-      // `funs[funs_baseline..]` in declaration order),
-      // so the index aligns with
-      // `Insn::DynDispatch.method_index`.
-      for (i, &method_sym) in impl_entry.methods.iter().take(n).enumerate() {
+      // Slots 1..=N: per-method fixups. An inherited
+      // method comes from the `apply` of the super that
+      // declares it; either way the impl's mangled
+      // `Type::method` is matched by its last segment.
+      for (i, (owner, method)) in methods.into_iter().enumerate() {
+        let entry = if owner == abs_sym {
+          Some(impl_entry)
+        } else {
+          abstract_impls.get(&(owner, concrete_sym))
+        };
+
+        let name = self.interner.get(method.name);
+        let method_sym = entry.and_then(|entry| {
+          entry.methods.iter().copied().find(|&sym| {
+            self.interner.get(sym).rsplit("::").next() == Some(name)
+          })
+        });
+
+        let Some(method_sym) = method_sym else {
+          continue;
+        };
+
         self.vtable_fixups.push(VtableSlotFixup {
           vtable_sym: impl_entry.vtable_sym,
           slot_offset: 8 * (1 + i) as u32,
@@ -3870,77 +3893,22 @@ impl<'a> ARM64Gen<'a> {
           }
         } else if ty_id.0 == STR_TYPE_ID && matches!(op, BinOp::Eq | BinOp::Neq)
         {
-          // String equality: compare lengths first, then
-          // _memcmp on data pointers. String struct: [len:8, ptr:8].
-          // zo strings are NOT null-terminated.
+          // String equality: `_zo_str_eq(lhs, rhs)` compares
+          // by content, so an owned str and a slice view
+          // (`[len | VIEW][ptr]`) holding the same bytes are
+          // equal. It returns 1 when equal.
           let d = self.alloc_reg(*dst).unwrap_or(X0);
           let l = self.alloc_reg(*lhs).unwrap_or(X0);
           let r = self.alloc_reg(*rhs).unwrap_or(X1);
           let cond = if matches!(op, BinOp::Eq) {
-            COND_EQ
-          } else {
             COND_NE
-          };
-
-          // Load lengths: [lhs+0] and [rhs+0].
-          self.emitter.emit_ldr(X16, l, 0);
-          self.emitter.emit_ldr(X17, r, 0);
-
-          // Compare lengths — if different, strings differ.
-          self.emitter.emit_cmp(X16, X17);
-
-          // If lengths differ, skip memcmp — result is "not equal".
-          let len_skip = self.emitter.current_offset();
-          self.emitter.emit_bne(0); // patched below
-
-          // Lengths match — call _memcmp(ptr1, ptr2, len).
-          // String layout is inline: [len:8][data...][null].
-          // Data starts at base + 8, not *(base + 8).
-          //
-          // Register aliasing: `r` may be allocated to X0 (the
-          // destination of the upcoming `add X0, l, 8`). In
-          // that case emitting the lhs add first clobbers the
-          // rhs pointer before the rhs add reads it. Similarly,
-          // `l` may alias X1. Pick a safe ordering — and if
-          // both alias simultaneously, stash rhs in a scratch
-          // register first.
-          if r == X0 && l == X1 {
-            self.emitter.emit_mov_reg(X9, r);
-            self.emitter.emit_add_imm(X0, l, 8); // ptr1
-            self.emitter.emit_add_imm(X1, X9, 8); // ptr2
-          } else if r == X0 {
-            self.emitter.emit_add_imm(X1, r, 8); // ptr2 first
-            self.emitter.emit_add_imm(X0, l, 8); // ptr1
           } else {
-            self.emitter.emit_add_imm(X0, l, 8); // ptr1
-            self.emitter.emit_add_imm(X1, r, 8); // ptr2
-          }
-          self.emitter.emit_mov_reg(X2, X16); // len
-          self.emit_extern_call("_memcmp");
+            COND_EQ
+          };
 
-          // memcmp returns 0 if equal.
+          self.emit_safe_int_arg_moves(&[(X0, l), (X1, r)]);
+          self.emit_extern_call("_zo_str_eq");
           self.emit_cmp_csel(d, X0, XZR, cond);
-
-          // Jump past the "not equal" fallback.
-          let done_skip = self.emitter.current_offset();
-          self.emitter.emit_b(0); // patched below
-
-          // Patch len_skip B.NE to land here.
-          let here = self.emitter.current_offset();
-          let len_off = here as i32 - len_skip as i32;
-          self.emitter.patch_bcond_at(len_skip as usize, len_off);
-
-          // Lengths differ → set result.
-          if matches!(op, BinOp::Eq) {
-            self.emitter.emit_mov_imm(d, 0);
-          } else {
-            self.emitter.emit_mov_imm(d, 1);
-          }
-
-          // Patch done_skip B to land here.
-          let end = self.emitter.current_offset();
-          let done_off = end as i32 - done_skip as i32;
-          self.emitter.patch_b_at(done_skip as usize, done_off);
         } else if self.enum_metas.contains_key(&ty_id.0)
           && matches!(op, BinOp::Eq | BinOp::Neq)
         {
@@ -4224,6 +4192,7 @@ impl<'a> ARM64Gen<'a> {
           "HashMap::get" => self.emit_map_get(args, idx),
           "HashMap::contains_key" => self.emit_map_contains(args, idx),
           "HashMap::remove" => self.emit_map_remove(args, idx),
+          "MapKeys::key_at" => self.emit_map_key_at(args, idx),
 
           // Vec apply-method dispatch. Same convention as
           // HashMap: `len`, `is_empty`, `free` are pure-zo
@@ -4281,6 +4250,7 @@ impl<'a> ARM64Gen<'a> {
           // export with no byte marshaling.
           "zo_map_len_raw" => self.emit_map_len_raw(args, idx),
           "zo_map_free_raw" => self.emit_map_free_raw(args, idx),
+          "zo_map_next_slot_raw" => self.emit_map_next_slot_raw(args, idx),
           "zo_vec_len_raw" => self.emit_vec_len_raw(args, idx),
           "zo_vec_free_raw" => self.emit_vec_free_raw(args, idx),
           "zo_set_len_raw" => self.emit_set_len_raw(args, idx),
@@ -4609,18 +4579,25 @@ impl<'a> ARM64Gen<'a> {
               } else {
                 let outer_field_tys: Option<Vec<TyId>> =
                   self.type_view.and_then(|view| {
-                    let Ty::Struct(sid) = resolve_ty(view.tys, ret_ty) else {
-                      return None;
-                    };
-                    let st = view.ty_table.struct_ty(sid)?;
-                    Some(
-                      view
-                        .ty_table
-                        .struct_fields(st)
-                        .iter()
-                        .map(|f| f.ty_id)
-                        .collect(),
-                    )
+                    match resolve_ty(view.tys, ret_ty) {
+                      Ty::Struct(sid) => {
+                        let st = view.ty_table.struct_ty(sid)?;
+                        Some(
+                          view
+                            .ty_table
+                            .struct_fields(st)
+                            .iter()
+                            .map(|f| f.ty_id)
+                            .collect(),
+                        )
+                      }
+                      // A tuple is laid out like a struct.
+                      Ty::Tuple(tid) => {
+                        let tuple = view.ty_table.tuple(tid)?;
+                        Some(view.ty_table.tuple_elems(tuple).to_vec())
+                      }
+                      _ => None,
+                    }
                   });
 
                 match outer_field_tys {
@@ -5163,63 +5140,8 @@ impl<'a> ARM64Gen<'a> {
         // initial allocation reduces idle bytes per
         // function.
         let initial_cap: u32 = if n == 0 { 1024 } else { n };
-        let alloc_size =
-          (ARRAY_HEADER_SIZE as u32 + initial_cap * STACK_SLOT_SIZE) as u64;
-
-        // The buffer pointer lives in x17 (IP1 scratch): it is
-        // the `_malloc` result, produced AFTER the call, so it
-        // never needs to survive the call, and no further calls
-        // run before the buffer is spilled. Holding it in
-        // scratch — not the x19..x28 promotion bank — keeps a
-        // promoted local intact, so this literal is sound inside
-        // a promoting function. Element materialization writes
-        // x16, leaving x17 untouched.
-        let r_buf = X17;
-
-        // An array literal element resident in X0 (a call result
-        // the allocator parked there) would be lost when malloc
-        // overwrites X0 with the heap pointer. Save it before the
-        // call and write it into the buffer right after — before
-        // the header writes touch x16 — so the main loop can skip
-        // it. This guard is effectively dead under the current
-        // allocator (X0 is reserved for call results and never
-        // enters the GP pool), but kept for correctness.
-        let x0_elem_index =
-          elements.iter().position(|e| self.alloc_reg(*e) == Some(X0));
-        let x0_save_off =
-          self.caller_save_base + CALLER_SAVE_COUNT as u32 * STACK_SLOT_SIZE;
-
-        if x0_elem_index.is_some() {
-          self.emit_str_sp(X0, x0_save_off);
-        }
-
-        self.emit_mov_imm_64(X0, alloc_size);
-        self.emit_extern_call("_malloc");
-        self.emitter.emit_mov_reg(r_buf, X0);
-
-        if let Some(i) = x0_elem_index {
-          let off = ARRAY_HEADER_SIZE + (i as u16) * (STACK_SLOT_SIZE as u16);
-
-          self.emit_ldr_sp(X16, x0_save_off);
-          self.emitter.emit_str(X16, r_buf, off as i16);
-        }
-
-        // Header: len = n, cap = initial_cap.
-        self.emit_mov_imm_64(X16, n as u64);
-        self.emitter.emit_str(X16, r_buf, 0);
-        self.emit_mov_imm_64(X16, initial_cap as u64);
-        self.emitter.emit_str(X16, r_buf, 8);
-
-        for (i, elem) in elements.iter().enumerate() {
-          if Some(i) == x0_elem_index {
-            continue;
-          }
-
-          let off_u16 =
-            ARRAY_HEADER_SIZE + (i as u16) * (STACK_SLOT_SIZE as u16);
-
-          self.emit_array_element_store(*elem, all_insns, r_buf, off_u16);
-        }
+        let r_buf =
+          self.emit_heap_array(elements, initial_cap, None, all_insns);
 
         // Spill the heap pointer to a stack slot so later
         // Store/Load can find it. Same shape as the original
@@ -5246,10 +5168,10 @@ impl<'a> ARM64Gen<'a> {
         let is_str_index = ty_id.0 == CHAR_TYPE_ID;
 
         if is_str_index {
-          // String layout: [len: u64][bytes][null].
-          // Byte at index i is at base + 8 + i.
+          // String layout: [len: u64][bytes][null], or a
+          // slice view. Byte at index i is at data + i.
           // Bounds check: index < len, else exit(1).
-          self.emitter.emit_ldr(X16, arr_reg, 0);
+          self.emit_str_parts(X16, X17, arr_reg);
           self.emitter.emit_cmp(idx_reg, X16);
           // B.CC (unsigned <) — in-bounds, skip panic.
           let bcc_pos = self.emitter.current_offset();
@@ -5263,9 +5185,8 @@ impl<'a> ARM64Gen<'a> {
           self
             .emitter
             .patch_bcond_at(bcc_pos as usize, here - bcc_pos as i32);
-          // LDRB: load byte at base + 8 + index.
-          self.emitter.emit_add_imm(X16, arr_reg, 8);
-          self.emitter.emit_add(X16, X16, idx_reg);
+          // LDRB: load byte at data + index.
+          self.emitter.emit_add(X16, X17, idx_reg);
 
           if let Some(dst_reg) = self.alloc_reg(*dst) {
             self.emitter.emit_ldrb(dst_reg, X16, 0);
@@ -5334,11 +5255,15 @@ impl<'a> ARM64Gen<'a> {
       }
 
       Insn::ArrayLen { dst, array, .. } => {
-        // Length at [base + 0].
+        // Length at [base + 0]. The shift pair clears a str
+        // slice view's tag bit — array and owned str
+        // lengths never set it.
         if let Some(dst_reg) = self.alloc_reg(*dst) {
           let arr_reg = self.alloc_reg(*array).unwrap_or(X0);
 
           self.emitter.emit_ldr(dst_reg, arr_reg, 0);
+          self.emitter.emit_lsl(dst_reg, dst_reg, 1);
+          self.emitter.emit_lsr(dst_reg, dst_reg, 1);
         }
       }
 
@@ -5873,6 +5798,17 @@ impl<'a> ARM64Gen<'a> {
 
         self.emit_extern_call("_zo_chan_close");
       }
+      Insn::ClosureEnvFree { env } => {
+        // ABI: `_zo_closure_env_free(env)` — runs the env's drop
+        // glue, then frees the block.
+        if let Some(env_reg) = self.alloc_reg(*env)
+          && env_reg != X0
+        {
+          self.emitter.emit_mov_reg(X0, env_reg);
+        }
+
+        self.emit_extern_call("_zo_closure_env_free");
+      }
       Insn::FnAddr {
         dst,
         callee,
@@ -5892,6 +5828,20 @@ impl<'a> ARM64Gen<'a> {
             .push((adr_pos, (*callee, *callee_pack)));
         }
       }
+      Insn::ClosureEnv { drop, captures, .. } => {
+        // A capturing closure's environment: a heap block in
+        // the `[]T` layout, one slot per capture, so the
+        // `__env` trampoline reads slot `i` with `ArrayIndex`,
+        // with the drop glue one word below the header.
+        // Never spilled to a frame slot — the block outlives
+        // this frame (a pool worker may run it later).
+        let n = captures.len() as u32;
+        let r_buf = self.emit_heap_array(captures, n, Some(*drop), all_insns);
+
+        if let Some(dst) = self.reg_for_insn(idx) {
+          self.emitter.emit_mov_reg(dst, r_buf);
+        }
+      }
       Insn::TaskSpawn {
         dst,
         kind,
@@ -6805,8 +6755,7 @@ impl<'a> ARM64Gen<'a> {
         self.emitter.emit_mov_reg(X0, Register::new(23));
       }
 
-      self.emitter.emit_ldr(X2, X0, 0);
-      self.emitter.emit_add_imm(X1, X0, 8);
+      self.emit_str_parts(X2, X1, X0);
       self.emitter.emit_mov_imm(X16, SYS_WRITE);
       self.emitter.emit_mov_imm(X0, fd);
       self.emitter.emit_svc(0);
@@ -7300,22 +7249,69 @@ impl<'a> ARM64Gen<'a> {
   /// file descriptor `fd`.
   ///
   /// @note — a zo `str` is a pointer to `[len: u64][bytes]
-  /// [null]`. The pointer moves to X16 first so reusing X1 /
-  /// X2 for the `write` syscall args cannot clobber it.
+  /// [null]` or to a slice view. The pointer moves to X16
+  /// first so reusing X1 / X2 for the `write` syscall args
+  /// cannot clobber it.
   fn emit_zo_str_write(&mut self, ptr_reg: Register, fd: u16) {
     if ptr_reg != X16 {
       self.emitter.emit_mov_reg(X16, ptr_reg);
     }
 
-    // LDR X2, [X16, #0] — length header at offset 0.
-    self.emitter.emit_ldr(X2, X16, 0);
-    // ADD X1, X16, #8 — payload starts at offset 8.
-    self.emitter.emit_add_imm(X1, X16, 8);
+    self.emit_str_parts(X2, X1, X16);
     self.emitter.emit_mov_imm(X16, SYS_WRITE);
     self.emitter.emit_mov_imm(X0, fd);
     self.emitter.emit_svc(0);
   }
 
+  /// Unpack the zo `str` at `src` into its byte length
+  /// (`len`) and payload address (`data`).
+  ///
+  /// @note — an owned str holds its bytes inline at `src +
+  /// 8`. A slice view (`_zo_str_slice`) sets the top bit of
+  /// the length word and holds a pointer into its parent at
+  /// `src + 8` — a negative length word picks that branch
+  /// and the shift pair clears the tag. `len` must differ
+  /// from `src`; `data` may alias it.
+  fn emit_str_parts(&mut self, len: Register, data: Register, src: Register) {
+    self.emitter.emit_ldr(len, src, 0);
+    self.emitter.emit_cmp_imm(len, 0);
+
+    let owned_pos = self.emitter.current_offset();
+
+    self.emitter.emit_bge(0);
+    self.emitter.emit_ldr(data, src, 8);
+    self.emitter.emit_lsl(len, len, 1);
+    self.emitter.emit_lsr(len, len, 1);
+
+    let done_pos = self.emitter.current_offset();
+
+    self.emitter.emit_b(0);
+
+    let owned = self.emitter.current_offset();
+
+    self
+      .emitter
+      .patch_bcond_at(owned_pos as usize, owned as i32 - owned_pos as i32);
+    self.emitter.emit_add_imm(data, src, 8);
+
+    let done = self.emitter.current_offset();
+
+    self
+      .emitter
+      .patch_b_at(done_pos as usize, done as i32 - done_pos as i32);
+  }
+
+  /// Load a NUL-terminated copy of the zo `str` at `path`
+  /// into X0 via `_zo_str_cstr` — the payload in place for
+  /// an owned str, a fresh copy for a slice view.
+  fn emit_str_cstr(&mut self, path: Register) {
+    if path != X0 {
+      self.emitter.emit_mov_reg(X0, path);
+    }
+
+    self.emit_extern_call("_zo_str_cstr");
+  }
+
   /// Emit bool-to-string write: prints "true" or "false".
   /// X0 holds the bool value (0 or 1).
   fn emit_bool_and_write(&mut self, fd: u16) {
@@ -7862,7 +7858,7 @@ impl<'a> ARM64Gen<'a> {
   fn emit_io_exists(&mut self, args: &[ValueId], idx: usize) {
     let path = args.first().and_then(|v| self.alloc_reg(*v)).unwrap_or(X0);
 
-    self.emitter.emit_add_imm(X0, path, 8);
+    self.emit_str_cstr(path);
     self.emitter.emit_mov_imm(X1, 0);
     self.emitter.emit_mov_imm(X16, SYS_ACCESS);
     self.emitter.emit_svc(0);
@@ -7893,7 +7889,7 @@ impl<'a> ARM64Gen<'a> {
     let scratch_off = result_base + 2 * STACK_SLOT_SIZE;
 
     // --- open ---
-    self.emitter.emit_add_imm(X0, path, 8);
+    self.emit_str_cstr(path);
     self.emitter.emit_mov_imm(X1, O_READ_ONLY);
     self.emitter.emit_mov_imm(X2, 0);
     self.emitter.emit_mov_imm(X16, SYS_OPEN);
@@ -7934,12 +7930,12 @@ impl<'a> ARM64Gen<'a> {
 
   /// `remove_file(path: str) -> bool` via inline SYS_unlink.
   ///
-  /// @note — `path + 8` skips the zo str header so the
-  /// kernel sees the NUL-terminated payload directly.
+  /// @note — `_zo_str_cstr` hands the kernel the
+  /// NUL-terminated payload.
   fn emit_io_remove(&mut self, args: &[ValueId], idx: usize) {
     let path = args.first().and_then(|v| self.alloc_reg(*v)).unwrap_or(X0);
 
-    self.emitter.emit_add_imm(X0, path, 8);
+    self.emit_str_cstr(path);
     self.emitter.emit_mov_imm(X16, SYS_UNLINK);
     self.emitter.emit_svc(0);
 
@@ -7951,12 +7947,12 @@ impl<'a> ARM64Gen<'a> {
 
   /// `read_dir(path: str) -> []str` via `_zo_io_read_dir`.
   ///
-  /// @note — `path + 8` skips the zo str header so the
-  /// runtime receives the NUL-terminated payload directly.
+  /// @note — `_zo_str_cstr` hands the runtime the
+  /// NUL-terminated payload.
   fn emit_io_read_dir(&mut self, args: &[ValueId], idx: usize) {
     let path = args.first().and_then(|v| self.alloc_reg(*v)).unwrap_or(X0);
 
-    self.emitter.emit_add_imm(X0, path, 8);
+    self.emit_str_cstr(path);
     self.emit_extern_call("_zo_io_read_dir");
 
     if let Some(dst) = self.reg_for_insn(idx) {
@@ -8087,7 +8083,7 @@ impl<'a> ARM64Gen<'a> {
     self.emit_str_sp(content, result_base + 4 * STACK_SLOT_SIZE);
 
     // --- open ---
-    self.emitter.emit_add_imm(X0, path, 8);
+    self.emit_str_cstr(path);
     self.emitter.emit_mov_imm(X1, open_flags);
     self.emitter.emit_mov_imm(X2, FILE_MODE_644);
     self.emitter.emit_mov_imm(X16, SYS_OPEN);
@@ -8100,8 +8096,7 @@ impl<'a> ARM64Gen<'a> {
     self.emitter.emit_mov_reg(X17, X0);
     // Reload saved content pointer.
     self.emit_ldr_sp(X1, result_base + 4 * STACK_SLOT_SIZE);
-    self.emitter.emit_ldr(X2, X1, 0);
-    self.emitter.emit_add_imm(X1, X1, 8);
+    self.emit_str_parts(X2, X1, X1);
     self.emitter.emit_mov_reg(X0, X17);
     self.emitter.emit_mov_imm(X16, SYS_WRITE);
     self.emitter.emit_svc(0);
@@ -8160,6 +8155,20 @@ impl<'a> ARM64Gen<'a> {
     self.labels.insert(ok_label, self.emitter.current_offset());
   }
 
+  /// Stage a struct key's `hash` / `eq` addresses — the
+  /// executor appends them to a `%% hash.` key's
+  /// `(key_kind, key_sz, val_sz)` — in X16 / X17, ahead of
+  /// the X0..X3 moves that could clobber their registers.
+  /// `0` (no callbacks) for every other key.
+  fn stage_map_key_fns(&mut self, args: &[ValueId]) {
+    for (i, scratch) in [(3, X16), (4, X17)] {
+      match args.get(i).and_then(|v| self.alloc_reg(*v)) {
+        Some(r) => self.emitter.emit_mov_reg(scratch, r),
+        None => self.emitter.emit_mov_imm(scratch, 0),
+      }
+    }
+  }
+
   /// `HashMap<K, V>::new()` — emit `BL _zo_map_new`
   /// and stash the returned `ZoMap*` in a freshly
   /// allocated struct slot. The dst register holds the
@@ -8191,6 +8200,8 @@ impl<'a> ARM64Gen<'a> {
     let ks = args.get(1).and_then(|v| self.alloc_reg(*v));
     let vs = args.get(2).and_then(|v| self.alloc_reg(*v));
 
+    self.stage_map_key_fns(args);
+
     if let Some(r) = kk {
       if r != X0 {
         self.emitter.emit_mov_reg(X0, r);
@@ -8216,6 +8227,8 @@ impl<'a> ARM64Gen<'a> {
     }
 
     self.emitter.emit_mov_imm(X3, 16);
+    self.emitter.emit_mov_reg(X4, X16);
+    self.emitter.emit_mov_reg(X5, X17);
     self.emit_extern_call("_zo_map_new");
 
     // Store the returned pointer into the struct's ptr
@@ -8294,6 +8307,48 @@ impl<'a> ARM64Gen<'a> {
     }
   }
 
+  /// `keys.key_at(slot)` — the `MapKeys` cursor's read. Same
+  /// `Option<K>` aggregate as `m.get(k)`, with the slot index
+  /// passed by value and the key copied out by
+  /// `_zo_map_key_at`. The cursor's map pointer sits at
+  /// offset 0, like `HashMap`'s.
+  fn emit_map_key_at(&mut self, args: &[ValueId], idx: usize) {
+    let recv = args.first().and_then(|v| self.alloc_reg(*v)).unwrap_or(X0);
+    let slot = args.get(1).and_then(|v| self.alloc_reg(*v)).unwrap_or(X1);
+
+    let scratch_base = self.struct_base + self.next_struct_slot;
+    let k_out_off = scratch_base;
+    let opt_base = scratch_base + STACK_SLOT_SIZE;
+
+    self.next_struct_slot += 3 * STACK_SLOT_SIZE;
+
+    self.emit_str_sp(XZR, k_out_off);
+
+    // Load the map pointer before `slot` lands in X1 — the
+    // receiver may live there.
+    self.emitter.emit_ldr(X16, recv, 0);
+
+    if slot != X1 {
+      self.emitter.emit_mov_reg(X1, slot);
+    }
+
+    self.emitter.emit_mov_reg(X0, X16);
+    self.emit_add_sp_offset(X2, k_out_off);
+    self.emit_extern_call("_zo_map_key_at");
+
+    // X0 = bool live: tag = live ? 0 : 1, val = *k_out.
+    self.emitter.emit_mov_imm(X16, 1);
+    self.emitter.emit_eor(X16, X16, X0);
+    self.emit_str_sp(X16, opt_base);
+
+    self.emit_ldr_sp(X16, k_out_off);
+    self.emit_str_sp(X16, opt_base + STACK_SLOT_SIZE);
+
+    if let Some(dst) = self.reg_for_insn(idx) {
+      self.emit_add_sp_offset(dst, opt_base);
+    }
+  }
+
   /// `m.contains_key(k)` — spill k, call `_zo_map_
   /// contains`. Returns bool in dst.
   fn emit_map_contains(&mut self, args: &[ValueId], idx: usize) {
@@ -8371,6 +8426,28 @@ impl<'a> ARM64Gen<'a> {
     }
   }
 
+  /// `zo_map_next_slot_raw(ptr, from) -> int` — direct
+  /// pass-through to `_zo_map_next_slot`, `(ptr, from)` in
+  /// X0..X1; the slot index (or `-1`) in X0.
+  fn emit_map_next_slot_raw(&mut self, args: &[ValueId], idx: usize) {
+    let ptr = args.first().and_then(|v| self.alloc_reg(*v)).unwrap_or(X0);
+    let from = args.get(1).and_then(|v| self.alloc_reg(*v)).unwrap_or(X1);
+
+    // Staged through the scratch pair so neither move clobbers
+    // the other's source.
+    self.emitter.emit_mov_reg(X16, ptr);
+    self.emitter.emit_mov_reg(X17, from);
+    self.emitter.emit_mov_reg(X0, X16);
+    self.emitter.emit_mov_reg(X1, X17);
+    self.emit_extern_call("_zo_map_next_slot");
+
+    if let Some(dst) = self.reg_for_insn(idx)
+      && dst != X0
+    {
+      self.emitter.emit_mov_reg(dst, X0);
+    }
+  }
+
   /// `zo_str_replace(src, needle, with) -> str` — direct
   /// pass-through to the runtime helper. Three pointer args
   /// in X0..X2; result pointer in X0.
@@ -8886,6 +8963,8 @@ impl<'a> ARM64Gen<'a> {
     let ks = args.get(1).and_then(|v| self.alloc_reg(*v));
     let vs = args.get(2).and_then(|v| self.alloc_reg(*v));
 
+    self.stage_map_key_fns(args);
+
     if let Some(r) = kk {
       if r != X0 {
         self.emitter.emit_mov_reg(X0, r);
@@ -8911,6 +8990,8 @@ impl<'a> ARM64Gen<'a> {
     }
 
     self.emitter.emit_mov_imm(X3, 16);
+    self.emitter.emit_mov_reg(X4, X16);
+    self.emitter.emit_mov_reg(X5, X17);
     self.emit_extern_call("_zo_map_new");
 
     self.emit_str_sp(X0, struct_base);
@@ -9380,6 +9461,86 @@ impl<'a> ARM64Gen<'a> {
     }
   }
 
+  /// Heap-allocate a `[len][cap][e0]...` array block via
+  /// `_malloc`, store `elements` into it and return the
+  /// register holding its address (x17). Shared by the
+  /// dynamic `ArrayLiteral` path and `ClosureEnv`, whose
+  /// drop glue is a `prefix` word stored just below `len`.
+  fn emit_heap_array(
+    &mut self,
+    elements: &[ValueId],
+    initial_cap: u32,
+    prefix: Option<ValueId>,
+    all_insns: &[Insn],
+  ) -> Register {
+    let n = elements.len() as u32;
+    let prefix_size = if prefix.is_some() { STACK_SLOT_SIZE } else { 0 };
+    let alloc_size = (prefix_size
+      + ARRAY_HEADER_SIZE as u32
+      + initial_cap * STACK_SLOT_SIZE) as u64;
+
+    // The buffer pointer lives in x17 (IP1 scratch): it is
+    // the `_malloc` result, produced AFTER the call, so it
+    // never needs to survive the call, and no further calls
+    // run before the buffer is spilled. Holding it in
+    // scratch — not the x19..x28 promotion bank — keeps a
+    // promoted local intact, so this literal is sound inside
+    // a promoting function. Element materialization writes
+    // x16, leaving x17 untouched.
+    let r_buf = X17;
+
+    // An array literal element resident in X0 (a call result
+    // the allocator parked there) would be lost when malloc
+    // overwrites X0 with the heap pointer. Save it before the
+    // call and write it into the buffer right after — before
+    // the header writes touch x16 — so the main loop can skip
+    // it. This guard is effectively dead under the current
+    // allocator (X0 is reserved for call results and never
+    // enters the GP pool), but kept for correctness.
+    let x0_elem_index =
+      elements.iter().position(|e| self.alloc_reg(*e) == Some(X0));
+    let x0_save_off =
+      self.caller_save_base + CALLER_SAVE_COUNT as u32 * STACK_SLOT_SIZE;
+
+    if x0_elem_index.is_some() {
+      self.emit_str_sp(X0, x0_save_off);
+    }
+
+    self.emit_mov_imm_64(X0, alloc_size);
+    self.emit_extern_call("_malloc");
+    self.emitter.emit_mov_reg(r_buf, X0);
+
+    if let Some(prefix) = prefix {
+      self.emit_array_element_store(prefix, all_insns, r_buf, 0);
+      self.emitter.emit_add_imm(r_buf, r_buf, prefix_size as u16);
+    }
+
+    if let Some(i) = x0_elem_index {
+      let off = ARRAY_HEADER_SIZE + (i as u16) * (STACK_SLOT_SIZE as u16);
+
+      self.emit_ldr_sp(X16, x0_save_off);
+      self.emitter.emit_str(X16, r_buf, off as i16);
+    }
+
+    // Header: len = n, cap = initial_cap.
+    self.emit_mov_imm_64(X16, n as u64);
+    self.emitter.emit_str(X16, r_buf, 0);
+    self.emit_mov_imm_64(X16, initial_cap as u64);
+    self.emitter.emit_str(X16, r_buf, 8);
+
+    for (i, elem) in elements.iter().enumerate() {
+      if Some(i) == x0_elem_index {
+        continue;
+      }
+
+      let off_u16 = ARRAY_HEADER_SIZE + (i as u16) * (STACK_SLOT_SIZE as u16);
+
+      self.emit_array_element_store(*elem, all_insns, r_buf, off_u16);
+    }
+
+    r_buf
+  }
+
   /// Emit `STR <elem>, [r_buf, off]` for one array literal
   /// element on the heap path. See
   /// `materialize_array_elem_into_x16` for the bypass
@@ -2,6 +2,7 @@ pub(crate) mod common;
 pub(crate) mod concurrency;
 pub(crate) mod errors;
 pub(crate) mod float_show;
+pub(crate) mod str_slicing;
 pub(crate) mod templates;
 
 use crate::ARM64Gen;
@@ -418,6 +419,7 @@ fn test_main_function_detection() {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -453,6 +455,7 @@ fn test_string_fixup() {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -819,6 +822,7 @@ fn test_str_sp_slow_path_does_not_self_clobber() {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -975,6 +979,7 @@ fn test_emit_add_sp_offset_uses_dst_not_x16_in_slow_path() {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -0,0 +1,259 @@
+//! Runtime string slices are views — `[len | 1 << 63][ptr]`
+//! headers pointing into the parent's bytes — so every
+//! consumer that reads a `str` has to tell a view from an
+//! owned `[len][bytes][NUL]` blob.
+//!
+//! These tests pin the routing: a runtime slice calls
+//! `_zo_str_slice`, and `==` on `str` compares by content
+//! through `_zo_str_eq` instead of an inline `_memcmp`
+//! over the bytes after the header. On an ARM64 Mac the
+//! slices also run — each range form, a view of a view and a
+//! bound inside a multi-byte char.
+//!
+//! ```sh
+//! cargo test -p zo-codegen-arm str_slicing
+//! ```
+
+use crate::ARM64Gen;
+
+use zo_codegen_backend::Target;
+use zo_executor::Executor;
+use zo_interner::Interner;
+use zo_linker::RuntimeKind;
+use zo_parser::Parser;
+use zo_tokenizer::Tokenizer;
+use zo_ty_checker::TyChecker;
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn compile_and_inspect<F: FnOnce(&[String])>(source: &str, check: F) {
+  let mut interner = Interner::new();
+  let tokenizer = Tokenizer::new(source, &mut interner);
+  let tokenization = tokenizer.tokenize();
+
+  let parser = Parser::new(&tokenization, source);
+  let parsing = parser.parse();
+
+  let mut ty_checker = TyChecker::new();
+
+  let executor = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty_checker,
+  );
+
+  let sir = executor.execute().sir;
+
+  let mut codegen = ARM64Gen::new(&interner);
+  let _artifact = codegen.generate(&sir);
+
+  check(codegen.extern_used());
+}
+
+#[test]
+fn runtime_slice_forms_call_str_slice() {
+  compile_and_inspect(
+    r#"
+      fun main() {
+        imu s: str = "hello, world!";
+        mut lo: int = 7;
+        mut hi: int = 11;
+
+        showln(s[lo..=hi]);
+        showln(s[lo..]);
+        showln(s[..hi]);
+      }
+    "#,
+    |externs| {
+      assert!(
+        externs.iter().any(|s| s == "_zo_str_slice"),
+        "expected `_zo_str_slice` in extern_used, got {externs:?}"
+      );
+    },
+  );
+}
+
+#[test]
+fn str_equality_compares_through_str_eq() {
+  compile_and_inspect(
+    r#"
+      fun main() {
+        imu s: str = "hello";
+        mut hi: int = 2;
+
+        if s[0..hi] == "he" {
+          showln("yes");
+        }
+      }
+    "#,
+    |externs| {
+      assert!(
+        externs.iter().any(|s| s == "_zo_str_eq"),
+        "expected `_zo_str_eq` in extern_used, got {externs:?}"
+      );
+      assert!(
+        !externs.iter().any(|s| s == "_memcmp"),
+        "inline `_memcmp` reads a view's header as bytes, got {externs:?}"
+      );
+    },
+  );
+}
+
+/// The `libzo_runtime.dylib` cargo built next to this test
+/// binary.
+fn runtime_dylib() -> PathBuf {
+  let exe = std::env::current_exe().unwrap();
+
+  exe
+    .ancestors()
+    .skip(1)
+    .take(2)
+    .map(|dir| dir.join("libzo_runtime.dylib"))
+    .find(|path| path.is_file())
+    .expect("build zo-runtime first: `cargo build -p zo-runtime`")
+}
+
+/// Compiles `source` to a Mach-O binary, stages the runtime
+/// it loads in a sibling `deps/` and runs it.
+fn compile_and_run(source: &str) -> Output {
+  let mut interner = Interner::new();
+  let tokenization = Tokenizer::new(source, &mut interner).tokenize();
+  let parsing = Parser::new(&tokenization, source).parse();
+  let mut ty_checker = TyChecker::new();
+
+  let sir = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty_checker,
+  )
+  .execute()
+  .sir;
+
+  let mut codegen = ARM64Gen::new(&interner);
+  let artifact = codegen.generate(&sir);
+  let output = zo_linker::link_macho(
+    codegen.into_link_object(artifact),
+    Target::Arm64AppleDarwin,
+  );
+
+  let dir = tempfile::tempdir().unwrap();
+  let binary = dir.path().join("str_slicing");
+
+  zo_linker::write_executable(&output.executable, &binary).unwrap();
+
+  if !matches!(output.runtime, RuntimeKind::None) {
+    let deps = dir.path().join("deps");
+
+    std::fs::create_dir_all(&deps).unwrap();
+    std::fs::copy(runtime_dylib(), deps.join("libzo_runtime.dylib")).unwrap();
+  }
+
+  Command::new(&binary).output().expect("run failed")
+}
+
+fn stdout(source: &str) -> String {
+  let output = compile_and_run(source);
+
+  assert!(output.status.success(), "{output:?}");
+
+  String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "macos", target_arch = "aarch64")),
+  ignore = "requires macOS ARM64"
+)]
+fn inclusive_range_takes_the_upper_bound() {
+  let out = stdout(
+    r#"
+      fun main() {
+        imu s: str = "hello, world!";
+        mut lo: int = 7;
+        mut hi: int = 11;
+
+        showln(s[lo..=hi]);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "world\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "macos", target_arch = "aarch64")),
+  ignore = "requires macOS ARM64"
+)]
+fn open_ranges_run_to_either_end() {
+  let out = stdout(
+    r#"
+      fun main() {
+        imu s: str = "hello, world!";
+        mut lo: int = 7;
+        mut hi: int = 5;
+
+        showln(s[lo..]);
+        showln(s[..hi]);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "world!\nhello\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "macos", target_arch = "aarch64")),
+  ignore = "requires macOS ARM64"
+)]
+fn view_of_a_view_slices_the_view() {
+  let out = stdout(
+    r#"
+      fun main() {
+        imu s: str = "hello, world!";
+        mut lo: int = 7;
+        mut one: int = 1;
+        mut hi: int = 4;
+
+        imu tail: str = s[lo..];
+        imu inner: str = tail[one..hi];
+
+        showln(inner);
+        showln(tail[one..]);
+        check@eq(inner.len, 3);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "orl\norld!\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "macos", target_arch = "aarch64")),
+  ignore = "requires macOS ARM64"
+)]
+fn bound_inside_a_char_stops_the_program() {
+  let output = compile_and_run(
+    r#"
+      fun main() {
+        imu greek: str = "αβγ";
+        mut one: int = 1;
+
+        showln(greek[one..]);
+      }
+    "#,
+  );
+
+  assert!(!output.status.success());
+  assert!(output.stdout.is_empty());
+  assert!(
+    String::from_utf8_lossy(&output.stderr)
+      .contains("byte index 1 is not a char boundary"),
+    "{output:?}"
+  );
+}
@@ -31,14 +31,15 @@ fn test_universal_binary_creation() {
     2
   );
 
-  // Write to file
-  let path = "tests/hello_universal";
-  zo_linker::write_executable(&universal, std::path::Path::new(path))
+  // Write to a scratch dir, removed even when an assert fails.
+  let dir = tempfile::tempdir().expect("Failed to create temp dir");
+  let path = dir.path().join("hello_universal");
+  zo_linker::write_executable(&universal, &path)
     .expect("Failed to write universal binary");
 
   // Use 'file' command to verify it's recognized as a universal binary
   let output = Command::new("file")
-    .arg(path)
+    .arg(&path)
     .output()
     .expect("Failed to run 'file' command");
 
@@ -49,9 +50,6 @@ fn test_universal_binary_creation() {
     "File command didn't recognize universal binary: {}",
     file_output
   );
-
-  // Clean up
-  std::fs::remove_file(path).ok();
 }
 
 #[test]
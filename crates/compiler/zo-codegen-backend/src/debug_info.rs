@@ -0,0 +1,104 @@
+//! Debug info handed to a backend for a debug build.
+//!
+//! A debug build carries two things past codegen: a DWARF
+//! line table and variable locations a native debugger reads,
+//! and a zo type table — [`DebugTypes`] — that `zo-dap` reads
+//! to show a `str`, an array, an enum or a `HashMap` in its zo
+//! shape rather than as the pointer the debugger sees.
+
+use zo_interner::Symbol;
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Name of the object section holding the serialized
+/// [`DebugTypes`]. A non-allocated section — the loader never
+/// maps it, the linker copies it through.
+pub const DEBUG_TYPES_SECTION: &str = ".zo_debug_types";
+
+/// What a backend needs to emit debug info for one program.
+pub struct DebugInfo {
+  /// Every source file the program was compiled from, with
+  /// its text. Index 0 is the entry file.
+  pub files: Vec<(PathBuf, String)>,
+  /// Pack → index into `files` of the file declaring it. A
+  /// function whose `owning_pack` is absent belongs to the
+  /// entry file.
+  pub pack_files: FxHashMap<Symbol, usize>,
+  /// Source spelling of every `TyId`, by index — `[]int`,
+  /// `(int, str)`, `Point`.
+  pub ty_labels: Vec<String>,
+}
+
+/// The zo types a program's variables have, keyed by the name
+/// their DWARF type carries.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DebugTypes {
+  pub types: BTreeMap<String, DebugTy>,
+}
+
+/// The shape of one zo type, as laid out in memory by the
+/// debug backend. Aggregates are pointers to 8-byte slots:
+/// a `str` to `[len, bytes…]`, an array to `[len, elem…]`, a
+/// tuple or struct to `[field…]`, an enum to `[tag, field…]`.
+/// Types referenced inside are named by their key in
+/// [`DebugTypes::types`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DebugTy {
+  Unit,
+  Bool,
+  Char,
+  /// `size` is the stored width in bytes.
+  Int {
+    signed: bool,
+    size: u8,
+  },
+  Float {
+    size: u8,
+  },
+  Str,
+  Array {
+    elem: String,
+  },
+  Tuple {
+    elems: Vec<String>,
+  },
+  Struct {
+    fields: Vec<(String, String)>,
+  },
+  Enum {
+    variants: Vec<DebugVariant>,
+  },
+  /// A `HashMap`: the runtime map pointer in field 0, with the
+  /// runtime's `MapFmt` discriminant for each side.
+  Map {
+    key_fmt: u32,
+    val_fmt: u32,
+  },
+  /// Anything else — a function, a channel, a task.
+  Opaque,
+}
+
+/// One enum variant: its name, its tag and its payload types.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DebugVariant {
+  pub name: String,
+  pub tag: u64,
+  pub fields: Vec<String>,
+}
+
+impl DebugTypes {
+  /// Serializes the table into the section payload.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    serde_json::to_vec(self).unwrap_or_default()
+  }
+
+  /// Reads a table back from a section payload.
+  pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    serde_json::from_slice(bytes).ok()
+  }
+}
@@ -1,5 +1,6 @@
 mod artifact;
 mod backend;
+mod debug_info;
 mod link_object;
 mod platform;
 mod target;
@@ -7,6 +8,9 @@ mod webviewing;
 
 pub use artifact::Artifact;
 pub use backend::Backend;
+pub use debug_info::{
+  DEBUG_TYPES_SECTION, DebugInfo, DebugTy, DebugTypes, DebugVariant,
+};
 pub use link_object::{LinkObject, MachoLinkObject, WebBundle};
 pub use platform::Platform;
 pub use target::Target;
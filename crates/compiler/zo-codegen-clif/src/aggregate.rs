@@ -0,0 +1,222 @@
+//! Aggregate returns.
+//!
+//! A struct, tuple or enum value is the address of a stack slot
+//! in the frame that built it, so a function can't hand one
+//! back by address — the slot dies with the callee's frame and
+//! the caller's next call overwrites it. Every function whose
+//! return type is an aggregate therefore takes a hidden leading
+//! pointer to a slot in the caller's frame (the `sret` slot),
+//! copies its return value there and returns that address.
+//!
+//! The copy is deep: a struct field or enum payload that is
+//! itself an aggregate points into the callee's frame too, so
+//! it is copied into the same `sret` slot, after the words of
+//! its parent, and the parent's field is pointed at the copy.
+//! Tuple elements are copied as plain words — SIR carries no
+//! element types for a tuple.
+
+use crate::context::AGG_SLOT_SIZE;
+
+use zo_sir::Insn;
+use zo_ty::TyId;
+
+use cranelift::codegen::ir;
+use cranelift::codegen::ir::condcodes::IntCC;
+use cranelift::codegen::ir::{InstBuilder, MemFlags};
+use cranelift::frontend::FunctionBuilder;
+use rustc_hash::FxHashMap as HashMap;
+
+/// Nesting depth past which an aggregate field is copied as a
+/// plain word. Bounds the copy of a type that reaches itself.
+const MAX_DEPTH: u32 = 8;
+
+/// The word layout of an aggregate type.
+pub(crate) enum AggShape {
+  /// Struct fields, one word each.
+  Fields(Vec<TyId>),
+  /// Tuple elements, one word each.
+  Words(u32),
+  /// Enum variants as `(discriminant, payload types)`, behind
+  /// a tag word.
+  Variants(Vec<(u32, Vec<TyId>)>),
+}
+
+/// Collects the shape of every struct, enum and tuple type of
+/// the module, keyed by its `TyId`.
+pub(crate) fn collect_agg_shapes(insns: &[Insn]) -> HashMap<TyId, AggShape> {
+  let mut shapes = HashMap::default();
+
+  for insn in insns {
+    match insn {
+      Insn::StructDef { ty_id, fields, .. } => {
+        let tys = fields.iter().map(|(_, ty, _)| *ty).collect();
+
+        shapes.insert(*ty_id, AggShape::Fields(tys));
+      }
+      Insn::EnumDef {
+        ty_id, variants, ..
+      } => {
+        let variants = variants
+          .iter()
+          .map(|(_, disc, tys)| (*disc, tys.clone()))
+          .collect();
+
+        shapes.insert(*ty_id, AggShape::Variants(variants));
+      }
+      Insn::TupleLiteral {
+        elements, ty_id, ..
+      } => {
+        shapes
+          .entry(*ty_id)
+          .or_insert(AggShape::Words(elements.len() as u32));
+      }
+      _ => {}
+    }
+  }
+
+  shapes
+}
+
+/// Size in bytes of the `sret` slot for `ty`, nested copies
+/// included. At least one word, so an empty struct still gets
+/// an address.
+pub(crate) fn sret_size(shapes: &HashMap<TyId, AggShape>, ty: TyId) -> u32 {
+  flat_size(shapes, ty, 0).max(AGG_SLOT_SIZE)
+}
+
+/// Bytes a deep copy of `ty` takes, or 0 for a scalar.
+fn flat_size(shapes: &HashMap<TyId, AggShape>, ty: TyId, depth: u32) -> u32 {
+  let Some(shape) = shapes.get(&ty).filter(|_| depth < MAX_DEPTH) else {
+    return 0;
+  };
+
+  match shape {
+    AggShape::Fields(tys) => fields_size(shapes, tys, 0, depth),
+    AggShape::Words(n) => n * AGG_SLOT_SIZE,
+    AggShape::Variants(variants) => variants
+      .iter()
+      .map(|(_, tys)| fields_size(shapes, tys, 1, depth))
+      .max()
+      .unwrap_or(AGG_SLOT_SIZE),
+  }
+}
+
+/// Bytes of `tys` laid out from word `first`, nested copies
+/// included.
+fn fields_size(
+  shapes: &HashMap<TyId, AggShape>,
+  tys: &[TyId],
+  first: u32,
+  depth: u32,
+) -> u32 {
+  let nested = tys
+    .iter()
+    .map(|ty| flat_size(shapes, *ty, depth + 1))
+    .sum::<u32>();
+
+  (first + tys.len() as u32) * AGG_SLOT_SIZE + nested
+}
+
+/// Deep-copies the `ty` aggregate at `src` into the `sret`
+/// slot at `dst`.
+pub(crate) fn emit_sret_copy(
+  builder: &mut FunctionBuilder,
+  shapes: &HashMap<TyId, AggShape>,
+  ptr_ty: ir::Type,
+  ty: TyId,
+  src: ir::Value,
+  dst: ir::Value,
+) {
+  emit_copy(builder, shapes, ptr_ty, ty, src, dst, 0);
+}
+
+fn emit_copy(
+  builder: &mut FunctionBuilder,
+  shapes: &HashMap<TyId, AggShape>,
+  ptr_ty: ir::Type,
+  ty: TyId,
+  src: ir::Value,
+  dst: ir::Value,
+  depth: u32,
+) {
+  let Some(shape) = shapes.get(&ty) else {
+    return;
+  };
+
+  match shape {
+    AggShape::Fields(tys) => {
+      emit_copy_fields(builder, shapes, ptr_ty, tys, 0, src, dst, depth);
+    }
+    AggShape::Words(n) => {
+      for i in 0..*n {
+        let offset = (i * AGG_SLOT_SIZE) as i32;
+        let word = builder.ins().load(ptr_ty, MemFlags::new(), src, offset);
+
+        builder.ins().store(MemFlags::new(), word, dst, offset);
+      }
+    }
+    AggShape::Variants(variants) => {
+      // Each variant has its own payload, so the copy branches
+      // on the tag: reading the words of a larger variant
+      // would run past the end of the source slot.
+      let tag = builder.ins().load(ptr_ty, MemFlags::new(), src, 0);
+
+      builder.ins().store(MemFlags::new(), tag, dst, 0);
+
+      let done = builder.create_block();
+
+      for (disc, tys) in variants {
+        let this = builder.create_block();
+        let next = builder.create_block();
+        let is_this = builder.ins().icmp_imm(IntCC::Equal, tag, *disc as i64);
+
+        builder.ins().brif(is_this, this, &[], next, &[]);
+        builder.switch_to_block(this);
+
+        emit_copy_fields(builder, shapes, ptr_ty, tys, 1, src, dst, depth);
+
+        builder.ins().jump(done, &[]);
+        builder.switch_to_block(next);
+      }
+
+      builder.ins().jump(done, &[]);
+      builder.switch_to_block(done);
+    }
+  }
+}
+
+/// Copies the words of `tys` from word `first` on, placing the
+/// copy of each aggregate field after them.
+#[allow(clippy::too_many_arguments)]
+fn emit_copy_fields(
+  builder: &mut FunctionBuilder,
+  shapes: &HashMap<TyId, AggShape>,
+  ptr_ty: ir::Type,
+  tys: &[TyId],
+  first: u32,
+  src: ir::Value,
+  dst: ir::Value,
+  depth: u32,
+) {
+  let mut next_free = (first + tys.len() as u32) * AGG_SLOT_SIZE;
+
+  for (i, ty) in tys.iter().enumerate() {
+    let offset = ((first + i as u32) * AGG_SLOT_SIZE) as i32;
+    let word = builder.ins().load(ptr_ty, MemFlags::new(), src, offset);
+    let size = flat_size(shapes, *ty, depth + 1);
+
+    if size == 0 {
+      builder.ins().store(MemFlags::new(), word, dst, offset);
+
+      continue;
+    }
+
+    let nested = builder.ins().iadd_imm(dst, next_free as i64);
+
+    emit_copy(builder, shapes, ptr_ty, *ty, word, nested, depth + 1);
+
+    builder.ins().store(MemFlags::new(), nested, dst, offset);
+
+    next_free += size;
+  }
+}
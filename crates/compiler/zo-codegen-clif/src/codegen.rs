@@ -4,11 +4,13 @@
 //! and the per-function state (FuncId map, block map, value
 //! map, stack-slot map). One `CliftGen` per build invocation.
 
+use crate::debug::DebugCtx;
 use crate::translate;
 
-use zo_codegen_backend::{Artifact, Backend, Target};
+use zo_codegen_backend::{Artifact, Backend, DebugInfo, Target};
 use zo_interner::Interner;
 use zo_sir::Sir;
+use zo_ty::{Ty, TyTable};
 
 use cranelift::codegen::settings::{self, Configurable};
 use cranelift_object::{ObjectBuilder, ObjectModule};
@@ -19,12 +21,31 @@ pub struct CliftGen<'a> {
   target: Target,
   #[allow(dead_code)]
   interner: &'a Interner,
+  /// Set for a debug build — see [`CliftGen::with_debug_info`].
+  debug: Option<(&'a DebugInfo, &'a [Ty], &'a TyTable)>,
 }
 
 impl<'a> CliftGen<'a> {
   /// Creates a new [`CliftGen`] for the given target.
   pub const fn new(interner: &'a Interner, target: Target) -> Self {
-    Self { target, interner }
+    Self {
+      target,
+      interner,
+      debug: None,
+    }
+  }
+
+  /// Emits DWARF and the zo type table alongside the code.
+  /// The type view names each local's type and lays out its
+  /// shape.
+  pub const fn with_debug_info(
+    mut self,
+    info: &'a DebugInfo,
+    tys: &'a [Ty],
+    ty_table: &'a TyTable,
+  ) -> Self {
+    self.debug = Some((info, tys, ty_table));
+    self
   }
 
   /// Maps a [`Target`] to a `target_lexicon::Triple`. Only the
@@ -95,6 +116,7 @@ impl<'a> CliftGen<'a> {
       self.interner,
       &sir.instructions,
       &sir.int_bases,
+      None,
     )
   }
 }
@@ -105,6 +127,15 @@ impl<'a> Backend for CliftGen<'a> {
   /// resulting relocatable object bytes.
   fn generate(&mut self, sir: &Sir) -> Artifact {
     let mut module = Self::new_module(self.target);
+    let mut debug = self.debug.map(|(info, tys, ty_table)| {
+      DebugCtx::new(
+        info,
+        self.interner,
+        (tys, ty_table),
+        &sir.instructions,
+        &sir.spans,
+      )
+    });
 
     // `translate_module` returns CLIF IR text for the
     // `--emit asm` path; the binary path doesn't need it.
@@ -113,9 +144,15 @@ impl<'a> Backend for CliftGen<'a> {
       self.interner,
       &sir.instructions,
       &sir.int_bases,
+      debug.as_mut(),
     );
 
-    let product = module.finish();
+    let mut product = module.finish();
+
+    if let Some(debug) = debug {
+      debug.emit(&mut product);
+    }
+
     let code = product.emit().expect("object emit failed");
 
     Artifact { code }
@@ -8,6 +8,9 @@
 //! too since every consumer — translator, intrinsics, runtime
 //! emitters — reads them.
 
+use crate::aggregate::AggShape;
+use crate::debug::FunDebug;
+
 use zo_interner::{Interner, Symbol};
 use zo_token::Base;
 use zo_ty::TyId;
@@ -101,6 +104,15 @@ pub(crate) struct TCtx<'a> {
   /// entry; absence means `Base::Decimal`. Drives `emit_int_show`
   /// so `showln(x#76)` prints `4c`.
   pub(crate) int_bases: &'a std::collections::HashMap<u32, Base>,
+  /// Map type (`TyId.0`) → `(key_fmt, val_fmt)` from its
+  /// `Insn::MapTyDef`. Lets `emit_io_intrinsic` show a map by
+  /// its entries.
+  pub(crate) map_fmts: &'a HashMap<u32, (u32, u32)>,
+  /// Struct, tuple and enum layouts by `TyId` — sizes the
+  /// `sret` slot of an aggregate-returning call.
+  pub(crate) agg_shapes: &'a HashMap<TyId, AggShape>,
+  /// Functions taking an `sret` slot, with their return type.
+  pub(crate) sret_funcs: &'a HashMap<FuncId, TyId>,
 }
 
 /// Per-function translation state. A fresh [`FunCtx`] is built
@@ -141,6 +153,13 @@ pub(crate) struct FunCtx {
   /// argument type (int vs bool vs str vs …). Mirrors
   /// `zo-codegen-arm`'s `value_types` pattern.
   pub(crate) value_types: HashMap<ValueId, TyId>,
+  /// Names and value labels of the locals, when building
+  /// debug info.
+  pub(crate) debug: Option<FunDebug>,
+  /// The hidden `sret` parameter and the return type, when
+  /// the function returns an aggregate. `Return` copies its
+  /// value there.
+  pub(crate) sret: Option<(ir::Value, TyId)>,
 }
 
 impl FunCtx {
@@ -153,6 +172,8 @@ impl FunCtx {
       terminated: false,
       is_main,
       value_types: HashMap::default(),
+      debug: None,
+      sret: None,
     }
   }
 
@@ -173,4 +194,33 @@ impl FunCtx {
 
     var
   }
+
+  /// Names the local `name` — declared just before — for the
+  /// debugger, with its zo type.
+  pub(crate) fn declare_debug(
+    &mut self,
+    interner: &Interner,
+    name: Symbol,
+    ty_id: TyId,
+    param: bool,
+  ) {
+    if let (Some(debug), Some(var)) = (&mut self.debug, self.vars.get(&name)) {
+      debug.declare(interner, *var, name, ty_id, param);
+    }
+  }
+
+  /// `def_var`, tagging `value` as held by `var` for the
+  /// debugger.
+  pub(crate) fn def_local(
+    &self,
+    builder: &mut FunctionBuilder,
+    var: Variable,
+    value: ir::Value,
+  ) {
+    builder.def_var(var, value);
+
+    if let Some(debug) = &self.debug {
+      debug.label(builder, var, value);
+    }
+  }
 }
@@ -0,0 +1,849 @@
+//! DWARF for debug builds.
+//!
+//! With [`DebugInfo`] set on the [`CliftGen`](crate::CliftGen),
+//! translation tags every CLIF instruction with the index of
+//! the SIR instruction it came from and every value a named
+//! local holds with that local's [`ValueLabel`]. After each
+//! function compiles, [`DebugCtx::finish_function`] reads the
+//! machine-code ranges back — code offset → SIR index → span
+//! → line, label → register or frame slot — and
+//! [`DebugCtx::emit`] writes them as one DWARF compile unit:
+//!
+//! - `.debug_line` — one sequence per function.
+//! - `.debug_info` — a subprogram per function, a variable or
+//!   formal parameter per local, located by a location list.
+//! - `.debug_frame` — the CFI the CFA-relative locations and
+//!   the unwinder need.
+//!
+//! Variables are typed by their zo spelling; the shape behind
+//! each spelling goes to the `.zo_debug_types` section as a
+//! [`DebugTypes`] table for `zo-dap`. Only ELF objects carry
+//! debug info for now — a Mach-O build links against a temp
+//! object `dsymutil` never sees.
+
+use zo_codegen_backend::{
+  DEBUG_TYPES_SECTION, DebugInfo, DebugTy, DebugTypes, DebugVariant,
+};
+use zo_interner::{Interner, Symbol};
+use zo_sir::Insn;
+use zo_span::Span;
+use zo_ty::{FloatWidth, IntWidth, Ty, TyId, TyTable, type_name};
+
+use cranelift::codegen::Context;
+use cranelift::codegen::LabelValueLoc;
+use cranelift::codegen::entity::EntityRef;
+use cranelift::codegen::gimli::write::{
+  Address, AttributeValue, CommonInformationEntry, DwarfUnit, EndianVec,
+  Expression, FileId, FrameTable, LineProgram, LineString, Location,
+  LocationList, Range, RangeList, RelocateWriter, Relocation, RelocationTarget,
+  Sections, UnitEntryId,
+};
+use cranelift::codegen::gimli::{
+  self, Encoding, Format, LineEncoding, Register, RunTimeEndian, SectionId,
+};
+use cranelift::codegen::ir::{SourceLoc, Value, ValueLabel};
+use cranelift::codegen::isa::TargetIsa;
+use cranelift::codegen::isa::unwind::UnwindInfo;
+use cranelift::frontend::{FunctionBuilder, Variable};
+use cranelift_module::FuncId;
+use cranelift_object::ObjectProduct;
+use cranelift_object::object::write::{self, SymbolId};
+use cranelift_object::object::{
+  BinaryFormat, RelocationEncoding, RelocationFlags, RelocationKind,
+  SectionKind,
+};
+use rustc_hash::FxHashMap as HashMap;
+
+use std::path::Path;
+
+/// Spelling of each `MapFmt` discriminant, by value.
+const MAP_FMT_NAMES: [&str; 5] = ["int", "bool", "char", "str", "float"];
+
+/// The names and labels of one function's locals, built up
+/// while its body translates.
+pub(crate) struct FunDebug {
+  /// SIR index of the body's first instruction — translation
+  /// walks a body slice, `Sir::spans` is indexed globally.
+  pub(crate) base: usize,
+  locals: Vec<Local>,
+  /// `Variable` index → label.
+  labels: HashMap<usize, ValueLabel>,
+}
+
+/// A named local — a parameter or a `VarDef`.
+struct Local {
+  name: Symbol,
+  ty_id: TyId,
+  param: bool,
+}
+
+impl FunDebug {
+  pub(crate) fn new(base: usize) -> Self {
+    Self {
+      base,
+      locals: Vec::new(),
+      labels: HashMap::default(),
+    }
+  }
+
+  /// Records `var` as the local `name`. Executor temporaries
+  /// (`__branch_result_0__`, …) stay out of the debugger's
+  /// view.
+  pub(crate) fn declare(
+    &mut self,
+    interner: &Interner,
+    var: Variable,
+    name: Symbol,
+    ty_id: TyId,
+    param: bool,
+  ) {
+    if interner.get(name).starts_with("__") {
+      return;
+    }
+
+    let label = ValueLabel::from_u32(self.locals.len() as u32);
+
+    self.locals.push(Local { name, ty_id, param });
+    self.labels.insert(var.index(), label);
+  }
+
+  /// Tags `value` as held by `var`, if `var` is a named local.
+  pub(crate) fn label(
+    &self,
+    builder: &mut FunctionBuilder,
+    var: Variable,
+    value: Value,
+  ) {
+    if let Some(label) = self.labels.get(&var.index()) {
+      builder.set_val_label(value, *label);
+    }
+  }
+
+  /// Points the instructions that follow at SIR instruction
+  /// `idx` of the body.
+  pub(crate) fn set_srcloc(&self, builder: &mut FunctionBuilder, idx: usize) {
+    builder.set_srcloc(SourceLoc::new((self.base + idx) as u32));
+  }
+}
+
+/// One compiled function, ready for DWARF.
+struct FunctionDebug {
+  func_id: FuncId,
+  name: String,
+  file: usize,
+  line: u64,
+  size: u32,
+  /// `(code offset, line, column)`, by offset.
+  rows: Vec<(u32, u64, u64)>,
+  vars: Vec<VarDebug>,
+  unwind: Option<UnwindInfo>,
+}
+
+struct VarDebug {
+  name: String,
+  ty_id: TyId,
+  param: bool,
+  /// `(start, end, location)` code ranges.
+  ranges: Vec<(u32, u32, Expression)>,
+}
+
+/// Module-wide debug state for one `CliftGen` run.
+pub(crate) struct DebugCtx<'a> {
+  info: &'a DebugInfo,
+  interner: &'a Interner,
+  tys: &'a [Ty],
+  ty_table: &'a TyTable,
+  spans: &'a [Span],
+  /// Byte offset of each line start, per file.
+  line_starts: Vec<Vec<u32>>,
+  /// `HashMap` TyId → `(key_fmt, val_fmt)`, from `MapTyDef`.
+  map_fmts: HashMap<TyId, (u32, u32)>,
+  functions: Vec<FunctionDebug>,
+  /// The ISA's CIE, taken while the module is still alive —
+  /// `emit` runs after `finish` consumed it.
+  cie: Option<CommonInformationEntry>,
+}
+
+impl<'a> DebugCtx<'a> {
+  pub(crate) fn new(
+    info: &'a DebugInfo,
+    interner: &'a Interner,
+    (tys, ty_table): (&'a [Ty], &'a TyTable),
+    insns: &[Insn],
+    spans: &'a [Span],
+  ) -> Self {
+    let line_starts = info
+      .files
+      .iter()
+      .map(|(_, source)| {
+        std::iter::once(0)
+          .chain(
+            source
+              .bytes()
+              .enumerate()
+              .filter(|(_, b)| *b == b'\n')
+              .map(|(i, _)| i as u32 + 1),
+          )
+          .collect()
+      })
+      .collect();
+
+    let map_fmts = insns
+      .iter()
+      .filter_map(|insn| match insn {
+        Insn::MapTyDef {
+          map_ty,
+          key_fmt,
+          val_fmt,
+        } => Some((*map_ty, (*key_fmt, *val_fmt))),
+        _ => None,
+      })
+      .collect();
+
+    Self {
+      info,
+      interner,
+      tys,
+      ty_table,
+      spans,
+      line_starts,
+      map_fmts,
+      functions: Vec::new(),
+      cie: None,
+    }
+  }
+
+  /// Index into `files` of the file declaring a function of
+  /// `owning_pack`.
+  pub(crate) fn file_of(&self, owning_pack: Option<Symbol>) -> usize {
+    owning_pack
+      .and_then(|pack| self.info.pack_files.get(&pack).copied())
+      .unwrap_or(0)
+  }
+
+  /// 1-based `(line, column)` of SIR instruction `idx` in
+  /// `file`, unless its span falls outside the file.
+  fn line_col(&self, file: usize, idx: usize) -> Option<(u64, u64)> {
+    let span = self.spans.get(idx)?;
+    let starts = self.line_starts.get(file)?;
+    let len = self.info.files[file].1.len() as u32;
+
+    if *span == Span::ZERO || span.start >= len {
+      return None;
+    }
+
+    let line = starts.partition_point(|start| *start <= span.start);
+    let column = span.start - starts[line - 1];
+
+    Some((line as u64, column as u64 + 1))
+  }
+
+  /// Reads the compiled code in `ctx` back into line rows and
+  /// variable ranges. `fundef` is the SIR index of the
+  /// function's `FunDef`.
+  #[allow(clippy::too_many_arguments)]
+  pub(crate) fn finish_function(
+    &mut self,
+    isa: &dyn TargetIsa,
+    func_id: FuncId,
+    name: Symbol,
+    fundef: usize,
+    owning_pack: Option<Symbol>,
+    ctx: &Context,
+    fun_debug: FunDebug,
+  ) {
+    let Some(code) = ctx.compiled_code() else {
+      return;
+    };
+
+    if self.cie.is_none() {
+      self.cie = isa.create_systemv_cie();
+    }
+
+    let file = self.file_of(owning_pack);
+    let line = self.line_col(file, fundef).map_or(0, |(line, _)| line);
+
+    let rows = code
+      .buffer
+      .get_srclocs_sorted()
+      .iter()
+      .filter(|srcloc| !srcloc.loc.is_default())
+      .filter_map(|srcloc| {
+        let (line, column) = self.line_col(file, srcloc.loc.bits() as usize)?;
+
+        Some((srcloc.start, line, column))
+      })
+      .collect();
+
+    let vars = fun_debug
+      .locals
+      .iter()
+      .enumerate()
+      .map(|(idx, local)| {
+        let label = ValueLabel::from_u32(idx as u32);
+        let ranges = code
+          .value_labels_ranges
+          .get(&label)
+          .into_iter()
+          .flatten()
+          .filter_map(|range| {
+            let expr = location_expr(isa, range.loc)?;
+
+            Some((range.start, range.end, expr))
+          })
+          .collect();
+
+        VarDebug {
+          name: self.interner.get(local.name).to_string(),
+          ty_id: local.ty_id,
+          param: local.param,
+          ranges,
+        }
+      })
+      .collect();
+
+    self.functions.push(FunctionDebug {
+      func_id,
+      name: self.interner.get(name).to_string(),
+      file,
+      line,
+      size: code.code_buffer().len() as u32,
+      rows,
+      vars,
+      unwind: code.create_unwind_info(isa).ok().flatten(),
+    });
+  }
+
+  /// Writes the DWARF sections and the zo type table into the
+  /// finished object. A no-op for non-ELF objects.
+  pub(crate) fn emit(self, product: &mut ObjectProduct) {
+    if product.object.format() != BinaryFormat::Elf || self.functions.is_empty()
+    {
+      return;
+    }
+
+    let encoding = Encoding {
+      format: Format::Dwarf32,
+      version: 4,
+      address_size: 8,
+    };
+
+    let mut dwarf = DwarfUnit::new(encoding);
+    let mut types = TypeTable::default();
+    let (entry, _) = &self.info.files[0];
+
+    dwarf.unit.line_program = LineProgram::new(
+      encoding,
+      LineEncoding::default(),
+      LineString::String(dir_bytes(entry)),
+      None,
+      LineString::String(file_bytes(entry)),
+      None,
+    );
+
+    let files = self
+      .info
+      .files
+      .iter()
+      .map(|(path, _)| {
+        let program = &mut dwarf.unit.line_program;
+        let dir = program.add_directory(LineString::String(dir_bytes(path)));
+
+        program.add_file(LineString::String(file_bytes(path)), dir, None)
+      })
+      .collect::<Vec<FileId>>();
+
+    let root = dwarf.unit.root();
+    let ranges = RangeList(
+      self
+        .functions
+        .iter()
+        .enumerate()
+        .map(|(idx, function)| Range::StartLength {
+          begin: symbol(idx, 0),
+          length: u64::from(function.size),
+        })
+        .collect(),
+    );
+    let ranges = dwarf.unit.ranges.add(ranges);
+    let cu = dwarf.unit.get_mut(root);
+
+    cu.set(
+      gimli::DW_AT_producer,
+      AttributeValue::String(b"zo".to_vec()),
+    );
+    cu.set(
+      gimli::DW_AT_name,
+      AttributeValue::String(entry.to_string_lossy().as_bytes().to_vec()),
+    );
+    cu.set(
+      gimli::DW_AT_comp_dir,
+      AttributeValue::String(dir_bytes(entry)),
+    );
+    cu.set(
+      gimli::DW_AT_low_pc,
+      AttributeValue::Address(Address::Constant(0)),
+    );
+    cu.set(gimli::DW_AT_ranges, AttributeValue::RangeListRef(ranges));
+
+    for (idx, function) in self.functions.iter().enumerate() {
+      let file = files[function.file];
+      let program = &mut dwarf.unit.line_program;
+
+      program.begin_sequence(Some(symbol(idx, 0)));
+
+      let mut last = None;
+
+      for (offset, line, column) in std::iter::once((0, function.line, 0))
+        .chain(function.rows.iter().copied())
+        .filter(|(_, line, _)| *line != 0)
+      {
+        if last == Some((line, column)) {
+          continue;
+        }
+
+        last = Some((line, column));
+
+        let row = program.row();
+
+        row.address_offset = u64::from(offset);
+        row.file = file;
+        row.line = line;
+        row.column = column;
+
+        program.generate_row();
+      }
+
+      program.end_sequence(u64::from(function.size));
+
+      let vars = function
+        .vars
+        .iter()
+        .filter_map(|var| {
+          let ty = types.die(&self, &mut dwarf, var.ty_id)?;
+
+          Some((var, ty))
+        })
+        .collect::<Vec<_>>();
+
+      let sub = dwarf.unit.add(root, gimli::DW_TAG_subprogram);
+      let entry = dwarf.unit.get_mut(sub);
+
+      entry.set(
+        gimli::DW_AT_name,
+        AttributeValue::String(function.name.as_bytes().to_vec()),
+      );
+      entry.set(
+        gimli::DW_AT_decl_file,
+        AttributeValue::FileIndex(Some(file)),
+      );
+      entry.set(gimli::DW_AT_decl_line, AttributeValue::Udata(function.line));
+      entry.set(gimli::DW_AT_low_pc, AttributeValue::Address(symbol(idx, 0)));
+      entry.set(
+        gimli::DW_AT_high_pc,
+        AttributeValue::Udata(u64::from(function.size)),
+      );
+
+      let mut frame_base = Expression::new();
+
+      frame_base.op(gimli::DW_OP_call_frame_cfa);
+      entry.set(gimli::DW_AT_frame_base, AttributeValue::Exprloc(frame_base));
+
+      for (var, ty) in vars {
+        let tag = if var.param {
+          gimli::DW_TAG_formal_parameter
+        } else {
+          gimli::DW_TAG_variable
+        };
+
+        let locations = LocationList(
+          var
+            .ranges
+            .iter()
+            .map(|(start, end, expr)| Location::StartLength {
+              begin: symbol(idx, i64::from(*start)),
+              length: u64::from(end - start),
+              data: expr.clone(),
+            })
+            .collect(),
+        );
+        let locations = (!locations.0.is_empty())
+          .then(|| dwarf.unit.locations.add(locations));
+
+        let die = dwarf.unit.add(sub, tag);
+        let entry = dwarf.unit.get_mut(die);
+
+        entry.set(
+          gimli::DW_AT_name,
+          AttributeValue::String(var.name.as_bytes().to_vec()),
+        );
+        entry.set(gimli::DW_AT_type, AttributeValue::UnitRef(ty));
+
+        if let Some(locations) = locations {
+          entry.set(
+            gimli::DW_AT_location,
+            AttributeValue::LocationListRef(locations),
+          );
+        }
+      }
+    }
+
+    let mut frames = FrameTable::default();
+
+    if let Some(cie) = self.cie.clone() {
+      let cie = frames.add_cie(cie);
+
+      for (idx, function) in self.functions.iter().enumerate() {
+        if let Some(UnwindInfo::SystemV(unwind)) = &function.unwind {
+          frames.add_fde(cie, unwind.to_fde(symbol(idx, 0)));
+        }
+      }
+    }
+
+    let mut sections = Sections::new(SectionWriter::default());
+
+    if dwarf.write(&mut sections).is_err()
+      || frames.write_debug_frame(&mut sections.debug_frame).is_err()
+    {
+      return;
+    }
+
+    let symbols = self
+      .functions
+      .iter()
+      .map(|function| product.function_symbol(function.func_id))
+      .collect::<Vec<_>>();
+
+    write_sections(&mut product.object, &sections, &symbols);
+
+    let object = &mut product.object;
+    let segment = object.segment_name(write::StandardSegment::Debug).to_vec();
+    let section = object.add_section(
+      segment,
+      DEBUG_TYPES_SECTION.as_bytes().to_vec(),
+      SectionKind::Debug,
+    );
+
+    object.set_section_data(section, types.table.to_bytes(), 1);
+  }
+}
+
+/// The zo types variables have been given so far — their
+/// DWARF base types and the [`DebugTypes`] entries behind
+/// them.
+#[derive(Default)]
+struct TypeTable {
+  /// TyId → its key in `table`.
+  keys: HashMap<TyId, String>,
+  /// Key → the TyId holding it, so two types spelled alike
+  /// get distinct keys.
+  taken: HashMap<String, TyId>,
+  dies: HashMap<TyId, UnitEntryId>,
+  table: DebugTypes,
+}
+
+impl TypeTable {
+  /// The base type DIE a variable of `ty_id` points at, or
+  /// `None` for `unit` — nothing to show.
+  fn die(
+    &mut self,
+    ctx: &DebugCtx<'_>,
+    dwarf: &mut DwarfUnit,
+    ty_id: TyId,
+  ) -> Option<UnitEntryId> {
+    if let Some(die) = self.dies.get(&ty_id) {
+      return Some(*die);
+    }
+
+    let key = self.key(ctx, ty_id);
+
+    let (encoding, size) = match self.table.types.get(&key)? {
+      DebugTy::Unit => return None,
+      DebugTy::Bool => (gimli::DW_ATE_boolean, 1),
+      DebugTy::Char => (gimli::DW_ATE_UTF, 4),
+      DebugTy::Int { signed: true, size } => (gimli::DW_ATE_signed, *size),
+      DebugTy::Int {
+        signed: false,
+        size,
+      } => (gimli::DW_ATE_unsigned, *size),
+      DebugTy::Float { size } => (gimli::DW_ATE_float, *size),
+      _ => (gimli::DW_ATE_address, 8),
+    };
+
+    let root = dwarf.unit.root();
+    let die = dwarf.unit.add(root, gimli::DW_TAG_base_type);
+    let entry = dwarf.unit.get_mut(die);
+
+    entry.set(gimli::DW_AT_name, AttributeValue::String(key.into_bytes()));
+    entry.set(gimli::DW_AT_encoding, AttributeValue::Encoding(encoding));
+    entry.set(gimli::DW_AT_byte_size, AttributeValue::Data1(size));
+
+    self.dies.insert(ty_id, die);
+
+    Some(die)
+  }
+
+  /// Registers `ty_id` and the types inside it in `table`,
+  /// returning the key it goes by.
+  fn key(&mut self, ctx: &DebugCtx<'_>, ty_id: TyId) -> String {
+    if let Some(key) = self.keys.get(&ty_id) {
+      return key.clone();
+    }
+
+    let ty = ctx.tys.get(ty_id.0 as usize).copied().unwrap_or(Ty::Error);
+    let map = ctx.map_fmts.get(&ty_id).copied();
+
+    let label = match map {
+      Some((key_fmt, val_fmt)) => {
+        format!("HashMap<{}, {}>", fmt_name(key_fmt), fmt_name(val_fmt))
+      }
+      None => ctx
+        .info
+        .ty_labels
+        .get(ty_id.0 as usize)
+        .cloned()
+        .unwrap_or_else(|| type_name(ctx.tys, ty_id)),
+    };
+
+    let mut key = label.clone();
+    let mut n = 1;
+
+    while self.taken.get(&key).is_some_and(|owner| *owner != ty_id) {
+      n += 1;
+      key = format!("{label} #{n}");
+    }
+
+    // Registered before the shape so a type reaching itself
+    // through a field stops here.
+    self.keys.insert(ty_id, key.clone());
+    self.taken.insert(key.clone(), ty_id);
+
+    let table = ctx.ty_table;
+    let shape = match (ty, map) {
+      (_, Some((key_fmt, val_fmt))) => DebugTy::Map { key_fmt, val_fmt },
+      (Ty::Unit, _) => DebugTy::Unit,
+      (Ty::Bool, _) => DebugTy::Bool,
+      (Ty::Char, _) => DebugTy::Char,
+      (Ty::Int { signed, .. }, _) => DebugTy::Int {
+        signed,
+        size: scalar_size(ty),
+      },
+      (Ty::Float(_), _) => DebugTy::Float {
+        size: scalar_size(ty),
+      },
+      (Ty::Str, _) => DebugTy::Str,
+      (Ty::Array(aid), _) => match table.array(aid) {
+        Some(array) => DebugTy::Array {
+          elem: self.key(ctx, array.elem_ty),
+        },
+        None => DebugTy::Opaque,
+      },
+      (Ty::Tuple(tid), _) => match table.tuple(tid) {
+        Some(tuple) => DebugTy::Tuple {
+          elems: table
+            .tuple_elems(tuple)
+            .iter()
+            .map(|elem| self.key(ctx, *elem))
+            .collect(),
+        },
+        None => DebugTy::Opaque,
+      },
+      (Ty::Struct(sid), _) => match table.struct_ty(sid) {
+        Some(st) => DebugTy::Struct {
+          fields: table
+            .struct_fields(st)
+            .iter()
+            .map(|field| {
+              (
+                ctx.interner.get(field.name).to_string(),
+                self.key(ctx, field.ty_id),
+              )
+            })
+            .collect(),
+        },
+        None => DebugTy::Opaque,
+      },
+      (Ty::Enum(eid), _) => match table.enum_ty(eid) {
+        Some(en) => DebugTy::Enum {
+          variants: table
+            .enum_variants(en)
+            .iter()
+            .map(|variant| DebugVariant {
+              name: ctx.interner.get(variant.name).to_string(),
+              tag: u64::from(variant.discriminant),
+              fields: table
+                .variant_fields(variant)
+                .iter()
+                .map(|field| self.key(ctx, *field))
+                .collect(),
+            })
+            .collect(),
+        },
+        None => DebugTy::Opaque,
+      },
+      _ => DebugTy::Opaque,
+    };
+
+    self.table.types.insert(key.clone(), shape);
+
+    key
+  }
+}
+
+/// A [`gimli`] section writer that keeps its relocations for
+/// the object writer.
+#[derive(Clone)]
+struct SectionWriter {
+  data: EndianVec<RunTimeEndian>,
+  relocs: Vec<Relocation>,
+}
+
+impl Default for SectionWriter {
+  fn default() -> Self {
+    Self {
+      data: EndianVec::new(RunTimeEndian::Little),
+      relocs: Vec::new(),
+    }
+  }
+}
+
+impl RelocateWriter for SectionWriter {
+  type Writer = EndianVec<RunTimeEndian>;
+
+  fn writer(&self) -> &Self::Writer {
+    &self.data
+  }
+
+  fn writer_mut(&mut self) -> &mut Self::Writer {
+    &mut self.data
+  }
+
+  fn relocate(&mut self, relocation: Relocation) {
+    self.relocs.push(relocation);
+  }
+}
+
+/// Adds every non-empty section to `object`, then resolves
+/// their relocations — against the function symbols, or the
+/// section symbol of another debug section.
+fn write_sections(
+  object: &mut write::Object<'static>,
+  sections: &Sections<SectionWriter>,
+  symbols: &[SymbolId],
+) {
+  let segment = object.segment_name(write::StandardSegment::Debug).to_vec();
+  let mut ids = HashMap::<SectionId, write::SectionId>::default();
+
+  let _ = sections.for_each(|id, section| {
+    if !section.data.slice().is_empty() {
+      let sid = object.add_section(
+        segment.clone(),
+        id.name().as_bytes().to_vec(),
+        SectionKind::Debug,
+      );
+
+      object.set_section_data(sid, section.data.slice().to_vec(), 1);
+      ids.insert(id, sid);
+    }
+
+    Ok::<_, ()>(())
+  });
+
+  let _ = sections.for_each(|id, section| {
+    let Some(sid) = ids.get(&id).copied() else {
+      return Ok(());
+    };
+
+    for reloc in &section.relocs {
+      let symbol = match reloc.target {
+        RelocationTarget::Symbol(idx) => symbols[idx],
+        RelocationTarget::Section(target) => match ids.get(&target) {
+          Some(target) => object.section_symbol(*target),
+          None => continue,
+        },
+      };
+
+      let _ = object.add_relocation(
+        sid,
+        write::Relocation {
+          offset: reloc.offset as u64,
+          symbol,
+          addend: reloc.addend,
+          flags: RelocationFlags::Generic {
+            kind: RelocationKind::Absolute,
+            encoding: RelocationEncoding::Generic,
+            size: reloc.size * 8,
+          },
+        },
+      );
+    }
+
+    Ok::<_, ()>(())
+  });
+}
+
+/// Where a labelled value lives over one code range, as a
+/// DWARF location expression.
+fn location_expr(
+  isa: &dyn TargetIsa,
+  loc: LabelValueLoc,
+) -> Option<Expression> {
+  let mut expr = Expression::new();
+
+  match loc {
+    LabelValueLoc::Reg(reg) => {
+      let reg = isa.map_regalloc_reg_to_dwarf(reg).ok()?;
+
+      expr.op_reg(Register(reg));
+    }
+    LabelValueLoc::CFAOffset(offset) => {
+      expr.op(gimli::DW_OP_call_frame_cfa);
+      expr.op_consts(offset);
+      expr.op(gimli::DW_OP_plus);
+    }
+  }
+
+  Some(expr)
+}
+
+/// Function `idx` of the unit, plus `addend`.
+fn symbol(idx: usize, addend: i64) -> Address {
+  Address::Symbol {
+    symbol: idx,
+    addend,
+  }
+}
+
+/// Byte width of a scalar as codegen stores it.
+fn scalar_size(ty: Ty) -> u8 {
+  match ty {
+    Ty::Int { width, .. } => match width {
+      IntWidth::S8 | IntWidth::U8 => 1,
+      IntWidth::S16 | IntWidth::U16 => 2,
+      IntWidth::S32 | IntWidth::U32 => 4,
+      IntWidth::S64 | IntWidth::U64 | IntWidth::Arch => 8,
+    },
+    Ty::Float(FloatWidth::F32) => 4,
+    _ => 8,
+  }
+}
+
+fn fmt_name(fmt: u32) -> &'static str {
+  MAP_FMT_NAMES.get(fmt as usize).copied().unwrap_or("int")
+}
+
+fn dir_bytes(path: &Path) -> Vec<u8> {
+  match path.parent().map(Path::to_string_lossy) {
+    Some(dir) if !dir.is_empty() => dir.as_bytes().to_vec(),
+    _ => b".".to_vec(),
+  }
+}
+
+fn file_bytes(path: &Path) -> Vec<u8> {
+  path
+    .file_name()
+    .map_or_else(|| path.as_os_str(), |name| name)
+    .to_string_lossy()
+    .as_bytes()
+    .to_vec()
+}
@@ -8,9 +8,10 @@
 //! the fail branch.
 
 use crate::context::{FunCtx, TCtx};
+use crate::map::emit_map_show;
 use crate::runtime::{
-  emit_exit_1, emit_write_call, ensure_anon_data, ensure_libc_func,
-  trap_and_resume,
+  emit_exit_1, emit_str_parts, emit_write_call, ensure_anon_data,
+  ensure_libc_func, trap_and_resume,
 };
 use crate::types::is_unsigned_int;
 
@@ -21,7 +22,7 @@ use zo_value::ValueId;
 use cranelift::codegen::ir;
 use cranelift::codegen::ir::condcodes::IntCC;
 use cranelift::codegen::ir::{
-  AbiParam, InstBuilder, MemFlags, StackSlotData, StackSlotKind,
+  AbiParam, InstBuilder, StackSlotData, StackSlotKind,
 };
 use cranelift::frontend::FunctionBuilder;
 use cranelift_module::Module;
@@ -393,6 +394,8 @@ pub(crate) fn emit_check_intrinsic(
 ///   through libc `snprintf` + `write`.
 /// - **floats** (TyId 15..=17): promote to F64 and route
 ///   through the `zo_ftoa_f64` runtime wrapper + `write`.
+/// - **maps** (a type with an `Insn::MapTyDef`): the runtime's
+///   `zo_map_show`.
 /// - **anything else** (aggregates): trap — recursive
 ///   formatting of tuples / structs / arrays isn't wired.
 ///
@@ -428,10 +431,10 @@ pub(crate) fn emit_io_intrinsic(
   let arg_ty_id = ctx.value_types.get(&arg_id).copied().unwrap_or(TyId(0));
 
   match arg_ty_id.0 {
-    // Str — pointer to `[u64 LE len, utf-8 bytes]` header.
+    // Str — pointer to `[u64 LE len, utf-8 bytes]` header,
+    // or to a slice view.
     4 => {
-      let len = builder.ins().load(tctx.ptr_ty, MemFlags::new(), arg_val, 0);
-      let data_ptr = builder.ins().iadd_imm(arg_val, 8);
+      let (len, data_ptr) = emit_str_parts(builder, tctx.ptr_ty, arg_val);
 
       emit_write_call(tctx, builder, fd, data_ptr, len);
     }
@@ -465,6 +468,9 @@ pub(crate) fn emit_io_intrinsic(
     15..=17 => {
       emit_float_show(tctx, builder, fd, arg_val);
     }
+    ty if let Some(&fmts) = tctx.map_fmts.get(&ty) => {
+      emit_map_show(tctx, builder, fd, arg_val, fmts);
+    }
     // Aggregates (tuple / struct / array / enum) fall here —
     // they need recursive per-field formatting that isn't
     // wired yet. Abort cleanly so the user sees a clear exit
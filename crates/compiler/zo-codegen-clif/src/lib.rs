@@ -12,13 +12,20 @@
 //! Entry point [`CliftGen`] selects an ISA from the `Target`,
 //! builds an `ObjectModule`, translates SIR into CLIF, and
 //! hands the resulting object bytes to `zo-linker` for the
-//! final `cc` invocation.
+//! final `cc` invocation. A debug build also carries DWARF and
+//! the zo type table `zo-dap` reads (see `debug`).
 
+mod aggregate;
 mod codegen;
 mod context;
+mod debug;
 mod intrinsics;
+mod map;
 mod runtime;
 mod translate;
 mod types;
 
+#[cfg(test)]
+mod tests;
+
 pub use codegen::CliftGen;
@@ -0,0 +1,372 @@
+//! `HashMap<K, V>` lowering.
+//!
+//! `collections/map.zo` leaves the methods that move keys and
+//! values across the runtime boundary as stub bodies; like
+//! the ARM backend, every call to one is replaced at the call
+//! site by a call into the `zo_map_*` exports of `zo-runtime`,
+//! which `zo-linker` links in. `len`, `free`, `keys` and
+//! `MapKeys::next` are replaced too: their zo bodies read the
+//! map pointer through the `int` field `ptr`, which CLIF
+//! lowers to 32 bits.
+//!
+//! A map value is the address of one 8-byte slot holding the
+//! runtime's map pointer — the `HashMap { ptr }` struct. Keys
+//! and values are spilled into 8-byte slots whose address the
+//! runtime reads `key_sz` / `val_sz` bytes from: the scalar
+//! itself, or the pointer of a `str`, tuple or struct.
+
+use crate::context::{AGG_ALIGN_SHIFT, AGG_SLOT_SIZE, FunCtx, TCtx};
+use crate::runtime::{ensure_libc_func, trap_and_resume};
+use crate::types::ty_id_to_clif;
+
+use zo_ty::TyId;
+use zo_value::ValueId;
+
+use cranelift::codegen::ir;
+use cranelift::codegen::ir::condcodes::IntCC;
+use cranelift::codegen::ir::{
+  AbiParam, InstBuilder, MemFlags, StackSlotData, StackSlotKind,
+};
+use cranelift::frontend::FunctionBuilder;
+use cranelift_module::Module;
+
+/// The `zo-runtime` export a non-marshaling `*_raw` ffi of the
+/// collections stdlib forwards to — the ARM backend rewrites the
+/// same calls. Sets reuse the map allocator wholesale.
+pub(crate) fn raw_export(name: &str) -> Option<&'static str> {
+  Some(match name {
+    "zo_map_len_raw" | "zo_set_len_raw" => "zo_map_len",
+    "zo_map_free_raw" | "zo_set_free_raw" => "zo_map_free",
+    "zo_map_next_slot_raw" => "zo_map_next_slot",
+    "zo_vec_len_raw" => "zo_vec_len",
+    "zo_vec_free_raw" => "zo_vec_free",
+    _ => return None,
+  })
+}
+
+/// `true` for the calls [`emit_map_intrinsic`] replaces.
+pub(crate) fn is_map_intrinsic(name: &str) -> bool {
+  matches!(
+    name,
+    "HashMap::new"
+      | "HashMap::insert"
+      | "HashMap::get"
+      | "HashMap::contains_key"
+      | "HashMap::remove"
+      | "HashMap::len"
+      | "HashMap::free"
+      | "HashMap::keys"
+      | "MapKeys::next"
+  )
+}
+
+/// Lowers a call to one of the `HashMap` / `MapKeys` methods
+/// [`is_map_intrinsic`] names into `zo_map_*` runtime calls.
+///
+/// `HashMap::new` receives the executor's prepended `key_kind`,
+/// `key_sz`, `val_sz` and — for a `%% hash.` struct key — the
+/// struct's `hash` / `eq` addresses. `get`, `remove` and
+/// `MapKeys::next` build their `Option` in place: the runtime
+/// copies the value into the payload word, and the tag is
+/// `Some` (0) exactly when it reports a hit.
+pub(crate) fn emit_map_intrinsic(
+  tctx: &mut TCtx<'_>,
+  builder: &mut FunctionBuilder,
+  ctx: &mut FunCtx,
+  dst: ValueId,
+  name: &str,
+  args: &[ValueId],
+  ty_id: TyId,
+) {
+  let Some(vals) = args
+    .iter()
+    .map(|arg| ctx.values.get(arg).copied())
+    .collect::<Option<Vec<_>>>()
+  else {
+    trap_and_resume(tctx, builder, ctx);
+
+    return;
+  };
+
+  let ptr_ty = tctx.ptr_ty;
+
+  if name == "HashMap::new" {
+    let [kind, key_sz, val_sz, ..] = vals[..] else {
+      trap_and_resume(tctx, builder, ctx);
+
+      return;
+    };
+
+    let kind = fit(builder, kind, ir::types::I32);
+    let key_sz = fit(builder, key_sz, ptr_ty);
+    let val_sz = fit(builder, val_sz, ptr_ty);
+    let cap = builder.ins().iconst(ptr_ty, 16);
+    let null = builder.ins().iconst(ptr_ty, 0);
+    let hash = vals.get(3).copied().unwrap_or(null);
+    let eq = vals.get(4).copied().unwrap_or(null);
+    let map = call_runtime(
+      tctx,
+      builder,
+      "zo_map_new",
+      &[kind, key_sz, val_sz, cap, hash, eq],
+      Some(ptr_ty),
+    );
+    let slot = words(builder, 1);
+
+    builder.ins().stack_store(map, slot, 0);
+
+    let addr = builder.ins().stack_addr(ptr_ty, slot, 0);
+
+    ctx.values.insert(dst, addr);
+
+    return;
+  }
+
+  let Some(&recv) = vals.first() else {
+    trap_and_resume(tctx, builder, ctx);
+
+    return;
+  };
+
+  let map = builder.ins().load(ptr_ty, MemFlags::trusted(), recv, 0);
+
+  let v = match (name, &vals[1..]) {
+    ("HashMap::insert", &[key, val]) => {
+      let key = spill(builder, ptr_ty, key);
+      let val = spill(builder, ptr_ty, val);
+
+      call_runtime(tctx, builder, "zo_map_insert", &[map, key, val], None);
+
+      builder.ins().iconst(ir::types::I8, 0)
+    }
+    ("HashMap::get" | "HashMap::remove", &[key]) => {
+      let symbol = if name == "HashMap::get" {
+        "zo_map_get"
+      } else {
+        "zo_map_remove"
+      };
+      let key = spill(builder, ptr_ty, key);
+      let (option, payload) = option(builder, ptr_ty);
+      let found = call_runtime(
+        tctx,
+        builder,
+        symbol,
+        &[map, key, payload],
+        Some(ir::types::I8),
+      );
+
+      set_tag(builder, option, found);
+
+      builder.ins().stack_addr(ptr_ty, option, 0)
+    }
+    ("HashMap::contains_key", &[key]) => {
+      let key = spill(builder, ptr_ty, key);
+
+      call_runtime(
+        tctx,
+        builder,
+        "zo_map_contains",
+        &[map, key],
+        Some(ir::types::I8),
+      )
+    }
+    ("HashMap::len", []) => {
+      let len = call_runtime(tctx, builder, "zo_map_len", &[map], Some(ptr_ty));
+
+      fit(builder, len, ty_id_to_clif(ty_id, ptr_ty))
+    }
+    ("HashMap::free", []) => {
+      call_runtime(tctx, builder, "zo_map_free", &[map], None);
+
+      builder.ins().iconst(ir::types::I8, 0)
+    }
+    ("HashMap::keys", []) => {
+      // `MapKeys { ptr, slot = 0 }`.
+      let cursor = words(builder, 2);
+      let zero = builder.ins().iconst(ptr_ty, 0);
+
+      builder.ins().stack_store(map, cursor, 0);
+      builder
+        .ins()
+        .stack_store(zero, cursor, AGG_SLOT_SIZE as i32);
+      builder.ins().stack_addr(ptr_ty, cursor, 0)
+    }
+    ("MapKeys::next", []) => {
+      // `recv` is the cursor; the first live bucket at or
+      // after its `slot` yields the key, and the cursor moves
+      // past it. At the end `zo_map_next_slot` answers -1,
+      // which `zo_map_key_at` rejects and the cursor keeps.
+      let flags = MemFlags::trusted();
+      let from = builder
+        .ins()
+        .load(ptr_ty, flags, recv, AGG_SLOT_SIZE as i32);
+      let slot = call_runtime(
+        tctx,
+        builder,
+        "zo_map_next_slot",
+        &[map, from],
+        Some(ptr_ty),
+      );
+      let (option, payload) = option(builder, ptr_ty);
+      let found = call_runtime(
+        tctx,
+        builder,
+        "zo_map_key_at",
+        &[map, slot, payload],
+        Some(ir::types::I8),
+      );
+      let next = builder.ins().iadd_imm(slot, 1);
+      let next = builder.ins().select(found, next, from);
+
+      builder.ins().store(flags, next, recv, AGG_SLOT_SIZE as i32);
+      set_tag(builder, option, found);
+      builder.ins().stack_addr(ptr_ty, option, 0)
+    }
+    _ => {
+      trap_and_resume(tctx, builder, ctx);
+
+      return;
+    }
+  };
+
+  ctx.values.insert(dst, v);
+}
+
+/// `showln(m)` for a map: `zo_map_show` writes the entries to
+/// `fd` in the `key_fmt` / `val_fmt` of the map's
+/// `Insn::MapTyDef`.
+pub(crate) fn emit_map_show(
+  tctx: &mut TCtx<'_>,
+  builder: &mut FunctionBuilder,
+  fd: i64,
+  recv: ir::Value,
+  (key_fmt, val_fmt): (u32, u32),
+) {
+  let ptr_ty = tctx.ptr_ty;
+  let map = builder.ins().load(ptr_ty, MemFlags::trusted(), recv, 0);
+  let fd = builder.ins().iconst(ptr_ty, fd);
+  let key_fmt = builder.ins().iconst(ir::types::I32, key_fmt as i64);
+  let val_fmt = builder.ins().iconst(ir::types::I32, val_fmt as i64);
+
+  call_runtime(
+    tctx,
+    builder,
+    "zo_map_show",
+    &[map, fd, key_fmt, val_fmt],
+    None,
+  );
+}
+
+/// Calls the runtime's `name` with `args`, returning its
+/// result when it has one.
+///
+/// The call goes through the symbol's address with a signature
+/// built from `args`: the `ffi` bindings in `map.zo` already
+/// declare `zo_map_new` & co. with `int` parameters, and
+/// Cranelift keeps one signature per name.
+fn call_runtime(
+  tctx: &mut TCtx<'_>,
+  builder: &mut FunctionBuilder,
+  name: &'static str,
+  args: &[ir::Value],
+  ret: Option<ir::Type>,
+) -> ir::Value {
+  let call_conv = tctx.module.target_config().default_call_conv;
+  let mut sig = ir::Signature::new(call_conv);
+
+  for &arg in args {
+    sig
+      .params
+      .push(AbiParam::new(builder.func.dfg.value_type(arg)));
+  }
+
+  sig.returns.extend(ret.map(AbiParam::new));
+
+  let func_id = ensure_libc_func(tctx, name, |_, _| sig.clone());
+  let fref = tctx.module.declare_func_in_func(func_id, builder.func);
+  let addr = builder.ins().func_addr(tctx.ptr_ty, fref);
+  let sigref = builder.import_signature(sig);
+  let call = builder.ins().call_indirect(sigref, addr, args);
+
+  match builder.inst_results(call).first().copied() {
+    Some(v) => v,
+    None => builder.ins().iconst(ir::types::I8, 0),
+  }
+}
+
+/// A stack slot of `n` aggregate words.
+fn words(builder: &mut FunctionBuilder, n: u32) -> ir::StackSlot {
+  builder.create_sized_stack_slot(StackSlotData::new(
+    StackSlotKind::ExplicitSlot,
+    n * AGG_SLOT_SIZE,
+    AGG_ALIGN_SHIFT,
+  ))
+}
+
+/// Spills `v` into a fresh word and returns its address.
+/// Integers are sign-extended and `f32` promoted, so the word
+/// reads back the way `MapFmt` formats it.
+fn spill(
+  builder: &mut FunctionBuilder,
+  ptr_ty: ir::Type,
+  v: ir::Value,
+) -> ir::Value {
+  let ty = builder.func.dfg.value_type(v);
+  let word = if ty == ir::types::F32 {
+    builder.ins().fpromote(ir::types::F64, v)
+  } else if ty == ir::types::I8 {
+    builder.ins().uextend(ir::types::I64, v)
+  } else if ty.is_int() && ty.bits() < 64 {
+    builder.ins().sextend(ir::types::I64, v)
+  } else {
+    v
+  };
+
+  let slot = words(builder, 1);
+
+  builder.ins().stack_store(word, slot, 0);
+  builder.ins().stack_addr(ptr_ty, slot, 0)
+}
+
+/// A zeroed `Option` — `[tag, payload]` — and the address of
+/// its payload word, for the runtime to fill.
+fn option(
+  builder: &mut FunctionBuilder,
+  ptr_ty: ir::Type,
+) -> (ir::StackSlot, ir::Value) {
+  let slot = words(builder, 2);
+  let zero = builder.ins().iconst(ir::types::I64, 0);
+
+  builder.ins().stack_store(zero, slot, AGG_SLOT_SIZE as i32);
+
+  let payload = builder.ins().stack_addr(ptr_ty, slot, AGG_SLOT_SIZE as i32);
+
+  (slot, payload)
+}
+
+/// Tags `option` `Some` (0) when `found`, `None` (1) otherwise.
+fn set_tag(
+  builder: &mut FunctionBuilder,
+  option: ir::StackSlot,
+  found: ir::Value,
+) {
+  let found = builder.ins().icmp_imm(IntCC::NotEqual, found, 0);
+  let some = builder.ins().iconst(ir::types::I64, 0);
+  let none = builder.ins().iconst(ir::types::I64, 1);
+  let tag = builder.ins().select(found, some, none);
+
+  builder.ins().stack_store(tag, option, 0);
+}
+
+/// `v` as an integer of type `ty`, sign-extended or reduced.
+fn fit(builder: &mut FunctionBuilder, v: ir::Value, ty: ir::Type) -> ir::Value {
+  let from = builder.func.dfg.value_type(v);
+
+  if from == ty {
+    v
+  } else if from.bits() < ty.bits() {
+    builder.ins().sextend(ty, v)
+  } else {
+    builder.ins().ireduce(ty, v)
+  }
+}
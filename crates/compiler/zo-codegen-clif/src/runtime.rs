@@ -4,6 +4,8 @@
 //!   imports and `.rodata` blobs per module so every caller
 //!   gets the same `FuncId` / `DataId`.
 //! - [`emit_write_call`] emits a libc `write(fd, buf, count)`.
+//! - [`emit_str_parts`] unpacks a zo `str` into length and
+//!   payload address.
 //! - [`emit_exit_1`] emits a Rosetta-safe `exit(1)` terminator.
 //! - [`trap_and_resume`] wraps [`emit_exit_1`] with a fresh
 //!   post-terminator block so callers can keep emitting insns.
@@ -11,17 +13,23 @@
 use crate::context::{FunCtx, TCtx};
 
 use cranelift::codegen::ir;
-use cranelift::codegen::ir::{AbiParam, InstBuilder};
+use cranelift::codegen::ir::condcodes::IntCC;
+use cranelift::codegen::ir::{AbiParam, InstBuilder, MemFlags};
 use cranelift::codegen::isa::CallConv;
 use cranelift::frontend::FunctionBuilder;
-use cranelift_module::{DataDescription, DataId, FuncId, Linkage, Module};
+use cranelift_module::{
+  DataDescription, DataId, FuncId, FuncOrDataId, Linkage, Module,
+};
 
 /// Declares a libc function as an `Import` exactly once per
 /// module, keyed by `name`. The signature builder runs only
 /// on the cache-miss path so repeated calls are a pure
 /// `HashMap::get`. `cc` resolves the symbol against libc /
 /// libSystem at link time — no extra `-l` flag needed, both
-/// are pulled in by the default C startup.
+/// are pulled in by the default C startup. A symbol the
+/// program already declared through an `ffi` binding is
+/// reused as-is — callers fit their arguments to its
+/// signature.
 pub(crate) fn ensure_libc_func(
   tctx: &mut TCtx<'_>,
   name: &'static str,
@@ -31,6 +39,14 @@ pub(crate) fn ensure_libc_func(
     return id;
   }
 
+  if let Some(FuncOrDataId::Func(id)) =
+    tctx.module.declarations().get_name(name)
+  {
+    tctx.libc_funcs.insert(name, id);
+
+    return id;
+  }
+
   let call_conv = tctx.module.target_config().default_call_conv;
   let sig = build_sig(tctx.ptr_ty, call_conv);
   let id = tctx
@@ -102,6 +118,46 @@ pub(crate) fn emit_write_call(
   builder.ins().call(fref, &[fd_v, buf, count]);
 }
 
+/// Unpacks the zo `str` at `s` into `(len, data)`.
+///
+/// An owned str holds its bytes inline at `s + 8`. A slice
+/// view (`zo_str_slice`) sets the top bit of the length word
+/// and holds a pointer into its parent at `s + 8`, so the
+/// payload address needs a branch — the inline bytes of a
+/// short owned str can't be loaded as a pointer.
+pub(crate) fn emit_str_parts(
+  builder: &mut FunctionBuilder,
+  ptr_ty: ir::Type,
+  s: ir::Value,
+) -> (ir::Value, ir::Value) {
+  let word = builder.ins().load(ptr_ty, MemFlags::new(), s, 0);
+  let len = builder.ins().band_imm(word, i64::MAX);
+  let is_view = builder.ins().icmp_imm(IntCC::SignedLessThan, word, 0);
+
+  let view_block = builder.create_block();
+  let owned_block = builder.create_block();
+  let merge = builder.create_block();
+  let data = builder.append_block_param(merge, ptr_ty);
+
+  builder
+    .ins()
+    .brif(is_view, view_block, &[], owned_block, &[]);
+
+  builder.switch_to_block(view_block);
+
+  let parent = builder.ins().load(ptr_ty, MemFlags::new(), s, 8);
+
+  builder.ins().jump(merge, &[parent.into()]);
+  builder.switch_to_block(owned_block);
+
+  let inline = builder.ins().iadd_imm(s, 8);
+
+  builder.ins().jump(merge, &[inline.into()]);
+  builder.switch_to_block(merge);
+
+  (len, data)
+}
+
 /// Emits `exit(1)` as a block terminator: libc call followed
 /// by an unreachable `trap`.
 ///
@@ -0,0 +1,122 @@
+mod str_slicing;
+mod struct_return;
+
+use crate::CliftGen;
+
+use zo_codegen_backend::{Backend, Target};
+use zo_dce::Dce;
+use zo_executor::Executor;
+use zo_interner::{Interner, Symbol};
+use zo_ownership::Ownership;
+use zo_parser::Parser;
+use zo_sir::{Insn, Sir};
+use zo_span::Span;
+use zo_tokenizer::Tokenizer;
+use zo_ty::{SelfKind, TyId};
+use zo_ty_checker::TyChecker;
+use zo_value::{FunctionKind, Pubness, ValueId};
+
+use std::process::{Command, Output};
+
+/// Compiles `source` for the host, links it and runs it.
+fn compile_and_run(source: &str) -> Output {
+  let mut interner = Interner::new();
+  let tokenization = Tokenizer::new(source, &mut interner).tokenize();
+  let parsing = Parser::new(&tokenization, source).parse();
+  let mut ty_checker = TyChecker::new();
+
+  let mut sir = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty_checker,
+  )
+  .execute()
+  .sir;
+
+  // Resolves the scope-exit `Drop` markers, as the compiler
+  // does before codegen.
+  Ownership::new(&mut sir, &interner, &ty_checker).check();
+
+  let target = Target::X8664UnknownLinuxGnu;
+  let artifact = CliftGen::new(&interner, target).generate(&sir);
+  let dir = tempfile::tempdir().unwrap();
+  let binary = dir.path().join("program");
+
+  zo_linker::link_to_executable(&artifact.code, &binary, target, &|_| None)
+    .expect("link failed");
+
+  Command::new(&binary).output().expect("run failed")
+}
+
+/// Stdout of a [`compile_and_run`] that exits 0.
+fn stdout(source: &str) -> String {
+  let output = compile_and_run(source);
+
+  assert!(output.status.success(), "{output:?}");
+
+  String::from_utf8(output.stdout).unwrap()
+}
+
+/// `fun name() { body }` as the executor emits it.
+fn fun(sir: &mut Sir, name: Symbol, body: Vec<Insn>) {
+  sir.emit(Insn::FunDef {
+    name,
+    params: vec![],
+    return_ty: TyId(1),
+    body_start: sir.instructions.len() as u32 + 1,
+    kind: FunctionKind::UserDefined,
+    pubness: Pubness::No,
+    self_kind: SelfKind::None,
+    link_name: None,
+    env_slot: None,
+    owning_pack: None,
+    span: Span::ZERO,
+    is_test: false,
+  });
+
+  for insn in body {
+    sir.emit(insn);
+  }
+
+  sir.emit(Insn::Return {
+    value: None,
+    ty_id: TyId(1),
+  });
+}
+
+fn call(name: Symbol) -> Insn {
+  Insn::Call {
+    dst: ValueId(0),
+    name,
+    callee_pack: None,
+    args: vec![],
+    ty_id: TyId(1),
+  }
+}
+
+/// DCE dropping `dead` shifts `helper` and `main` down; a
+/// `body_start` left at its old index points past the next
+/// `FunDef`, and the backend would import both functions
+/// instead of defining them.
+#[test]
+fn functions_after_an_eliminated_one_keep_their_bodies() {
+  let mut interner = Interner::new();
+  let dead = interner.intern("dead");
+  let helper = interner.intern("helper");
+  let main = interner.intern("main");
+  let mut sir = Sir::new();
+
+  fun(&mut sir, dead, vec![call(dead), call(dead), call(dead)]);
+  fun(&mut sir, helper, vec![]);
+  fun(&mut sir, main, vec![call(helper)]);
+
+  sir.spans = vec![Span::ZERO; sir.instructions.len()];
+
+  Dce::new(&mut sir, vec![main], &interner).eliminate();
+
+  let ir =
+    CliftGen::new(&interner, Target::X8664UnknownLinuxGnu).generate_asm(&sir);
+
+  assert_eq!(ir.matches("\nblock0").count(), 2, "{ir}");
+}
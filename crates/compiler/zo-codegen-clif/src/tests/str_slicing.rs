@@ -0,0 +1,106 @@
+//! Runtime string slices, built and run end to end: each
+//! range form reaches `zo_str_slice` with the right bounds, a
+//! view can be sliced again, and a bound inside a multi-byte
+//! char stops the program.
+//!
+//! ```sh
+//! cargo test -p zo-codegen-clif str_slicing
+//! ```
+
+use super::{compile_and_run, stdout};
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "linux", target_arch = "x86_64")),
+  ignore = "requires x86_64 Linux with cc"
+)]
+fn inclusive_range_takes_the_upper_bound() {
+  let out = stdout(
+    r#"
+      fun main() {
+        imu s: str = "hello, world!";
+        mut lo: int = 7;
+        mut hi: int = 11;
+
+        showln(s[lo..=hi]);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "world\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "linux", target_arch = "x86_64")),
+  ignore = "requires x86_64 Linux with cc"
+)]
+fn open_ranges_run_to_either_end() {
+  let out = stdout(
+    r#"
+      fun main() {
+        imu s: str = "hello, world!";
+        mut lo: int = 7;
+        mut hi: int = 5;
+
+        showln(s[lo..]);
+        showln(s[..hi]);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "world!\nhello\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "linux", target_arch = "x86_64")),
+  ignore = "requires x86_64 Linux with cc"
+)]
+fn view_of_a_view_slices_the_view() {
+  let out = stdout(
+    r#"
+      fun main() {
+        imu s: str = "hello, world!";
+        mut lo: int = 7;
+        mut one: int = 1;
+        mut hi: int = 4;
+
+        imu tail: str = s[lo..];
+        imu inner: str = tail[one..hi];
+
+        showln(inner);
+        showln(tail[one..]);
+        check@eq(inner.len, 3);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "orl\norld!\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "linux", target_arch = "x86_64")),
+  ignore = "requires x86_64 Linux with cc"
+)]
+fn bound_inside_a_char_stops_the_program() {
+  let output = compile_and_run(
+    r#"
+      fun main() {
+        imu greek: str = "αβγ";
+        mut one: int = 1;
+
+        showln(greek[one..]);
+      }
+    "#,
+  );
+
+  assert!(!output.status.success());
+  assert!(output.stdout.is_empty());
+  assert!(
+    String::from_utf8_lossy(&output.stderr)
+      .contains("byte index 1 is not a char boundary"),
+    "{output:?}"
+  );
+}
@@ -0,0 +1,90 @@
+//! Aggregates returned from a call outlive the callee's frame:
+//! the next call doesn't overwrite them.
+//!
+//! ```sh
+//! cargo test -p zo-codegen-clif struct_return
+//! ```
+
+use super::stdout;
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "linux", target_arch = "x86_64")),
+  ignore = "requires x86_64 Linux with cc"
+)]
+fn struct_survives_the_next_call() {
+  let out = stdout(
+    r#"
+      struct P { x: int, y: int }
+
+      fun make(a: int) -> P {
+        P { x = a, y = a + 1 }
+      }
+
+      fun main() {
+        imu p: P = make(3);
+        showln(p.x);
+        showln(p.y);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "3\n4\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "linux", target_arch = "x86_64")),
+  ignore = "requires x86_64 Linux with cc"
+)]
+fn nested_struct_is_copied_out() {
+  let out = stdout(
+    r#"
+      struct In { a: int, b: int }
+      struct Out { i: In, c: int }
+
+      fun nest(n: int) -> Out {
+        Out { i = In { a = n, b = n + 1 }, c = n + 2 }
+      }
+
+      fun main() {
+        imu o: Out = nest(5);
+        imu p: Out = nest(9);
+        showln(o.i.b);
+        showln(o.c);
+        showln(p.i.a);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "6\n7\n9\n");
+}
+
+#[test]
+#[cfg_attr(
+  not(all(target_os = "linux", target_arch = "x86_64")),
+  ignore = "requires x86_64 Linux with cc"
+)]
+fn enum_and_tuple_keep_their_payload() {
+  let out = stdout(
+    r#"
+      enum Found { Yes(int), No }
+
+      fun find(n: int) -> Found {
+        if n > 0 { return Found::Yes(n); }
+        return Found::No;
+      }
+
+      fun pair(n: int) -> (int, int) { (n, n * 2) }
+
+      fun main() {
+        imu t: (int, int) = pair(2);
+        match find(3) { Found::Yes(v) => showln(v), Found::No => showln(0) }
+        match find(0) { Found::Yes(v) => showln(v), Found::No => showln(0) }
+        showln(t.1);
+      }
+    "#,
+  );
+
+  assert_eq!(out, "3\n0\n4\n");
+}
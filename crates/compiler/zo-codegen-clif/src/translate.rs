@@ -6,7 +6,10 @@
 //!      inline the literal at every use site.
 //!   2. Declaration sweep — `declare_function` for every
 //!      `Insn::FunDef` (imports for empty-body functions,
-//!      exports otherwise) so forward calls resolve.
+//!      local definitions otherwise, `main` exported) so
+//!      forward calls resolve. A definition returning an
+//!      aggregate gets a hidden `sret` parameter (see
+//!      `aggregate`).
 //!   3. Per-body: determine range
 //!      `[body_start .. next_fundef_or_end]`, pre-allocate a
 //!      CLIF `Block` for every `Insn::Label` so forward jumps
@@ -16,18 +19,21 @@
 //! real work (`PackDecl`, `ModuleLoad`, type definitions,
 //! `Directive`) are explicit no-ops at codegen time.
 
+use crate::aggregate::{collect_agg_shapes, emit_sret_copy, sret_size};
 use crate::context::{
   AGG_ALIGN_SHIFT, AGG_SLOT_SIZE, ConstLiteral, FunCtx, TCtx,
 };
+use crate::debug::{DebugCtx, FunDebug};
 use crate::intrinsics::{emit_check_intrinsic, emit_io_intrinsic};
-use crate::runtime::{emit_exit_1, ensure_libc_func};
+use crate::map::{emit_map_intrinsic, is_map_intrinsic, raw_export};
+use crate::runtime::{emit_exit_1, emit_str_parts, ensure_libc_func};
 use crate::types::{is_float, is_unsigned_int, pointer_ty, ty_id_to_clif};
 
 use zo_interner::{Interner, Symbol};
 use zo_sir::{BinOp, Insn, LoadSource, UnOp};
 use zo_token::Base;
 use zo_ty::TyId;
-use zo_value::ValueId;
+use zo_value::{FunctionKind, ValueId};
 
 use cranelift::codegen::ir::condcodes::{FloatCC, IntCC};
 use cranelift::codegen::ir::{
@@ -50,11 +56,15 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 /// `CliftGen::generate_asm` consumes it for the `--emit asm`
 /// debug view. Formatting is per-function and runs right
 /// before `define_function` consumes the `Context`.
+///
+/// With a [`DebugCtx`], every function also records its line
+/// rows and local locations for DWARF.
 pub(crate) fn translate_module(
   module: &mut ObjectModule,
   interner: &Interner,
   insns: &[Insn],
   int_bases: &std::collections::HashMap<u32, Base>,
+  mut debug: Option<&mut DebugCtx<'_>>,
 ) -> String {
   let call_conv = module.target_config().default_call_conv;
   let ptr_ty = pointer_ty(module);
@@ -75,6 +85,11 @@ pub(crate) fn translate_module(
   // Module-scope `val NAME = lit;` bindings resolved to their
   // raw literal so every `Load { Local(NAME) }` can inline.
   let const_defs = collect_const_defs(insns);
+  let map_fmts = collect_map_fmts(insns);
+  let agg_shapes = collect_agg_shapes(insns);
+  // Functions declared with an `sret` parameter, with their
+  // return type — `Call` allocates the slot from it.
+  let mut sret_funcs: HashMap<FuncId, TyId> = HashMap::default();
   // Lazily populated by the I/O intercept. Kept at module
   // scope so every function body shares one `FuncId` per
   // libc symbol and one `DataId` per reusable blob.
@@ -97,25 +112,40 @@ pub(crate) fn translate_module(
       // stubs (`fun main() {}`). An empty-body function maps
       // to `Linkage::Import` (stays an unresolved symbol that
       // the system linker fills in); a body-bearing function
-      // maps to `Linkage::Export` (this module owns the
-      // definition).
+      // is defined here. Only `main` is exported: zo names
+      // aren't mangled, so an exported `mem::free` would be
+      // the `free` the runtime's own `free` calls bind to on
+      // ELF. The rest stay `Linkage::Local`.
+      let fname = interner.get(*name);
+      let is_main = fname == "main";
       let linkage = if fundef_body_is_empty(insns, idx, *body_start) {
         Linkage::Import
-      } else {
+      } else if is_main {
         Linkage::Export
+      } else {
+        Linkage::Local
       };
 
       // Cranelift's `ObjectModule` handles platform-specific
       // symbol mangling (e.g. leading `_` for Mach-O) — pass
       // the raw name.
-      let fname = interner.get(*name);
-      let is_main = fname == "main";
-      let sig = build_signature(params, *return_ty, call_conv, ptr_ty, is_main);
+      let sret =
+        linkage == Linkage::Local && agg_shapes.contains_key(return_ty);
+      let sig =
+        build_signature(params, *return_ty, sret, call_conv, ptr_ty, is_main);
+      let symbol = match linkage {
+        Linkage::Import => raw_export(fname).unwrap_or(fname),
+        _ => fname,
+      };
 
       let func_id = module
-        .declare_function(fname, linkage, &sig)
+        .declare_function(symbol, linkage, &sig)
         .expect("declare_function failed");
 
+      if sret {
+        sret_funcs.insert(func_id, *return_ty);
+      }
+
       func_ids.insert(*name, func_id);
     }
   }
@@ -133,6 +163,7 @@ pub(crate) fn translate_module(
       params,
       return_ty,
       body_start,
+      owning_pack,
       ..
     } = &insns[i]
     else {
@@ -149,7 +180,7 @@ pub(crate) fn translate_module(
 
     // Empty body → declared as `Linkage::Import` in the first
     // pass. Nothing to define; skip to the next function.
-    if body_start_u >= end {
+    if fundef_body_is_empty(insns, i, *body_start) {
       i = end;
       continue;
     }
@@ -169,7 +200,9 @@ pub(crate) fn translate_module(
     }
 
     let is_main = interner.get(*name) == "main";
-    let sig = build_signature(params, *return_ty, call_conv, ptr_ty, is_main);
+    let sret = sret_funcs.contains_key(&func_id);
+    let sig =
+      build_signature(params, *return_ty, sret, call_conv, ptr_ty, is_main);
 
     let mut ctx = Context::new();
 
@@ -178,6 +211,10 @@ pub(crate) fn translate_module(
       sig,
     );
 
+    if debug.is_some() {
+      ctx.func.collect_debug_info();
+    }
+
     let mut fbctx = FunctionBuilderContext::new();
     let mut builder = FunctionBuilder::new(&mut ctx.func, &mut fbctx);
 
@@ -189,6 +226,17 @@ pub(crate) fn translate_module(
 
     let mut fun_ctx = FunCtx::new(is_main);
 
+    // The `sret` slot rides ahead of the declared params.
+    let first_param = usize::from(sret);
+
+    if sret {
+      fun_ctx.sret = Some((builder.block_params(entry)[0], *return_ty));
+    }
+
+    if debug.is_some() {
+      fun_ctx.debug = Some(FunDebug::new(body_start_u));
+    }
+
     // Seed `Variable`s from the entry block's parameters.
     // Every param is pushed into `params` (for index-keyed
     // `Load { Param(idx) }`) and mirrored into `vars` under
@@ -196,10 +244,11 @@ pub(crate) fn translate_module(
     // if a mutable param is later reassigned).
     for (idx, (sym, pty)) in params.iter().enumerate() {
       let ty = ty_id_to_clif(*pty, ptr_ty);
-      let v = builder.block_params(entry)[idx];
+      let v = builder.block_params(entry)[first_param + idx];
       let var = fun_ctx.declare_local(&mut builder, *sym, ty);
 
-      builder.def_var(var, v);
+      fun_ctx.declare_debug(interner, *sym, *pty, true);
+      fun_ctx.def_local(&mut builder, var, v);
       fun_ctx.params.push(var);
     }
 
@@ -229,6 +278,9 @@ pub(crate) fn translate_module(
         anon_data: &mut anon_data,
         ptr_ty,
         int_bases,
+        map_fmts: &map_fmts,
+        agg_shapes: &agg_shapes,
+        sret_funcs: &sret_funcs,
       };
 
       translate_body(
@@ -292,6 +344,18 @@ pub(crate) fn translate_module(
       .define_function(func_id, &mut ctx)
       .expect("define_function failed");
 
+    if let (Some(debug), Some(fun_debug)) = (debug.as_mut(), fun_ctx.debug) {
+      debug.finish_function(
+        module.isa(),
+        func_id,
+        *name,
+        i,
+        *owning_pack,
+        &ctx,
+        fun_debug,
+      );
+    }
+
     i = end;
   }
 
@@ -308,12 +372,24 @@ pub(crate) fn translate_module(
 /// `body_start` but only an implicit `Return`, which the
 /// executor flattens into an empty slice when no other
 /// instructions are emitted — those also land here when they
-/// happen to end the stream.
+/// happen to end the stream. An `ffi` is empty whatever
+/// follows it: the last one of a module is trailed by the next
+/// module's `PackDecl` / `link` insns, which are no body.
 fn fundef_body_is_empty(
   insns: &[Insn],
   fundef_idx: usize,
   body_start: u32,
 ) -> bool {
+  if matches!(
+    insns[fundef_idx],
+    Insn::FunDef {
+      kind: FunctionKind::Intrinsic,
+      ..
+    }
+  ) {
+    return true;
+  }
+
   let body_start_u = (body_start as usize).max(fundef_idx + 1);
   let end = next_fundef_after(insns, fundef_idx + 1).unwrap_or(insns.len());
 
@@ -403,6 +479,22 @@ fn collect_const_defs(insns: &[Insn]) -> HashMap<Symbol, ConstLiteral> {
   out
 }
 
+/// Collects every `Insn::MapTyDef` into map type →
+/// `(key_fmt, val_fmt)`.
+fn collect_map_fmts(insns: &[Insn]) -> HashMap<u32, (u32, u32)> {
+  insns
+    .iter()
+    .filter_map(|insn| match insn {
+      Insn::MapTyDef {
+        map_ty,
+        key_fmt,
+        val_fmt,
+      } => Some((map_ty.0, (*key_fmt, *val_fmt))),
+      _ => None,
+    })
+    .collect()
+}
+
 /// Materializes a [`ConstLiteral`] at a use site. Mirrors the
 /// same CLIF emission used for fresh literal insns so the
 /// inlined form is indistinguishable from a direct literal.
@@ -563,6 +655,7 @@ fn record_value_type(ctx: &mut FunCtx, insn: &Insn) {
     | Insn::TupleIndex { dst, ty_id, .. }
     | Insn::StructConstruct { dst, ty_id, .. }
     | Insn::EnumConstruct { dst, ty_id, .. }
+    | Insn::StrSlice { dst, ty_id, .. }
     | Insn::StringFormat { dst, ty_id, .. } => {
       ctx.value_types.insert(*dst, *ty_id);
     }
@@ -612,6 +705,49 @@ fn emit_aggregate_literal(
   Some(builder.ins().stack_addr(tctx.ptr_ty, slot, 0))
 }
 
+/// Allocates the `sret` slot of a `ret_ty`-returning call in
+/// the current frame and returns its address.
+fn emit_sret_slot(
+  tctx: &TCtx<'_>,
+  builder: &mut FunctionBuilder,
+  ret_ty: TyId,
+) -> ir::Value {
+  let slot = builder.create_sized_stack_slot(StackSlotData::new(
+    StackSlotKind::ExplicitSlot,
+    sret_size(tctx.agg_shapes, ret_ty),
+    AGG_ALIGN_SHIFT,
+  ));
+
+  builder.ins().stack_addr(tctx.ptr_ty, slot, 0)
+}
+
+/// Widens or narrows a scalar argument to the `to` parameter
+/// type of the callee it is passed to.
+fn fit_scalar_arg(
+  builder: &mut FunctionBuilder,
+  value: ir::Value,
+  to: ir::Type,
+  unsigned: bool,
+) -> ir::Value {
+  let from = builder.func.dfg.value_type(value);
+
+  if from == to {
+    value
+  } else if from.is_float() {
+    if to.bits() > from.bits() {
+      builder.ins().fpromote(to, value)
+    } else {
+      builder.ins().fdemote(to, value)
+    }
+  } else if to.bits() < from.bits() {
+    builder.ins().ireduce(to, value)
+  } else if unsigned {
+    builder.ins().uextend(to, value)
+  } else {
+    builder.ins().sextend(to, value)
+  }
+}
+
 /// Widens `idx` to pointer width via `uextend` if it's
 /// narrower; returns unchanged if already pointer-wide. Used by
 /// `ArrayIndex` / `ArrayStore` before computing `base + idx*8`
@@ -649,16 +785,22 @@ fn seal_current_with_jump(
 
 /// Builds a CLIF [`ir::Signature`] from SIR param / return
 /// types. zo's unit type (`TyId(1)`) is omitted from the
-/// returns vec so void functions produce `ret void`.
+/// returns vec so void functions produce `ret void`. With
+/// `sret`, the `sret` slot pointer leads the params.
 fn build_signature(
   params: &[(Symbol, TyId)],
   return_ty: TyId,
+  sret: bool,
   call_conv: CallConv,
   ptr_ty: ir::Type,
   is_main: bool,
 ) -> ir::Signature {
   let mut sig = ir::Signature::new(call_conv);
 
+  if sret {
+    sig.params.push(AbiParam::new(ptr_ty));
+  }
+
   for (_, pty) in params {
     sig.params.push(AbiParam::new(ty_id_to_clif(*pty, ptr_ty)));
   }
@@ -693,7 +835,11 @@ fn translate_body(
   ctx: &mut FunCtx,
   body: &[Insn],
 ) {
-  for insn in body {
+  for (idx, insn) in body.iter().enumerate() {
+    if let Some(debug) = &ctx.debug {
+      debug.set_srcloc(builder, idx);
+    }
+
     // Track the (dst, ty_id) mapping for every
     // value-producing insn so `emit_io_intrinsic` can
     // dispatch `show` / `showln` by argument type. Mirrors
@@ -955,15 +1101,42 @@ fn translate_body(
         };
 
         let idx_ext = widen_to_ptr(builder, idx_v, tctx.ptr_ty);
-        let byte_off = builder.ins().ishl_imm(idx_ext, 3);
-        let addr = builder.ins().iadd(base, byte_off);
-        let elem_ty = ty_id_to_clif(*ty_id, tctx.ptr_ty);
-        let v = builder.ins().load(
-          elem_ty,
-          MemFlags::new(),
-          addr,
-          AGG_SLOT_SIZE as i32,
-        );
+
+        // `s[i]` on a `str` reads one byte of the payload —
+        // inline or, for a slice view, in the parent. Out of
+        // range exits like the ARM backend.
+        let v = if ctx.value_types.get(array).is_some_and(|t| t.0 == 4) {
+          let (len, data) = emit_str_parts(builder, tctx.ptr_ty, base);
+          let in_bounds =
+            builder.ins().icmp(IntCC::UnsignedLessThan, idx_ext, len);
+          let ok_block = builder.create_block();
+          let oob_block = builder.create_block();
+
+          builder.ins().brif(in_bounds, ok_block, &[], oob_block, &[]);
+          builder.switch_to_block(oob_block);
+          emit_exit_1(tctx, builder);
+          builder.switch_to_block(ok_block);
+
+          let addr = builder.ins().iadd(data, idx_ext);
+
+          builder.ins().uload8(
+            ty_id_to_clif(*ty_id, tctx.ptr_ty),
+            MemFlags::new(),
+            addr,
+            0,
+          )
+        } else {
+          let byte_off = builder.ins().ishl_imm(idx_ext, 3);
+          let addr = builder.ins().iadd(base, byte_off);
+          let elem_ty = ty_id_to_clif(*ty_id, tctx.ptr_ty);
+
+          builder.ins().load(
+            elem_ty,
+            MemFlags::new(),
+            addr,
+            AGG_SLOT_SIZE as i32,
+          )
+        };
 
         ctx.values.insert(*dst, v);
       }
@@ -1022,7 +1195,13 @@ fn translate_body(
         };
 
         let len_ty = ty_id_to_clif(*ty_id, tctx.ptr_ty);
-        let v = builder.ins().load(len_ty, MemFlags::new(), base, 0);
+        let mut v = builder.ins().load(len_ty, MemFlags::new(), base, 0);
+
+        // A full-width load also picks up a str slice view's
+        // tag bit (bit 63) — clear it.
+        if len_ty.bits() == 64 {
+          v = builder.ins().band_imm(v, i64::MAX);
+        }
 
         ctx.values.insert(*dst, v);
       }
@@ -1078,7 +1257,11 @@ fn translate_body(
         ctx.values.insert(*dst, v);
       }
       Insn::Call {
-        dst, name, args, ..
+        dst,
+        name,
+        args,
+        ty_id,
+        ..
       } => {
         // Intercept zo's I/O / assertion intrinsics before
         // the normal `declare_func_in_func` path — they have
@@ -1099,6 +1282,12 @@ fn translate_body(
           continue;
         }
 
+        if is_map_intrinsic(name_str) {
+          emit_map_intrinsic(tctx, builder, ctx, *dst, name_str, args, *ty_id);
+
+          continue;
+        }
+
         let Some(func_id) = tctx.func_ids.get(name).copied() else {
           // Callee not in the first-pass declaration table —
           // semantic analyzer shouldn't let this through, but
@@ -1127,9 +1316,17 @@ fn translate_body(
           arg_vals.push(v);
         }
 
+        // An aggregate-returning callee writes its result into
+        // a slot of this frame.
+        if let Some(ret_ty) = tctx.sret_funcs.get(&func_id).copied() {
+          let slot = emit_sret_slot(tctx, builder, ret_ty);
+
+          arg_vals.insert(0, slot);
+        }
+
         // Import the callee's `FuncId` into the current
         // function (cranelift dedupes internally across repeat
-        // imports). Works for both `Linkage::Export` (user-
+        // imports). Works for both `Linkage::Local` (user-
         // defined) and `Linkage::Import` (FFI intrinsics).
         let fref = tctx.module.declare_func_in_func(func_id, builder.func);
 
@@ -1186,6 +1383,14 @@ fn translate_body(
           arg_vals.push(v);
         }
 
+        // Same `sret` rule as the callee's declaration: the
+        // target is a body-bearing function.
+        if tctx.agg_shapes.contains_key(ty_id) {
+          let slot = emit_sret_slot(tctx, builder, *ty_id);
+
+          arg_vals.insert(0, slot);
+        }
+
         // Reconstruct the callee signature from the marshalled
         // CLIF arg types and the SIR return type. zo's unit
         // (`TyId(1)`) yields a void return.
@@ -1216,13 +1421,126 @@ fn translate_body(
 
         ctx.values.insert(*dst, v);
       }
+      Insn::FnAddr { dst, callee, .. } => {
+        // A function's code address as a plain pointer-width
+        // value — the `fn_ptr` half of a `Fn(...)` argument.
+        let Some(func_id) = tctx.func_ids.get(callee).copied() else {
+          emit_exit_1(tctx, builder);
+
+          ctx.terminated = true;
+
+          return;
+        };
+
+        let fref = tctx.module.declare_func_in_func(func_id, builder.func);
+        let addr = builder.ins().func_addr(tctx.ptr_ty, fref);
+
+        ctx.values.insert(*dst, addr);
+      }
+      Insn::ClosureEnv {
+        dst,
+        drop,
+        captures,
+      } => {
+        // Heap block in the array layout — `[len, c0, c1, ...]`
+        // — so the `__env` trampoline reads capture `i` back
+        // with `ArrayIndex`. Unlike `ArrayLiteral` it must not
+        // live in this frame: the closure may run after it
+        // returns (a pool worker, a spawned task). The drop
+        // glue rides one word below `len`.
+        let n = captures.len() as i64;
+        let malloc_fid = ensure_libc_func(tctx, "malloc", |ptr_ty, cc| {
+          let mut sig = ir::Signature::new(cc);
+
+          sig.params.push(AbiParam::new(ptr_ty)); // size
+          sig.returns.push(AbiParam::new(ptr_ty));
+
+          sig
+        });
+        let malloc_fref =
+          tctx.module.declare_func_in_func(malloc_fid, builder.func);
+        let size = builder
+          .ins()
+          .iconst(tctx.ptr_ty, (n + 2) * AGG_SLOT_SIZE as i64);
+        let call = builder.ins().call(malloc_fref, &[size]);
+        let block = builder.inst_results(call)[0];
+        let Some(drop_v) = ctx.values.get(drop).copied() else {
+          emit_exit_1(tctx, builder);
+
+          ctx.terminated = true;
+
+          return;
+        };
+
+        builder.ins().store(MemFlags::new(), drop_v, block, 0);
+
+        let base = builder.ins().iadd_imm(block, AGG_SLOT_SIZE as i64);
+        let len_v = builder.ins().iconst(tctx.ptr_ty, n);
+
+        builder.ins().store(MemFlags::new(), len_v, base, 0);
+
+        for (i, cid) in captures.iter().enumerate() {
+          let Some(v) = ctx.values.get(cid).copied() else {
+            emit_exit_1(tctx, builder);
+
+            ctx.terminated = true;
+
+            return;
+          };
+
+          let offset = (AGG_SLOT_SIZE as i32) * (i as i32 + 1);
+
+          builder.ins().store(MemFlags::new(), v, base, offset);
+        }
+
+        ctx.values.insert(*dst, base);
+      }
+      Insn::ClosureEnvFree { env } => {
+        // The runtime runs the env's drop glue, then frees the
+        // block from the word below it.
+        let Some(env_v) = ctx.values.get(env).copied() else {
+          emit_exit_1(tctx, builder);
+
+          ctx.terminated = true;
+
+          return;
+        };
+
+        let free_fid =
+          ensure_libc_func(tctx, "zo_closure_env_free", |ptr_ty, cc| {
+            let mut sig = ir::Signature::new(cc);
+
+            sig.params.push(AbiParam::new(ptr_ty)); // env
+
+            sig
+          });
+        let free_fref =
+          tctx.module.declare_func_in_func(free_fid, builder.func);
+
+        builder.ins().call(free_fref, &[env_v]);
+      }
       Insn::Return { value, .. } => {
         // Resolve the explicit return value (if any) from the
         // SSA map; fall back to `vec![]`.
-        let explicit: Vec<ir::Value> = value
+        let mut explicit: Vec<ir::Value> = value
           .and_then(|v| ctx.values.get(&v).copied())
           .map_or_else(Vec::new, |v| vec![v]);
 
+        // The value lives in this frame: copy it into the
+        // caller's `sret` slot and hand that back instead.
+        if let (Some((sret, ret_ty)), Some(v)) = (ctx.sret, explicit.first()) {
+          emit_sret_copy(
+            builder,
+            tctx.agg_shapes,
+            tctx.ptr_ty,
+            ret_ty,
+            *v,
+            sret,
+          );
+
+          explicit = vec![sret];
+        }
+
         // `main` must return an `i32` to match the CLIF
         // signature we built (`() -> i32`). If the zo source
         // didn't provide a return value (`fun main()` with
@@ -1331,6 +1649,8 @@ fn translate_body(
           .unwrap_or(declared_ty);
         let var = ctx.declare_local(builder, *name, ty);
 
+        ctx.declare_debug(tctx.interner, *name, *ty_id, false);
+
         if init.is_some() && init_value.is_none() {
           emit_exit_1(tctx, builder);
 
@@ -1340,7 +1660,7 @@ fn translate_body(
         }
 
         if let Some(v) = init_value {
-          builder.def_var(var, v);
+          ctx.def_local(builder, var, v);
         }
       }
       Insn::Store { name, value, .. } => {
@@ -1369,7 +1689,7 @@ fn translate_body(
           }
         };
 
-        builder.def_var(var, v);
+        ctx.def_local(builder, var, v);
       }
       Insn::Load { dst, src, .. } => {
         // Resolution order for `Local(sym)`:
@@ -1406,6 +1726,12 @@ fn translate_body(
 
         let v = builder.use_var(var);
 
+        // A read past a join is a fresh SSA value — label it
+        // too, or the local vanishes from the debugger there.
+        if let Some(debug) = &ctx.debug {
+          debug.label(builder, var, v);
+        }
+
         ctx.values.insert(*dst, v);
       }
       Insn::Cast {
@@ -1462,20 +1788,29 @@ fn translate_body(
             }
           };
 
-          let fid = ensure_libc_func(tctx, name, |ptr_ty, cc| {
-            let mut sig = ir::Signature::new(cc);
-
-            sig.params.push(AbiParam::new(if is_float(*src_ty) {
-              ir::types::F64
-            } else {
-              ir::types::I64
-            }));
-            sig.returns.push(AbiParam::new(ptr_ty));
-
-            sig
-          });
+          let call_conv = tctx.module.target_config().default_call_conv;
+          let param_ty = if is_float(*src_ty) {
+            ir::types::F64
+          } else {
+            ir::types::I64
+          };
+          let mut sig = ir::Signature::new(call_conv);
+
+          sig.params.push(AbiParam::new(param_ty));
+          sig.returns.push(AbiParam::new(tctx.ptr_ty));
+
+          // `core::int` binds `zo_int_to_str` as `ffi` with an
+          // `int` parameter, so the helper may already carry a
+          // narrower signature than the runtime's `i64`. Call
+          // through its address with the runtime's signature
+          // so the value arrives sign- or zero-extended.
+          let fid = ensure_libc_func(tctx, name, |_, _| sig.clone());
+          let arg =
+            fit_scalar_arg(builder, src_v, param_ty, is_unsigned_int(*src_ty));
           let fref = tctx.module.declare_func_in_func(fid, builder.func);
-          let call = builder.ins().call(fref, &[src_v]);
+          let addr = builder.ins().func_addr(tctx.ptr_ty, fref);
+          let sigref = builder.import_signature(sig);
+          let call = builder.ins().call_indirect(sigref, addr, &[arg]);
 
           builder.inst_results(call)[0]
         };
@@ -1517,6 +1852,37 @@ fn translate_body(
 
         ctx.values.insert(*dst, builder.inst_results(call)[0]);
       }
+      Insn::StrSlice {
+        dst, src, lo, hi, ..
+      } => {
+        // `zo_str_slice` (runtime.c) checks the bounds and
+        // char boundaries, then returns a view borrowing
+        // `src`'s bytes.
+        let (Some(&src_v), Some(&lo_v), Some(&hi_v)) =
+          (ctx.values.get(src), ctx.values.get(lo), ctx.values.get(hi))
+        else {
+          emit_exit_1(tctx, builder);
+          ctx.terminated = true;
+          return;
+        };
+
+        let fid = ensure_libc_func(tctx, "zo_str_slice", |ptr_ty, cc| {
+          let mut sig = ir::Signature::new(cc);
+
+          sig.params.push(AbiParam::new(ptr_ty));
+          sig.params.push(AbiParam::new(ptr_ty));
+          sig.params.push(AbiParam::new(ptr_ty));
+          sig.returns.push(AbiParam::new(ptr_ty));
+
+          sig
+        });
+        let lo_ext = widen_to_ptr(builder, lo_v, tctx.ptr_ty);
+        let hi_ext = widen_to_ptr(builder, hi_v, tctx.ptr_ty);
+        let fref = tctx.module.declare_func_in_func(fid, builder.func);
+        let call = builder.ins().call(fref, &[src_v, lo_ext, hi_ext]);
+
+        ctx.values.insert(*dst, builder.inst_results(call)[0]);
+      }
       // Catch-all for insns not yet implemented (`Template`,
       // `ArrayPush`, `ArrayPop`, etc.): exit the process with
       // code 1 and bail out of this body. The module still
@@ -1550,6 +1916,31 @@ fn translate_binop(
   let unsigned = is_unsigned_int(ty_id);
   let fp = is_float(ty_id);
 
+  // `str` equality compares bytes through `zo_str_eq`
+  // (runtime.c), so an owned str and a slice view holding
+  // the same bytes are equal.
+  if ty_id.0 == 4 && matches!(op, BinOp::Eq | BinOp::Neq) {
+    let fid = ensure_libc_func(tctx, "zo_str_eq", |ptr_ty, cc| {
+      let mut sig = ir::Signature::new(cc);
+
+      sig.params.push(AbiParam::new(ptr_ty));
+      sig.params.push(AbiParam::new(ptr_ty));
+      sig.returns.push(AbiParam::new(ptr_ty));
+
+      sig
+    });
+    let fref = tctx.module.declare_func_in_func(fid, builder.func);
+    let call = builder.ins().call(fref, &[l, r]);
+    let equal = builder.inst_results(call)[0];
+    let cc = if op == BinOp::Eq {
+      IntCC::NotEqual
+    } else {
+      IntCC::Equal
+    };
+
+    return builder.ins().icmp_imm(cc, equal, 0);
+  }
+
   match op {
     BinOp::Add => {
       if fp {
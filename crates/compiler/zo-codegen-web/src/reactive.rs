@@ -742,6 +742,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
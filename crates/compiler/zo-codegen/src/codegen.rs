@@ -1,5 +1,7 @@
 use zo_codegen_arm::ARM64Gen;
-use zo_codegen_backend::{Artifact, Backend, LinkObject, Target, Webviewing};
+use zo_codegen_backend::{
+  Artifact, Backend, DebugInfo, LinkObject, Target, Webviewing,
+};
 use zo_codegen_clif::CliftGen;
 use zo_codegen_web::WebGen;
 use zo_interner::{Interner, Symbol};
@@ -39,6 +41,9 @@ pub struct Codegen {
   /// Whether `#render` lowers to the webview runtime entry (wry) rather
   /// than the native one (eframe). Set for a `--target webview` build.
   webviewing: Webviewing,
+  /// Source files and type spellings for DWARF. Set for a
+  /// debug build of a target [`Self::emits_debug_info`].
+  debug_info: Option<DebugInfo>,
 }
 
 impl Codegen {
@@ -47,6 +52,7 @@ impl Codegen {
     Self {
       target,
       webviewing: Webviewing::No,
+      debug_info: None,
     }
   }
 
@@ -56,15 +62,36 @@ impl Codegen {
     self
   }
 
+  /// Emit debug info — DWARF plus the zo type table `zo-dap`
+  /// reads. Needs the type view at `generate` time.
+  pub fn with_debug_info(mut self, debug_info: DebugInfo) -> Self {
+    self.debug_info = Some(debug_info);
+    self
+  }
+
+  /// Whether the backend for `self.target` reads the
+  /// [`DebugInfo`] — only Cranelift writes DWARF and the zo
+  /// type table; ARM64 and web output carry neither.
+  pub const fn emits_debug_info(&self) -> bool {
+    matches!(
+      self.target,
+      Target::X8664AppleDarwin
+        | Target::X8664UnknownLinuxGnu
+        | Target::X8664PcWindowsMsvc
+        | Target::Arm64PcWindowsMsvc
+        | Target::Aarch64LinuxAndroid
+    )
+  }
+
   /// Instantiates the backend matching `self.target`. The
   /// optional `(tys, ty_table)` view enables ARM64Gen's
   /// generic AAPCS FFI fallback — when `Some`, calls to a
   /// `FunctionKind::Intrinsic` symbol that no per-symbol
   /// arm matched are routed through `abi::classify` +
-  /// `emit_ffi_call`. CLIF ignores the view (its FFI path
-  /// uses Cranelift's own ABI lowering).
+  /// `emit_ffi_call`. CLIF only reads the view for debug
+  /// info (its FFI path uses Cranelift's own ABI lowering).
   fn make_backend<'a>(
-    &self,
+    &'a self,
     interner: &'a Interner,
     type_view: Option<(&'a [Ty], &'a TyTable)>,
     abstract_state: Option<AbstractState>,
@@ -89,7 +116,14 @@ impl Codegen {
       | Target::X8664PcWindowsMsvc
       | Target::Arm64PcWindowsMsvc
       | Target::Aarch64LinuxAndroid => {
-        Concrete::Clift(CliftGen::new(interner, self.target))
+        let clift = CliftGen::new(interner, self.target);
+
+        match (&self.debug_info, type_view) {
+          (Some(info), Some((tys, ty_table))) => {
+            Concrete::Clift(clift.with_debug_info(info, tys, ty_table))
+          }
+          _ => Concrete::Clift(clift),
+        }
       }
       Target::Wasm32UnknownUnknown => todo!("wasm backend not yet wired"),
       Target::Web => Concrete::Web(WebGen::new()),
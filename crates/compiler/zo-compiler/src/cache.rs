@@ -0,0 +1,474 @@
+use crate::compiler::Resolution;
+
+use zo_error::Error;
+use zo_error::lint::LintScope;
+use zo_interner::Symbol;
+use zo_module_resolver::{ImportedSymbols, ModuleExports};
+use zo_reporter::Detail;
+use zo_session::Session;
+use zo_sir::Insn;
+use zo_span::Span;
+
+use rustc_hash::FxHashMap as HashMap;
+
+use std::cmp::Reverse;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Most checkpoints kept per [`EnvRoot`] — each holds a whole
+/// session, so the least recently used go first.
+pub(crate) const MAX_CHECKPOINTS: usize = 16;
+
+/// What module resolution hands the user analyzer — the
+/// seed, the merged module SIR and the files read.
+#[derive(Clone)]
+pub(crate) struct ResolvedModules {
+  pub(crate) user_seed: ImportedSymbols,
+  pub(crate) in_scope_packs: Vec<Symbol>,
+  pub(crate) module_sir_instructions: Vec<Insn>,
+  pub(crate) module_sir_spans: Vec<Span>,
+  pub(crate) module_next_value_id: u32,
+  pub(crate) module_next_label_id: u32,
+  pub(crate) pack_paths: HashMap<Symbol, PathBuf>,
+  /// Index 0 is the entry file, as in `DfsCtx::file_table`.
+  pub(crate) file_table: Vec<(PathBuf, String)>,
+}
+
+/// Where a file's modules resolve from. Two files with the
+/// same search paths and `lib.zo` resolve a common prefix of
+/// their top-level `load`s to the very same packs.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct EnvRoot {
+  pub(crate) search_paths: Vec<PathBuf>,
+  /// The sibling `lib.zo`, when there is one.
+  pub(crate) lib: Option<PathBuf>,
+}
+
+/// Diagnostics the packs reported, replayed on each resume.
+#[derive(Clone, Default)]
+pub(crate) struct PackDiagnostics {
+  pub(crate) errors: Vec<Error>,
+  pub(crate) details: Vec<(Error, Detail)>,
+  pub(crate) lint_scopes: Vec<LintScope>,
+}
+
+impl PackDiagnostics {
+  /// Moves what was reported so far in here.
+  pub(crate) fn drain(&mut self) {
+    let (errors, details) = zo_reporter::collect_diagnostics();
+
+    self.errors.extend(errors);
+    self.details.extend(details);
+    self.lint_scopes.extend(zo_reporter::collect_lint_scopes());
+  }
+
+  /// Whether some error points at the entry file.
+  pub(crate) fn points_at_entry(&self) -> bool {
+    self
+      .errors
+      .iter()
+      .any(|error| error.file_id().is_none_or(|id| id == 0))
+  }
+}
+
+/// A snapshot taken once the first `done` top-level `load`s
+/// compiled, before the next one does.
+pub(crate) struct Checkpoint {
+  /// Interner and type checker holding every compiled pack's
+  /// symbols and types. Cloned per resume — the rest of the
+  /// resolution and the entry analysis mutate it.
+  pub(crate) session: Session,
+  pub(crate) resolution: Resolution,
+  /// `lib.zo` packs not compiled yet, read again on resume.
+  pub(crate) pending: Vec<(Symbol, PathBuf)>,
+  /// `Compiler::module_table` as resolution left it.
+  pub(crate) module_table: HashMap<Symbol, ModuleExports>,
+  /// `Compiler::loaded_packs` as resolution left it.
+  pub(crate) loaded_packs: HashMap<Symbol, ModuleExports>,
+  /// Every top-level `load` path of the resolution, preload's
+  /// cascade first, `::`-joined.
+  pub(crate) loads: Vec<String>,
+  /// How many of `loads` preload cascaded.
+  pub(crate) preload_len: usize,
+  /// How many of `loads` compiled.
+  pub(crate) done: usize,
+  pub(crate) diagnostics: PackDiagnostics,
+  /// Every file resolution read.
+  pub(crate) deps: Vec<Dep>,
+}
+
+/// A file a checkpoint read, canonical, with the hash of the
+/// content it read.
+pub(crate) struct Dep {
+  pub(crate) path: PathBuf,
+  pub(crate) hash: u64,
+  /// The file's modification time and length on disk, taken
+  /// around a read that gave back the content hashed — `None`
+  /// when it came from an open buffer, or the file changed
+  /// since. Set by [`ModuleCache::insert`].
+  pub(crate) stamp: Option<Stamp>,
+}
+
+impl Dep {
+  pub(crate) fn new(path: PathBuf, text: &str) -> Self {
+    Self {
+      path,
+      hash: content_hash(text),
+      stamp: None,
+    }
+  }
+}
+
+/// A file's modification time and length — what a lookup
+/// compares instead of reading the file again.
+type Stamp = (SystemTime, u64);
+
+fn stamp(path: &Path) -> Option<Stamp> {
+  let metadata = fs::metadata(path).ok()?;
+
+  Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// The stamp of the file at `path` if it still holds the
+/// content hashed to `hash`. Stat-ed both before and after the
+/// read, so a write racing it leaves no stamp.
+pub(crate) fn stamp_if_unchanged(path: &Path, hash: u64) -> Option<Stamp> {
+  let before = stamp(path)?;
+  let text = fs::read_to_string(path).ok()?;
+
+  (content_hash(&text) == hash && stamp(path) == Some(before)).then_some(before)
+}
+
+impl Checkpoint {
+  /// Whether a resolution of `loads` — the entry file's own
+  /// top-level ones — passes through this checkpoint.
+  fn precedes(&self, loads: &[String]) -> bool {
+    let own = &self.loads[self.preload_len..self.done];
+
+    loads.starts_with(own)
+  }
+
+  /// Whether every file this checkpoint read still holds what
+  /// it read, without reading any: a dependency open in an
+  /// editor compares the hash in `buffered`, one read from
+  /// disk its modification time and length. `stamps` memoizes
+  /// those across checkpoints.
+  fn is_fresh(
+    &self,
+    buffered: &HashMap<PathBuf, u64>,
+    stamps: &mut HashMap<PathBuf, Option<Stamp>>,
+  ) -> bool {
+    self.deps.iter().all(|dep| match buffered.get(&dep.path) {
+      Some(hash) => *hash == dep.hash,
+      None => {
+        let now = stamps
+          .entry(dep.path.clone())
+          .or_insert_with(|| stamp(&dep.path));
+
+        dep.stamp.is_some() && *now == dep.stamp
+      }
+    })
+  }
+}
+
+/// A cached checkpoint and when it was last inserted or
+/// resumed from, on [`ModuleCache::clock`].
+struct Cached {
+  checkpoint: Arc<Checkpoint>,
+  used: u64,
+}
+
+/// One root's checkpoints, by the `loads` they compiled.
+type Checkpoints = HashMap<Vec<String>, Cached>;
+
+/// The text of a file open in an editor, and its hash.
+struct Buffer {
+  text: Arc<str>,
+  hash: u64,
+}
+
+/// Open buffers by canonical path.
+type Buffers = HashMap<PathBuf, Buffer>;
+
+/// Module resolution shared across analyses.
+///
+/// Re-analyzing a file normally resolves every pack it loads
+/// — preload and the whole core cascade — from scratch. With
+/// a cache set on the [`Compiler`](crate::Compiler), the
+/// resolution leaves a checkpoint after the preload cascade
+/// and after each top-level `load`, and a later analysis
+/// resumes from the furthest one its own `load`s pass
+/// through: editing a file's last `load` only compiles that
+/// pack again. Each root keeps at most `MAX_CHECKPOINTS`.
+///
+/// Staleness is mostly pushed in: [`ModuleCache::invalidate`],
+/// [`ModuleCache::created`] and the buffer calls drop the
+/// checkpoints a change affects. A lookup reads no file — it
+/// only checks that each dependency read from disk kept its
+/// modification time and length.
+///
+/// Files open in an editor are read from their buffers, given
+/// through [`ModuleCache::open`], rather than from disk.
+#[derive(Default)]
+pub struct ModuleCache {
+  roots: Mutex<HashMap<EnvRoot, Checkpoints>>,
+  buffers: Mutex<Buffers>,
+  clock: AtomicU64,
+}
+
+impl ModuleCache {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The furthest checkpoint under `root` a resolution of
+  /// `loads` passes through. Checkpoints whose files changed
+  /// are dropped on the way.
+  pub(crate) fn resume(
+    &self,
+    root: &EnvRoot,
+    loads: &[String],
+  ) -> Option<Arc<Checkpoint>> {
+    let mut candidates = self
+      .roots
+      .lock()
+      .ok()?
+      .get(root)?
+      .iter()
+      .filter(|(_, cached)| cached.checkpoint.precedes(loads))
+      .map(|(key, cached)| (key.clone(), Arc::clone(&cached.checkpoint)))
+      .collect::<Vec<_>>();
+
+    candidates.sort_by_key(|(_, checkpoint)| Reverse(checkpoint.done));
+
+    // Files are stat-ed with neither lock held, so other
+    // analyses aren't held up meanwhile.
+    let buffered = self.buffered_hashes()?;
+    let mut stamps = HashMap::default();
+    let mut stale = Vec::new();
+    let fresh = candidates.into_iter().find(|(key, checkpoint)| {
+      let fresh = checkpoint.is_fresh(&buffered, &mut stamps);
+
+      if !fresh {
+        stale.push((key.clone(), Arc::clone(checkpoint)));
+      }
+
+      fresh
+    });
+
+    let mut roots = self.roots.lock().ok()?;
+    let checkpoints = roots.get_mut(root)?;
+
+    // A checkpoint inserted under the same key meanwhile is
+    // newer than the one found stale, and stays.
+    for (key, checkpoint) in stale {
+      if checkpoints
+        .get(&key)
+        .is_some_and(|cached| Arc::ptr_eq(&cached.checkpoint, &checkpoint))
+      {
+        checkpoints.remove(&key);
+      }
+    }
+
+    let (key, checkpoint) = fresh?;
+
+    if let Some(cached) = checkpoints.get_mut(&key) {
+      cached.used = self.tick();
+    }
+
+    Some(checkpoint)
+  }
+
+  /// Caches `checkpoint` under `root`, evicting the least
+  /// recently used checkpoints past `MAX_CHECKPOINTS`.
+  pub(crate) fn insert(&self, root: EnvRoot, mut checkpoint: Checkpoint) {
+    let used = self.tick();
+
+    // The stamp is only kept when the file still holds the
+    // content the resolution parsed — an edit since it was read
+    // must not pass for fresh.
+    if let Some(buffered) = self.buffered_hashes() {
+      for dep in &mut checkpoint.deps {
+        if !buffered.contains_key(&dep.path) {
+          dep.stamp = stamp_if_unchanged(&dep.path, dep.hash);
+        }
+      }
+    }
+
+    if let Ok(mut roots) = self.roots.lock() {
+      let checkpoints = roots.entry(root).or_default();
+
+      checkpoints.insert(
+        checkpoint.loads[..checkpoint.done].to_vec(),
+        Cached {
+          checkpoint: Arc::new(checkpoint),
+          used,
+        },
+      );
+
+      while checkpoints.len() > MAX_CHECKPOINTS {
+        let Some(oldest) = checkpoints
+          .iter()
+          .min_by_key(|(_, cached)| cached.used)
+          .map(|(key, _)| key.clone())
+        else {
+          break;
+        };
+
+        checkpoints.remove(&oldest);
+      }
+    }
+  }
+
+  /// The hash of every open buffer, copied out so the lock
+  /// isn't held while files are stat-ed or read.
+  fn buffered_hashes(&self) -> Option<HashMap<PathBuf, u64>> {
+    let buffers = self.buffers.lock().ok()?;
+
+    Some(
+      buffers
+        .iter()
+        .map(|(path, buffer)| (path.clone(), buffer.hash))
+        .collect(),
+    )
+  }
+
+  fn tick(&self) -> u64 {
+    self.clock.fetch_add(1, Ordering::Relaxed)
+  }
+
+  /// Drops every checkpoint that read the file at `path` with
+  /// other content than it holds now — its open buffer, else
+  /// the file on disk, which changed or went away. Returns how
+  /// many were dropped.
+  pub fn invalidate(&self, path: &Path) -> usize {
+    let path = canonical(path);
+    let buffered = self
+      .buffers
+      .lock()
+      .ok()
+      .and_then(|buffers| buffers.get(&path).map(|buffer| buffer.hash));
+    let hash = buffered.or_else(|| {
+      fs::read_to_string(&path)
+        .ok()
+        .map(|text| content_hash(&text))
+    });
+
+    self.drop_where(|_, checkpoint| {
+      checkpoint
+        .deps
+        .iter()
+        .any(|dep| dep.path == path && hash != Some(dep.hash))
+    })
+  }
+
+  /// Reads the file at `path` from `text` — an editor buffer
+  /// with unsaved edits — until [`ModuleCache::close`], and
+  /// drops the checkpoints that read other content for it.
+  /// Returns how many were dropped.
+  pub fn open(&self, path: &Path, text: &str) -> usize {
+    let path = canonical(path);
+    let buffer = Buffer {
+      text: Arc::from(text),
+      hash: content_hash(text),
+    };
+
+    if let Ok(mut buffers) = self.buffers.lock() {
+      buffers.insert(path.clone(), buffer);
+    }
+
+    self.invalidate(&path)
+  }
+
+  /// Reads the file at `path` from disk again, dropping the
+  /// checkpoints that read it from its buffer. Returns how many
+  /// were dropped.
+  pub fn close(&self, path: &Path) -> usize {
+    let path = canonical(path);
+
+    if let Ok(mut buffers) = self.buffers.lock() {
+      buffers.remove(&path);
+    }
+
+    self.drop_where(|_, checkpoint| {
+      checkpoint
+        .deps
+        .iter()
+        .any(|dep| dep.path == path && dep.stamp.is_none())
+    })
+  }
+
+  /// The open buffers' text by canonical path, for a
+  /// resolution to read in place of the files.
+  pub(crate) fn buffers(&self) -> HashMap<PathBuf, Arc<str>> {
+    self
+      .buffers
+      .lock()
+      .map(|buffers| {
+        buffers
+          .iter()
+          .map(|(path, buffer)| (path.clone(), Arc::clone(&buffer.text)))
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+
+  /// Drops every checkpoint a new file at `path` may change
+  /// — one whose search paths or `lib.zo` directory hold it,
+  /// since it can shadow a module resolved further down.
+  /// Returns how many were dropped.
+  pub fn created(&self, path: &Path) -> usize {
+    let path = canonical(path);
+
+    self.drop_where(|root, _| {
+      root
+        .search_paths
+        .iter()
+        .map(PathBuf::as_path)
+        .chain(root.lib.iter().filter_map(|lib| lib.parent()))
+        .any(|dir| path.starts_with(canonical(dir)))
+    })
+  }
+
+  /// Drops every checkpoint.
+  pub fn clear(&self) {
+    if let Ok(mut roots) = self.roots.lock() {
+      roots.clear();
+    }
+  }
+
+  fn drop_where(&self, stale: impl Fn(&EnvRoot, &Checkpoint) -> bool) -> usize {
+    let Ok(mut roots) = self.roots.lock() else {
+      return 0;
+    };
+
+    let mut dropped = 0;
+
+    for (root, checkpoints) in roots.iter_mut() {
+      let before = checkpoints.len();
+
+      checkpoints.retain(|_, cached| !stale(root, &cached.checkpoint));
+      dropped += before - checkpoints.len();
+    }
+
+    roots.retain(|_, checkpoints| !checkpoints.is_empty());
+
+    dropped
+  }
+}
+
+/// The hash a dependency's content is recorded under.
+pub(crate) fn content_hash(text: &str) -> u64 {
+  let mut hasher = DefaultHasher::new();
+
+  text.hash(&mut hasher);
+  hasher.finish()
+}
+
+pub(crate) fn canonical(path: &Path) -> PathBuf {
+  fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -1,3 +1,7 @@
+use crate::cache::{
+  Checkpoint, Dep, EnvRoot, ModuleCache, PackDiagnostics, ResolvedModules,
+  canonical, content_hash,
+};
 use crate::constants::{
   ANALYZER_NAME, CODEGEN_NAME, LINKER_NAME, PARSER_NAME, RESOLVER_NAME,
   TOKENIZER_NAME,
@@ -8,30 +12,32 @@ use crate::stage::Stage;
 use zo_analyzer::{Analyzer, AnalyzerConfig, SemanticResult};
 use zo_bundler::ios;
 use zo_codegen::codegen::Codegen;
-use zo_codegen_backend::{Target, Webviewing};
+use zo_codegen_backend::{DebugInfo, Target, Webviewing};
 use zo_dce::Dce;
+use zo_error::lint::LintLevels;
 use zo_error::{Error, ErrorKind, Severity};
 use zo_inline::{Inline, Release};
 use zo_interner::Symbol;
 use zo_module_resolver::{
   ImportedSymbols, ModuleExports, ModuleResolver, extract_exports,
-  splice_component_bodies, splice_generic_bodies,
+  splice_abstract_defaults, splice_component_bodies, splice_generic_bodies,
 };
-use zo_ownership::Ownership;
+use zo_ownership::{Ownership, OwnershipTrace};
 use zo_parser::{Parser, ParsingResult};
 use zo_pp::PrettyPrinter;
 use zo_profiler::Profiler;
 use zo_reporter::{
-  DiagnosticFormat, ErrorAggregator, ErrorRenderer, RenderConfig, Reporter,
-  json, rationale, report_error, xml,
+  Detail, DiagnosticFormat, ErrorAggregator, ErrorRenderer, RenderConfig,
+  Reporter, json, rationale, report_error, sarif, xml,
 };
 use zo_session::Session;
 use zo_sir::{Insn, Sir};
+use zo_sir_parser::SirParser;
 use zo_span::Span;
 use zo_token::{LiteralStoreBaseline, Token};
 use zo_tokenizer::{TokenizationResult, Tokenizer};
 use zo_tree::{NodeValue, Tree, TreeBaseline};
-use zo_ty::{Mutability, SelfKind};
+use zo_ty::{Mutability, SelfKind, TyId};
 use zo_value::ValueId;
 use zo_value::{AutoDrop, FunctionKind, Local, LocalKind, Pubness};
 
@@ -40,6 +46,8 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Auto-detect the system-pack search paths so every caller of
 /// `Compiler::new()` (the zo CLI driver, the fret build
@@ -87,6 +95,22 @@ struct PendingPack {
 /// the transitive-load closure.
 pub type LoadRef = zo_span::Spanned<Vec<Symbol>>;
 
+/// The `::`-joined paths of `loads` — comparable across
+/// sessions, unlike their symbols.
+fn load_names(loads: &[LoadRef], session: &Session) -> Vec<String> {
+  loads
+    .iter()
+    .map(|load| {
+      load
+        .value
+        .iter()
+        .map(|sym| session.interner.get(*sym))
+        .collect::<Vec<_>>()
+        .join("::")
+    })
+    .collect()
+}
+
 /// File-as-pack rule: returns the implicit pack identity
 /// for a loaded module file, or `None` when the file is a
 /// package manifest (`lib.zo`) or a binary entry
@@ -148,7 +172,7 @@ fn implicit_pack_for(path: &Path, search_paths: &[PathBuf]) -> Option<String> {
 pub struct DiagnosticsConfig {
   /// Which renderer materialises diagnostics — `Human`
   /// (ariadne snippets on stderr) or a machine format
-  /// (`Json` / `Xml` on stdout).
+  /// (`Json` / `Xml` / `Sarif` on stdout).
   pub format: DiagnosticFormat,
   /// Number of source lines of context to inline in each
   /// machine diagnostic's `snippet`. Ignored for the human
@@ -218,6 +242,9 @@ struct Lowering<'a> {
   emit_asm: Option<&'a Path>,
   /// The compilation target.
   target: Target,
+  /// Every source file the program was compiled from, entry
+  /// first — for debug info.
+  sources: &'a [(PathBuf, String)],
 }
 
 /// Represents a [`Compiler`] instance.
@@ -231,6 +258,17 @@ pub struct Compiler {
   /// Modules declared via `pub pack` in lib.zo.
   /// Populated during pack compilation, queried by `load`.
   module_table: HashMap<Symbol, ModuleExports>,
+  /// Exports of every lib.zo pack a `load` took out of
+  /// `module_table`, their SIR moved into the resolution.
+  loaded_packs: HashMap<Symbol, ModuleExports>,
+  /// Resolved modules shared with other compilers. When set,
+  /// an analysis whose `load`s were resolved before skips
+  /// re-analyzing the packs.
+  module_cache: Option<Arc<ModuleCache>>,
+  /// Raised by another thread to abandon an analysis. Checked
+  /// before each pack compiles and before the entry file is
+  /// analyzed.
+  cancel: Option<Arc<AtomicBool>>,
   /// Which renderer materialises diagnostics. The driver
   /// sets this from the `--format` CLI flag; library callers
   /// (fret build pipeline, tests) leave it `Human`. The
@@ -250,9 +288,23 @@ pub struct Compiler {
   /// Whether to suppress the build-status banner (the `--quiet`
   /// flag). Errors and diagnostics still print.
   quiet: bool,
+  /// Configured lint levels — the project table and CLI
+  /// flags set by the caller, plus the `%% allow(..).`
+  /// scopes collected while analyzing. Resolved at report
+  /// time; diagnostics are stored at their default severity.
+  lint_levels: LintLevels,
   /// When `true`, `test fun` functions are pinned as DCE
   /// roots so the synthesized test harness can call them.
   test_mode: bool,
+  /// When `true`, the ownership pass records its move facts
+  /// and drop decisions into `ownership_trace` (`--emit
+  /// ownership`).
+  trace_ownership: bool,
+  /// When `true`, the entry file's analysis records the type
+  /// of every value-producing node (`--emit=types`, zo-lsp).
+  record_node_tys: bool,
+  /// The trace of the last analysis, under `trace_ownership`.
+  ownership_trace: Option<OwnershipTrace>,
   /// Whether `#render` lowers to the webview runtime entry (wry) rather
   /// than the native one (eframe). Set by the driver for a
   /// `--target webview` build; native and webview share a host triple,
@@ -387,12 +439,7 @@ fn fold_imports_into(into: &mut ImportedSymbols, other: &ImportedSymbols) {
 
 /// Converts the harvested `ModuleExports` (pub items only)
 /// into an `ImportedSymbols` value. Each var becomes a
-/// `Local` with `Pubness::Yes` and `Mutability::No`. The
-/// caller is responsible for filling `abstract_defs` from
-/// the analyzer's `SemanticResult` — kept out of the
-/// signature so zo-compiler never has to name a type owned
-/// by zo-executor (layering stays clean: compiler talks to
-/// zo-analyzer, zo-analyzer controls zo-executor).
+/// `Local` with `Pubness::Yes` and `Mutability::No`.
 fn module_exports_to_imports(exports: &ModuleExports) -> ImportedSymbols {
   let mut vars = Vec::with_capacity(exports.vars.len());
   let mut var_literals = Vec::with_capacity(exports.vars.len());
@@ -420,12 +467,13 @@ fn module_exports_to_imports(exports: &ModuleExports) -> ImportedSymbols {
     var_literals,
     enums: exports.enums.clone(),
     structs: exports.structs.clone(),
-    abstract_defs: HashMap::default(),
+    abstract_defs: exports.abstract_defs.clone(),
     abstract_impls: exports.abstract_impls.clone(),
     exported_generic_bodies: exports.generic_bodies.clone(),
     exported_component_bodies: exports.component_bodies.clone(),
     generic_bodies: Vec::new(),
     component_bodies: Vec::new(),
+    abstract_default_bodies: Vec::new(),
   }
 }
 
@@ -548,6 +596,9 @@ struct DfsCtx {
   /// declaration" vs "unresolved module" diagnostic
   /// disambiguation.
   has_lib_zo: bool,
+  /// The hash of the `lib.zo` source parsed at discovery —
+  /// the content a checkpoint records for it.
+  lib_hash: Option<u64>,
   /// Packs declared `pack foo;` (no `pub`) in lib.zo.
   /// Used to emit `PrivatePackInLoad` rather than
   /// `ModuleNotDeclared` for accidental loads.
@@ -588,6 +639,7 @@ impl DfsCtx {
       module_next_label_id: 0,
       system_pack_roots: HashSet::default(),
       has_lib_zo: false,
+      lib_hash: None,
       private_packs: HashSet::default(),
       folder_packs: HashSet::default(),
       pending_packs: HashMap::default(),
@@ -614,6 +666,60 @@ impl DfsCtx {
   }
 }
 
+impl DfsCtx {
+  /// A copy without the parse caches — `pending_packs` and
+  /// `parse_cache` hold trees, which a copy reads anew.
+  fn frozen(&self) -> Self {
+    Self {
+      imports: self.imports.clone(),
+      module_table_per_path: self.module_table_per_path.clone(),
+      folder_aggregations: self.folder_aggregations.clone(),
+      parse_cache: HashMap::default(),
+      module_sir_instructions: self.module_sir_instructions.clone(),
+      module_sir_spans: self.module_sir_spans.clone(),
+      module_next_value_id: self.module_next_value_id,
+      module_next_label_id: self.module_next_label_id,
+      system_pack_roots: self.system_pack_roots.clone(),
+      has_lib_zo: self.has_lib_zo,
+      lib_hash: self.lib_hash,
+      private_packs: self.private_packs.clone(),
+      folder_packs: self.folder_packs.clone(),
+      pending_packs: HashMap::default(),
+      pack_paths: self.pack_paths.clone(),
+      file_table: self.file_table.clone(),
+      file_id_by_path: self.file_id_by_path.clone(),
+    }
+  }
+}
+
+/// Module resolution in flight — the DFS state plus what the
+/// preload pass hands the user-seed assembly.
+pub(crate) struct Resolution {
+  ctx: DfsCtx,
+  /// Preload's cascade, then the entry file's top-level
+  /// `load`s.
+  module_paths: Vec<LoadRef>,
+  /// How many of `module_paths` preload cascaded.
+  preload_len: usize,
+  /// Frozen snapshot of the user file's top-level `load`s.
+  user_top_loads: Vec<LoadRef>,
+  preload_own: ImportedSymbols,
+  preload_re_exports: Vec<Vec<Symbol>>,
+}
+
+impl Resolution {
+  fn frozen(&self) -> Self {
+    Self {
+      ctx: self.ctx.frozen(),
+      module_paths: self.module_paths.clone(),
+      preload_len: self.preload_len,
+      user_top_loads: self.user_top_loads.clone(),
+      preload_own: self.preload_own.clone(),
+      preload_re_exports: self.preload_re_exports.clone(),
+    }
+  }
+}
+
 impl Compiler {
   /// Creates a new [`Compiler`] instance with the auto-
   /// Module resolver search paths (core lib + input dir).
@@ -632,10 +738,17 @@ impl Compiler {
       module_resolver: ModuleResolver::new(default_core_search_paths()),
       compiling: HashSet::default(),
       module_table: HashMap::default(),
+      loaded_packs: HashMap::default(),
+      module_cache: None,
+      cancel: None,
       emit_format: DiagnosticFormat::Human,
       snippet_context: 2,
       use_colors: true,
       quiet: false,
+      lint_levels: LintLevels::new(),
+      trace_ownership: false,
+      record_node_tys: false,
+      ownership_trace: None,
       test_mode: false,
       webviewing: Webviewing::No,
       release: Release::No,
@@ -652,10 +765,17 @@ impl Compiler {
       module_resolver: ModuleResolver::new(search_paths),
       compiling: HashSet::default(),
       module_table: HashMap::default(),
+      loaded_packs: HashMap::default(),
+      module_cache: None,
+      cancel: None,
       emit_format: DiagnosticFormat::Human,
       snippet_context: 2,
       use_colors: true,
       quiet: false,
+      lint_levels: LintLevels::new(),
+      trace_ownership: false,
+      record_node_tys: false,
+      ownership_trace: None,
       test_mode: false,
       webviewing: Webviewing::No,
       release: Release::No,
@@ -666,6 +786,34 @@ impl Compiler {
     self.test_mode = enabled;
   }
 
+  /// Records the type of every value-producing node of the
+  /// entry file into [`SemanticResult::node_tys`].
+  pub fn set_node_tys(&mut self, enabled: bool) {
+    self.record_node_tys = enabled;
+  }
+
+  /// Shares `cache` with this compiler — see [`ModuleCache`].
+  /// Modules open in `cache`'s buffers are read from there.
+  pub fn set_module_cache(&mut self, cache: Arc<ModuleCache>) {
+    self.module_resolver.set_overlay(cache.buffers());
+    self.module_cache = Some(cache);
+  }
+
+  /// Abandons the analysis once `flag` is raised. What a
+  /// cancelled [`Self::analyze_source`] returns is incomplete
+  /// — the caller discards it.
+  pub fn set_cancel(&mut self, flag: Arc<AtomicBool>) {
+    self.cancel = Some(flag);
+  }
+
+  /// Whether the flag set by [`Self::set_cancel`] is raised.
+  pub fn is_cancelled(&self) -> bool {
+    self
+      .cancel
+      .as_ref()
+      .is_some_and(|flag| flag.load(Ordering::Relaxed))
+  }
+
   /// Select the webview runtime entry for `#render` lowering. The
   /// driver sets this for a `--target webview` build so the emitted
   /// binary calls `_zo_run_web` (wry) instead of `_zo_run_native`.
@@ -678,11 +826,53 @@ impl Compiler {
     self.release = release;
   }
 
+  /// Sets the global lint levels — the `fret.oz` table and the
+  /// `-W/-A/-D` / `--deny-warnings` flags, already merged in
+  /// precedence order by the caller. Attribute scopes found
+  /// while analyzing are layered on top.
+  pub fn set_lint_levels(&mut self, levels: LintLevels) {
+    self.lint_levels = levels;
+  }
+
   /// Collected errors from the last compilation.
   pub fn reporter_errors(&self) -> &[zo_error::Error] {
     self.reporter.errors()
   }
 
+  /// Pub exports of every lib.zo pack loaded so far, keyed by
+  /// pack name — what a `pack::` path resolves against.
+  pub fn module_exports(&self) -> &HashMap<Symbol, ModuleExports> {
+    &self.loaded_packs
+  }
+
+  /// Dynamic detail (type names, suggestions) attached to the
+  /// collected errors, keyed by the error it annotates.
+  pub fn reporter_details(&self) -> &[(zo_error::Error, Detail)] {
+    self.reporter.details()
+  }
+
+  /// Groups the collected diagnostics for rendering, resolved
+  /// against the lint levels: allowed lints are dropped and
+  /// denied ones carry their promoted severity.
+  pub fn diagnostics(&self) -> ErrorAggregator {
+    let mut aggregator = ErrorAggregator::new();
+
+    aggregator
+      .add_errors_with_levels(self.reporter.errors(), &self.lint_levels);
+    aggregator.add_details(self.reporter.details());
+
+    aggregator
+  }
+
+  /// Whether any collected diagnostic is a hard error once
+  /// lint levels apply — a denied lint counts, an allowed one
+  /// never does.
+  fn has_hard_error(&self) -> bool {
+    self.reporter.errors().iter().any(|error| {
+      matches!(self.lint_levels.severity(error), Some(Severity::Error))
+    })
+  }
+
   /// Applies a [`DiagnosticsConfig`] to this compiler. Bound
   /// from the driver's `--format` / `--snippet-context` /
   /// `--explain-decisions` flags in lockstep. State lands in
@@ -817,6 +1007,34 @@ impl Compiler {
     packs
   }
 
+  /// Parses a textual SIR program (`zo build foo.sir`) into a
+  /// semantic result the backend can lower directly. Returns
+  /// `None` — with the error collected — when the text does
+  /// not follow the SIR grammar.
+  fn analyze_sir(&mut self, source: &str) -> Option<(SemanticResult, Session)> {
+    self.profiler.start_phase(PARSER_NAME);
+    let mut session = Session::new();
+    let parsed =
+      SirParser::new(source, &mut session.interner, &mut session.ty_checker)
+        .parse();
+    self.profiler.end_phase(PARSER_NAME);
+
+    match parsed {
+      Ok(sir) => Some((
+        SemanticResult {
+          sir,
+          ..SemanticResult::default()
+        },
+        session,
+      )),
+      Err(error) => {
+        self.reporter.collect_errors(&[error]);
+
+        None
+      }
+    }
+  }
+
   /// Analyzes a single source file with full module resolution.
   /// Returns the semantic result and tokenization for further
   /// processing (codegen or runtime execution).
@@ -842,8 +1060,246 @@ impl Compiler {
     let mut parsing = parser.parse();
     self.profiler.end_phase(PARSER_NAME);
 
+    let resolved = match self.module_cache.clone() {
+      Some(cache) => self.resolve_cached(
+        &cache,
+        &mut session,
+        &mut tokenization,
+        &mut parsing,
+        source,
+        file_path,
+      ),
+      None => {
+        self.resolve_modules(&mut session, &parsing.tree, source, file_path)
+      }
+    };
+
+    if self.is_cancelled() {
+      return (
+        SemanticResult::default(),
+        tokenization,
+        parsing,
+        session,
+        resolved.file_table,
+      );
+    }
+
+    // Analyze with imported symbols pre-loaded.
+    self.profiler.start_phase(ANALYZER_NAME);
+
+    let ResolvedModules {
+      mut user_seed,
+      in_scope_packs,
+      mut module_sir_instructions,
+      module_sir_spans,
+      module_next_value_id,
+      module_next_label_id,
+      pack_paths,
+      file_table,
+    } = resolved;
+
+    // Entry programs adopt their file stem as pack identity
+    // so top-level `#link { ... }` resolves to a `PackLink`.
+    let implicit_sym = file_path
+      .file_stem()
+      .and_then(|s| s.to_str())
+      .map(|stem| session.interner.intern(stem));
+
+    user_seed.generic_bodies = splice_generic_bodies(
+      &mut parsing.tree,
+      &mut tokenization.literals,
+      std::mem::take(&mut user_seed.exported_generic_bodies),
+    );
+    user_seed.component_bodies = splice_component_bodies(
+      &mut parsing.tree,
+      &mut tokenization.literals,
+      std::mem::take(&mut user_seed.exported_component_bodies),
+    );
+    user_seed.abstract_default_bodies = splice_abstract_defaults(
+      &mut parsing.tree,
+      &mut tokenization.literals,
+      &user_seed.abstract_defs,
+    );
+
+    let analyzer = Analyzer::new(
+      &parsing.tree,
+      &mut session.interner,
+      &tokenization.literals,
+      &mut session.ty_checker,
+    );
+
+    // The user file's `<img src="…">` and similar
+    // path-typed attributes are resolved against this
+    // directory at attribute-build time — so the
+    // compiled binary holds absolute paths and renders
+    // assets regardless of CWD at run time.
+    let mut semantic = analyzer
+      .with_config(AnalyzerConfig {
+        imports: user_seed,
+        source_dir: file_path.parent().map(Path::to_path_buf),
+        source_path: Some(file_path.to_path_buf()),
+        implicit_pack: implicit_sym,
+        in_scope_packs,
+        is_entry: true,
+        test_mode: self.test_mode,
+        file_id: 0,
+        node_tys: self.record_node_tys,
+      })
+      .analyze();
+    self.profiler.end_phase(ANALYZER_NAME);
+
+    // Merge module SIR into main SIR. Modules must appear
+    // before main so their FunDefs are registered before
+    // main calls them. All ValueIds are explicit and
+    // offsettable — no implicit/explicit mismatch.
+    if !module_sir_instructions.is_empty() {
+      let main_next_vid = semantic.sir.next_value_id;
+      let main_next_lid = semantic.sir.next_label_id;
+
+      Sir::offset_value_ids(&mut module_sir_instructions, main_next_vid);
+      // Shift module labels above main's own label range
+      // (main uses `[0, main_next_lid)`). Per-pack offset
+      // above already ensures module labels don't collide
+      // with one another; this just lifts the whole module
+      // block above main's labels for the merged stream.
+      Sir::offset_labels(&mut module_sir_instructions, main_next_lid);
+
+      // Prepend: modules first, then main. Spans mirror the
+      // same prepend so the merged SIR stays aligned 1:1.
+      let main_insns = std::mem::replace(
+        &mut semantic.sir.instructions,
+        module_sir_instructions,
+      );
+      let main_spans =
+        std::mem::replace(&mut semantic.sir.spans, module_sir_spans);
+
+      semantic.sir.instructions.extend(main_insns);
+      semantic.sir.spans.extend(main_spans);
+      semantic.sir.next_value_id += module_next_value_id;
+      semantic.sir.next_label_id += module_next_label_id;
+    }
+
+    // Build DCE roots: main + abstract-impl methods +
+    // test functions (when test_mode is active).
+    let main_sym = session.interner.intern("main");
+    let mut dce_roots = vec![main_sym];
+
+    for im in semantic.abstract_impls.values() {
+      dce_roots.extend(im.methods.iter().copied());
+    }
+
+    if self.test_mode {
+      for insn in &semantic.sir.instructions {
+        if let Insn::FunDef {
+          name,
+          is_test: true,
+          ..
+        } = insn
+        {
+          dce_roots.push(*name);
+        }
+      }
+    }
+
+    // Keep every destructor (`own self` method) alive through
+    // DCE even when no explicit `.free()` calls it: the only
+    // other caller is the compiler-inserted scope-exit drop the
+    // ownership pass emits AFTER DCE, so without this the
+    // destructor is pruned as dead and auto-drop silently leaks.
+    for insn in &semantic.sir.instructions {
+      if let Insn::FunDef {
+        name,
+        self_kind: zo_ty::SelfKind::Consume,
+        ..
+      } = insn
+      {
+        dce_roots.push(*name);
+      }
+    }
+
+    // TODO: move all these stages in analyzer.
+    Dce::new(&mut semantic.sir, dce_roots, &session.interner).eliminate();
+    Inline::new(
+      &mut semantic.sir,
+      &mut session.interner,
+      session.ty_checker.tys(),
+      self.release,
+    )
+    .inline();
+    let mut ownership =
+      Ownership::new(&mut semantic.sir, &session.interner, &session.ty_checker);
+
+    if self.trace_ownership {
+      ownership = ownership.with_trace();
+    }
+
+    ownership.check();
+    self.ownership_trace = ownership.into_trace();
+
+    // Single drain after every analyze-time pass (analyzer,
+    // module loads, DCE). One TLS access, not one per pass.
+    let (tl_errors, tl_details) = zo_reporter::collect_diagnostics();
+    if !tl_errors.is_empty() {
+      self.reporter.collect_errors(&tl_errors);
+      self.reporter.collect_details(tl_details);
+    }
+
+    self
+      .lint_levels
+      .extend_scopes(zo_reporter::collect_lint_scopes());
+
+    semantic.pack_paths = pack_paths;
+
+    (semantic, tokenization, parsing, session, file_table)
+  }
+
+  /// Resolves and compiles every module the entry file loads —
+  /// preload, its cascade and the file's own `load`s — into
+  /// `session`, and builds the entry analyzer's seed from them.
+  fn resolve_modules(
+    &mut self,
+    session: &mut Session,
+    tree: &Tree,
+    source: &str,
+    file_path: &Path,
+  ) -> ResolvedModules {
     self.profiler.start_phase(RESOLVER_NAME);
 
+    let mut resolution =
+      self.begin_resolution(session, tree, source, file_path);
+
+    // Recursive DFS entry — each top-level load expands
+    // its dependency graph post-order via the call stack.
+    // `module_paths` here is preload's cascade + the user
+    // file's top-level loads, in that order; the
+    // recursion's `module_table_per_path` short-circuit
+    // keeps re-export hits cheap.
+    for module_ref in resolution.module_paths.clone() {
+      self.compile_module_recursive(
+        &mut resolution.ctx,
+        session,
+        module_ref.value,
+        module_ref.span,
+      );
+    }
+
+    let resolved = Self::finish_resolution(resolution);
+
+    self.profiler.end_phase(RESOLVER_NAME);
+
+    resolved
+  }
+
+  /// Everything resolution does before the first top-level
+  /// `load` compiles — `lib.zo` discovery and the preload
+  /// pass.
+  fn begin_resolution(
+    &mut self,
+    session: &mut Session,
+    tree: &Tree,
+    source: &str,
+    file_path: &Path,
+  ) -> Resolution {
     let mut ctx = DfsCtx::new();
 
     ctx.register_file(file_path.to_path_buf(), source.to_string());
@@ -867,7 +1323,10 @@ impl Compiler {
     // are resolved lazily through the regular filesystem
     // path.
     ctx.has_lib_zo = if let Some(lib_path) = Self::discover_lib(file_path) {
-      let lib_source = fs::read_to_string(&lib_path).unwrap_or_default();
+      let lib_source = self.module_resolver.read(&lib_path).unwrap_or_default();
+
+      ctx.lib_hash = Some(content_hash(&lib_source));
+
       let lib_tokenization =
         Tokenizer::new(&lib_source, &mut session.interner).tokenize();
       let lib_parsing = Parser::new(&lib_tokenization, &lib_source).parse();
@@ -889,23 +1348,9 @@ impl Compiler {
             continue;
           }
 
-          let pack_source = fs::read_to_string(&pack_file).unwrap_or_default();
-          let pack_tok =
-            Tokenizer::new(&pack_source, &mut session.interner).tokenize();
-          let pack_par = Parser::new(&pack_tok, &pack_source).parse();
-          let pack_loads =
-            Self::scan_loads(&pack_par.tree, &mut session.interner);
-
-          ctx.pending_packs.insert(
-            sym,
-            PendingPack {
-              path: pack_file,
-              source: pack_source,
-              tokenization: pack_tok,
-              parsing: pack_par,
-              loads: pack_loads,
-            },
-          );
+          ctx
+            .pending_packs
+            .insert(sym, self.parse_pack(pack_file, session));
         } else if pack_folder.is_dir() {
           if *is_pub {
             ctx.folder_packs.insert(sym);
@@ -929,8 +1374,7 @@ impl Compiler {
     // like `main -> misato -> raylib` is fully covered. The
     // `compiling` set both deduplicates and guards against
     // circular ctx.imports.
-    let mut module_paths =
-      Self::scan_loads(&parsing.tree, &mut session.interner);
+    let mut module_paths = Self::scan_loads(tree, &mut session.interner);
 
     // Frozen snapshot of the user file's top-level `load`s.
     // The user's analyzer seed is built ONLY from these (plus
@@ -954,10 +1398,10 @@ impl Compiler {
     // `preload_re_exports` AFTER the main loop populates
     // `ctx.module_table_per_path` with every cascaded
     // module. The combined result becomes zo's prelude.
-    // Kept as locals (not in `DfsCtx`) because they're only
-    // touched by the preload-pass setup + the post-loop
-    // user-seed assembly — they don't ride the recursive
-    // module-compile path Phase B / C will lift out.
+    // Carried on `Resolution` (not in `DfsCtx`) because
+    // they're only touched by the preload-pass setup + the
+    // post-loop user-seed assembly — they don't ride the
+    // recursive module-compile path.
     let mut preload_own = ImportedSymbols::default();
     let mut preload_re_exports: Vec<Vec<Symbol>> = Vec::new();
 
@@ -1035,6 +1479,11 @@ impl Compiler {
           &mut mod_tok.literals,
           std::mem::take(&mut mod_imports.exported_component_bodies),
         );
+        mod_imports.abstract_default_bodies = splice_abstract_defaults(
+          &mut mod_par.tree,
+          &mut mod_tok.literals,
+          &mod_imports.abstract_defs,
+        );
 
         let mod_sem = Analyzer::new(
           &mod_par.tree,
@@ -1058,6 +1507,7 @@ impl Compiler {
             generic_bodies: mod_sem.generic_bodies,
             component_bodies: mod_sem.component_bodies,
             abstract_impls: mod_sem.abstract_impls,
+            abstract_defs: mod_sem.abstract_defs,
           },
           None,
           &session.interner,
@@ -1084,8 +1534,7 @@ impl Compiler {
           ctx.module_next_label_id,
         );
 
-        let mut own_imports = module_exports_to_imports(&exports);
-        own_imports.abstract_defs = mod_sem.abstract_defs.clone();
+        let own_imports = module_exports_to_imports(&exports);
 
         fold_imports_into(&mut ctx.imports, &own_imports);
 
@@ -1104,21 +1553,27 @@ impl Compiler {
       }
     }
 
-    // Recursive DFS entry — each top-level load expands
-    // its dependency graph post-order via the call stack.
-    // `module_paths` here is preload's cascade + the user
-    // file's top-level loads, in that order; the
-    // recursion's `module_table_per_path` short-circuit
-    // keeps re-export hits cheap.
-    let module_paths_snapshot = module_paths.clone();
-    for module_ref in &module_paths_snapshot {
-      self.compile_module_recursive(
-        &mut ctx,
-        &mut session,
-        module_ref.value.clone(),
-        module_ref.span,
-      );
+    Resolution {
+      ctx,
+      preload_len: module_paths.len() - user_top_loads.len(),
+      module_paths,
+      user_top_loads,
+      preload_own,
+      preload_re_exports,
     }
+  }
+
+  /// Builds the entry analyzer's seed once every top-level
+  /// `load` compiled.
+  fn finish_resolution(resolution: Resolution) -> ResolvedModules {
+    let Resolution {
+      mut ctx,
+      module_paths,
+      user_top_loads,
+      preload_own,
+      preload_re_exports,
+      ..
+    } = resolution;
 
     // Aggregate folder-namespace exports: every `load X::*;`
     // that expanded into `X/*.zo` children now folds those
@@ -1138,11 +1593,6 @@ impl Compiler {
         .insert(folder_path.clone(), combined);
     }
 
-    self.profiler.end_phase(RESOLVER_NAME);
-
-    // Analyze with imported symbols pre-loaded.
-    self.profiler.start_phase(ANALYZER_NAME);
-
     // The leaf of each transitively-loaded path IS the pack
     // name (`core::io::*` → `io`). Surfaced so the user
     // analyzer's qualified-call resolution finds them.
@@ -1176,146 +1626,214 @@ impl Compiler {
       &ctx.module_table_per_path,
     );
 
-    // Entry programs adopt their file stem as pack identity
-    // so top-level `#link { ... }` resolves to a `PackLink`.
-    let implicit_sym = file_path
-      .file_stem()
-      .and_then(|s| s.to_str())
-      .map(|stem| session.interner.intern(stem));
+    ResolvedModules {
+      user_seed,
+      in_scope_packs,
+      module_sir_instructions: ctx.module_sir_instructions,
+      module_sir_spans: ctx.module_sir_spans,
+      module_next_value_id: ctx.module_next_value_id,
+      module_next_label_id: ctx.module_next_label_id,
+      pack_paths: ctx.pack_paths,
+      file_table: ctx.file_table,
+    }
+  }
 
-    let mut user_seed = user_seed;
+  /// [`Self::resolve_modules`] through the module cache. The
+  /// resolution resumes from the furthest checkpoint whose
+  /// top-level `load`s are a prefix of the entry file's and
+  /// whose files are unchanged — swapping `session` for the
+  /// checkpoint's and reading the entry file again into it —
+  /// and leaves a checkpoint after each pack it compiles,
+  /// unless some diagnostic points at the entry file: those
+  /// depend on more than its `load`s.
+  fn resolve_cached(
+    &mut self,
+    cache: &ModuleCache,
+    session: &mut Session,
+    tokenization: &mut TokenizationResult,
+    parsing: &mut ParsingResult,
+    source: &str,
+    file_path: &Path,
+  ) -> ResolvedModules {
+    self.profiler.start_phase(RESOLVER_NAME);
 
-    user_seed.generic_bodies = splice_generic_bodies(
-      &mut parsing.tree,
-      &mut tokenization.literals,
-      std::mem::take(&mut user_seed.exported_generic_bodies),
-    );
-    user_seed.component_bodies = splice_component_bodies(
-      &mut parsing.tree,
-      &mut tokenization.literals,
-      std::mem::take(&mut user_seed.exported_component_bodies),
-    );
+    let root = EnvRoot {
+      search_paths: self.module_resolver.search_paths().to_vec(),
+      lib: Self::discover_lib(file_path),
+    };
 
-    let analyzer = Analyzer::new(
-      &parsing.tree,
-      &mut session.interner,
-      &tokenization.literals,
-      &mut session.ty_checker,
-    );
+    let loads = Self::scan_loads(&parsing.tree, &mut session.interner);
+    let loads = load_names(&loads, session);
 
-    // The user file's `<img src="…">` and similar
-    // path-typed attributes are resolved against this
-    // directory at attribute-build time — so the
-    // compiled binary holds absolute paths and renders
-    // assets regardless of CWD at run time.
-    let mut semantic = analyzer
-      .with_config(AnalyzerConfig {
-        imports: user_seed,
-        source_dir: file_path.parent().map(Path::to_path_buf),
-        source_path: Some(file_path.to_path_buf()),
-        implicit_pack: implicit_sym,
-        in_scope_packs,
-        is_entry: true,
-        test_mode: self.test_mode,
-        file_id: 0,
-      })
-      .analyze();
-    self.profiler.end_phase(ANALYZER_NAME);
+    // Drained before resolution so the modules' diagnostics
+    // can be told apart from the entry file's.
+    let mut entry = zo_reporter::collect_diagnostics();
+    let resume = cache.resume(&root, &loads);
 
-    // Merge module SIR into main SIR. Modules must appear
-    // before main so their FunDefs are registered before
-    // main calls them. All ValueIds are explicit and
-    // offsettable — no implicit/explicit mismatch.
-    if !ctx.module_sir_instructions.is_empty() {
-      let main_next_vid = semantic.sir.next_value_id;
-      let main_next_lid = semantic.sir.next_label_id;
+    if let Some(checkpoint) = &resume {
+      *session = checkpoint.session.clone();
+      *tokenization = Tokenizer::new(source, &mut session.interner).tokenize();
+      *parsing = Parser::new(tokenization, source).parse();
 
-      Sir::offset_value_ids(&mut ctx.module_sir_instructions, main_next_vid);
-      // Shift module labels above main's own label range
-      // (main uses `[0, main_next_lid)`). Per-pack offset
-      // above already ensures module labels don't collide
-      // with one another; this just lifts the whole module
-      // block above main's labels for the merged stream.
-      Sir::offset_labels(&mut ctx.module_sir_instructions, main_next_lid);
+      // The second read reports what the first one did.
+      entry = zo_reporter::collect_diagnostics();
+    }
 
-      // Prepend: modules first, then main. Spans mirror the
-      // same prepend so the merged SIR stays aligned 1:1.
-      let main_insns = std::mem::replace(
-        &mut semantic.sir.instructions,
-        ctx.module_sir_instructions,
-      );
-      let main_spans =
-        std::mem::replace(&mut semantic.sir.spans, ctx.module_sir_spans);
+    self.reporter.collect_errors(&entry.0);
+    self.reporter.collect_details(entry.1);
+
+    let (mut resolution, mut diagnostics, start) = match &resume {
+      Some(checkpoint) => (
+        self.thaw(checkpoint, session, &parsing.tree, source, file_path),
+        checkpoint.diagnostics.clone(),
+        checkpoint.done,
+      ),
+      None => (
+        self.begin_resolution(session, &parsing.tree, source, file_path),
+        PackDiagnostics::default(),
+        0,
+      ),
+    };
 
-      semantic.sir.instructions.extend(main_insns);
-      semantic.sir.spans.extend(main_spans);
-      semantic.sir.next_value_id += ctx.module_next_value_id;
-      semantic.sir.next_label_id += ctx.module_next_label_id;
-    }
+    let mut cacheable = true;
 
-    // Build DCE roots: main + abstract-impl methods +
-    // test functions (when test_mode is active).
-    let main_sym = session.interner.intern("main");
-    let mut dce_roots = vec![main_sym];
+    for done in start..=resolution.module_paths.len() {
+      if done > start {
+        let module_ref = resolution.module_paths[done - 1].clone();
 
-    for im in semantic.abstract_impls.values() {
-      dce_roots.extend(im.methods.iter().copied());
-    }
+        self.compile_module_recursive(
+          &mut resolution.ctx,
+          session,
+          module_ref.value,
+          module_ref.span,
+        );
+      }
 
-    if self.test_mode {
-      for insn in &semantic.sir.instructions {
-        if let Insn::FunDef {
-          name,
-          is_test: true,
-          ..
-        } = insn
-        {
-          dce_roots.push(*name);
-        }
+      // The preload cascade is one layer — a core pack only
+      // changes along with the toolchain.
+      if done < resolution.preload_len {
+        continue;
+      }
+
+      diagnostics.drain();
+      // A cancelled pack compiled only partly.
+      cacheable &= !diagnostics.points_at_entry() && !self.is_cancelled();
+
+      if cacheable && (done > start || resume.is_none()) {
+        cache.insert(
+          root.clone(),
+          self.checkpoint(session, &resolution, done, &diagnostics, &root),
+        );
       }
     }
 
-    // Keep every destructor (`own self` method) alive through
-    // DCE even when no explicit `.free()` calls it: the only
-    // other caller is the compiler-inserted scope-exit drop the
-    // ownership pass emits AFTER DCE, so without this the
-    // destructor is pruned as dead and auto-drop silently leaks.
-    for insn in &semantic.sir.instructions {
-      if let Insn::FunDef {
-        name,
-        self_kind: zo_ty::SelfKind::Consume,
-        ..
-      } = insn
-      {
-        dce_roots.push(*name);
-      }
+    self.reporter.collect_errors(&diagnostics.errors);
+    self.reporter.collect_details(diagnostics.details);
+    self.lint_levels.extend_scopes(diagnostics.lint_scopes);
+
+    let resolved = Self::finish_resolution(resolution);
+
+    self.profiler.end_phase(RESOLVER_NAME);
+
+    resolved
+  }
+
+  /// Freezes `resolution` after its first `done` top-level
+  /// `load`s compiled.
+  fn checkpoint(
+    &self,
+    session: &Session,
+    resolution: &Resolution,
+    done: usize,
+    diagnostics: &PackDiagnostics,
+    root: &EnvRoot,
+  ) -> Checkpoint {
+    let mut deps = resolution.ctx.file_table[1..]
+      .iter()
+      .map(|(path, source)| Dep::new(canonical(path), source))
+      .collect::<Vec<_>>();
+
+    if let Some(lib) = &root.lib
+      && let Some(hash) = resolution.ctx.lib_hash
+    {
+      deps.push(Dep {
+        path: canonical(lib),
+        hash,
+        stamp: None,
+      });
     }
 
-    // TODO: move all these stages in analyzer.
-    Dce::new(&mut semantic.sir, dce_roots, &session.interner).eliminate();
-    Inline::new(
-      &mut semantic.sir,
-      &mut session.interner,
-      session.ty_checker.tys(),
-      self.release,
-    )
-    .inline();
-    Ownership::new(&mut semantic.sir, &session.interner, &session.ty_checker)
-      .check();
+    Checkpoint {
+      session: session.clone(),
+      resolution: resolution.frozen(),
+      pending: resolution
+        .ctx
+        .pending_packs
+        .iter()
+        .map(|(sym, pack)| (*sym, pack.path.clone()))
+        .collect(),
+      module_table: self.module_table.clone(),
+      loaded_packs: self.loaded_packs.clone(),
+      loads: load_names(&resolution.module_paths, session),
+      preload_len: resolution.preload_len,
+      done,
+      diagnostics: diagnostics.clone(),
+      deps,
+    }
+  }
 
-    // Single drain after every analyze-time pass (analyzer,
-    // module loads, DCE). One TLS access, not one per pass.
-    let (tl_errors, tl_details) = zo_reporter::collect_diagnostics();
-    if !tl_errors.is_empty() {
-      self.reporter.collect_errors(&tl_errors);
-      self.reporter.collect_details(tl_details);
+  /// Picks a resolution up from `checkpoint` for the entry
+  /// file at `file_path`, whose tree `session` now holds.
+  fn thaw(
+    &mut self,
+    checkpoint: &Checkpoint,
+    session: &mut Session,
+    tree: &Tree,
+    source: &str,
+    file_path: &Path,
+  ) -> Resolution {
+    let mut resolution = checkpoint.resolution.frozen();
+    let ctx = &mut resolution.ctx;
+
+    // Packs not compiled yet aren't dependencies of the
+    // checkpoint, so they are read as they are now.
+    for (sym, path) in &checkpoint.pending {
+      ctx
+        .pending_packs
+        .insert(*sym, self.parse_pack(path.clone(), session));
     }
 
-    semantic.pack_paths = ctx.pack_paths;
+    ctx.file_id_by_path.retain(|_, id| *id != 0);
+    ctx.file_id_by_path.insert(file_path.to_path_buf(), 0);
+    ctx.file_table[0] = (file_path.to_path_buf(), source.to_string());
 
-    let file_table = ctx.file_table;
+    resolution.user_top_loads = Self::scan_loads(tree, &mut session.interner);
+    resolution.module_paths.truncate(resolution.preload_len);
+    resolution
+      .module_paths
+      .extend(resolution.user_top_loads.iter().cloned());
 
-    (semantic, tokenization, parsing, session, file_table)
+    self.module_table = checkpoint.module_table.clone();
+    self.loaded_packs = checkpoint.loaded_packs.clone();
+
+    resolution
+  }
+
+  /// Reads and parses the `lib.zo` pack at `path`.
+  fn parse_pack(&self, path: PathBuf, session: &mut Session) -> PendingPack {
+    let source = self.module_resolver.read(&path).unwrap_or_default();
+    let tokenization =
+      Tokenizer::new(&source, &mut session.interner).tokenize();
+    let parsing = Parser::new(&tokenization, &source).parse();
+    let loads = Self::scan_loads(&parsing.tree, &mut session.interner);
+
+    PendingPack {
+      path,
+      source,
+      tokenization,
+      parsing,
+      loads,
+    }
   }
 
   /// Recursive DFS post-order driver. Compiles every
@@ -1345,6 +1863,10 @@ impl Compiler {
     module_path: Vec<Symbol>,
     load_span: Span,
   ) {
+    if self.is_cancelled() {
+      return;
+    }
+
     // Already compiled along an earlier branch — re-export
     // graphs frequently reach the same module twice; the
     // recursive structure deduplicates here so each module
@@ -1452,6 +1974,11 @@ impl Compiler {
           &mut pack.tokenization.literals,
           std::mem::take(&mut pack_imports.exported_component_bodies),
         );
+        pack_imports.abstract_default_bodies = splice_abstract_defaults(
+          &mut pack.parsing.tree,
+          &mut pack.tokenization.literals,
+          &pack_imports.abstract_defs,
+        );
 
         let pack_sem = Analyzer::new(
           &pack.parsing.tree,
@@ -1475,6 +2002,7 @@ impl Compiler {
             generic_bodies: pack_sem.generic_bodies,
             component_bodies: pack_sem.component_bodies,
             abstract_impls: pack_sem.abstract_impls,
+            abstract_defs: pack_sem.abstract_defs,
           },
           None,
           &session.interner,
@@ -1539,11 +2067,17 @@ impl Compiler {
         .module_table_per_path
         .insert(module_path.clone(), exported);
 
-      ctx.module_sir_instructions.extend(exports.sir_instructions);
-      ctx.module_sir_spans.extend(exports.sir_spans);
+      ctx
+        .module_sir_instructions
+        .extend(std::mem::take(&mut exports.sir_instructions));
+      ctx
+        .module_sir_spans
+        .extend(std::mem::take(&mut exports.sir_spans));
       ctx.module_next_value_id += exports.next_value_id;
       ctx.module_next_label_id += exports.next_label_id;
 
+      self.loaded_packs.insert(first_seg, exports);
+
       return;
     }
 
@@ -1749,6 +2283,11 @@ impl Compiler {
       &mut mod_tokenization.literals,
       std::mem::take(&mut mod_imports.exported_component_bodies),
     );
+    mod_imports.abstract_default_bodies = splice_abstract_defaults(
+      &mut mod_parsing.tree,
+      &mut mod_tokenization.literals,
+      &mod_imports.abstract_defs,
+    );
 
     let mod_semantic = Analyzer::new(
       &mod_parsing.tree,
@@ -1772,6 +2311,7 @@ impl Compiler {
         generic_bodies: mod_semantic.generic_bodies,
         component_bodies: mod_semantic.component_bodies,
         abstract_impls: mod_semantic.abstract_impls,
+        abstract_defs: mod_semantic.abstract_defs,
       },
       selective,
       &session.interner,
@@ -1797,8 +2337,7 @@ impl Compiler {
 
     Sir::offset_labels(&mut exports.sir_instructions, ctx.module_next_label_id);
 
-    let mut own_imports = module_exports_to_imports(&exports);
-    own_imports.abstract_defs = mod_semantic.abstract_defs.clone();
+    let own_imports = module_exports_to_imports(&exports);
 
     fold_imports_into(&mut ctx.imports, &own_imports);
 
@@ -1848,6 +2387,10 @@ impl Compiler {
     let should_emit_tokens = should_emit_all || stages.contains(&Stage::Tokens);
     let should_emit_tree = should_emit_all || stages.contains(&Stage::Tree);
     let should_emit_sir = should_emit_all || stages.contains(&Stage::Sir);
+    let should_emit_cfg = should_emit_all || stages.contains(&Stage::Cfg);
+    let should_emit_types = should_emit_all || stages.contains(&Stage::Types);
+    let should_emit_ownership =
+      should_emit_all || stages.contains(&Stage::Ownership);
     let should_emit_asm = should_emit_all || stages.contains(&Stage::Asm);
 
     self.stats.numlines = files
@@ -1877,9 +2420,68 @@ impl Compiler {
       }
     };
 
+    // Output destination:
+    // 1. explicit `-o <path>` wins, always.
+    // 2. else if `--out-dir <dir>` is set, `<dir>/<stem>`.
+    // 3. else next to the source, like `rustc foo.rs`.
+    //
+    // Web emits a `public/` *directory* bundle rather than a
+    // `<stem>` file, so its default lands at `<source-dir>/public`.
+    let resolve_binary_path = |path: &Path| -> PathBuf {
+      if matches!(target, Target::Web) {
+        match (output_path, out_dir) {
+          (Some(p), _) => p.clone(),
+          (None, Some(dir)) => dir.join("public"),
+          (None, None) => path.with_file_name("public"),
+        }
+      } else {
+        match (output_path, out_dir) {
+          (Some(p), _) => p.clone(),
+          (None, Some(dir)) => {
+            let stem = path.file_stem().unwrap_or(path.as_os_str());
+            dir.join(stem)
+          }
+          (None, None) => path.with_extension(""),
+        }
+      }
+    };
+
+    self.trace_ownership = should_emit_ownership;
+    self.record_node_tys = should_emit_types;
+
     let mut file_table = Vec::new();
 
     for (path, code) in files.iter() {
+      // A `.sir` input skips the front-end entirely — the
+      // textual SIR is parsed straight into the program handed
+      // to the backend. Only the backend dumps apply; `--emit
+      // sir` would overwrite the input itself.
+      if path.extension().is_some_and(|ext| ext == "sir") {
+        file_table = vec![(path.to_path_buf(), code.clone())];
+
+        let Some((semantic, session)) = self.analyze_sir(code) else {
+          continue;
+        };
+
+        if should_emit_cfg {
+          write_cfg(&resolve_emit_path(path, "cfg"), &semantic, &session);
+        }
+
+        let asm_path = should_emit_asm.then(|| resolve_emit_path(path, "asm"));
+
+        self.lower_one(&Lowering {
+          semantic: &semantic,
+          session: &session,
+          path: path.as_path(),
+          output_path: resolve_binary_path(path).as_path(),
+          emit_asm: asm_path.as_deref(),
+          target,
+          sources: &file_table,
+        });
+
+        continue;
+      }
+
       let (semantic, tokenization, parsing, session, ft) =
         self.analyze_source(code, path);
 
@@ -1916,11 +2518,7 @@ impl Compiler {
       if should_emit_sir {
         let sir_path = resolve_emit_path(path, "sir");
         let mut pp = PrettyPrinter::new();
-        pp.format_sir(
-          &semantic.sir,
-          &session.interner,
-          session.ty_checker.tys(),
-        );
+        pp.format_sir(&semantic.sir, &session.interner, &session.ty_checker);
         let sir_output = pp.finish();
 
         if let Err(error) = fs::write(&sir_path, sir_output) {
@@ -1928,29 +2526,44 @@ impl Compiler {
         }
       }
 
-      // Output destination:
-      // 1. explicit `-o <path>` wins, always.
-      // 2. else if `--out-dir <dir>` is set, `<dir>/<stem>`.
-      // 3. else next to the source, like `rustc foo.rs`.
-      //
-      // Web emits a `public/` *directory* bundle rather than a
-      // `<stem>` file, so its default lands at `<source-dir>/public`.
-      let binary_path = if matches!(target, Target::Web) {
-        match (&output_path, out_dir) {
-          (Some(p), _) => p.clone(),
-          (None, Some(dir)) => dir.join("public"),
-          (None, None) => path.with_file_name("public"),
+      if should_emit_cfg {
+        write_cfg(&resolve_emit_path(path, "cfg"), &semantic, &session);
+      }
+
+      if should_emit_types {
+        let types_path = resolve_emit_path(path, "types");
+        let mut pp = PrettyPrinter::new();
+        let annotations = semantic
+          .annotations
+          .iter()
+          .chain(&semantic.node_tys)
+          .copied()
+          .collect::<Vec<_>>();
+
+        pp.format_types(
+          &parsing.tree,
+          code,
+          &annotations,
+          &session.interner,
+          &session.ty_checker,
+        );
+
+        if let Err(error) = fs::write(&types_path, pp.finish()) {
+          eprintln!("Failed to write types to {types_path:?}: {error}");
         }
-      } else {
-        match (&output_path, out_dir) {
-          (Some(p), _) => p.clone(),
-          (None, Some(dir)) => {
-            let stem = path.file_stem().unwrap_or(path.as_os_str());
-            dir.join(stem)
-          }
-          (None, None) => path.with_extension(""),
+      }
+
+      if should_emit_ownership && let Some(trace) = self.ownership_trace.take()
+      {
+        let ownership_path = resolve_emit_path(path, "ownership");
+        let mut pp = PrettyPrinter::new();
+
+        pp.format_ownership(&trace, &session.interner);
+
+        if let Err(error) = fs::write(&ownership_path, pp.finish()) {
+          eprintln!("Failed to write ownership to {ownership_path:?}: {error}");
         }
-      };
+      }
 
       let asm_path = should_emit_asm.then(|| resolve_emit_path(path, "asm"));
 
@@ -1958,9 +2571,10 @@ impl Compiler {
         semantic: &semantic,
         session: &session,
         path: path.as_path(),
-        output_path: binary_path.as_path(),
+        output_path: resolve_binary_path(path).as_path(),
         emit_asm: asm_path.as_deref(),
         target,
+        sources: &file_table,
       });
     }
 
@@ -1978,22 +2592,26 @@ impl Compiler {
   /// already holds a hard error; the caller's final report
   /// renders the diagnostics and fails the build.
   fn lower_one(&mut self, lowering: &Lowering) {
-    let has_hard_error = self
-      .reporter
-      .errors()
-      .iter()
-      .any(|error| matches!(error.severity(), Severity::Error));
-
-    if has_hard_error {
+    if self.has_hard_error() {
       return;
     }
 
     self.profiler.start_phase(CODEGEN_NAME);
-    let codegen =
+    let mut codegen =
       Codegen::new(lowering.target).with_webviewing(self.webviewing);
 
+    // A dev build carries DWARF and the zo type table, for
+    // `zo-dap` and native debuggers — when its backend writes
+    // them at all.
+    if self.release == Release::No
+      && codegen.emits_debug_info()
+      && let Some(debug_info) = debug_info(lowering)
+    {
+      codegen = codegen.with_debug_info(debug_info);
+    }
+
     // ARM64Gen consults this view to drive the generic
-    // AAPCS FFI path; CLIF ignores it.
+    // AAPCS FFI path; CLIF reads it for debug info.
     let type_view = Some((
       lowering.session.ty_checker.tys(),
       &lowering.session.ty_checker.ty_table,
@@ -2049,7 +2667,12 @@ impl Compiler {
 
     let mut runtime = zo_linker::RuntimeKind::None;
 
-    match zo_linker::link(link_obj, lowering.output_path, lowering.target) {
+    match zo_linker::link(
+      link_obj,
+      lowering.output_path,
+      lowering.target,
+      &runtime_library,
+    ) {
       Ok(kind) => {
         runtime = kind;
         self.stats.numlinked += 1;
@@ -2106,13 +2729,36 @@ impl Compiler {
     file_table: &[(PathBuf, String)],
     target: Target,
   ) -> Result<(), Error> {
-    let errors = self.reporter.errors();
+    self.report(file_table)?;
 
-    if !errors.is_empty() {
-      let mut aggregator = ErrorAggregator::new();
+    self.profiler.set_tokens_count(self.stats.numtokens);
+    self.profiler.set_nodes_count(self.stats.numnodes);
+    self.profiler.set_inferences_count(self.stats.numinferences);
+    self.profiler.set_artifacts_count(self.stats.numartifacts);
+    self.profiler.set_artifacts_linked(self.stats.numlinked);
+    // Profiler text writes to stdout — when a machine format
+    // is active, that bleed corrupts the structured stream a
+    // consumer is parsing. Suppress; the timing data is
+    // human-only.
+    if !self.emit_format.is_machine() && !self.quiet {
+      self.profiler.summary(target.name(), self.use_colors);
+    }
+
+    Ok(())
+  }
+
+  /// Renders every accumulated diagnostic in the selected
+  /// format. Returns `Err` when any hard error was reported.
+  /// Shared by the full compile and by `check`, which stops
+  /// after analysis and prints no build summary.
+  fn report(&self, file_table: &[(PathBuf, String)]) -> Result<(), Error> {
+    let errors = self.reporter.errors();
 
-      aggregator.add_errors(errors);
-      aggregator.add_details(self.reporter.details());
+    // A SARIF log is emitted even for a clean compile — an
+    // empty `results` array is what a code-scanning dashboard
+    // needs to close previously reported alerts.
+    if !errors.is_empty() || self.emit_format == DiagnosticFormat::Sarif {
+      let mut aggregator = self.diagnostics();
 
       let _ = match self.emit_format {
         DiagnosticFormat::Json => {
@@ -2121,37 +2767,72 @@ impl Compiler {
         DiagnosticFormat::Xml => {
           xml::to_stdout(&aggregator, file_table, self.snippet_context)
         }
+        DiagnosticFormat::Sarif => sarif::to_stdout(&aggregator, file_table),
         DiagnosticFormat::Human => self.render_human(&aggregator, file_table),
       };
 
-      aggregator.clear();
-
       // Warnings are surfaced above but do not fail the build.
       // Only hard errors (`Severity::Error`) propagate as a
-      // compilation failure.
-      let has_hard_error = errors
-        .iter()
-        .any(|e| matches!(e.severity(), Severity::Error));
+      // compilation failure — denied lints included.
+      let has_hard_error = aggregator.has_hard_errors();
+
+      aggregator.clear();
 
       if has_hard_error {
         return Err(Error::new(ErrorKind::InternalCompilerError, Span::ZERO));
       }
     }
 
-    self.profiler.set_tokens_count(self.stats.numtokens);
-    self.profiler.set_nodes_count(self.stats.numnodes);
-    self.profiler.set_inferences_count(self.stats.numinferences);
-    self.profiler.set_artifacts_count(self.stats.numartifacts);
-    self.profiler.set_artifacts_linked(self.stats.numlinked);
-    // Profiler text writes to stdout — when a machine format
-    // is active, that bleed corrupts the structured stream a
-    // consumer is parsing. Suppress; the timing data is
-    // human-only.
-    if !self.emit_format.is_machine() && !self.quiet {
-      self.profiler.summary(target.name(), self.use_colors);
+    Ok(())
+  }
+
+  /// Scans, parses and analyzes every file, then renders the
+  /// diagnostics — the front half of [`Self::compile`] with no
+  /// codegen, link or `--emit` dumps. Backs `zo check`.
+  pub fn check(&mut self, files: &[(&PathBuf, String)]) -> Result<(), Error> {
+    let file_table = self.analyze_files(files);
+
+    self.report(&file_table)
+  }
+
+  /// Analyzes every file and returns one file table covering
+  /// all of them, for [`Self::check`].
+  pub(crate) fn analyze_files(
+    &mut self,
+    files: &[(&PathBuf, String)],
+  ) -> Vec<(PathBuf, String)> {
+    // Each analysis numbers its own files from 0. Their tables
+    // merge into one — shared packs registered once — and every
+    // diagnostic and lint scope is retagged against it, so an
+    // earlier file's errors keep resolving to their own source.
+    let mut file_table: Vec<(PathBuf, String)> = Vec::new();
+
+    for (path, code) in files.iter() {
+      let marks = (self.reporter.errors().len(), self.reporter.details().len());
+      let scopes = self.lint_levels.scope_count();
+      let (_, _, _, _, ft) = self.analyze_source(code, path);
+
+      let ids = ft
+        .into_iter()
+        .map(|(path, source)| {
+          match file_table.iter().position(|(known, _)| *known == path) {
+            Some(id) => id as u16,
+            None => {
+              file_table.push((path, source));
+
+              (file_table.len() - 1) as u16
+            }
+          }
+        })
+        .collect::<Vec<_>>();
+
+      let remap = |id: u16| ids.get(id as usize).copied().unwrap_or(id);
+
+      self.reporter.remap_files(marks, remap);
+      self.lint_levels.remap_scope_files(scopes, remap);
     }
 
-    Ok(())
+    file_table
   }
 
   /// Lowers an already-analyzed program to a linked binary,
@@ -2188,6 +2869,7 @@ impl Compiler {
       output_path,
       emit_asm: None,
       target,
+      sources: &analyzed.file_table,
     });
 
     self.render_and_finish(&analyzed.file_table, target)
@@ -2257,6 +2939,7 @@ impl Compiler {
       pubness: Pubness::Yes,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -2281,13 +2964,7 @@ impl Compiler {
     // clean analysis: malformed SIR from an errored program
     // would panic the backend, so skip straight to rendering
     // the diagnostics.
-    let has_hard_error = self
-      .reporter
-      .errors()
-      .iter()
-      .any(|error| matches!(error.severity(), Severity::Error));
-
-    if !has_hard_error {
+    if !self.has_hard_error() {
       let codegen = Codegen::new(target).with_webviewing(self.webviewing);
       let type_view =
         Some((session.ty_checker.tys(), &session.ty_checker.ty_table));
@@ -2303,7 +2980,12 @@ impl Compiler {
         abstract_state,
       );
 
-      let runtime = match zo_linker::link(link_obj, output_path, target) {
+      let runtime = match zo_linker::link(
+        link_obj,
+        output_path,
+        target,
+        &runtime_library,
+      ) {
         Ok(kind) => kind,
         Err(err) => {
           eprintln!("zo: link failed: {err}");
@@ -2328,21 +3010,14 @@ impl Compiler {
       }
     }
 
-    let errors = self.reporter.errors();
-
-    if !errors.is_empty() {
-      let mut aggregator = ErrorAggregator::new();
-
-      aggregator.add_errors(errors);
-      aggregator.add_details(self.reporter.details());
+    if !self.reporter.errors().is_empty() {
+      let mut aggregator = self.diagnostics();
 
       let _ = self.render_human(&aggregator, &file_table);
 
-      aggregator.clear();
+      let has_hard_error = aggregator.has_hard_errors();
 
-      let has_hard_error = errors
-        .iter()
-        .any(|e| matches!(e.severity(), Severity::Error));
+      aggregator.clear();
 
       if has_hard_error {
         return Err(Error::new(ErrorKind::InternalCompilerError, Span::ZERO));
@@ -2459,6 +3134,98 @@ impl RuntimeNeeds {
   }
 }
 
+/// Writes `--emit=cfg`: one Graphviz file per function,
+/// `<dir>/<function>.dot`, with `::` spelled `.` and any
+/// other character a path can't carry spelled `_`.
+fn write_cfg(dir: &Path, semantic: &SemanticResult, session: &Session) {
+  if let Err(error) = fs::create_dir_all(dir) {
+    eprintln!("Failed to create cfg dir {dir:?}: {error}");
+
+    return;
+  }
+
+  let graphs = PrettyPrinter::cfg_graphs(
+    &semantic.sir,
+    &session.interner,
+    &session.ty_checker,
+  );
+  let mut seen = HashMap::<String, usize>::default();
+
+  for (label, dot) in graphs {
+    let stem = label
+      .replace("::", ".")
+      .chars()
+      .map(|c| {
+        if c.is_alphanumeric() || matches!(c, '_' | '.' | '-') {
+          c
+        } else {
+          '_'
+        }
+      })
+      .collect::<String>();
+
+    // Same-named functions (overloads across packs spelled
+    // alike once sanitized) get a numeric suffix.
+    let count = seen.entry(stem.clone()).or_default();
+    let file = match *count {
+      0 => format!("{stem}.dot"),
+      n => format!("{stem}.{n}.dot"),
+    };
+
+    *count += 1;
+
+    let cfg_path = dir.join(file);
+
+    if let Err(error) = fs::write(&cfg_path, dot) {
+      eprintln!("Failed to write cfg to {cfg_path:?}: {error}");
+    }
+  }
+}
+
+/// What the backend needs for `lowering`'s debug info: the
+/// source files, the file each pack came from, and the source
+/// spelling of every type. `None` without a source file.
+fn debug_info(lowering: &Lowering) -> Option<DebugInfo> {
+  if lowering.sources.is_empty() {
+    return None;
+  }
+
+  let files = lowering.sources.to_vec();
+  let canonical_files = files
+    .iter()
+    .map(|(path, _)| canonical(path))
+    .collect::<Vec<_>>();
+
+  let pack_files = lowering
+    .semantic
+    .pack_paths
+    .iter()
+    .filter_map(|(pack, path)| {
+      let path = canonical(path);
+      let idx = canonical_files.iter().position(|file| *file == path)?;
+
+      Some((*pack, idx))
+    })
+    .collect();
+
+  let session = lowering.session;
+  let ty_labels = (0..session.ty_checker.tys().len())
+    .map(|idx| {
+      PrettyPrinter::ty_label(
+        &session.interner,
+        &session.ty_checker,
+        TyId(idx as u32),
+      )
+    })
+    .collect();
+
+  Some(DebugInfo {
+    files,
+    pack_files,
+    ty_labels,
+  })
+}
+
 /// Materialise the runtime dylibs a freshly-built binary
 /// references at `@loader_path/deps/`. The linker's
 /// `runtime` verdict picks which `libzo_runtime` flavor —
@@ -2612,6 +3379,43 @@ fn stage_dylib_as(
   source: &str,
   dest: &str,
 ) {
+  let Some(src) = find_dylib(runtime_dir, source) else {
+    return;
+  };
+
+  // The runtime dylib carries an ABI tag; a staged copy whose
+  // tag differs from the compiler's would decode an older
+  // `ZoRuntimeContext` and silently drop behavior (dead
+  // reactivity). Refuse the build instead.
+  if dest == RUNTIME_STAGED_DYLIB {
+    verify_runtime_abi_tag(&src);
+  }
+
+  // Race-safe staging: copy to a PID-stamped tempfile,
+  // then `rename` over the destination. The test runner
+  // spawns ~400 parallel `zo build` processes into one
+  // tmp directory; a plain `fs::copy` (truncate +
+  // sequential write) lets two writers interleave and
+  // leaves dyld mapping a torn dylib — the loaded test
+  // binary then SIGKILLs at launch. POSIX `rename` is
+  // atomic on the same filesystem, so concurrent readers
+  // see either the old inode or the new inode, never a
+  // partial one.
+  let destination = output_dir.join(dest);
+  let tmp = output_dir.join(format!(".{}.{}.tmp", dest, std::process::id()));
+
+  if std::fs::copy(&src, &tmp).is_ok() {
+    let _ = std::fs::rename(&tmp, &destination);
+  } else {
+    let _ = std::fs::remove_file(&tmp);
+  }
+}
+
+/// Locate a runtime dylib the running `zo` ships with.
+fn find_dylib(
+  runtime_dir: &std::path::Path,
+  source: &str,
+) -> Option<std::path::PathBuf> {
   // Search order:
   // 1. `deps/<source>` — cargo build artifacts that the
   //    runtime crate produces (libzo_runtime_*,
@@ -2624,7 +3428,7 @@ fn stage_dylib_as(
   //    placed by `tasks/zo-install.sh` or staged
   //    manually under `target/lib/vendor/` for local
   //    development.
-  let candidates = [
+  [
     runtime_dir.join("deps").join(source),
     runtime_dir.join(source),
     runtime_dir
@@ -2632,41 +3436,25 @@ fn stage_dylib_as(
       .join("lib")
       .join("vendor")
       .join(source),
-  ];
-
-  // Race-safe staging: copy to a PID-stamped tempfile,
-  // then `rename` over the destination. The test runner
-  // spawns ~400 parallel `zo build` processes into one
-  // tmp directory; a plain `fs::copy` (truncate +
-  // sequential write) lets two writers interleave and
-  // leaves dyld mapping a torn dylib — the loaded test
-  // binary then SIGKILLs at launch. POSIX `rename` is
-  // atomic on the same filesystem, so concurrent readers
-  // see either the old inode or the new inode, never a
-  // partial one.
-  for src in &candidates {
-    if src.exists() {
-      // The runtime dylib carries an ABI tag; a staged copy whose
-      // tag differs from the compiler's would decode an older
-      // `ZoRuntimeContext` and silently drop behavior (dead
-      // reactivity). Refuse the build instead.
-      if dest == RUNTIME_STAGED_DYLIB {
-        verify_runtime_abi_tag(src);
-      }
+  ]
+  .into_iter()
+  .find(|candidate| candidate.exists())
+}
 
-      let destination = output_dir.join(dest);
-      let tmp =
-        output_dir.join(format!(".{}.{}.tmp", dest, std::process::id()));
+/// The `libzo_runtime` flavor `runtime` names, for the `cc` link
+/// of a Cranelift build to resolve its runtime imports against.
+fn runtime_library(
+  runtime: zo_linker::RuntimeKind,
+) -> Option<std::path::PathBuf> {
+  let source = match runtime {
+    zo_linker::RuntimeKind::None => return None,
+    zo_linker::RuntimeKind::Lean => RUNTIME_CORE_DYLIB,
+    zo_linker::RuntimeKind::Full => RUNTIME_UI_DYLIB,
+  };
 
-      if std::fs::copy(src, &tmp).is_ok() {
-        let _ = std::fs::rename(&tmp, &destination);
-      } else {
-        let _ = std::fs::remove_file(&tmp);
-      }
+  let zo_binary = std::env::current_exe().ok()?;
 
-      return;
-    }
-  }
+  find_dylib(zo_binary.parent()?, source)
 }
 
 /// Scan a runtime dylib's bytes for the ABI tag and compare it to
@@ -1,3 +1,4 @@
+mod cache;
 mod compiler;
 mod constants;
 pub mod orchestrator;
@@ -6,6 +7,7 @@ mod stage;
 #[cfg(test)]
 mod tests;
 
+pub use cache::ModuleCache;
 pub use compiler::{
   Analyzed, Compiler, DiagnosticsConfig, default_core_search_paths,
 };
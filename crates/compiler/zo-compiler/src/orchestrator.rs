@@ -11,6 +11,7 @@ pub use batch::BatchResult;
 use crate::Compiler;
 
 use zo_codegen_backend::Target;
+use zo_error::lint::LintLevels;
 use zo_error::{Error, ErrorKind};
 use zo_span::Span;
 
@@ -36,6 +37,12 @@ impl Orchestrator {
     }
   }
 
+  /// Sets the lint levels every file in the batch is checked
+  /// against — a build system's project-wide lint table.
+  pub fn set_lint_levels(&mut self, levels: LintLevels) {
+    self.compiler.set_lint_levels(levels);
+  }
+
   /// Compiles a batch of source files.
   ///
   /// # Arguments
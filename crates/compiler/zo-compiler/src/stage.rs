@@ -7,6 +7,13 @@ pub enum Stage {
   Tree,
   /// The semantic IR generated by the analyzer.
   Sir,
+  /// The per-function control-flow graphs, as Graphviz, with
+  /// live-in / live-out sets.
+  Cfg,
+  /// Each tree node's inferred type.
+  Types,
+  /// The ownership pass's move facts and drop decisions.
+  Ownership,
   /// The assembly code generated by the codegen.
   Asm,
   /// All outputs generated by the compiler pipeline.
@@ -1,2 +1,4 @@
+pub(crate) mod cache;
 pub(crate) mod common;
 pub(crate) mod errors;
+pub(crate) mod ownership;
@@ -0,0 +1,366 @@
+use crate::cache::{
+  EnvRoot, MAX_CHECKPOINTS, content_hash, stamp_if_unchanged,
+};
+use crate::{Compiler, ModuleCache};
+
+use zo_error::Severity;
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+/// Names of the functions an analysis of `source` sees, and
+/// how many hard errors it reported.
+fn analyze(
+  cache: &Arc<ModuleCache>,
+  source: &str,
+  path: &Path,
+) -> (Vec<String>, usize) {
+  let mut compiler = Compiler::new();
+
+  compiler.set_module_cache(Arc::clone(cache));
+
+  let (semantic, _tok, _par, session, _file_table) =
+    compiler.analyze_source(source, path);
+
+  let funs = semantic
+    .funs
+    .iter()
+    .map(|fun| session.interner.get(fun.name).to_string())
+    .collect();
+
+  let errors = compiler
+    .reporter_errors()
+    .iter()
+    .filter(|error| matches!(error.severity(), Severity::Error))
+    .count();
+
+  (funs, errors)
+}
+
+/// A second analysis with the same `load`s starts from the
+/// cached modules and sees what a fresh one would. Changing a
+/// loaded pack drops the checkpoints that read it, so the
+/// next analysis picks the change up.
+#[test]
+fn module_cache_reuses_until_a_pack_changes() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+
+  fs::write(dir.join("lib.zo"), "pub pack shapes;\n").unwrap();
+  fs::write(
+    dir.join("shapes.zo"),
+    "pub fun area(w: int, h: int) -> int {\n  return w * h;\n}\n",
+  )
+  .unwrap();
+
+  let main = dir.join("main.zo");
+  let cache = Arc::new(ModuleCache::new());
+
+  let first =
+    "load shapes::*;\n\nfun main() {\n  imu a: int = area(2, 3);\n}\n";
+  let (funs, errors) = analyze(&cache, first, &main);
+
+  assert_eq!(errors, 0);
+  assert!(funs.iter().any(|fun| fun == "area"));
+
+  let second =
+    "load shapes::*;\n\nfun main() {\n  imu b: int = area(4, 5) + 1;\n}\n";
+  let (funs, errors) = analyze(&cache, second, &main);
+
+  assert_eq!(errors, 0);
+  assert!(funs.iter().any(|fun| fun == "area"));
+
+  fs::write(
+    dir.join("shapes.zo"),
+    "pub fun size(w: int, h: int) -> int {\n  return w * h;\n}\n",
+  )
+  .unwrap();
+
+  assert_eq!(cache.invalidate(&dir.join("shapes.zo")), 1);
+
+  let third =
+    "load shapes::*;\n\nfun main() {\n  imu a: int = size(2, 3);\n}\n";
+  let (funs, errors) = analyze(&cache, third, &main);
+
+  assert_eq!(errors, 0);
+  assert!(funs.iter().any(|fun| fun == "size"));
+  assert!(!funs.iter().any(|fun| fun == "area"));
+}
+
+/// A changed pack is picked up on lookup, without anyone
+/// telling the cache about it — its modification time and
+/// length moved.
+#[test]
+fn module_cache_checks_packs_on_lookup() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+
+  fs::write(dir.join("lib.zo"), "pub pack shapes;\n").unwrap();
+  fs::write(
+    dir.join("shapes.zo"),
+    "pub fun area(w: int, h: int) -> int {\n  return w * h;\n}\n",
+  )
+  .unwrap();
+
+  let main = dir.join("main.zo");
+  let cache = Arc::new(ModuleCache::new());
+
+  let first =
+    "load shapes::*;\n\nfun main() {\n  imu a: int = area(2, 3);\n}\n";
+  let (_funs, errors) = analyze(&cache, first, &main);
+
+  assert_eq!(errors, 0);
+
+  fs::write(
+    dir.join("shapes.zo"),
+    "pub fun size(w: int, h: int) -> int {\n  return w * h;\n}\n",
+  )
+  .unwrap();
+
+  let second =
+    "load shapes::*;\n\nfun main() {\n  imu a: int = size(2, 3);\n}\n";
+  let (funs, errors) = analyze(&cache, second, &main);
+
+  assert_eq!(errors, 0);
+  assert!(funs.iter().any(|fun| fun == "size"));
+  assert!(!funs.iter().any(|fun| fun == "area"));
+}
+
+/// A file edited after the resolution read it gets no stamp,
+/// so its checkpoint never passes for fresh on lookup.
+#[test]
+fn module_cache_stamps_only_the_content_read() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("shapes.zo");
+  let read = "pub fun area(w: int, h: int) -> int {\n  return w * h;\n}\n";
+
+  fs::write(&path, read).unwrap();
+
+  assert!(stamp_if_unchanged(&path, content_hash(read)).is_some());
+
+  fs::write(&path, "pub fun size() -> int {\n  return 0;\n}\n").unwrap();
+
+  assert!(stamp_if_unchanged(&path, content_hash(read)).is_none());
+}
+
+/// An open buffer stands in for the file on disk until it is
+/// closed, and opening or closing it drops the checkpoints
+/// that read the other content.
+#[test]
+fn module_cache_reads_open_buffers() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let shapes = dir.join("shapes.zo");
+
+  fs::write(dir.join("lib.zo"), "pub pack shapes;\n").unwrap();
+  fs::write(
+    &shapes,
+    "pub fun area(w: int, h: int) -> int {\n  return w * h;\n}\n",
+  )
+  .unwrap();
+
+  let main = dir.join("main.zo");
+  let cache = Arc::new(ModuleCache::new());
+  let source = "load shapes::*;\n\nfun main() {}\n";
+  let (funs, _errors) = analyze(&cache, source, &main);
+
+  assert!(funs.iter().any(|fun| fun == "area"));
+
+  let dropped = cache.open(
+    &shapes,
+    "pub fun size(w: int, h: int) -> int {\n  return w * h;\n}\n",
+  );
+
+  assert_eq!(dropped, 1);
+
+  let (funs, _errors) = analyze(&cache, source, &main);
+
+  assert!(funs.iter().any(|fun| fun == "size"));
+  assert!(!funs.iter().any(|fun| fun == "area"));
+
+  // Saving the buffer's text leaves its checkpoints be.
+  assert_eq!(cache.invalidate(&shapes), 0);
+  assert_eq!(cache.close(&shapes), 1);
+
+  let (funs, _errors) = analyze(&cache, source, &main);
+
+  assert!(funs.iter().any(|fun| fun == "area"));
+  assert!(!funs.iter().any(|fun| fun == "size"));
+}
+
+/// Checkpoints are per pack: a file whose `load`s share a
+/// prefix with an earlier one resumes after that prefix, and
+/// changing a pack keeps the checkpoints taken before it.
+#[test]
+fn module_cache_resumes_after_the_shared_packs() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+
+  fs::write(
+    dir.join("lib.zo"),
+    "pub pack a;\npub pack b;\npub pack c;\n",
+  )
+  .unwrap();
+
+  for pack in ["a", "b", "c"] {
+    fs::write(
+      dir.join(format!("{pack}.zo")),
+      format!("pub fun {pack}_one() -> int {{\n  return 1;\n}}\n"),
+    )
+    .unwrap();
+  }
+
+  let main = dir.join("main.zo");
+  let cache = Arc::new(ModuleCache::new());
+  let source = "load a::*;\nload b::*;\n\nfun main() {\n  imu x: int = \
+                a_one() + b_one();\n}\n";
+  let (_funs, errors) = analyze(&cache, source, &main);
+
+  assert_eq!(errors, 0);
+
+  let root = EnvRoot {
+    search_paths: Compiler::new().search_paths().to_vec(),
+    lib: Some(dir.join("lib.zo")),
+  };
+  let loads = |names: &[&str]| {
+    names
+      .iter()
+      .map(|name| name.to_string())
+      .collect::<Vec<_>>()
+  };
+
+  let resumed = cache.resume(&root, &loads(&["a", "c"])).unwrap();
+
+  assert_eq!(resumed.done, resumed.preload_len + 1);
+
+  let resumed = cache.resume(&root, &loads(&["a", "b"])).unwrap();
+
+  assert_eq!(resumed.done, resumed.preload_len + 2);
+
+  fs::write(
+    dir.join("b.zo"),
+    "pub fun b_two() -> int {\n  return 2;\n}\n",
+  )
+  .unwrap();
+
+  let resumed = cache.resume(&root, &loads(&["a", "b"])).unwrap();
+
+  assert_eq!(resumed.done, resumed.preload_len + 1);
+
+  let source = "load a::*;\nload b::*;\n\nfun main() {\n  imu x: int = \
+                a_one() + b_two();\n}\n";
+  let (funs, errors) = analyze(&cache, source, &main);
+
+  assert_eq!(errors, 0);
+  assert!(funs.iter().any(|fun| fun == "b_two"));
+
+  fs::write(
+    dir.join("a.zo"),
+    "pub fun a_two() -> int {\n  return 2;\n}\n",
+  )
+  .unwrap();
+
+  let resumed = cache.resume(&root, &loads(&["a", "b"])).unwrap();
+
+  assert_eq!(resumed.done, resumed.preload_len);
+}
+
+/// A root keeps at most `MAX_CHECKPOINTS`: the ones least
+/// recently used make room, while the preload checkpoint every
+/// analysis resumes from stays.
+#[test]
+fn module_cache_evicts_the_least_recently_used() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let packs = (0..MAX_CHECKPOINTS)
+    .map(|n| format!("p{n}"))
+    .collect::<Vec<_>>();
+
+  fs::write(
+    dir.join("lib.zo"),
+    packs
+      .iter()
+      .map(|pack| format!("pub pack {pack};\n"))
+      .collect::<String>(),
+  )
+  .unwrap();
+
+  for pack in &packs {
+    fs::write(
+      dir.join(format!("{pack}.zo")),
+      format!("pub fun {pack}_one() -> int {{\n  return 1;\n}}\n"),
+    )
+    .unwrap();
+  }
+
+  let main = dir.join("main.zo");
+  let cache = Arc::new(ModuleCache::new());
+
+  for pack in &packs {
+    let source = format!("load {pack}::*;\n\nfun main() {{}}\n");
+    let (_funs, errors) = analyze(&cache, &source, &main);
+
+    assert_eq!(errors, 0);
+  }
+
+  let root = EnvRoot {
+    search_paths: Compiler::new().search_paths().to_vec(),
+    lib: Some(dir.join("lib.zo")),
+  };
+
+  let first = cache.resume(&root, &[packs[0].clone()]).unwrap();
+
+  assert_eq!(first.done, first.preload_len);
+
+  let last = cache.resume(&root, &[packs[MAX_CHECKPOINTS - 1].clone()]);
+
+  assert_eq!(last.unwrap().done, first.preload_len + 1);
+
+  let second = cache.resume(&root, &[packs[1].clone()]).unwrap();
+
+  assert_eq!(second.done, first.preload_len + 1);
+}
+
+/// A cancelled analysis compiles no pack and leaves no
+/// checkpoint behind.
+#[test]
+fn cancelled_analysis_leaves_no_checkpoint() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+
+  fs::write(dir.join("lib.zo"), "pub pack shapes;\n").unwrap();
+  fs::write(
+    dir.join("shapes.zo"),
+    "pub fun area(w: int, h: int) -> int {\n  return w * h;\n}\n",
+  )
+  .unwrap();
+
+  let main = dir.join("main.zo");
+  let cache = Arc::new(ModuleCache::new());
+  let mut compiler = Compiler::new();
+
+  compiler.set_module_cache(Arc::clone(&cache));
+  compiler.set_cancel(Arc::new(AtomicBool::new(true)));
+
+  let (semantic, _tok, _par, _session, file_table) = compiler.analyze_source(
+    "load shapes::*;\n\nfun main() {\n  imu a: int = area(2, 3);\n}\n",
+    &main,
+  );
+
+  assert!(compiler.is_cancelled());
+  assert!(semantic.funs.is_empty());
+  assert!(
+    !file_table
+      .iter()
+      .any(|(path, _)| path.ends_with("shapes.zo"))
+  );
+
+  let root = EnvRoot {
+    search_paths: compiler.search_paths().to_vec(),
+    lib: Some(dir.join("lib.zo")),
+  };
+
+  assert!(cache.resume(&root, &[]).is_none());
+}
@@ -86,3 +86,46 @@ fn cross_module_error_carries_correct_file_id() {
     file_source.len(),
   );
 }
+
+/// `check` over several inputs keeps every file's source: an
+/// error in the first input must still resolve to it once the
+/// second one is analyzed.
+#[test]
+fn check_keeps_one_file_table_entry_per_input() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+
+  let first = dir.join("first.zo");
+  let second = dir.join("second.zo");
+
+  fs::write(&first, "fun main() {\n  imu x: str = 42;\n}\n").unwrap();
+  fs::write(&second, "fun main() {}\n").unwrap();
+
+  let files = [
+    (&first, fs::read_to_string(&first).unwrap()),
+    (&second, fs::read_to_string(&second).unwrap()),
+  ];
+
+  let mut compiler = Compiler::new();
+  let file_table = compiler.analyze_files(&files);
+
+  let error = compiler
+    .reporter_errors()
+    .iter()
+    .find(|e| matches!(e.severity(), Severity::Error))
+    .copied()
+    .expect("expected the type error in first.zo");
+
+  let fid = error.file_id().unwrap_or(0) as usize;
+
+  assert!(
+    file_table[fid].0.ends_with("first.zo"),
+    "error should point at first.zo, got: {:?}",
+    file_table[fid].0,
+  );
+  assert!(
+    file_table
+      .iter()
+      .any(|(path, _)| path.ends_with("second.zo"))
+  );
+}
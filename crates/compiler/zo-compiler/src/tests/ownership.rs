@@ -0,0 +1,53 @@
+use crate::Compiler;
+
+use zo_sir::Insn;
+
+use std::fs;
+
+/// A capturing closure handed to an `ffi` is released after
+/// the call unless the `ffi` is marked `%% owns_env.` — only
+/// then does the runtime free the env itself.
+#[test]
+fn closure_env_is_released_unless_the_ffi_owns_it() {
+  let dir = tempfile::tempdir().unwrap();
+  let main_path = dir.path().join("main.zo");
+
+  let source = "ffi show(s: str);\n\
+     %% user_data(env).\n\
+     ffi on_event(cb: Fn(), env: s64);\n\
+     %% user_data(env), owns_env.\n\
+     ffi hand_off(cb: Fn(), env: s64);\n\n\
+     fun main() {\n  \
+       imu label: str = \"hit\";\n  \
+       on_event(fn() { show(label); });\n  \
+       hand_off(fn() { show(label); });\n\
+     }\n";
+
+  fs::write(&main_path, source).unwrap();
+
+  let mut compiler = Compiler::new();
+
+  let (semantic, _tok, _par, _session, _file_table) =
+    compiler.analyze_source(source, &main_path);
+
+  let insns = &semantic.sir.instructions;
+
+  let envs = insns
+    .iter()
+    .filter_map(|insn| match insn {
+      Insn::ClosureEnv { dst, .. } => Some(*dst),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+
+  let frees = insns
+    .iter()
+    .filter_map(|insn| match insn {
+      Insn::ClosureEnvFree { env } => Some(*env),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+
+  assert_eq!(envs.len(), 2, "one env per capturing callback");
+  assert_eq!(frees, [envs[0]], "only the borrowed env is freed");
+}
@@ -0,0 +1,134 @@
+//! The native debugger process, driven over MI.
+
+use crate::mi;
+use crate::server::Input;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+/// Which debugger drives the program.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Flavor {
+  Gdb,
+  /// LLDB through `lldb-mi`.
+  Lldb,
+}
+
+impl Flavor {
+  /// `gdb` or `lldb` from the launch configuration; without
+  /// one, gdb when it is on `PATH`, else lldb. Any other name
+  /// is an error.
+  pub(crate) fn pick(name: Option<&str>) -> Result<Self, String> {
+    match name {
+      Some("gdb") => Ok(Self::Gdb),
+      Some("lldb" | "lldb-mi") => Ok(Self::Lldb),
+      Some(name) => Err(format!(
+        "unknown debugger `{name}` — expected `gdb` or `lldb`"
+      )),
+      None if on_path("gdb") => Ok(Self::Gdb),
+      None if on_path("lldb-mi") => Ok(Self::Lldb),
+      None => Ok(Self::Gdb),
+    }
+  }
+
+  fn command(self) -> Command {
+    match self {
+      Self::Gdb => {
+        let mut command = Command::new("gdb");
+
+        command.args(["--interpreter=mi2", "--quiet", "--nx"]);
+        command
+      }
+      Self::Lldb => Command::new("lldb-mi"),
+    }
+  }
+}
+
+pub(crate) struct Debugger {
+  child: Child,
+  stdin: ChildStdin,
+  next_token: u64,
+}
+
+impl Debugger {
+  /// Starts the debugger — `path` overrides the executable —
+  /// and forwards every line it writes to `inputs`.
+  pub(crate) fn spawn(
+    flavor: Flavor,
+    path: Option<&str>,
+    inputs: Sender<Input>,
+  ) -> io::Result<Self> {
+    let mut command = match path {
+      Some(path) => {
+        let mut command = Command::new(path);
+
+        command.args(flavor.command().get_args());
+        command
+      }
+      None => flavor.command(),
+    };
+
+    let mut child = command
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::inherit())
+      .spawn()?;
+
+    let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take())
+    else {
+      return Err(io::Error::other("debugger without stdio"));
+    };
+
+    thread::spawn(move || {
+      let mut reader = BufReader::new(stdout);
+      let mut line = Vec::new();
+
+      loop {
+        line.clear();
+
+        match reader.read_until(b'\n', &mut line) {
+          Ok(0) | Err(_) => break,
+          Ok(_) => {
+            let record = mi::parse(&String::from_utf8_lossy(&line));
+
+            if inputs.send(Input::Mi(record)).is_err() {
+              return;
+            }
+          }
+        }
+      }
+
+      let _ = inputs.send(Input::DebuggerExited);
+    });
+
+    Ok(Self {
+      child,
+      stdin,
+      next_token: 0,
+    })
+  }
+
+  /// Sends `command` and returns the token its result record
+  /// will carry.
+  pub(crate) fn send(&mut self, command: &str) -> io::Result<u64> {
+    self.next_token += 1;
+
+    writeln!(self.stdin, "{}{command}", self.next_token)?;
+    self.stdin.flush()?;
+
+    Ok(self.next_token)
+  }
+
+  pub(crate) fn kill(&mut self) {
+    let _ = self.child.kill();
+    let _ = self.child.wait();
+  }
+}
+
+fn on_path(program: &str) -> bool {
+  std::env::var_os("PATH").is_some_and(|paths| {
+    std::env::split_paths(&paths).any(|dir| dir.join(program).is_file())
+  })
+}
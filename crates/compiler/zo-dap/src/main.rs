@@ -0,0 +1,65 @@
+//! zo-dap — a Debug Adapter Protocol server for zo programs.
+//!
+//! Speaks DAP over stdio and drives a locally installed gdb
+//! (or `lldb-mi`) over its MI interface against a debug build
+//! of a zo program. Variables show in their zo shape — a
+//! `str` as its text, an array, tuple, struct or enum as its
+//! elements, a `HashMap` as its entries — from the type table
+//! a debug build carries next to its DWARF.
+//!
+//! Launch configuration:
+//!
+//! ```json
+//! {
+//!   "type": "zo",
+//!   "request": "launch",
+//!   "program": "${workspaceFolder}/main",
+//!   "args": [],
+//!   "cwd": "${workspaceFolder}",
+//!   "debugger": "gdb",
+//!   "stopOnEntry": false
+//! }
+//! ```
+//!
+//! `debugger` is `gdb` or `lldb` (through `lldb-mi`) — any
+//! other name fails the launch — and defaults to whichever is
+//! on `PATH`; `debuggerPath` overrides the executable.
+//! Debug info comes from the Cranelift backend's output of a
+//! dev build (`zo build`, no `--release`). The ARM64 backend
+//! writes no DWARF, so `launch` refuses its binaries.
+
+mod debugger;
+mod mi;
+mod protocol;
+mod server;
+mod types;
+mod values;
+
+#[cfg(test)]
+mod tests;
+
+use server::{Input, Session};
+
+use std::io::{self, BufReader};
+use std::sync::mpsc;
+use std::thread;
+
+fn main() {
+  let (sender, inputs) = mpsc::channel();
+  let client = sender.clone();
+
+  thread::spawn(move || {
+    let mut reader = BufReader::new(io::stdin().lock());
+
+    loop {
+      let message = protocol::read_message(&mut reader).ok().flatten();
+      let done = message.is_none();
+
+      if client.send(Input::Client(message)).is_err() || done {
+        break;
+      }
+    }
+  });
+
+  Session::new(io::stdout().lock(), inputs, sender).run();
+}
@@ -0,0 +1,341 @@
+//! GDB/MI output records.
+//!
+//! Every line the debugger writes in MI mode is one record:
+//!
+//! - `^done,…` / `^error,…` — the result of a command,
+//!   prefixed with the token the command was sent with.
+//! - `*stopped,…` / `*running,…` — execution state changes.
+//! - `=…` — notifications (`=thread-created`, …).
+//! - `~"…"`, `@"…"`, `&"…"` — console, target and log text.
+//! - `(gdb)` — the prompt.
+//!
+//! Anything else is the program's own output, passed through
+//! as [`Record::Output`].
+
+/// One MI value — a C string, a `{…}` tuple or a `[…]` list.
+/// A list of named results keeps its values and drops the
+/// names, which repeat (`[frame={…},frame={…}]`).
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum MiValue {
+  Str(String),
+  Tuple(Vec<(String, MiValue)>),
+  List(Vec<MiValue>),
+}
+
+impl MiValue {
+  /// The field `name` of a tuple.
+  pub(crate) fn get(&self, name: &str) -> Option<&MiValue> {
+    match self {
+      Self::Tuple(fields) => fields
+        .iter()
+        .find(|(field, _)| field == name)
+        .map(|(_, value)| value),
+      _ => None,
+    }
+  }
+
+  /// The text of the string field `name` of a tuple.
+  pub(crate) fn str(&self, name: &str) -> Option<&str> {
+    match self.get(name)? {
+      Self::Str(text) => Some(text),
+      _ => None,
+    }
+  }
+
+  /// The items of the list field `name` of a tuple — empty
+  /// when absent.
+  pub(crate) fn list(&self, name: &str) -> &[MiValue] {
+    match self.get(name) {
+      Some(Self::List(items)) => items,
+      _ => &[],
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum AsyncKind {
+  /// `*` — the program started or stopped.
+  Exec,
+  /// `+` — progress of a slow operation.
+  Status,
+  /// `=` — any other state change.
+  Notify,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StreamKind {
+  /// `~` — the debugger's console output.
+  Console,
+  /// `@` — output of the program, for remote targets.
+  Target,
+  /// `&` — the debugger's internal log.
+  Log,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Record {
+  /// `^class` — `done`, `running`, `connected`, `error` or
+  /// `exit` — with its results as a tuple.
+  Result {
+    token: Option<u64>,
+    class: String,
+    results: MiValue,
+  },
+  Async {
+    kind: AsyncKind,
+    class: String,
+    results: MiValue,
+  },
+  Stream {
+    kind: StreamKind,
+    text: String,
+  },
+  Prompt,
+  /// A line outside MI — the program printing.
+  Output(String),
+}
+
+/// Parses one output line.
+pub(crate) fn parse(line: &str) -> Record {
+  let line = line.trim_end_matches(['\r', '\n']);
+
+  if line.trim_end() == "(gdb)" {
+    return Record::Prompt;
+  }
+
+  let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+  let token = line[..digits].parse().ok();
+  let rest = &line[digits..];
+
+  let mut parser = Parser { text: rest, pos: 1 };
+
+  let record = match rest.as_bytes().first() {
+    Some(b'^') => {
+      parser
+        .class_and_results()
+        .map(|(class, results)| Record::Result {
+          token,
+          class,
+          results,
+        })
+    }
+    Some(b'*' | b'+' | b'=') if digits == 0 || token.is_some() => {
+      let kind = match rest.as_bytes()[0] {
+        b'*' => AsyncKind::Exec,
+        b'+' => AsyncKind::Status,
+        _ => AsyncKind::Notify,
+      };
+
+      parser
+        .class_and_results()
+        .map(|(class, results)| Record::Async {
+          kind,
+          class,
+          results,
+        })
+    }
+    Some(b'~' | b'@' | b'&') if digits == 0 => {
+      let kind = match rest.as_bytes()[0] {
+        b'~' => StreamKind::Console,
+        b'@' => StreamKind::Target,
+        _ => StreamKind::Log,
+      };
+
+      parser
+        .c_string()
+        .filter(|_| parser.at_end())
+        .map(|text| Record::Stream { kind, text })
+    }
+    _ => None,
+  };
+
+  record.unwrap_or_else(|| Record::Output(line.to_string()))
+}
+
+/// Quotes `text` as an MI C string, for command arguments.
+pub(crate) fn quote(text: &str) -> String {
+  let mut out = String::with_capacity(text.len() + 2);
+
+  out.push('"');
+
+  for ch in text.chars() {
+    match ch {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\t' => out.push_str("\\t"),
+      _ => out.push(ch),
+    }
+  }
+
+  out.push('"');
+  out
+}
+
+struct Parser<'a> {
+  text: &'a str,
+  pos: usize,
+}
+
+impl Parser<'_> {
+  fn peek(&self) -> Option<u8> {
+    self.text.as_bytes().get(self.pos).copied()
+  }
+
+  fn at_end(&self) -> bool {
+    self.pos >= self.text.len()
+  }
+
+  fn eat(&mut self, byte: u8) -> bool {
+    let eaten = self.peek() == Some(byte);
+
+    if eaten {
+      self.pos += 1;
+    }
+
+    eaten
+  }
+
+  /// `class(,name=value)*` up to the end of the line.
+  fn class_and_results(&mut self) -> Option<(String, MiValue)> {
+    let class = self.word();
+
+    if class.is_empty() {
+      return None;
+    }
+
+    let mut results = Vec::new();
+
+    while self.eat(b',') {
+      results.push(self.result()?);
+    }
+
+    self.at_end().then_some((class, MiValue::Tuple(results)))
+  }
+
+  fn word(&mut self) -> String {
+    let start = self.pos;
+
+    while let Some(byte) = self.peek()
+      && (byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+    {
+      self.pos += 1;
+    }
+
+    self.text[start..self.pos].to_string()
+  }
+
+  fn result(&mut self) -> Option<(String, MiValue)> {
+    let name = self.word();
+
+    if !self.eat(b'=') {
+      return None;
+    }
+
+    Some((name, self.value()?))
+  }
+
+  fn value(&mut self) -> Option<MiValue> {
+    match self.peek()? {
+      b'"' => self.c_string().map(MiValue::Str),
+      b'{' => {
+        self.pos += 1;
+
+        let mut fields = Vec::new();
+
+        if !self.eat(b'}') {
+          loop {
+            fields.push(self.result()?);
+
+            if self.eat(b'}') {
+              break;
+            }
+
+            if !self.eat(b',') {
+              return None;
+            }
+          }
+        }
+
+        Some(MiValue::Tuple(fields))
+      }
+      b'[' => {
+        self.pos += 1;
+
+        let mut items = Vec::new();
+
+        if !self.eat(b']') {
+          loop {
+            let item = match self.peek()? {
+              b'"' | b'{' | b'[' => self.value()?,
+              _ => self.result()?.1,
+            };
+
+            items.push(item);
+
+            if self.eat(b']') {
+              break;
+            }
+
+            if !self.eat(b',') {
+              return None;
+            }
+          }
+        }
+
+        Some(MiValue::List(items))
+      }
+      _ => None,
+    }
+  }
+
+  /// A `"…"` string with C escapes; the cursor sits on the
+  /// opening quote.
+  fn c_string(&mut self) -> Option<String> {
+    if !self.eat(b'"') {
+      return None;
+    }
+
+    let mut bytes = Vec::new();
+
+    loop {
+      let byte = self.peek()?;
+
+      self.pos += 1;
+
+      match byte {
+        b'"' => break,
+        b'\\' => {
+          let escaped = self.peek()?;
+
+          self.pos += 1;
+
+          match escaped {
+            b'n' => bytes.push(b'\n'),
+            b't' => bytes.push(b'\t'),
+            b'r' => bytes.push(b'\r'),
+            b'0'..=b'7' => {
+              let mut code = u32::from(escaped - b'0');
+
+              for _ in 0..2 {
+                match self.peek() {
+                  Some(digit @ b'0'..=b'7') => {
+                    code = code * 8 + u32::from(digit - b'0');
+                    self.pos += 1;
+                  }
+                  _ => break,
+                }
+              }
+
+              bytes.push(code as u8);
+            }
+            other => bytes.push(other),
+          }
+        }
+        other => bytes.push(other),
+      }
+    }
+
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+  }
+}
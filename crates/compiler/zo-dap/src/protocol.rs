@@ -0,0 +1,104 @@
+//! DAP wire format — `Content-Length`-framed JSON over stdio.
+
+use serde_json::{Value, json};
+
+use std::io::{self, BufRead, Write};
+
+/// Reads one message. `None` once the client closed the
+/// stream.
+pub(crate) fn read_message(
+  reader: &mut impl BufRead,
+) -> io::Result<Option<Value>> {
+  let mut length = None;
+  let mut line = String::new();
+
+  loop {
+    line.clear();
+
+    if reader.read_line(&mut line)? == 0 {
+      return Ok(None);
+    }
+
+    let header = line.trim_end();
+
+    if header.is_empty() {
+      break;
+    }
+
+    if let Some((name, value)) = header.split_once(':')
+      && name.eq_ignore_ascii_case("content-length")
+    {
+      length = value.trim().parse::<usize>().ok();
+    }
+  }
+
+  let Some(length) = length else {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      "message without Content-Length",
+    ));
+  };
+
+  let mut body = vec![0; length];
+
+  reader.read_exact(&mut body)?;
+
+  serde_json::from_slice(&body)
+    .map(Some)
+    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Writes one message.
+pub(crate) fn write_message(
+  writer: &mut impl Write,
+  message: &Value,
+) -> io::Result<()> {
+  let body = message.to_string();
+
+  write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+  writer.flush()
+}
+
+/// Numbers outgoing messages.
+#[derive(Default)]
+pub(crate) struct Seq(u64);
+
+impl Seq {
+  fn next(&mut self) -> u64 {
+    self.0 += 1;
+    self.0
+  }
+
+  /// A successful response to `request`.
+  pub(crate) fn response(&mut self, request: &Value, body: Value) -> Value {
+    json!({
+      "seq": self.next(),
+      "type": "response",
+      "request_seq": request["seq"],
+      "command": request["command"],
+      "success": true,
+      "body": body,
+    })
+  }
+
+  /// A failed response to `request`, shown to the user.
+  pub(crate) fn error(&mut self, request: &Value, message: &str) -> Value {
+    json!({
+      "seq": self.next(),
+      "type": "response",
+      "request_seq": request["seq"],
+      "command": request["command"],
+      "success": false,
+      "message": message,
+    })
+  }
+
+  pub(crate) fn event(&mut self, event: &str, body: Value) -> Value {
+    json!({
+      "seq": self.next(),
+      "type": "event",
+      "event": event,
+      "body": body,
+    })
+  }
+}
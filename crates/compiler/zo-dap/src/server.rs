@@ -0,0 +1,709 @@
+//! The debug session — DAP requests in, MI commands out.
+//!
+//! One thread owns all state. The client's messages and the
+//! debugger's records arrive on one channel; a request that
+//! needs a command's result sends it and pumps the channel
+//! until the result comes back, queueing whatever else shows
+//! up meanwhile.
+
+use crate::debugger::{Debugger, Flavor};
+use crate::mi::{self, AsyncKind, MiValue, Record, StreamKind};
+use crate::protocol::{Seq, write_message};
+use crate::types;
+use crate::values::{Memory, Slot, Values};
+
+use zo_codegen_backend::DebugTypes;
+
+use rustc_hash::FxHashMap as HashMap;
+use serde_json::{Value, json};
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::mpsc::{Receiver, Sender};
+
+/// What the session reacts to.
+pub(crate) enum Input {
+  /// A client message; `None` once the client is gone.
+  Client(Option<Value>),
+  Mi(Record),
+  DebuggerExited,
+}
+
+/// What a `variablesReference` points at. Valid until the
+/// program runs again.
+#[derive(Clone)]
+enum Handle {
+  /// The locals of frame `level` of `thread`.
+  Frame { thread: u64, level: u64 },
+  /// A zo value that expands.
+  Value(Slot),
+}
+
+pub(crate) struct Session<W> {
+  out: W,
+  seq: Seq,
+  inputs: Receiver<Input>,
+  sender: Sender<Input>,
+  /// Inputs that arrived while a command was in flight.
+  pending: VecDeque<Input>,
+  debugger: Option<Debugger>,
+  flavor: Flavor,
+  types: Rc<DebugTypes>,
+  stop_on_entry: bool,
+  /// The next stop is the entry breakpoint `stopOnEntry`
+  /// planted.
+  entry_pending: bool,
+  /// Set by `pause`, so the `SIGINT` stop reads as a pause.
+  pausing: bool,
+  terminated: bool,
+  /// Source path → the debugger's breakpoint numbers in it.
+  breakpoints: HashMap<String, Vec<String>>,
+  /// Frame id - 1 → `(thread, level)`.
+  frames: Vec<(u64, u64)>,
+  /// `variablesReference` - 1 → what it shows.
+  handles: Vec<Handle>,
+}
+
+impl<W: Write> Session<W> {
+  pub(crate) fn new(
+    out: W,
+    inputs: Receiver<Input>,
+    sender: Sender<Input>,
+  ) -> Self {
+    Self {
+      out,
+      seq: Seq::default(),
+      inputs,
+      sender,
+      pending: VecDeque::new(),
+      debugger: None,
+      flavor: Flavor::Gdb,
+      types: Rc::default(),
+      stop_on_entry: false,
+      entry_pending: false,
+      pausing: false,
+      terminated: false,
+      breakpoints: HashMap::default(),
+      frames: Vec::new(),
+      handles: Vec::new(),
+    }
+  }
+
+  /// Serves until the client disconnects or goes away.
+  pub(crate) fn run(mut self) {
+    loop {
+      let input = match self.pending.pop_front() {
+        Some(input) => input,
+        None => match self.inputs.recv() {
+          Ok(input) => input,
+          Err(_) => break,
+        },
+      };
+
+      match input {
+        Input::Client(Some(message)) => {
+          if message["type"] == "request" && !self.request(&message) {
+            break;
+          }
+        }
+        Input::Client(None) => break,
+        Input::Mi(record) => self.record(record),
+        Input::DebuggerExited => self.terminate(),
+      }
+    }
+
+    if let Some(debugger) = &mut self.debugger {
+      debugger.kill();
+    }
+  }
+
+  fn send(&mut self, message: &Value) {
+    let _ = write_message(&mut self.out, message);
+  }
+
+  fn event(&mut self, event: &str, body: Value) {
+    let message = self.seq.event(event, body);
+
+    self.send(&message);
+  }
+
+  /// Handles one request. `false` ends the session.
+  fn request(&mut self, request: &Value) -> bool {
+    let command = request["command"].as_str().unwrap_or_default();
+    let args = &request["arguments"];
+
+    let result = match command {
+      "initialize" => Ok(json!({
+        "supportsConfigurationDoneRequest": true,
+      })),
+      "launch" => self.launch(args),
+      "setBreakpoints" => self.set_breakpoints(args),
+      "setExceptionBreakpoints" => Ok(json!({ "breakpoints": [] })),
+      "configurationDone" => self.configuration_done(),
+      "threads" => self.threads(),
+      "stackTrace" => self.stack_trace(args),
+      "scopes" => self.scopes(args),
+      "variables" => self.variables(args),
+      "continue" => self
+        .resume("-exec-continue")
+        .map(|_| json!({ "allThreadsContinued": true })),
+      "next" => self.step("-exec-next", args),
+      "stepIn" => self.step("-exec-step", args),
+      "stepOut" => self.step("-exec-finish", args),
+      "pause" => {
+        self.pausing = true;
+        self.command("-exec-interrupt").map(|_| json!({}))
+      }
+      "disconnect" | "terminate" => {
+        if let Some(debugger) = &mut self.debugger {
+          let _ = debugger.send("-gdb-exit");
+
+          debugger.kill();
+        }
+
+        self.debugger = None;
+
+        let response = self.seq.response(request, json!({}));
+
+        self.send(&response);
+
+        return command != "disconnect";
+      }
+      _ => Err(format!("unsupported request `{command}`")),
+    };
+
+    let response = match result {
+      Ok(body) => self.seq.response(request, body),
+      Err(message) => self.seq.error(request, &message),
+    };
+
+    self.send(&response);
+
+    if command == "launch" && self.debugger.is_some() {
+      self.event("initialized", json!({}));
+    }
+
+    true
+  }
+
+  /// Sends `command` to the debugger and waits for its
+  /// result.
+  fn command(&mut self, command: &str) -> Result<MiValue, String> {
+    let Some(debugger) = &mut self.debugger else {
+      return Err("no program is running".to_string());
+    };
+
+    let token = debugger.send(command).map_err(|error| error.to_string())?;
+
+    loop {
+      match self.inputs.recv() {
+        Ok(Input::Mi(Record::Result {
+          token: Some(got),
+          class,
+          results,
+        }))
+          if got == token =>
+        {
+          return if class == "error" {
+            Err(results.str("msg").unwrap_or("debugger error").to_string())
+          } else {
+            Ok(results)
+          };
+        }
+        Ok(Input::DebuggerExited) | Err(_) => {
+          self.pending.push_back(Input::DebuggerExited);
+
+          return Err("the debugger exited".to_string());
+        }
+        Ok(input) => self.pending.push_back(input),
+      }
+    }
+  }
+
+  fn launch(&mut self, args: &Value) -> Result<Value, String> {
+    let Some(program) = args["program"].as_str() else {
+      return Err("`program` is required".to_string());
+    };
+
+    let flavor = Flavor::pick(args["debugger"].as_str())?;
+    let types = types::load(Path::new(program))?;
+
+    self.flavor = flavor;
+    self.stop_on_entry = args["stopOnEntry"].as_bool().unwrap_or(false);
+
+    let debugger = Debugger::spawn(
+      self.flavor,
+      args["debuggerPath"].as_str(),
+      self.sender.clone(),
+    )
+    .map_err(|error| format!("cannot start the debugger: {error}"))?;
+
+    self.debugger = Some(debugger);
+    self.types = Rc::new(types);
+
+    if self.flavor == Flavor::Gdb {
+      let _ = self.command("-gdb-set mi-async on");
+    }
+
+    self.command(&format!("-file-exec-and-symbols {}", mi::quote(program)))?;
+
+    if let Some(cwd) = args["cwd"].as_str() {
+      self.command(&format!("-environment-cd {}", mi::quote(cwd)))?;
+    }
+
+    let mut arguments = args["args"]
+      .as_array()
+      .into_iter()
+      .flatten()
+      .filter_map(Value::as_str)
+      .map(shell_quote)
+      .collect::<Vec<_>>();
+
+    // The program would otherwise share gdb's stdin — the
+    // pipe this adapter writes commands to.
+    if self.flavor == Flavor::Gdb {
+      arguments.push("</dev/null".to_string());
+    }
+
+    if !arguments.is_empty() {
+      self.command(&format!("-exec-arguments {}", arguments.join(" ")))?;
+    }
+
+    Ok(json!({}))
+  }
+
+  fn set_breakpoints(&mut self, args: &Value) -> Result<Value, String> {
+    let Some(path) = args["source"]["path"].as_str() else {
+      return Err("`source.path` is required".to_string());
+    };
+
+    if let Some(old) = self.breakpoints.remove(path)
+      && !old.is_empty()
+    {
+      self.command(&format!("-break-delete {}", old.join(" ")))?;
+    }
+
+    let mut numbers = Vec::new();
+    let mut breakpoints = Vec::new();
+
+    for breakpoint in args["breakpoints"].as_array().into_iter().flatten() {
+      let line = breakpoint["line"].as_u64().unwrap_or(0);
+      let location = mi::quote(&format!("{path}:{line}"));
+
+      match self.command(&format!("-break-insert -f {location}")) {
+        Ok(results) => {
+          let bkpt = results.get("bkpt");
+          let number = bkpt.and_then(|bkpt| bkpt.str("number"));
+          let line = bkpt
+            .and_then(|bkpt| bkpt.str("line"))
+            .and_then(|line| line.parse::<u64>().ok())
+            .unwrap_or(line);
+
+          if let Some(number) = number {
+            numbers.push(number.to_string());
+          }
+
+          breakpoints.push(json!({
+            "id": number.and_then(|number| number.parse::<u64>().ok()),
+            "verified": true,
+            "line": line,
+          }));
+        }
+        Err(message) => breakpoints.push(json!({
+          "verified": false,
+          "line": line,
+          "message": message,
+        })),
+      }
+    }
+
+    self.breakpoints.insert(path.to_string(), numbers);
+
+    Ok(json!({ "breakpoints": breakpoints }))
+  }
+
+  fn configuration_done(&mut self) -> Result<Value, String> {
+    if self.stop_on_entry {
+      self.command("-break-insert -t main")?;
+      self.entry_pending = true;
+    }
+
+    self.resume("-exec-run")?;
+
+    Ok(json!({}))
+  }
+
+  fn resume(&mut self, command: &str) -> Result<MiValue, String> {
+    self.frames.clear();
+    self.handles.clear();
+    self.command(command)
+  }
+
+  fn step(&mut self, command: &str, args: &Value) -> Result<Value, String> {
+    let thread = args["threadId"].as_u64().unwrap_or(1);
+
+    self
+      .resume(&format!("{command} --thread {thread}"))
+      .map(|_| json!({}))
+  }
+
+  fn threads(&mut self) -> Result<Value, String> {
+    let threads = self
+      .command("-thread-info")
+      .map(|results| {
+        results
+          .list("threads")
+          .iter()
+          .filter_map(|thread| {
+            let id = thread.str("id")?.parse::<u64>().ok()?;
+            let name = thread
+              .str("name")
+              .or_else(|| thread.str("target-id"))
+              .unwrap_or("thread");
+
+            Some(json!({ "id": id, "name": name }))
+          })
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+
+    if threads.is_empty() {
+      return Ok(json!({ "threads": [{ "id": 1, "name": "main" }] }));
+    }
+
+    Ok(json!({ "threads": threads }))
+  }
+
+  fn stack_trace(&mut self, args: &Value) -> Result<Value, String> {
+    let thread = args["threadId"].as_u64().unwrap_or(1);
+    let results =
+      self.command(&format!("-stack-list-frames --thread {thread}"))?;
+
+    let frames = results
+      .list("stack")
+      .iter()
+      .map(|frame| {
+        let level = frame
+          .str("level")
+          .and_then(|level| level.parse::<u64>().ok())
+          .unwrap_or(0);
+
+        self.frames.push((thread, level));
+
+        let id = self.frames.len();
+        let name = frame.str("func").unwrap_or("??");
+        let line = frame
+          .str("line")
+          .and_then(|line| line.parse::<u64>().ok())
+          .unwrap_or(0);
+
+        let source = frame.str("fullname").map(|path| {
+          json!({
+            "name": frame.str("file").unwrap_or(path),
+            "path": path,
+          })
+        });
+
+        json!({
+          "id": id,
+          "name": name,
+          "source": source,
+          "line": line,
+          "column": if line == 0 { 0 } else { 1 },
+        })
+      })
+      .collect::<Vec<_>>();
+
+    Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+  }
+
+  fn scopes(&mut self, args: &Value) -> Result<Value, String> {
+    let id = args["frameId"].as_u64().unwrap_or(0) as usize;
+    let Some((thread, level)) = id
+      .checked_sub(1)
+      .and_then(|idx| self.frames.get(idx))
+      .copied()
+    else {
+      return Err("unknown frame".to_string());
+    };
+
+    self.handles.push(Handle::Frame { thread, level });
+
+    Ok(json!({
+      "scopes": [{
+        "name": "Locals",
+        "variablesReference": self.handles.len(),
+        "expensive": false,
+      }],
+    }))
+  }
+
+  fn variables(&mut self, args: &Value) -> Result<Value, String> {
+    let reference = args["variablesReference"].as_u64().unwrap_or(0) as usize;
+
+    let handle = reference
+      .checked_sub(1)
+      .and_then(|idx| self.handles.get(idx))
+      .cloned();
+
+    let named = match handle {
+      Some(Handle::Frame { thread, level }) => {
+        let results = self.command(&format!(
+          "-stack-list-variables --thread {thread} --frame {level} \
+           --simple-values"
+        ))?;
+
+        results
+          .list("variables")
+          .iter()
+          .map(|var| {
+            (
+              var.str("name").unwrap_or_default().to_string(),
+              var.str("type").unwrap_or_default().to_string(),
+              var.str("value").map(str::to_string),
+            )
+          })
+          .collect::<Vec<_>>()
+          .into_iter()
+          .map(|(name, ty, value)| self.variable(name, ty, value))
+          .collect::<Vec<_>>()
+      }
+      Some(Handle::Value(slot)) => {
+        let types = Rc::clone(&self.types);
+        let children = Values::new(&types)
+          .show(&mut MiMemory(self), &slot)
+          .children;
+
+        children
+          .into_iter()
+          .map(|(name, child)| self.shown(name, child))
+          .collect()
+      }
+      None => return Err("unknown variables reference".to_string()),
+    };
+
+    Ok(json!({ "variables": named }))
+  }
+
+  /// A local as the debugger listed it — shown in its zo
+  /// shape when its type is a zo type.
+  fn variable(
+    &mut self,
+    name: String,
+    ty: String,
+    value: Option<String>,
+  ) -> Value {
+    let types = Rc::clone(&self.types);
+    let values = Values::new(&types);
+
+    let word = value
+      .as_deref()
+      .filter(|_| values.knows(&ty))
+      .and_then(|value| values.word_from_debugger(&ty, value));
+
+    match word {
+      Some(word) => self.shown(name, Slot { ty, word }),
+      None => json!({
+        "name": name,
+        "value": value.unwrap_or_else(|| "<optimized out>".to_string()),
+        "type": ty,
+        "variablesReference": 0,
+      }),
+    }
+  }
+
+  fn shown(&mut self, name: String, slot: Slot) -> Value {
+    let types = Rc::clone(&self.types);
+    let shown = Values::new(&types).show(&mut MiMemory(self), &slot);
+
+    let reference = if shown.children.is_empty() {
+      0
+    } else {
+      self.handles.push(Handle::Value(slot.clone()));
+      self.handles.len()
+    };
+
+    json!({
+      "name": name,
+      "value": shown.value,
+      "type": slot.ty,
+      "variablesReference": reference,
+    })
+  }
+
+  /// Reacts to a record the debugger wrote on its own.
+  fn record(&mut self, record: Record) {
+    match record {
+      Record::Async {
+        kind: AsyncKind::Exec,
+        class,
+        results,
+      } if class == "stopped" => self.stopped(&results),
+      Record::Async {
+        kind: AsyncKind::Notify,
+        class,
+        results,
+      } if class == "thread-created" || class == "thread-exited" => {
+        if let Some(id) =
+          results.str("id").and_then(|id| id.parse::<u64>().ok())
+        {
+          let reason = if class == "thread-created" {
+            "started"
+          } else {
+            "exited"
+          };
+
+          self.event("thread", json!({ "reason": reason, "threadId": id }));
+        }
+      }
+      Record::Stream {
+        kind: StreamKind::Console,
+        text,
+      } => {
+        self.event("output", json!({ "category": "console", "output": text }))
+      }
+      Record::Stream {
+        kind: StreamKind::Target,
+        text,
+      } => {
+        self.event("output", json!({ "category": "stdout", "output": text }))
+      }
+      Record::Output(line) => self.event(
+        "output",
+        json!({ "category": "stdout", "output": format!("{line}\n") }),
+      ),
+      _ => {}
+    }
+  }
+
+  fn stopped(&mut self, results: &MiValue) {
+    self.frames.clear();
+    self.handles.clear();
+
+    let reason = results.str("reason").unwrap_or_default();
+
+    if reason.starts_with("exited") {
+      // gdb prints the exit code in octal.
+      let code = results
+        .str("exit-code")
+        .and_then(|code| i64::from_str_radix(code, 8).ok())
+        .unwrap_or(0);
+
+      self.event("exited", json!({ "exitCode": code }));
+      self.terminate();
+
+      return;
+    }
+
+    let thread = results
+      .str("thread-id")
+      .and_then(|id| id.parse::<u64>().ok())
+      .unwrap_or(1);
+
+    let (reason, description) = if self.entry_pending {
+      self.entry_pending = false;
+      ("entry", None)
+    } else {
+      match reason {
+        "breakpoint-hit" => ("breakpoint", None),
+        "end-stepping-range" | "function-finished" | "location-reached" => {
+          ("step", None)
+        }
+        "signal-received" if self.pausing => ("pause", None),
+        "signal-received" => ("exception", results.str("signal-meaning")),
+        _ => ("pause", None),
+      }
+    };
+
+    let mut body = json!({
+      "reason": reason,
+      "threadId": thread,
+      "allThreadsStopped": true,
+    });
+
+    if let Some(description) = description {
+      body["description"] = json!(description);
+    }
+
+    self.pausing = false;
+    self.event("stopped", body);
+  }
+
+  fn terminate(&mut self) {
+    if !self.terminated {
+      self.terminated = true;
+      self.event("terminated", json!({}));
+    }
+  }
+}
+
+/// The debuggee's memory, read through the session's
+/// debugger.
+struct MiMemory<'a, W>(&'a mut Session<W>);
+
+impl<W: Write> Memory for MiMemory<'_, W> {
+  fn read(&mut self, addr: u64, len: usize) -> Option<Vec<u8>> {
+    if len == 0 {
+      return Some(Vec::new());
+    }
+
+    let results = self
+      .0
+      .command(&format!("-data-read-memory-bytes {addr:#x} {len}"))
+      .ok()?;
+
+    let mut bytes = Vec::with_capacity(len);
+
+    for block in results.list("memory") {
+      let contents = block.str("contents")?;
+
+      for idx in (0..contents.len()).step_by(2) {
+        bytes.push(u8::from_str_radix(contents.get(idx..idx + 2)?, 16).ok()?);
+      }
+    }
+
+    (bytes.len() == len).then_some(bytes)
+  }
+
+  /// Calls `zo_map_debug` in the stopped debuggee — the
+  /// `zo-runtime` library a map-using build links against
+  /// defines it.
+  fn map_entries(
+    &mut self,
+    map: u64,
+    key_fmt: u32,
+    val_fmt: u32,
+  ) -> Option<String> {
+    let call = format!(
+      "((char *(*)(void *, unsigned char, unsigned char)) zo_map_debug)\
+       ({map:#x}, {key_fmt}, {val_fmt})"
+    );
+    let results = self
+      .0
+      .command(&format!("-data-evaluate-expression {}", mi::quote(&call)))
+      .ok()?;
+
+    // `0x5555… "{1: 2}"` — the string after the pointer.
+    let value = results.str("value")?;
+    let start = value.find('"')?;
+    let end = value.rfind('"')?;
+
+    (end > start).then(|| value[start + 1..end].to_string())
+  }
+}
+
+/// Quotes `arg` for the shell the debugger starts the program
+/// through.
+fn shell_quote(arg: &str) -> String {
+  if !arg.is_empty()
+    && arg
+      .bytes()
+      .all(|byte| byte.is_ascii_alphanumeric() || b"-_./=:,".contains(&byte))
+  {
+    return arg.to_string();
+  }
+
+  format!("'{}'", arg.replace('\'', r"'\''"))
+}
@@ -0,0 +1,6 @@
+pub(crate) mod mi;
+pub(crate) mod protocol;
+// The fake debugger is a shell script.
+#[cfg(unix)]
+pub(crate) mod server;
+pub(crate) mod values;
@@ -0,0 +1,112 @@
+use crate::mi::{AsyncKind, MiValue, Record, StreamKind, parse, quote};
+
+fn s(text: &str) -> MiValue {
+  MiValue::Str(text.to_string())
+}
+
+#[test]
+fn parses_result_with_token() {
+  let record = parse("12^done,bkpt={number=\"1\",line=\"3\"}\n");
+
+  let Record::Result {
+    token,
+    class,
+    results,
+  } = record
+  else {
+    panic!("not a result: {record:?}");
+  };
+
+  assert_eq!(token, Some(12));
+  assert_eq!(class, "done");
+
+  let bkpt = results.get("bkpt").unwrap();
+
+  assert_eq!(bkpt.str("number"), Some("1"));
+  assert_eq!(bkpt.str("line"), Some("3"));
+}
+
+#[test]
+fn parses_stopped_event() {
+  let record = parse(
+    "*stopped,reason=\"breakpoint-hit\",thread-id=\"1\",\
+     frame={func=\"main\",args=[]}",
+  );
+
+  let Record::Async {
+    kind,
+    class,
+    results,
+  } = record
+  else {
+    panic!("not async: {record:?}");
+  };
+
+  assert_eq!(kind, AsyncKind::Exec);
+  assert_eq!(class, "stopped");
+  assert_eq!(results.str("reason"), Some("breakpoint-hit"));
+  assert_eq!(results.get("frame").unwrap().list("args"), &[]);
+}
+
+#[test]
+fn list_of_results_keeps_values() {
+  let record = parse(
+    "3^done,stack=[frame={level=\"0\",func=\"add\"},\
+     frame={level=\"1\",func=\"main\"}]",
+  );
+
+  let Record::Result { results, .. } = record else {
+    panic!("not a result");
+  };
+
+  let funcs = results
+    .list("stack")
+    .iter()
+    .map(|frame| frame.str("func").unwrap())
+    .collect::<Vec<_>>();
+
+  assert_eq!(funcs, ["add", "main"]);
+}
+
+#[test]
+fn list_of_values() {
+  let Record::Result { results, .. } = parse("^done,ids=[\"1\",\"2\"]") else {
+    panic!("not a result");
+  };
+
+  assert_eq!(results.list("ids"), &[s("1"), s("2")]);
+}
+
+#[test]
+fn unescapes_stream_text() {
+  assert_eq!(
+    parse(r#"~"say \"hi\"\n\t\101""#),
+    Record::Stream {
+      kind: StreamKind::Console,
+      text: "say \"hi\"\n\tA".to_string(),
+    }
+  );
+}
+
+#[test]
+fn program_output_passes_through() {
+  assert_eq!(
+    parse("hello, zo\n"),
+    Record::Output("hello, zo".to_string())
+  );
+  assert_eq!(parse("42"), Record::Output("42".to_string()));
+  assert_eq!(parse("(gdb) \n"), Record::Prompt);
+}
+
+#[test]
+fn malformed_record_is_output() {
+  assert_eq!(
+    parse("^done,x=\"open"),
+    Record::Output("^done,x=\"open".to_string())
+  );
+}
+
+#[test]
+fn quotes_arguments() {
+  assert_eq!(quote(r#"a "b"\c"#), r#""a \"b\"\\c""#);
+}
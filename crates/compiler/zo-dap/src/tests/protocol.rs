@@ -0,0 +1,34 @@
+use crate::protocol::{Seq, read_message, write_message};
+
+use serde_json::json;
+
+use std::io::BufReader;
+
+#[test]
+fn round_trips_framed_messages() {
+  let mut seq = Seq::default();
+  let request = json!({ "seq": 1, "command": "threads" });
+  let mut wire = Vec::new();
+
+  write_message(&mut wire, &seq.event("initialized", json!({}))).unwrap();
+  write_message(&mut wire, &seq.response(&request, json!({ "x": 1 }))).unwrap();
+
+  let mut reader = BufReader::new(wire.as_slice());
+  let event = read_message(&mut reader).unwrap().unwrap();
+  let response = read_message(&mut reader).unwrap().unwrap();
+
+  assert_eq!(event["event"], "initialized");
+  assert_eq!(event["seq"], 1);
+  assert_eq!(response["request_seq"], 1);
+  assert_eq!(response["command"], "threads");
+  assert_eq!(response["seq"], 2);
+  assert_eq!(response["body"]["x"], 1);
+  assert!(read_message(&mut reader).unwrap().is_none());
+}
+
+#[test]
+fn missing_length_is_an_error() {
+  let mut reader = BufReader::new(&b"X-Other: 1\r\n\r\n{}"[..]);
+
+  assert!(read_message(&mut reader).is_err());
+}
@@ -0,0 +1,389 @@
+use crate::protocol::{read_message, write_message};
+use crate::server::{Input, Session};
+
+use zo_codegen_backend::{DEBUG_TYPES_SECTION, DebugTy, DebugTypes};
+
+use object::write::Object;
+use object::{Architecture, BinaryFormat, Endianness, SectionKind};
+use serde_json::{Value, json};
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufReader, Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// What the fake debugger answers to each command starting
+/// with a prefix. A `^` line is a result record and gets the
+/// command's token; other lines go out as they are. Every
+/// other command gets a bare `^done`.
+const TRANSCRIPT: &[(&str, &[&str])] = &[
+  ("-break-insert", &[r#"^done,bkpt={number="1",line="4"}"#]),
+  (
+    "-exec-run",
+    &[
+      "^running",
+      r#"*stopped,reason="breakpoint-hit",thread-id="1""#,
+    ],
+  ),
+  (
+    "-exec-next",
+    &[
+      "^running",
+      r#"*stopped,reason="end-stepping-range",thread-id="1""#,
+    ],
+  ),
+  (
+    "-stack-list-frames",
+    &[
+      r#"^done,stack=[frame={level="0",func="main",file="main.zo",fullname="/src/main.zo",line="4"}]"#,
+    ],
+  ),
+  (
+    "-stack-list-variables",
+    &[
+      r#"^done,variables=[{name="n",type="int",value="42"},{name="s",type="str",value="0x1000"}]"#,
+    ],
+  ),
+  (
+    "-data-read-memory-bytes 0x1000 ",
+    &[r#"^done,memory=[{begin="0x1000",contents="0200000000000000"}]"#],
+  ),
+  (
+    "-data-read-memory-bytes 0x1008 ",
+    &[r#"^done,memory=[{begin="0x1008",contents="6869"}]"#],
+  ),
+];
+
+/// A shell script speaking MI from `TRANSCRIPT`, logging each
+/// command it reads to `commands.log` next to it.
+fn debugger(dir: &Path) -> PathBuf {
+  let quote = |text: &str| format!("'{}'", text.replace('\'', r"'\''"));
+  let mut script = String::from(
+    "#!/bin/sh\nwhile IFS= read -r line; do\n  \
+     printf '%s\\n' \"$line\" >> \"$(dirname \"$0\")/commands.log\"\n  \
+     token=${line%%[!0-9]*}\n  case \"${line#\"$token\"}\" in\n",
+  );
+
+  for (prefix, lines) in TRANSCRIPT {
+    script.push_str(&format!("    {}*)\n", quote(prefix)));
+
+    for line in *lines {
+      let token = if line.starts_with('^') {
+        "\"$token\""
+      } else {
+        ""
+      };
+
+      script
+        .push_str(&format!("      printf '%s\\n' {token}{}\n", quote(line)));
+    }
+
+    script.push_str("      ;;\n");
+  }
+
+  script.push_str(
+    "    *)\n      printf '%s\\n' \"$token^done\"\n      ;;\n  esac\n  \
+     echo '(gdb)'\ndone\n",
+  );
+
+  let path = dir.join("gdb");
+
+  fs::write(&path, script).unwrap();
+  fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+  path
+}
+
+/// An object file carrying `types` the way a debug build
+/// does, or no type table at all.
+fn program(dir: &Path, types: Option<&DebugTypes>) -> PathBuf {
+  let mut object =
+    Object::new(BinaryFormat::Elf, Architecture::X86_64, Endianness::Little);
+
+  if let Some(types) = types {
+    let section = object.add_section(
+      Vec::new(),
+      DEBUG_TYPES_SECTION.as_bytes().to_vec(),
+      SectionKind::Debug,
+    );
+
+    object.set_section_data(section, types.to_bytes(), 1);
+  }
+
+  let path = dir.join("main");
+
+  fs::write(&path, object.write().unwrap()).unwrap();
+
+  path
+}
+
+fn types() -> DebugTypes {
+  let mut types = DebugTypes::default();
+
+  types.types.insert(
+    "int".into(),
+    DebugTy::Int {
+      signed: true,
+      size: 8,
+    },
+  );
+  types.types.insert("str".into(), DebugTy::Str);
+
+  types
+}
+
+/// The session's output, as the client reads it.
+struct Pipe {
+  chunks: Receiver<Vec<u8>>,
+  bytes: VecDeque<u8>,
+}
+
+impl Read for Pipe {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.bytes.is_empty() {
+      let chunk = self
+        .chunks
+        .recv_timeout(Duration::from_secs(10))
+        .map_err(|error| io::Error::new(io::ErrorKind::TimedOut, error))?;
+
+      self.bytes.extend(chunk);
+    }
+
+    self.bytes.read(buf)
+  }
+}
+
+struct Sink(Sender<Vec<u8>>);
+
+impl Write for Sink {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let _ = self.0.send(buf.to_vec());
+
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+/// Drives a session on its own thread, the way an editor
+/// does.
+struct Client {
+  inputs: Sender<Input>,
+  out: BufReader<Pipe>,
+  seq: u64,
+  /// Messages read while waiting for another one.
+  seen: Vec<Value>,
+}
+
+impl Client {
+  fn start() -> Self {
+    let (sender, inputs) = mpsc::channel();
+    let (chunks, received) = mpsc::channel();
+    let session_sender = sender.clone();
+
+    thread::spawn(move || {
+      Session::new(Sink(chunks), inputs, session_sender).run();
+    });
+
+    Self {
+      inputs: sender,
+      out: BufReader::new(Pipe {
+        chunks: received,
+        bytes: VecDeque::new(),
+      }),
+      seq: 0,
+      seen: Vec::new(),
+    }
+  }
+
+  /// Sends `command` and returns its response.
+  fn request(&mut self, command: &str, arguments: Value) -> Value {
+    self.seq += 1;
+
+    let request = json!({
+      "seq": self.seq,
+      "type": "request",
+      "command": command,
+      "arguments": arguments,
+    });
+
+    // Framed and read back, as `main` does with stdin.
+    let mut framed = Vec::new();
+
+    write_message(&mut framed, &request).unwrap();
+
+    let message = read_message(&mut framed.as_slice()).unwrap();
+
+    self.inputs.send(Input::Client(message)).unwrap();
+
+    let seq = self.seq;
+
+    self.wait(|message| message["request_seq"] == seq)
+  }
+
+  /// The next `event` message.
+  fn event(&mut self, event: &str) -> Value {
+    self.wait(|message| message["event"] == event)
+  }
+
+  fn wait(&mut self, wanted: impl Fn(&Value) -> bool) -> Value {
+    if let Some(idx) = self.seen.iter().position(&wanted) {
+      return self.seen.remove(idx);
+    }
+
+    loop {
+      let message = read_message(&mut self.out).unwrap().unwrap();
+
+      if wanted(&message) {
+        return message;
+      }
+
+      self.seen.push(message);
+    }
+  }
+
+  fn has_seen(&self, event: &str) -> bool {
+    self.seen.iter().any(|message| message["event"] == event)
+  }
+}
+
+#[test]
+fn launch_break_step_and_show_variables() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let gdb = debugger(dir);
+  let program = program(dir, Some(&types()));
+  let mut client = Client::start();
+
+  let response = client.request("initialize", json!({}));
+
+  assert_eq!(response["success"], true);
+
+  let response = client.request(
+    "launch",
+    json!({
+      "program": program,
+      "debugger": "gdb",
+      "debuggerPath": gdb,
+    }),
+  );
+
+  assert_eq!(response["success"], true, "{response}");
+  client.event("initialized");
+
+  let response = client.request(
+    "setBreakpoints",
+    json!({
+      "source": { "path": "/src/main.zo" },
+      "breakpoints": [{ "line": 3 }],
+    }),
+  );
+  let breakpoint = &response["body"]["breakpoints"][0];
+
+  assert_eq!(breakpoint["verified"], true);
+  assert_eq!(breakpoint["id"], 1);
+  assert_eq!(breakpoint["line"], 4);
+
+  client.request("configurationDone", json!({}));
+
+  let stopped = client.event("stopped");
+
+  assert_eq!(stopped["body"]["reason"], "breakpoint");
+  assert_eq!(stopped["body"]["threadId"], 1);
+
+  let response = client.request("stackTrace", json!({ "threadId": 1 }));
+  let frame = &response["body"]["stackFrames"][0];
+
+  assert_eq!(frame["name"], "main");
+  assert_eq!(frame["line"], 4);
+  assert_eq!(frame["source"]["path"], "/src/main.zo");
+
+  let response = client.request("scopes", json!({ "frameId": frame["id"] }));
+  let locals = response["body"]["scopes"][0]["variablesReference"].clone();
+
+  let response =
+    client.request("variables", json!({ "variablesReference": locals }));
+  let variables = &response["body"]["variables"];
+
+  assert_eq!(variables[0]["name"], "n");
+  assert_eq!(variables[0]["value"], "42");
+  assert_eq!(variables[1]["name"], "s");
+  assert_eq!(variables[1]["value"], r#""hi""#);
+  assert_eq!(variables[1]["type"], "str");
+
+  let response = client.request("next", json!({ "threadId": 1 }));
+
+  assert_eq!(response["success"], true);
+  assert_eq!(client.event("stopped")["body"]["reason"], "step");
+
+  client.request("disconnect", json!({}));
+
+  let commands = fs::read_to_string(dir.join("commands.log")).unwrap();
+
+  assert!(commands.contains(r#"-break-insert -f "/src/main.zo:3""#));
+  assert!(commands.contains("-exec-next --thread 1"));
+}
+
+#[test]
+fn launch_refuses_a_binary_without_debug_info() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let gdb = debugger(dir);
+  let program = program(dir, None);
+  let mut client = Client::start();
+
+  let response = client.request(
+    "launch",
+    json!({
+      "program": program,
+      "debuggerPath": gdb,
+    }),
+  );
+
+  assert_eq!(response["success"], false);
+  assert!(
+    response["message"]
+      .as_str()
+      .unwrap()
+      .contains("carries no zo debug info")
+  );
+  assert!(!client.has_seen("initialized"));
+  assert!(!dir.join("commands.log").exists());
+
+  client.request("disconnect", json!({}));
+}
+
+#[test]
+fn launch_refuses_an_unknown_debugger() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let gdb = debugger(dir);
+  let program = program(dir, Some(&types()));
+  let mut client = Client::start();
+
+  let response = client.request(
+    "launch",
+    json!({
+      "program": program,
+      "debugger": "windbg",
+      "debuggerPath": gdb,
+    }),
+  );
+
+  assert_eq!(response["success"], false);
+  assert!(
+    response["message"]
+      .as_str()
+      .unwrap()
+      .contains("unknown debugger `windbg`")
+  );
+  assert!(!dir.join("commands.log").exists());
+
+  client.request("disconnect", json!({}));
+}
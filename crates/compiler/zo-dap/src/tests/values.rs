@@ -0,0 +1,292 @@
+use crate::values::{Memory, Slot, Values};
+
+use zo_codegen_backend::{DebugTy, DebugTypes, DebugVariant};
+
+use std::collections::BTreeMap;
+
+/// Memory laid out by hand: 8-byte words from `base` up.
+struct Fake {
+  base: u64,
+  bytes: Vec<u8>,
+  map: Option<String>,
+}
+
+impl Fake {
+  fn new(base: u64) -> Self {
+    Self {
+      base,
+      bytes: Vec::new(),
+      map: None,
+    }
+  }
+
+  /// Appends `words` and returns their address.
+  fn words(&mut self, words: &[u64]) -> u64 {
+    let addr = self.base + self.bytes.len() as u64;
+
+    for word in words {
+      self.bytes.extend_from_slice(&word.to_le_bytes());
+    }
+
+    addr
+  }
+
+  /// Appends a `str` and returns its address.
+  fn str(&mut self, text: &str) -> u64 {
+    let addr = self.words(&[text.len() as u64]);
+
+    self.bytes.extend_from_slice(text.as_bytes());
+    self.bytes.resize(self.bytes.len().next_multiple_of(8), 0);
+
+    addr
+  }
+}
+
+impl Memory for Fake {
+  fn read(&mut self, addr: u64, len: usize) -> Option<Vec<u8>> {
+    let start = addr.checked_sub(self.base)? as usize;
+
+    self.bytes.get(start..start + len).map(<[u8]>::to_vec)
+  }
+
+  fn map_entries(&mut self, _: u64, _: u32, _: u32) -> Option<String> {
+    self.map.clone()
+  }
+}
+
+fn types() -> DebugTypes {
+  let entries = [
+    (
+      "int",
+      DebugTy::Int {
+        signed: true,
+        size: 8,
+      },
+    ),
+    (
+      "i32",
+      DebugTy::Int {
+        signed: true,
+        size: 4,
+      },
+    ),
+    ("bool", DebugTy::Bool),
+    ("char", DebugTy::Char),
+    ("f64", DebugTy::Float { size: 8 }),
+    ("str", DebugTy::Str),
+    ("[]int", DebugTy::Array { elem: "int".into() }),
+    (
+      "(int, str)",
+      DebugTy::Tuple {
+        elems: vec!["int".into(), "str".into()],
+      },
+    ),
+    (
+      "Point",
+      DebugTy::Struct {
+        fields: vec![("x".into(), "i32".into()), ("y".into(), "i32".into())],
+      },
+    ),
+    (
+      "Option<int>",
+      DebugTy::Enum {
+        variants: vec![
+          DebugVariant {
+            name: "None".into(),
+            tag: 0,
+            fields: vec![],
+          },
+          DebugVariant {
+            name: "Some".into(),
+            tag: 1,
+            fields: vec!["int".into()],
+          },
+        ],
+      },
+    ),
+    (
+      "HashMap<str, int>",
+      DebugTy::Map {
+        key_fmt: 3,
+        val_fmt: 0,
+      },
+    ),
+  ];
+
+  DebugTypes {
+    types: entries
+      .into_iter()
+      .map(|(key, ty)| (key.to_string(), ty))
+      .collect::<BTreeMap<_, _>>(),
+  }
+}
+
+fn slot(ty: &str, word: u64) -> Slot {
+  Slot {
+    ty: ty.to_string(),
+    word,
+  }
+}
+
+#[test]
+fn scalars_from_debugger_text() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+
+  let mut show = |ty: &str, text: &str| {
+    let word = values.word_from_debugger(ty, text).unwrap();
+
+    values.show(&mut memory, &slot(ty, word)).value
+  };
+
+  assert_eq!(show("int", "-7"), "-7");
+  assert_eq!(show("bool", "true"), "true");
+  assert_eq!(show("char", "97 U'a'"), "'a'");
+  assert_eq!(show("f64", "1.5"), "1.5");
+  assert!(!values.knows("Ptr"));
+}
+
+#[test]
+fn narrow_ints_ignore_slot_garbage() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+
+  let word = 0xdead_beef_ffff_fffe;
+
+  assert_eq!(values.show(&mut memory, &slot("i32", word)).value, "-2");
+}
+
+#[test]
+fn str_shows_its_text() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+  let addr = memory.str("zo \"rocks\"");
+
+  let shown = values.show(&mut memory, &slot("str", addr));
+
+  assert_eq!(shown.value, r#""zo \"rocks\"""#);
+  assert!(shown.children.is_empty());
+}
+
+#[test]
+fn str_view_follows_its_data_pointer() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+  let parent = memory.str("hello, world");
+  let view = memory.words(&[5 | 1 << 63, parent + 8 + 7]);
+
+  let shown = values.show(&mut memory, &slot("str", view));
+
+  assert_eq!(shown.value, r#""world""#);
+}
+
+#[test]
+fn array_lists_its_elements() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+  let addr = memory.words(&[3, 10, 20, 30]);
+
+  let shown = values.show(&mut memory, &slot("[]int", addr));
+
+  assert_eq!(shown.value, "[10, 20, 30]");
+  assert_eq!(
+    shown.children,
+    vec![
+      ("[0]".to_string(), slot("int", 10)),
+      ("[1]".to_string(), slot("int", 20)),
+      ("[2]".to_string(), slot("int", 30)),
+    ]
+  );
+}
+
+#[test]
+fn long_array_summary_is_cut() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+  let words = std::iter::once(10).chain(0..10).collect::<Vec<u64>>();
+  let addr = memory.words(&words);
+
+  let shown = values.show(&mut memory, &slot("[]int", addr));
+
+  assert_eq!(shown.value, "[0, 1, 2, 3, 4, 5, 6, 7, …]");
+  assert_eq!(shown.children.len(), 10);
+}
+
+#[test]
+fn tuple_and_struct_nest() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+  let name = memory.str("hi");
+  let tuple = memory.words(&[1, name]);
+  let point = memory.words(&[3, u64::from(-4i32 as u32)]);
+
+  assert_eq!(
+    values.show(&mut memory, &slot("(int, str)", tuple)).value,
+    r#"(1, "hi")"#
+  );
+  assert_eq!(
+    values.show(&mut memory, &slot("Point", point)).value,
+    "Point { x: 3, y: -4 }"
+  );
+}
+
+#[test]
+fn enum_shows_its_variant() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+  let some = memory.words(&[1, 42]);
+  let none = memory.words(&[0, 0]);
+
+  let shown = values.show(&mut memory, &slot("Option<int>", some));
+
+  assert_eq!(shown.value, "Some(42)");
+  assert_eq!(shown.children, vec![("0".to_string(), slot("int", 42))]);
+  assert_eq!(
+    values.show(&mut memory, &slot("Option<int>", none)).value,
+    "None"
+  );
+}
+
+#[test]
+fn map_shows_runtime_entries() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+  let map = memory.words(&[0xbeef]);
+
+  assert_eq!(
+    values
+      .show(&mut memory, &slot("HashMap<str, int>", map))
+      .value,
+    format!("HashMap<str, int> @ {map:#x}")
+  );
+
+  memory.map = Some(r#"{"a": 1}"#.to_string());
+
+  assert_eq!(
+    values
+      .show(&mut memory, &slot("HashMap<str, int>", map))
+      .value,
+    r#"{"a": 1}"#
+  );
+}
+
+#[test]
+fn unreadable_memory_is_reported() {
+  let types = types();
+  let values = Values::new(&types);
+  let mut memory = Fake::new(0x1000);
+
+  assert_eq!(
+    values.show(&mut memory, &slot("str", 0x10)).value,
+    "<unreadable 0x10>"
+  );
+}
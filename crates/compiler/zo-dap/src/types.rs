@@ -0,0 +1,32 @@
+//! The zo type table a debug build carries.
+
+use zo_codegen_backend::{DEBUG_TYPES_SECTION, DebugTypes};
+
+use object::{Object, ObjectSection};
+
+use std::fs;
+use std::path::Path;
+
+/// Reads the [`DebugTypes`] out of `program`. A binary without
+/// them — a release build, or one from the ARM64 backend,
+/// which writes no DWARF — is refused: the debugger could
+/// neither place breakpoints nor show variables in it.
+pub(crate) fn load(program: &Path) -> Result<DebugTypes, String> {
+  let shown = program.display();
+  let bytes = fs::read(program)
+    .map_err(|error| format!("cannot read `{shown}`: {error}"))?;
+
+  let file = object::File::parse(&*bytes)
+    .map_err(|_| format!("`{shown}` is not an executable"))?;
+
+  file
+    .section_by_name(DEBUG_TYPES_SECTION)
+    .and_then(|section| DebugTypes::from_bytes(section.data().ok()?))
+    .ok_or_else(|| {
+      format!(
+        "`{shown}` carries no zo debug info — build it without \
+         `--release` for a target of the Cranelift backend (x86_64, \
+         Windows, Android); ARM64 macOS and Linux builds have no DWARF"
+      )
+    })
+}
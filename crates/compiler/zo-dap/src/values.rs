@@ -0,0 +1,323 @@
+//! Shows debuggee values in their zo shape.
+//!
+//! The debugger only sees base types: a scalar, or an 8-byte
+//! word the DWARF calls an address. The [`DebugTypes`] table
+//! says what that word points at, and [`Values`] walks the
+//! memory behind it — `str` headers, array and tuple slots,
+//! enum tags — into what the zo source would print.
+
+use zo_codegen_backend::{DebugTy, DebugTypes};
+
+/// Longest `str` read, in bytes.
+const MAX_STR_LEN: u64 = 4096;
+/// Length-word flag of a `str` slice view — mirrors
+/// `STR_VIEW_BIT` in `zo-runtime`.
+const STR_VIEW_BIT: u64 = 1 << 63;
+/// Most array elements listed as children.
+const MAX_CHILDREN: u64 = 1000;
+/// Most items a one-line summary spells out.
+const SUMMARY_ITEMS: usize = 8;
+/// How deep a one-line summary looks into nested values.
+const SUMMARY_DEPTH: usize = 2;
+
+/// Reads the debuggee.
+pub(crate) trait Memory {
+  /// `len` bytes at `addr`, or `None` if unmapped.
+  fn read(&mut self, addr: u64, len: usize) -> Option<Vec<u8>>;
+
+  /// The entries of the runtime map at `map`, as
+  /// `zo_map_debug` formats them inside the debuggee.
+  fn map_entries(
+    &mut self,
+    map: u64,
+    key_fmt: u32,
+    val_fmt: u32,
+  ) -> Option<String>;
+}
+
+/// One value: its zo type — a key of [`DebugTypes::types`] —
+/// and the 8-byte word holding it, the scalar itself or the
+/// pointer to an aggregate.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Slot {
+  pub(crate) ty: String,
+  pub(crate) word: u64,
+}
+
+/// A value as shown: a one-line summary and the named values
+/// it expands to.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Shown {
+  pub(crate) value: String,
+  pub(crate) children: Vec<(String, Slot)>,
+}
+
+pub(crate) struct Values<'a> {
+  types: &'a DebugTypes,
+}
+
+impl<'a> Values<'a> {
+  pub(crate) fn new(types: &'a DebugTypes) -> Self {
+    Self { types }
+  }
+
+  /// Whether `ty` — a type name the debugger reported — is a
+  /// zo type this table lays out.
+  pub(crate) fn knows(&self, ty: &str) -> bool {
+    self.types.types.contains_key(ty)
+  }
+
+  /// The word a variable of type `ty` holds, from the text
+  /// the debugger printed for it.
+  pub(crate) fn word_from_debugger(&self, ty: &str, text: &str) -> Option<u64> {
+    let text = text.trim();
+    let first = text.split_whitespace().next()?;
+
+    match self.types.types.get(ty)? {
+      DebugTy::Unit => Some(0),
+      DebugTy::Bool => match first {
+        "true" => Some(1),
+        "false" => Some(0),
+        _ => parse_int(first),
+      },
+      DebugTy::Char | DebugTy::Int { .. } => parse_int(first),
+      DebugTy::Float { size: 4 } => {
+        first.parse::<f32>().ok().map(|f| u64::from(f.to_bits()))
+      }
+      DebugTy::Float { .. } => first.parse::<f64>().ok().map(f64::to_bits),
+      _ => parse_hex(first),
+    }
+  }
+
+  /// Shows `slot`.
+  pub(crate) fn show(&self, memory: &mut dyn Memory, slot: &Slot) -> Shown {
+    Shown {
+      value: self.summary(memory, slot, 0),
+      children: self.children(memory, slot).unwrap_or_default(),
+    }
+  }
+
+  fn summary(
+    &self,
+    memory: &mut dyn Memory,
+    slot: &Slot,
+    depth: usize,
+  ) -> String {
+    let word = slot.word;
+
+    let Some(ty) = self.types.types.get(&slot.ty) else {
+      return format!("{word:#x}");
+    };
+
+    let nested = |values: &Self, memory: &mut dyn Memory| {
+      let children = values.children(memory, slot)?;
+      let shown = children
+        .iter()
+        .take(SUMMARY_ITEMS)
+        .map(|(name, child)| {
+          (name.clone(), values.summary(memory, child, depth + 1))
+        })
+        .collect::<Vec<_>>();
+
+      Some((shown, children.len() > SUMMARY_ITEMS))
+    };
+
+    if depth >= SUMMARY_DEPTH
+      && matches!(
+        ty,
+        DebugTy::Array { .. }
+          | DebugTy::Tuple { .. }
+          | DebugTy::Struct { .. }
+          | DebugTy::Enum { .. }
+      )
+    {
+      return "…".to_string();
+    }
+
+    match ty {
+      DebugTy::Unit => "()".to_string(),
+      DebugTy::Bool => (word & 0xff != 0).to_string(),
+      DebugTy::Char => char::from_u32(word as u32)
+        .map_or_else(|| format!("{word:#x}"), |ch| format!("{ch:?}")),
+      DebugTy::Int { signed, size } => {
+        let bits = u32::from(*size).clamp(1, 8) * 8;
+        let shift = 64 - bits;
+
+        if *signed {
+          (((word << shift) as i64) >> shift).to_string()
+        } else {
+          ((word << shift) >> shift).to_string()
+        }
+      }
+      DebugTy::Float { size: 4 } => {
+        format!("{:?}", f32::from_bits(word as u32))
+      }
+      DebugTy::Float { .. } => format!("{:?}", f64::from_bits(word)),
+      DebugTy::Str => read_str(memory, word)
+        .map_or_else(|| unreadable(word), |text| format!("{text:?}")),
+      DebugTy::Array { .. } => match nested(self, memory) {
+        Some((items, more)) => {
+          format!("[{}]", join(items.into_iter().map(|(_, v)| v), more))
+        }
+        None => unreadable(word),
+      },
+      DebugTy::Tuple { .. } => match nested(self, memory) {
+        Some((items, more)) => {
+          format!("({})", join(items.into_iter().map(|(_, v)| v), more))
+        }
+        None => unreadable(word),
+      },
+      DebugTy::Struct { .. } => match nested(self, memory) {
+        Some((fields, more)) => format!(
+          "{} {{ {} }}",
+          slot.ty,
+          join(fields.into_iter().map(|(n, v)| format!("{n}: {v}")), more)
+        ),
+        None => unreadable(word),
+      },
+      DebugTy::Enum { variants } => {
+        let variant = word_at(memory, word)
+          .and_then(|tag| variants.iter().find(|variant| variant.tag == tag));
+
+        match (variant, nested(self, memory)) {
+          (Some(variant), Some((fields, _))) if fields.is_empty() => {
+            variant.name.clone()
+          }
+          (Some(variant), Some((fields, more))) => format!(
+            "{}({})",
+            variant.name,
+            join(fields.into_iter().map(|(_, v)| v), more)
+          ),
+          _ => unreadable(word),
+        }
+      }
+      DebugTy::Map { key_fmt, val_fmt } => word_at(memory, word)
+        .and_then(|map| memory.map_entries(map, *key_fmt, *val_fmt))
+        .unwrap_or_else(|| format!("{} @ {word:#x}", slot.ty)),
+      DebugTy::Opaque => format!("{} @ {word:#x}", slot.ty),
+    }
+  }
+
+  /// The values `slot` expands to. `None` when its memory is
+  /// unreadable.
+  fn children(
+    &self,
+    memory: &mut dyn Memory,
+    slot: &Slot,
+  ) -> Option<Vec<(String, Slot)>> {
+    let word = slot.word;
+
+    let (start, names, tys) = match self.types.types.get(&slot.ty)? {
+      DebugTy::Array { elem } => {
+        let len = word_at(memory, word)?.min(MAX_CHILDREN) as usize;
+
+        (
+          word + 8,
+          (0..len).map(|idx| format!("[{idx}]")).collect::<Vec<_>>(),
+          vec![elem.clone(); len],
+        )
+      }
+      DebugTy::Tuple { elems } => (
+        word,
+        (0..elems.len()).map(|idx| idx.to_string()).collect(),
+        elems.clone(),
+      ),
+      DebugTy::Struct { fields } => (
+        word,
+        fields.iter().map(|(name, _)| name.clone()).collect(),
+        fields.iter().map(|(_, ty)| ty.clone()).collect(),
+      ),
+      DebugTy::Enum { variants } => {
+        let tag = word_at(memory, word)?;
+        let fields = variants
+          .iter()
+          .find(|variant| variant.tag == tag)
+          .map(|variant| variant.fields.clone())
+          .unwrap_or_default();
+
+        (
+          word + 8,
+          (0..fields.len()).map(|idx| idx.to_string()).collect(),
+          fields,
+        )
+      }
+      _ => return Some(Vec::new()),
+    };
+
+    if tys.is_empty() {
+      return Some(Vec::new());
+    }
+
+    let bytes = memory.read(start, tys.len() * 8)?;
+
+    Some(
+      names
+        .into_iter()
+        .zip(tys)
+        .zip(bytes.chunks_exact(8))
+        .map(|((name, ty), chunk)| {
+          let word = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+
+          (name, Slot { ty, word })
+        })
+        .collect(),
+    )
+  }
+}
+
+fn word_at(memory: &mut dyn Memory, addr: u64) -> Option<u64> {
+  let bytes = memory.read(addr, 8)?;
+
+  Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// The text of the `str` at `addr` — `[len: u64][bytes]`, or
+/// for a slice view `[len | STR_VIEW_BIT][ptr]`, whose bytes
+/// live at `ptr` inside the parent string.
+fn read_str(memory: &mut dyn Memory, addr: u64) -> Option<String> {
+  let header = word_at(memory, addr)?;
+  let len = header & !STR_VIEW_BIT;
+  let data = if header & STR_VIEW_BIT != 0 {
+    word_at(memory, addr + 8)?
+  } else {
+    addr + 8
+  };
+
+  let bytes = memory.read(data, len.min(MAX_STR_LEN) as usize)?;
+  let mut text = String::from_utf8_lossy(&bytes).into_owned();
+
+  if len > MAX_STR_LEN {
+    text.push('…');
+  }
+
+  Some(text)
+}
+
+fn join(items: impl Iterator<Item = String>, more: bool) -> String {
+  let mut items = items.collect::<Vec<_>>();
+
+  if more {
+    items.push("…".to_string());
+  }
+
+  items.join(", ")
+}
+
+fn unreadable(word: u64) -> String {
+  format!("<unreadable {word:#x}>")
+}
+
+fn parse_int(text: &str) -> Option<u64> {
+  parse_hex(text)
+    .or_else(|| text.parse::<i64>().ok().map(|n| n as u64))
+    .or_else(|| text.parse::<u64>().ok())
+}
+
+fn parse_hex(text: &str) -> Option<u64> {
+  let digits = text.strip_prefix("0x")?;
+  let end = digits
+    .find(|ch: char| !ch.is_ascii_hexdigit())
+    .unwrap_or(digits.len());
+
+  u64::from_str_radix(&digits[..end], 16).ok()
+}
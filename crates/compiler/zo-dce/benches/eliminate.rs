@@ -24,6 +24,7 @@ fn make_fun(name: Symbol, calls: Vec<Symbol>) -> Vec<Insn> {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
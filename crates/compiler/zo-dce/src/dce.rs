@@ -63,6 +63,7 @@ impl<'a> Dce<'a> {
     // TODO: dead variable elimination disabled.
     // self.eliminate_dead_variables();
     self.eliminate_dead_instructions();
+    Sir::fixup_fundef_offsets(&mut self.sir.instructions);
   }
 
   // ==============================================================
@@ -503,6 +504,7 @@ fn is_impure(insn: &Insn) -> bool {
     insn,
     Insn::Call { .. }
       | Insn::CallIndirect { .. }
+      | Insn::ClosureEnvFree { .. }
       | Insn::Store { .. }
       | Insn::FieldStore { .. }
       | Insn::ArrayStore { .. }
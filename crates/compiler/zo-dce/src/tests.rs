@@ -253,6 +253,28 @@ fn only_main_no_other_functions() {
   assert_eq!(fun_names(&sir), vec![main]);
 }
 
+#[test]
+fn body_start_follows_its_fundef_after_removal() {
+  let mut interner = Interner::new();
+  let dead = interner.intern("dead");
+  let main = interner.intern("main");
+
+  let mut insns = vec![];
+
+  insns.extend(fun(dead, Pubness::No, vec![call(dead)]));
+  insns.extend(fun(main, Pubness::No, vec![call(main)]));
+
+  let mut sir = make_sir(insns);
+
+  Dce::new(&mut sir, vec![main], &interner).eliminate();
+
+  assert_eq!(fun_names(&sir), vec![main]);
+  assert!(matches!(
+    sir.instructions[0],
+    Insn::FunDef { body_start: 1, .. }
+  ));
+}
+
 // ===== DEAD INSTRUCTION ELIMINATION =====
 
 #[test]
@@ -271,6 +293,7 @@ fn dead_insn_unused_const() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -332,6 +355,7 @@ fn dead_insn_chain() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -386,6 +410,7 @@ fn dead_insn_preserves_calls() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -427,6 +452,7 @@ fn dead_insn_preserves_array_store() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -473,6 +499,7 @@ fn dead_var_unused_store() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -545,6 +572,7 @@ fn dead_var_overwritten_store() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -618,6 +646,7 @@ fn unreachable_after_return() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -662,6 +691,7 @@ fn unreachable_stops_at_label() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -756,6 +786,7 @@ fn template_computed_binding_pins_closure() {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -870,6 +901,7 @@ fn struct_construct_feeding_return_survives() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -921,6 +953,7 @@ fn live_dependency_chain_survives() {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -37,6 +37,7 @@ pub fn fun(
     pubness,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -33,6 +33,7 @@ fn warns_on_unused_function() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -50,6 +51,7 @@ fn warns_on_unused_function() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -90,6 +92,7 @@ fn warns_on_unused_variable() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -139,6 +142,7 @@ fn no_warning_when_all_used() {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -1,4 +1,7 @@
+use crate::constants::EXIT_CODE_USAGE;
+
 use zo_codegen_backend::Target;
+use zo_error::lint::{self, Level, LintLevels};
 use zo_reporter::DiagnosticFormat;
 
 use std::path::PathBuf;
@@ -32,7 +35,8 @@ pub struct Args {
   /// `xml` emits one well-formed `<diagnostics>` document
   /// to stdout. Both machine formats target agent / IDE
   /// consumers and share a frozen schema keyed by stable
-  /// kebab-case `id`.
+  /// kebab-case `id`. `sarif` emits one SARIF 2.1.0 log to
+  /// stdout for code-scanning dashboards.
   #[arg(long, value_enum, default_value_t = Format::Human)]
   pub format: Format,
   /// Number of source lines of context to include before
@@ -77,9 +81,39 @@ pub struct Args {
   /// `FORCE_COLOR` overrides those. This flag forces it off.
   #[arg(long)]
   pub no_color: bool,
+  /// Report a lint as a warning, e.g. `-W unused-variable`.
+  /// Repeatable. Lints are named by their kebab-case `id`.
+  #[arg(short = 'W', long = "warn", value_name = "LINT")]
+  pub warn: Vec<String>,
+  /// Silence a lint, e.g. `-A non-snake-case-name`. Repeatable.
+  #[arg(short = 'A', long = "allow", value_name = "LINT")]
+  pub allow: Vec<String>,
+  /// Turn a lint into a hard error, e.g. `-D unused-function`.
+  /// Repeatable. When one lint is passed to several of
+  /// `-A/-W/-D`, the strictest level wins. `%% allow(..).`
+  /// attributes in the source still override these flags.
+  #[arg(short = 'D', long = "deny", value_name = "LINT")]
+  pub deny: Vec<String>,
+  /// Turn every warning still at the `warn` level into a hard
+  /// error — for CI. Lints allowed by a flag or an attribute
+  /// stay silent.
+  #[arg(long)]
+  pub deny_warnings: bool,
   /// Watch file changes.
   #[arg(long)]
   pub watch: bool,
+  /// `check` / `build` only: apply every machine-applicable
+  /// fix-it to the input files in place before compiling,
+  /// re-analyzing until no fix-it is left. Overlapping fix-its
+  /// are deferred to the next pass; a file whose fix-its land
+  /// in an unresolved merge conflict is left untouched.
+  #[arg(long, conflicts_with = "fix_dry_run")]
+  pub fix: bool,
+  /// `check` / `build` only: like `--fix`, but print the
+  /// edits as a unified diff on stdout instead of writing
+  /// them, then stop.
+  #[arg(long)]
+  pub fix_dry_run: bool,
 }
 
 impl Args {
@@ -93,6 +127,37 @@ impl Args {
       self.no_color,
     )
   }
+
+  /// The lint levels set by `-A/-W/-D` and `--deny-warnings`.
+  /// Levels apply weakest first, so the strictest flag wins for
+  /// a lint named twice. Exits with a usage error on an id that
+  /// isn't a lint.
+  pub fn lint_levels(&self) -> LintLevels {
+    let mut levels = LintLevels::new();
+    let flags = [
+      (Level::Allow, &self.allow),
+      (Level::Warn, &self.warn),
+      (Level::Deny, &self.deny),
+    ];
+
+    for (level, ids) in flags {
+      for id in ids {
+        let Some(kind) = lint::lookup(id) else {
+          eprintln!(
+            "Error: unknown lint `{id}` for `--{}` — only warnings have a level",
+            level.as_str()
+          );
+
+          std::process::exit(EXIT_CODE_USAGE);
+        };
+
+        levels.set(kind, level);
+      }
+    }
+
+    levels.set_deny_warnings(self.deny_warnings);
+    levels
+  }
 }
 
 /// The compilation platform. The friendly names — `native`,
@@ -188,7 +253,8 @@ impl From<ArgsTarget> for Target {
 /// to stderr; `Json` streams one NDJSON object per error to
 /// stdout; `Xml` emits one well-formed `<diagnostics>`
 /// document to stdout. Both machine formats are for agentic
-/// consumers and share one frozen, isomorphic schema.
+/// consumers and share one frozen, isomorphic schema. `Sarif`
+/// emits one SARIF 2.1.0 log to stdout for code scanning.
 #[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[clap(rename_all = "lower")]
 pub enum Format {
@@ -196,6 +262,7 @@ pub enum Format {
   Human,
   Json,
   Xml,
+  Sarif,
 }
 
 /// Bridges the clap-facing CLI enum to the renderer selector
@@ -207,6 +274,7 @@ impl From<Format> for DiagnosticFormat {
       Format::Human => Self::Human,
       Format::Json => Self::Json,
       Format::Xml => Self::Xml,
+      Format::Sarif => Self::Sarif,
     }
   }
 }
@@ -242,6 +310,13 @@ pub enum Stage {
   Tree,
   /// The collection of sir instructions.
   Sir,
+  /// The per-function control-flow graphs, one Graphviz `.dot`
+  /// per function under `<stem>.cfg/`.
+  Cfg,
+  /// The inferred type of each tree node.
+  Types,
+  /// The ownership move facts and drop decisions.
+  Ownership,
   Asm,
   /// The collection of all output stage.
   All,
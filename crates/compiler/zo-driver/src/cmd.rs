@@ -1,4 +1,5 @@
 mod build;
+mod check;
 mod repl;
 mod run;
 mod test;
@@ -86,7 +87,6 @@ pub(crate) fn read_source(path: &Path) -> String {
   }
 }
 
-// TODO: add `check` — scan, parse, analyze and report.
 // TODO: add `fmt` — format program.
 
 /// Represents a [`Cmd`] enumeration.
@@ -100,6 +100,14 @@ Examples:
   zo build app.zo --target web       static `public/` web bundle
   zo build app.zo --target webview   double-clickable .app (system webview)")]
   Build(build::Build),
+  /// scan, parse, analyze and report, without building.
+  #[command(long_about = "scan, parse, analyze and report, without building.
+
+Examples:
+  zo check app.zo                 report diagnostics only
+  zo check app.zo --fix           apply machine-applicable fixes in place
+  zo check app.zo --fix-dry-run   print those fixes as a unified diff")]
+  Check(check::Check),
   /// read eval print and loop a program (not implemented yet).
   Repl(repl::Repl),
   /// build and run a program.
@@ -1,6 +1,7 @@
 use crate::args;
 use crate::cmd;
 use crate::cmd::Handle;
+use crate::fix;
 
 use zo_bundler::macos;
 use zo_codegen_backend::Webviewing;
@@ -20,6 +21,14 @@ pub(crate) struct Build {
 
 impl Build {
   fn build(&self) -> Result<(), Error> {
+    if self.args.fix || self.args.fix_dry_run {
+      fix::fix_files(&self.args)?;
+
+      if self.args.fix_dry_run {
+        return Ok(());
+      }
+    }
+
     let source_files: Vec<_> = self
       .args
       .files
@@ -42,6 +51,9 @@ impl Build {
         args::Stage::Tokens => Stage::Tokens,
         args::Stage::Tree => Stage::Tree,
         args::Stage::Sir => Stage::Sir,
+        args::Stage::Cfg => Stage::Cfg,
+        args::Stage::Types => Stage::Types,
+        args::Stage::Ownership => Stage::Ownership,
         args::Stage::Asm => Stage::Asm,
         args::Stage::All => Stage::All,
       })
@@ -68,6 +80,8 @@ impl Build {
       quiet: self.args.quiet,
     });
 
+    compiler.set_lint_levels(self.args.lint_levels());
+
     compiler.set_release(self.args.release.into());
 
     compiler
@@ -0,0 +1,53 @@
+use crate::args;
+use crate::cmd;
+use crate::cmd::Handle;
+use crate::fix;
+
+use zo_compiler::{Compiler, DiagnosticsConfig};
+use zo_error::Error;
+
+#[derive(clap::Args, Debug)]
+pub(crate) struct Check {
+  #[command(flatten)]
+  pub(crate) args: args::Args,
+}
+
+impl Check {
+  fn check(&self) -> Result<(), Error> {
+    if self.args.fix || self.args.fix_dry_run {
+      fix::fix_files(&self.args)?;
+
+      if self.args.fix_dry_run {
+        return Ok(());
+      }
+    }
+
+    let source_files: Vec<_> = self
+      .args
+      .files
+      .iter()
+      .map(|path| (path, cmd::read_source(path)))
+      .collect();
+
+    let search_paths = cmd::search_paths(source_files[0].0);
+    let mut compiler = Compiler::with_search_paths(search_paths);
+
+    compiler.configure_diagnostics(DiagnosticsConfig {
+      format: self.args.format.into(),
+      snippet_context: self.args.snippet_context,
+      explain_decisions: self.args.explain_decisions,
+      use_colors: self.args.use_colors(),
+      quiet: self.args.quiet,
+    });
+
+    compiler.set_lint_levels(self.args.lint_levels());
+
+    compiler.check(&source_files)
+  }
+}
+
+impl Handle for Check {
+  fn handle(&self) {
+    cmd::handle_with_watch(&self.args, || self.check());
+  }
+}
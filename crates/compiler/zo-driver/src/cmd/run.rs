@@ -6,7 +6,6 @@ use crate::constants::{EXIT_CODE_ERROR, EXIT_CODE_USAGE};
 use zo_bundler::ios::simulator::Simulator;
 use zo_compiler::{Analyzed, Compiler};
 use zo_error::Error;
-use zo_runtime::Server;
 
 #[derive(clap::Args, Debug)]
 pub(crate) struct Run {
@@ -40,6 +39,8 @@ impl Run {
       quiet: self.args.quiet,
     });
 
+    compiler.set_lint_levels(self.args.lint_levels());
+
     // The webview bundler sets the `webviewing` flag and owns its own
     // analysis (its codegen differs), so it runs before the shared
     // analysis below.
@@ -227,12 +228,7 @@ impl Run {
 
     compiler.compile_analyzed(analyzed, self.args.target.into(), &public)?;
 
-    let server = Server::new(&public).with_quiet(self.args.quiet.into());
-    if let Err(error) = server.serve() {
-      eprintln!("Error serving web bundle: {error}");
-
-      std::process::exit(EXIT_CODE_ERROR);
-    }
+    let _ = EXIT_CODE_ERROR;
 
     Ok(())
   }
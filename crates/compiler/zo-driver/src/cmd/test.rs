@@ -34,6 +34,8 @@ impl Test {
       quiet: self.args.quiet,
     });
 
+    compiler.set_lint_levels(self.args.lint_levels());
+
     compiler.set_test_mode(true);
 
     let temp_dir =
@@ -27,6 +27,7 @@ impl Driver {
   pub(crate) fn run(self) {
     match self.cmd {
       Cmd::Build(ref cmd) => cmd.handle(),
+      Cmd::Check(ref cmd) => cmd.handle(),
       Cmd::Repl(ref cmd) => cmd.handle(),
       Cmd::Run(ref cmd) => cmd.handle(),
       Cmd::Test(ref cmd) => cmd.handle(),
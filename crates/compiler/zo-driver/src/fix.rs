@@ -0,0 +1,142 @@
+//! `--fix` / `--fix-dry-run` for `zo check` / `zo build`.
+//!
+//! Each input file is analyzed, its machine-applicable fix-its
+//! are spliced in, and the result is analyzed again — until a
+//! pass yields no edit (the fixpoint) or [`MAX_FIX_PASSES`] is
+//! hit. The passes run on an in-memory copy, so `--fix` writes
+//! each file once and `--fix-dry-run` prints the same end state
+//! as a unified diff without touching the disk.
+//!
+//! Only edits in the input file itself are applied. Diagnostics
+//! in loaded packs are fixed when the pack is passed as an input
+//! — `--fix` never rewrites the core library. A file whose edits
+//! land in an unresolved merge conflict is refused as a whole.
+
+use crate::args;
+use crate::cmd;
+
+use zo_compiler::Compiler;
+use zo_error::lint::LintLevels;
+use zo_error::{Error, ErrorKind};
+use zo_reporter::apply;
+use zo_span::Span;
+
+use std::io::Write;
+use std::path::Path;
+
+/// Upper bound on analysis passes per file. Every pass must
+/// shrink the diagnostics for the loop to continue; the cap
+/// guards against a fix-it that keeps re-triggering itself.
+const MAX_FIX_PASSES: usize = 8;
+
+/// What the fix passes did to one file.
+pub(crate) enum Outcome {
+  /// No fix-it applied — the file is left as is.
+  Unchanged,
+  /// Fix-its applied; `source` is the file's new contents.
+  Fixed { source: String, edits: usize },
+  /// An edit landed in an unresolved merge conflict.
+  Refused,
+}
+
+/// Runs the fix passes over every input file, then writes the
+/// results (`--fix`) or prints them as a diff (`--fix-dry-run`).
+/// Returns `Err` when a file was refused or could not be
+/// written, so the caller fails the command.
+pub(crate) fn fix_files(args: &args::Args) -> Result<(), Error> {
+  let mut failed = false;
+
+  let levels = args.lint_levels();
+
+  for path in &args.files {
+    let original = cmd::read_source(path);
+
+    match fix_source(path, &original, &levels) {
+      Outcome::Unchanged => {}
+      Outcome::Refused => {
+        eprintln!(
+          "zo — refusing to fix {}: fix-its overlap an unresolved merge conflict",
+          path.display()
+        );
+
+        failed = true;
+      }
+      Outcome::Fixed { source, .. } if args.fix_dry_run => {
+        let name = path.to_string_lossy().replace('\\', "/");
+        let diff = apply::unified_diff(&name, &original, &source);
+        let mut stdout = std::io::stdout().lock();
+
+        let _ = stdout.write_all(diff.as_bytes());
+      }
+      Outcome::Fixed { source, edits } => {
+        if let Err(error) = std::fs::write(path, source) {
+          eprintln!("zo — failed to write {}: {error}", path.display());
+
+          failed = true;
+        } else if !args.quiet {
+          eprintln!("zo — fixed {} ({edits} edits)", path.display());
+        }
+      }
+    }
+  }
+
+  if failed {
+    return Err(Error::new(ErrorKind::FixNotApplied, Span::ZERO));
+  }
+
+  Ok(())
+}
+
+/// Drives the analyze → apply loop for one file's source.
+/// Lints allowed by `levels` or an attribute are never fixed.
+pub(crate) fn fix_source(
+  path: &Path,
+  original: &str,
+  levels: &LintLevels,
+) -> Outcome {
+  let mut source = original.to_string();
+  let mut edits = 0;
+  let mut last = usize::MAX;
+
+  for _ in 0..MAX_FIX_PASSES {
+    // A fresh compiler per pass: the reporter accumulates
+    // across analyses, and every pass must see only the
+    // diagnostics of the current text.
+    let mut compiler = Compiler::with_search_paths(cmd::search_paths(path));
+
+    compiler.set_lint_levels(levels.clone());
+    compiler.analyze_source(&source, path);
+
+    let diagnostics = compiler.diagnostics();
+    let pending = apply::edits_for(&diagnostics, 0);
+
+    // A pass that leaves as many diagnostics as the one before
+    // made no progress — a fix-it re-triggering itself.
+    let count = diagnostics.errors().iter().map(|phase| phase.count()).sum();
+
+    if pending.is_empty() || count >= last {
+      break;
+    }
+
+    last = count;
+
+    if apply::touches_conflict(&pending, &apply::conflict_regions(&source)) {
+      return Outcome::Refused;
+    }
+
+    let applied = apply::apply(&source, &pending);
+
+    if applied.applied == 0 || applied.source == source {
+      break;
+    }
+
+    source = applied.source;
+    edits += applied.applied;
+  }
+
+  if edits == 0 {
+    Outcome::Unchanged
+  } else {
+    Outcome::Fixed { source, edits }
+  }
+}
@@ -2,8 +2,12 @@ mod args;
 mod cmd;
 mod constants;
 mod driver;
+mod fix;
 mod watch;
 
+#[cfg(test)]
+mod tests;
+
 pub(crate) use driver::Driver;
 
 use clap::Parser;
@@ -0,0 +1,54 @@
+use crate::cmd;
+use crate::fix::{Outcome, fix_source};
+
+use zo_compiler::Compiler;
+use zo_error::lint::LintLevels;
+
+use std::path::Path;
+
+/// How many errors an analysis of `source` reports.
+fn errors(source: &str, path: &Path) -> usize {
+  let mut compiler = Compiler::with_search_paths(cmd::search_paths(path));
+
+  compiler.analyze_source(source, path);
+  compiler
+    .diagnostics()
+    .errors()
+    .iter()
+    .map(|phase| phase.count())
+    .sum()
+}
+
+/// A fixed file compiles again.
+#[test]
+fn fixed_source_compiles() {
+  let path = Path::new("main.zo");
+  let source = "fun add(a: int, b: int): int {\n  return a + b;\n}\n\n\
+    fun main() {\n  showln(add(1, 2));\n}\n";
+
+  assert!(errors(source, path) > 0);
+
+  let Outcome::Fixed { source, edits } =
+    fix_source(path, source, &LintLevels::new())
+  else {
+    panic!("expected the `:` before the return type to be fixed");
+  };
+
+  assert_eq!(edits, 1);
+  assert_eq!(errors(&source, path), 0, "fixed source:\n{source}");
+}
+
+/// A fix-it that isn't machine-applicable is left for the
+/// user — `mut ` spliced in at the assignment would not
+/// compile.
+#[test]
+fn immutable_assignment_is_not_fixed() {
+  let path = Path::new("main.zo");
+  let source = "fun main() {\n  imu x: int = 3;\n  x = 4;\n}\n";
+
+  assert!(errors(source, path) > 0);
+  assert!(matches!(
+    fix_source(path, source, &LintLevels::new()),
+    Outcome::Unchanged
+  ));
+}
@@ -223,7 +223,8 @@ pub const fn severity(kind: ErrorKind) -> Severity {
     | ErrorKind::UnreachableCode
     | ErrorKind::NonPascalCaseName
     | ErrorKind::NonScreamingCaseName
-    | ErrorKind::NonSnakeCaseName => Severity::Warning,
+    | ErrorKind::NonSnakeCaseName
+    | ErrorKind::UnknownLint => Severity::Warning,
     ErrorKind::DeadCodeEliminated | ErrorKind::UnreachableMatchArm => {
       Severity::Note
     }
@@ -232,6 +233,12 @@ pub const fn severity(kind: ErrorKind) -> Severity {
 }
 
 impl ErrorKind {
+  /// The last declared variant. Variants are only ever
+  /// appended, so this moves with every new kind — keep it
+  /// pointing at the tail of the enum. [`id_registry::kinds`]
+  /// walks `0..=LAST` to enumerate the registry.
+  pub const LAST: Self = Self::FixNotApplied;
+
   /// Stable kebab-case identifier — see `id_registry` for
   /// the freeze contract. Bound by agent prompts, doc URLs,
   /// snapshots; the canonical name for this variant.
@@ -414,7 +421,7 @@ pub enum ErrorKind {
   UnresolvedModule,
   CircularImport,
 
-  // String slicing errors (compile-time only in v1).
+  // String slicing errors.
   StrSliceRequiresConstBounds,
   StrSliceRequiresStr,
   StrSliceOutOfBounds,
@@ -426,10 +433,11 @@ pub enum ErrorKind {
   SpawnOutsideNursery, // `spawn` without enclosing `nursery { }`
   AwaitOnNonTask,      // `await expr` where expr is not `Ty::Task(_)`
   ChannelCapacityNotLiteral, // `channel(N)` with non-literal N
-  // A capturing closure cannot become a bare function
-  // pointer — the runtime ABI carries no environment, so
-  // only a non-capturing function or closure can flow into
-  // a `Fn()` position that materializes a real address.
+  // A capturing closure escapes as a bare function pointer —
+  // its `(fn_ptr, env_ptr)` pair is only built where it is
+  // passed to a `Fn(...)` param that carries the env, so it
+  // cannot be returned, nor handed to an `ffi` callback
+  // without a `%% user_data(..).` slot.
   CapturingClosureAsFnPointer,
 
   // Repeat-array literal `[v...]` / `[v...n]` errors.
@@ -463,9 +471,9 @@ pub enum ErrorKind {
   // E0601 takes.
   MissingMainFunction,
 
-  // Emitted by the `%% serialize.` / `%% deserialize.`
-  // derive synthesizer when a struct or enum-payload
-  // field's type can't be lowered to JSON. Span anchored
+  // Emitted by the derive synthesizers (`%% serialize.`,
+  // `%% eq.`, `%% hash.`, ...) when a struct or enum-payload
+  // field's type can't be lowered for that derive. Span anchored
   // at the field declaration so the user can see exactly
   // which field is blocking the derive.
   DeriveUnsupportedField,
@@ -513,8 +521,8 @@ pub enum ErrorKind {
   BoundNotSatisfied,
 
   /// `abstract X : Y { ... }` — colon-after-abstract-name.
-  /// Inheritance would force vtable chaining; abstracts
-  /// are flat single-level declarations.
+  /// No longer raised: super-abstracts are supported. Kept
+  /// so the code stays reserved (see `id_registry`).
   AbstractInheritanceUnsupported,
 
   /// `any <Abstract>` over an abstract that uses `Self`
@@ -595,4 +603,29 @@ pub enum ErrorKind {
   /// dot member access does not apply — only the `::` path
   /// resolves a pack's items.
   PackDotAccess,
+  /// A lint-level attribute or flag (`%% allow(..).`,
+  /// `-W/-A/-D`) names an id that is not a lint — unknown,
+  /// or a hard error whose severity can't be configured.
+  UnknownLint,
+  /// A textual SIR file (`zo build foo.sir`) that strays from
+  /// the grammar `--emit=sir` prints — an unknown mnemonic, a
+  /// missing operand, or an instruction with no textual form.
+  MalformedSir,
+  /// A type in a textual SIR file that names neither a
+  /// primitive nor a `struct_def` / `enum_def` of the file.
+  UnknownSirType,
+  /// A `{name:spec}` interpolation whose spec strays from the
+  /// `[[fill]align][+][#][0][width][.precision][kind]` grammar.
+  InvalidFormatSpec,
+  /// A format spec that doesn't apply to the value's type —
+  /// `{name:x}` on a `str`, `{price:.2}` on an `int`.
+  FormatSpecTypeMismatch,
+  /// A `return`, `break`, `continue` or `?` inside a `defer`
+  /// body. The body runs while its scope is already exiting,
+  /// so it cannot start another exit of its own.
+  ControlFlowInDefer,
+  /// A `--fix` that left a file as it was — its fix-its
+  /// overlap an unresolved merge conflict, or the fixed source
+  /// could not be written back.
+  FixNotApplied,
 }
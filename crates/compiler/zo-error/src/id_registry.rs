@@ -76,6 +76,7 @@ const fn entry(kind: ErrorKind) -> (&'static str, u16) {
     ErrorKind::UnmatchedClosingDelimiter => ("unmatched-closing-delimiter", 21),
     ErrorKind::MismatchedDelimiter => ("mismatched-delimiter", 22),
     ErrorKind::UnterminatedRegex => ("unterminated-regex", 23),
+    ErrorKind::InvalidFormatSpec => ("invalid-format-spec", 24),
 
     // --- Parser (E0100 .. E0299) ---
     ErrorKind::UnexpectedToken => ("unexpected-token", 100),
@@ -118,6 +119,8 @@ const fn entry(kind: ErrorKind) -> (&'static str, u16) {
     ErrorKind::ParenthesizedCondition => ("parenthesized-condition", 137),
     ErrorKind::MixedLoopBodyForms => ("mixed-loop-body-forms", 138),
     ErrorKind::ReservedKeyword => ("reserved-keyword", 139),
+    ErrorKind::MalformedSir => ("malformed-sir", 140),
+    ErrorKind::UnknownSirType => ("unknown-sir-type", 141),
 
     // --- Analyzer: semantic + types (E0300 .. E0499) ---
     ErrorKind::DuplicateDefinition => ("duplicate-definition", 300),
@@ -191,6 +194,9 @@ const fn entry(kind: ErrorKind) -> (&'static str, u16) {
     ErrorKind::EventOnComponent => ("event-on-component", 357),
     ErrorKind::StatementInTemplate => ("statement-in-template", 358),
     ErrorKind::PackDotAccess => ("pack-dot-access", 359),
+    ErrorKind::UnknownLint => ("unknown-lint", 360),
+    ErrorKind::FormatSpecTypeMismatch => ("format-spec-type-mismatch", 361),
+    ErrorKind::ControlFlowInDefer => ("control-flow-in-defer", 362),
 
     // --- Constants & arithmetic (E0500 .. E0599) ---
     ErrorKind::DivisionByZero => ("division-by-zero", 500),
@@ -234,6 +240,7 @@ const fn entry(kind: ErrorKind) -> (&'static str, u16) {
       ("test-fn-must-be-parameterless", 801)
     }
     ErrorKind::TestFnMustReturnUnit => ("test-fn-must-return-unit", 802),
+    ErrorKind::FixNotApplied => ("fix-not-applied", 803),
 
     // --- Rationale notes (E0900 .. E0999) ---
     //
@@ -263,3 +270,26 @@ pub const fn id(kind: ErrorKind) -> &'static str {
 pub const fn code(kind: ErrorKind) -> u16 {
   entry(kind).1
 }
+
+/// Every registered `ErrorKind`, in discriminant order.
+///
+/// Drives consumers that need the whole table rather than one
+/// entry — the SARIF `rules` array, documentation generators.
+/// The order is the declaration order, which is append-only,
+/// so an index into this sequence is as stable as the codes.
+pub fn kinds() -> impl Iterator<Item = ErrorKind> {
+  (0..=ErrorKind::LAST as u16).map(|n| {
+    // SAFETY: `ErrorKind` is `#[repr(u16)]` with implicit,
+    // contiguous discriminants `0..=LAST` — see
+    // `Error::kind` for the same invariant.
+    unsafe { std::mem::transmute::<u16, ErrorKind>(n) }
+  })
+}
+
+/// Reverse lookup: the `ErrorKind` registered under a
+/// kebab-case `id`, or `None` for an unknown id. Backs the
+/// `-W/-A/-D <id>` flags and the `%%allow(<id>).` attribute,
+/// which name diagnostics by their frozen id.
+pub fn kind(id: &str) -> Option<ErrorKind> {
+  kinds().find(|kind| kind.id() == id)
+}
@@ -1,5 +1,6 @@
 mod error;
 pub mod id_registry;
+pub mod lint;
 
 pub use error::{Error, ErrorKind, Severity, severity};
 
@@ -81,6 +82,33 @@ mod tests {
     }
   }
 
+  /// Every kind `kinds()` yields is its own discriminant, and
+  /// its id and code are unique and resolve back to it — so
+  /// two variants can never share a diagnostic name.
+  #[test]
+  fn id_registry_kinds_round_trip() {
+    let kinds = id_registry::kinds().collect::<Vec<_>>();
+
+    for (idx, kind) in kinds.iter().enumerate() {
+      assert_eq!(*kind as usize, idx, "{kind:?} is out of order");
+      assert_eq!(id_registry::kind(kind.id()), Some(*kind), "{kind:?}");
+    }
+
+    let mut ids = kinds.iter().map(|k| k.id()).collect::<Vec<_>>();
+
+    ids.sort_unstable();
+    ids.dedup();
+
+    assert_eq!(ids.len(), kinds.len(), "ids must be unique");
+
+    let mut codes = kinds.iter().map(|k| k.code()).collect::<Vec<_>>();
+
+    codes.sort_unstable();
+    codes.dedup();
+
+    assert_eq!(codes.len(), kinds.len(), "codes must be unique");
+  }
+
   /// Numeric codes must fit the documented phase ranges
   /// (E0001..E0899). New variants picking codes outside
   /// these ranges break the human-readable phase-grouping
@@ -0,0 +1,321 @@
+//! Lint levels — per-diagnostic and per-scope overrides of a
+//! warning's severity.
+//!
+//! A *lint* is any `ErrorKind` whose default [`severity`] is
+//! `Severity::Warning`. Its level is resolved from, lowest to
+//! highest precedence:
+//!
+//! 1. the default — [`Level::Warn`];
+//! 2. the project table (`lints: (...)` in `fret.oz`);
+//! 3. the `-W/-A/-D <id>` command-line flags;
+//! 4. `%%allow(<id>).` / `%%warn(..)` / `%%deny(..)` attributes,
+//!    innermost scope first.
+//!
+//! Layers 2 and 3 both land through [`LintLevels::set`] — the
+//! caller applies them in that order, the last write wins.
+//! `--deny-warnings` is applied last and promotes whatever
+//! still resolves to `Warn`; an explicit `allow` stays
+//! silent.
+//!
+//! Hard errors and rationale notes are not lints: their
+//! severity is never overridden. Levels ride beside the
+//! diagnostics rather than on them, so `Error` keeps its
+//! 16-byte packing.
+
+use crate::error::{Error, ErrorKind, Severity, severity};
+use crate::id_registry;
+
+/// What to do with a lint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Level {
+  /// Drop the diagnostic.
+  Allow,
+  /// Report it as a warning — the default.
+  Warn,
+  /// Report it as a hard error that fails the build.
+  Deny,
+}
+
+impl Level {
+  /// Parses the attribute / config spelling — `allow`, `warn`
+  /// or `deny`.
+  pub fn parse(name: &str) -> Option<Self> {
+    match name {
+      "allow" => Some(Self::Allow),
+      "warn" => Some(Self::Warn),
+      "deny" => Some(Self::Deny),
+      _ => None,
+    }
+  }
+
+  /// The attribute / config spelling of this level.
+  #[inline]
+  pub const fn as_str(self) -> &'static str {
+    match self {
+      Self::Allow => "allow",
+      Self::Warn => "warn",
+      Self::Deny => "deny",
+    }
+  }
+}
+
+/// Whether `kind` is a lint — a diagnostic whose level can be
+/// configured. Only warnings qualify.
+#[inline]
+pub const fn is_lint(kind: ErrorKind) -> bool {
+  matches!(severity(kind), Severity::Warning)
+}
+
+/// Resolves a lint by its kebab-case id. `None` when the id is
+/// unknown or names a diagnostic that is not a lint.
+pub fn lookup(id: &str) -> Option<ErrorKind> {
+  id_registry::kind(id).filter(|kind| is_lint(*kind))
+}
+
+/// A level set by an attribute on one item — applies to every
+/// lint of `kind` reported inside `start..end` of file
+/// `file_id`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LintScope {
+  /// The file the attribute sits in. `0` is the entry file —
+  /// diagnostics without a file id resolve against it.
+  pub file_id: u16,
+  /// Byte offset the scope starts at (the attribute itself).
+  pub start: u32,
+  /// Byte offset the scope ends at (exclusive) — the end of
+  /// the attributed item.
+  pub end: u32,
+  /// The lint the attribute names.
+  pub kind: ErrorKind,
+  /// The level it sets.
+  pub level: Level,
+}
+
+impl LintScope {
+  /// Byte length of the scope, used to pick the innermost of
+  /// several matching scopes.
+  #[inline]
+  const fn width(&self) -> u32 {
+    self.end.saturating_sub(self.start)
+  }
+
+  /// Whether this scope covers `error`.
+  fn covers(&self, error: &Error) -> bool {
+    let span = error.span();
+
+    self.kind == error.kind()
+      && self.file_id == error.file_id().unwrap_or(0)
+      && (self.start..self.end).contains(&span.start)
+  }
+}
+
+/// Every configured lint level: the global table, the
+/// attribute scopes and the `--deny-warnings` switch.
+#[derive(Clone, Debug, Default)]
+pub struct LintLevels {
+  /// Global overrides — project table, then CLI flags. Later
+  /// entries win over earlier ones for the same kind.
+  levels: Vec<(ErrorKind, Level)>,
+  /// Attribute scopes collected from the sources.
+  scopes: Vec<LintScope>,
+  /// Promote every lint still at `Warn` to `Deny`.
+  deny_warnings: bool,
+}
+
+impl LintLevels {
+  /// Creates an empty table — every lint at its default.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the global level of `kind`, overriding any earlier
+  /// setting for it.
+  pub fn set(&mut self, kind: ErrorKind, level: Level) {
+    self.levels.push((kind, level));
+  }
+
+  /// Turns `--deny-warnings` on or off.
+  pub fn set_deny_warnings(&mut self, deny: bool) {
+    self.deny_warnings = deny;
+  }
+
+  /// Registers an attribute scope.
+  pub fn push_scope(&mut self, scope: LintScope) {
+    self.scopes.push(scope);
+  }
+
+  /// Registers a batch of attribute scopes.
+  pub fn extend_scopes(&mut self, scopes: impl IntoIterator<Item = LintScope>) {
+    self.scopes.extend(scopes);
+  }
+
+  /// Number of attribute scopes registered so far.
+  pub fn scope_count(&self) -> usize {
+    self.scopes.len()
+  }
+
+  /// Rewrites the file id of every scope registered at or
+  /// after `from` — used when several analyses share one file
+  /// table.
+  pub fn remap_scope_files(&mut self, from: usize, remap: impl Fn(u16) -> u16) {
+    for scope in &mut self.scopes[from..] {
+      scope.file_id = remap(scope.file_id);
+    }
+  }
+
+  /// Drops every attribute scope, keeping the global table —
+  /// scopes belong to one analysis, the table to the session.
+  pub fn clear_scopes(&mut self) {
+    self.scopes.clear();
+  }
+
+  /// Whether no level is configured, i.e. every diagnostic
+  /// keeps its default severity.
+  pub fn is_default(&self) -> bool {
+    self.levels.is_empty() && self.scopes.is_empty() && !self.deny_warnings
+  }
+
+  /// The level `error` resolves to, or `None` when it is not
+  /// a lint.
+  pub fn level(&self, error: &Error) -> Option<Level> {
+    let kind = error.kind();
+
+    if !is_lint(kind) {
+      return None;
+    }
+
+    let scoped = self
+      .scopes
+      .iter()
+      .filter(|scope| scope.covers(error))
+      .min_by_key(|scope| scope.width())
+      .map(|scope| scope.level);
+
+    let level = scoped
+      .or_else(|| {
+        self
+          .levels
+          .iter()
+          .rev()
+          .find(|(k, _)| *k == kind)
+          .map(|(_, level)| *level)
+      })
+      .unwrap_or(Level::Warn);
+
+    Some(match level {
+      Level::Warn if self.deny_warnings => Level::Deny,
+      level => level,
+    })
+  }
+
+  /// The severity `error` is reported with, or `None` when it
+  /// is allowed and must be dropped. Non-lints keep their
+  /// default severity.
+  pub fn severity(&self, error: &Error) -> Option<Severity> {
+    match self.level(error) {
+      None => Some(error.severity()),
+      Some(Level::Allow) => None,
+      Some(Level::Warn) => Some(Severity::Warning),
+      Some(Level::Deny) => Some(Severity::Error),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use zo_span::Span;
+
+  fn unused(start: u32) -> Error {
+    Error::new(ErrorKind::UnusedVariable, Span::new(start, 1))
+  }
+
+  fn scope(start: u32, end: u32, level: Level) -> LintScope {
+    LintScope {
+      file_id: 0,
+      start,
+      end,
+      kind: ErrorKind::UnusedVariable,
+      level,
+    }
+  }
+
+  #[test]
+  fn lookup_accepts_only_lints() {
+    assert_eq!(lookup("unused-variable"), Some(ErrorKind::UnusedVariable));
+    assert_eq!(
+      lookup("non-snake-case-name"),
+      Some(ErrorKind::NonSnakeCaseName)
+    );
+    assert_eq!(lookup("type-mismatch"), None);
+    assert_eq!(lookup("no-such-lint"), None);
+  }
+
+  #[test]
+  fn defaults_keep_severity() {
+    let levels = LintLevels::new();
+    let mismatch = Error::new(ErrorKind::TypeMismatch, Span::ZERO);
+
+    assert!(levels.is_default());
+    assert_eq!(levels.severity(&unused(0)), Some(Severity::Warning));
+    assert_eq!(levels.severity(&mismatch), Some(Severity::Error));
+  }
+
+  #[test]
+  fn last_global_setting_wins() {
+    let mut levels = LintLevels::new();
+
+    levels.set(ErrorKind::UnusedVariable, Level::Deny);
+    levels.set(ErrorKind::UnusedVariable, Level::Allow);
+
+    assert_eq!(levels.severity(&unused(0)), None);
+  }
+
+  #[test]
+  fn innermost_scope_wins_over_table() {
+    let mut levels = LintLevels::new();
+
+    levels.set(ErrorKind::UnusedVariable, Level::Deny);
+    levels.push_scope(scope(0, 100, Level::Allow));
+    levels.push_scope(scope(10, 20, Level::Warn));
+
+    assert_eq!(levels.severity(&unused(15)), Some(Severity::Warning));
+    assert_eq!(levels.severity(&unused(50)), None);
+    assert_eq!(levels.severity(&unused(200)), Some(Severity::Error));
+  }
+
+  #[test]
+  fn scopes_are_per_file() {
+    let mut levels = LintLevels::new();
+
+    levels.push_scope(scope(0, 100, Level::Allow));
+
+    let elsewhere = unused(5).tagged(1);
+
+    assert_eq!(levels.severity(&unused(5)), None);
+    assert_eq!(levels.severity(&elsewhere), Some(Severity::Warning));
+  }
+
+  #[test]
+  fn deny_warnings_spares_allowed_lints() {
+    let mut levels = LintLevels::new();
+
+    levels.set_deny_warnings(true);
+    levels.push_scope(scope(0, 10, Level::Allow));
+
+    assert_eq!(levels.severity(&unused(5)), None);
+    assert_eq!(levels.severity(&unused(50)), Some(Severity::Error));
+  }
+
+  #[test]
+  fn non_lints_are_never_overridden() {
+    let mut levels = LintLevels::new();
+    let mismatch = Error::new(ErrorKind::TypeMismatch, Span::ZERO);
+
+    levels.set(ErrorKind::TypeMismatch, Level::Allow);
+
+    assert_eq!(levels.level(&mismatch), None);
+    assert_eq!(levels.severity(&mismatch), Some(Severity::Error));
+  }
+}
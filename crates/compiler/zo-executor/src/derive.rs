@@ -0,0 +1,105 @@
+//! Derive attributes beyond JSON.
+//!
+//! `%% eq, hash, show, clone, default, ord.` on a struct or
+//! enum synthesizes one method per attribute, built field by
+//! field in declaration order. The methods that implement a
+//! core abstract (`Eq`, `Ord`, `Show`, `Hash`, `Clone`) are
+//! registered as its `apply`, so `==`, `<`, `showln` and
+//! generic bounds dispatch to them exactly as they would to a
+//! hand-written block.
+
+use zo_interner::Symbol;
+use zo_span::Span;
+use zo_ty::TyId;
+use zo_value::{Pubness, ValueId};
+
+/// One derivable method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Derive {
+  Eq,
+  Ord,
+  Hash,
+  Show,
+  Clone,
+  Default,
+}
+
+impl Derive {
+  /// Every derive, in the order the synths run.
+  pub(crate) const ALL: [Self; 6] = [
+    Self::Eq,
+    Self::Ord,
+    Self::Hash,
+    Self::Show,
+    Self::Clone,
+    Self::Default,
+  ];
+
+  /// The attribute name — also the synthesized method's.
+  pub(crate) fn name(self) -> &'static str {
+    match self {
+      Self::Eq => "eq",
+      Self::Ord => "cmp",
+      Self::Hash => "hash",
+      Self::Show => "show",
+      Self::Clone => "clone",
+      Self::Default => "default",
+    }
+  }
+
+  /// The attribute as written — `ord` derives `cmp`.
+  pub(crate) fn attribute(self) -> &'static str {
+    match self {
+      Self::Ord => "ord",
+      _ => self.name(),
+    }
+  }
+
+  /// The core abstract the method implements. `default`
+  /// takes no receiver, so it implements none.
+  pub(crate) fn abstract_name(self) -> Option<&'static str> {
+    Some(match self {
+      Self::Eq => "Eq",
+      Self::Ord => "Ord",
+      Self::Hash => "Hash",
+      Self::Show => "Show",
+      Self::Clone => "Clone",
+      Self::Default => return None,
+    })
+  }
+}
+
+/// The struct or enum a derive synthesizes for.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DeriveItem {
+  pub(crate) name: Symbol,
+  pub(crate) ty_id: TyId,
+  /// The item's introducer — the synthesized method's span.
+  pub(crate) span: Span,
+  pub(crate) pubness: Pubness,
+}
+
+/// How a derive treats one field, by its type.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum FieldShape {
+  /// `int` of any width, `bool`, `char` — one word whose
+  /// bits are its identity.
+  Scalar,
+  Float,
+  Str,
+  /// A struct or enum, derived through its own method.
+  Named(Symbol),
+  /// Arrays, tuples, functions, type parameters.
+  Other,
+}
+
+/// One piece of a derived `show`'s output.
+pub(crate) enum ShowPart {
+  Text(String),
+  Value(ValueId),
+}
+
+/// The 32-bit FNV-1a offset basis and prime `hash` folds each
+/// field's word through — `int` is 32 bits wide.
+pub(crate) const FNV_OFFSET: u64 = 0x811c_9dc5;
+pub(crate) const FNV_PRIME: u64 = 0x0100_0193;
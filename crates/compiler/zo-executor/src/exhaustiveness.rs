@@ -0,0 +1,460 @@
+//! Exhaustiveness and usefulness of `match` arms.
+//!
+//! Works on the typed [`Pat`]s the decision tree is compiled
+//! from, arranged as a pattern matrix: one row per arm, one
+//! column per value still to look at. A pattern is *useful*
+//! against the rows above it when some value matches it and
+//! none of them — an arm that isn't useful is unreachable. A
+//! `match` is exhaustive when a lone `_` isn't useful against
+//! its unguarded arms; when it is, the search hands back the
+//! uncovered values as witness patterns, `(None, false)`.
+//!
+//! Both searches split a column by the constructors of its
+//! type: every row that agrees with a constructor continues
+//! with that constructor's fields in place of the column. The
+//! type table lives in the executor, so the constructors come
+//! from the caller as [`Ctors`].
+
+use crate::pattern::{Lit, Pat, PatKind};
+
+use zo_interner::Symbol;
+use zo_span::Span;
+use zo_ty::TyId;
+
+/// Most witnesses one check returns. Wide tuples of enums
+/// multiply out fast; past this many the diagnostic stops
+/// being readable anyway.
+const MAX_WITNESSES: usize = 16;
+
+/// The constructors the values of a type are built from.
+pub(crate) enum Ctors {
+  /// `true` and `false`.
+  Bool,
+  /// One per variant, in declaration order.
+  Variants(Vec<VariantCtor>),
+  /// A tuple or a struct: one constructor with these slots.
+  Fields(Vec<TyId>),
+  /// Too many values to list — ints, floats, chars, strings.
+  /// Only a wildcard covers them all.
+  Open,
+  /// Not known here: a type parameter, an error. Checks
+  /// give up rather than guess.
+  Unknown,
+}
+
+/// An enum variant and the types of its payload.
+pub(crate) struct VariantCtor {
+  pub(crate) name: Symbol,
+  pub(crate) discriminant: u32,
+  pub(crate) fields: Vec<TyId>,
+}
+
+/// One constructor of a finite type.
+#[derive(Clone, Copy, PartialEq)]
+enum Ctor {
+  Bool(bool),
+  Variant(u32),
+}
+
+type Row = Vec<Pat>;
+
+/// The patterns of the values of type `ty` that none of
+/// `pats` match — empty when they're exhaustive.
+pub(crate) fn missing(
+  pats: &[&Pat],
+  ty: TyId,
+  ctors: &mut dyn FnMut(TyId) -> Ctors,
+) -> Vec<Pat> {
+  let rows = pats.iter().map(|pat| vec![(*pat).clone()]).collect();
+
+  witnesses(rows, &[ty], ctors)
+    .into_iter()
+    .filter_map(|mut witness| witness.pop())
+    .collect()
+}
+
+/// Whether some value of type `ty` matches `pat` and none of
+/// `pats`.
+pub(crate) fn useful(
+  pats: &[&Pat],
+  pat: &Pat,
+  ty: TyId,
+  ctors: &mut dyn FnMut(TyId) -> Ctors,
+) -> bool {
+  let rows = pats.iter().map(|pat| vec![(*pat).clone()]).collect();
+
+  is_useful(rows, vec![pat.clone()], &[ty], ctors)
+}
+
+fn witnesses(
+  rows: Vec<Row>,
+  tys: &[TyId],
+  ctors: &mut dyn FnMut(TyId) -> Ctors,
+) -> Vec<Row> {
+  let Some((&ty, rest_tys)) = tys.split_first() else {
+    return if rows.is_empty() {
+      vec![Vec::new()]
+    } else {
+      Vec::new()
+    };
+  };
+
+  let rows = expand(rows);
+  let (ty, found) = column_ctors(rows.iter().map(|row| &row[0]), ty, ctors);
+
+  match found {
+    Ctors::Unknown => Vec::new(),
+    Ctors::Open => witnesses(default_rows(&rows), rest_tys, ctors)
+      .into_iter()
+      .map(|rest| prepend(Pat::wild(ty, Span::ZERO), rest))
+      .collect(),
+    Ctors::Fields(field_tys) => {
+      let tys = [field_tys.as_slice(), rest_tys].concat();
+
+      witnesses(specialize(&rows, None, &field_tys), &tys, ctors)
+        .into_iter()
+        .map(|mut witness| {
+          let rest = witness.split_off(field_tys.len());
+          let pat = Pat {
+            kind: PatKind::Fields(witness),
+            ty,
+            span: Span::ZERO,
+          };
+
+          prepend(pat, rest)
+        })
+        .collect()
+    }
+    finite => {
+      let all = ctor_list(finite);
+      let seen = all
+        .iter()
+        .map(|(ctor, ..)| rows.iter().any(|row| head(&row[0]) == Some(*ctor)))
+        .collect::<Vec<_>>();
+
+      let mut out = Vec::new();
+
+      // Every constructor shows up: each one's rows decide
+      // what's left of its values.
+      if seen.iter().all(|seen| *seen) {
+        for (ctor, name, field_tys) in all {
+          let tys = [field_tys.as_slice(), rest_tys].concat();
+          let rows = specialize(&rows, Some(ctor), &field_tys);
+
+          for mut witness in witnesses(rows, &tys, ctors) {
+            let rest = witness.split_off(field_tys.len());
+
+            out.push(prepend(ctor_pat(ctor, name, witness, ty), rest));
+          }
+
+          if out.len() >= MAX_WITNESSES {
+            break;
+          }
+        }
+
+        out.truncate(MAX_WITNESSES);
+
+        return out;
+      }
+
+      // Some constructor never shows up, so only the rows
+      // that match anything here can cover its values.
+      let rest = witnesses(default_rows(&rows), rest_tys, ctors);
+
+      if rest.is_empty() {
+        return out;
+      }
+
+      // Name the constructors left out — unless none show up
+      // at all, where `_` says it shorter.
+      let heads = if seen.iter().any(|seen| *seen) {
+        all
+          .into_iter()
+          .zip(&seen)
+          .filter(|(_, seen)| !**seen)
+          .map(|((ctor, name, field_tys), _)| {
+            let fields = field_tys
+              .iter()
+              .map(|ty| Pat::wild(*ty, Span::ZERO))
+              .collect();
+
+            ctor_pat(ctor, name, fields, ty)
+          })
+          .collect()
+      } else {
+        vec![Pat::wild(ty, Span::ZERO)]
+      };
+
+      for head in heads {
+        for rest in &rest {
+          out.push(prepend(head.clone(), rest.clone()));
+        }
+      }
+
+      out.truncate(MAX_WITNESSES);
+      out
+    }
+  }
+}
+
+fn is_useful(
+  rows: Vec<Row>,
+  vector: Row,
+  tys: &[TyId],
+  ctors: &mut dyn FnMut(TyId) -> Ctors,
+) -> bool {
+  let Some((&ty, rest_tys)) = tys.split_first() else {
+    return rows.is_empty();
+  };
+
+  // Useful as soon as one alternative is.
+  if let PatKind::Or(alts) = &vector[0].kind {
+    return alts.iter().any(|alt| {
+      let vector = prepend(alt.clone(), vector[1..].to_vec());
+
+      is_useful(rows.clone(), vector, tys, ctors)
+    });
+  }
+
+  let rows = expand(rows);
+  let first = &vector[0];
+  let rest = vector[1..].to_vec();
+  let heads = rows.iter().map(|row| &row[0]).chain([first]);
+  let (_, found) = column_ctors(heads, ty, ctors);
+
+  match found {
+    Ctors::Unknown => true,
+    Ctors::Open => match &first.kind {
+      PatKind::Wild | PatKind::Bind(_) => {
+        is_useful(default_rows(&rows), rest, rest_tys, ctors)
+      }
+      PatKind::Lit(_) | PatKind::Range(_) => {
+        let rows = rows
+          .iter()
+          .filter(|row| covers(&row[0], first))
+          .map(|row| row[1..].to_vec())
+          .collect();
+
+        is_useful(rows, rest, rest_tys, ctors)
+      }
+      _ => true,
+    },
+    Ctors::Fields(field_tys) => {
+      let Some(vector) =
+        specialize(std::slice::from_ref(&vector), None, &field_tys).pop()
+      else {
+        return true;
+      };
+
+      let tys = [field_tys.as_slice(), rest_tys].concat();
+
+      is_useful(specialize(&rows, None, &field_tys), vector, &tys, ctors)
+    }
+    finite => {
+      let all = ctor_list(finite);
+
+      let useful_under =
+        |ctor: Ctor,
+         field_tys: &[TyId],
+         ctors: &mut dyn FnMut(TyId) -> Ctors| {
+          let Some(vector) =
+            specialize(std::slice::from_ref(&vector), Some(ctor), field_tys)
+              .pop()
+          else {
+            return false;
+          };
+
+          let tys = [field_tys, rest_tys].concat();
+
+          is_useful(
+            specialize(&rows, Some(ctor), field_tys),
+            vector,
+            &tys,
+            ctors,
+          )
+        };
+
+      match (&first.kind, head(first)) {
+        (_, Some(ctor)) => match all.iter().find(|(c, ..)| *c == ctor) {
+          Some((_, _, field_tys)) => useful_under(ctor, field_tys, ctors),
+          None => true,
+        },
+        (PatKind::Wild | PatKind::Bind(_), None) => {
+          let complete = all.iter().all(|(ctor, ..)| {
+            rows.iter().any(|row| head(&row[0]) == Some(*ctor))
+          });
+
+          if complete {
+            all
+              .iter()
+              .any(|(ctor, _, field_tys)| useful_under(*ctor, field_tys, ctors))
+          } else {
+            is_useful(default_rows(&rows), rest, rest_tys, ctors)
+          }
+        }
+        _ => true,
+      }
+    }
+  }
+}
+
+/// The type and constructors of a column typed `ty`. A
+/// payload declared by name is an inference variable, which
+/// says nothing — the column's patterns, typed by the names
+/// they spell, stand in for it then.
+fn column_ctors<'p>(
+  heads: impl Iterator<Item = &'p Pat>,
+  ty: TyId,
+  ctors: &mut dyn FnMut(TyId) -> Ctors,
+) -> (TyId, Ctors) {
+  let found = ctors(ty);
+
+  if !matches!(found, Ctors::Unknown) {
+    return (ty, found);
+  }
+
+  for head in heads.filter(|head| head.ty != ty) {
+    let found = ctors(head.ty);
+
+    if !matches!(found, Ctors::Unknown) {
+      return (head.ty, found);
+    }
+  }
+
+  (ty, found)
+}
+
+/// The constructors of a finite type, each with its variant
+/// name and field types.
+fn ctor_list(ctors: Ctors) -> Vec<(Ctor, Symbol, Vec<TyId>)> {
+  match ctors {
+    Ctors::Bool => [true, false]
+      .into_iter()
+      .map(|value| (Ctor::Bool(value), Symbol::UNDERSCORE, Vec::new()))
+      .collect(),
+    Ctors::Variants(variants) => variants
+      .into_iter()
+      .map(|v| (Ctor::Variant(v.discriminant), v.name, v.fields))
+      .collect(),
+    Ctors::Fields(_) | Ctors::Open | Ctors::Unknown => Vec::new(),
+  }
+}
+
+/// The constructor a pattern commits its value to, if any.
+fn head(pat: &Pat) -> Option<Ctor> {
+  match &pat.kind {
+    PatKind::Lit(Lit::Bool(value)) => Some(Ctor::Bool(*value)),
+    PatKind::Variant { discriminant, .. } => Some(Ctor::Variant(*discriminant)),
+    _ => None,
+  }
+}
+
+fn ctor_pat(ctor: Ctor, name: Symbol, fields: Vec<Pat>, ty: TyId) -> Pat {
+  let kind = match ctor {
+    Ctor::Bool(value) => PatKind::Lit(Lit::Bool(value)),
+    Ctor::Variant(discriminant) => PatKind::Variant {
+      name,
+      discriminant,
+      fields,
+    },
+  };
+
+  Pat {
+    kind,
+    ty,
+    span: Span::ZERO,
+  }
+}
+
+fn prepend(pat: Pat, mut rest: Row) -> Row {
+  rest.insert(0, pat);
+  rest
+}
+
+/// Splits rows whose first column is an `|` pattern into one
+/// row per alternative.
+fn expand(rows: Vec<Row>) -> Vec<Row> {
+  let mut out = Vec::with_capacity(rows.len());
+  let mut work = rows;
+
+  work.reverse();
+
+  while let Some(row) = work.pop() {
+    match &row[0].kind {
+      PatKind::Or(alts) => {
+        for alt in alts.iter().rev() {
+          work.push(prepend(alt.clone(), row[1..].to_vec()));
+        }
+      }
+      _ => out.push(row),
+    }
+  }
+
+  out
+}
+
+/// The rows that can match a value built by `ctor` — the
+/// single constructor of a tuple or struct when `None` — with
+/// their first column replaced by its fields.
+fn specialize(
+  rows: &[Row],
+  ctor: Option<Ctor>,
+  field_tys: &[TyId],
+) -> Vec<Row> {
+  rows
+    .iter()
+    .filter_map(|row| {
+      let (first, rest) = row.split_first()?;
+      let mut fields = match (&first.kind, ctor) {
+        (PatKind::Wild | PatKind::Bind(_), _) => field_tys
+          .iter()
+          .map(|ty| Pat::wild(*ty, first.span))
+          .collect(),
+        (PatKind::Fields(fields), None) => fields.clone(),
+        (
+          PatKind::Variant {
+            discriminant,
+            fields,
+            ..
+          },
+          Some(Ctor::Variant(tag)),
+        ) if *discriminant == tag => fields.clone(),
+        (PatKind::Lit(Lit::Bool(value)), Some(Ctor::Bool(other)))
+          if *value == other =>
+        {
+          Vec::new()
+        }
+        _ => return None,
+      };
+
+      fields.extend_from_slice(rest);
+
+      Some(fields)
+    })
+    .collect()
+}
+
+/// The rows whose first column matches anything, without it.
+fn default_rows(rows: &[Row]) -> Vec<Row> {
+  rows
+    .iter()
+    .filter(|row| matches!(row[0].kind, PatKind::Wild | PatKind::Bind(_)))
+    .map(|row| row[1..].to_vec())
+    .collect()
+}
+
+/// Whether `pat` matches every value the literal or range
+/// `target` does.
+fn covers(pat: &Pat, target: &Pat) -> bool {
+  match (&pat.kind, &target.kind) {
+    (PatKind::Wild | PatKind::Bind(_), _) => true,
+    (PatKind::Lit(lit), PatKind::Lit(other)) => lit == other,
+    (PatKind::Range(range), PatKind::Lit(lit)) => {
+      range.contains(lit) == Some(true)
+    }
+    (PatKind::Range(range), PatKind::Range(other)) => {
+      range.covers(other) == Some(true)
+    }
+    _ => false,
+  }
+}
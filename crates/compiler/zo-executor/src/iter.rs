@@ -0,0 +1,219 @@
+//! Lazy iterator adapters fused into a `for` loop.
+//!
+//! `for x := src.map(f).filter(g).take(3) { ... }` never
+//! builds an intermediate array or adapter struct. The header
+//! is peeled into a [`Pipe`] — the source at the root, one
+//! node per adapter around it — and the loop head lowers to a
+//! single pull through the whole chain: each adapter asks its
+//! source for the next element and jumps to the loop's exit
+//! once a source runs dry.
+//!
+//! Closures are called by name, so every `map` / `filter`
+//! stage is a direct call the `--release` inliner folds into
+//! the loop body.
+
+use zo_interner::Symbol;
+use zo_ty::TyId;
+use zo_value::ValueId;
+
+/// An adapter method recognized in a `for` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Adapter {
+  Map,
+  Filter,
+  Enumerate,
+  Zip,
+  Take,
+  Chain,
+}
+
+impl Adapter {
+  /// The adapter named `name`, if any.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "map" => Self::Map,
+      "filter" => Self::Filter,
+      "enumerate" => Self::Enumerate,
+      "zip" => Self::Zip,
+      "take" => Self::Take,
+      "chain" => Self::Chain,
+      _ => return None,
+    })
+  }
+
+  /// The adapter's method name.
+  pub(crate) fn name(self) -> &'static str {
+    match self {
+      Self::Map => "map",
+      Self::Filter => "filter",
+      Self::Enumerate => "enumerate",
+      Self::Zip => "zip",
+      Self::Take => "take",
+      Self::Chain => "chain",
+    }
+  }
+}
+
+/// One adapter call in a `for` header: `name` `.` `(` … `)`,
+/// by tree index.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Stage {
+  pub(crate) adapter: Adapter,
+  /// The adapter's `Ident` — everything before it is the
+  /// stage's source.
+  pub(crate) name_idx: usize,
+  pub(crate) lparen_idx: usize,
+  pub(crate) rparen_idx: usize,
+}
+
+/// A `for` header's evaluated rhs.
+pub(crate) enum ForRhs {
+  /// A plain value — the source with no adapter fused.
+  Value {
+    value: ValueId,
+    sir: ValueId,
+    ty: TyId,
+  },
+  Pipe(Pipe),
+}
+
+/// A function a stage calls per element, with the values its
+/// captures were bound to when the header ran.
+#[derive(Clone, Debug)]
+pub(crate) struct StageFn {
+  pub(crate) name: Symbol,
+  pub(crate) captures: Vec<ValueId>,
+}
+
+/// A fused pipeline. Every synthetic local it names is
+/// declared before the loop head, so a pull only loads and
+/// stores.
+#[derive(Clone, Debug)]
+pub(crate) enum Pipe {
+  /// `arr[idx]`, bumping `idx`.
+  Array {
+    arr: Symbol,
+    arr_ty: TyId,
+    idx: Symbol,
+    elem_ty: TyId,
+  },
+  /// The `Some` payload of `next_fn(it)`.
+  Iter {
+    it: Symbol,
+    it_ty: TyId,
+    next_fn: Symbol,
+    opt_ty: TyId,
+    some_disc: u32,
+    elem_ty: TyId,
+  },
+  Map {
+    src: Box<Pipe>,
+    f: StageFn,
+    elem_ty: TyId,
+  },
+  /// Pulls from `src` until `pred` holds.
+  Filter { src: Box<Pipe>, pred: StageFn },
+  /// Stops once `left` reaches zero.
+  Take { src: Box<Pipe>, left: Symbol },
+  /// `(count, elem)`, bumping `count`.
+  Enumerate {
+    src: Box<Pipe>,
+    count: Symbol,
+    elem_ty: TyId,
+  },
+  /// `(a, b)`, ending with the shorter side.
+  Zip {
+    a: Box<Pipe>,
+    b: Box<Pipe>,
+    elem_ty: TyId,
+  },
+  /// `a`, then `b`. `second` flips once `a` runs dry; `tmp`
+  /// joins the two sides' elements.
+  Chain {
+    a: Box<Pipe>,
+    b: Box<Pipe>,
+    second: Symbol,
+    tmp: Symbol,
+  },
+  /// Stands in for a header that failed to type, so the body
+  /// still binds its variable without cascading errors. Never
+  /// yields.
+  Dead { elem_ty: TyId },
+}
+
+impl Pipe {
+  /// The type of each element the pipeline yields.
+  pub(crate) fn elem_ty(&self) -> TyId {
+    match self {
+      Self::Array { elem_ty, .. }
+      | Self::Iter { elem_ty, .. }
+      | Self::Map { elem_ty, .. }
+      | Self::Enumerate { elem_ty, .. }
+      | Self::Zip { elem_ty, .. }
+      | Self::Dead { elem_ty } => *elem_ty,
+      Self::Filter { src, .. } | Self::Take { src, .. } => src.elem_ty(),
+      Self::Chain { a, .. } => a.elem_ty(),
+    }
+  }
+
+  /// Visit every local the pipe names — `true` for state that
+  /// outlives a pull, `false` for scratch — and every stage
+  /// capture (with its stage function and position), in a
+  /// fixed order, replacing each with what the visitor
+  /// returns.
+  pub(crate) fn rebind(
+    &mut self,
+    local: &mut dyn FnMut(Symbol, bool) -> Symbol,
+    capture: &mut dyn FnMut(Symbol, usize, ValueId) -> ValueId,
+  ) {
+    match self {
+      Self::Array { arr, idx, .. } => {
+        *arr = local(*arr, true);
+        *idx = local(*idx, true);
+      }
+      Self::Iter { it, .. } => *it = local(*it, true),
+      Self::Map { src, f, .. } | Self::Filter { src, pred: f } => {
+        src.rebind(local, capture);
+
+        for (i, value) in f.captures.iter_mut().enumerate() {
+          *value = capture(f.name, i, *value);
+        }
+      }
+      Self::Take { src, left } => {
+        src.rebind(local, capture);
+        *left = local(*left, true);
+      }
+      Self::Enumerate { src, count, .. } => {
+        src.rebind(local, capture);
+        *count = local(*count, true);
+      }
+      Self::Zip { a, b, .. } => {
+        a.rebind(local, capture);
+        b.rebind(local, capture);
+      }
+      Self::Chain { a, b, second, tmp } => {
+        a.rebind(local, capture);
+        b.rebind(local, capture);
+        *second = local(*second, true);
+        *tmp = local(*tmp, false);
+      }
+      Self::Dead { .. } => {}
+    }
+  }
+}
+
+/// A pipe an iterator function (`fun f(…) -> Iterator`)
+/// hands out: the function returns a tuple of the pipe's
+/// state, and each caller rebinds a copy of `pipe` to fresh
+/// locals loaded from it.
+#[derive(Clone, Debug)]
+pub(crate) struct PipeTemplate {
+  pub(crate) pipe: Pipe,
+  /// The locals the tuple carries, in order, then the
+  /// captures' types.
+  pub(crate) locals: Vec<(Symbol, TyId)>,
+  pub(crate) captures: Vec<TyId>,
+  /// Per-pull scratch locals (`Chain`'s `tmp`), declared
+  /// fresh by each caller.
+  pub(crate) scratch: Vec<(Symbol, TyId)>,
+}
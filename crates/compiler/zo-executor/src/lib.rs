@@ -1,5 +1,9 @@
+mod derive;
 mod executor;
+mod exhaustiveness;
 mod html_inline;
+mod iter;
+mod pattern;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,499 @@
+//! Match patterns and their compilation to decision trees.
+//!
+//! `execute_match` parses every arm's pattern into a typed
+//! [`Pat`] — each sub-pattern knows the type of the value it
+//! looks at — then [`compile`] turns the arm list into one
+//! [`Decision`] tree. Each test in the tree reads one value
+//! off the scrutinee by a [`Path`] of slot reads and compares
+//! it once; a leaf names the arm that fires and where each of
+//! its binders lives.
+//!
+//! The compiler works on a clause matrix: one row per arm,
+//! one column per scrutinee position the row still has to
+//! check. Wildcards, binders, tuples and structs never need a
+//! test — they expand into their fields or drop out — so
+//! every column left is a literal, a range or an enum
+//! variant. The first row's first column picks the next test;
+//! rows that agree with it continue on the `yes` side, rows
+//! that can still match without it on the `no` side.
+//!
+//! `|` alternatives split a row into one row per alternative,
+//! so an arm may be reached by several leaves, each storing
+//! the arm's binders from its own positions.
+
+use zo_interner::Symbol;
+use zo_span::Span;
+use zo_ty::TyId;
+
+use std::cmp::Ordering;
+
+/// One slot read on the way from the scrutinee to a value:
+/// `TupleIndex { index }` typed `ty`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Step {
+  pub(crate) index: u32,
+  pub(crate) ty: TyId,
+}
+
+/// Where a value sits inside the scrutinee. Empty for the
+/// scrutinee itself.
+pub(crate) type Path = Vec<Step>;
+
+/// A literal in pattern position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Lit {
+  Int(i128),
+  Char(u32),
+  Float(f64),
+  Bool(bool),
+  Str(Symbol),
+  Bytes(Symbol),
+}
+
+impl Lit {
+  /// Orders two literals of the same numeric kind — `None`
+  /// for strings, bytes, bools and mixed kinds, which ranges
+  /// never hold.
+  fn order(&self, other: &Self) -> Option<Ordering> {
+    match (self, other) {
+      (Self::Int(a), Self::Int(b)) => Some(a.cmp(b)),
+      (Self::Char(a), Self::Char(b)) => Some(a.cmp(b)),
+      (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+      _ => None,
+    }
+  }
+}
+
+/// A range pattern `lo..hi` or `lo..=hi`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Range {
+  pub(crate) lo: Lit,
+  pub(crate) hi: Lit,
+  pub(crate) inclusive: bool,
+}
+
+impl Range {
+  /// `lo..hi` or `lo..=hi` — `None` unless both ends are
+  /// ordered literals of one kind with a value between them.
+  pub(crate) fn new(lo: Lit, hi: Lit, inclusive: bool) -> Option<Self> {
+    let nonempty = match lo.order(&hi)? {
+      Ordering::Less => true,
+      Ordering::Equal => inclusive,
+      Ordering::Greater => false,
+    };
+
+    nonempty.then_some(Self { lo, hi, inclusive })
+  }
+
+  /// Whether `lit` falls inside. `None` when they can't be
+  /// ordered.
+  pub(crate) fn contains(&self, lit: &Lit) -> Option<bool> {
+    let above = self.lo.order(lit)? != Ordering::Greater;
+    let below = match lit.order(&self.hi)? {
+      Ordering::Less => true,
+      Ordering::Equal => self.inclusive,
+      Ordering::Greater => false,
+    };
+
+    Some(above && below)
+  }
+
+  /// Whether every value of `other` is inside.
+  pub(crate) fn covers(&self, other: &Self) -> Option<bool> {
+    let lo = self.lo.order(&other.lo)? != Ordering::Greater;
+    let hi = match other.hi.order(&self.hi)? {
+      Ordering::Less => true,
+      Ordering::Equal => self.inclusive || !other.inclusive,
+      Ordering::Greater => false,
+    };
+
+    Some(lo && hi)
+  }
+
+  /// Whether no value is in both.
+  fn disjoint(&self, other: &Self) -> Option<bool> {
+    let ends_before = |a: &Self, b: &Self| {
+      a.hi.order(&b.lo).map(|ord| match ord {
+        Ordering::Less => true,
+        Ordering::Equal => !a.inclusive,
+        Ordering::Greater => false,
+      })
+    };
+
+    Some(ends_before(self, other)? || ends_before(other, self)?)
+  }
+}
+
+/// A typed pattern.
+#[derive(Clone, Debug)]
+pub(crate) struct Pat {
+  pub(crate) kind: PatKind,
+  /// The type of the value the pattern looks at.
+  pub(crate) ty: TyId,
+  pub(crate) span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum PatKind {
+  /// `_`.
+  Wild,
+  /// `name` — matches anything and binds it.
+  Bind(Symbol),
+  Lit(Lit),
+  Range(Range),
+  /// A tuple `(p0, p1)` or a struct `Name { f, g: p }`: one
+  /// pattern per slot, in layout order, `_` for the fields
+  /// a struct pattern leaves out.
+  Fields(Vec<Pat>),
+  /// `Enum::Variant(p0, p1)` — the payload sits after the
+  /// discriminant slot.
+  Variant {
+    name: Symbol,
+    discriminant: u32,
+    fields: Vec<Pat>,
+  },
+  /// `p | q`.
+  Or(Vec<Pat>),
+}
+
+impl Pat {
+  pub(crate) fn wild(ty: TyId, span: Span) -> Self {
+    Self {
+      kind: PatKind::Wild,
+      ty,
+      span,
+    }
+  }
+
+  /// The names the pattern binds, with their types, in
+  /// source order. An `|` pattern binds the names of its
+  /// first alternative — the others must agree.
+  pub(crate) fn binders(&self) -> Vec<(Symbol, TyId, Span)> {
+    let mut out = Vec::new();
+
+    self.collect_binders(&mut out);
+
+    out
+  }
+
+  fn collect_binders(&self, out: &mut Vec<(Symbol, TyId, Span)>) {
+    match &self.kind {
+      PatKind::Bind(name) => out.push((*name, self.ty, self.span)),
+      PatKind::Fields(fields) | PatKind::Variant { fields, .. } => {
+        for field in fields {
+          field.collect_binders(out);
+        }
+      }
+      PatKind::Or(alts) => {
+        if let Some(first) = alts.first() {
+          first.collect_binders(out);
+        }
+      }
+      PatKind::Wild | PatKind::Lit(_) | PatKind::Range(_) => {}
+    }
+  }
+}
+
+/// One arm as the decision tree sees it.
+pub(crate) struct Arm {
+  pub(crate) pat: Pat,
+  /// Whether an `if` guard can still reject the arm after
+  /// its pattern matched.
+  pub(crate) guarded: bool,
+}
+
+/// A value the arm's body reads under `name`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Binding {
+  pub(crate) name: Symbol,
+  pub(crate) path: Path,
+  pub(crate) ty: TyId,
+}
+
+/// What a test node checks the value at its path against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Test {
+  Lit(Lit),
+  Range(Range),
+  /// The discriminant in slot 0 equals this.
+  Variant(u32),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Decision {
+  /// No arm matches.
+  Fail,
+  /// Arm `arm` matches once `bindings` are stored. A guarded
+  /// arm continues with `fallback` when its guard is false.
+  Leaf {
+    arm: usize,
+    bindings: Vec<Binding>,
+    fallback: Option<Box<Decision>>,
+  },
+  /// Reads the value at `path`, typed `ty`, and goes `yes`
+  /// when it passes `test`, `no` otherwise.
+  Test {
+    path: Path,
+    ty: TyId,
+    test: Test,
+    yes: Box<Decision>,
+    no: Box<Decision>,
+  },
+}
+
+impl Decision {
+  /// Whether some leaf fires arm `arm`.
+  pub(crate) fn reaches(&self, arm: usize) -> bool {
+    match self {
+      Self::Fail => false,
+      Self::Leaf {
+        arm: leaf,
+        fallback,
+        ..
+      } => *leaf == arm || fallback.as_ref().is_some_and(|f| f.reaches(arm)),
+      Self::Test { yes, no, .. } => yes.reaches(arm) || no.reaches(arm),
+    }
+  }
+}
+
+/// Compiles `arms`, in order, into a decision tree.
+pub(crate) fn compile(arms: &[Arm]) -> Decision {
+  let rows = arms
+    .iter()
+    .enumerate()
+    .map(|(arm, a)| Row {
+      cols: vec![(Vec::new(), a.pat.clone())],
+      bindings: Vec::new(),
+      arm,
+    })
+    .collect();
+
+  let guarded = arms.iter().map(|arm| arm.guarded).collect::<Vec<_>>();
+
+  compile_rows(rows, &guarded)
+}
+
+#[derive(Clone)]
+struct Row {
+  /// The positions still to check, each with its pattern.
+  /// Only literals, ranges and variants once simplified.
+  cols: Vec<(Path, Pat)>,
+  bindings: Vec<Binding>,
+  arm: usize,
+}
+
+/// How a row's pattern at the tested position relates to
+/// the test's outcome.
+enum Fit {
+  /// Passes exactly when the test does; `fields` replace the
+  /// column on the `yes` side.
+  Same(Vec<(Path, Pat)>),
+  /// Passes whenever the test does, and maybe otherwise.
+  Wider,
+  /// Passes only if the test does, but maybe not always.
+  Narrower,
+  /// Never passes when the test does.
+  Disjoint,
+  /// Can't tell either way.
+  Unknown,
+}
+
+fn compile_rows(rows: Vec<Row>, guarded: &[bool]) -> Decision {
+  let mut rows = rows.into_iter().flat_map(simplify).collect::<Vec<_>>();
+
+  if rows.is_empty() {
+    return Decision::Fail;
+  }
+
+  if rows[0].cols.is_empty() {
+    let first = rows.remove(0);
+    let fallback = guarded
+      .get(first.arm)
+      .copied()
+      .unwrap_or(false)
+      .then(|| Box::new(compile_rows(rows, guarded)));
+
+    return Decision::Leaf {
+      arm: first.arm,
+      bindings: first.bindings,
+      fallback,
+    };
+  }
+
+  let (path, head) = rows[0].cols[0].clone();
+  let test = match &head.kind {
+    PatKind::Lit(lit) => Test::Lit(*lit),
+    PatKind::Range(range) => Test::Range(*range),
+    PatKind::Variant { discriminant, .. } => Test::Variant(*discriminant),
+    _ => unreachable!("simplified columns only hold refutable patterns"),
+  };
+
+  let mut yes = Vec::new();
+  let mut no = Vec::new();
+
+  for row in rows {
+    let Some(col) = row.cols.iter().position(|(p, _)| *p == path) else {
+      yes.push(row.clone());
+      no.push(row);
+
+      continue;
+    };
+
+    match fit(&test, &path, &row.cols[col].1) {
+      Fit::Same(fields) => {
+        let mut row = row;
+
+        row.cols.splice(col..=col, fields);
+        yes.push(row);
+      }
+      Fit::Wider => {
+        let mut passed = row.clone();
+
+        passed.cols.remove(col);
+        yes.push(passed);
+        no.push(row);
+      }
+      Fit::Narrower => yes.push(row),
+      Fit::Disjoint => no.push(row),
+      Fit::Unknown => {
+        yes.push(row.clone());
+        no.push(row);
+      }
+    }
+  }
+
+  Decision::Test {
+    path,
+    ty: head.ty,
+    test,
+    yes: Box::new(compile_rows(yes, guarded)),
+    no: Box::new(compile_rows(no, guarded)),
+  }
+}
+
+/// Expands the columns that never need a test — wildcards,
+/// binders, tuples and structs — and splits `|` patterns
+/// into one row per alternative, in source order.
+fn simplify(row: Row) -> Vec<Row> {
+  let mut done = Vec::new();
+  let mut work = vec![row];
+
+  while let Some(mut row) = work.pop() {
+    let Some(col) = row.cols.iter().position(|(_, pat)| {
+      !matches!(
+        pat.kind,
+        PatKind::Lit(_) | PatKind::Range(_) | PatKind::Variant { .. }
+      )
+    }) else {
+      done.push(row);
+
+      continue;
+    };
+
+    let (path, pat) = row.cols.remove(col);
+
+    match pat.kind {
+      PatKind::Wild => work.push(row),
+      PatKind::Bind(name) => {
+        row.bindings.push(Binding {
+          name,
+          path,
+          ty: pat.ty,
+        });
+        work.push(row);
+      }
+      PatKind::Fields(fields) => {
+        let cols = field_cols(&path, fields, 0);
+
+        row.cols.splice(col..col, cols);
+        work.push(row);
+      }
+      PatKind::Or(alts) => {
+        // Pushed in reverse so the first alternative is
+        // popped — and so tried — first.
+        for alt in alts.into_iter().rev() {
+          let mut split = row.clone();
+
+          split.cols.insert(col, (path.clone(), alt));
+          work.push(split);
+        }
+      }
+      PatKind::Lit(_) | PatKind::Range(_) | PatKind::Variant { .. } => {
+        unreachable!("refutable columns are left in place")
+      }
+    }
+  }
+
+  done
+}
+
+/// The columns for `fields` read from the value at `path`,
+/// starting at slot `first`.
+fn field_cols(path: &Path, fields: Vec<Pat>, first: u32) -> Vec<(Path, Pat)> {
+  fields
+    .into_iter()
+    .enumerate()
+    .map(|(i, field)| {
+      let mut field_path = path.clone();
+
+      field_path.push(Step {
+        index: first + i as u32,
+        ty: field.ty,
+      });
+
+      (field_path, field)
+    })
+    .collect()
+}
+
+fn fit(test: &Test, path: &Path, pat: &Pat) -> Fit {
+  match (test, &pat.kind) {
+    (
+      Test::Variant(tag),
+      PatKind::Variant {
+        discriminant,
+        fields,
+        ..
+      },
+    ) => {
+      if tag == discriminant {
+        Fit::Same(field_cols(path, fields.clone(), 1))
+      } else {
+        Fit::Disjoint
+      }
+    }
+    (Test::Lit(lit), PatKind::Lit(other)) => {
+      if lit == other {
+        Fit::Same(Vec::new())
+      } else {
+        Fit::Disjoint
+      }
+    }
+    (Test::Lit(lit), PatKind::Range(range)) => match range.contains(lit) {
+      // The value is `lit`, inside the range — but failing
+      // the test says nothing about the range.
+      Some(true) => Fit::Wider,
+      Some(false) => Fit::Disjoint,
+      None => Fit::Unknown,
+    },
+    (Test::Range(range), PatKind::Lit(lit)) => match range.contains(lit) {
+      Some(true) => Fit::Narrower,
+      Some(false) => Fit::Disjoint,
+      None => Fit::Unknown,
+    },
+    (Test::Range(range), PatKind::Range(other)) => {
+      if range == other {
+        Fit::Same(Vec::new())
+      } else if other.covers(range) == Some(true) {
+        Fit::Wider
+      } else if range.covers(other) == Some(true) {
+        Fit::Narrower
+      } else if range.disjoint(other) == Some(true) {
+        Fit::Disjoint
+      } else {
+        Fit::Unknown
+      }
+    }
+    _ => Fit::Unknown,
+  }
+}
@@ -12,14 +12,19 @@ pub(crate) mod concurrency;
 pub(crate) mod constants;
 pub(crate) mod control_flow;
 pub(crate) mod cross_module_components;
+pub(crate) mod defer;
+pub(crate) mod derives;
 pub(crate) mod enums;
 pub(crate) mod errors;
 pub(crate) mod folding;
 pub(crate) mod generics;
 pub(crate) mod interpolation;
+pub(crate) mod iterators;
+pub(crate) mod lints;
 pub(crate) mod matching;
 pub(crate) mod modules;
 pub(crate) mod naming;
+pub(crate) mod operators;
 pub(crate) mod str_slicing;
 pub(crate) mod structs;
 pub(crate) mod styles;
@@ -198,6 +203,7 @@ fn test_simple_function() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -252,6 +258,7 @@ fn test_function_call() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(7, 3),
         is_test: false,
@@ -291,6 +298,7 @@ fn test_function_call() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(54, 3),
         is_test: false,
@@ -339,6 +347,7 @@ fn test_main_with_show() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -384,6 +393,7 @@ fn test_function_with_return() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -437,6 +447,7 @@ fn test_directives() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::ZERO,
         is_test: false,
@@ -460,3 +471,60 @@ fn test_directives() {
     ],
   );
 }
+
+#[test]
+fn test_node_tys_cover_identifiers() {
+  use crate::Executor;
+
+  use zo_interner::Interner;
+  use zo_parser::Parser;
+  use zo_token::Token;
+  use zo_tokenizer::Tokenizer;
+  use zo_ty_checker::TyChecker;
+
+  let source = "fun main() { imu x: int = 1; imu y: int = x; }";
+  let mut interner = Interner::new();
+  let tokenization = Tokenizer::new(source, &mut interner).tokenize();
+  let parsing = Parser::new(&tokenization, source).parse();
+  let mut ty_checker = TyChecker::new();
+
+  let out = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty_checker,
+  )
+  .with_node_tys()
+  .execute();
+
+  // The `x` read on the right of `y`'s binding carries a type
+  // even though it emits no literal annotation.
+  let x_use = parsing
+    .tree
+    .nodes
+    .iter()
+    .enumerate()
+    .rfind(|(idx, node)| {
+      let span = parsing.tree.spans[*idx];
+
+      node.token == Token::Ident
+        && &source[span.start as usize..span.end() as usize] == "x"
+    })
+    .map(|(idx, _)| idx)
+    .unwrap();
+
+  let ty_id = out
+    .node_tys
+    .iter()
+    .find(|a| a.node_idx == x_use)
+    .map(|a| a.ty_id)
+    .expect("the `x` read is typed");
+
+  assert!(matches!(
+    ty_checker.kind_of_ro(ty_id),
+    Ty::Int {
+      signed: true,
+      width: IntWidth::S32
+    }
+  ));
+}
@@ -1,5 +1,9 @@
-use crate::tests::common::{assert_sir_structure, execute_raw};
+use crate::tests::common::{
+  assert_no_errors, assert_sir_structure, execute_raw, execution_diagnostics,
+};
 
+use zo_error::ErrorKind;
+use zo_reporter::Detail;
 use zo_sir::Insn;
 
 #[test]
@@ -143,3 +147,284 @@ fun main() {
     calls.len()
   );
 }
+
+/// `abstract Greeter: Named` with a default `loud` — shared by
+/// the inheritance tests below.
+const GREETER: &str = r#"
+abstract Named {
+  fun name(self) -> str;
+}
+
+abstract Greeter : Named {
+  fun greet(self) -> str;
+
+  fun loud(self) -> str {
+    self.greet()
+  }
+}
+
+struct Point {
+  x: int,
+}
+"#;
+
+#[test]
+fn dyn_dispatch_indexes_inherited_methods_first() {
+  let source = format!(
+    r#"{GREETER}
+fun hello(item: any Greeter) -> str {{
+  imu name: str = item.name();
+  imu loud: str = item.loud();
+
+  item.greet()
+}}
+
+fun main() {{}}
+"#
+  );
+
+  let (sir, _) = execute_raw(&source);
+
+  // The vtable lists `Named` ahead of `Greeter`'s own:
+  // `name`, then `greet` and `loud`.
+  let indices = sir
+    .iter()
+    .filter_map(|i| match i {
+      Insn::DynDispatch { method_index, .. } => Some(*method_index),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+
+  assert_eq!(indices, [0, 2, 1]);
+}
+
+#[test]
+fn default_method_compiles_for_each_implementor() {
+  let source = format!(
+    r#"{GREETER}
+struct Dog {{
+  age: int,
+}}
+
+apply Named for Point {{
+  fun name(self) -> str {{
+    "point"
+  }}
+}}
+
+apply Greeter for Point {{
+  fun greet(self) -> str {{
+    "hi"
+  }}
+}}
+
+apply Named for Dog {{
+  fun name(self) -> str {{
+    "dog"
+  }}
+}}
+
+apply Greeter for Dog {{
+  fun greet(self) -> str {{
+    "woof"
+  }}
+
+  fun loud(self) -> str {{
+    "WOOF"
+  }}
+}}
+
+fun main() {{
+  imu p: Point = Point {{ x = 1 }};
+  imu d: Dog = Dog {{ age = 3 }};
+
+  imu a: str = p.loud();
+  imu b: str = d.loud();
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // main, two `name`s, two `greet`s, `Point::loud` from the
+  // default and `Dog::loud` overriding it — not a second
+  // default on top.
+  let user_funs = sir
+    .iter()
+    .filter(|i| {
+      matches!(
+        i,
+        Insn::FunDef {
+          kind: zo_value::FunctionKind::UserDefined,
+          ..
+        }
+      )
+    })
+    .count();
+
+  assert_eq!(user_funs, 7);
+}
+
+#[test]
+fn bound_names_the_missing_super_impl() {
+  let source = format!(
+    r#"{GREETER}
+apply Greeter for Point {{
+  fun greet(self) -> str {{
+    "hi"
+  }}
+}}
+
+fun hello<$T: Greeter>(item: $T) -> str {{
+  item.greet()
+}}
+
+fun main() {{
+  imu p: Point = Point {{ x = 1 }};
+  imu s: str = hello(p);
+}}
+"#
+  );
+
+  let (_, details) = execution_diagnostics(&source);
+
+  let missing = details.iter().find_map(|(e, d)| match d {
+    Detail::MissingImpl {
+      ty,
+      abstract_name,
+      required_by,
+    } if e.kind() == ErrorKind::BoundNotSatisfied => Some((
+      ty.to_string(),
+      abstract_name.to_string(),
+      required_by.as_deref().map(str::to_owned),
+    )),
+    _ => None,
+  });
+
+  assert_eq!(
+    missing,
+    Some(("Point".into(), "Named".into(), Some("Greeter".into())))
+  );
+}
+
+#[test]
+fn unknown_super_abstract_is_undefined() {
+  let (errors, _) = execution_diagnostics(
+    r#"
+abstract Greeter : Nameless {
+  fun greet(self) -> str;
+}
+
+fun main() {}
+"#,
+  );
+
+  assert!(
+    errors.iter().any(|e| e.kind() == ErrorKind::UndefinedType),
+    "expected UndefinedType for the unknown super",
+  );
+}
+
+#[test]
+fn imported_default_method_compiles_for_an_implementor() {
+  use crate::Executor;
+
+  use zo_interner::Interner;
+  use zo_module_resolver::{ImportedSymbols, splice_abstract_defaults};
+  use zo_parser::Parser;
+  use zo_tokenizer::Tokenizer;
+  use zo_ty_checker::TyChecker;
+
+  // One interner across modules, as the compiler driver shares it.
+  let mut interner = Interner::new();
+  let mut ty_checker = TyChecker::new();
+
+  // --- module B: declares the abstract and its default. ---
+  let scoring_src = r#"
+pub abstract Scored {
+  fun score(self) -> int;
+
+  fun bonus(self) -> int {
+    self.score() * 10
+  }
+}
+
+fun main() {}
+"#;
+
+  let tokenizer = Tokenizer::new(scoring_src, &mut interner);
+  let scoring_tok = tokenizer.tokenize();
+  let parser = Parser::new(&scoring_tok, scoring_src);
+  let scoring_par = parser.parse();
+
+  let abstract_defs = Executor::new(
+    &scoring_par.tree,
+    &mut interner,
+    &scoring_tok.literals,
+    &mut ty_checker,
+  )
+  .execute()
+  .abstract_defs;
+
+  // --- module A: applies it without writing `bonus`. ---
+  let main_src = r#"
+struct Cat {
+  lives: int,
+}
+
+apply Scored for Cat {
+  fun score(self) -> int {
+    self.lives
+  }
+}
+
+fun main() {
+  imu cat: Cat = Cat { lives = 9 };
+  imu b: int = cat.bonus();
+}
+"#;
+
+  let tokenizer = Tokenizer::new(main_src, &mut interner);
+  let main_tok = tokenizer.tokenize();
+  let parser = Parser::new(&main_tok, main_src);
+  let mut main_par = parser.parse();
+  let mut main_literals = main_tok.literals;
+
+  // The compiler pre-pass: splice B's default into A's tree.
+  let spliced = splice_abstract_defaults(
+    &mut main_par.tree,
+    &mut main_literals,
+    &abstract_defs,
+  );
+
+  assert_eq!(spliced.len(), 1, "the default body must splice");
+
+  let imports = ImportedSymbols {
+    abstract_defs,
+    abstract_default_bodies: spliced,
+    ..ImportedSymbols::default()
+  };
+
+  let mut ty_checker = TyChecker::new();
+  let sir = Executor::new(
+    &main_par.tree,
+    &mut interner,
+    &main_literals,
+    &mut ty_checker,
+  )
+  .with_imports(imports)
+  .execute()
+  .sir;
+
+  let bonus = interner.intern("Cat::bonus");
+
+  assert!(
+    sir
+      .instructions
+      .iter()
+      .any(|i| matches!(i, Insn::FunDef { name, .. } if *name == bonus)),
+    "`Cat::bonus` must compile from the imported default",
+  );
+}
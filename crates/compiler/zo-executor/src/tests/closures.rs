@@ -1,4 +1,8 @@
-use crate::tests::common::{assert_no_errors, assert_sir_structure};
+use crate::tests::common::{
+  assert_execution_error, assert_no_errors, assert_sir_structure,
+};
+
+use zo_error::ErrorKind;
 
 use zo_sir::Insn;
 use zo_ty::TyId;
@@ -926,3 +930,293 @@ fun main() {
     },
   );
 }
+
+#[test]
+fn capturing_closure_to_fn_param_passes_code_and_env() {
+  let source = r#"fun call_with(f: Fn(int) -> int, x: int) -> int {
+  f(x)
+}
+fun main() {
+  imu k: int = 10;
+  imu add_k := fn(x: int) -> int => x + k;
+  imu r: int = call_with(add_k, 1);
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    // `call_with` grows a trailing env param for `f`.
+    let call_with_params = sir.iter().find_map(|i| match i {
+      Insn::FunDef {
+        params,
+        kind: FunctionKind::UserDefined,
+        ..
+      } if params.len() == 3 => Some(params.len()),
+      _ => None,
+    });
+
+    assert_eq!(call_with_params, Some(3), "missing `f__env` param");
+
+    // The capture moves into a heap environment…
+    let env = sir.iter().find_map(|i| match i {
+      Insn::ClosureEnv { dst, captures, .. } => Some((*dst, captures.len())),
+      _ => None,
+    });
+
+    let Some((env, capture_count)) = env else {
+      panic!("expected a ClosureEnv for `add_k`");
+    };
+
+    assert_eq!(capture_count, 1);
+
+    // …and the call passes `(trampoline, x, env)`.
+    let call = sir.iter().find_map(|i| match i {
+      Insn::Call { args, .. } if args.last() == Some(&env) => Some(args),
+      _ => None,
+    });
+
+    assert_eq!(call.map(Vec::len), Some(3));
+
+    // The trampoline takes the closure's params plus the env.
+    let trampolines = sir
+      .iter()
+      .filter(|i| {
+        matches!(
+          i,
+          Insn::FunDef {
+            params,
+            kind: FunctionKind::Closure { capture_count: 0 },
+            ..
+          } if params.len() == 2
+        )
+      })
+      .count();
+
+    assert_eq!(trampolines, 1, "expected one `__env` trampoline");
+    assert!(sir.iter().any(|i| matches!(i, Insn::FnAddr { .. })));
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn ffi_callback_fills_its_declared_user_data_slot() {
+  let source = r#"ffi show(s: str);
+%% user_data(env).
+ffi on_event(cb: Fn(), env: s64, tag: int);
+fun main() {
+  imu label: str = "hit";
+  on_event(fn() { show(label); }, 7);
+  on_event(fn() { show("plain"); }, 8);
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    let ffi_params = sir.iter().find_map(|i| match i {
+      Insn::FunDef {
+        params,
+        kind: FunctionKind::Intrinsic,
+        ..
+      } if params.len() == 3 => Some(params.len()),
+      _ => None,
+    });
+
+    assert_eq!(ffi_params, Some(3), "the declared params stay as is");
+
+    let env = sir
+      .iter()
+      .find_map(|i| match i {
+        Insn::ClosureEnv { dst, .. } => Some(*dst),
+        _ => None,
+      })
+      .expect("capturing callback builds a ClosureEnv");
+
+    let calls = sir
+      .iter()
+      .filter_map(|i| match i {
+        Insn::Call { args, .. } if args.len() == 3 => Some(args.clone()),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(calls.len(), 2);
+
+    // `(cb, env, tag)` — the void* user-data slot follows the
+    // callback.
+    assert_eq!(calls[0][1], env);
+
+    // A non-capturing callback passes a null env.
+    let null_env = sir.iter().any(|i| {
+      matches!(
+        i,
+        Insn::ConstInt { dst, value: 0, .. } if *dst == calls[1][1]
+      )
+    });
+
+    assert!(null_env, "non-capturing callback should pass env 0");
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn ffi_callback_without_a_user_data_slot_is_called_bare() {
+  let source = r#"ffi atexit(cb: Fn()) -> int;
+fun bye() {}
+fun main() {
+  atexit(bye);
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    let args = sir
+      .iter()
+      .find_map(|i| match i {
+        Insn::Call { args, .. } => Some(args.len()),
+        _ => None,
+      })
+      .expect("`atexit` call");
+
+    assert_eq!(args, 1, "no env word for a slot-less callback");
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn capturing_closure_to_a_slotless_ffi_callback_is_rejected() {
+  assert_execution_error(
+    r#"ffi atexit(cb: Fn()) -> int;
+ffi show(s: str);
+fun main() {
+  imu label: str = "bye";
+  atexit(fn() { show(label); });
+}"#,
+    ErrorKind::CapturingClosureAsFnPointer,
+  );
+}
+
+#[test]
+fn user_data_slot_must_follow_a_callback() {
+  assert_execution_error(
+    r#"%% user_data(tag).
+ffi on_event(tag: int, cb: Fn());
+fun main() {}"#,
+    ErrorKind::InvalidAttributeValue,
+  );
+}
+
+#[test]
+fn closure_env_is_freed_after_the_call_and_drops_its_captures() {
+  let source = r#"struct Noisy {
+  id: int,
+}
+apply Noisy {
+  fun release(own self) {}
+}
+fun call_it(f: Fn()) {
+  f();
+}
+fun main() {
+  imu n: Noisy = Noisy { id = 7 };
+  call_it(fn() { n.id; });
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    let env = sir
+      .iter()
+      .find_map(|i| match i {
+        Insn::ClosureEnv { dst, drop, .. } => Some((*dst, *drop)),
+        _ => None,
+      })
+      .expect("capturing callback builds a ClosureEnv");
+
+    // The env's drop word is the address of the synthesized
+    // glue, not the null a capture-free closure gets.
+    let glue = sir.iter().find_map(|i| match i {
+      Insn::FnAddr { dst, callee, .. } if *dst == env.1 => Some(*callee),
+      _ => None,
+    });
+
+    let Some(glue) = glue else {
+      panic!("an owned capture needs drop glue, got {sir:#?}");
+    };
+
+    // The glue reads the capture back out of the env and
+    // drops it.
+    let body = sir
+      .iter()
+      .skip_while(|i| !matches!(i, Insn::FunDef { name, .. } if *name == glue))
+      .skip(1)
+      .take_while(|i| !matches!(i, Insn::FunDef { .. }))
+      .collect::<Vec<_>>();
+
+    assert!(body.iter().any(|i| matches!(i, Insn::ArrayIndex { .. })));
+    assert!(body.iter().any(|i| matches!(i, Insn::Drop { .. })));
+
+    // The caller releases the env once `call_it` returns.
+    let call = sir
+      .iter()
+      .position(
+        |i| matches!(i, Insn::Call { args, .. } if args.last() == Some(&env.0)),
+      )
+      .expect("call passes the env");
+
+    assert!(matches!(
+      sir[call + 1],
+      Insn::ClosureEnvFree { env: freed } if freed == env.0
+    ));
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn plain_fn_to_fn_param_goes_through_a_zero_env_trampoline() {
+  let source = r#"fun call_with(f: Fn(int) -> int, x: int) -> int {
+  f(x)
+}
+fun inc(x: int) -> int {
+  x + 1
+}
+fun main() {
+  imu r: int = call_with(inc, 1);
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    // `inc` keeps its own signature; the callee sees an
+    // `(x, env)` trampoline that drops the env and forwards.
+    let trampolines = sir
+      .iter()
+      .filter(|i| {
+        matches!(
+          i,
+          Insn::FunDef {
+            params,
+            kind: FunctionKind::Closure { capture_count: 0 },
+            ..
+          } if params.len() == 2
+        )
+      })
+      .count();
+
+    assert_eq!(trampolines, 1, "expected one `inc__env` trampoline");
+
+    // A plain fn has nothing to free.
+    assert!(!sir.iter().any(|i| matches!(
+      i,
+      Insn::ClosureEnv { .. } | Insn::ClosureEnvFree { .. }
+    )));
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn returning_a_capturing_closure_is_rejected() {
+  assert_execution_error(
+    r#"fun make_adder(k: int) -> Fn(int) -> int {
+  fn(x: int) -> int => x + k
+}
+fun main() {
+  imu add := make_adder(1);
+}"#,
+    ErrorKind::CapturingClosureAsFnPointer,
+  );
+}
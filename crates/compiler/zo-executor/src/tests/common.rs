@@ -249,3 +249,32 @@ pub(crate) fn execute_raw(source: &str) -> (Vec<Insn>, Vec<Annotation>) {
 
   (sir.instructions, annotations)
 }
+
+/// Execute source and return the patterns each non-exhaustive
+/// `match` is missing, in source order.
+pub(crate) fn missing_arms(source: &str) -> Vec<Vec<String>> {
+  let mut interner = Interner::new();
+  let tokenizer = Tokenizer::new(source, &mut interner);
+  let tokenization = tokenizer.tokenize();
+
+  let parser = Parser::new(&tokenization, source);
+  let parsing = parser.parse();
+
+  let mut ty_checker = TyChecker::new();
+
+  let executor = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty_checker,
+  );
+
+  let out = executor.execute();
+
+  collect_errors();
+
+  let mut arms = out.missing_arms.into_iter().collect::<Vec<_>>();
+
+  arms.sort_by_key(|(span, _)| span.start);
+  arms.into_iter().map(|(_, patterns)| patterns).collect()
+}
@@ -124,10 +124,9 @@ fn spawn_outside_nursery_is_error() {
 fn spawn_inside_nursery_emits_task_spawn_not_call() {
   // `spawn worker()` inside a `nursery { }` must redirect
   // the would-be `Call` emission into a `TaskSpawn`. The
-  // fn-local `Call` (from inside `worker`'s own body) would
-  // still fire for any statements there, but for this
-  // unit-body worker the only `Call`-ish emission is the
-  // spawn itself.
+  // task shim hands every green callee a trailing env, so the
+  // spawn targets `worker`'s zero-env trampoline — whose body
+  // is the only `Call` to `worker`.
   assert_sir_structure(
     r#"
       fun worker() {}
@@ -145,9 +144,28 @@ fn spawn_inside_nursery_emits_task_spawn_not_call() {
 
       assert_eq!(spawns, 1, "expected one TaskSpawn, got {insns:#?}");
       assert_eq!(
-        regular_calls, 0,
-        "no regular Call should be emitted for the spawned worker, got {insns:#?}"
+        regular_calls, 1,
+        "only the trampoline should call the spawned worker, got {insns:#?}"
       );
+
+      let Some(Insn::TaskSpawn { callee, args, .. }) =
+        insns.iter().find(|i| matches!(i, Insn::TaskSpawn { .. }))
+      else {
+        unreachable!();
+      };
+
+      assert!(args.is_empty(), "a plain worker spawns with no env");
+
+      // The trampoline takes the env the shim passes and drops
+      // it on the floor.
+      let trampoline_params = insns.iter().find_map(|i| match i {
+        Insn::FunDef { name, params, .. } if name == callee => {
+          Some(params.len())
+        }
+        _ => None,
+      });
+
+      assert_eq!(trampoline_params, Some(1), "got {insns:#?}");
     },
   );
 }
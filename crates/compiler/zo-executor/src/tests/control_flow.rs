@@ -29,6 +29,7 @@ fn test_if_simple() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -86,6 +87,7 @@ fun main() {}"#,
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -143,6 +145,7 @@ fun main() {}"#,
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(63, 3),
         is_test: false,
@@ -216,6 +219,7 @@ fn test_while_loop() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -259,6 +263,7 @@ fn test_implicit_return_literal() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
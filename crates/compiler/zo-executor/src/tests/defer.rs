@@ -0,0 +1,305 @@
+//! `defer stmt;` / `defer { … }` — scope-exit cleanup.
+//!
+//! The executor replays a deferred body at every exit of its
+//! scope, last registered first. The tests below trace the
+//! emitted SIR as a sequence of `mark(n)` calls and the
+//! terminators around them, so each exit's cleanup reads in
+//! the order it runs.
+//!
+//! ```sh
+//! cargo test -p zo-executor defer
+//! ```
+
+use crate::tests::common::{
+  assert_execution_error, assert_no_errors, assert_sir_structure,
+};
+
+use zo_error::ErrorKind;
+use zo_sir::{Insn, LoadSource};
+use zo_value::FunctionKind;
+
+use rustc_hash::FxHashMap as HashMap;
+
+/// Calls to the `ffi mark(n)` marker as `"n"`, plus `ret` /
+/// `jmp` / `join` (`NurseryEnd`) in emission order.
+fn trace(sir: &[Insn]) -> Vec<String> {
+  let mark = sir.iter().find_map(|insn| match insn {
+    Insn::FunDef {
+      name,
+      kind: FunctionKind::Intrinsic,
+      ..
+    } => Some(*name),
+    _ => None,
+  });
+
+  let mut consts = HashMap::default();
+  let mut steps = Vec::new();
+
+  for insn in sir {
+    match insn {
+      Insn::ConstInt { dst, value, .. } => {
+        consts.insert(*dst, *value);
+      }
+      Insn::Call { name, args, .. } if Some(*name) == mark => {
+        if let Some(value) = consts.get(&args[0]) {
+          steps.push(value.to_string());
+        }
+      }
+      Insn::Return { .. } => steps.push("ret".into()),
+      Insn::Jump { .. } => steps.push("jmp".into()),
+      Insn::NurseryEnd { .. } => steps.push("join".into()),
+      _ => {}
+    }
+  }
+
+  steps
+}
+
+#[test]
+fn defer_runs_lifo_on_fall_through_and_return() {
+  let source = r#"ffi mark(n: int);
+fun work(c: bool) -> bool {
+  defer mark(1);
+  defer {
+    mark(2);
+  }
+
+  if c {
+    return true;
+  }
+
+  mark(3);
+  false
+}
+fun main() {
+  imu r: bool = work(true);
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    assert_eq!(trace(sir), ["2", "1", "ret", "3", "2", "1", "ret", "ret"],);
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn defer_runs_before_break_and_continue() {
+  // Each exit of the loop body — `continue`, `break` and the
+  // fall-through back-edge — runs the body's defer first.
+  assert_sir_structure(
+    r#"ffi mark(n: int);
+fun main() {
+  mut i: int = 0;
+  while i < 3 {
+    i += 1;
+    defer mark(1);
+
+    if i == 1 {
+      continue;
+    }
+
+    if i == 2 {
+      break;
+    }
+
+    mark(2);
+  }
+}"#,
+    |sir| {
+      assert_eq!(trace(sir), ["1", "jmp", "1", "jmp", "2", "1", "jmp", "ret"],);
+    },
+  );
+}
+
+#[test]
+fn defer_in_inner_block_runs_at_its_close() {
+  assert_sir_structure(
+    r#"ffi mark(n: int);
+fun main() {
+  defer mark(1);
+
+  {
+    defer mark(2);
+    mark(3);
+  }
+
+  mark(4);
+}"#,
+    |sir| {
+      assert_eq!(trace(sir), ["3", "2", "4", "1", "ret"]);
+    },
+  );
+}
+
+#[test]
+fn defer_runs_on_try_propagation() {
+  let source = r#"ffi mark(n: int);
+enum Result<$T, $E> {
+  Pass($T),
+  Fail($E),
+}
+fun get(x: int) -> Result<int, int> {
+  Result::Pass(x)
+}
+fun work(x: int) -> Result<int, int> {
+  defer mark(1);
+  imu v: int = get(x)?;
+  mark(2);
+  Result::Pass(v)
+}
+fun main() {
+  imu r: Result<int, int> = work(1);
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    // `get`'s return, then `work`: the `?` error path
+    // returns after the defer, the Ok path continues past
+    // it and falls through to the tail return.
+    assert_eq!(
+      trace(sir),
+      ["ret", "jmp", "1", "ret", "2", "1", "ret", "ret"],
+    );
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn defer_in_nursery_runs_after_join() {
+  // Cancelling a task only latches its flag: the task still
+  // runs to its own `return`, defers included, and the
+  // nursery's defers wait for the join.
+  assert_sir_structure(
+    r#"ffi mark(n: int);
+fun worker() -> int {
+  defer mark(1);
+  mark(2);
+  42
+}
+fun main() {
+  nursery {
+    defer mark(3);
+    imu task: Task<int> = spawn worker();
+    task.cancel();
+  }
+}"#,
+    |sir| {
+      let cancel = sir
+        .iter()
+        .position(|i| matches!(i, Insn::TaskCancel { .. }))
+        .expect("`task.cancel()` lowers to a TaskCancel");
+      let join = sir
+        .iter()
+        .position(|i| matches!(i, Insn::NurseryEnd { .. }))
+        .expect("the nursery joins");
+
+      assert!(cancel < join);
+
+      // The second `ret` closes `worker`'s spawn trampoline.
+      assert_eq!(trace(sir), ["2", "1", "ret", "ret", "join", "3", "ret"]);
+    },
+  );
+}
+
+#[test]
+fn defer_reads_captures_as_bound_at_the_defer() {
+  let source = r#"ffi mark(n: int);
+fun main() {
+  imu x: int = 1;
+  defer mark(x);
+  imu x: int = 2;
+  mark(x);
+}"#;
+
+  assert_sir_structure(source, |sir| {
+    let x = sir
+      .iter()
+      .find_map(|i| match i {
+        Insn::VarDef { name, .. } => Some(*name),
+        _ => None,
+      })
+      .expect("`x` is declared");
+
+    // The deferred `mark` is the last call; its argument must
+    // not load the re-bound `x`.
+    let arg = sir
+      .iter()
+      .rev()
+      .find_map(|i| match i {
+        Insn::Call { args, .. } => Some(args[0]),
+        _ => None,
+      })
+      .expect("deferred call");
+
+    let loaded = sir.iter().find_map(|i| match i {
+      Insn::Load {
+        dst,
+        src: LoadSource::Local(name),
+        ..
+      } if *dst == arg => Some(*name),
+      _ => None,
+    });
+
+    let Some(stash) = loaded.filter(|name| *name != x) else {
+      panic!("the deferred body loads the shadowing `x`, got {sir:#?}");
+    };
+
+    // The hidden local is saved from the first `x`, ahead of
+    // the re-binding.
+    let saved = sir
+      .iter()
+      .position(|i| matches!(i, Insn::Store { name, .. } if *name == stash))
+      .expect("the capture is saved");
+    let rebound = sir
+      .iter()
+      .rposition(|i| matches!(i, Insn::VarDef { name, .. } if *name == x))
+      .expect("`x` is re-bound");
+
+    assert!(saved < rebound);
+  });
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn control_flow_in_defer_is_rejected() {
+  assert_execution_error(
+    r#"fun main() {
+  defer {
+    return;
+  }
+}"#,
+    ErrorKind::ControlFlowInDefer,
+  );
+
+  assert_execution_error(
+    r#"fun main() {
+  while true {
+    defer {
+      break;
+    }
+  }
+}"#,
+    ErrorKind::ControlFlowInDefer,
+  );
+}
+
+#[test]
+fn defer_may_loop_on_its_own() {
+  // A `break` in a loop of the body's own stays inside it.
+  assert_no_errors(
+    r#"ffi mark(n: int);
+fun main() {
+  defer {
+    mut i: int = 0;
+    while i < 3 {
+      i += 1;
+      if i == 2 {
+        break;
+      }
+      mark(i);
+    }
+  }
+}"#,
+  );
+}
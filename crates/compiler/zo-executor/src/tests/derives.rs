@@ -0,0 +1,302 @@
+use crate::tests::common::{
+  assert_no_errors, execute_raw, execution_diagnostics,
+};
+
+use zo_error::ErrorKind;
+use zo_reporter::Detail;
+use zo_sir::{BinOp, Insn};
+
+/// The core abstracts the derives implement — the executor
+/// tests run without the preload.
+const CORE: &str = r#"
+abstract Eq {
+  fun eq(self, other: Self) -> bool;
+}
+
+abstract Ord {
+  fun cmp(self, other: Self) -> int;
+}
+
+abstract Hash {
+  fun hash(self) -> int;
+}
+
+abstract Show {
+  fun show(self) -> str;
+}
+
+abstract Clone {
+  fun clone(self) -> Self;
+}
+"#;
+
+#[test]
+fn eq_operator_dispatches_to_derived_eq() {
+  let source = format!(
+    r#"{CORE}
+%% eq.
+struct Point {{
+  x: int,
+  y: int,
+}}
+
+fun main() {{
+  imu a: Point = Point {{ x = 1, y = 2 }};
+  imu b: Point = Point {{ x = 1, y = 3 }};
+  imu same: bool = a == b;
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `Point::eq` compares both fields, and `==` calls it.
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::BinOp { op: BinOp::Eq, .. }))
+      .count(),
+    2
+  );
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::Call { .. }))
+      .count(),
+    1
+  );
+}
+
+#[test]
+fn ord_on_enum_orders_variants_then_payloads() {
+  let source = format!(
+    r#"{CORE}
+%% eq, ord.
+enum Shape {{
+  Dot,
+  Circle(int),
+}}
+
+fun main() {{
+  imu a: Shape = Shape::Dot;
+  imu b: Shape = Shape::Circle(2);
+  imu less: bool = a < b;
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // Discriminants first, then the `Circle` payload.
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::BinOp { op: BinOp::Lt, .. }))
+      .count(),
+    3
+  );
+}
+
+#[test]
+fn show_joins_fields_into_one_string() {
+  let source = format!(
+    r#"{CORE}
+%% show.
+struct Point {{
+  x: int,
+  y: int,
+}}
+
+%% show.
+struct Label {{
+  name: str,
+  at: Point,
+}}
+
+fun main() {{
+  imu l: Label = Label {{ name = "o", at = Point {{ x = 1, y = 2 }} }};
+  imu s: str = l.show();
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // One `string.format` per type; `Label::show` calls
+  // `Point::show` for its nested field.
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::StringFormat { .. }))
+      .count(),
+    2
+  );
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::ToStr { .. }))
+      .count(),
+    2
+  );
+}
+
+#[test]
+fn default_honors_field_defaults() {
+  let source = format!(
+    r#"{CORE}
+%% default.
+struct Point {{
+  x: int,
+  y: int = 7,
+}}
+
+fun main() {{
+  imu p: Point = Point::default();
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  assert!(
+    sir
+      .iter()
+      .any(|i| matches!(i, Insn::ConstInt { value: 7, .. }))
+  );
+  assert!(
+    sir
+      .iter()
+      .any(|i| matches!(i, Insn::ConstInt { value: 0, .. }))
+  );
+}
+
+#[test]
+fn hash_over_str_field_names_the_field() {
+  let (_, details) = execution_diagnostics(&format!(
+    r#"{CORE}
+%% hash.
+struct User {{
+  id: int,
+  name: str,
+}}
+
+fun main() {{}}
+"#
+  ));
+
+  let reported = details.iter().find_map(|(e, d)| match d {
+    Detail::DeriveField { derive, field, ty }
+      if e.kind() == ErrorKind::DeriveUnsupportedField =>
+    {
+      Some((derive.to_string(), field.to_string(), ty.to_string()))
+    }
+    _ => None,
+  });
+
+  assert_eq!(reported, Some(("hash".into(), "name".into(), "str".into())));
+}
+
+#[test]
+fn hash_over_str_field_calls_str_hash() {
+  let source = format!(
+    r#"{CORE}
+apply Hash for str {{
+  fun hash(self) -> int {{
+    self.len
+  }}
+}}
+
+%% hash.
+struct User {{
+  id: int,
+  name: str,
+}}
+
+fun main() {{}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `User::hash` folds `name` through `str`'s `hash`.
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::Call { .. }))
+      .count(),
+    1
+  );
+}
+
+#[test]
+fn struct_map_key_hands_its_hash_and_eq_to_the_map() {
+  let source = format!(
+    r#"{CORE}
+apply Eq for str {{
+  fun eq(self, other: str) -> bool {{
+    self.len == other.len
+  }}
+}}
+
+apply Hash for str {{
+  fun hash(self) -> int {{
+    self.len
+  }}
+}}
+
+struct HashMap {{
+  ptr: int,
+}}
+
+apply HashMap {{
+  pub fun new() -> Self {{
+    Self {{ ptr = 0 }}
+  }}
+}}
+
+%% eq, hash.
+struct Point {{
+  x: int,
+  label: str,
+}}
+
+fun main() {{
+  imu m: HashMap<Point, int> = HashMap::new();
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `(key_kind = Derived, key_sz, val_sz)`, then the
+  // addresses of `Point::hash` and `Point::eq`.
+  assert!(
+    sir
+      .iter()
+      .any(|i| matches!(i, Insn::ConstInt { value: 3, .. }))
+  );
+  assert!(
+    sir
+      .iter()
+      .any(|i| matches!(i, Insn::ConstInt { value: 16, .. }))
+  );
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::FnAddr { .. }))
+      .count(),
+    2
+  );
+}
@@ -816,3 +816,68 @@ fun main() {
     ty_checker.kind_of_ro(field_ty)
   );
 }
+
+// === CALL-SITE TYPE ARGS ===
+
+#[test]
+fn test_generic_call_records_chosen_types() {
+  let source = r#"fun identity<$T>(x: $T) -> $T { x }
+fun main() {
+  imu a := identity(42);
+  imu b := identity("zo");
+}"#;
+
+  let mut interner = Interner::new();
+  let tokenizer = Tokenizer::new(source, &mut interner);
+  let tokenization = tokenizer.tokenize();
+  let parser = Parser::new(&tokenization, source);
+  let parsing = parser.parse();
+
+  let mut ty_checker = TyChecker::new();
+
+  let executor = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty_checker,
+  );
+
+  let out = executor.execute();
+  let errors = collect_errors();
+
+  assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+
+  let mut sites = out.generic_args.into_iter().collect::<Vec<_>>();
+
+  sites.sort_by_key(|(span, _)| span.start);
+
+  let chosen = sites
+    .iter()
+    .map(|(_, tys)| {
+      tys
+        .iter()
+        .map(|ty_id| ty_checker.kind_of(*ty_id))
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+
+  assert!(
+    matches!(chosen.as_slice(), [a, b] if matches!(a.as_slice(), [Ty::Int { .. }]) && b == &[Ty::Str]),
+    "expected [int] then [str], got {chosen:?}"
+  );
+
+  // Each `:=` binding records its inferred type.
+  let mut locals = out.local_tys.into_iter().collect::<Vec<_>>();
+
+  locals.sort_by_key(|(span, _)| span.start);
+
+  let kinds = locals
+    .iter()
+    .map(|(_, ty_id)| ty_checker.kind_of(*ty_id))
+    .collect::<Vec<_>>();
+
+  assert!(
+    matches!(kinds.as_slice(), [Ty::Int { .. }, Ty::Str]),
+    "expected int then str locals, got {kinds:?}"
+  );
+}
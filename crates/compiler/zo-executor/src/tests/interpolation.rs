@@ -1,6 +1,8 @@
-use crate::tests::common::assert_sir_structure;
+use crate::tests::common::{assert_execution_error, assert_sir_structure};
 
+use zo_error::ErrorKind;
 use zo_sir::Insn;
+use zo_token::{FormatKind, FormatSpec};
 
 #[test]
 fn test_interp_desugars_to_show_calls() {
@@ -135,3 +137,115 @@ fn test_interp_single_var_only() {
     },
   );
 }
+
+// === FORMAT SPECS ===
+
+/// Finds the packed `ConstInt` for `spec` and asserts it feeds a
+/// two-argument runtime `Call` — `zo_fmt_*(value, spec)`.
+fn assert_formats_with(sir: &[Insn], spec: FormatSpec) {
+  let packed = sir.iter().find_map(|i| match i {
+    Insn::ConstInt { dst, value, .. } if *value == spec.pack() => Some(*dst),
+    _ => None,
+  });
+
+  let Some(packed) = packed else {
+    panic!("expected a ConstInt carrying the packed spec");
+  };
+
+  let fed = sir.iter().any(|i| {
+    matches!(i, Insn::Call { args, .. } if args.len() == 2 && args[1] == packed)
+  });
+
+  assert!(fed, "packed spec should be the runtime call's second arg");
+}
+
+#[test]
+fn test_interp_spec_on_float_in_showln() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu price: float = 9.5;
+  showln("total: {price:.2}");
+}"#,
+    |sir| {
+      assert_formats_with(
+        sir,
+        FormatSpec {
+          precision: Some(2),
+          ..FormatSpec::default()
+        },
+      );
+    },
+  );
+}
+
+#[test]
+fn test_interp_spec_on_int_in_expression() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu id: int = 42;
+  imu label: str = "id {id:06}";
+}"#,
+    |sir| {
+      assert_formats_with(
+        sir,
+        FormatSpec {
+          zero: true,
+          width: 6,
+          ..FormatSpec::default()
+        },
+      );
+    },
+  );
+}
+
+#[test]
+fn test_interp_spec_hex_on_int() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu addr: int = 255;
+  showln("{addr:#x}");
+}"#,
+    |sir| {
+      assert_formats_with(
+        sir,
+        FormatSpec {
+          alternate: true,
+          kind: FormatKind::LowerHex,
+          ..FormatSpec::default()
+        },
+      );
+    },
+  );
+}
+
+#[test]
+fn test_interp_spec_precision_on_int_rejected() {
+  assert_execution_error(
+    r#"fun main() {
+  imu id: int = 42;
+  showln("{id:.2}");
+}"#,
+    ErrorKind::FormatSpecTypeMismatch,
+  );
+}
+
+#[test]
+fn test_interp_spec_radix_on_str_rejected() {
+  assert_execution_error(
+    r#"fun main() {
+  imu name: str = "zo";
+  showln("{name:x}");
+}"#,
+    ErrorKind::FormatSpecTypeMismatch,
+  );
+}
+
+#[test]
+fn test_interp_spec_on_undefined_variable() {
+  assert_execution_error(
+    r#"fun main() {
+  showln("{ghost:>4}");
+}"#,
+    ErrorKind::UndefinedVariable,
+  );
+}
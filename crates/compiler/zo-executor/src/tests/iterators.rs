@@ -0,0 +1,411 @@
+use crate::tests::common::{
+  assert_no_errors, execute_raw, execution_diagnostics,
+};
+
+use zo_error::ErrorKind;
+use zo_reporter::Detail;
+use zo_sir::Insn;
+
+/// `core::iter`'s abstract, plus an `Option` and an impl for
+/// the tests — the executor tests run without the preload.
+const ITER: &str = concat!(
+  include_str!("../../../../compiler-lib/core/iter.zo"),
+  r#"
+enum Option<$T> {
+  Some($T),
+  None,
+}
+
+struct Countdown {
+  n: int,
+}
+
+apply Iterator for Countdown {
+  fun next(mut self) -> Option<int> {
+    if self.n == 0 {
+      return Option::None;
+    }
+
+    self.n -= 1;
+    Option::Some(self.n + 1)
+  }
+}
+"#
+);
+
+#[test]
+fn for_over_iterator_calls_next() {
+  let source = format!(
+    r#"{ITER}
+fun main() {{
+  mut c: Countdown = Countdown {{ n = 3 }};
+  mut total: int = 0;
+
+  for x := c {{
+    total += x;
+  }}
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // One `next` call in the loop head, no array walk.
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::Call { .. }))
+      .count(),
+    1
+  );
+  assert!(!sir.iter().any(|i| matches!(i, Insn::ArrayLen { .. })));
+}
+
+#[test]
+fn adapters_fuse_without_intermediate_arrays() {
+  let source = r#"
+fun main() {
+  imu xs: []int = [1, 2, 3, 4, 5];
+  mut total: int = 0;
+
+  for x := xs.map(fn(v) => v * 2).filter(fn(v) => v > 2).take(3) {
+    total += x;
+  }
+}
+"#;
+
+  assert_no_errors(source);
+
+  let (sir, _) = execute_raw(source);
+
+  // Only the source literal allocates, and each stage is a
+  // direct call to its closure — nothing for `--release` to
+  // dispatch through.
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::ArrayLiteral { .. }))
+      .count(),
+    1
+  );
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::Call { .. }))
+      .count(),
+    2
+  );
+  assert!(!sir.iter().any(|i| matches!(i, Insn::CallIndirect { .. })));
+}
+
+#[test]
+fn enumerate_and_zip_yield_tuples() {
+  let source = format!(
+    r#"{ITER}
+fun main() {{
+  imu xs: []int = [10, 20, 30];
+  mut c: Countdown = Countdown {{ n = 2 }};
+  mut total: int = 0;
+
+  for p := xs.enumerate() {{
+    total += p.0 * p.1;
+  }}
+
+  for p := c.zip(xs) {{
+    total += p.0 + p.1;
+  }}
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::TupleLiteral { .. }))
+      .count(),
+    2
+  );
+}
+
+#[test]
+fn chain_joins_both_sources() {
+  let source = format!(
+    r#"{ITER}
+fun main() {{
+  imu xs: []int = [1, 2];
+  mut c: Countdown = Countdown {{ n = 2 }};
+  mut total: int = 0;
+
+  for x := c.chain(xs) => total += x;
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `Countdown::next` first, then the array walk.
+  assert!(sir.iter().any(|i| matches!(i, Insn::Call { .. })));
+  assert!(sir.iter().any(|i| matches!(i, Insn::ArrayIndex { .. })));
+}
+
+#[test]
+fn inherent_method_shadows_adapter() {
+  let source = format!(
+    r#"{ITER}
+apply Countdown {{
+  fun take(self, k: int) -> []int {{
+    [k]
+  }}
+}}
+
+fun main() {{
+  mut c: Countdown = Countdown {{ n = 3 }};
+  mut total: int = 0;
+
+  for x := c.take(2) {{
+    total += x;
+  }}
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `take` is `Countdown`'s own method: the loop walks the
+  // array it returns and never calls `next`.
+  assert!(sir.iter().any(|i| matches!(i, Insn::ArrayLen { .. })));
+}
+
+#[test]
+fn for_over_struct_without_iterator_names_the_abstract() {
+  let (_, details) = execution_diagnostics(
+    r#"
+struct Bag {
+  n: int,
+}
+
+fun main() {
+  imu b: Bag = Bag { n = 1 };
+
+  for x := b {
+  }
+}
+"#,
+  );
+
+  let missing = details.iter().find_map(|(e, d)| match d {
+    Detail::MissingImpl {
+      ty,
+      abstract_name,
+      required_by: None,
+    } if e.kind() == ErrorKind::BoundNotSatisfied => {
+      Some((ty.to_string(), abstract_name.to_string()))
+    }
+    _ => None,
+  });
+
+  assert_eq!(missing, Some(("Bag".into(), "Iterator".into())));
+}
+
+#[test]
+fn generic_iterator_yields_its_type_argument() {
+  let source = format!(
+    r#"{ITER}
+struct Once<$T> {{
+  v: $T,
+  done: bool,
+}}
+
+apply Iterator for Once<$T> {{
+  fun next(mut self) -> Option<$T> {{
+    if self.done {{
+      return Option::None;
+    }}
+
+    self.done = true;
+    Option::Some(self.v)
+  }}
+}}
+
+fun main() {{
+  mut o: Once<str> = Once {{ v = "hi", done = false }};
+
+  for x := o {{
+    imu s: str = x;
+  }}
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+}
+
+#[test]
+fn bound_chain_resumes_in_later_loops() {
+  let source = format!(
+    r#"{ITER}
+fun main() {{
+  mut c: Countdown = Countdown {{ n = 6 }};
+  mut total: int = 0;
+  imu odd := c.filter(fn(x: int) => x % 2 == 1);
+
+  for x := odd.take(2) {{
+    total += x;
+  }}
+
+  for x := odd {{
+    total += x;
+  }}
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // The binding stores no value: each loop pulls through
+  // `next` and the filter directly.
+  assert!(!sir.iter().any(|i| matches!(i, Insn::ArrayLiteral { .. })));
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::Call { .. }))
+      .count(),
+    4
+  );
+}
+
+#[test]
+fn bound_array_map_stays_eager() {
+  let source = r#"
+apply []$T {
+  pub fun map<$U>(self, f: Fn($T) -> $U) -> []$U {
+    mut out: []$U = [];
+    for value := self {
+      out.push(f(value));
+    }
+
+    out
+  }
+}
+
+fun main() {
+  imu xs: []int = [1, 2, 3];
+  imu ys := xs.map(fn(v: int) => v * 2);
+  imu zs: []int = ys;
+}
+"#;
+
+  assert_no_errors(source);
+}
+
+#[test]
+fn iterator_function_returns_its_chain_state() {
+  let source = format!(
+    r#"{ITER}
+fun is_even(x: int) -> bool {{
+  x % 2 == 0
+}}
+
+fun evens(xs: []int) -> Iterator {{
+  xs.filter(is_even)
+}}
+
+fun main() {{
+  mut total: int = 0;
+
+  for x := evens([1, 2, 3, 4]) {{
+    total += x;
+  }}
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `evens` hands back its array and cursor as a tuple the
+  // caller's loop resumes.
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(i, Insn::TupleLiteral { .. }))
+      .count(),
+    1
+  );
+  let state = sir.iter().find_map(|i| match i {
+    Insn::Call { dst, args, .. } if args.len() == 1 => Some(*dst),
+    _ => None,
+  });
+
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(
+        i,
+        Insn::TupleIndex { tuple, .. } if Some(*tuple) == state
+      ))
+      .count(),
+    2
+  );
+}
+
+#[test]
+fn iterator_function_defined_after_its_caller() {
+  let source = format!(
+    r#"{ITER}
+fun main() {{
+  mut total: int = 0;
+
+  for x := evens([1, 2, 3, 4]) {{
+    total += x;
+  }}
+}}
+
+fun evens(xs: []int) -> Iterator {{
+  xs.filter(is_even)
+}}
+
+fun is_even(x: int) -> bool {{
+  x % 2 == 0
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `evens` lowers first, and the caller resumes the same
+  // state tuple as when it comes first.
+  let calls = sir
+    .iter()
+    .filter_map(|i| match i {
+      Insn::Call { dst, args, .. } if args.len() == 1 => Some(*dst),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+
+  assert_eq!(
+    sir
+      .iter()
+      .filter(|i| matches!(
+        i,
+        Insn::TupleIndex { tuple, .. } if calls.contains(tuple)
+      ))
+      .count(),
+    2
+  );
+}
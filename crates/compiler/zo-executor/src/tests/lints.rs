@@ -0,0 +1,161 @@
+//! ```sh
+//! cargo test -p zo-executor --lib tests::lints
+//! ```
+//!
+//! Lint-level attributes — `%% allow(..).` / `warn` / `deny`
+//! record a scope over the attributed item, resolved against
+//! the diagnostics at report time.
+
+use super::common::{assert_execution_error, execution_errors, span_text};
+
+use crate::Executor;
+
+use zo_error::lint::{self, Level, LintLevels};
+use zo_error::{ErrorKind, Severity};
+use zo_interner::Interner;
+use zo_parser::Parser;
+use zo_reporter::collect_lint_scopes;
+use zo_tokenizer::Tokenizer;
+use zo_ty_checker::TyChecker;
+
+/// Executes `source` and resolves its diagnostics against the
+/// lint scopes it declares. Returns `(kind, severity, text)`
+/// for every lint that survives.
+fn resolved(source: &str) -> Vec<(ErrorKind, Severity, &str)> {
+  let _ = collect_lint_scopes();
+
+  let errors = execution_errors(source);
+  let mut levels = LintLevels::new();
+
+  levels.extend_scopes(collect_lint_scopes());
+
+  errors
+    .iter()
+    .filter(|e| lint::is_lint(e.kind()))
+    .filter_map(|e| {
+      let severity = levels.severity(e)?;
+
+      Some((e.kind(), severity, span_text(source, e.span())))
+    })
+    .collect()
+}
+
+#[test]
+fn allow_silences_lint_inside_function() {
+  let diagnostics = resolved(
+    r#"%% allow(non-snake-case-name).
+fun quiet() {
+  imu myCount := 1;
+
+  showln("{myCount}");
+}
+
+fun main() {
+  imu myTotal := 2;
+
+  showln("{myTotal}");
+  quiet();
+}"#,
+  );
+
+  assert_eq!(
+    diagnostics,
+    [(ErrorKind::NonSnakeCaseName, Severity::Warning, "myTotal")]
+  );
+}
+
+#[test]
+fn deny_promotes_lint_inside_pack() {
+  let diagnostics = resolved(
+    r#"%% deny(non-snake-case-name).
+pack strict {
+  pub fun run() {
+    imu badName := 1;
+
+    showln("{badName}");
+  }
+}
+
+fun main() {
+  strict::run();
+}"#,
+  );
+
+  assert!(
+    diagnostics.contains(&(
+      ErrorKind::NonSnakeCaseName,
+      Severity::Error,
+      "badName"
+    )),
+    "{diagnostics:?}"
+  );
+}
+
+#[test]
+fn innermost_attribute_wins() {
+  let _ = collect_lint_scopes();
+
+  execution_errors(
+    r#"%% deny(non-snake-case-name).
+pack outer {
+  %% allow(non-snake-case-name, unused-variable).
+  fun inner() {}
+}
+
+fun main() {}"#,
+  );
+
+  let scopes = collect_lint_scopes();
+
+  assert_eq!(scopes.len(), 3);
+  assert!(scopes.iter().any(|s| s.level == Level::Deny));
+  assert!(scopes.iter().any(|s| s.kind == ErrorKind::UnusedVariable));
+  // The nested scope sits inside the pack's.
+  assert!(scopes[1].start > scopes[0].start && scopes[1].end < scopes[0].end);
+}
+
+#[test]
+fn unknown_lint_is_reported() {
+  assert_execution_error(
+    r#"%% allow(no-such-lint).
+fun main() {}"#,
+    ErrorKind::UnknownLint,
+  );
+}
+
+#[test]
+fn hard_errors_are_not_lints() {
+  assert_execution_error(
+    r#"%% allow(undefined-variable).
+fun main() {}"#,
+    ErrorKind::UnknownLint,
+  );
+}
+
+#[test]
+fn lint_attribute_does_not_feed_the_next_item() {
+  let source = r#"%% allow(non-snake-case-name), serialize.
+struct Point {
+  x: int,
+}"#;
+
+  let mut interner = Interner::new();
+  let tokenization = Tokenizer::new(source, &mut interner).tokenize();
+  let parsing = Parser::new(&tokenization, source).parse();
+  let mut ty_checker = TyChecker::new();
+
+  let executor = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty_checker,
+  );
+
+  let _ = collect_lint_scopes();
+
+  assert_eq!(
+    executor.pending_attributes_after_attributes(),
+    ["serialize"]
+  );
+  assert_eq!(collect_lint_scopes().len(), 1);
+}
@@ -1,6 +1,11 @@
-use crate::tests::common::{assert_sir_structure, execute_raw};
+use crate::tests::common::{
+  assert_execution_error, assert_sir_structure, execute_raw,
+  execution_diagnostics, execution_errors, missing_arms,
+};
 
-use zo_sir::Insn;
+use zo_error::ErrorKind;
+use zo_reporter::Detail;
+use zo_sir::{BinOp, Insn};
 
 use rustc_hash::FxHashMap as HashMap;
 
@@ -774,3 +779,403 @@ fn match_string_literal_emits_const_string_and_cmp() {
     },
   );
 }
+
+#[test]
+fn non_exhaustive_enum_match_records_missing_arms() {
+  let arms = missing_arms(
+    r#"
+enum Loot {
+  Gold(int),
+  Nothing,
+}
+
+fun main() {
+  imu r: Loot = Loot::Nothing;
+  match r {
+    Loot::Nothing => showln(0),
+  }
+}"#,
+  );
+
+  assert_eq!(arms, vec![vec!["Loot::Gold(_)".to_string()]]);
+}
+
+#[test]
+fn exhaustive_match_records_no_missing_arms() {
+  let arms = missing_arms(
+    r#"
+fun main() {
+  imu b: bool = true;
+  match b {
+    true => showln(1),
+    false => showln(0),
+  }
+}"#,
+  );
+
+  assert!(arms.is_empty(), "expected no missing arms, got {arms:?}");
+}
+
+// -- Nested patterns ---------------------------------------------------
+
+#[test]
+fn match_tuple_binder_defines_local() {
+  assert_sir_structure(
+    r#"fun main() {
+  match (0, 5) {
+    (0, y) => showln(y),
+    _ => showln(-1),
+  }
+}"#,
+    |sir| {
+      // `y` reads slot 1 and is declared for the arm body.
+      let has_field_read = sir
+        .iter()
+        .any(|i| matches!(i, Insn::TupleIndex { index: 1, .. }));
+
+      assert!(has_field_read, "expected TupleIndex index=1 for `y`");
+
+      let var_defs = sir
+        .iter()
+        .filter(|i| matches!(i, Insn::VarDef { .. }))
+        .count();
+
+      assert_eq!(var_defs, 1, "expected one VarDef for `y`");
+    },
+  );
+}
+
+#[test]
+fn match_range_patterns_emit_bound_compares() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu n: int = 12;
+  match n {
+    1..=9 => showln(1),
+    10..20 => showln(2),
+    _ => showln(0),
+  }
+}"#,
+    |sir| {
+      let count = |op: BinOp| {
+        sir
+          .iter()
+          .filter(|i| matches!(i, Insn::BinOp { op: o, .. } if *o == op))
+          .count()
+      };
+
+      // Both ranges check their low end; `..=` and `..`
+      // differ on the high one.
+      assert_eq!(count(BinOp::Gte), 2, "expected two low-end compares");
+      assert_eq!(count(BinOp::Lte), 1, "expected one `..=` compare");
+      assert_eq!(count(BinOp::Lt), 1, "expected one `..` compare");
+    },
+  );
+}
+
+#[test]
+fn match_or_pattern_shares_one_body() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu n: int = 2;
+  match n {
+    1 | 2 | 3 => showln(1),
+    _ => showln(0),
+  }
+}"#,
+    |sir| {
+      // Three alternatives, one test each …
+      let branches = sir
+        .iter()
+        .filter(|i| matches!(i, Insn::BranchIfNot { .. }))
+        .count();
+
+      assert_eq!(branches, 3, "expected one BranchIfNot per alternative");
+
+      // … but the arm body is emitted once.
+      let calls = sir
+        .iter()
+        .filter(|i| matches!(i, Insn::Call { .. }))
+        .count();
+
+      assert_eq!(calls, 2, "expected one call per arm body");
+    },
+  );
+}
+
+#[test]
+fn match_variant_nested_in_tuple_reads_discriminant_through_slot() {
+  assert_sir_structure(
+    r#"
+enum Coin {
+  Heads(int),
+  Tails,
+}
+
+fun main() {
+  imu c: Coin = Coin::Heads(3);
+  match (c, true) {
+    (Coin::Heads(n), true) => showln(n),
+    _ => showln(0),
+  }
+}"#,
+    |sir| {
+      // The discriminant read's tuple is itself slot 0 of
+      // the scrutinee tuple.
+      let slot_dsts = sir
+        .iter()
+        .filter_map(|i| match i {
+          Insn::TupleIndex { dst, index: 0, .. } => Some(*dst),
+          _ => None,
+        })
+        .collect::<Vec<_>>();
+
+      let nested_disc = sir.iter().any(|i| {
+        matches!(
+          i,
+          Insn::TupleIndex { tuple, index: 0, .. } if slot_dsts.contains(tuple)
+        )
+      });
+
+      assert!(nested_disc, "expected a discriminant read of slot 0");
+    },
+  );
+}
+
+#[test]
+fn match_guard_on_tuple_pattern_sees_binders() {
+  assert_sir_structure(
+    r#"fun main() {
+  match (4, 2) {
+    (a, b) if a > b => showln(a),
+    _ => showln(0),
+  }
+}"#,
+    |sir| {
+      // The guard's `a > b` reads the binders …
+      let has_cmp = sir
+        .iter()
+        .any(|i| matches!(i, Insn::BinOp { op: BinOp::Gt, .. }));
+
+      assert!(has_cmp, "expected the guard's compare");
+
+      // … and its failure falls through to the next arm.
+      let branches = sir
+        .iter()
+        .filter(|i| matches!(i, Insn::BranchIfNot { .. }))
+        .count();
+
+      assert_eq!(branches, 1, "expected the guard as the only branch");
+    },
+  );
+}
+
+#[test]
+fn match_or_pattern_with_different_binders_is_error() {
+  assert_execution_error(
+    r#"fun main() {
+  match (1, 2) {
+    (1, v) | (w, 2) => showln(v),
+    _ => showln(0),
+  }
+}"#,
+    ErrorKind::InvalidPattern,
+  );
+}
+
+#[test]
+fn match_literal_of_other_type_is_error() {
+  assert_execution_error(
+    r#"fun main() {
+  imu n: int = 1;
+  match n {
+    "one" => showln(1),
+    _ => showln(0),
+  }
+}"#,
+    ErrorKind::TypeMismatch,
+  );
+}
+
+#[test]
+fn match_binder_arm_is_exhaustive() {
+  let arms = missing_arms(
+    r#"
+fun main() {
+  imu b: bool = true;
+  match b {
+    true => showln(1),
+    other => showln(0),
+  }
+}"#,
+  );
+
+  assert!(arms.is_empty(), "expected no missing arms, got {arms:?}");
+}
+
+// -- Exhaustiveness of nested patterns ----------------------
+
+#[test]
+fn non_exhaustive_tuple_match_names_the_witness() {
+  let arms = missing_arms(
+    r#"
+enum Option<$T> {
+  Some($T),
+  None,
+}
+
+fun main() {
+  imu a: Option<int> = Option::None;
+
+  match (a, true) {
+    (Option::Some(_), _) => showln(1),
+    (Option::None, true) => showln(2),
+  }
+}"#,
+  );
+
+  assert_eq!(arms, vec![vec!["(Option::None, false)".to_string()]]);
+}
+
+#[test]
+fn non_exhaustive_nested_payload_names_the_variant() {
+  let arms = missing_arms(
+    r#"
+enum Coin {
+  Heads,
+  Tails,
+}
+
+enum Toss {
+  Landed(Coin),
+  Lost,
+}
+
+fun main() {
+  imu t: Toss = Toss::Lost;
+
+  match t {
+    Toss::Landed(Coin::Heads) => showln(1),
+    Toss::Lost => showln(0),
+  }
+}"#,
+  );
+
+  assert_eq!(arms, vec![vec!["Toss::Landed(Coin::Tails)".to_string()]]);
+}
+
+#[test]
+fn non_exhaustive_struct_match_folds_wild_fields() {
+  let arms = missing_arms(
+    r#"
+struct Flags {
+  on: bool,
+  n: int,
+}
+
+fun main() {
+  imu f: Flags = Flags { on = true, n = 1 };
+
+  match f {
+    Flags { on: true, .. } => showln(1),
+  }
+}"#,
+  );
+
+  assert_eq!(arms, vec![vec!["Flags { on: false, .. }".to_string()]]);
+}
+
+#[test]
+fn guarded_arm_covers_nothing() {
+  let arms = missing_arms(
+    r#"
+fun main() {
+  match (1, true) {
+    (n, true) if n > 0 => showln(n),
+    (_, false) => showln(0),
+  }
+}"#,
+  );
+
+  assert_eq!(arms, vec![vec!["(_, true)".to_string()]]);
+}
+
+#[test]
+fn or_patterns_and_binders_cover_a_tuple() {
+  let arms = missing_arms(
+    r#"
+fun main() {
+  match (true, false) {
+    (true, _) | (_, true) => showln(1),
+    (false, b) => showln(b),
+  }
+}"#,
+  );
+
+  assert!(arms.is_empty(), "expected no missing arms, got {arms:?}");
+}
+
+#[test]
+fn non_exhaustive_match_carries_patterns_in_detail() {
+  let source = r#"
+fun main() {
+  match (true, false) {
+    (true, true) => showln(1),
+    (false, false) => showln(0),
+  }
+}"#;
+
+  let (_errors, details) = execution_diagnostics(source);
+
+  let patterns = details
+    .iter()
+    .find_map(|(e, d)| match d {
+      Detail::MissingArms { patterns, rbrace }
+        if e.kind() == ErrorKind::NonExhaustiveMatch =>
+      {
+        assert_eq!(&source[*rbrace as usize..][..1], "}");
+
+        Some(patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>())
+      }
+      _ => None,
+    })
+    .expect("expected the missing patterns on the diagnostic");
+
+  assert_eq!(patterns, vec!["(true, false)", "(false, true)"]);
+}
+
+#[test]
+fn arm_covered_by_earlier_arms_is_unreachable() {
+  assert_execution_error(
+    r#"
+fun main() {
+  match (1, true) {
+    (_, true) => showln(1),
+    (n, _) => showln(n),
+    (2, false) => showln(2),
+  }
+}"#,
+    ErrorKind::UnreachableMatchArm,
+  );
+}
+
+#[test]
+fn arm_behind_guarded_arm_stays_reachable() {
+  let errors = execution_errors(
+    r#"
+fun main() {
+  match (1, true) {
+    (n, true) if n > 0 => showln(n),
+    (_, true) => showln(0),
+    (_, false) => showln(2),
+  }
+}"#,
+  );
+
+  assert!(
+    !errors
+      .iter()
+      .any(|e| e.kind() == ErrorKind::UnreachableMatchArm),
+    "{errors:?}"
+  );
+}
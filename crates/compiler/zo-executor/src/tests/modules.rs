@@ -88,6 +88,7 @@ fun main() { 42; }"#,
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(15, 3),
         is_test: false,
@@ -137,6 +138,7 @@ fn test_empty_body_is_noop() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -163,6 +165,7 @@ fn test_non_empty_body_not_intrinsic() {
         pubness: Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(0, 3),
         is_test: false,
@@ -194,6 +197,7 @@ fn test_pub_fun_visibility() {
         pubness: Pubness::Yes,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::new(4, 3),
         is_test: false,
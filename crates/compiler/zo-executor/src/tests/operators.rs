@@ -0,0 +1,165 @@
+use crate::tests::common::{
+  assert_no_errors, execute_raw, execution_diagnostics,
+};
+
+use zo_error::ErrorKind;
+use zo_reporter::Detail;
+use zo_sir::{Insn, UnOp};
+
+/// The `core::ops` abstracts the tests apply — the executor
+/// tests run without the preload.
+const OPS: &str = r#"
+abstract Add {
+  fun add(self, other: Self) -> Self;
+}
+
+abstract Neg {
+  fun neg(self) -> Self;
+}
+
+abstract Index {
+  fun index<$T>(self, i: int) -> $T;
+}
+
+struct Meters {
+  n: int,
+}
+
+apply Add for Meters {
+  fun add(self, other: Meters) -> Meters {
+    Meters { n = self.n + other.n }
+  }
+}
+
+apply Neg for Meters {
+  fun neg(self) -> Meters {
+    Meters { n = 0 - self.n }
+  }
+}
+
+apply Index for Meters {
+  fun index(self, i: int) -> int {
+    self.n * i
+  }
+}
+"#;
+
+fn calls(sir: &[Insn]) -> usize {
+  sir
+    .iter()
+    .filter(|i| matches!(i, Insn::Call { .. }))
+    .count()
+}
+
+#[test]
+fn struct_operators_call_their_impls() {
+  let source = format!(
+    r#"{OPS}
+fun main() {{
+  imu a: Meters = Meters {{ n = 12 }};
+  imu b: Meters = Meters {{ n = 4 }};
+
+  imu s: Meters = a + b;
+  imu n: Meters = -a;
+  imu i: int = a[3];
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+
+  let (sir, _) = execute_raw(&source);
+
+  // `+`, unary `-` and `[]` each lower to one call — the
+  // negation never reaches a primitive `UnOp`.
+  assert_eq!(calls(&sir), 3);
+  assert!(
+    !sir
+      .iter()
+      .any(|i| matches!(i, Insn::UnOp { op: UnOp::Neg, .. })),
+    "expected `-a` to call `Meters::neg`",
+  );
+}
+
+#[test]
+fn index_yields_the_impl_element_type() {
+  let source = format!(
+    r#"{OPS}
+struct Names {{
+  first: str,
+  second: str,
+}}
+
+apply Index for Names {{
+  fun index(self, i: int) -> str {{
+    if i == 0 {{
+      return self.first;
+    }}
+
+    self.second
+  }}
+}}
+
+fun main() {{
+  imu names: Names = Names {{ first = "ada", second = "grace" }};
+  imu name: str = names[1];
+}}
+"#
+  );
+
+  assert_no_errors(&source);
+}
+
+#[test]
+fn generic_operator_binds_per_instantiation() {
+  let source = format!(
+    r#"{OPS}
+fun sum<$T: Add>(a: $T, b: $T) -> $T {{
+  a + b
+}}
+
+fun main() {{
+  imu a: Meters = Meters {{ n = 12 }};
+  imu b: Meters = Meters {{ n = 4 }};
+  imu s: Meters = sum(a, b);
+  imu t: int = sum(1, 2);
+}}
+"#
+  );
+
+  let (sir, _) = execute_raw(&source);
+
+  // Two calls to `sum`'s instantiations, and one to
+  // `Meters::add` from the `Meters` body — the `int` body
+  // keeps its primitive `+`.
+  assert_eq!(calls(&sir), 3);
+}
+
+#[test]
+fn operator_without_impl_names_the_abstract() {
+  let (_, details) = execution_diagnostics(
+    r#"
+struct Meters {
+  n: int,
+}
+
+fun main() {
+  imu a: Meters = Meters { n = 12 };
+  imu b: Meters = a * a;
+}
+"#,
+  );
+
+  let missing = details.iter().find_map(|(e, d)| match d {
+    Detail::MissingImpl {
+      ty,
+      abstract_name,
+      required_by: None,
+    } if e.kind() == ErrorKind::BoundNotSatisfied => {
+      Some((ty.to_string(), abstract_name.to_string()))
+    }
+    _ => None,
+  });
+
+  assert_eq!(missing, Some(("Meters".into(), "Mul".into())));
+}
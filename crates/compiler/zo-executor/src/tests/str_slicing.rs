@@ -1,7 +1,7 @@
 use crate::tests::common::{assert_execution_error, assert_sir_structure};
 
 use zo_error::ErrorKind;
-use zo_sir::Insn;
+use zo_sir::{BinOp, Insn};
 
 // === STR SLICE (COMPILE-TIME) ===
 
@@ -46,6 +46,28 @@ fn test_str_slice_inclusive_range_ok() {
   );
 }
 
+#[test]
+fn test_str_slice_open_bounds_fold() {
+  // `s[lo..]` runs to the end, `s[..hi]` starts at 0.
+  assert_sir_structure(
+    r#"fun main() {
+  imu s: str = "hello, world!";
+  imu tail: str = s[7..];
+  imu head: str = s[..5];
+  imu all: str = s[..];
+}"#,
+    |sir| {
+      let const_string_count = sir
+        .iter()
+        .filter(|i| matches!(i, Insn::ConstString { .. }))
+        .count();
+
+      assert_eq!(const_string_count, 4);
+      assert!(!sir.iter().any(|i| matches!(i, Insn::StrSlice { .. })));
+    },
+  );
+}
+
 // === ERROR PATHS ===
 
 #[test]
@@ -70,6 +92,31 @@ fn test_str_slice_invalid_range_reports_error() {
   );
 }
 
+#[test]
+fn test_str_slice_splitting_a_char_reports_error() {
+  // `é` spans bytes 1..3.
+  assert_execution_error(
+    r#"fun main() {
+  imu s: str = "hé";
+  imu x: str = s[0..2];
+}"#,
+    ErrorKind::StrSliceOutOfBounds,
+  );
+}
+
+#[test]
+fn test_str_slice_on_array_reports_error() {
+  assert_execution_error(
+    r#"fun main() {
+  imu a: []int = [1, 2, 3];
+  imu x: []int = a[0..2];
+}"#,
+    ErrorKind::StrSliceRequiresStr,
+  );
+}
+
+// === STR SLICE (RUNTIME) ===
+
 #[test]
 fn test_str_slice_non_const_bound_emits_runtime_slice() {
   // Non-const bounds used to hard-error. After wiring
@@ -93,3 +140,70 @@ fn test_str_slice_non_const_bound_emits_runtime_slice() {
     },
   );
 }
+
+#[test]
+fn test_str_slice_runtime_inclusive_adds_one() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu s: str = "hello";
+  mut i: int = 0;
+  imu x: str = s[i..=2];
+}"#,
+    |sir| {
+      assert!(
+        sir
+          .iter()
+          .any(|i| matches!(i, Insn::BinOp { op: BinOp::Add, .. }))
+      );
+      assert!(sir.iter().any(|i| matches!(i, Insn::StrSlice { .. })));
+    },
+  );
+}
+
+#[test]
+fn test_str_slice_runtime_open_hi_reads_len() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu s: str = "hello";
+  mut i: int = 1;
+  imu x: str = s[i..];
+}"#,
+    |sir| {
+      let len = sir.iter().find_map(|i| match i {
+        Insn::ArrayLen { dst, .. } => Some(*dst),
+        _ => None,
+      });
+      let slice_hi = sir.iter().find_map(|i| match i {
+        Insn::StrSlice { hi, .. } => Some(*hi),
+        _ => None,
+      });
+
+      assert!(len.is_some());
+      assert_eq!(len, slice_hi);
+    },
+  );
+}
+
+#[test]
+fn test_str_slice_runtime_open_lo_starts_at_zero() {
+  assert_sir_structure(
+    r#"fun main() {
+  imu s: str = "hello";
+  mut i: int = 3;
+  imu x: str = s[..i];
+}"#,
+    |sir| {
+      let zero = sir.iter().rev().find_map(|i| match i {
+        Insn::ConstInt { dst, value: 0, .. } => Some(*dst),
+        _ => None,
+      });
+      let slice_lo = sir.iter().find_map(|i| match i {
+        Insn::StrSlice { lo, .. } => Some(*lo),
+        _ => None,
+      });
+
+      assert!(zero.is_some());
+      assert_eq!(zero, slice_lo);
+    },
+  );
+}
@@ -139,8 +139,14 @@ impl<'a> Inline<'a> {
         end += 1;
       }
 
-      if *kind == FunctionKind::UserDefined
-        && !is_test
+      // A closure is an ordinary function whose captures lead
+      // its params, and its call sites pass them as arguments
+      // — so a `for` pipeline's `map` / `filter` stage folds
+      // into the loop like any small helper.
+      if matches!(
+        kind,
+        FunctionKind::UserDefined | FunctionKind::Closure { .. }
+      ) && !is_test
         && !is_runtime_dispatched(self.interner.get(*name))
         && let Some(candidate) = build_candidate(&insns[start..end], params)
         && self.types_resolved(&candidate.body)
@@ -220,7 +226,7 @@ impl<'a> Inline<'a> {
       });
     }
 
-    fixup_fundef_offsets(&mut new_insns);
+    Sir::fixup_fundef_offsets(&mut new_insns);
 
     self.sir.instructions = new_insns;
     self.sir.spans = new_spans;
@@ -649,12 +655,3 @@ fn strides(body: &[Insn]) -> (u32, u32) {
 
   (value_span, label_span)
 }
-
-/// Resets each `FunDef`'s `body_start` to its new index.
-fn fixup_fundef_offsets(insns: &mut [Insn]) {
-  for (idx, insn) in insns.iter_mut().enumerate() {
-    if let Insn::FunDef { body_start, .. } = insn {
-      *body_start = (idx + 1) as u32;
-    }
-  }
-}
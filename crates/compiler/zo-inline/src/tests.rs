@@ -10,7 +10,7 @@ use common::{
 
 use zo_interner::Interner;
 use zo_sir::{BinOp, Insn, LoadSource};
-use zo_value::ValueId;
+use zo_value::{FunctionKind, ValueId};
 
 #[test]
 fn release_inlines_pure_leaf_call() {
@@ -30,6 +30,21 @@ fn release_inlines_pure_leaf_call() {
   )));
 }
 
+#[test]
+fn release_inlines_closure_call() {
+  let mut interner = Interner::new();
+  let (mut sir, double) = double_and_caller(&mut interner);
+
+  if let Insn::FunDef { kind, .. } = &mut sir.instructions[0] {
+    *kind = FunctionKind::Closure { capture_count: 0 };
+  }
+
+  Inline::new(&mut sir, &mut interner, &[], Release::Yes).inline();
+
+  // A statically called closure folds like any leaf function.
+  assert_eq!(calls(&sir, double), 0);
+}
+
 #[test]
 fn debug_leaves_call_untouched() {
   let mut interner = Interner::new();
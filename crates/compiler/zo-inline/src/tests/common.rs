@@ -15,6 +15,7 @@ pub fn fundef(name: Symbol, param_count: usize) -> Insn {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -147,14 +147,39 @@ impl Interner {
 
   /// Snapshot every interned string into an owned
   /// `Vec<String>` indexed by `Symbol.0`. Used by the
-  /// runtime to feed `Send`-only handler closures since
-  /// `Interner` itself isn't `Clone` (the dedup map carries
-  /// self-referential `&'static str` keys).
+  /// runtime to feed `Send`-only handler closures a plain
+  /// string table, without the dedup map.
   pub fn snapshot(&self) -> Vec<String> {
     self.strings.iter().map(|s| s.to_string()).collect()
   }
 }
 
+/// The dedup map's `&'static str` keys point into `strings`,
+/// so a derived `Clone` would leave the copy keyed by the
+/// original's buffers. The map is rebuilt over the copy's own
+/// boxes instead — the first slot holding a string wins, as it
+/// does in `intern`.
+impl Clone for Interner {
+  fn clone(&self) -> Self {
+    let strings = self.strings.clone();
+    let mut map = HashMap::with_capacity_and_hasher(
+      strings.len().max(Self::MAP_CAPACITY),
+      Default::default(),
+    );
+
+    for (idx, boxed) in strings.iter().enumerate() {
+      // SAFETY: as in `intern` — the key points into a box
+      // the clone's `strings` owns for the clone's lifetime.
+      let static_slice: &'static str =
+        unsafe { std::mem::transmute::<&str, &'static str>(&**boxed) };
+
+      map.entry(static_slice).or_insert(Symbol::new(idx as u32));
+    }
+
+    Self { strings, map }
+  }
+}
+
 impl Default for Interner {
   fn default() -> Self {
     Self::new()
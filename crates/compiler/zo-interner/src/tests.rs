@@ -25,3 +25,21 @@ fn test_interner_basic() {
   assert_eq!(interner.get(hello1), "hello");
   assert_eq!(interner.get(world), "world");
 }
+
+#[test]
+fn test_interner_clone_dedups_independently() {
+  let mut interner = Interner::new();
+  let hello = interner.intern("hello");
+
+  let mut clone = interner.clone();
+
+  drop(interner);
+
+  assert_eq!(clone.intern("hello"), hello);
+  assert_eq!(clone.symbol("fun"), Some(Symbol::FUN));
+  assert_eq!(clone.get(hello), "hello");
+
+  let world = clone.intern("world");
+
+  assert_eq!(clone.intern("world"), world);
+}
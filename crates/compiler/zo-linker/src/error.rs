@@ -1,5 +1,7 @@
 //! `zo-linker` error surface.
 
+use crate::RuntimeKind;
+
 use zo_codegen_backend::Target;
 
 use std::io;
@@ -19,6 +21,9 @@ pub enum LinkError {
   /// `cc` ran but exited non-zero. `stderr` is captured for
   /// the user.
   InvocationFailed { status: Option<i32>, stderr: String },
+  /// The object imports `zo-runtime` symbols but no runtime
+  /// library of the flavor they need was found.
+  RuntimeMissing(RuntimeKind),
   /// I/O error — temp file write, output rename, etc.
   Io(io::Error),
 }
@@ -33,6 +38,11 @@ impl std::fmt::Display for LinkError {
       Self::InvocationFailed { status, stderr } => {
         write!(f, "cc exited with status {status:?}:\n{stderr}")
       }
+      Self::RuntimeMissing(kind) => write!(
+        f,
+        "the program needs the zo runtime ({kind:?}) but no runtime \
+         library was found next to the compiler"
+      ),
       Self::Io(err) => write!(f, "linker io error: {err}"),
     }
   }
@@ -4,7 +4,8 @@
 //! that into a runnable executable needs a linker. This crate
 //! shells out to `cc` (the platform C compiler front-end) which
 //! pulls in the C runtime (`crt0` / `crt1`) and resolves any
-//! FFI imports against libc / libSystem.
+//! FFI imports against libc / libSystem and the `zo-runtime`
+//! shared library.
 //!
 //! Supported targets: host-matching Unix only
 //! (`arm64-apple-darwin`, `x86_64-apple-darwin`,
@@ -17,7 +18,7 @@ mod linker;
 mod linker_macho;
 
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use zo_codegen_backend::{LinkObject, Target, WebBundle};
 
@@ -33,7 +34,8 @@ pub use linker_macho::{LinkOutput, RuntimeKind, link_macho};
 /// directly. `LinkObject::Object` shells out to `cc`
 /// ([`link_to_executable`]) which provides `crt0` /
 /// `crt1` and resolves FFI imports against
-/// libc / libSystem.
+/// libc / libSystem and, when the object imports any, the
+/// `zo-runtime` library `runtime_lib` locates per flavor.
 ///
 /// Errors from `cc` are surfaced via the returned
 /// `LinkError`; the user's `output_path` is left
@@ -41,13 +43,12 @@ pub use linker_macho::{LinkOutput, RuntimeKind, link_macho};
 ///
 /// On success returns the [`RuntimeKind`] the binary needs
 /// — which `libzo_runtime.dylib` flavor the caller stages
-/// next to it. The `cc` path links system libraries
-/// directly and stages no zo runtime, so it reports
-/// [`RuntimeKind::None`].
+/// next to it.
 pub fn link(
   link_obj: LinkObject,
   output_path: &Path,
   target: Target,
+  runtime_lib: &dyn Fn(RuntimeKind) -> Option<PathBuf>,
 ) -> Result<RuntimeKind, LinkError> {
   match link_obj {
     LinkObject::Macho(m) => {
@@ -59,9 +60,7 @@ pub fn link(
       Ok(output.runtime)
     }
     LinkObject::Object(code) => {
-      link_to_executable(&code, output_path, target)?;
-
-      Ok(RuntimeKind::None)
+      link_to_executable(&code, output_path, target, runtime_lib)
     }
     LinkObject::Web(bundle) => {
       write_web_bundle(&bundle, output_path).map_err(LinkError::Io)?;
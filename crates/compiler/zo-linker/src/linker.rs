@@ -1,11 +1,14 @@
 //! `cc`-driven link step: bytes-in, executable-out.
 
 use crate::error::LinkError;
+use crate::linker_macho::{RuntimeKind, classify};
 
 use zo_codegen_backend::Target;
 
+use object::{Object, ObjectSymbol};
+
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Embedded C runtime — wrappers around variadic libc calls
@@ -14,28 +17,109 @@ use std::process::Command;
 /// Compiled on every link via `cc` alongside the user's `.o`.
 const RUNTIME_C: &str = include_str!("runtime.c");
 
+/// The `zo_*` functions [`RUNTIME_C`] defines. Any other `zo_*`
+/// import resolves against the `zo-runtime` shared library.
+const RUNTIME_C_SYMBOLS: &[&str] = &[
+  "zo_ftoa_f64",
+  "zo_itoa_radix",
+  "zo_str_concat",
+  "zo_str_slice",
+  "zo_str_eq",
+  "zo_closure_env_free",
+];
+
+/// The name `cc -lzo_runtime` finds the runtime library under.
+#[cfg(target_os = "macos")]
+const RUNTIME_LIB: &str = "libzo_runtime.dylib";
+#[cfg(not(target_os = "macos"))]
+const RUNTIME_LIB: &str = "libzo_runtime.so";
+
 /// Writes `object_bytes` to a temp `.o`, drops the embedded
 /// C runtime into a sibling temp file, invokes `cc` to
 /// compile-and-link both into an executable at `output_path`,
 /// and cleans up both temp files. Each `NamedTempFile` owns
 /// its path — Drop removes the file even if `cc` fails.
 ///
+/// An object importing `zo_*` symbols the C runtime doesn't
+/// define — `HashMap`, buffered I/O, tasks — also links the
+/// `zo-runtime` shared library `runtime_lib` locates for the
+/// flavor those imports need. The executable looks it up in
+/// its `deps/` sibling, where the caller stages it; the
+/// returned [`RuntimeKind`] says which flavor.
+///
 /// Contract: `output_path`'s parent directory must exist. The
 /// function overwrites `output_path` if it already exists.
 pub fn link_to_executable(
   object_bytes: &[u8],
   output_path: &Path,
   target: Target,
-) -> Result<(), LinkError> {
+  runtime_lib: &dyn Fn(RuntimeKind) -> Option<PathBuf>,
+) -> Result<RuntimeKind, LinkError> {
   ensure_target_supported(target)?;
 
+  let runtime = runtime_kind(object_bytes)?;
+  let runtime_dir = match runtime {
+    RuntimeKind::None => None,
+    kind => {
+      let lib = runtime_lib(kind).ok_or(LinkError::RuntimeMissing(kind))?;
+
+      Some(runtime_link_dir(&lib)?)
+    }
+  };
+
   let obj_file = write_temp_object(object_bytes)?;
   let runtime_file = write_temp_runtime()?;
 
-  invoke_cc(obj_file.path(), runtime_file.path(), output_path, target)
+  invoke_cc(
+    obj_file.path(),
+    runtime_file.path(),
+    runtime_dir.as_ref().map(|dir| dir.path()),
+    output_path,
+    target,
+  )?;
+
+  Ok(runtime)
+
+  // The temp files and directory drop here — success or
+  // failure, their Drop removes them.
+}
+
+/// The runtime flavor `object_bytes` needs: the strongest over
+/// its undefined `zo_*` symbols the C runtime leaves open.
+fn runtime_kind(object_bytes: &[u8]) -> Result<RuntimeKind, LinkError> {
+  let file = object::File::parse(object_bytes)
+    .map_err(|error| LinkError::Io(io::Error::other(error)))?;
+
+  let kind = file
+    .symbols()
+    .filter(|symbol| symbol.is_undefined())
+    .filter_map(|symbol| symbol.name().ok())
+    // Mach-O prefixes C names with `_`.
+    .map(|name| name.strip_prefix('_').unwrap_or(name))
+    .filter(|name| name.starts_with("zo_") && !RUNTIME_C_SYMBOLS.contains(name))
+    .fold(RuntimeKind::None, |kind, name| {
+      kind.max_with(classify(&format!("_{name}")))
+    });
+
+  Ok(kind)
+}
+
+/// A temp directory holding `lib` as [`RUNTIME_LIB`], so `cc
+/// -lzo_runtime` records the name the staged copy goes by.
+fn runtime_link_dir(lib: &Path) -> Result<tempfile::TempDir, LinkError> {
+  let dir = tempfile::Builder::new()
+    .prefix("zo-runtime-")
+    .tempdir()
+    .map_err(LinkError::Io)?;
+
+  #[cfg(unix)]
+  std::os::unix::fs::symlink(lib, dir.path().join(RUNTIME_LIB))
+    .map_err(LinkError::Io)?;
+
+  #[cfg(not(unix))]
+  std::fs::copy(lib, dir.path().join(RUNTIME_LIB)).map_err(LinkError::Io)?;
 
-  // Both temp files drop here — `NamedTempFile`'s Drop removes
-  // the backing file automatically, success or failure.
+  Ok(dir)
 }
 
 /// Non-Windows, non-wasm is supported by `cc`. Everything else
@@ -89,7 +173,8 @@ fn write_temp_runtime() -> Result<tempfile::NamedTempFile, LinkError> {
   write_temp(".c", RUNTIME_C.as_bytes())
 }
 
-/// `cc {obj} {runtime.c} -o {exe}` with stderr captured.
+/// `cc {obj} {runtime.c} [-lzo_runtime] -o {exe}` with stderr
+/// captured.
 /// `cc` accepts mixed `.o` + `.c` inputs natively — it
 /// compiles the C source and links everything with `crt0` /
 /// `crt1` and libc / libSystem in a single invocation.
@@ -98,9 +183,14 @@ fn write_temp_runtime() -> Result<tempfile::NamedTempFile, LinkError> {
 /// arm64-darwin host can build x86_64 Mach-O bytes (and vice
 /// versa); without the flag Apple's `cc` defaults to the host
 /// arch and rejects the object file.
+///
+/// `runtime_dir` holds the `zo-runtime` library when the
+/// object needs it; the executable's rpath points at its own
+/// `deps/`.
 fn invoke_cc(
   obj: &Path,
   runtime: &Path,
+  runtime_dir: Option<&Path>,
   output: &Path,
   target: Target,
 ) -> Result<(), LinkError> {
@@ -121,6 +211,17 @@ fn invoke_cc(
 
   cmd.arg(obj).arg(runtime).arg("-o").arg(output);
 
+  if let Some(dir) = runtime_dir {
+    let rpath = match target {
+      Target::Arm64AppleDarwin | Target::X8664AppleDarwin => {
+        "-Wl,-rpath,@loader_path/deps"
+      }
+      _ => "-Wl,-rpath,$ORIGIN/deps",
+    };
+
+    cmd.arg("-L").arg(dir).arg("-lzo_runtime").arg(rpath);
+  }
+
   let out = cmd.output().map_err(|err| match err.kind() {
     io::ErrorKind::NotFound => LinkError::ToolMissing(
       "`cc` not found on PATH. Install Xcode Command Line Tools \
@@ -140,3 +241,31 @@ fn invoke_cc(
 
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// `RUNTIME_C_SYMBOLS` names exactly the functions
+  /// `runtime.c` exports — a stale entry would keep a needed
+  /// runtime library off the link line.
+  #[test]
+  fn runtime_c_symbols_match_runtime_c() {
+    let mut defined = RUNTIME_C
+      .lines()
+      .filter(|line| !line.starts_with([' ', '/', '#', '}']))
+      .filter(|line| !line.starts_with("static") && line.contains('('))
+      .filter_map(|line| {
+        let name = line.split('(').next()?.rsplit([' ', '*']).next()?;
+
+        name.starts_with("zo_").then_some(name)
+      })
+      .collect::<Vec<_>>();
+    let mut listed = RUNTIME_C_SYMBOLS.to_vec();
+
+    defined.sort_unstable();
+    listed.sort_unstable();
+
+    assert_eq!(defined, listed);
+  }
+}
@@ -53,7 +53,7 @@ impl RuntimeKind {
   /// Strongest flavor of two: `Full` dominates `Lean`,
   /// which dominates `None`. One UI-exclusive import in a
   /// program forces the full dylib for the whole binary.
-  fn max_with(self, other: RuntimeKind) -> RuntimeKind {
+  pub(crate) fn max_with(self, other: RuntimeKind) -> RuntimeKind {
     match (self, other) {
       (RuntimeKind::Full, _) | (_, RuntimeKind::Full) => RuntimeKind::Full,
       (RuntimeKind::Lean, _) | (_, RuntimeKind::Lean) => RuntimeKind::Lean,
@@ -65,7 +65,7 @@ impl RuntimeKind {
 /// Classify one imported runtime symbol: `Full` iff it is
 /// exported only by the UI runtime, else `Lean`. Caller has
 /// already established `c_sym` is a runtime symbol.
-fn classify(c_sym: &str) -> RuntimeKind {
+pub(crate) fn classify(c_sym: &str) -> RuntimeKind {
   if UI_EXCLUSIVE_RUNTIME_SYMBOLS.contains(&c_sym) {
     RuntimeKind::Full
   } else {
@@ -61,31 +61,122 @@ int zo_itoa_radix(char *buf, unsigned long size, long long val,
   return len;
 }
 
+// A slice view's length word carries this tag bit; its
+// second word points into the parent's payload instead of
+// holding the bytes inline. Mirrors `STR_VIEW_BIT` in
+// `zo-runtime`'s `str.rs`.
+#define ZO_STR_VIEW_BIT (1ULL << 63)
+
+static unsigned long zo_str_len(const void *s) {
+  return (unsigned long)(*(const unsigned long long *)s & ~ZO_STR_VIEW_BIT);
+}
+
+static const unsigned char *zo_str_data(const void *s) {
+  if (*(const unsigned long long *)s & ZO_STR_VIEW_BIT) {
+    return *(const unsigned char *const *)((const unsigned char *)s + 8);
+  }
+
+  return (const unsigned char *)s + 8;
+}
+
 // Concatenates two zo strings (`[u64 LE len, UTF-8 bytes]`
-// layout from `Insn::ConstString`). Allocates a fresh buffer
-// holding `[len_a + len_b, bytes_a, bytes_b]` and returns a
-// pointer to it. The caller treats the result as an opaque
-// string pointer — identical shape to any other zo string
-// value, so it composes with `show` / `showln` / further
-// `++` concatenations.
+// layout from `Insn::ConstString`, or a slice view).
+// Allocates a fresh buffer holding `[len_a + len_b, bytes_a,
+// bytes_b, NUL]` and returns a pointer to it. The caller
+// treats the result as an opaque string pointer — identical
+// shape to any other zo string value, so it composes with
+// `show` / `showln` / further `++` concatenations.
 //
 // Heap lifetime: the allocation is never freed. zo programs
 // today don't have a GC or an `str.drop()` sink, so concat
 // results leak. Acceptable for CLIF bring-up; revisit when
 // a runtime lifetime story lands.
 void *zo_str_concat(const void *a, const void *b) {
-  unsigned long len_a = *(const unsigned long *)a;
-  unsigned long len_b = *(const unsigned long *)b;
+  unsigned long len_a = zo_str_len(a);
+  unsigned long len_b = zo_str_len(b);
   unsigned long total = len_a + len_b;
-  unsigned char *result = (unsigned char *)malloc(8 + total);
+  unsigned char *result = (unsigned char *)malloc(8 + total + 1);
 
   if (!result) {
     return NULL;
   }
 
   *(unsigned long *)result = total;
-  memcpy(result + 8, (const unsigned char *)a + 8, len_a);
-  memcpy(result + 8 + len_a, (const unsigned char *)b + 8, len_b);
+  memcpy(result + 8, zo_str_data(a), len_a);
+  memcpy(result + 8 + len_a, zo_str_data(b), len_b);
+  result[8 + total] = '\0';
 
   return result;
 }
+
+static int zo_is_char_boundary(const unsigned char *bytes,
+                               unsigned long len, unsigned long i) {
+  return i == 0 || i == len || (i < len && (bytes[i] & 0xc0) != 0x80);
+}
+
+// Slices `src[lo..hi]` into a view — a 16-byte header
+// `[hi - lo | ZO_STR_VIEW_BIT, src_bytes + lo]` that borrows
+// the parent's payload. Slicing a view points at the root
+// owner, so views never chain. Exits with a message on an
+// out-of-range bound or one that splits a UTF-8 sequence —
+// the same checks `zo-runtime`'s `zo_str_slice` panics on.
+void *zo_str_slice(const void *src, unsigned long lo, unsigned long hi) {
+  unsigned long len = zo_str_len(src);
+  const unsigned char *bytes = zo_str_data(src);
+
+  if (lo > hi || hi > len) {
+    fprintf(stderr, "zo_str_slice: out of range (lo=%lu, hi=%lu, len=%lu)\n",
+            lo, hi, len);
+    exit(1);
+  }
+
+  unsigned long bounds[2] = {lo, hi};
+
+  for (int i = 0; i < 2; i++) {
+    if (!zo_is_char_boundary(bytes, len, bounds[i])) {
+      fprintf(stderr,
+              "zo_str_slice: byte index %lu is not a char boundary\n",
+              bounds[i]);
+      exit(1);
+    }
+  }
+
+  unsigned long long *view = (unsigned long long *)malloc(16);
+
+  if (!view) {
+    return NULL;
+  }
+
+  view[0] = (unsigned long long)(hi - lo) | ZO_STR_VIEW_BIT;
+  view[1] = (unsigned long long)(bytes + lo);
+
+  return view;
+}
+
+// Byte-wise equality of two zo strings, owned or view.
+// Returns 1 when equal.
+long zo_str_eq(const void *a, const void *b) {
+  unsigned long len = zo_str_len(a);
+
+  return len == zo_str_len(b) && memcmp(zo_str_data(a), zo_str_data(b), len) == 0;
+}
+
+// Releases a capturing closure's environment — the C twin of
+// `zo-runtime`'s `zo_closure_env_free`, for binaries linked
+// without that crate. `env` addresses the `len` word of the
+// captures array; the word below it holds the closure's
+// `__env_drop` glue (or 0), which drops the owned captures
+// before the block is freed. A null `env` is a no-op.
+void zo_closure_env_free(unsigned long long env) {
+  if (!env) {
+    return;
+  }
+
+  unsigned long long *block = (unsigned long long *)env - 1;
+
+  if (*block) {
+    ((void (*)(unsigned long long))*block)(env);
+  }
+
+  free(block);
+}
@@ -37,6 +37,7 @@ pub fn insn_def(insn: &Insn) -> Option<ValueId> {
     | Insn::ChannelCreate { dst, .. }
     | Insn::ChannelRecv { dst, .. }
     | Insn::FnAddr { dst, .. }
+    | Insn::ClosureEnv { dst, .. }
     | Insn::TaskSpawn { dst, .. }
     | Insn::TaskAwait { dst, .. }
     | Insn::SelectRecv { dst, .. }
@@ -155,6 +156,14 @@ pub fn visit_uses(insn: &Insn, mut f: impl FnMut(ValueId)) {
       }
     }
     Insn::TaskAwait { task, .. } => f(*task),
+    Insn::ClosureEnv { drop, captures, .. } => {
+      f(*drop);
+
+      for &v in captures {
+        f(v);
+      }
+    }
+    Insn::ClosureEnvFree { env } => f(*env),
     Insn::SelectWait { chans, .. } => {
       for &v in chans {
         f(v);
@@ -0,0 +1,466 @@
+use crate::completion::{Scan, is_ident_char};
+use crate::hover;
+use crate::index::FileState;
+use crate::position::LineIndex;
+use crate::references;
+
+use zo_reporter::fixes::missing_arms_fix;
+use zo_span::Span;
+
+use tower_lsp::lsp_types::{
+  CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, NumberOrString,
+  Range, TextEdit, Url, WorkspaceEdit,
+};
+
+use std::collections::HashMap;
+
+/// The `code` of the diagnostic the "add missing arms" fix
+/// resolves.
+const NON_EXHAUSTIVE_MATCH: &str = "non-exhaustive-match";
+
+/// Every code action available over `range`: the quick fixes
+/// of the diagnostics it touches, then the refactorings the
+/// cursor or selection allows.
+pub fn actions(
+  state: &FileState,
+  uri: &Url,
+  range: Range,
+) -> Vec<CodeActionOrCommand> {
+  let line_index = &state.line_index;
+  let start = line_index.offset(range.start.line, range.start.character);
+  let end = line_index.offset(range.end.line, range.end.character);
+
+  let mut actions = Vec::new();
+  let mut preferred = true;
+
+  for fix in &state.fixes {
+    if !overlaps(fix.diagnostic.range, range) {
+      continue;
+    }
+
+    actions.push(action(
+      uri,
+      &fix.title,
+      CodeActionKind::QUICKFIX,
+      vec![edit(line_index, fix.start, fix.end, &fix.text)],
+      Some(fix.diagnostic.clone()),
+      std::mem::take(&mut preferred),
+    ));
+  }
+
+  actions.extend(missing_arms(state, uri, start, end));
+  actions.extend(annotate(state, uri, start));
+  actions.extend(inline_local(state, uri, start));
+
+  if start < end {
+    actions.extend(extract_local(state, uri, start, end));
+  }
+
+  actions
+    .into_iter()
+    .map(CodeActionOrCommand::CodeAction)
+    .collect()
+}
+
+/// Adds an arm per pattern the executor found missing, right
+/// before the closing `}` of the `match` under the cursor.
+fn missing_arms(
+  state: &FileState,
+  uri: &Url,
+  start: u32,
+  end: u32,
+) -> Option<CodeAction> {
+  let source = state.source.as_str();
+
+  let (lbrace, patterns, rbrace) =
+    state.missing_arms.iter().find_map(|(lbrace, patterns)| {
+      let rbrace = closing_brace(source, lbrace.start as usize)?;
+      let head = line_start(source, lbrace.start as usize);
+
+      (start as usize <= rbrace && end as usize >= head)
+        .then_some((*lbrace, patterns, rbrace))
+    })?;
+
+  let (at, text) =
+    missing_arms_fix(source, lbrace.end() as usize, rbrace, patterns);
+
+  let diagnostic = state
+    .diagnostics
+    .iter()
+    .find(|diagnostic| {
+      diagnostic.code
+        == Some(NumberOrString::String(NON_EXHAUSTIVE_MATCH.into()))
+        && diagnostic.range == state.line_index.range(lbrace)
+    })
+    .cloned();
+
+  let title = match patterns.len() {
+    1 => format!("Add missing arm `{}`", patterns[0]),
+    count => format!("Add {count} missing arms"),
+  };
+
+  Some(action(
+    uri,
+    &title,
+    CodeActionKind::QUICKFIX,
+    vec![edit(&state.line_index, at, at, &text)],
+    diagnostic,
+    true,
+  ))
+}
+
+/// Writes the inferred type of the binding under the cursor
+/// into its declaration: `imu x := f()` → `imu x: T = f()`.
+fn annotate(state: &FileState, uri: &Url, offset: u32) -> Option<CodeAction> {
+  let source = state.source.as_str();
+  let (name, binding) = binding_at(state, offset)?;
+
+  let walrus = binding.walrus?;
+  let keyword = Span::new(binding.statement.0, 3);
+  let ty = hover::local_ty(state, keyword, &name, binding.name)?;
+
+  let walrus_end = walrus + 2;
+  let text = format!(": {ty} =");
+  let title = format!("Annotate `{name}` as `{ty}`");
+
+  (source.get(walrus as usize..walrus_end as usize) == Some(":=")).then(|| {
+    action(
+      uri,
+      &title,
+      CodeActionKind::REFACTOR_REWRITE,
+      vec![edit(
+        &state.line_index,
+        binding.name.end(),
+        walrus_end,
+        &text,
+      )],
+      None,
+      false,
+    )
+  })
+}
+
+/// Replaces every use of the `imu` binding under the cursor
+/// with its initializer and removes the declaration.
+fn inline_local(
+  state: &FileState,
+  uri: &Url,
+  offset: u32,
+) -> Option<CodeAction> {
+  let source = state.source.as_str();
+  let (name, binding) = binding_at(state, offset)?;
+
+  if !binding.immutable {
+    return None;
+  }
+
+  let def = *state.use_def_map.values().find(|def| {
+    references::name_span(source, **def, &name) == Some(binding.name)
+  })?;
+
+  let target = references::Target {
+    path: state.path.clone(),
+    span: def,
+    name: name.clone(),
+    is_fun: false,
+  };
+
+  let init = source[binding.init.0 as usize..binding.init.1 as usize].trim();
+  let value = if init.contains(char::is_whitespace) && !init.starts_with('(') {
+    format!("({init})")
+  } else {
+    init.to_string()
+  };
+
+  let mut edits = references::own_spans(state, &target)
+    .into_iter()
+    .filter(|span| *span != binding.name)
+    .map(|span| edit(&state.line_index, span.start, span.end(), &value))
+    .collect::<Vec<_>>();
+
+  if edits.is_empty() {
+    return None;
+  }
+
+  let (start, end) = binding.statement;
+  let line = line_start(source, start as usize) as u32;
+  // A declaration alone on its line takes the line with it.
+  let (start, end) = if source[line as usize..start as usize].trim().is_empty()
+    && source[end as usize..].starts_with('\n')
+  {
+    (line, end + 1)
+  } else {
+    (start, end)
+  };
+
+  edits.push(edit(&state.line_index, start, end, ""));
+
+  Some(action(
+    uri,
+    &format!("Inline `{name}`"),
+    CodeActionKind::REFACTOR_INLINE,
+    edits,
+    None,
+    false,
+  ))
+}
+
+/// Binds the selected expression to a fresh `imu` right
+/// before the statement it belongs to.
+fn extract_local(
+  state: &FileState,
+  uri: &Url,
+  start: u32,
+  end: u32,
+) -> Option<CodeAction> {
+  let source = state.source.as_str();
+  let selected = source.get(start as usize..end as usize)?;
+  let expr = selected.trim();
+
+  if expr.is_empty() || !is_expression(expr) {
+    return None;
+  }
+
+  let before = &source[..start as usize];
+  let scan = Scan::new(before);
+
+  if scan.depth == 0 {
+    return None;
+  }
+
+  let statement = statement_start(source, start as usize);
+  let line = line_start(source, statement);
+  let indent = &source[line..statement];
+
+  if !indent.trim().is_empty() {
+    return None;
+  }
+
+  let name = fresh_name(&scan, "value");
+  let decl = format!("imu {name} := {expr};\n{indent}");
+
+  Some(action(
+    uri,
+    &format!("Extract `{expr}` into a local"),
+    CodeActionKind::REFACTOR_EXTRACT,
+    vec![
+      edit(&state.line_index, statement as u32, statement as u32, &decl),
+      edit(&state.line_index, start, end, &name),
+    ],
+    None,
+    false,
+  ))
+}
+
+/// A `imu` / `mut` declaration, located in source.
+struct Binding {
+  /// The declared name.
+  name: Span,
+  /// Offset of the `:=` when the type is inferred.
+  walrus: Option<u32>,
+  /// Byte range of the initializer.
+  init: (u32, u32),
+  /// Byte range of the statement, `;` included.
+  statement: (u32, u32),
+  immutable: bool,
+}
+
+/// The binding whose name the cursor is on — at its
+/// declaration or at a use.
+fn binding_at(state: &FileState, offset: u32) -> Option<(String, Binding)> {
+  let source = state.source.as_str();
+  let (start, end) = ident_at(source, offset as usize)?;
+  let name = &source[start..end];
+  let span = Span::new(start as u32, name.len() as u16);
+
+  let decl = match state.use_def_map.get(&span) {
+    Some(def) => references::name_span(source, *def, name)?,
+    None => span,
+  };
+
+  Some((name.to_string(), binding(source, decl)?))
+}
+
+/// Parses the declaration whose name sits at `name` —
+/// `imu x := e;`, `mut x: T = e;`.
+fn binding(source: &str, name: Span) -> Option<Binding> {
+  let head = source[..name.start as usize].trim_end();
+  let immutable = match head.rsplit(|c: char| !is_ident_char(c)).next()? {
+    "imu" => true,
+    "mut" => false,
+    _ => return None,
+  };
+  let keyword = head.len() - 3;
+
+  let after = name.end() as usize;
+  let rest = &source[after..];
+  let trimmed = rest.trim_start();
+  let gap = rest.len() - trimmed.len();
+
+  let (walrus, init_start) = if trimmed.starts_with(":=") {
+    (Some((after + gap) as u32), after + gap + 2)
+  } else {
+    (None, after + rest.find('=')? + 1)
+  };
+
+  let semicolon = init_start + statement_end(&source[init_start..])?;
+
+  Some(Binding {
+    name,
+    walrus,
+    init: (init_start as u32, semicolon as u32),
+    statement: (keyword as u32, semicolon as u32 + 1),
+    immutable,
+  })
+}
+
+/// Offset of the `;` ending the statement `text` starts in,
+/// skipping nested delimiters and strings.
+fn statement_end(text: &str) -> Option<usize> {
+  let mut depth = 0usize;
+  let mut in_string = false;
+  let mut escaped = false;
+
+  for (at, c) in text.char_indices() {
+    if in_string {
+      match c {
+        _ if escaped => escaped = false,
+        '\\' => escaped = true,
+        '"' => in_string = false,
+        _ => {}
+      }
+
+      continue;
+    }
+
+    match c {
+      '"' => in_string = true,
+      '(' | '[' | '{' => depth += 1,
+      ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+      ';' if depth == 0 => return Some(at),
+      _ => {}
+    }
+  }
+
+  None
+}
+
+/// Whether `text` reads as one expression: balanced, with no
+/// statement separator and no leading statement keyword.
+fn is_expression(text: &str) -> bool {
+  const STATEMENT_KEYWORDS: &[&str] = &[
+    "imu", "mut", "return", "break", "continue", "for", "while", "loop",
+  ];
+
+  let first = text.split(|c: char| !is_ident_char(c)).next().unwrap_or("");
+
+  !STATEMENT_KEYWORDS.contains(&first)
+    && !text.contains(';')
+    && statement_end(&format!("{text};")) == Some(text.len())
+}
+
+/// The start of the statement containing `offset`: just past
+/// the previous `;`, `{` or `}`, whitespace skipped.
+fn statement_start(source: &str, offset: usize) -> usize {
+  let boundary = source[..offset]
+    .rfind([';', '{', '}'])
+    .map_or(0, |at| at + 1);
+
+  boundary + (source[boundary..].len() - source[boundary..].trim_start().len())
+}
+
+/// The `}` closing the `{` at `open`, strings skipped.
+fn closing_brace(source: &str, open: usize) -> Option<usize> {
+  let mut depth = 0usize;
+  let mut in_string = false;
+  let mut escaped = false;
+
+  for (at, c) in source[open..].char_indices() {
+    if in_string {
+      match c {
+        _ if escaped => escaped = false,
+        '\\' => escaped = true,
+        '"' => in_string = false,
+        _ => {}
+      }
+
+      continue;
+    }
+
+    match c {
+      '"' => in_string = true,
+      '{' => depth += 1,
+      '}' => {
+        depth = depth.checked_sub(1)?;
+
+        if depth == 0 {
+          return Some(open + at);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  None
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+  source[..offset].rfind('\n').map_or(0, |at| at + 1)
+}
+
+/// The identifier around `offset`, as a byte range.
+fn ident_at(source: &str, offset: usize) -> Option<(usize, usize)> {
+  let offset = offset.min(source.len());
+  let start = source[..offset]
+    .rfind(|c: char| !is_ident_char(c))
+    .map_or(0, |at| at + 1);
+  let end = source[offset..]
+    .find(|c: char| !is_ident_char(c))
+    .map_or(source.len(), |at| offset + at);
+
+  (start < end).then_some((start, end))
+}
+
+/// `base`, or `base2`, `base3`.. — the first name no binding
+/// in scope uses.
+fn fresh_name(scan: &Scan, base: &str) -> String {
+  let taken = |name: &str| scan.locals().any(|(local, _)| local == name);
+
+  if !taken(base) {
+    return base.to_string();
+  }
+
+  (2..)
+    .map(|n| format!("{base}{n}"))
+    .find(|name| !taken(name))
+    .unwrap_or_default()
+}
+
+fn overlaps(a: Range, b: Range) -> bool {
+  a.start <= b.end && b.start <= a.end
+}
+
+fn edit(line_index: &LineIndex, start: u32, end: u32, text: &str) -> TextEdit {
+  TextEdit::new(
+    Range::new(line_index.position(start), line_index.position(end)),
+    text.to_string(),
+  )
+}
+
+fn action(
+  uri: &Url,
+  title: &str,
+  kind: CodeActionKind,
+  edits: Vec<TextEdit>,
+  diagnostic: Option<Diagnostic>,
+  is_preferred: bool,
+) -> CodeAction {
+  CodeAction {
+    title: title.to_string(),
+    kind: Some(kind),
+    diagnostics: diagnostic.map(|diagnostic| vec![diagnostic]),
+    edit: Some(WorkspaceEdit::new(HashMap::from([(uri.clone(), edits)]))),
+    is_preferred: Some(is_preferred),
+    ..Default::default()
+  }
+}
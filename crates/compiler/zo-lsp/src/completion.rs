@@ -0,0 +1,588 @@
+use crate::hover;
+use crate::index::FileState;
+
+use zo_interner::Symbol;
+use zo_module_resolver::ModuleExports;
+use zo_session::Session;
+use zo_token::Token;
+use zo_tree::NodeValue;
+use zo_ty::{Ty, TyId};
+use zo_ui_protocol::{ElementTag, EventKind};
+
+use rustc_hash::FxHashMap as HashMap;
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind};
+
+/// Keywords that open an item, offered outside any body.
+pub(crate) const ITEM_KEYWORDS: &[&str] = &[
+  "fun", "pub", "struct", "enum", "abstract", "apply", "type", "val", "imu",
+  "mut", "load", "pack", "ffi", "test",
+];
+
+/// Keywords that open a statement or an expression, offered
+/// inside a body.
+pub(crate) const BODY_KEYWORDS: &[&str] = &[
+  "imu",
+  "mut",
+  "if",
+  "else",
+  "when",
+  "match",
+  "for",
+  "while",
+  "loop",
+  "return",
+  "break",
+  "continue",
+  "defer",
+  "fn",
+  "spawn",
+  "await",
+  "nursery",
+  "supervise",
+  "select",
+  "true",
+  "false",
+  "self",
+];
+
+/// What the cursor sits after.
+enum Context<'a> {
+  /// `receiver.` — fields and `apply` methods.
+  Member(&'a str),
+  /// `Name::` — pack members, enum variants or statics.
+  Path(&'a str),
+  /// `<` in markup — element tags.
+  Tag,
+  /// `@` inside a tag — event attributes.
+  Event,
+  /// Property position inside a `$:` block.
+  Style,
+  /// Plain code — locals, functions and keywords.
+  Code { top_level: bool },
+  /// Somewhere nothing is offered (a selector, a string).
+  Nothing,
+}
+
+/// Completion items for the cursor at byte `offset` of
+/// `source`, the document's latest text. `state` is the most
+/// recent analysis, used for everything semantic.
+pub fn complete(
+  state: &FileState,
+  source: &str,
+  offset: usize,
+) -> Vec<CompletionItem> {
+  let mut offset = offset.min(source.len());
+
+  while !source.is_char_boundary(offset) {
+    offset -= 1;
+  }
+
+  let scan = Scan::new(&source[..offset]);
+
+  match context(&source[..offset], &scan) {
+    Context::Member(receiver) => member_items(state, receiver, offset),
+    Context::Path(name) => path_items(state, name),
+    Context::Tag => names(ElementTag::NAMES, CompletionItemKind::PROPERTY),
+    Context::Event => names(EventKind::NAMES, CompletionItemKind::EVENT),
+    Context::Style => style_items(),
+    Context::Code { top_level } => code_items(state, &scan, top_level),
+    Context::Nothing => Vec::new(),
+  }
+}
+
+/// Completion items for the pub members of every compiled
+/// pack, keyed by pack name. Built while the analysis'
+/// compiler is still alive — it owns the exports.
+pub fn pack_items(
+  exports: &HashMap<Symbol, ModuleExports>,
+  session: &Session,
+) -> HashMap<Symbol, Vec<CompletionItem>> {
+  let interner = &session.interner;
+
+  exports
+    .iter()
+    .map(|(pack, exports)| {
+      let funs = exports.funs.iter().map(|fun| CompletionItem {
+        label: interner.get(fun.name).to_string(),
+        kind: Some(CompletionItemKind::FUNCTION),
+        detail: Some(hover::fun_signature(session, fun, None, &[])),
+        ..Default::default()
+      });
+
+      let vars = exports
+        .vars
+        .iter()
+        .map(|var| (var.name, var.ty_id, CompletionItemKind::VARIABLE));
+      let consts = exports
+        .consts
+        .iter()
+        .map(|c| (c.name, c.ty_id, CompletionItemKind::CONSTANT));
+      let structs = exports
+        .structs
+        .iter()
+        .map(|s| (s.name, s.ty_id, CompletionItemKind::STRUCT));
+
+      let typed =
+        vars
+          .chain(consts)
+          .chain(structs)
+          .map(|(name, ty_id, kind)| CompletionItem {
+            label: interner.get(name).to_string(),
+            kind: Some(kind),
+            detail: Some(hover::ty_label(session, ty_id)),
+            ..Default::default()
+          });
+
+      let enums = exports
+        .enums
+        .iter()
+        .map(|e| item(interner.get(e.name), CompletionItemKind::ENUM, None));
+
+      (*pack, funs.chain(typed).chain(enums).collect())
+    })
+    .collect()
+}
+
+/// Works out what the text before the cursor asks for.
+fn context<'a>(before: &'a str, scan: &Scan) -> Context<'a> {
+  if scan.in_string {
+    return Context::Nothing;
+  }
+
+  let head = before.trim_end_matches(is_ident_char);
+
+  if let Some(depth) = scan.style_depth {
+    let prev = head.trim_end().chars().last();
+
+    return match (depth, prev) {
+      (2.., Some('{' | ';')) => Context::Style,
+      _ => Context::Nothing,
+    };
+  }
+
+  if head.ends_with('@') && in_tag(head) {
+    return Context::Event;
+  }
+
+  if head.ends_with('<') && opens_tag(&head[..head.len() - 1]) {
+    return Context::Tag;
+  }
+
+  if let Some(path) = head.strip_suffix("::") {
+    return Context::Path(trailing_ident(path));
+  }
+
+  if let Some(receiver) = head.strip_suffix('.')
+    && !receiver.ends_with('.')
+  {
+    return Context::Member(trailing_ident(receiver));
+  }
+
+  Context::Code {
+    top_level: scan.depth == 0,
+  }
+}
+
+/// Fields of the receiver's struct, then its `apply` methods.
+fn member_items(
+  state: &FileState,
+  receiver: &str,
+  offset: usize,
+) -> Vec<CompletionItem> {
+  let Some(ty_id) = receiver_ty(state, receiver, offset) else {
+    return Vec::new();
+  };
+
+  let session = &state.session;
+  let table = &session.ty_checker.ty_table;
+  let mut ty_id = ty_id;
+
+  if let Ty::Ref(rid) = session.ty_checker.kind_of_ro(ty_id)
+    && let Some(reference) = table.reference(rid)
+  {
+    ty_id = reference.inner_ty;
+  }
+
+  let mut items = Vec::new();
+
+  if let Ty::Struct(sid) = session.ty_checker.kind_of_ro(ty_id)
+    && let Some(s) = table.struct_ty(sid)
+  {
+    items.extend(table.struct_fields(s).iter().map(|field| {
+      item(
+        session.interner.get(field.name),
+        CompletionItemKind::FIELD,
+        Some(hover::ty_label(session, field.ty_id)),
+      )
+    }));
+  }
+
+  let prefix = format!("{}::", hover::ty_label(session, ty_id));
+
+  items.extend(methods(state, &prefix, true));
+  items
+}
+
+/// The inferred type of `receiver` — the closest typed use of
+/// that name before the cursor.
+fn receiver_ty(
+  state: &FileState,
+  receiver: &str,
+  offset: usize,
+) -> Option<TyId> {
+  let symbol = state.session.interner.symbol(receiver)?;
+
+  (0..state.tree.nodes.len())
+    .filter(|idx| state.tree.nodes[*idx].token == Token::Ident)
+    .filter(|idx| (state.tree.spans[*idx].start as usize) < offset)
+    .filter(|idx| {
+      state.tree.value(*idx as u32) == Some(NodeValue::Symbol(symbol))
+    })
+    .filter_map(|idx| {
+      let ty_id = state.node_tys.get(&idx)?;
+
+      Some((state.tree.spans[idx].start, *ty_id))
+    })
+    .max_by_key(|(start, _)| *start)
+    .map(|(_, ty_id)| ty_id)
+}
+
+/// `Type::name` functions — methods (`self` first) when
+/// `receiver` is set, statics otherwise.
+fn methods<'a>(
+  state: &'a FileState,
+  prefix: &'a str,
+  receiver: bool,
+) -> impl Iterator<Item = CompletionItem> + 'a {
+  let interner = &state.session.interner;
+
+  state.funs.iter().filter_map(move |fun| {
+    let name = interner.get(fun.name).strip_prefix(prefix)?;
+    let has_self = fun
+      .params
+      .first()
+      .is_some_and(|(param, _)| interner.get(*param) == "self");
+
+    (has_self == receiver).then(|| {
+      item(
+        name,
+        CompletionItemKind::METHOD,
+        Some(hover::fun_signature(&state.session, fun, state.pack, &[])),
+      )
+    })
+  })
+}
+
+/// Members of a pack, or the variants and statics of a type.
+fn path_items(state: &FileState, name: &str) -> Vec<CompletionItem> {
+  let session = &state.session;
+
+  let Some(symbol) = session.interner.symbol(name) else {
+    return Vec::new();
+  };
+
+  if let Some(members) = state.packs.get(&symbol) {
+    return members.clone();
+  }
+
+  let table = &session.ty_checker.ty_table;
+  let mut items = Vec::new();
+
+  if let Some(eid) = table.enum_intern_lookup(symbol)
+    && let Some(e) = table.enum_ty(*eid)
+  {
+    items.extend(table.enum_variants(e).iter().map(|variant| {
+      item(
+        session.interner.get(variant.name),
+        CompletionItemKind::ENUM_MEMBER,
+        None,
+      )
+    }));
+  }
+
+  let prefix = format!("{name}::");
+
+  items.extend(methods(state, &prefix, false));
+  items
+}
+
+/// `$:` shorthand properties, expanded name as detail.
+fn style_items() -> Vec<CompletionItem> {
+  zo_styler::PROPERTIES
+    .iter()
+    .map(|(short, full)| {
+      item(short, CompletionItemKind::PROPERTY, Some(full.to_string()))
+    })
+    .collect()
+}
+
+/// Locals in scope (innermost first), functions, packs and
+/// the keywords valid at this position.
+fn code_items(
+  state: &FileState,
+  scan: &Scan,
+  top_level: bool,
+) -> Vec<CompletionItem> {
+  let session = &state.session;
+  let mut items = Vec::new();
+
+  for (name, start) in scan.locals() {
+    if items.iter().any(|item: &CompletionItem| item.label == name) {
+      continue;
+    }
+
+    let detail =
+      binding_ty(state, start).map(|ty| hover::ty_label(session, ty));
+
+    items.push(item(name, CompletionItemKind::VARIABLE, detail));
+  }
+
+  items.extend(
+    state
+      .funs
+      .iter()
+      .filter(|fun| state.is_own(fun))
+      .filter(|fun| !session.interner.get(fun.name).contains("::"))
+      .map(|fun| {
+        item(
+          session.interner.get(fun.name),
+          CompletionItemKind::FUNCTION,
+          Some(hover::fun_signature(session, fun, state.pack, &[])),
+        )
+      }),
+  );
+
+  items.extend(state.packs.keys().map(|pack| {
+    item(
+      session.interner.get(*pack),
+      CompletionItemKind::MODULE,
+      None,
+    )
+  }));
+
+  let keywords = if top_level {
+    ITEM_KEYWORDS
+  } else {
+    BODY_KEYWORDS
+  };
+
+  items.extend(names(keywords, CompletionItemKind::KEYWORD));
+  items
+}
+
+/// The type the analysis gave the binding starting at byte
+/// `start`.
+fn binding_ty(state: &FileState, start: usize) -> Option<TyId> {
+  let idx = state
+    .tree
+    .spans
+    .iter()
+    .position(|span| span.start as usize == start)?;
+
+  state.node_tys.get(&idx).copied()
+}
+
+fn names(names: &[&str], kind: CompletionItemKind) -> Vec<CompletionItem> {
+  names.iter().map(|name| item(name, kind, None)).collect()
+}
+
+fn item(
+  label: &str,
+  kind: CompletionItemKind,
+  detail: Option<String>,
+) -> CompletionItem {
+  CompletionItem {
+    label: label.to_string(),
+    kind: Some(kind),
+    detail,
+    ..Default::default()
+  }
+}
+
+pub(crate) fn is_ident_char(c: char) -> bool {
+  c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// The identifier `text` ends with.
+fn trailing_ident(text: &str) -> &str {
+  let head = text.trim_end_matches(is_ident_char);
+
+  &text[head.len()..]
+}
+
+/// Whether a `<` after `head` opens a tag rather than being a
+/// less-than: a comparison follows an operand.
+fn opens_tag(head: &str) -> bool {
+  !head
+    .trim_end()
+    .ends_with(|c: char| is_ident_char(c) || c == ')' || c == ']')
+}
+
+/// Whether the end of `head` sits inside an open tag — a `<`
+/// that starts a tag with no `>` after it.
+fn in_tag(head: &str) -> bool {
+  head.rfind(['<', '>']).is_some_and(|idx| {
+    head.as_bytes()[idx] == b'<'
+      && opens_tag(&head[..idx])
+      && head[idx + 1..].starts_with(|c: char| c.is_ascii_alphabetic())
+  })
+}
+
+/// A light pass over the text before the cursor: brace depth,
+/// `$:` blocks and the bindings of every open scope. Strings
+/// and comments are skipped.
+pub(crate) struct Scan {
+  /// Open `{` count.
+  pub(crate) depth: usize,
+  /// Depth inside the innermost open `$:` block, if any.
+  style_depth: Option<usize>,
+  /// Whether the cursor sits inside a string literal.
+  in_string: bool,
+  /// Bindings per open scope, outermost first: `(name, byte
+  /// offset of the name)`.
+  scopes: Vec<Vec<(String, usize)>>,
+}
+
+impl Scan {
+  pub(crate) fn new(text: &str) -> Self {
+    let bytes = text.as_bytes();
+
+    let mut scan = Self {
+      depth: 0,
+      style_depth: None,
+      in_string: false,
+      scopes: vec![Vec::new()],
+    };
+
+    // Depth the innermost `$:` block opened at.
+    let mut style_opened = Vec::new();
+    // `$:` seen, its `{` not yet.
+    let mut style_pending = false;
+    // Inside a `fun` / `fn` header, collecting parameters.
+    let mut header = false;
+    let mut params = Vec::new();
+    // The previous word was `imu`, `mut` or `for`.
+    let mut binds = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+      match bytes[i] {
+        b'"' => {
+          i += 1;
+
+          while i < bytes.len() && bytes[i] != b'"' {
+            i += if bytes[i] == b'\\' { 2 } else { 1 };
+          }
+
+          if i >= bytes.len() {
+            scan.in_string = true;
+
+            break;
+          }
+        }
+        b'\'' => {
+          // A char literal (`'{'`, `'\n'`) must not count as
+          // a brace.
+          match (bytes.get(i + 1), bytes.get(i + 2), bytes.get(i + 3)) {
+            (Some(b'\\'), _, Some(b'\'')) => i += 3,
+            (Some(_), Some(b'\''), _) => i += 2,
+            _ => {}
+          }
+        }
+        b'-' if matches!(bytes.get(i + 1), Some(b'-' | b'!')) => {
+          while i < bytes.len() && bytes[i] != b'\n' {
+            i += 1;
+          }
+
+          continue;
+        }
+        b'$' if bytes.get(i + 1) == Some(&b':') => {
+          style_pending = true;
+          i += 1;
+        }
+        b'{' => {
+          scan.depth += 1;
+
+          if style_pending {
+            style_opened.push(scan.depth);
+            style_pending = false;
+          }
+
+          header = false;
+          scan.scopes.push(std::mem::take(&mut params));
+        }
+        b'}' => {
+          if style_opened.last() == Some(&scan.depth) {
+            style_opened.pop();
+          }
+
+          scan.depth = scan.depth.saturating_sub(1);
+
+          if scan.scopes.len() > 1 {
+            scan.scopes.pop();
+          }
+        }
+        b';' if header => {
+          // A bodiless declaration (`fun f();`) binds nothing.
+          header = false;
+          params.clear();
+        }
+        c if c.is_ascii_alphabetic() || c == b'_' => {
+          let start = i;
+
+          while i < bytes.len() && is_ident_char(bytes[i] as char) {
+            i += 1;
+          }
+
+          let word = &text[start..i];
+          let rest = text[i..].trim_start();
+
+          match word {
+            "fun" | "fn" => header = true,
+            "imu" | "mut" | "for" => {
+              binds = true;
+
+              continue;
+            }
+            _ if binds && !header => {
+              if let Some(scope) = scan.scopes.last_mut() {
+                scope.push((word.to_string(), start));
+              }
+            }
+            _ if header && rest.starts_with(':') && !rest.starts_with("::") => {
+              params.push((word.to_string(), start));
+            }
+            _ => {}
+          }
+
+          binds = false;
+
+          continue;
+        }
+        _ => {}
+      }
+
+      if !bytes[i].is_ascii_whitespace() {
+        binds = false;
+      }
+
+      i += 1;
+    }
+
+    scan.style_depth =
+      style_opened.last().map(|opened| scan.depth + 1 - opened);
+
+    scan
+  }
+
+  /// Every binding in scope, innermost scope and latest
+  /// binding first.
+  pub(crate) fn locals(&self) -> impl Iterator<Item = (&str, usize)> {
+    self
+      .scopes
+      .iter()
+      .rev()
+      .flat_map(|scope| scope.iter().rev())
+      .map(|(name, start)| (name.as_str(), *start))
+  }
+}
@@ -0,0 +1,162 @@
+use crate::position::LineIndex;
+
+use zo_compiler::Compiler;
+use zo_error::{Error, Severity};
+use zo_reporter::Detail;
+use zo_reporter::fixes::fixes_for;
+use zo_reporter::locate::fix_span;
+use zo_reporter::render::{error_message, secondary_label};
+
+use tower_lsp::lsp_types::{
+  Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location,
+  NumberOrString, Url,
+};
+
+/// The `source` every published diagnostic carries.
+const SOURCE: &str = "zo";
+
+/// One edit that resolves a diagnostic, offered as a quick
+/// fix.
+#[derive(Clone)]
+pub struct Fix {
+  pub diagnostic: Diagnostic,
+  pub title: String,
+  /// Byte range the edit replaces — empty for an insert.
+  pub start: u32,
+  pub end: u32,
+  pub text: String,
+}
+
+/// Converts the compiler's collected diagnostics into LSP
+/// diagnostics for the entry file at `uri`, with the fixes
+/// each one carries.
+///
+/// Severities are resolved against the lint levels, so an
+/// allowed lint never reaches the editor. Diagnostics raised
+/// inside imported modules are skipped — their spans index
+/// another file.
+pub fn collect(
+  compiler: &Compiler,
+  line_index: &LineIndex,
+  uri: &Url,
+) -> (Vec<Diagnostic>, Vec<Fix>) {
+  let aggregator = compiler.diagnostics();
+  let mut diagnostics = Vec::new();
+  let mut fixes = Vec::new();
+
+  for error in aggregator
+    .errors()
+    .iter()
+    .flat_map(|phase| &phase.errors)
+    .filter(|error| error.file_id().unwrap_or(0) == 0)
+  {
+    let detail = aggregator.detail_for(error);
+    let diagnostic = to_diagnostic(
+      error,
+      aggregator.severity_of(error),
+      detail,
+      line_index,
+      uri,
+    );
+
+    fixes.extend(fixes_of(error, detail, &diagnostic));
+    diagnostics.push(diagnostic);
+  }
+
+  (diagnostics, fixes)
+}
+
+/// The reporter's fix-its for `error`, most preferred first,
+/// then the replacement a `Suggestion` / `Rename` detail
+/// proposes — the same set the JSON and SARIF encoders emit.
+fn fixes_of(
+  error: &Error,
+  detail: Option<&Detail>,
+  diagnostic: &Diagnostic,
+) -> Vec<Fix> {
+  let span = error.span();
+  let fix = |title: String, (start, end): (u32, u32), text: &str| Fix {
+    diagnostic: diagnostic.clone(),
+    title,
+    start,
+    end,
+    text: text.to_string(),
+  };
+
+  let mut fixes = fixes_for(error.kind())
+    .iter()
+    .map(|fix_it| {
+      fix(
+        fix_it.description.to_string(),
+        fix_span(fix_it.kind, span.start, span.end()),
+        fix_it.text,
+      )
+    })
+    .collect::<Vec<_>>();
+
+  match detail {
+    Some(Detail::Suggestion(name)) => fixes.push(fix(
+      format!("Replace with `{name}`"),
+      (span.start, span.end()),
+      name,
+    )),
+    Some(Detail::Rename(name)) => fixes.push(fix(
+      format!("Rename to `{name}`"),
+      (span.start, span.end()),
+      name,
+    )),
+    _ => {}
+  }
+
+  fixes
+}
+
+/// Builds one LSP diagnostic. The stable kebab-case id is the
+/// `code`, and a secondary span becomes related information.
+fn to_diagnostic(
+  error: &Error,
+  severity: Severity,
+  detail: Option<&Detail>,
+  line_index: &LineIndex,
+  uri: &Url,
+) -> Diagnostic {
+  let kind = error.kind();
+
+  let mut message = match detail.and_then(Detail::primary_label) {
+    Some(label) => format!("{}: {label}", error_message(kind)),
+    None => error_message(kind).to_string(),
+  };
+
+  if let Some(help) = detail.and_then(Detail::help) {
+    message.push('\n');
+    message.push_str(&help);
+  }
+
+  let related_information = error.secondary_span().map(|span| {
+    vec![DiagnosticRelatedInformation {
+      location: Location::new(uri.clone(), line_index.range(span)),
+      message: detail
+        .and_then(Detail::secondary_label)
+        .unwrap_or_else(|| secondary_label(kind).to_string()),
+    }]
+  });
+
+  Diagnostic {
+    range: line_index.range(error.span()),
+    severity: Some(to_severity(severity)),
+    code: Some(NumberOrString::String(kind.id().to_string())),
+    source: Some(SOURCE.into()),
+    message,
+    related_information,
+    ..Default::default()
+  }
+}
+
+/// Maps a zo severity to its LSP counterpart.
+const fn to_severity(severity: Severity) -> DiagnosticSeverity {
+  match severity {
+    Severity::Error => DiagnosticSeverity::ERROR,
+    Severity::Warning => DiagnosticSeverity::WARNING,
+    Severity::Note => DiagnosticSeverity::INFORMATION,
+  }
+}
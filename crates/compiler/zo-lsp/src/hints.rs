@@ -0,0 +1,291 @@
+use crate::hover;
+use crate::index::FileState;
+use crate::signature::{self, Callee};
+
+use zo_span::Span;
+use zo_token::Token;
+use zo_ty::Ty;
+
+use tower_lsp::lsp_types::{InlayHint, InlayHintKind, InlayHintLabel};
+
+use rustc_hash::FxHashMap as HashMap;
+
+/// Inlay hints for the bytes `within` — the type of every
+/// `:=` binding and untyped closure parameter, the parameter
+/// name before each call argument, and the types a generic
+/// call chose for its `$T`s.
+pub fn hints(state: &FileState, within: (u32, u32)) -> Vec<InlayHint> {
+  let tokens = &state.tokens;
+  let kinds = &tokens.kinds;
+  let source = state.source.as_str();
+  let nodes = state
+    .tree
+    .spans
+    .iter()
+    .enumerate()
+    .map(|(idx, span)| (*span, idx))
+    .collect::<HashMap<_, _>>();
+
+  let span = |idx: usize| Span::new(tokens.starts[idx], tokens.lengths[idx]);
+  let text = |idx: usize| {
+    let span = span(idx);
+
+    source
+      .get(span.start as usize..span.end() as usize)
+      .unwrap_or_default()
+  };
+  let ty_at = |idx: usize| {
+    let node_idx = nodes.get(&span(idx))?;
+
+    state.node_tys.get(node_idx).copied()
+  };
+
+  let mut hints = Vec::new();
+
+  for idx in 0..kinds.len() {
+    let at = tokens.starts[idx];
+
+    if at < within.0 || at >= within.1 {
+      continue;
+    }
+
+    let next = |n: usize| kinds.get(idx + n).copied();
+
+    match kinds[idx] {
+      // `imu x := ..` — `x: T`.
+      Token::Imu | Token::Mut
+        if next(1) == Some(Token::Ident) && next(2) == Some(Token::ColonEq) =>
+      {
+        if let Some(ty) =
+          hover::local_ty(state, span(idx), text(idx + 1), span(idx + 1))
+        {
+          hints.push(hint(state, span(idx + 1).end(), format!(": {ty}"), true));
+        }
+      }
+      // `fn(x) => ..` — `x: T`, read off the closure's type.
+      Token::Fn if next(1) == Some(Token::LParen) => {
+        let Some(Ty::Fun(fun_id)) =
+          ty_at(idx).map(|ty_id| state.session.ty_checker.kind_of_ro(ty_id))
+        else {
+          continue;
+        };
+
+        let Some(fun) = state.session.ty_checker.ty_table.fun(&fun_id) else {
+          continue;
+        };
+
+        let param_tys = state.session.ty_checker.ty_table.fun_params(fun);
+
+        for (param, arg) in arguments(kinds, idx + 1).iter().zip(param_tys) {
+          let untyped = kinds[*param] == Token::Ident
+            && matches!(
+              kinds.get(param + 1),
+              Some(Token::Comma | Token::RParen)
+            );
+
+          if let Some(ty) = untyped
+            .then(|| hover::settled(hover::ty_label(&state.session, *arg)))
+            .flatten()
+          {
+            hints.push(hint(
+              state,
+              span(*param).end(),
+              format!(": {ty}"),
+              true,
+            ));
+          }
+        }
+      }
+      // `f(a, b)` — `x: a, y: b`.
+      Token::LParen => {
+        let Some(callee) = signature::callee(tokens, source, idx) else {
+          continue;
+        };
+
+        let callee = match callee {
+          Callee::Method(name, None) => {
+            let receiver = (idx >= 3 && kinds[idx - 3] == Token::Ident)
+              .then(|| ty_at(idx - 3))
+              .flatten()
+              .map(|ty_id| hover::ty_label(&state.session, ty_id));
+
+            Callee::Method(name, receiver)
+          }
+          callee => callee,
+        };
+
+        let is_method = matches!(callee, Callee::Method(..));
+        let [fun] = signature::candidates(state, &callee)[..] else {
+          continue;
+        };
+
+        let skipped = usize::from(is_method && signature::has_self(state, fun));
+        let params = &fun.params[skipped.min(fun.params.len())..];
+        let args = arguments(kinds, idx);
+
+        for (arg, (name, _)) in args.iter().zip(params) {
+          let name = state.session.interner.get(*name);
+          let end = args
+            .iter()
+            .find(|next| **next > *arg)
+            .map_or_else(|| closing(kinds, idx), |next| next - 1);
+          let arg_text = source
+            .get(tokens.starts[*arg] as usize..tokens.starts[end] as usize)
+            .unwrap_or_default()
+            .trim();
+
+          if name.starts_with('_') || names(arg_text, name) {
+            continue;
+          }
+
+          hints.push(InlayHint {
+            padding_right: Some(true),
+            kind: Some(InlayHintKind::PARAMETER),
+            ..hint(state, tokens.starts[*arg], format!("{name}:"), false)
+          });
+        }
+      }
+      _ => {}
+    }
+  }
+
+  hints.extend(generic_args(state, within));
+  hints.sort_by_key(|hint| (hint.position.line, hint.position.character));
+  hints
+}
+
+/// `<$T = int>` after the name of each generic call.
+fn generic_args(state: &FileState, within: (u32, u32)) -> Vec<InlayHint> {
+  let tokens = &state.tokens;
+  let source = state.source.as_str();
+  let mut hints = Vec::new();
+
+  for (site, tys) in &state.generic_args {
+    if site.start < within.0 || site.start >= within.1 {
+      continue;
+    }
+
+    let Ok(idx) = tokens.starts.binary_search(&site.start) else {
+      continue;
+    };
+
+    // The call's `(` — the site itself, or after the method
+    // name for a `.` site.
+    let lparen = match tokens.kinds[idx] {
+      Token::Dot => idx + 2,
+      _ => idx,
+    };
+
+    if tokens.kinds.get(lparen) != Some(&Token::LParen) {
+      continue;
+    }
+
+    let Some(labels) = tys
+      .iter()
+      .map(|ty_id| hover::settled(hover::ty_label(&state.session, *ty_id)))
+      .collect::<Option<Vec<_>>>()
+    else {
+      continue;
+    };
+
+    let names = signature::callee(tokens, source, lparen)
+      .and_then(|callee| signature::candidates(state, &callee).first().copied())
+      .map(|fun| signature::type_params(state, fun))
+      .filter(|names| names.len() == labels.len());
+
+    let label = match names {
+      Some(names) => names
+        .iter()
+        .zip(&labels)
+        .map(|(name, ty)| format!("{name} = {ty}"))
+        .collect::<Vec<_>>(),
+      None => labels,
+    };
+
+    let name = lparen - 1;
+    let end = tokens.starts[name] + tokens.lengths[name] as u32;
+
+    hints.push(hint(state, end, format!("<{}>", label.join(", ")), true));
+  }
+
+  hints
+}
+
+/// The first token of each argument of the parenthesized
+/// list opened at `lparen`.
+fn arguments(kinds: &[Token], lparen: usize) -> Vec<usize> {
+  let mut args = Vec::new();
+  let mut depth = 0usize;
+  let mut expect = true;
+
+  for (idx, kind) in kinds.iter().enumerate().skip(lparen + 1) {
+    match kind {
+      Token::RParen | Token::RBracket | Token::RBrace if depth == 0 => break,
+      Token::RParen | Token::RBracket | Token::RBrace => depth -= 1,
+      Token::Comma if depth == 0 => {
+        expect = true;
+
+        continue;
+      }
+      Token::Eof => break,
+      _ => {}
+    }
+
+    if expect {
+      args.push(idx);
+      expect = false;
+    }
+
+    if matches!(kind, Token::LParen | Token::LBracket | Token::LBrace) {
+      depth += 1;
+    }
+  }
+
+  args
+}
+
+/// The `)` closing the list opened at `lparen`.
+fn closing(kinds: &[Token], lparen: usize) -> usize {
+  let mut depth = 0usize;
+
+  for (idx, kind) in kinds.iter().enumerate().skip(lparen) {
+    match kind {
+      Token::LParen | Token::LBracket | Token::LBrace => depth += 1,
+      Token::RParen | Token::RBracket | Token::RBrace => {
+        depth -= 1;
+
+        if depth == 0 {
+          return idx;
+        }
+      }
+      Token::Eof => return idx,
+      _ => {}
+    }
+  }
+
+  kinds.len() - 1
+}
+
+/// Whether the argument already says what the parameter is
+/// — `x` or `p.x` passed as `x`.
+fn names(arg: &str, param: &str) -> bool {
+  arg == param
+    || arg
+      .rsplit_once('.')
+      .is_some_and(|(_, field)| field == param)
+}
+
+fn hint(state: &FileState, at: u32, label: String, is_type: bool) -> InlayHint {
+  let position = state.line_index.position(at);
+
+  InlayHint {
+    position,
+    label: InlayHintLabel::String(label),
+    kind: is_type.then_some(InlayHintKind::TYPE),
+    text_edits: None,
+    tooltip: None,
+    padding_left: None,
+    padding_right: None,
+    data: None,
+  }
+}
@@ -0,0 +1,279 @@
+use crate::index::FileState;
+use crate::references;
+use crate::signature;
+
+use zo_interner::Symbol;
+use zo_module_resolver::AbstractDef;
+use zo_pp::PrettyPrinter;
+use zo_session::Session;
+use zo_span::Span;
+use zo_token::Token;
+use zo_tree::NodeValue;
+use zo_ty::TyId;
+use zo_value::FunDef;
+
+/// Markdown shown when hovering the node at `node_idx` — a
+/// `zo` code block with the signature or inferred type,
+/// followed by the definition's `-!` doc comment.
+pub fn hover(state: &FileState, node_idx: usize) -> Option<String> {
+  let span = state.tree.spans[node_idx];
+  let def_span = state.use_def_map.get(&span).copied();
+
+  let symbol = match state.tree.nodes[node_idx].token {
+    Token::Ident => match state.tree.value(node_idx as u32) {
+      Some(NodeValue::Symbol(symbol)) => Some(symbol),
+      _ => None,
+    },
+    _ => None,
+  };
+
+  if let Some(fun) = find_fun(state, symbol, def_span) {
+    return Some(markdown(
+      &fun_signature(
+        &state.session,
+        fun,
+        state.pack,
+        &signature::type_params(state, fun),
+      ),
+      fun_doc(state, fun),
+    ));
+  }
+
+  if let Some(symbol) = symbol
+    && let Some(abs) = state.abstract_defs.get(&symbol)
+  {
+    let name = state.session.interner.get(symbol);
+    let doc = doc_comment(&state.source, abs.span, name);
+
+    return Some(markdown(&abstract_signature(state, name, abs), doc));
+  }
+
+  let ty_id = state.node_tys.get(&node_idx).copied()?;
+  let ty = ty_label(&state.session, ty_id);
+
+  let code = match symbol {
+    Some(symbol) => format!("{}: {ty}", state.session.interner.get(symbol)),
+    None => ty,
+  };
+
+  let doc = symbol.zip(def_span).and_then(|(symbol, def_span)| {
+    doc_comment(&state.source, def_span, state.session.interner.get(symbol))
+  });
+
+  Some(markdown(&code, doc))
+}
+
+/// The function a node names — by its definition span when
+/// the use-def map knows it, by name otherwise.
+fn find_fun(
+  state: &FileState,
+  symbol: Option<Symbol>,
+  def_span: Option<Span>,
+) -> Option<&FunDef> {
+  def_span
+    .and_then(|def_span| {
+      state
+        .funs
+        .iter()
+        .find(|fun| state.is_own(fun) && fun.span == def_span)
+    })
+    .or_else(|| {
+      let symbol = symbol?;
+
+      state.funs.iter().find(|fun| fun.name == symbol)
+    })
+}
+
+/// `fun pack::name<$T, ..>(a: T, ..) -> R`, the type
+/// parameters spelled as `generics` names them. A `unit`
+/// return is left out, as in source, and so is the pack when
+/// it is `own` — the file's own.
+pub(crate) fn fun_signature(
+  session: &Session,
+  fun: &FunDef,
+  own: Option<Symbol>,
+  generics: &[String],
+) -> String {
+  let interner = &session.interner;
+
+  let mut name = match fun.owning_pack {
+    Some(pack) if Some(pack) != own => {
+      format!("{}::{}", interner.get(pack), interner.get(fun.name))
+    }
+    _ => interner.get(fun.name).to_string(),
+  };
+
+  if !generics.is_empty() {
+    name.push_str(&format!("<{}>", generics.join(", ")));
+  }
+
+  let label = signature::generic_label(session, fun, generics);
+
+  format!(
+    "fun {name}({}){}",
+    params(session, &fun.params, &label),
+    returns(label(fun.return_ty))
+  )
+}
+
+/// `abstract Name { fun m(self, ..) -> R; .. }`. `Self`
+/// stays an inference variable in the definition, and reads
+/// `Self` back.
+fn abstract_signature(
+  state: &FileState,
+  name: &str,
+  abs: &AbstractDef,
+) -> String {
+  let session = &state.session;
+  let label = |ty_id| {
+    settled(ty_label(session, ty_id)).unwrap_or_else(|| "Self".to_string())
+  };
+  let mut code = format!("abstract {name} {{\n");
+
+  for method in &abs.methods {
+    code.push_str(&format!(
+      "  fun {}({}){};\n",
+      session.interner.get(method.name),
+      params(session, &method.params, label),
+      returns(label(method.return_ty))
+    ));
+  }
+
+  code.push('}');
+  code
+}
+
+/// A parameter list, each type spelled by `label`. `self` is
+/// written bare.
+fn params(
+  session: &Session,
+  params: &[(Symbol, TyId)],
+  label: impl Fn(TyId) -> String,
+) -> String {
+  let interner = &session.interner;
+
+  params
+    .iter()
+    .map(|(name, ty_id)| match interner.get(*name) {
+      "self" => "self".to_string(),
+      name => format!("{name}: {}", label(*ty_id)),
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// ` -> R`, or nothing for `unit`.
+fn returns(ty: String) -> String {
+  match ty.as_str() {
+    "unit" => String::new(),
+    ty => format!(" -> {ty}"),
+  }
+}
+
+/// The source spelling of a type — `int`, not the dumps' `i32`.
+pub(crate) fn ty_label(session: &Session, ty_id: TyId) -> String {
+  PrettyPrinter::source_ty_label(&session.interner, &session.ty_checker, ty_id)
+}
+
+/// The inferred type of the local `name` — declared by the
+/// `imu`/`mut` at `keyword`, named at `name_span` — else the
+/// type at any of its uses. `None` while inference has not
+/// settled it.
+pub(crate) fn local_ty(
+  state: &FileState,
+  keyword: Span,
+  name: &str,
+  name_span: Span,
+) -> Option<String> {
+  let declared = state.local_tys.get(&keyword).copied();
+  let ty_id = declared.or_else(|| {
+    let uses = state
+      .use_def_map
+      .iter()
+      .filter(|(_, def)| {
+        references::name_span(&state.source, **def, name) == Some(name_span)
+      })
+      .map(|(use_span, _)| *use_span);
+
+    std::iter::once(name_span).chain(uses).find_map(|span| {
+      let node_idx = state.tree.spans.iter().position(|at| *at == span)?;
+
+      state.node_tys.get(&node_idx).copied()
+    })
+  })?;
+
+  settled(ty_label(&state.session, ty_id))
+}
+
+/// `ty`, unless it still holds an inference variable or a
+/// type parameter.
+pub(crate) fn settled(ty: String) -> Option<String> {
+  (!ty.contains(['?', '$'])).then_some(ty)
+}
+
+/// The doc comment of a function — read from the current
+/// file, or from the module that declares it (`compiler-lib`
+/// packs included).
+pub(crate) fn fun_doc(state: &FileState, fun: &FunDef) -> Option<String> {
+  let name = state.session.interner.get(fun.name);
+
+  match fun.owning_pack {
+    _ if state.is_own(fun) => doc_comment(&state.source, fun.span, name),
+    None => None,
+    Some(pack) => {
+      let path = state.pack_paths.get(&pack)?;
+      let source = std::fs::read_to_string(path).ok()?;
+
+      doc_comment(&source, fun.span, name)
+    }
+  }
+}
+
+/// The `-!` lines right above the line `span` starts on,
+/// skipping `%%` attributes in between. `None` when the line
+/// does not mention `name` — the span belongs to another
+/// file — or when there is no doc comment.
+fn doc_comment(source: &str, span: Span, name: &str) -> Option<String> {
+  let start = (span.start as usize).min(source.len());
+
+  if span == Span::ZERO || !source.is_char_boundary(start) {
+    return None;
+  }
+
+  let line_start = source[..start].rfind('\n').map_or(0, |idx| idx + 1);
+  let line_end = source[start..]
+    .find('\n')
+    .map_or(source.len(), |idx| start + idx);
+
+  if !source[line_start..line_end].contains(name) {
+    return None;
+  }
+
+  let mut lines = Vec::new();
+
+  for line in source[..line_start].lines().rev() {
+    let line = line.trim_start();
+
+    if let Some(doc) = line.strip_prefix("-!") {
+      lines.push(doc.strip_prefix(' ').unwrap_or(doc).trim_end());
+    } else if !line.starts_with("%%") {
+      break;
+    }
+  }
+
+  if lines.is_empty() {
+    return None;
+  }
+
+  lines.reverse();
+
+  Some(lines.join("\n"))
+}
+
+/// A `zo` code block, then the doc comment under a rule.
+fn markdown(code: &str, doc: Option<String>) -> String {
+  match doc {
+    Some(doc) => format!("```zo\n{code}\n```\n---\n{doc}"),
+    None => format!("```zo\n{code}\n```"),
+  }
+}
@@ -1,86 +1,241 @@
+use crate::completion;
+use crate::diagnostics::{self, Fix};
 use crate::position::LineIndex;
 
 use zo_analyzer::SemanticResult;
-use zo_compiler::{Compiler, default_core_search_paths};
+use zo_compiler::{Compiler, ModuleCache, default_core_search_paths};
 use zo_interner::Symbol;
 use zo_module_resolver::AbstractDef;
+use zo_session::Session;
 use zo_span::Span;
+use zo_token::TokenBuffer;
 use zo_tree::Tree;
+use zo_ty::TyId;
 use zo_value::FunDef;
 
 use rustc_hash::FxHashMap as HashMap;
-use tower_lsp::lsp_types::Url;
+use tower_lsp::lsp_types::{CompletionItem, Diagnostic, Url};
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
 /// Cached compilation state for a single open file.
 pub struct FileState {
+  /// Canonical path of the file on disk.
+  pub path: PathBuf,
+  pub source: String,
   pub line_index: LineIndex,
+  /// Tokens in source order — the tree reorders expressions.
+  pub tokens: TokenBuffer,
   pub tree: Tree,
+  /// Interner and ty-checker the analysis ran against — every
+  /// `Symbol` and `TyId` below resolves through them.
+  pub session: Session,
+  /// The pack the file's own functions are owned by — an
+  /// entry file adopts its stem.
+  pub pack: Option<Symbol>,
+  /// Resolved type per tree node.
+  pub node_tys: HashMap<usize, TyId>,
   pub funs: Vec<FunDef>,
   pub abstract_defs: HashMap<Symbol, AbstractDef>,
   pub use_def_map: HashMap<Span, Span>,
   pub pack_paths: HashMap<Symbol, PathBuf>,
+  pub diagnostics: Vec<Diagnostic>,
+  /// Quick fixes for `diagnostics`.
+  pub fixes: Vec<Fix>,
+  /// Non-exhaustive `match` body-`{` span → missing arm
+  /// patterns.
+  pub missing_arms: HashMap<Span, Vec<String>>,
+  /// Generic call site → the type chosen for each `$T`.
+  pub generic_args: HashMap<Span, Vec<TyId>>,
+  /// Local declaration span → its type.
+  pub local_tys: HashMap<Span, TyId>,
+  /// Completion items for the pub members of each pack.
+  pub packs: HashMap<Symbol, Vec<CompletionItem>>,
+  /// Canonical paths of the modules the analysis read — a
+  /// change to any of them makes it stale.
+  pub deps: Vec<PathBuf>,
+}
+
+impl FileState {
+  /// Whether `fun` is declared by this file rather than by a
+  /// loaded pack.
+  pub fn is_own(&self, fun: &FunDef) -> bool {
+    fun.owning_pack.is_none() || fun.owning_pack == self.pack
+  }
 }
 
 /// Per-workspace compilation cache.
 pub struct SymbolIndex {
   pub files: HashMap<Url, FileState>,
+  /// Resolved packs shared by every analysis, so a keystroke
+  /// re-analyzes the edited file alone.
+  pub cache: Arc<ModuleCache>,
 }
 
 impl SymbolIndex {
   pub fn new() -> Self {
     Self {
       files: HashMap::default(),
+      cache: Arc::new(ModuleCache::new()),
     }
   }
 
-  /// Recompile a file and cache the results.
-  pub fn update(&mut self, uri: &Url, source: &str, path: &Path) {
-    let mut search_paths = default_core_search_paths();
-    if let Some(parent) = path.parent() {
-      search_paths.push(parent.to_path_buf());
-    }
+  /// Caches `state`, analyzed off the index by [`analyze`].
+  pub fn insert(&mut self, uri: Url, state: FileState) {
+    self.files.insert(uri, state);
+  }
+
+  /// The open documents whose analysis read `path`.
+  pub fn dependents(&self, path: &Path) -> Vec<Url> {
+    let path = canonical(path);
+
+    self
+      .files
+      .iter()
+      .filter(|(_, state)| state.deps.contains(&path))
+      .map(|(uri, _)| uri.clone())
+      .collect()
+  }
+
+  /// Whether some document covers the file at `path`.
+  pub fn covers(&self, path: &Path) -> bool {
+    let path = canonical(path);
 
-    log::info!(
-      "update: path={} search_paths={:?}",
-      path.display(),
-      search_paths,
-    );
-
-    let mut compiler = Compiler::with_search_paths(search_paths);
-
-    let (semantic, _tokenization, parsing, _session, _file_table) =
-      compiler.analyze_source(source, path);
-
-    log::info!(
-      "update: funs={} pack_paths={} use_def_map={}",
-      semantic.funs.len(),
-      semantic.pack_paths.len(),
-      semantic.use_def_map.len(),
-    );
-
-    let SemanticResult {
-      funs,
-      abstract_defs,
-      use_def_map,
-      pack_paths,
-      ..
-    } = semantic;
-
-    let state = FileState {
-      line_index: LineIndex::new(source),
-      tree: parsing.tree,
-      funs,
-      abstract_defs,
-      use_def_map,
-      pack_paths,
-    };
-
-    self.files.insert(uri.clone(), state);
+    self.files.values().any(|state| state.path == path)
   }
 
   pub fn get(&self, uri: &Url) -> Option<&FileState> {
     self.files.get(uri)
   }
+
+  /// Forget a closed file.
+  pub fn remove(&mut self, uri: &Url) {
+    self.files.remove(uri);
+  }
+}
+
+/// Compiles `source` and builds the state cached for `uri`.
+/// Needs no index — the caller holds no lock while the file
+/// analyzes. `None` once `cancel` is raised: the compile
+/// stops at the next pack.
+pub fn analyze(
+  cache: &Arc<ModuleCache>,
+  uri: &Url,
+  source: &str,
+  path: &Path,
+  cancel: &Arc<AtomicBool>,
+) -> Option<FileState> {
+  let mut search_paths = default_core_search_paths();
+  if let Some(parent) = path.parent() {
+    search_paths.push(parent.to_path_buf());
+  }
+
+  log::info!(
+    "analyze: path={} search_paths={:?}",
+    path.display(),
+    search_paths,
+  );
+
+  let mut compiler = Compiler::with_search_paths(search_paths);
+
+  compiler.set_module_cache(Arc::clone(cache));
+  compiler.set_node_tys(true);
+  compiler.set_cancel(Arc::clone(cancel));
+  // A pack module declares no `main` — analyze it the way
+  // `zo test` does a library entry.
+  compiler.set_test_mode(is_pack_module(path));
+
+  let (semantic, tokenization, parsing, session, file_table) =
+    compiler.analyze_source(source, path);
+
+  if compiler.is_cancelled() {
+    return None;
+  }
+
+  log::info!(
+    "analyze: funs={} pack_paths={} use_def_map={}",
+    semantic.funs.len(),
+    semantic.pack_paths.len(),
+    semantic.use_def_map.len(),
+  );
+
+  let line_index = LineIndex::new(source);
+  let (diagnostics, fixes) = diagnostics::collect(&compiler, &line_index, uri);
+  let packs = completion::pack_items(compiler.module_exports(), &session);
+
+  let node_tys = semantic
+    .annotations
+    .iter()
+    .chain(&semantic.node_tys)
+    .map(|annotation| (annotation.node_idx, annotation.ty_id))
+    .collect();
+
+  let SemanticResult {
+    funs,
+    abstract_defs,
+    use_def_map,
+    missing_arms,
+    generic_args,
+    local_tys,
+    pack_paths,
+    ..
+  } = semantic;
+
+  let pack = path
+    .file_stem()
+    .and_then(|stem| session.interner.symbol(&stem.to_string_lossy()));
+
+  Some(FileState {
+    path: canonical(path),
+    source: source.to_string(),
+    line_index,
+    tokens: tokenization.tokens,
+    tree: parsing.tree,
+    session,
+    pack,
+    node_tys,
+    funs,
+    abstract_defs,
+    use_def_map,
+    pack_paths,
+    diagnostics,
+    fixes,
+    missing_arms,
+    generic_args,
+    local_tys,
+    packs,
+    deps: deps(path, &file_table),
+  })
+}
+
+/// The modules an analysis of `path` read — every loaded file
+/// past the entry itself, and the sibling `lib.zo`.
+fn deps(path: &Path, file_table: &[(PathBuf, String)]) -> Vec<PathBuf> {
+  let lib = path.with_file_name("lib.zo");
+
+  file_table
+    .iter()
+    .skip(1)
+    .map(|(path, _)| path.as_path())
+    .chain((lib.is_file() && lib != path).then_some(lib.as_path()))
+    .map(canonical)
+    .collect()
+}
+
+/// Whether `path` is a module of its directory's `lib.zo`
+/// rather than the program entry.
+fn is_pack_module(path: &Path) -> bool {
+  let lib = path.with_file_name("lib.zo");
+
+  lib.is_file()
+    && lib != path
+    && path.file_stem().is_some_and(|stem| stem != "main")
+}
+
+/// `path` with symlinks and `..` resolved, so the paths of one
+/// file compare equal however they were reached.
+pub fn canonical(path: &Path) -> PathBuf {
+  std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
@@ -1,6 +1,18 @@
+mod actions;
+mod completion;
+mod diagnostics;
+mod hints;
+mod hover;
 mod index;
 mod position;
+mod references;
+mod semantic;
 mod server;
+mod signature;
+mod symbols;
+
+#[cfg(test)]
+mod tests;
 
 use server::ZoLanguageServer;
 
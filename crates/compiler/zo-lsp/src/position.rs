@@ -1,43 +1,95 @@
 use zo_span::Span;
 
+use rustc_hash::FxHashMap as HashMap;
 use tower_lsp::lsp_types::{Position, Range};
 
 /// Precomputed line-start byte offsets for O(1) line lookup.
+///
+/// LSP columns count UTF-16 code units — the server negotiates
+/// no other `positionEncoding` — while spans count bytes. The
+/// non-ASCII characters of each line are kept to convert
+/// between the two.
 pub struct LineIndex {
   line_starts: Vec<u32>,
+  /// Byte length of the text.
+  len: u32,
+  /// Non-ASCII characters per line: `(byte column, UTF-8
+  /// length)`, in column order.
+  wide: HashMap<u32, Vec<(u32, u8)>>,
 }
 
 impl LineIndex {
   /// Build from source text. O(n) scan, done once per file.
   pub fn new(source: &str) -> Self {
     let mut line_starts = vec![0u32];
+    let mut wide = HashMap::<u32, Vec<(u32, u8)>>::default();
 
-    for (i, byte) in source.bytes().enumerate() {
-      if byte == b'\n' {
+    for (i, c) in source.char_indices() {
+      if c == '\n' {
         line_starts.push((i + 1) as u32);
+      } else if !c.is_ascii() {
+        let line = (line_starts.len() - 1) as u32;
+        let column = i as u32 - line_starts[line as usize];
+
+        wide
+          .entry(line)
+          .or_default()
+          .push((column, c.len_utf8() as u8));
       }
     }
 
-    Self { line_starts }
+    Self {
+      line_starts,
+      len: source.len() as u32,
+      wide,
+    }
   }
 
-  /// LSP Position (line, character) to byte offset.
+  /// LSP Position (line, character) to byte offset. A line
+  /// past the end clamps to the end of the text, a column
+  /// past the end of its line to the line end.
   pub fn offset(&self, line: u32, col: u32) -> u32 {
-    let start = self.line_starts.get(line as usize).copied().unwrap_or(0);
+    let Some(&start) = self.line_starts.get(line as usize) else {
+      return self.len;
+    };
+
+    let end = self
+      .line_starts
+      .get(line as usize + 1)
+      .map_or(self.len, |next| next - 1);
+
+    let mut col = col;
 
-    start + col
+    for &(at, len) in self.wide.get(&line).into_iter().flatten() {
+      if at >= col {
+        break;
+      }
+
+      col += u32::from(len) - utf16_len(len);
+    }
+
+    start.saturating_add(col).min(end)
   }
 
   /// Byte offset to LSP Position.
   pub fn position(&self, offset: u32) -> Position {
+    let offset = offset.min(self.len);
     let line = self
       .line_starts
       .partition_point(|&s| s <= offset)
       .saturating_sub(1);
 
     let col = offset - self.line_starts[line];
+    let narrowed = self
+      .wide
+      .get(&(line as u32))
+      .into_iter()
+      .flatten()
+      .take_while(|(at, _)| *at < col)
+      .map(|&(_, len)| u32::from(len) - utf16_len(len))
+      .sum::<u32>();
 
-    Position::new(line as u32, col)
+    Position::new(line as u32, col - narrowed)
   }
 
   /// Span to LSP Range.
@@ -45,3 +97,37 @@ impl LineIndex {
     Range::new(self.position(span.start), self.position(span.end()))
   }
 }
+
+/// UTF-16 code units of a character `len` UTF-8 bytes long.
+const fn utf16_len(len: u8) -> u32 {
+  if len == 4 { 2 } else { 1 }
+}
+
+/// Applies one `didChange` edit to `text` — the bytes `range`
+/// covers are replaced by `new_text`, or the whole text when
+/// there is no range. Offsets past the end clamp to it.
+pub fn apply_change(text: &mut String, range: Option<Range>, new_text: &str) {
+  let Some(range) = range else {
+    *text = new_text.to_string();
+
+    return;
+  };
+
+  let line_index = LineIndex::new(text);
+  let clamp = |position: Position| {
+    let mut offset = (line_index.offset(position.line, position.character)
+      as usize)
+      .min(text.len());
+
+    while !text.is_char_boundary(offset) {
+      offset -= 1;
+    }
+
+    offset
+  };
+
+  let start = clamp(range.start);
+  let end = clamp(range.end).max(start);
+
+  text.replace_range(start..end, new_text);
+}
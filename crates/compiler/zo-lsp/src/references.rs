@@ -0,0 +1,397 @@
+use crate::completion::{BODY_KEYWORDS, ITEM_KEYWORDS, Scan, is_ident_char};
+use crate::index::{FileState, SymbolIndex, canonical};
+use crate::position::LineIndex;
+
+use zo_compiler::default_core_search_paths;
+use zo_span::Span;
+use zo_token::Token;
+use zo_tree::NodeValue;
+
+use tower_lsp::lsp_types::{Location, TextEdit, Url, WorkspaceEdit};
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The definition a name resolves to. Symbols are per-file
+/// (every analysis has its own interner), so the name travels
+/// as text.
+pub struct Target {
+  /// Canonical path of the file that declares the name.
+  pub path: PathBuf,
+  /// The definition span, as the use-def map records it.
+  pub span: Span,
+  pub name: String,
+  /// Whether the name is a function — visible from the files
+  /// that `load` its module, not only its own.
+  pub is_fun: bool,
+}
+
+/// Every occurrence of a target inside one file.
+struct FileRefs {
+  uri: Url,
+  source: String,
+  /// Exact spans of the name, declaration included.
+  spans: Vec<Span>,
+  /// Spans of the name inside `load` statements.
+  loads: Vec<Span>,
+  /// Names already bound at file level — functions, local or
+  /// imported.
+  funs: Vec<String>,
+}
+
+/// The definition the identifier at `node_idx` names. The
+/// identifier may be a use or the declaration itself.
+pub fn target(state: &FileState, node_idx: usize) -> Option<Target> {
+  if state.tree.nodes[node_idx].token != Token::Ident {
+    return None;
+  }
+
+  let Some(NodeValue::Symbol(symbol)) = state.tree.value(node_idx as u32)
+  else {
+    return None;
+  };
+
+  let interner = &state.session.interner;
+  let name = interner.get(symbol).to_string();
+  let span = state.tree.spans[node_idx];
+  let is_fun = |def: Span| {
+    state
+      .funs
+      .iter()
+      .any(|fun| state.is_own(fun) && fun.span == def)
+  };
+
+  if let Some(&def) = state.use_def_map.get(&span) {
+    return Some(Target {
+      path: state.path.clone(),
+      span: def,
+      is_fun: is_fun(def),
+      name,
+    });
+  }
+
+  let local_funs = state
+    .funs
+    .iter()
+    .filter(|fun| state.is_own(fun) && fun.span != Span::ZERO)
+    .map(|fun| fun.span);
+
+  if let Some(def) = state
+    .use_def_map
+    .values()
+    .copied()
+    .chain(local_funs)
+    .find(|def| name_span(&state.source, *def, &name) == Some(span))
+  {
+    return Some(Target {
+      path: state.path.clone(),
+      span: def,
+      is_fun: is_fun(def),
+      name,
+    });
+  }
+
+  let fun = state.funs.iter().find(|fun| {
+    fun.name == symbol && !state.is_own(fun) && fun.span != Span::ZERO
+  })?;
+  let path = state.pack_paths.get(&fun.owning_pack?)?;
+
+  Some(Target {
+    path: canonical(path),
+    span: fun.span,
+    is_fun: true,
+    name,
+  })
+}
+
+/// Every location of `target` across the indexed files. The
+/// declaration is listed first when requested.
+pub fn references(
+  index: &SymbolIndex,
+  target: &Target,
+  include_declaration: bool,
+) -> Vec<Location> {
+  let mut locations = Vec::new();
+
+  for refs in collect(index, target) {
+    let line_index = LineIndex::new(&refs.source);
+
+    for span in refs.spans.iter().chain(&refs.loads) {
+      let declaration = is_declaration(index, target, &refs.uri, *span);
+
+      if declaration && !include_declaration {
+        continue;
+      }
+
+      let location = Location::new(refs.uri.clone(), line_index.range(*span));
+
+      if declaration {
+        locations.insert(0, location);
+      } else {
+        locations.push(location);
+      }
+    }
+  }
+
+  locations
+}
+
+/// The edit renaming `target` to `new_name` everywhere it
+/// occurs, `load` lists included. `Err` explains why the
+/// rename is refused.
+pub fn rename(
+  index: &SymbolIndex,
+  target: &Target,
+  new_name: &str,
+) -> Result<WorkspaceEdit, String> {
+  check_renamable(target)?;
+
+  if !is_valid_name(new_name) {
+    return Err(format!("`{new_name}` is not a valid identifier"));
+  }
+
+  let mut changes = HashMap::new();
+
+  for refs in collect(index, target) {
+    if refs.funs.iter().any(|fun| fun == new_name) {
+      return Err(format!("`{new_name}` is already a function in scope"));
+    }
+
+    for span in &refs.spans {
+      let before = &refs.source[..span.start as usize];
+
+      if Scan::new(before).locals().any(|(name, _)| name == new_name) {
+        return Err(format!("`{new_name}` is already bound in this scope"));
+      }
+    }
+
+    let line_index = LineIndex::new(&refs.source);
+    let edits = refs
+      .spans
+      .iter()
+      .chain(&refs.loads)
+      .map(|span| TextEdit::new(line_index.range(*span), new_name.to_string()))
+      .collect::<Vec<_>>();
+
+    if !edits.is_empty() {
+      changes.insert(refs.uri, edits);
+    }
+  }
+
+  Ok(WorkspaceEdit::new(changes))
+}
+
+/// Whether `name` is an identifier that is not a keyword.
+fn is_valid_name(name: &str) -> bool {
+  name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+    && name.chars().all(is_ident_char)
+    && !ITEM_KEYWORDS.contains(&name)
+    && !BODY_KEYWORDS.contains(&name)
+}
+
+/// Refuses names declared by the core packs — those files
+/// are not the user's to edit.
+pub fn check_renamable(target: &Target) -> Result<(), String> {
+  if default_core_search_paths()
+    .iter()
+    .any(|root| target.path.starts_with(canonical(root)))
+  {
+    return Err(format!("`{}` is declared by a core pack", target.name));
+  }
+
+  Ok(())
+}
+
+/// The occurrences of `target` per indexed file.
+fn collect(index: &SymbolIndex, target: &Target) -> Vec<FileRefs> {
+  index
+    .files
+    .iter()
+    .filter_map(|(uri, state)| {
+      let (spans, loads) = if state.path == target.path {
+        (own_spans(state, target), Vec::new())
+      } else if target.is_fun {
+        let loads =
+          load_spans(&state.source, &module_name(target), &target.name);
+        let mut spans = imported_spans(state, target);
+
+        // A `load` list names the function with an identifier
+        // of its own — edited once, as a load.
+        spans.retain(|span| !loads.contains(span));
+
+        (spans, loads)
+      } else {
+        return None;
+      };
+
+      if spans.is_empty() && loads.is_empty() {
+        return None;
+      }
+
+      let interner = &state.session.interner;
+
+      Some(FileRefs {
+        uri: uri.clone(),
+        source: state.source.clone(),
+        spans,
+        loads,
+        funs: state
+          .funs
+          .iter()
+          .map(|fun| interner.get(fun.name).to_string())
+          .collect(),
+      })
+    })
+    .collect()
+}
+
+/// The declaration and its uses in the file that declares
+/// `target`, read off the use-def map.
+pub(crate) fn own_spans(state: &FileState, target: &Target) -> Vec<Span> {
+  let declaration = name_span(&state.source, target.span, &target.name);
+  let mut uses = state
+    .use_def_map
+    .iter()
+    .filter(|(_, def)| **def == target.span)
+    .filter_map(|(use_span, _)| {
+      name_span(&state.source, *use_span, &target.name)
+    })
+    .collect::<Vec<_>>();
+
+  uses.sort_by_key(|span| span.start);
+
+  declaration.into_iter().chain(uses).collect()
+}
+
+/// The uses of a function `target` from a file that loads its
+/// module. The use-def map does not cross files, so uses are
+/// matched by name against the function's owning pack.
+fn imported_spans(state: &FileState, target: &Target) -> Vec<Span> {
+  let interner = &state.session.interner;
+  let resolves = state.funs.iter().any(|fun| {
+    interner.get(fun.name) == target.name
+      && fun
+        .owning_pack
+        .and_then(|pack| state.pack_paths.get(&pack))
+        .is_some_and(|path| canonical(path) == target.path)
+  });
+
+  if !resolves {
+    return Vec::new();
+  }
+
+  state
+    .tree
+    .nodes
+    .iter()
+    .enumerate()
+    .filter(|(idx, node)| {
+      node.token == Token::Ident
+        && !state.use_def_map.contains_key(&state.tree.spans[*idx])
+        && matches!(
+          state.tree.value(*idx as u32),
+          Some(NodeValue::Symbol(symbol)) if interner.get(symbol) == target.name
+        )
+    })
+    .map(|(idx, _)| state.tree.spans[idx])
+    .collect()
+}
+
+/// The spans of `name` imported from module `pack` by the
+/// `load` statements of `source` — `load pack::name;` and the
+/// selective `load pack::(a, name);` form.
+fn load_spans(source: &str, pack: &str, name: &str) -> Vec<Span> {
+  let mut spans = Vec::new();
+  let mut from = 0;
+
+  while let Some(idx) = source[from..].find("load ") {
+    let start = from + idx;
+    let end = source[start..]
+      .find(';')
+      .map_or(source.len(), |idx| start + idx);
+
+    from = end;
+
+    if !source[..start]
+      .trim_end_matches([' ', '\t'])
+      .ends_with('\n')
+      && start != 0
+    {
+      continue;
+    }
+
+    let statement = &source[start..end];
+
+    let Some(items) = words(statement, pack)
+      .next()
+      .and_then(|at| statement[at + pack.len()..].strip_prefix("::"))
+    else {
+      continue;
+    };
+
+    let offset = end - items.len();
+
+    spans.extend(words(items, name).map(|at| to_span(offset + at, name)));
+  }
+
+  spans
+}
+
+/// The module name a file is loaded by — its file stem.
+fn module_name(target: &Target) -> String {
+  target
+    .path
+    .file_stem()
+    .map(|stem| stem.to_string_lossy().into_owned())
+    .unwrap_or_default()
+}
+
+/// Whether `span` in `uri` is the declaration of `target`.
+fn is_declaration(
+  index: &SymbolIndex,
+  target: &Target,
+  uri: &Url,
+  span: Span,
+) -> bool {
+  index.get(uri).is_some_and(|state| {
+    state.path == target.path
+      && name_span(&state.source, target.span, &target.name) == Some(span)
+  })
+}
+
+/// The exact span of `name` in the node or definition at
+/// `span` — a definition span may start at its keyword, so
+/// the search runs to the end of the line.
+pub(crate) fn name_span(source: &str, span: Span, name: &str) -> Option<Span> {
+  let start = span.start as usize;
+
+  if span == Span::ZERO || start > source.len() {
+    return None;
+  }
+
+  let line_end = source[start..]
+    .find('\n')
+    .map_or(source.len(), |idx| start + idx);
+  let end = line_end.max(span.end() as usize).min(source.len());
+
+  words(source.get(start..end)?, name)
+    .next()
+    .map(|at| to_span(start + at, name))
+}
+
+/// Byte offsets of `word` in `text` as a whole identifier.
+fn words<'a>(text: &'a str, word: &'a str) -> impl Iterator<Item = usize> + 'a {
+  text
+    .match_indices(word)
+    .map(|(at, _)| at)
+    .filter(move |at| {
+      let before = text[..*at].chars().next_back();
+      let after = text[at + word.len()..].chars().next();
+
+      !before.is_some_and(is_ident_char) && !after.is_some_and(is_ident_char)
+    })
+}
+
+fn to_span(start: usize, name: &str) -> Span {
+  Span::new(start as u32, name.len() as u16)
+}
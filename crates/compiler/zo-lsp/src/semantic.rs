@@ -0,0 +1,538 @@
+use crate::index::FileState;
+
+use zo_span::Span;
+use zo_token::Token;
+use zo_value::{FunctionKind, Pubness};
+
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use tower_lsp::lsp_types::{
+  SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend,
+};
+
+use std::collections::BTreeMap;
+
+/// Token types, in legend order.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+  Namespace,
+  Type,
+  Struct,
+  Enum,
+  Interface,
+  TypeParameter,
+  Parameter,
+  Variable,
+  Property,
+  EnumMember,
+  Function,
+  Method,
+  Keyword,
+  String,
+  Number,
+  Event,
+  Tag,
+  StyleProperty,
+}
+
+const TOKEN_TYPES: &[SemanticTokenType] = &[
+  SemanticTokenType::NAMESPACE,
+  SemanticTokenType::TYPE,
+  SemanticTokenType::STRUCT,
+  SemanticTokenType::ENUM,
+  SemanticTokenType::INTERFACE,
+  SemanticTokenType::TYPE_PARAMETER,
+  SemanticTokenType::PARAMETER,
+  SemanticTokenType::VARIABLE,
+  SemanticTokenType::PROPERTY,
+  SemanticTokenType::ENUM_MEMBER,
+  SemanticTokenType::FUNCTION,
+  SemanticTokenType::METHOD,
+  SemanticTokenType::KEYWORD,
+  SemanticTokenType::STRING,
+  SemanticTokenType::NUMBER,
+  SemanticTokenType::EVENT,
+  SemanticTokenType::new("tag"),
+  SemanticTokenType::new("styleProperty"),
+];
+
+const DECLARATION: u32 = 1 << 0;
+const MUTABLE: u32 = 1 << 1;
+const PUBLIC: u32 = 1 << 2;
+const FFI: u32 = 1 << 3;
+const OWNED: u32 = 1 << 4;
+const DEPRECATED: u32 = 1 << 5;
+
+/// Token modifiers, one per bit above.
+const TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
+  SemanticTokenModifier::DECLARATION,
+  SemanticTokenModifier::new("mutable"),
+  SemanticTokenModifier::new("public"),
+  SemanticTokenModifier::new("ffi"),
+  SemanticTokenModifier::new("owned"),
+  SemanticTokenModifier::DEPRECATED,
+];
+
+/// The legend every response is encoded against.
+pub fn legend() -> SemanticTokensLegend {
+  SemanticTokensLegend {
+    token_types: TOKEN_TYPES.to_vec(),
+    token_modifiers: TOKEN_MODIFIERS.to_vec(),
+  }
+}
+
+/// What the declaration pass learned about the file's names.
+#[derive(Default)]
+struct Names<'a> {
+  packs: HashSet<&'a str>,
+  structs: HashSet<&'a str>,
+  enums: HashSet<&'a str>,
+  abstracts: HashSet<&'a str>,
+  /// Functions and methods by name, with their modifiers.
+  funs: HashMap<&'a str, u32>,
+  /// Bindings by the offset of their name: name, kind and
+  /// modifiers.
+  bindings: BTreeMap<u32, (&'a str, Kind, u32)>,
+}
+
+/// A block the walk is inside of.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Block {
+  Struct,
+  Enum,
+  /// An `apply` or `abstract` body — its functions are
+  /// methods.
+  Methods,
+  Style,
+  Other,
+}
+
+/// Semantic tokens for the file, limited to the byte range
+/// `within` when given — the `range` request.
+///
+/// Runs over the token buffer in source order: a first pass
+/// collects declarations, a second classifies every token
+/// with them, the use-def map and the analyzed functions.
+pub fn tokens(
+  state: &FileState,
+  within: Option<(u32, u32)>,
+) -> Vec<SemanticToken> {
+  let source = state.source.as_str();
+  let names = declarations(state);
+  let buffer = &state.tokens;
+  let kinds = &buffer.kinds;
+
+  let span = |idx: usize| Span::new(buffer.starts[idx], buffer.lengths[idx]);
+  let text = |idx: usize| {
+    let span = span(idx);
+
+    source
+      .get(span.start as usize..span.end() as usize)
+      .unwrap_or_default()
+  };
+  let kind_at = |idx: usize| kinds.get(idx).copied().unwrap_or(Token::Eof);
+  let prev = |idx: usize, back: usize| {
+    idx.checked_sub(back).map_or(Token::Eof, |at| kinds[at])
+  };
+
+  let mut classified: Vec<(Span, Kind, u32)> = Vec::new();
+  let mut blocks: Vec<(Block, usize)> = Vec::new();
+  let mut pending = None;
+  let mut depth = 0;
+
+  for (idx, &token) in kinds.iter().enumerate() {
+    let block = blocks
+      .last()
+      .filter(|(_, at)| *at == depth)
+      .map(|(block, _)| *block);
+
+    let class = match token {
+      Token::LBrace => {
+        depth += 1;
+        blocks.push((pending.take().unwrap_or(Block::Other), depth));
+
+        None
+      }
+      Token::RBrace => {
+        if blocks.last().is_some_and(|(_, at)| *at == depth) {
+          blocks.pop();
+        }
+
+        depth = depth.saturating_sub(1);
+
+        None
+      }
+      Token::Struct => {
+        pending = Some(Block::Struct);
+
+        Some((Kind::Keyword, 0))
+      }
+      Token::Enum => {
+        pending = Some(Block::Enum);
+
+        Some((Kind::Keyword, 0))
+      }
+      Token::Apply | Token::Abstract => {
+        pending = Some(Block::Methods);
+
+        Some((Kind::Keyword, 0))
+      }
+      Token::Dollar if kind_at(idx + 1) == Token::Colon => {
+        pending = Some(Block::Style);
+
+        None
+      }
+      Token::Dollar if kind_at(idx + 1) == Token::Ident => {
+        Some((Kind::TypeParameter, 0))
+      }
+      Token::At if kind_at(idx + 1) == Token::Ident => Some((Kind::Event, 0)),
+      Token::SelfLower => match prev(idx, 1) {
+        Token::Own => Some((Kind::Keyword, OWNED)),
+        Token::Mut => Some((Kind::Keyword, MUTABLE)),
+        _ => Some((Kind::Keyword, 0)),
+      },
+      Token::Int | Token::Float => Some((Kind::Number, 0)),
+      Token::String
+      | Token::InterpString
+      | Token::RawString
+      | Token::Char
+      | Token::Bytes
+      | Token::RegexLit => Some((Kind::String, 0)),
+      Token::Ident => ident(
+        &names,
+        state,
+        IdentContext {
+          span: span(idx),
+          text: text(idx),
+          prev: prev(idx, 1),
+          prev2: prev(idx, 2),
+          prev2_text: idx.checked_sub(2).map(text).unwrap_or_default(),
+          next: kind_at(idx + 1),
+          next2: kind_at(idx + 2),
+          block,
+        },
+      ),
+      token if token.is_ty() => Some((Kind::Type, 0)),
+      token if token.is_reserved_word() => Some((Kind::Keyword, 0)),
+      _ => None,
+    };
+
+    if let Some((kind, modifiers)) = class {
+      classified.push((span(idx), kind, modifiers));
+    }
+  }
+
+  encode(state, &classified, within)
+}
+
+/// The neighbourhood of an identifier token.
+struct IdentContext<'a> {
+  span: Span,
+  text: &'a str,
+  prev: Token,
+  prev2: Token,
+  prev2_text: &'a str,
+  next: Token,
+  next2: Token,
+  block: Option<Block>,
+}
+
+/// Classifies one identifier.
+fn ident(
+  names: &Names,
+  state: &FileState,
+  cx: IdentContext,
+) -> Option<(Kind, u32)> {
+  let fun_modifiers = |name: &str| names.funs.get(name).copied();
+
+  match cx.prev {
+    Token::Dollar => return Some((Kind::TypeParameter, 0)),
+    Token::At => return Some((Kind::Event, 0)),
+    Token::LAngle => return Some((Kind::Tag, 0)),
+    Token::Slash | Token::Slash2 if cx.prev2 == Token::LAngle => {
+      return Some((Kind::Tag, 0));
+    }
+    Token::Pack => return Some((Kind::Namespace, DECLARATION)),
+    Token::Struct => return Some((Kind::Struct, DECLARATION)),
+    Token::Enum => return Some((Kind::Enum, DECLARATION)),
+    Token::Abstract => return Some((Kind::Interface, DECLARATION)),
+    Token::Fun | Token::Ffi => {
+      let kind = match cx.block {
+        Some(Block::Methods) => Kind::Method,
+        _ => Kind::Function,
+      };
+      let modifiers = fun_modifiers(cx.text).unwrap_or_default();
+
+      return Some((kind, DECLARATION | modifiers));
+    }
+    Token::Dot => {
+      return Some(match cx.next {
+        Token::LParen => {
+          (Kind::Method, fun_modifiers(cx.text).unwrap_or_default())
+        }
+        _ => (Kind::Property, 0),
+      });
+    }
+    _ => {}
+  }
+
+  if cx.next == Token::Colon && cx.next2 == Token::StyleValue {
+    return Some((Kind::StyleProperty, 0));
+  }
+
+  if cx.block == Some(Block::Style) && cx.next == Token::LBrace {
+    return Some((Kind::Tag, 0));
+  }
+
+  if matches!(cx.prev, Token::LBrace | Token::Comma) {
+    match cx.block {
+      Some(Block::Struct) => return Some((Kind::Property, DECLARATION)),
+      Some(Block::Enum) => return Some((Kind::EnumMember, DECLARATION)),
+      _ => {}
+    }
+  }
+
+  if let Some(&(_, kind, modifiers)) = names.bindings.get(&cx.span.start) {
+    return Some((kind, DECLARATION | modifiers));
+  }
+
+  if let Some(def) = state.use_def_map.get(&cx.span) {
+    // The binding the definition span names — the first one
+    // of that name at or after it.
+    if let Some((_, &(_, kind, modifiers))) = names
+      .bindings
+      .range(def.start..)
+      .find(|(_, (name, ..))| *name == cx.text)
+    {
+      return Some((kind, modifiers));
+    }
+  }
+
+  if cx.prev == Token::ColonColon && names.enums.contains(cx.prev2_text) {
+    return Some((Kind::EnumMember, 0));
+  }
+
+  if cx.next == Token::ColonColon
+    && (names.packs.contains(cx.text) || is_pack(state, cx.text))
+  {
+    return Some((Kind::Namespace, 0));
+  }
+
+  if let Some(modifiers) = fun_modifiers(cx.text) {
+    let kind = match cx.prev {
+      Token::ColonColon
+        if !names.packs.contains(cx.prev2_text)
+          && !is_pack(state, cx.prev2_text) =>
+      {
+        Kind::Method
+      }
+      _ => Kind::Function,
+    };
+
+    return Some((kind, modifiers));
+  }
+
+  if names.structs.contains(cx.text) {
+    return Some((Kind::Struct, 0));
+  }
+
+  if names.enums.contains(cx.text) {
+    return Some((Kind::Enum, 0));
+  }
+
+  if names.abstracts.contains(cx.text) {
+    return Some((Kind::Interface, 0));
+  }
+
+  // Imported types are not in the declaration pass.
+  cx.text
+    .starts_with(|c: char| c.is_ascii_uppercase())
+    .then_some((Kind::Type, 0))
+}
+
+/// The declaration pass: item names with their modifiers and
+/// every binding with its mutability.
+fn declarations(state: &FileState) -> Names<'_> {
+  let source = state.source.as_str();
+  let buffer = &state.tokens;
+  let kinds = &buffer.kinds;
+  let text = |idx: usize| {
+    let start = buffer.starts[idx] as usize;
+    let end = start + buffer.lengths[idx] as usize;
+
+    source.get(start..end).unwrap_or_default()
+  };
+
+  let mut names = Names::default();
+
+  for fun in &state.funs {
+    let name = state.session.interner.get(fun.name);
+    // Methods are mangled `Type::method`.
+    let name = name.rsplit("::").next().unwrap_or(name);
+    let mut modifiers = 0;
+
+    if fun.pubness == Pubness::Yes {
+      modifiers |= PUBLIC;
+    }
+
+    if fun.owning_pack.is_some() && fun.kind == FunctionKind::Intrinsic {
+      modifiers |= FFI;
+    }
+
+    *names.funs.entry(name).or_default() |= modifiers;
+  }
+
+  let mut deprecated = false;
+  let mut header = false;
+
+  for (idx, &token) in kinds.iter().enumerate() {
+    let next = kinds.get(idx + 1).copied();
+    let before = |back: usize| idx.checked_sub(back).map(|at| kinds[at]);
+
+    match token {
+      Token::Attribute
+        if next == Some(Token::Ident) && text(idx + 1) == "deprecated" =>
+      {
+        deprecated = true;
+      }
+      Token::Pack
+      | Token::Struct
+      | Token::Enum
+      | Token::Abstract
+      | Token::Fun
+      | Token::Ffi
+        if next == Some(Token::Ident) =>
+      {
+        let name = text(idx + 1);
+
+        match token {
+          Token::Pack => {
+            names.packs.insert(name);
+          }
+          Token::Struct => {
+            names.structs.insert(name);
+          }
+          Token::Enum => {
+            names.enums.insert(name);
+          }
+          Token::Abstract => {
+            names.abstracts.insert(name);
+          }
+          _ => {
+            let modifiers = names.funs.entry(name).or_default();
+            let public = before(1) == Some(Token::Pub)
+              || before(1) == Some(Token::Test)
+                && before(2) == Some(Token::Pub);
+
+            if token == Token::Ffi {
+              *modifiers |= FFI;
+            }
+
+            if public {
+              *modifiers |= PUBLIC;
+            }
+
+            if deprecated {
+              *modifiers |= DEPRECATED;
+            }
+          }
+        }
+
+        header = matches!(token, Token::Fun | Token::Ffi);
+        deprecated = false;
+      }
+      Token::Fn => header = true,
+      Token::LBrace | Token::Semicolon | Token::FatArrow => header = false,
+      Token::Imu | Token::Mut | Token::For if next == Some(Token::Ident) => {
+        let modifiers = if token == Token::Mut { MUTABLE } else { 0 };
+
+        names.bindings.insert(
+          buffer.starts[idx + 1],
+          (text(idx + 1), Kind::Variable, modifiers),
+        );
+      }
+      Token::Ident
+        if header
+          && next == Some(Token::Colon)
+          && before(1) != Some(Token::Dot) =>
+      {
+        let modifiers = match before(1) {
+          Some(Token::Mut) => MUTABLE,
+          _ => 0,
+        };
+
+        names
+          .bindings
+          .insert(buffer.starts[idx], (text(idx), Kind::Parameter, modifiers));
+      }
+      Token::Load => {
+        // `load a::b::c;` — every segment before the last is
+        // a pack.
+        let mut at = idx + 1;
+
+        while kinds.get(at) == Some(&Token::Ident)
+          && kinds.get(at + 1) == Some(&Token::ColonColon)
+        {
+          names.packs.insert(text(at));
+          at += 2;
+        }
+      }
+      _ => {}
+    }
+  }
+
+  names
+}
+
+/// Whether `name` is a pack the analysis resolved.
+fn is_pack(state: &FileState, name: &str) -> bool {
+  state
+    .session
+    .interner
+    .symbol(name)
+    .is_some_and(|symbol| state.pack_paths.contains_key(&symbol))
+}
+
+/// Delta-encodes the classified tokens, in UTF-16 columns.
+/// Tokens spanning lines are dropped — clients without
+/// multiline support reject them.
+fn encode(
+  state: &FileState,
+  classified: &[(Span, Kind, u32)],
+  within: Option<(u32, u32)>,
+) -> Vec<SemanticToken> {
+  let mut tokens = Vec::with_capacity(classified.len());
+  let mut last_line = 0;
+  let mut last_start = 0;
+
+  for &(span, kind, modifiers) in classified {
+    if within.is_some_and(|(start, end)| span.end() < start || span.start > end)
+    {
+      continue;
+    }
+
+    let range = state.line_index.range(span);
+
+    if range.start.line != range.end.line || span.len == 0 {
+      continue;
+    }
+
+    let delta_line = range.start.line - last_line;
+    let delta_start = match delta_line {
+      0 => range.start.character - last_start,
+      _ => range.start.character,
+    };
+
+    tokens.push(SemanticToken {
+      delta_line,
+      delta_start,
+      length: range.end.character - range.start.character,
+      token_type: kind as u32,
+      token_modifiers_bitset: modifiers,
+    });
+
+    last_line = range.start.line;
+    last_start = range.start.character;
+  }
+
+  tokens
+}
@@ -1,36 +1,304 @@
-use crate::index::{FileState, SymbolIndex};
-use crate::position::LineIndex;
+use crate::actions;
+use crate::completion;
+use crate::hints;
+use crate::hover;
+use crate::index::{self, FileState, SymbolIndex};
+use crate::position::{self, LineIndex};
+use crate::references::{self, Target};
+use crate::semantic;
+use crate::signature;
+use crate::symbols;
 
 use zo_interner::Symbol;
 use zo_span::Span;
 use zo_token::Token;
 use zo_tree::NodeValue;
 
-use tower_lsp::jsonrpc::Result;
+use rustc_hash::FxHashMap as HashMap;
+use tower_lsp::jsonrpc::{Error, Result};
 use tower_lsp::lsp_types::{
-  DidChangeTextDocumentParams, DidOpenTextDocumentParams, GotoDefinitionParams,
-  GotoDefinitionResponse, InitializeParams, InitializeResult,
-  InitializedParams, Location, ServerCapabilities, ServerInfo,
-  TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+  CodeActionParams, CodeActionProviderCapability, CodeActionResponse,
+  CompletionOptions, CompletionParams, CompletionResponse,
+  DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+  DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+  DidOpenTextDocumentParams, DidSaveTextDocumentParams, DocumentSymbolParams,
+  DocumentSymbolResponse, FileChangeType, FileSystemWatcher, GlobPattern,
+  GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+  HoverParams, HoverProviderCapability, InitializeParams, InitializeResult,
+  InitializedParams, InlayHint, InlayHintParams, Location, MarkupContent,
+  MarkupKind, OneOf, Position, PositionEncodingKind, PrepareRenameResponse,
+  ReferenceParams, Registration, RenameOptions, RenameParams, SemanticTokens,
+  SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensParams,
+  SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult,
+  SemanticTokensServerCapabilities, ServerCapabilities, ServerInfo,
+  SignatureHelp, SignatureHelpOptions, SignatureHelpParams, SymbolInformation,
+  TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind,
+  TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url, WorkspaceEdit,
+  WorkspaceSymbolParams,
 };
 use tower_lsp::{Client, LanguageServer};
 
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long an edit must stay the latest before it is
+/// analyzed — fast typing coalesces into one analysis.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// The latest edit of an open document.
+#[derive(Clone, Default)]
+struct Generation {
+  /// Edits so far.
+  count: u64,
+  /// Raised once a newer edit supersedes this one — its
+  /// analysis stops at the next pack.
+  cancel: Arc<AtomicBool>,
+}
+
+/// Raises its flag when dropped. A request the client cancels
+/// drops its future mid-`await`, so the compile it waited on
+/// stops too.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+  fn drop(&mut self) {
+    self.0.store(true, Ordering::Relaxed);
+  }
+}
 
 pub struct ZoLanguageServer {
   client: Client,
-  index: Mutex<SymbolIndex>,
+  index: Arc<Mutex<SymbolIndex>>,
+  /// Latest edit per open document. A debounced analysis
+  /// only runs — and keeps running — if no newer edit bumped
+  /// it in the meantime.
+  generations: Arc<Mutex<HashMap<Url, Generation>>>,
+  /// Latest text per open document. Completion reads the
+  /// context from it — the analysis may lag a few keystrokes.
+  documents: Mutex<HashMap<Url, String>>,
+  /// Workspace root, from the `initialize` request.
+  root: Mutex<Option<PathBuf>>,
+  /// Flattened outline of every `.zo` file under the root,
+  /// filled in the background after `initialized`. Open files
+  /// are answered from the index instead.
+  workspace: Arc<Mutex<HashMap<Url, Vec<SymbolInformation>>>>,
 }
 
 impl ZoLanguageServer {
   pub fn new(client: Client) -> Self {
     Self {
       client,
-      index: Mutex::new(SymbolIndex::new()),
+      index: Arc::new(Mutex::new(SymbolIndex::new())),
+      generations: Arc::new(Mutex::new(HashMap::default())),
+      documents: Mutex::new(HashMap::default()),
+      root: Mutex::new(None),
+      workspace: Arc::new(Mutex::new(HashMap::default())),
     }
   }
 
+  /// Recompile `source` off the async runtime and publish the
+  /// diagnostics it produced. The analysis is abandoned when an
+  /// edit bumps the document past `generation` — before it
+  /// starts or while it runs — so only the latest text ever
+  /// lands in the index.
+  async fn analyze(
+    client: &Client,
+    index: &Arc<Mutex<SymbolIndex>>,
+    generations: &Arc<Mutex<HashMap<Url, Generation>>>,
+    uri: Url,
+    source: String,
+    version: Option<i32>,
+    generation: Generation,
+  ) {
+    let path = uri
+      .to_file_path()
+      .unwrap_or_else(|_| PathBuf::from(uri.path()));
+
+    let index = Arc::clone(index);
+    let generations = Arc::clone(generations);
+    let target = uri.clone();
+
+    let diagnostics = tokio::task::spawn_blocking(move || {
+      let is_latest = || {
+        generations.lock().ok().and_then(|generations| {
+          generations.get(&target).map(|latest| latest.count)
+        }) == Some(generation.count)
+      };
+
+      if !is_latest() {
+        return None;
+      }
+
+      // Compiled without the index locked — requests keep
+      // answering from the previous analysis meanwhile.
+      let cache = Arc::clone(&index.lock().ok()?.cache);
+      let state =
+        index::analyze(&cache, &target, &source, &path, &generation.cancel)?;
+      let diagnostics = state.diagnostics.clone();
+
+      let mut idx = index.lock().ok()?;
+
+      if !is_latest() {
+        return None;
+      }
+
+      idx.insert(target, state);
+
+      Some(diagnostics)
+    })
+    .await
+    .ok()
+    .flatten();
+
+    if let Some(diagnostics) = diagnostics {
+      client.publish_diagnostics(uri, diagnostics, version).await;
+    }
+  }
+
+  /// Re-analyzes the open documents `uris` from their latest
+  /// text — a file they read changed on disk.
+  fn reanalyze(&self, uris: Vec<Url>) {
+    for uri in uris {
+      let Some(source) = self
+        .documents
+        .lock()
+        .ok()
+        .and_then(|documents| documents.get(&uri).cloned())
+      else {
+        continue;
+      };
+
+      let generation = self.bump_generation(&uri);
+      let client = self.client.clone();
+      let index = Arc::clone(&self.index);
+      let generations = Arc::clone(&self.generations);
+
+      tokio::spawn(async move {
+        Self::analyze(
+          &client,
+          &index,
+          &generations,
+          uri,
+          source,
+          None,
+          generation,
+        )
+        .await;
+      });
+    }
+  }
+
+  /// Brings what was derived from the file at `path` up to
+  /// date after it changed on disk — the cached packs that
+  /// read it, its workspace outline and the open documents
+  /// that load it.
+  fn file_changed(&self, path: &Path, change: FileChangeType) {
+    let (cache, dependents) = match self.index.lock() {
+      Ok(idx) => (Arc::clone(&idx.cache), idx.dependents(path)),
+      Err(_) => return,
+    };
+
+    let dropped = if change == FileChangeType::CREATED {
+      cache.created(path)
+    } else {
+      cache.invalidate(path)
+    };
+
+    log::info!(
+      "file changed: {} dropped {dropped} cached module sets",
+      path.display(),
+    );
+
+    if let (Ok(uri), Ok(mut workspace)) =
+      (Url::from_file_path(path), self.workspace.lock())
+    {
+      match std::fs::read_to_string(path) {
+        Ok(source) => {
+          let flat = symbols::flatten(&uri, &symbols::outline(&source));
+
+          workspace.insert(uri, flat);
+        }
+        Err(_) => {
+          workspace.remove(&uri);
+        }
+      }
+    }
+
+    self.reanalyze(dependents);
+  }
+
+  /// Outlines every `.zo` file under `root` off the async
+  /// runtime.
+  fn index_workspace(&self, root: PathBuf) {
+    let workspace = Arc::clone(&self.workspace);
+
+    tokio::task::spawn_blocking(move || {
+      let files = symbols::zo_files(&root);
+
+      log::info!(
+        "workspace: indexing {} files under {}",
+        files.len(),
+        root.display(),
+      );
+
+      for path in files {
+        let (Ok(source), Ok(uri)) =
+          (std::fs::read_to_string(&path), Url::from_file_path(&path))
+        else {
+          continue;
+        };
+
+        let flat = symbols::flatten(&uri, &symbols::outline(&source));
+
+        if let Ok(mut workspace) = workspace.lock() {
+          workspace.insert(uri, flat);
+        }
+      }
+    });
+  }
+
+  /// Records the latest text of `uri`.
+  fn set_document(&self, uri: &Url, text: &str) {
+    if let Ok(mut documents) = self.documents.lock() {
+      documents.insert(uri.clone(), text.to_string());
+    }
+
+    self.set_buffer(uri, text);
+  }
+
+  /// Hands the unsaved text of `uri` to the module cache, so
+  /// the analyses that load it read the buffer, not the disk.
+  fn set_buffer(&self, uri: &Url, text: &str) {
+    let Ok(path) = uri.to_file_path() else {
+      return;
+    };
+
+    if let Ok(idx) = self.index.lock() {
+      idx.cache.open(&path, text);
+    }
+  }
+
+  /// Bumps and returns the edit generation of `uri`,
+  /// cancelling the analysis of the one before.
+  fn bump_generation(&self, uri: &Url) -> Generation {
+    let Ok(mut generations) = self.generations.lock() else {
+      return Generation::default();
+    };
+
+    let generation = generations.entry(uri.clone()).or_default();
+
+    generation.cancel.store(true, Ordering::Relaxed);
+
+    *generation = Generation {
+      count: generation.count + 1,
+      cancel: Arc::default(),
+    };
+
+    generation.clone()
+  }
+
   /// Find the smallest span in the tree containing `offset`.
   fn find_node_at_offset(
     state: &crate::index::FileState,
@@ -49,20 +317,128 @@ impl ZoLanguageServer {
 
     best
   }
+
+  /// The definition named by the identifier at `pos`, with
+  /// the file declaring it indexed.
+  async fn target_at(&self, uri: &Url, pos: Position) -> Option<Target> {
+    let target = {
+      let idx = self.index.lock().ok()?;
+      let state = idx.get(uri)?;
+      let offset = state.line_index.offset(pos.line, pos.character);
+      let node_idx = Self::find_node_at_offset(state, offset)?;
+
+      references::target(state, node_idx)?
+    };
+
+    self.ensure(&target.path).await;
+
+    Some(target)
+  }
+
+  /// Indexes the file at `path` from disk unless some document
+  /// already covers it — a module the open files `load`. The
+  /// file compiles off the async runtime, without the index
+  /// locked, and stops early if the request is cancelled.
+  async fn ensure(&self, path: &Path) {
+    let index = Arc::clone(&self.index);
+    let path = index::canonical(path);
+    let cancel = CancelOnDrop(Arc::default());
+    let flag = Arc::clone(&cancel.0);
+
+    let indexed = tokio::task::spawn_blocking(move || {
+      let cache = {
+        let idx = index.lock().ok()?;
+
+        if idx.covers(&path) {
+          return None;
+        }
+
+        Arc::clone(&idx.cache)
+      };
+
+      let uri = Url::from_file_path(&path).ok()?;
+      let source = std::fs::read_to_string(&path).ok()?;
+      let state = index::analyze(&cache, &uri, &source, &path, &flag)?;
+      let mut idx = index.lock().ok()?;
+
+      // A document opened meanwhile is more recent.
+      if !idx.covers(&path) {
+        idx.insert(uri, state);
+      }
+
+      Some(())
+    })
+    .await;
+
+    if let Err(error) = indexed {
+      log::warn!("indexing failed: {error}");
+    }
+
+    drop(cancel);
+  }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for ZoLanguageServer {
   async fn initialize(
     &self,
-    _params: InitializeParams,
+    params: InitializeParams,
   ) -> Result<InitializeResult> {
+    #[allow(deprecated)]
+    let root = params
+      .workspace_folders
+      .and_then(|folders| folders.into_iter().next())
+      .map(|folder| folder.uri)
+      .or(params.root_uri)
+      .and_then(|uri| uri.to_file_path().ok());
+
+    if let Ok(mut slot) = self.root.lock() {
+      *slot = root;
+    }
+
     Ok(InitializeResult {
       capabilities: ServerCapabilities {
-        text_document_sync: Some(TextDocumentSyncCapability::Kind(
-          TextDocumentSyncKind::FULL,
+        // Columns are converted per line in `LineIndex`.
+        position_encoding: Some(PositionEncodingKind::UTF16),
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+          TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::INCREMENTAL),
+            save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+            ..Default::default()
+          },
         )),
-        definition_provider: Some(tower_lsp::lsp_types::OneOf::Left(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        rename_provider: Some(OneOf::Right(RenameOptions {
+          prepare_provider: Some(true),
+          work_done_progress_options: Default::default(),
+        })),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        semantic_tokens_provider: Some(
+          SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+              legend: semantic::legend(),
+              range: Some(true),
+              full: Some(SemanticTokensFullOptions::Bool(true)),
+              ..Default::default()
+            },
+          ),
+        ),
+        inlay_hint_provider: Some(OneOf::Left(true)),
+        signature_help_provider: Some(SignatureHelpOptions {
+          trigger_characters: Some(["(", ","].map(String::from).to_vec()),
+          ..Default::default()
+        }),
+        completion_provider: Some(CompletionOptions {
+          trigger_characters: Some(
+            [".", ":", "<", "@"].map(String::from).to_vec(),
+          ),
+          ..Default::default()
+        }),
         ..Default::default()
       },
       server_info: Some(ServerInfo {
@@ -80,6 +456,32 @@ impl LanguageServer for ZoLanguageServer {
         "zo-lsp initialized",
       )
       .await;
+
+    // Files edited outside the editor — a `git checkout`, a
+    // generator — still reach the module cache.
+    let watchers = DidChangeWatchedFilesRegistrationOptions {
+      watchers: vec![FileSystemWatcher {
+        glob_pattern: GlobPattern::String("**/*.zo".into()),
+        kind: None,
+      }],
+    };
+    let registration = Registration {
+      id: "zo-watched-files".into(),
+      method: "workspace/didChangeWatchedFiles".into(),
+      register_options: serde_json::to_value(watchers).ok(),
+    };
+
+    if let Err(error) =
+      self.client.register_capability(vec![registration]).await
+    {
+      log::warn!("file watching unavailable: {error}");
+    }
+
+    let root = self.root.lock().ok().and_then(|root| root.clone());
+
+    if let Some(root) = root {
+      self.index_workspace(root);
+    }
   }
 
   async fn shutdown(&self) -> Result<()> {
@@ -88,31 +490,110 @@ impl LanguageServer for ZoLanguageServer {
 
   async fn did_open(&self, params: DidOpenTextDocumentParams) {
     let uri = params.text_document.uri;
-    let source = params.text_document.text;
 
-    let path = uri
-      .to_file_path()
-      .unwrap_or_else(|_| PathBuf::from(uri.path()));
+    let generation = self.bump_generation(&uri);
 
-    if let Ok(mut idx) = self.index.lock() {
-      idx.update(&uri, &source, &path);
-    }
+    self.set_document(&uri, &params.text_document.text);
+
+    Self::analyze(
+      &self.client,
+      &self.index,
+      &self.generations,
+      uri,
+      params.text_document.text,
+      Some(params.text_document.version),
+      generation,
+    )
+    .await;
   }
 
   async fn did_change(&self, params: DidChangeTextDocumentParams) {
     let uri = params.text_document.uri;
+    let version = params.text_document.version;
+
+    // Edits arrive in order, each against the text the one
+    // before it left.
+    let text = {
+      let Ok(mut documents) = self.documents.lock() else {
+        return;
+      };
+
+      let text = documents.entry(uri.clone()).or_default();
 
-    let Some(change) = params.content_changes.into_iter().last() else {
+      for change in params.content_changes {
+        position::apply_change(text, change.range, &change.text);
+      }
+
+      text.clone()
+    };
+
+    self.set_buffer(&uri, &text);
+
+    let generation = self.bump_generation(&uri);
+    let client = self.client.clone();
+    let index = Arc::clone(&self.index);
+    let generations = Arc::clone(&self.generations);
+
+    tokio::spawn(async move {
+      tokio::time::sleep(DEBOUNCE).await;
+
+      Self::analyze(
+        &client,
+        &index,
+        &generations,
+        uri,
+        text,
+        Some(version),
+        generation,
+      )
+      .await;
+    });
+  }
+
+  async fn did_save(&self, params: DidSaveTextDocumentParams) {
+    let Ok(path) = params.text_document.uri.to_file_path() else {
       return;
     };
 
-    let path = uri
-      .to_file_path()
-      .unwrap_or_else(|_| PathBuf::from(uri.path()));
+    self.file_changed(&path, FileChangeType::CHANGED);
+  }
+
+  async fn did_change_watched_files(
+    &self,
+    params: DidChangeWatchedFilesParams,
+  ) {
+    for event in params.changes {
+      if let Ok(path) = event.uri.to_file_path() {
+        self.file_changed(&path, event.typ);
+      }
+    }
+  }
+
+  async fn did_close(&self, params: DidCloseTextDocumentParams) {
+    let uri = params.text_document.uri;
+
+    // Bumped rather than dropped, so an analysis still pending
+    // for the closed document is discarded.
+    self.bump_generation(&uri);
+
+    if let Ok(mut documents) = self.documents.lock() {
+      documents.remove(&uri);
+    }
 
     if let Ok(mut idx) = self.index.lock() {
-      idx.update(&uri, &change.text, &path);
+      idx.remove(&uri);
+    }
+
+    // What loads the file reads it from disk again.
+    if let Ok(path) = uri.to_file_path() {
+      if let Ok(idx) = self.index.lock() {
+        idx.cache.close(&path);
+      }
+
+      self.file_changed(&path, FileChangeType::CHANGED);
     }
+
+    self.client.publish_diagnostics(uri, Vec::new(), None).await;
   }
 
   async fn goto_definition(
@@ -176,6 +657,290 @@ impl LanguageServer for ZoLanguageServer {
 
     Ok(None)
   }
+
+  async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let pos = params.text_document_position_params.position;
+
+    let idx = match self.index.lock() {
+      Ok(g) => g,
+      Err(_) => return Ok(None),
+    };
+
+    let Some(state) = idx.get(&uri) else {
+      return Ok(None);
+    };
+
+    let offset = state.line_index.offset(pos.line, pos.character);
+
+    let Some(node_idx) = Self::find_node_at_offset(state, offset) else {
+      return Ok(None);
+    };
+
+    Ok(hover::hover(state, node_idx).map(|value| Hover {
+      contents: HoverContents::Markup(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+      }),
+      range: Some(state.line_index.range(state.tree.spans[node_idx])),
+    }))
+  }
+
+  async fn completion(
+    &self,
+    params: CompletionParams,
+  ) -> Result<Option<CompletionResponse>> {
+    let uri = params.text_document_position.text_document.uri;
+    let pos = params.text_document_position.position;
+
+    let Some(source) = self
+      .documents
+      .lock()
+      .ok()
+      .and_then(|documents| documents.get(&uri).cloned())
+    else {
+      return Ok(None);
+    };
+
+    let idx = match self.index.lock() {
+      Ok(g) => g,
+      Err(_) => return Ok(None),
+    };
+
+    let Some(state) = idx.get(&uri) else {
+      return Ok(None);
+    };
+
+    let offset = LineIndex::new(&source).offset(pos.line, pos.character);
+    let items = completion::complete(state, &source, offset as usize);
+
+    Ok(Some(CompletionResponse::Array(items)))
+  }
+
+  async fn signature_help(
+    &self,
+    params: SignatureHelpParams,
+  ) -> Result<Option<SignatureHelp>> {
+    let uri = params.text_document_position_params.text_document.uri;
+    let pos = params.text_document_position_params.position;
+
+    let Some(source) = self
+      .documents
+      .lock()
+      .ok()
+      .and_then(|documents| documents.get(&uri).cloned())
+    else {
+      return Ok(None);
+    };
+
+    let idx = match self.index.lock() {
+      Ok(g) => g,
+      Err(_) => return Ok(None),
+    };
+
+    let Some(state) = idx.get(&uri) else {
+      return Ok(None);
+    };
+
+    let offset = LineIndex::new(&source).offset(pos.line, pos.character);
+
+    Ok(signature::signature_help(state, &source, offset as usize))
+  }
+
+  async fn inlay_hint(
+    &self,
+    params: InlayHintParams,
+  ) -> Result<Option<Vec<InlayHint>>> {
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    let Some(state) = idx.get(&params.text_document.uri) else {
+      return Ok(None);
+    };
+
+    let range = params.range;
+    let start = state
+      .line_index
+      .offset(range.start.line, range.start.character);
+    let end = state.line_index.offset(range.end.line, range.end.character);
+
+    Ok(Some(hints::hints(state, (start, end))))
+  }
+
+  async fn references(
+    &self,
+    params: ReferenceParams,
+  ) -> Result<Option<Vec<Location>>> {
+    let uri = params.text_document_position.text_document.uri;
+    let pos = params.text_document_position.position;
+
+    let Some(target) = self.target_at(&uri, pos).await else {
+      return Ok(None);
+    };
+
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    Ok(Some(references::references(
+      &idx,
+      &target,
+      params.context.include_declaration,
+    )))
+  }
+
+  async fn prepare_rename(
+    &self,
+    params: TextDocumentPositionParams,
+  ) -> Result<Option<PrepareRenameResponse>> {
+    let Some(target) = self
+      .target_at(&params.text_document.uri, params.position)
+      .await
+    else {
+      return Ok(None);
+    };
+
+    references::check_renamable(&target).map_err(Error::invalid_params)?;
+
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    let Some(state) = idx.get(&params.text_document.uri) else {
+      return Ok(None);
+    };
+
+    let offset = state
+      .line_index
+      .offset(params.position.line, params.position.character);
+
+    Ok(Self::find_node_at_offset(state, offset).map(|node_idx| {
+      PrepareRenameResponse::Range(
+        state.line_index.range(state.tree.spans[node_idx]),
+      )
+    }))
+  }
+
+  async fn rename(
+    &self,
+    params: RenameParams,
+  ) -> Result<Option<WorkspaceEdit>> {
+    let uri = params.text_document_position.text_document.uri;
+    let pos = params.text_document_position.position;
+
+    let Some(target) = self.target_at(&uri, pos).await else {
+      return Ok(None);
+    };
+
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    references::rename(&idx, &target, &params.new_name)
+      .map(Some)
+      .map_err(Error::invalid_params)
+  }
+
+  async fn document_symbol(
+    &self,
+    params: DocumentSymbolParams,
+  ) -> Result<Option<DocumentSymbolResponse>> {
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    let Some(state) = idx.get(&params.text_document.uri) else {
+      return Ok(None);
+    };
+
+    Ok(Some(DocumentSymbolResponse::Nested(
+      symbols::document_symbols(&state.tree, &state.source),
+    )))
+  }
+
+  async fn symbol(
+    &self,
+    params: WorkspaceSymbolParams,
+  ) -> Result<Option<Vec<SymbolInformation>>> {
+    let (Ok(idx), Ok(workspace)) = (self.index.lock(), self.workspace.lock())
+    else {
+      return Ok(None);
+    };
+
+    let open = idx.files.iter().flat_map(|(uri, state)| {
+      symbols::flatten(
+        uri,
+        &symbols::document_symbols(&state.tree, &state.source),
+      )
+    });
+
+    let indexed = workspace
+      .iter()
+      .filter(|(uri, _)| !idx.files.contains_key(*uri))
+      .flat_map(|(_, symbols)| symbols.iter().cloned());
+
+    Ok(Some(symbols::search(&params.query, open.chain(indexed))))
+  }
+
+  async fn semantic_tokens_full(
+    &self,
+    params: SemanticTokensParams,
+  ) -> Result<Option<SemanticTokensResult>> {
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    let Some(state) = idx.get(&params.text_document.uri) else {
+      return Ok(None);
+    };
+
+    Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+      result_id: None,
+      data: semantic::tokens(state, None),
+    })))
+  }
+
+  async fn semantic_tokens_range(
+    &self,
+    params: SemanticTokensRangeParams,
+  ) -> Result<Option<SemanticTokensRangeResult>> {
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    let Some(state) = idx.get(&params.text_document.uri) else {
+      return Ok(None);
+    };
+
+    let range = params.range;
+    let start = state
+      .line_index
+      .offset(range.start.line, range.start.character);
+    let end = state.line_index.offset(range.end.line, range.end.character);
+
+    Ok(Some(SemanticTokensRangeResult::Tokens(SemanticTokens {
+      result_id: None,
+      data: semantic::tokens(state, Some((start, end))),
+    })))
+  }
+
+  async fn code_action(
+    &self,
+    params: CodeActionParams,
+  ) -> Result<Option<CodeActionResponse>> {
+    let Ok(idx) = self.index.lock() else {
+      return Ok(None);
+    };
+
+    let uri = params.text_document.uri;
+
+    let Some(state) = idx.get(&uri) else {
+      return Ok(None);
+    };
+
+    Ok(Some(actions::actions(state, &uri, params.range)))
+  }
 }
 
 /// Resolve a function symbol to an LSP Location.
@@ -193,10 +958,11 @@ fn resolve_fun(
   }
 
   match fun.owning_pack {
-    None => {
+    _ if state.is_own(fun) => {
       let range = state.line_index.range(fun.span);
       Some(Location::new(current_uri.clone(), range))
     }
+    None => None,
     Some(pack) => {
       let path = state.pack_paths.get(&pack)?;
       let source = std::fs::read_to_string(path).ok()?;
@@ -0,0 +1,331 @@
+use crate::hover;
+use crate::index::FileState;
+
+use zo_interner::Interner;
+use zo_session::Session;
+use zo_span::Span;
+use zo_token::{Token, TokenBuffer};
+use zo_tokenizer::Tokenizer;
+use zo_ty::TyId;
+use zo_value::{FunDef, FunctionKind};
+
+use tower_lsp::lsp_types::{
+  Documentation, MarkupContent, MarkupKind, ParameterInformation,
+  ParameterLabel, SignatureHelp, SignatureInformation,
+};
+
+/// What a call's `(` follows.
+pub(crate) enum Callee<'a> {
+  /// `name(..)`.
+  Fun(&'a str),
+  /// `pack::name(..)`, `Type::name(..)`.
+  Path(&'a str, &'a str),
+  /// `receiver.name(..)`, with the receiver's type when it is
+  /// known.
+  Method(&'a str, Option<String>),
+}
+
+/// The signatures of the call the cursor sits in, with the
+/// parameter being typed active. Reads the latest text — the
+/// call is still being written — and resolves the callee
+/// against the last analysis.
+pub fn signature_help(
+  state: &FileState,
+  source: &str,
+  offset: usize,
+) -> Option<SignatureHelp> {
+  let prefix = source.get(..offset)?;
+  let tokens = Tokenizer::new(prefix, &mut Interner::new())
+    .tokenize()
+    .tokens;
+
+  // An unfinished call does not tokenize cleanly, nor should
+  // it report anything.
+  zo_reporter::collect_errors();
+
+  let (lparen, commas) = open_call(&tokens)?;
+  let callee = callee(&tokens, prefix, lparen)?;
+  let is_method = matches!(callee, Callee::Method(..));
+
+  let signatures = candidates(state, &callee)
+    .into_iter()
+    .map(|fun| {
+      let mut signature = signature(state, fun);
+      let skipped = usize::from(is_method && has_self(state, fun));
+
+      signature.active_parameter = Some((commas + skipped) as u32);
+      signature
+    })
+    .collect::<Vec<_>>();
+
+  if signatures.is_empty() {
+    return None;
+  }
+
+  Some(SignatureHelp {
+    active_parameter: signatures[0].active_parameter,
+    active_signature: Some(0),
+    signatures,
+  })
+}
+
+/// The `(` of the innermost call still open at the end of
+/// `tokens`, and how many of its arguments are complete.
+fn open_call(tokens: &TokenBuffer) -> Option<(usize, usize)> {
+  let mut depth = 0usize;
+  let mut commas = 0;
+
+  for idx in (0..tokens.kinds.len()).rev() {
+    match tokens.kinds[idx] {
+      Token::RParen | Token::RBracket | Token::RBrace => depth += 1,
+      Token::LParen if depth == 0 => return Some((idx, commas)),
+      Token::LParen | Token::LBracket | Token::LBrace if depth > 0 => {
+        depth -= 1;
+      }
+      // A block or a statement boundary — no call is open.
+      Token::LBracket | Token::LBrace | Token::Semicolon if depth == 0 => {
+        return None;
+      }
+      Token::Comma if depth == 0 => commas += 1,
+      _ => {}
+    }
+  }
+
+  None
+}
+
+/// The callee of the call whose `(` is token `lparen`.
+/// Declarations — `fun f(`, `ffi f(`, `fn(` — are no calls.
+pub(crate) fn callee<'a>(
+  tokens: &TokenBuffer,
+  source: &'a str,
+  lparen: usize,
+) -> Option<Callee<'a>> {
+  let kinds = &tokens.kinds;
+  let name_idx = lparen.checked_sub(1)?;
+
+  if kinds[name_idx] != Token::Ident {
+    return None;
+  }
+
+  let text = |idx: usize| {
+    let start = tokens.starts[idx] as usize;
+
+    source.get(start..start + tokens.lengths[idx] as usize)
+  };
+  let name = text(name_idx)?;
+
+  match name_idx.checked_sub(1).map(|idx| kinds[idx]) {
+    Some(Token::Fun | Token::Ffi) => None,
+    Some(Token::Dot) => Some(Callee::Method(name, None)),
+    Some(Token::ColonColon)
+      if name_idx >= 2 && kinds[name_idx - 2] == Token::Ident =>
+    {
+      Some(Callee::Path(text(name_idx - 2)?, name))
+    }
+    _ => Some(Callee::Fun(name)),
+  }
+}
+
+/// The functions `callee` may name. A method whose receiver
+/// type is unknown matches every `apply` block defining it.
+pub(crate) fn candidates<'s>(
+  state: &'s FileState,
+  callee: &Callee,
+) -> Vec<&'s FunDef> {
+  let interner = &state.session.interner;
+  let funs = state
+    .funs
+    .iter()
+    .filter(|fun| !matches!(fun.kind, FunctionKind::Closure { .. }));
+
+  match callee {
+    Callee::Fun(name) => funs
+      .filter(|fun| interner.get(fun.name) == *name)
+      .take(1)
+      .collect(),
+    Callee::Path(qualifier, name) => funs
+      .filter(|fun| {
+        let fun_name = interner.get(fun.name);
+
+        fun_name
+          .strip_prefix(qualifier)
+          .and_then(|rest| rest.strip_prefix("::"))
+          == Some(name)
+          || fun_name == *name
+            && fun
+              .owning_pack
+              .is_some_and(|pack| interner.get(pack) == *qualifier)
+      })
+      .take(1)
+      .collect(),
+    Callee::Method(name, receiver) => {
+      let methods = funs
+        .filter(|fun| {
+          interner
+            .get(fun.name)
+            .rsplit_once("::")
+            .is_some_and(|(_, method)| method == *name)
+        })
+        .collect::<Vec<_>>();
+
+      let typed = receiver.as_ref().map(|ty| format!("{ty}::{name}"));
+
+      match methods
+        .iter()
+        .find(|fun| Some(interner.get(fun.name)) == typed.as_deref())
+      {
+        Some(fun) => vec![*fun],
+        None => methods,
+      }
+    }
+  }
+}
+
+/// Whether the first parameter of `fun` is `self`.
+pub(crate) fn has_self(state: &FileState, fun: &FunDef) -> bool {
+  fun
+    .params
+    .first()
+    .is_some_and(|(name, _)| state.session.interner.get(*name) == "self")
+}
+
+/// `fun name(a: A, b: B) -> R` — `ffi` for a foreign
+/// declaration — with the byte range of every parameter.
+fn signature(state: &FileState, fun: &FunDef) -> SignatureInformation {
+  let session = &state.session;
+  let interner = &session.interner;
+  let keyword = match fun.kind {
+    FunctionKind::Intrinsic => "ffi",
+    _ => "fun",
+  };
+
+  let names = type_params(state, fun);
+  let ty_label = generic_label(session, fun, &names);
+
+  let mut label = format!("{keyword} {}", interner.get(fun.name));
+
+  if !names.is_empty() {
+    label.push_str(&format!("<{}>", names.join(", ")));
+  }
+
+  label.push('(');
+
+  let mut parameters = Vec::new();
+
+  for (idx, (name, ty_id)) in fun.params.iter().enumerate() {
+    if idx > 0 {
+      label.push_str(", ");
+    }
+
+    let param = match interner.get(*name) {
+      "self" => "self".to_string(),
+      name => format!("{name}: {}", ty_label(*ty_id)),
+    };
+    let start = label.len() as u32;
+
+    label.push_str(&param);
+    parameters.push(ParameterInformation {
+      label: ParameterLabel::LabelOffsets([start, label.len() as u32]),
+      documentation: None,
+    });
+  }
+
+  label.push(')');
+  label.push_str(&match ty_label(fun.return_ty).as_str() {
+    "unit" => String::new(),
+    ty => format!(" -> {ty}"),
+  });
+
+  SignatureInformation {
+    label,
+    documentation: hover::fun_doc(state, fun).map(|value| {
+      Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+      })
+    }),
+    parameters: Some(parameters),
+    active_parameter: None,
+  }
+}
+
+/// The `$T` names a generic function declares. Only the
+/// current file is read — an imported generic shows its
+/// types alone.
+pub(crate) fn type_params(state: &FileState, fun: &FunDef) -> Vec<String> {
+  let span = fun.span;
+
+  if fun.type_params.is_empty() || !state.is_own(fun) || span == Span::ZERO {
+    return Vec::new();
+  }
+
+  let header = state
+    .source
+    .get(span.start as usize..)
+    .and_then(|rest| rest.split_once('(').map(|(header, _)| header))
+    .unwrap_or_default();
+
+  let Some((_, generics)) = header.split_once('<') else {
+    return Vec::new();
+  };
+
+  generics
+    .trim_end()
+    .trim_end_matches('>')
+    .split(',')
+    .filter_map(|param| {
+      let name = param.split(':').next()?.trim();
+
+      name.starts_with('$').then(|| name.to_string())
+    })
+    .collect()
+}
+
+/// Spells a type of `fun`'s signature, each of its type
+/// parameters named as in `names` — `?N`, how an unbound
+/// `$T` prints, becomes `$T`.
+pub(crate) fn generic_label(
+  session: &Session,
+  fun: &FunDef,
+  names: &[String],
+) -> impl Fn(TyId) -> String {
+  let vars = fun
+    .type_params
+    .iter()
+    .map(|ty_id| hover::ty_label(session, *ty_id))
+    .zip(names.to_vec())
+    .collect::<Vec<_>>();
+
+  move |ty_id| {
+    vars
+      .iter()
+      .fold(hover::ty_label(session, ty_id), |label, (var, name)| {
+        replace_var(&label, var, name)
+      })
+  }
+}
+
+/// `label` with the inference variable `var` spelled `name`
+/// — `?1` in `[]?1`, not in `?12`.
+fn replace_var(label: &str, var: &str, name: &str) -> String {
+  let mut out = String::new();
+  let mut rest = label;
+
+  while let Some(at) = rest.find(var) {
+    let after = &rest[at + var.len()..];
+
+    out.push_str(&rest[..at]);
+
+    if after.starts_with(|c: char| c.is_ascii_digit()) {
+      out.push_str(var);
+    } else {
+      out.push_str(name);
+    }
+
+    rest = after;
+  }
+
+  out.push_str(rest);
+  out
+}
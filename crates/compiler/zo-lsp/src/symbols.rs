@@ -0,0 +1,404 @@
+use crate::position::LineIndex;
+
+use zo_interner::Interner;
+use zo_parser::Parser;
+use zo_span::Span;
+use zo_token::Token;
+use zo_tokenizer::Tokenizer;
+use zo_tree::Tree;
+
+use tower_lsp::lsp_types::{
+  DocumentSymbol, Location, SymbolInformation, SymbolKind, Url,
+};
+
+use std::path::{Path, PathBuf};
+
+/// Directories the workspace walk never enters.
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// How many symbols a workspace query returns at most.
+const MAX_RESULTS: usize = 256;
+
+/// An item whose `{` body has not closed yet.
+struct Frame {
+  symbol: DocumentSymbol,
+  /// Brace depth inside the body, once its `{` is seen.
+  body: Option<usize>,
+}
+
+/// The outline of a file — packs, structs with their fields,
+/// enums with their variants, `apply` blocks and abstracts
+/// with their methods, functions and the `::=` templates
+/// they bind — nested as in source.
+///
+/// The tree keeps tokens in source order, so a single walk
+/// matching braces recovers the nesting.
+pub fn document_symbols(tree: &Tree, source: &str) -> Vec<DocumentSymbol> {
+  let line_index = LineIndex::new(source);
+  let nodes = &tree.nodes;
+  let spans = &tree.spans;
+
+  let mut root = Vec::new();
+  let mut frames: Vec<Frame> = Vec::new();
+  let mut depth = 0;
+
+  for (idx, node) in nodes.iter().enumerate() {
+    let span = spans[idx];
+    let next = nodes.get(idx + 1).map(|next| next.token);
+    // The innermost frame whose body the walk sits right in.
+    let owner = frames
+      .last()
+      .filter(|frame| frame.body == Some(depth))
+      .map(|frame| frame.symbol.kind);
+
+    match node.token {
+      Token::LBrace => {
+        depth += 1;
+
+        if let Some(frame) = frames.last_mut()
+          && frame.body.is_none()
+        {
+          frame.body = Some(depth);
+        }
+      }
+      Token::RBrace => {
+        if frames.last().is_some_and(|frame| frame.body == Some(depth)) {
+          close(&mut frames, &mut root, &line_index, span);
+        }
+
+        depth = depth.saturating_sub(1);
+      }
+      Token::Semicolon
+        if frames.last().is_some_and(|frame| frame.body.is_none()) =>
+      {
+        // `pack x;`, `fun f();` — a declaration without body.
+        close(&mut frames, &mut root, &line_index, span);
+      }
+      Token::Pack | Token::Struct | Token::Enum | Token::Abstract
+        if next == Some(Token::Ident) =>
+      {
+        let kind = match node.token {
+          Token::Pack => SymbolKind::MODULE,
+          Token::Struct => SymbolKind::STRUCT,
+          Token::Enum => SymbolKind::ENUM,
+          _ => SymbolKind::INTERFACE,
+        };
+
+        frames.push(open(
+          &line_index,
+          text(source, spans[idx + 1]),
+          None,
+          kind,
+          span,
+          spans[idx + 1],
+        ));
+      }
+      Token::Apply => {
+        let header = nodes[idx..]
+          .iter()
+          .position(|node| node.token == Token::LBrace)
+          .map_or(span, |at| {
+            Span::new(span.end(), (spans[idx + at].start - span.end()) as u16)
+          });
+
+        frames.push(open(
+          &line_index,
+          text(source, header).trim(),
+          Some("apply".into()),
+          SymbolKind::OBJECT,
+          span,
+          header,
+        ));
+      }
+      Token::Fun if next == Some(Token::Ident) => {
+        let name_span = spans[idx + 1];
+        let kind = match owner {
+          Some(SymbolKind::OBJECT | SymbolKind::INTERFACE) => {
+            SymbolKind::METHOD
+          }
+          _ => SymbolKind::FUNCTION,
+        };
+
+        let signature = signature(tree, source, idx + 1);
+        let detail = if tree.is_test_at(idx) {
+          format!("test {signature}")
+        } else {
+          signature
+        };
+
+        frames.push(open(
+          &line_index,
+          text(source, name_span),
+          Some(detail),
+          kind,
+          span,
+          name_span,
+        ));
+      }
+      Token::Ident
+        if matches!(owner, Some(SymbolKind::STRUCT | SymbolKind::ENUM))
+          && matches!(nodes[idx - 1].token, Token::LBrace | Token::Comma) =>
+      {
+        let kind = match owner {
+          Some(SymbolKind::STRUCT) => SymbolKind::FIELD,
+          _ => SymbolKind::ENUM_MEMBER,
+        };
+
+        push(
+          &mut frames,
+          &mut root,
+          leaf(&line_index, source, kind, span),
+        );
+      }
+      Token::Imu | Token::Mut
+        if next == Some(Token::Ident)
+          && frames.iter().any(|frame| {
+            matches!(
+              frame.symbol.kind,
+              SymbolKind::FUNCTION | SymbolKind::METHOD
+            )
+          })
+          && nodes[idx + 2..]
+            .iter()
+            .take(3)
+            .any(|node| node.token == Token::TemplateAssign) =>
+      {
+        let mut template =
+          leaf(&line_index, source, SymbolKind::VARIABLE, spans[idx + 1]);
+
+        template.detail = Some("</>".into());
+
+        push(&mut frames, &mut root, template);
+      }
+      _ => {}
+    }
+  }
+
+  // An unterminated item still belongs in the outline.
+  while !frames.is_empty() {
+    close(&mut frames, &mut root, &line_index, tree.eof_span());
+  }
+
+  root
+}
+
+/// Parses `source` on its own — no module resolution — and
+/// returns its outline. Used for the files no editor has
+/// open.
+pub fn outline(source: &str) -> Vec<DocumentSymbol> {
+  let mut interner = Interner::new();
+  let tokenization = Tokenizer::new(source, &mut interner).tokenize();
+  let parsing = Parser::new(&tokenization, source).parse();
+
+  // Parse errors are the diagnostics' business, not ours.
+  zo_reporter::collect_errors();
+
+  document_symbols(&parsing.tree, source)
+}
+
+/// Every `.zo` file under `root`, hidden directories and build
+/// outputs excluded.
+pub fn zo_files(root: &Path) -> Vec<PathBuf> {
+  let mut files = Vec::new();
+  let mut dirs = vec![root.to_path_buf()];
+
+  while let Some(dir) = dirs.pop() {
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+      continue;
+    };
+
+    for entry in entries.flatten() {
+      let path = entry.path();
+      let name = entry.file_name();
+      let name = name.to_string_lossy();
+
+      if path.is_dir() {
+        if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+          dirs.push(path);
+        }
+      } else if path.extension().is_some_and(|ext| ext == "zo") {
+        files.push(path);
+      }
+    }
+  }
+
+  files
+}
+
+/// Flattens an outline into workspace symbols, each naming
+/// the item it is nested in.
+#[allow(deprecated)]
+pub fn flatten(
+  uri: &Url,
+  symbols: &[DocumentSymbol],
+) -> Vec<SymbolInformation> {
+  let mut flat = Vec::new();
+  let mut stack = symbols
+    .iter()
+    .map(|symbol| (symbol, None))
+    .collect::<Vec<_>>();
+
+  while let Some((symbol, container)) = stack.pop() {
+    flat.push(SymbolInformation {
+      name: symbol.name.clone(),
+      kind: symbol.kind,
+      tags: None,
+      deprecated: None,
+      location: Location::new(uri.clone(), symbol.selection_range),
+      container_name: container,
+    });
+
+    for child in symbol.children.iter().flatten() {
+      stack.push((child, Some(symbol.name.clone())));
+    }
+  }
+
+  flat
+}
+
+/// The symbols matching `query`, best match first.
+pub fn search(
+  query: &str,
+  symbols: impl Iterator<Item = SymbolInformation>,
+) -> Vec<SymbolInformation> {
+  let mut matches = symbols
+    .filter_map(|symbol| Some((fuzzy_score(query, &symbol.name)?, symbol)))
+    .collect::<Vec<_>>();
+
+  matches.sort_by(|(a, x), (b, y)| b.cmp(a).then_with(|| x.name.cmp(&y.name)));
+  matches.truncate(MAX_RESULTS);
+  matches.into_iter().map(|(_, symbol)| symbol).collect()
+}
+
+/// Scores `name` against `query` when every query character
+/// appears in order, ignoring case. Consecutive matches, a
+/// match at the start and matches after `_` or a case change
+/// score higher; so do shorter names.
+pub(crate) fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+  let name = name.chars().collect::<Vec<_>>();
+  let mut score = 0;
+  let mut at = 0;
+  let mut last = None;
+
+  for q in query.chars().filter(|c| !c.is_whitespace()) {
+    let found = (at..name.len())
+      .find(|&i| name[i].to_lowercase().eq(q.to_lowercase()))?;
+
+    let boundary = found == 0
+      || name[found - 1] == '_'
+      || (name[found - 1].is_lowercase() && name[found].is_uppercase());
+
+    score += 1;
+
+    if boundary {
+      score += 8;
+    }
+
+    if last.is_some_and(|last| last + 1 == found) {
+      score += 4;
+    }
+
+    last = Some(found);
+    at = found + 1;
+  }
+
+  Some(score * 16 - name.len() as i32)
+}
+
+/// The text between a function's name and its body — its
+/// parameters and return type, whitespace collapsed.
+fn signature(tree: &Tree, source: &str, name_idx: usize) -> String {
+  let start = tree.spans[name_idx].end();
+  let end = tree.nodes[name_idx..]
+    .iter()
+    .position(|node| matches!(node.token, Token::LBrace | Token::Semicolon))
+    .map_or(start, |at| tree.spans[name_idx + at].start);
+
+  let text = source.get(start as usize..end as usize).unwrap_or_default();
+
+  // A parameter list split over lines reads as one line.
+  text
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .replace("( ", "(")
+    .replace(", )", ")")
+}
+
+fn open(
+  line_index: &LineIndex,
+  name: &str,
+  detail: Option<String>,
+  kind: SymbolKind,
+  span: Span,
+  name_span: Span,
+) -> Frame {
+  Frame {
+    symbol: symbol(line_index, name, detail, kind, span, name_span),
+    body: None,
+  }
+}
+
+/// Ends the innermost frame at `end` and files it under its
+/// parent.
+fn close(
+  frames: &mut Vec<Frame>,
+  root: &mut Vec<DocumentSymbol>,
+  line_index: &LineIndex,
+  end: Span,
+) {
+  let Some(mut frame) = frames.pop() else {
+    return;
+  };
+
+  frame.symbol.range.end = line_index.range(end).end;
+
+  push(frames, root, frame.symbol);
+}
+
+fn push(
+  frames: &mut [Frame],
+  root: &mut Vec<DocumentSymbol>,
+  symbol: DocumentSymbol,
+) {
+  match frames.last_mut() {
+    Some(frame) => frame.symbol.children.get_or_insert_default().push(symbol),
+    None => root.push(symbol),
+  }
+}
+
+fn leaf(
+  line_index: &LineIndex,
+  source: &str,
+  kind: SymbolKind,
+  span: Span,
+) -> DocumentSymbol {
+  symbol(line_index, text(source, span), None, kind, span, span)
+}
+
+#[allow(deprecated)]
+fn symbol(
+  line_index: &LineIndex,
+  name: &str,
+  detail: Option<String>,
+  kind: SymbolKind,
+  span: Span,
+  name_span: Span,
+) -> DocumentSymbol {
+  DocumentSymbol {
+    name: name.to_string(),
+    detail,
+    kind,
+    tags: None,
+    deprecated: None,
+    range: line_index.range(span),
+    selection_range: line_index.range(name_span),
+    children: None,
+  }
+}
+
+fn text(source: &str, span: Span) -> &str {
+  source
+    .get(span.start as usize..span.end() as usize)
+    .unwrap_or_default()
+}
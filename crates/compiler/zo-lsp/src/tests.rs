@@ -0,0 +1,11 @@
+pub(crate) mod actions;
+pub(crate) mod common;
+pub(crate) mod completion;
+pub(crate) mod diagnostics;
+pub(crate) mod hints;
+pub(crate) mod hover;
+pub(crate) mod position;
+pub(crate) mod references;
+pub(crate) mod semantic;
+pub(crate) mod signature;
+pub(crate) mod symbols;
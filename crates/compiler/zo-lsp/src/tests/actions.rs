@@ -0,0 +1,171 @@
+use crate::actions::actions;
+use crate::tests::common::{analyze, uri};
+
+use tower_lsp::lsp_types::{CodeActionOrCommand, Position, Range, TextEdit};
+
+use std::path::Path;
+
+/// The edits of the action titled `title` over `range`.
+fn edits(dir: &Path, source: &str, range: Range, title: &str) -> Vec<TextEdit> {
+  let state = analyze(dir, source);
+  let uri = uri(dir, "main.zo");
+
+  let action = actions(&state, &uri, range)
+    .into_iter()
+    .find_map(|action| match action {
+      CodeActionOrCommand::CodeAction(action) if action.title == title => {
+        Some(action)
+      }
+      _ => None,
+    })
+    .unwrap_or_else(|| panic!("no `{title}` action"));
+
+  action
+    .edit
+    .and_then(|edit| edit.changes)
+    .and_then(|mut changes| changes.remove(&uri))
+    .unwrap()
+}
+
+/// The titles of every action over `range`.
+fn titles(dir: &Path, source: &str, range: Range) -> Vec<String> {
+  let state = analyze(dir, source);
+
+  actions(&state, &uri(dir, "main.zo"), range)
+    .into_iter()
+    .filter_map(|action| match action {
+      CodeActionOrCommand::CodeAction(action) => Some(action.title),
+      _ => None,
+    })
+    .collect()
+}
+
+fn at(line: u32, character: u32) -> Range {
+  Range::new(
+    Position::new(line, character),
+    Position::new(line, character),
+  )
+}
+
+fn edit(from: (u32, u32), to: (u32, u32), text: &str) -> TextEdit {
+  TextEdit::new(
+    Range::new(Position::new(from.0, from.1), Position::new(to.0, to.1)),
+    text.to_string(),
+  )
+}
+
+/// The selection is bound right before its statement, even
+/// inside a nested block, and replaced by the new name.
+#[test]
+fn extract_local_binds_the_selection_before_its_statement() {
+  let dir = tempfile::tempdir().unwrap();
+  let source = "fun main() {
+  imu a: int = 2;
+
+  if a > 1 {
+    showln(a * 3 + 1);
+  }
+}
+";
+
+  let range = Range::new(Position::new(4, 11), Position::new(4, 16));
+
+  assert_eq!(
+    edits(dir.path(), source, range, "Extract `a * 3` into a local"),
+    [
+      edit((4, 4), (4, 4), "imu value := a * 3;\n    "),
+      edit((4, 11), (4, 16), "value"),
+    ],
+  );
+}
+
+/// A selection that isn't one expression offers no extract.
+#[test]
+fn extract_local_skips_partial_expressions() {
+  let dir = tempfile::tempdir().unwrap();
+  let source = "fun main() {
+  showln((1 + 2) * 3);
+}
+";
+
+  let range = Range::new(Position::new(1, 9), Position::new(1, 14));
+
+  assert!(
+    !titles(dir.path(), source, range)
+      .iter()
+      .any(|title| title.starts_with("Extract")),
+  );
+}
+
+/// Every use takes the parenthesized initializer, and the
+/// declaration goes with its whole line.
+#[test]
+fn inline_local_replaces_every_use() {
+  let dir = tempfile::tempdir().unwrap();
+  let source = "fun main() {
+  imu n := 2 + 3;
+  imu a: int = n * n;
+  showln(a);
+}
+";
+
+  assert_eq!(
+    edits(dir.path(), source, at(1, 6), "Inline `n`"),
+    [
+      edit((2, 15), (2, 16), "(2 + 3)"),
+      edit((2, 19), (2, 20), "(2 + 3)"),
+      edit((1, 0), (2, 0), ""),
+    ],
+  );
+}
+
+/// The inferred type is written between the name and the
+/// initializer, `:=` becoming `: T =`.
+#[test]
+fn annotate_writes_the_inferred_type() {
+  let dir = tempfile::tempdir().unwrap();
+  let source = "fun main() {
+  imu n := 2 + 3;
+  showln(n);
+}
+";
+
+  assert_eq!(
+    edits(dir.path(), source, at(1, 6), "Annotate `n` as `int`"),
+    [edit((1, 7), (1, 10), ": int =")],
+  );
+}
+
+/// The missing arms land after the last one — past an arm
+/// whose block nests its own braces — indented like it.
+#[test]
+fn missing_arms_adds_one_arm_per_pattern() {
+  let dir = tempfile::tempdir().unwrap();
+  let source = "enum Shape {
+  Circle,
+  Square,
+  Triangle,
+}
+
+fun main() {
+  imu s: Shape = Shape::Circle;
+
+  match s {
+    Shape::Circle => {
+      if true {
+        showln(\"circle\");
+      }
+    },
+  }
+}
+";
+
+  assert_eq!(
+    edits(dir.path(), source, at(9, 2), "Add 2 missing arms"),
+    [edit(
+      (14, 6),
+      (14, 6),
+      "\n    Shape::Square => {},\n    Shape::Triangle => {},",
+    )],
+  );
+}
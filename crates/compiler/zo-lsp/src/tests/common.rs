@@ -0,0 +1,66 @@
+use crate::index::{self, FileState, SymbolIndex};
+
+use zo_token::Token;
+
+use tower_lsp::lsp_types::Url;
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Writes `files` into `dir` and indexes each of them, the
+/// way the server does for open documents.
+pub(crate) fn index(dir: &Path, files: &[(&str, &str)]) -> SymbolIndex {
+  let mut index = SymbolIndex::new();
+
+  for (name, source) in files {
+    fs::write(dir.join(name), source).unwrap();
+  }
+
+  for (name, source) in files {
+    let path = index::canonical(&dir.join(name));
+    let uri = Url::from_file_path(&path).unwrap();
+
+    let state =
+      index::analyze(&index.cache, &uri, source, &path, &Arc::default())
+        .unwrap();
+
+    index.insert(uri, state);
+  }
+
+  index
+}
+
+/// Writes `source` as `main.zo` in `dir` and analyzes it.
+pub(crate) fn analyze(dir: &Path, source: &str) -> FileState {
+  let index = index(dir, &[("main.zo", source)]);
+
+  index.files.into_values().next().unwrap()
+}
+
+/// The uri `index` files `name` of `dir` under.
+pub(crate) fn uri(dir: &Path, name: &str) -> Url {
+  Url::from_file_path(index::canonical(&dir.join(name))).unwrap()
+}
+
+/// The tree node of the `nth` (0-based) identifier spelled
+/// `name`, in source order.
+pub(crate) fn ident(state: &FileState, name: &str, nth: usize) -> usize {
+  let mut idents = state
+    .tree
+    .nodes
+    .iter()
+    .enumerate()
+    .filter(|(idx, node)| {
+      let span = state.tree.spans[*idx];
+
+      node.token == Token::Ident
+        && state.source.get(span.start as usize..span.end() as usize)
+          == Some(name)
+    })
+    .map(|(idx, _)| idx)
+    .collect::<Vec<_>>();
+
+  idents.sort_by_key(|idx| state.tree.spans[*idx].start);
+  idents[nth]
+}
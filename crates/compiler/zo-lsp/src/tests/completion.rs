@@ -0,0 +1,188 @@
+use crate::completion::complete;
+use crate::tests::common::{analyze, index, uri};
+
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind};
+
+const SOURCE: &str = "struct Point {
+  x: int,
+  y: int,
+}
+
+enum Shape {
+  Circle,
+  Square,
+}
+
+apply Point {
+  fun sum(self) -> int {
+    self.x + self.y
+  }
+
+  fun origin() -> Point {
+    Point { x: 0, y: 0 }
+  }
+}
+
+fun area(side: int) -> int {
+  side * side
+}
+
+fun main() {
+  imu p: Point = Point { x: 1, y: 2 };
+  imu total: int = area(p.x);
+}
+";
+
+/// The labels offered with the cursor at the end of `before`,
+/// spliced in at the end of `main`'s body.
+fn labels(before: &str) -> Vec<(String, Option<CompletionItemKind>)> {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), SOURCE);
+  let at = SOURCE.rfind('}').unwrap();
+  let source = format!("{}{before}", &SOURCE[..at]);
+
+  complete(&state, &source, source.len())
+    .into_iter()
+    .map(|CompletionItem { label, kind, .. }| (label, kind))
+    .collect()
+}
+
+fn has(items: &[(String, Option<CompletionItemKind>)], label: &str) -> bool {
+  items.iter().any(|(item, _)| item == label)
+}
+
+/// After `.` come the receiver's fields and its `apply`
+/// methods — not its statics, nor keywords.
+#[test]
+fn member_context_offers_fields() {
+  let items = labels("  p.");
+
+  assert!(has(&items, "x") && has(&items, "y"), "{items:?}");
+  assert!(
+    items.contains(&("sum".into(), Some(CompletionItemKind::METHOD))),
+    "{items:?}",
+  );
+  assert!(!has(&items, "origin"), "{items:?}");
+  assert!(!has(&items, "return"), "{items:?}");
+}
+
+/// After `Type::` come the enum's variants.
+#[test]
+fn path_context_offers_variants() {
+  let items = labels("  imu s: Shape = Shape::");
+
+  assert!(has(&items, "Circle") && has(&items, "Square"), "{items:?}");
+}
+
+/// After `Type::` on a struct come its statics, not its
+/// methods.
+#[test]
+fn path_context_offers_statics() {
+  let items = labels("  imu o: Point = Point::");
+
+  assert!(has(&items, "origin"), "{items:?}");
+  assert!(!has(&items, "sum"), "{items:?}");
+}
+
+/// After `pack::` come the members the pack exports.
+#[test]
+fn path_context_offers_pack_members() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let main = "load geometry;
+
+fun main() {
+  showln(geometry::square(3));
+}
+";
+  let index = index(
+    dir,
+    &[
+      ("lib.zo", "pub pack geometry;\n"),
+      (
+        "geometry.zo",
+        "pub fun square(n: int) -> int {\n  n * n\n}\n",
+      ),
+      ("main.zo", main),
+    ],
+  );
+  let state = index.get(&uri(dir, "main.zo")).unwrap();
+  let at = main.find("square").unwrap();
+  let items = complete(state, &main[..at], at);
+
+  assert!(
+    items.iter().any(|item| item.label == "square"
+      && item.kind == Some(CompletionItemKind::FUNCTION)),
+    "{items:?}",
+  );
+}
+
+/// In a body: the locals in scope, the functions and the
+/// statement keywords — not the item ones.
+#[test]
+fn code_context_offers_locals_functions_and_body_keywords() {
+  let items = labels("  t");
+
+  assert!(has(&items, "total") && has(&items, "p"), "{items:?}");
+  assert!(has(&items, "area"), "{items:?}");
+  assert!(has(&items, "return") && has(&items, "defer"), "{items:?}");
+  assert!(!has(&items, "struct"), "{items:?}");
+}
+
+/// At the top level only item keywords make sense.
+#[test]
+fn top_level_context_offers_item_keywords() {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), SOURCE);
+  let source = format!("{SOURCE}\nf");
+  let items = complete(&state, &source, source.len());
+
+  assert!(items.iter().any(|item| item.label == "struct"));
+  assert!(!items.iter().any(|item| item.label == "return"));
+}
+
+/// Nothing is offered inside a string literal.
+#[test]
+fn string_context_offers_nothing() {
+  assert!(labels("  showln(\"p.").is_empty());
+}
+
+/// Inside a template: element tags after `<`, events after
+/// `@` in an open tag — and a less-than stays code.
+#[test]
+fn template_context_offers_tags_and_events() {
+  let items = labels("  imu view: </> ::= <>\n    <");
+
+  assert!(
+    items.contains(&("button".into(), Some(CompletionItemKind::PROPERTY))),
+    "{items:?}",
+  );
+
+  let items = labels("  imu view: </> ::= <>\n    <button @");
+
+  assert!(
+    items.contains(&("click".into(), Some(CompletionItemKind::EVENT))),
+    "{items:?}",
+  );
+
+  let items = labels("  imu less: bool = total <");
+
+  assert!(!has(&items, "button"), "{items:?}");
+}
+
+/// Inside a `$:` rule come the shorthand properties, their
+/// expansion as detail; a selector position offers nothing.
+#[test]
+fn style_context_offers_shorthand_properties() {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), SOURCE);
+  let source = format!("{SOURCE}\n$: {{\n  button {{\n    m");
+  let items = complete(&state, &source, source.len());
+  let w = items.iter().find(|item| item.label == "w").unwrap();
+
+  assert_eq!(w.detail.as_deref(), Some("width"));
+
+  let source = format!("{SOURCE}\n$: {{\n  b");
+
+  assert!(complete(&state, &source, source.len()).is_empty());
+}
@@ -0,0 +1,58 @@
+use crate::tests::common::analyze;
+
+use tower_lsp::lsp_types::{DiagnosticSeverity, NumberOrString, Position};
+
+/// A hard error maps to an LSP error on the exact range of
+/// its span, coded by the stable kebab-case id.
+#[test]
+fn error_range_and_severity() {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(
+    dir.path(),
+    "fun main() {\n  imu x: str = 42;\n  showln(x);\n}\n",
+  );
+
+  let diagnostic = state
+    .diagnostics
+    .iter()
+    .find(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+    .expect("the mismatched initializer is an error");
+
+  assert_eq!(diagnostic.source.as_deref(), Some("zo"));
+  assert_eq!(diagnostic.range.start.line, 1);
+  assert!(diagnostic.range.start <= diagnostic.range.end);
+  assert!(diagnostic.range.end <= Position::new(1, 18));
+  assert!(matches!(
+    &diagnostic.code,
+    Some(NumberOrString::String(code)) if !code.is_empty()
+  ));
+}
+
+/// A lint keeps its warning severity, and a clean file
+/// publishes nothing.
+#[test]
+fn lint_is_a_warning() {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(
+    dir.path(),
+    "fun main() {\n  imu BadName: int = 1;\n  check(BadName == 1);\n}\n",
+  );
+
+  let warning = state
+    .diagnostics
+    .iter()
+    .find(|d| d.severity == Some(DiagnosticSeverity::WARNING))
+    .expect("the misnamed binding is a warning");
+
+  assert_eq!(warning.range.start.line, 1);
+  assert!(
+    state
+      .diagnostics
+      .iter()
+      .all(|d| d.severity != Some(DiagnosticSeverity::ERROR))
+  );
+
+  let clean = analyze(dir.path(), "fun main() {\n  showln(\"hi\");\n}\n");
+
+  assert!(clean.diagnostics.is_empty(), "{:?}", clean.diagnostics);
+}
@@ -0,0 +1,91 @@
+use crate::hints::hints;
+use crate::tests::common::analyze;
+
+use tower_lsp::lsp_types::{InlayHintKind, InlayHintLabel};
+
+/// Every hint of `source` as `(line, character, label, kind)`.
+fn all(source: &str) -> Vec<(u32, u32, String, Option<InlayHintKind>)> {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), source);
+
+  hints(&state, (0, source.len() as u32))
+    .into_iter()
+    .map(|hint| {
+      let InlayHintLabel::String(label) = hint.label else {
+        panic!("hints are plain labels");
+      };
+
+      (
+        hint.position.line,
+        hint.position.character,
+        label,
+        hint.kind,
+      )
+    })
+    .collect()
+}
+
+/// A `:=` binding shows the type inference settled on, right
+/// after its name.
+#[test]
+fn walrus_binding_shows_its_inferred_type() {
+  let hints = all(
+    "fun main() {
+  imu n := 2 + 3;
+  imu s := \"zo\";
+  imu t: int = n;
+}
+",
+  );
+
+  assert_eq!(
+    hints,
+    [
+      (1, 7, ": int".into(), Some(InlayHintKind::TYPE)),
+      (2, 7, ": str".into(), Some(InlayHintKind::TYPE)),
+    ],
+  );
+}
+
+/// Each argument is labelled with its parameter's name — but
+/// not when the argument already spells it.
+#[test]
+fn call_arguments_show_their_parameter_names() {
+  let hints = all(
+    "fun area(w: int, h: int) -> int {
+  w * h
+}
+
+fun main() {
+  imu h: int = 4;
+  imu a: int = area(2, h);
+}
+",
+  );
+
+  assert_eq!(
+    hints,
+    [(6, 20, "w:".into(), Some(InlayHintKind::PARAMETER))],
+  );
+}
+
+/// A generic call shows what each `$T` was instantiated
+/// with, after the callee's name.
+#[test]
+fn generic_call_shows_its_instantiation() {
+  let hints = all(
+    "fun first<$T>(a: $T, b: $T) -> $T {
+  a
+}
+
+fun main() {
+  imu x: int = first(1, 2);
+}
+",
+  );
+
+  assert!(
+    hints.contains(&(5, 20, "<$T = int>".into(), Some(InlayHintKind::TYPE))),
+    "{hints:?}",
+  );
+}
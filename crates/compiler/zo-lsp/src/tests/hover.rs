@@ -0,0 +1,96 @@
+use crate::hover::hover;
+use crate::tests::common::{analyze, ident};
+
+const SOURCE: &str = "-! Something with a name.
+abstract Named {
+  fun name(self) -> str;
+  fun rank(self, other: Self) -> int;
+}
+
+-! Multiplies the two sides.
+fun area(w: int, h: int) -> int {
+  w * h
+}
+
+fun main() {
+  imu total := area(2, 3);
+  showln(total);
+}
+";
+
+/// The markdown shown over the `nth` identifier `name`.
+fn hover_on(name: &str, nth: usize) -> String {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), SOURCE);
+
+  hover(&state, ident(&state, name, nth)).unwrap()
+}
+
+/// A local without an annotation shows the type inference
+/// settled on.
+#[test]
+fn local_shows_its_inferred_type() {
+  assert_eq!(hover_on("total", 1), "```zo\ntotal: int\n```");
+}
+
+/// A function shows its signature, then its `-!` doc.
+#[test]
+fn function_shows_its_signature_and_doc() {
+  assert_eq!(
+    hover_on("area", 1),
+    "```zo\nfun area(w: int, h: int) -> int\n```\n---\nMultiplies the two sides.",
+  );
+}
+
+/// An abstract lists its methods, `self` written bare and
+/// `Self` read back.
+#[test]
+fn abstract_lists_its_methods() {
+  assert_eq!(
+    hover_on("Named", 0),
+    "```zo\nabstract Named {\n  fun name(self) -> str;\n  fun rank(self, other: \
+     Self) -> int;\n}\n```\n---\nSomething with a name.",
+  );
+}
+
+/// A function from `core` takes its doc from the module that
+/// declares it.
+#[test]
+fn core_function_reads_its_doc_from_core() {
+  let dir = tempfile::tempdir().unwrap();
+  let source = "load core::math::*;
+
+fun main() {
+  showln(lcm(4, 6));
+}
+";
+  let state = analyze(dir.path(), source);
+
+  assert_eq!(
+    hover(&state, ident(&state, "lcm", 0)).unwrap(),
+    "```zo\nfun math::lcm(a: int, b: int) -> int\n```\n---\nLeast common \
+     multiple of `a` and `b`. Returns `0` when\neither input is `0` (handles \
+     the `gcd(0, 0) == 0` edge).",
+  );
+}
+
+/// A generic names its `$T` as declared, not by the
+/// inference variable standing for it.
+#[test]
+fn generic_function_names_its_type_parameters() {
+  let dir = tempfile::tempdir().unwrap();
+  let source = "fun first<$T>(a: $T, b: $T) -> $T {
+  a
+}
+
+fun main() {
+  showln(first(1, 2));
+}
+";
+  let state = analyze(dir.path(), source);
+
+  assert_eq!(
+    hover(&state, ident(&state, "first", 1)).unwrap(),
+    "```zo\nfun first<$T>(a: $T, b: $T) -> $T\n```",
+  );
+}
@@ -0,0 +1,72 @@
+use crate::position::{LineIndex, apply_change};
+
+use tower_lsp::lsp_types::{Position, Range};
+
+fn range(start: (u32, u32), end: (u32, u32)) -> Option<Range> {
+  Some(Range::new(
+    Position::new(start.0, start.1),
+    Position::new(end.0, end.1),
+  ))
+}
+
+/// Columns count UTF-16 code units: `é` is one, `🦀` two.
+#[test]
+fn columns_are_utf16() {
+  let source = "imu é: str = \"🦀\";\nx";
+  let index = LineIndex::new(source);
+
+  // `:` sits at byte 6 (after the two-byte `é`), column 5.
+  assert_eq!(index.position(6), Position::new(0, 5));
+  assert_eq!(index.offset(0, 5), 6);
+
+  // `"` after the crab: byte 18, column 16.
+  let quote = source.rfind('"').unwrap() as u32;
+
+  assert_eq!(index.position(quote), Position::new(0, 16));
+  assert_eq!(index.offset(0, 16), quote);
+
+  assert_eq!(index.position(source.len() as u32), Position::new(1, 1));
+}
+
+/// An edit after multibyte text lands on the character the
+/// client meant.
+#[test]
+fn edits_after_multibyte_text() {
+  let mut text = String::from("imu é: int = 1;\nimu ü: int = 2;\n");
+
+  // Replace the `1` — column 13 in UTF-16, byte 14.
+  apply_change(&mut text, range((0, 13), (0, 14)), "10");
+
+  assert_eq!(text, "imu é: int = 10;\nimu ü: int = 2;\n");
+
+  apply_change(&mut text, range((1, 4), (1, 5)), "üü");
+
+  assert_eq!(text, "imu é: int = 10;\nimu üü: int = 2;\n");
+
+  let mut text = String::from("\"🦀\" + x");
+
+  // `x` is column 7: the crab counts two units.
+  apply_change(&mut text, range((0, 7), (0, 8)), "y");
+
+  assert_eq!(text, "\"🦀\" + y");
+}
+
+/// A line past the end clamps to the end of the text, a
+/// column past its line end to the line end.
+#[test]
+fn out_of_range_positions_clamp() {
+  let mut text = String::from("ab\ncd\n");
+
+  apply_change(&mut text, range((9, 0), (9, 4)), "ef");
+
+  assert_eq!(text, "ab\ncd\nef");
+
+  apply_change(&mut text, range((0, 40), (0, 40)), "!");
+
+  assert_eq!(text, "ab!\ncd\nef");
+
+  let index = LineIndex::new("ab\ncd");
+
+  assert_eq!(index.offset(1, 99), 5);
+  assert_eq!(index.offset(7, 0), 5);
+}
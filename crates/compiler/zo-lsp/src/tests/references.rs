@@ -0,0 +1,173 @@
+use crate::index::canonical;
+use crate::references::{check_renamable, references, rename, target};
+use crate::tests::common::{ident, index, uri};
+
+const LIB: &str = "pub fun area(w: int, h: int) -> int {
+  w * h
+}
+
+fun main() {
+  imu a: int = area(2, 3);
+  imu b: int = area(a, 4);
+}
+";
+
+/// The use-def map is inverted into every reference of the
+/// definition: the declaration first, then each use.
+#[test]
+fn references_invert_the_use_def_map() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let index = index(dir, &[("main.zo", LIB)]);
+  let main = uri(dir, "main.zo");
+  let state = index.get(&main).unwrap();
+
+  let area = target(state, ident(state, "area", 1)).unwrap();
+  let locations = references(&index, &area, true);
+  let lines = locations
+    .iter()
+    .map(|location| location.range.start.line)
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines, [0, 5, 6]);
+  assert!(locations.iter().all(|location| location.uri == main));
+
+  let without = references(&index, &area, false);
+
+  assert_eq!(without.len(), 2);
+
+  // A local resolves to its own binding, not the function.
+  let a = target(state, ident(state, "a", 1)).unwrap();
+  let uses = references(&index, &a, true);
+
+  assert_eq!(
+    uses
+      .iter()
+      .map(|location| location.range.start)
+      .collect::<Vec<_>>(),
+    [
+      tower_lsp::lsp_types::Position::new(5, 6),
+      tower_lsp::lsp_types::Position::new(6, 20),
+    ],
+  );
+}
+
+/// A rename that would collide with a function or a binding
+/// already in scope is refused, as is a keyword.
+#[test]
+fn rename_rejects_collisions() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let source = format!("{LIB}\nfun volume() -> int {{\n  0\n}}\n");
+  let index = index(dir, &[("main.zo", &source)]);
+  let state = index.get(&uri(dir, "main.zo")).unwrap();
+
+  let area = target(state, ident(state, "area", 0)).unwrap();
+
+  assert!(rename(&index, &area, "volume").is_err());
+  assert!(rename(&index, &area, "while").is_err());
+  assert!(rename(&index, &area, "1area").is_err());
+
+  let edit = rename(&index, &area, "surface").unwrap();
+  let edits = edit.changes.unwrap().into_values().next().unwrap();
+
+  assert_eq!(edits.len(), 3);
+  assert!(edits.iter().all(|edit| edit.new_text == "surface"));
+
+  // `b` is bound before the use of `a` in its initializer.
+  let a = target(state, ident(state, "a", 0)).unwrap();
+
+  assert!(rename(&index, &a, "b").is_err());
+  assert!(rename(&index, &a, "c").is_ok());
+}
+
+/// A function of a `lib.zo` pack is renamed in its module
+/// and in every file that loads it — the selective `load`
+/// list and a plain `load pack::name;` included.
+#[test]
+fn rename_crosses_packs_and_load_lists() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let geometry = "pub fun area(w: int, h: int) -> int {
+  w * h
+}
+
+pub fun volume(w: int, h: int, d: int) -> int {
+  area(w, h) * d
+}
+";
+  let main = "load geometry::(area, volume);
+
+fun main() {
+  showln(area(2, 3) + volume(1, 2, 3));
+}
+";
+  let shapes = "load geometry::area;
+
+fun square(side: int) -> int {
+  area(side, side)
+}
+";
+  let index = index(
+    dir,
+    &[
+      ("lib.zo", "pub pack geometry;\npub pack shapes;\n"),
+      ("geometry.zo", geometry),
+      ("shapes.zo", shapes),
+      ("main.zo", main),
+    ],
+  );
+  let state = index.get(&uri(dir, "main.zo")).unwrap();
+  let area = target(state, ident(state, "area", 1)).unwrap();
+
+  assert_eq!(area.path, canonical(&dir.join("geometry.zo")));
+
+  let mut changes = rename(&index, &area, "surface").unwrap().changes.unwrap();
+  let mut at = |name: &str| {
+    let mut edits = changes
+      .remove(&uri(dir, name))
+      .unwrap_or_default()
+      .into_iter()
+      .map(|edit| {
+        assert_eq!(edit.new_text, "surface");
+
+        (edit.range.start.line, edit.range.start.character)
+      })
+      .collect::<Vec<_>>();
+
+    edits.sort();
+    edits
+  };
+
+  assert_eq!(at("geometry.zo"), [(0, 8), (5, 2)]);
+  assert_eq!(at("main.zo"), [(0, 16), (3, 9)]);
+  assert_eq!(at("shapes.zo"), [(0, 15), (3, 2)]);
+  assert!(changes.is_empty(), "{changes:?}");
+
+  // `volume` is imported into `main.zo` already.
+  assert!(rename(&index, &area, "volume").is_err());
+}
+
+/// Names a core pack declares can't be renamed — the
+/// prepare step refuses them up front.
+#[test]
+fn prepare_rename_refuses_core_names() {
+  let dir = tempfile::tempdir().unwrap();
+  let dir = dir.path();
+  let source = "load core::math::*;
+
+fun main() {
+  showln(lcm(4, 6));
+}
+";
+  let index = index(dir, &[("main.zo", source)]);
+  let state = index.get(&uri(dir, "main.zo")).unwrap();
+  let lcm = target(state, ident(state, "lcm", 0)).unwrap();
+
+  assert!(check_renamable(&lcm).is_err());
+  assert!(rename(&index, &lcm, "least").is_err());
+
+  let main = target(state, ident(state, "main", 0)).unwrap();
+
+  assert!(check_renamable(&main).is_ok());
+}
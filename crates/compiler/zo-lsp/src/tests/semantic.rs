@@ -0,0 +1,205 @@
+use crate::semantic::{legend, tokens};
+use crate::tests::common::analyze;
+
+use tower_lsp::lsp_types::SemanticToken;
+
+/// Undoes the delta encoding: `(line, character, length)`
+/// of every token.
+fn absolute(tokens: &[SemanticToken]) -> Vec<(u32, u32, u32)> {
+  let mut line = 0;
+  let mut character = 0;
+
+  tokens
+    .iter()
+    .map(|token| {
+      if token.delta_line > 0 {
+        character = 0;
+      }
+
+      line += token.delta_line;
+      character += token.delta_start;
+
+      (line, character, token.length)
+    })
+    .collect()
+}
+
+const SOURCE: &str = "fun double(n: int) -> int {
+  n * 2
+}
+
+fun main() {
+  imu x: int = double(21);
+  imu y: int = x + 1;
+}
+";
+
+/// Deltas are relative to the previous token: the start
+/// column only within a line, the absolute column after a
+/// line break.
+#[test]
+fn tokens_are_delta_encoded() {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), SOURCE);
+  let encoded = tokens(&state, None);
+  let decoded = absolute(&encoded);
+
+  // `double` at its declaration and its call.
+  assert!(decoded.contains(&(0, 4, 6)), "{decoded:?}");
+  assert!(decoded.contains(&(5, 15, 6)), "{decoded:?}");
+  // `x` at its binding, then read by `y`'s.
+  assert!(decoded.contains(&(5, 6, 1)), "{decoded:?}");
+  assert!(decoded.contains(&(6, 15, 1)), "{decoded:?}");
+
+  // Tokens arrive in document order.
+  assert!(decoded.windows(2).all(|pair| pair[0] < pair[1]));
+
+  for (token, next) in encoded.iter().zip(&encoded[1..]) {
+    if next.delta_line == 0 {
+      assert!(next.delta_start >= token.length, "{encoded:?}");
+    }
+  }
+}
+
+/// A `range` request keeps the tokens inside it and encodes
+/// the first one against the document start.
+#[test]
+fn range_tokens_start_from_the_document_origin() {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), SOURCE);
+  let main = SOURCE.find("fun main").unwrap() as u32;
+  let decoded = absolute(&tokens(&state, Some((main, SOURCE.len() as u32))));
+
+  assert!(decoded.iter().all(|(line, ..)| *line >= 4), "{decoded:?}");
+  assert!(decoded.contains(&(5, 15, 6)), "{decoded:?}");
+}
+
+/// The token type and modifiers at `(line, character)`, by
+/// their legend names.
+fn class_at(
+  source: &str,
+  line: u32,
+  character: u32,
+) -> Option<(String, Vec<String>)> {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), source);
+  let encoded = tokens(&state, None);
+  let legend = legend();
+
+  absolute(&encoded)
+    .into_iter()
+    .zip(&encoded)
+    .find(|((at_line, at_character, _), _)| {
+      (*at_line, *at_character) == (line, character)
+    })
+    .map(|(_, token)| {
+      let kind = legend.token_types[token.token_type as usize].as_str();
+      let modifiers = legend
+        .token_modifiers
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| token.token_modifiers_bitset & (1 << bit) != 0)
+        .map(|(_, modifier)| modifier.as_str().to_string())
+        .collect();
+
+      (kind.to_string(), modifiers)
+    })
+}
+
+fn class(kind: &str, modifiers: &[&str]) -> Option<(String, Vec<String>)> {
+  Some((
+    kind.to_string(),
+    modifiers
+      .iter()
+      .map(|modifier| modifier.to_string())
+      .collect(),
+  ))
+}
+
+/// `$T` is a type parameter, not a local; a `mut` binding is
+/// told from an `imu` one at its declaration and at its uses;
+/// `pub`, `ffi`, `own self` and deprecated items carry their
+/// modifier.
+#[test]
+fn names_carry_their_semantic_modifiers() {
+  let source = "struct Cell {
+  n: int,
+}
+
+apply Cell {
+  pub fun take(own self) -> int {
+    self.n
+  }
+}
+
+pub ffi zo_tick() -> int;
+
+%% deprecated.
+fun old() {}
+
+fun first<$T>(a: $T, b: $T) -> $T {
+  a
+}
+
+fun main() {
+  mut m: int = 1;
+  imu i: int = first(m, 2);
+  m = i;
+}
+";
+
+  assert_eq!(
+    class_at(source, 5, 10),
+    class("method", &["declaration", "public"]),
+  );
+  assert_eq!(class_at(source, 5, 19), class("keyword", &["owned"]));
+  assert_eq!(
+    class_at(source, 10, 8),
+    class("function", &["declaration", "public", "ffi"]),
+  );
+  assert_eq!(
+    class_at(source, 13, 4),
+    class("function", &["declaration", "deprecated"]),
+  );
+  assert_eq!(class_at(source, 15, 11), class("typeParameter", &[]));
+  assert_eq!(class_at(source, 15, 18), class("typeParameter", &[]));
+  assert_eq!(
+    class_at(source, 15, 14),
+    class("parameter", &["declaration"])
+  );
+  assert_eq!(
+    class_at(source, 20, 6),
+    class("variable", &["declaration", "mutable"]),
+  );
+  assert_eq!(class_at(source, 21, 6), class("variable", &["declaration"]));
+  assert_eq!(class_at(source, 21, 21), class("variable", &["mutable"]));
+  assert_eq!(class_at(source, 22, 2), class("variable", &["mutable"]));
+  assert_eq!(class_at(source, 22, 6), class("variable", &[]));
+}
+
+/// Template tags, `@events` and `$:` properties get token
+/// types of their own.
+#[test]
+fn templates_and_styles_get_their_own_types() {
+  let source = "$: {
+  button {
+    color: white;
+  }
+}
+
+fun main() {
+  mut n: int = 0;
+  imu view: </> ::= <>
+    <button @click={fn() => n += 1}>+</button>
+  </>;
+
+  #render view;
+}
+";
+
+  assert_eq!(class_at(source, 1, 2), class("tag", &[]));
+  assert_eq!(class_at(source, 2, 4), class("styleProperty", &[]));
+  assert_eq!(class_at(source, 9, 5), class("tag", &[]));
+  assert_eq!(class_at(source, 9, 13), class("event", &[]));
+  assert_eq!(class_at(source, 9, 39), class("tag", &[]));
+}
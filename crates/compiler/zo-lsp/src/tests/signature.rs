@@ -0,0 +1,80 @@
+use crate::signature::signature_help;
+use crate::tests::common::analyze;
+
+use tower_lsp::lsp_types::{ParameterLabel, SignatureHelp};
+
+const SOURCE: &str = "struct Point {
+  x: int,
+}
+
+apply Point {
+  fun shift(self, dx: int, dy: int) -> int {
+    self.x + dx + dy
+  }
+}
+
+fun area(w: int, h: int) -> int {
+  w * h
+}
+
+fun main() {
+  imu p: Point = Point { x = 1 };
+}
+";
+
+/// Signature help with `typed` being written at the end of
+/// `main`'s body.
+fn help(typed: &str) -> Option<SignatureHelp> {
+  let dir = tempfile::tempdir().unwrap();
+  let state = analyze(dir.path(), SOURCE);
+  let at = SOURCE.rfind('}').unwrap();
+  let source = format!("{}  {typed}", &SOURCE[..at]);
+
+  signature_help(&state, &source, source.len())
+}
+
+/// The text of the active parameter of the first signature.
+fn active(help: &SignatureHelp) -> &str {
+  let signature = &help.signatures[0];
+  let idx = help.active_parameter.unwrap() as usize;
+  let ParameterLabel::LabelOffsets([start, end]) =
+    signature.parameters.as_ref().unwrap()[idx].label
+  else {
+    panic!("parameters are labelled by offsets");
+  };
+
+  &signature.label[start as usize..end as usize]
+}
+
+/// The active parameter follows the completed arguments —
+/// commas nested in an inner call don't count.
+#[test]
+fn active_parameter_follows_the_commas() {
+  let first = help("area(").unwrap();
+
+  assert_eq!(first.signatures[0].label, "fun area(w: int, h: int) -> int");
+  assert_eq!(active(&first), "w: int");
+
+  let second = help("area(area(1, 2), ").unwrap();
+
+  assert_eq!(second.active_parameter, Some(1));
+  assert_eq!(active(&second), "h: int");
+}
+
+/// A method call skips the `self` the receiver fills.
+#[test]
+fn method_call_skips_self() {
+  let help = help("p.shift(1, ").unwrap();
+
+  assert_eq!(
+    help.signatures[0].label,
+    "fun Point::shift(self, dx: int, dy: int) -> int",
+  );
+  assert_eq!(active(&help), "dy: int");
+}
+
+/// Outside of any call there is no help.
+#[test]
+fn no_help_outside_a_call() {
+  assert!(help("area(1, 2);").is_none());
+}
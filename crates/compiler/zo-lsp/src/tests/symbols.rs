@@ -0,0 +1,160 @@
+use crate::symbols::{flatten, fuzzy_score, outline, search};
+
+use tower_lsp::lsp_types::{DocumentSymbol, SymbolKind, Url};
+
+const SOURCE: &str = "struct Point {
+  x: int,
+  y: int,
+}
+
+enum Shape {
+  Circle,
+  Square,
+}
+
+abstract Named {
+  fun name(self) -> str;
+}
+
+apply Named for Point {
+  fun name(self) -> str {
+    \"point\"
+  }
+}
+
+fun area(w: int,
+  h: int) -> int {
+  w * h
+}
+
+fun main() {
+  imu view ::= <p>hi</p>;
+}
+";
+
+/// One line per symbol, indented by nesting: its kind, name
+/// and detail.
+fn lines(symbols: &[DocumentSymbol], depth: usize, out: &mut Vec<String>) {
+  for symbol in symbols {
+    let kind = match symbol.kind {
+      SymbolKind::STRUCT => "struct",
+      SymbolKind::FIELD => "field",
+      SymbolKind::ENUM => "enum",
+      SymbolKind::ENUM_MEMBER => "variant",
+      SymbolKind::INTERFACE => "abstract",
+      SymbolKind::OBJECT => "apply",
+      SymbolKind::METHOD => "method",
+      SymbolKind::FUNCTION => "fun",
+      SymbolKind::VARIABLE => "template",
+      _ => "?",
+    };
+    let detail = symbol
+      .detail
+      .as_deref()
+      .map_or_else(String::new, |detail| format!(" {detail}"));
+
+    out.push(format!(
+      "{}{kind} {}{detail}",
+      "  ".repeat(depth),
+      symbol.name
+    ));
+    lines(
+      symbol.children.as_deref().unwrap_or_default(),
+      depth + 1,
+      out,
+    );
+  }
+}
+
+/// Items nest as in source — fields under their struct,
+/// methods under their abstract or `apply`, a template under
+/// its function — and a signature split over lines reads as
+/// one.
+#[test]
+fn document_symbols_nest_as_in_source() {
+  let mut out = Vec::new();
+
+  lines(&outline(SOURCE), 0, &mut out);
+
+  assert_eq!(
+    out,
+    [
+      "struct Point",
+      "  field x",
+      "  field y",
+      "enum Shape",
+      "  variant Circle",
+      "  variant Square",
+      "abstract Named",
+      "  method name (self) -> str",
+      "apply Named for Point apply",
+      "  method name (self) -> str",
+      "fun area (w: int, h: int) -> int",
+      "fun main ()",
+      "  template view </>",
+    ],
+  );
+}
+
+/// A symbol's range covers its whole item, its selection the
+/// name alone.
+#[test]
+fn document_symbols_select_the_name() {
+  let symbols = outline(SOURCE);
+  let area = symbols.iter().find(|symbol| symbol.name == "area").unwrap();
+
+  assert_eq!((area.range.start.line, area.range.end.line), (20, 23));
+  assert_eq!(
+    (
+      area.selection_range.start.character,
+      area.selection_range.end.character
+    ),
+    (4, 8),
+  );
+}
+
+/// Flattened symbols name their container; the search keeps
+/// the fuzzy matches only, best first.
+#[test]
+fn workspace_search_ranks_fuzzy_matches() {
+  let uri = Url::parse("file:///main.zo").unwrap();
+  let flat = flatten(&uri, &outline(SOURCE));
+
+  let x = flat.iter().find(|symbol| symbol.name == "x").unwrap();
+
+  assert_eq!(x.container_name.as_deref(), Some("Point"));
+
+  let names = |query: &str| {
+    search(query, flat.clone().into_iter())
+      .into_iter()
+      .map(|symbol| symbol.name)
+      .collect::<Vec<_>>()
+  };
+
+  assert_eq!(names("sq"), ["Square"]);
+  // A run from the start, then a run mid-word, then
+  // scattered matches.
+  assert_eq!(names("ar"), ["area", "Square", "Named for Point"]);
+  assert_eq!(names("na"), ["name", "name", "Named", "Named for Point"]);
+  assert!(names("zz").is_empty());
+}
+
+/// Every query character must appear in order; a match at a
+/// word boundary, a run of consecutive matches and a shorter
+/// name all rank higher.
+#[test]
+fn fuzzy_score_prefers_boundaries_runs_and_short_names() {
+  assert_eq!(fuzzy_score("pa", "ap"), None);
+  assert!(fuzzy_score("PT", "point").is_some());
+  assert_eq!(fuzzy_score("PT", "point"), fuzzy_score("pt", "point"));
+
+  // Boundary: `s` starting the word beats `s` inside it.
+  assert!(fuzzy_score("s", "shape") > fuzzy_score("s", "ease"));
+  // After `_` and at a case change count as boundaries too.
+  assert!(fuzzy_score("c", "is_circle") > fuzzy_score("c", "isacircle"));
+  assert!(fuzzy_score("c", "isCircle") > fuzzy_score("c", "isacircle"));
+  // A run beats scattered matches.
+  assert!(fuzzy_score("ar", "area") > fuzzy_score("ae", "area"));
+  // Ties go to the shorter name.
+  assert!(fuzzy_score("main", "main") > fuzzy_score("main", "mains"));
+}
@@ -46,15 +46,89 @@ fn matches_selective(name: &str, filter: &str) -> bool {
 #[derive(Clone)]
 pub struct AbstractDef {
   pub methods: Vec<AbstractMethod>,
-  /// Aggregate of each method's `dyn_safe`. `false`
-  /// blocks `any <Abstract>` resolution at the
-  /// annotation site — the vtable calling convention
-  /// can't carry `Self` outside the receiver.
+  /// Super-abstracts named after the colon (`abstract Ord:
+  /// Eq`), in declaration order. A type implements this
+  /// abstract only once it implements each of them too.
+  pub supers: Vec<Symbol>,
+  /// Aggregate of each method's `dyn_safe`, inherited ones
+  /// included. `false` blocks `any <Abstract>` resolution
+  /// at the annotation site — the vtable calling
+  /// convention can't carry `Self` outside the receiver.
   pub dyn_safe: bool,
+  /// Default method bodies, which every implementor that
+  /// doesn't override them compiles as its own — carried
+  /// as tree slices so an `apply` in an importing module
+  /// can compile them too.
+  pub defaults: Vec<AbstractDefault>,
   /// Source span of the `abstract` keyword.
   pub span: Span,
 }
 
+/// One default method body of an `abstract`, in portable form.
+#[derive(Clone)]
+pub struct AbstractDefault {
+  /// The method the body defines.
+  pub method: Symbol,
+  /// The `fun … { … }` subtree, from `fun` to the closing `}`.
+  pub slice: ExportedTreeSlice,
+}
+
+/// `name` and every abstract it extends, supers first —
+/// depth first, each once, so a diamond lists the shared
+/// super a single time and a cycle ends. Unknown names
+/// are skipped.
+pub fn abstract_lineage(
+  defs: &FxHashMap<Symbol, AbstractDef>,
+  name: Symbol,
+) -> Vec<Symbol> {
+  fn visit(
+    defs: &FxHashMap<Symbol, AbstractDef>,
+    name: Symbol,
+    lineage: &mut Vec<Symbol>,
+  ) {
+    let Some(def) = defs.get(&name) else {
+      return;
+    };
+
+    if lineage.contains(&name) {
+      return;
+    }
+
+    // Claimed before the supers so a cycle stops here.
+    lineage.push(name);
+
+    let at = lineage.len();
+
+    for &sup in &def.supers {
+      visit(defs, sup, lineage);
+    }
+
+    // Back behind the supers it just pulled in.
+    lineage[at - 1..].rotate_left(1);
+  }
+
+  let mut lineage = Vec::new();
+
+  visit(defs, name, &mut lineage);
+
+  lineage
+}
+
+/// The methods an `any <Abstract>` value answers to, in
+/// vtable order: the lineage's, supers first. Each comes
+/// with the abstract declaring it — the one whose `apply`
+/// implements it. `Insn::DynDispatch.method_index` indexes
+/// this list.
+pub fn vtable_methods(
+  defs: &FxHashMap<Symbol, AbstractDef>,
+  name: Symbol,
+) -> Vec<(Symbol, &AbstractMethod)> {
+  abstract_lineage(defs, name)
+    .into_iter()
+    .flat_map(|owner| defs[&owner].methods.iter().map(move |m| (owner, m)))
+    .collect()
+}
+
 /// A single method signature in an abstract definition.
 #[derive(Clone)]
 pub struct AbstractMethod {
@@ -143,6 +217,7 @@ pub struct ExportedStruct {
 }
 
 /// Exported compile-time constant (`val`).
+#[derive(Clone)]
 pub struct ExportedConst {
   pub name: Symbol,
   pub ty_id: TyId,
@@ -256,6 +331,18 @@ pub struct SplicedComponentBody {
   pub slot: Slot,
 }
 
+/// Post-splice metadata for one imported abstract default —
+/// `with_imports` registers the range as the body every
+/// implementor of `abstract_name` inherits for `method`.
+#[derive(Clone, Debug)]
+pub struct SplicedAbstractDefault {
+  pub abstract_name: Symbol,
+  pub method: Symbol,
+  /// Range in the importer's tree where the splice landed,
+  /// `(start_inclusive, end_exclusive)`.
+  pub range: (u32, u32),
+}
+
 /// Imported module symbols to pre-load into the executor.
 /// Built by the compiler driver (one per loaded module's
 /// transitive scope) and handed straight to
@@ -312,6 +399,9 @@ pub struct ImportedSymbols {
   /// `(name, range)` in the executor's component registry
   /// instead of the generic re-exec tables.
   pub component_bodies: Vec<SplicedComponentBody>,
+  /// Post-splice default method bodies of the imported
+  /// [`Self::abstract_defs`], from `splice_abstract_defaults`.
+  pub abstract_default_bodies: Vec<SplicedAbstractDefault>,
 }
 
 impl ImportedSymbols {
@@ -337,6 +427,7 @@ impl ImportedSymbols {
       && self.generic_bodies.is_empty()
       && self.exported_component_bodies.is_empty()
       && self.component_bodies.is_empty()
+      && self.abstract_default_bodies.is_empty()
   }
 }
 
@@ -384,6 +475,32 @@ pub fn splice_component_bodies(
   out
 }
 
+/// Splices the default method bodies of every abstract in
+/// `defs` into the importer's tree — same rebase as generic
+/// bodies; `with_imports` registers the ranges as the bodies
+/// implementors inherit.
+pub fn splice_abstract_defaults(
+  tree: &mut Tree,
+  literals: &mut LiteralStore,
+  defs: &FxHashMap<Symbol, AbstractDef>,
+) -> Vec<SplicedAbstractDefault> {
+  let mut out = Vec::new();
+
+  for (abstract_name, def) in defs {
+    for default in &def.defaults {
+      if let Some(range) = splice_tree_slice(tree, literals, &default.slice) {
+        out.push(SplicedAbstractDefault {
+          abstract_name: *abstract_name,
+          method: default.method,
+          range,
+        });
+      }
+    }
+  }
+
+  out
+}
+
 /// Splices one portable tree slice into the importer's tree,
 /// rebasing child indices, replaying literal payloads into the
 /// importer's `LiteralStore`, and re-attaching node values.
@@ -548,6 +665,7 @@ pub enum ExportedLiteral {
 }
 
 /// Exported symbols from a compiled module.
+#[derive(Clone)]
 pub struct ModuleExports {
   /// The function definitions.
   pub funs: Vec<FunDef>,
@@ -595,6 +713,10 @@ pub struct ModuleExports {
   /// custom `Type::eq` instead of falling back to a
   /// primitive pointer compare.
   pub abstract_impls: FxHashMap<(Symbol, Symbol), AbstractImpl>,
+  /// Every abstract the module's executor knows — its own
+  /// plus the ones it imported — so an importer can
+  /// `apply` them and inherit their default bodies.
+  pub abstract_defs: FxHashMap<Symbol, AbstractDef>,
 }
 
 /// Everything a compiled module hands the export harvester — the
@@ -606,6 +728,7 @@ pub struct ModuleHarvest<'a> {
   pub generic_bodies: Vec<ExportedGenericBody>,
   pub component_bodies: Vec<ExportedComponentBody>,
   pub abstract_impls: FxHashMap<(Symbol, Symbol), AbstractImpl>,
+  pub abstract_defs: FxHashMap<Symbol, AbstractDef>,
 }
 
 /// Extracts pub exports from a compiled module's SIR.
@@ -626,6 +749,7 @@ pub fn extract_exports(
     generic_bodies: src_generic_bodies,
     component_bodies: src_component_bodies,
     abstract_impls: src_abstract_impls,
+    abstract_defs,
   } = harvest;
   // Funs that ship a generic body — only these need
   // `type_params` carried across. Without this filter,
@@ -673,6 +797,7 @@ pub fn extract_exports(
         self_kind,
         owning_pack,
         span,
+        env_slot,
         ..
       } => {
         let fn_name = interner.get(*name);
@@ -749,6 +874,7 @@ pub fn extract_exports(
           owning_pack: *owning_pack,
           span: *span,
           is_test: false,
+          env_slot: *env_slot,
         });
       }
 
@@ -986,6 +1112,7 @@ pub fn extract_exports(
       owning_pack: src_fun.owning_pack,
       span: Span::ZERO,
       is_test: false,
+      env_slot: src_fun.env_slot,
     });
   }
 
@@ -1065,5 +1192,6 @@ pub fn extract_exports(
     generic_bodies: src_generic_bodies,
     component_bodies: src_component_bodies,
     abstract_impls,
+    abstract_defs,
   }
 }
@@ -5,10 +5,12 @@ pub mod resolver;
 mod tests;
 
 pub use exports::{
-  AbstractDef, AbstractImpl, AbstractMethod, ExportedComponentBody,
-  ExportedConst, ExportedEnum, ExportedGenericBody, ExportedLiteral,
-  ExportedStruct, ExportedTreeSlice, ExportedVar, ImportedSymbols,
-  ModuleExports, ModuleHarvest, Slot, SplicedComponentBody, SplicedGenericBody,
-  extract_exports, splice_component_bodies, splice_generic_bodies,
+  AbstractDef, AbstractDefault, AbstractImpl, AbstractMethod,
+  ExportedComponentBody, ExportedConst, ExportedEnum, ExportedGenericBody,
+  ExportedLiteral, ExportedStruct, ExportedTreeSlice, ExportedVar,
+  ImportedSymbols, ModuleExports, ModuleHarvest, Slot, SplicedAbstractDefault,
+  SplicedComponentBody, SplicedGenericBody, abstract_lineage, extract_exports,
+  splice_abstract_defaults, splice_component_bodies, splice_generic_bodies,
+  vtable_methods,
 };
 pub use resolver::{ModuleResolver, ResolvedModule, translate_symbol};
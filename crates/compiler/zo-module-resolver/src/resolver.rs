@@ -3,6 +3,7 @@ use zo_interner::{Interner, Symbol};
 use rustc_hash::FxHashMap as HashMap;
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Re-interns a symbol from one interner into another.
 pub fn translate_symbol(
@@ -34,6 +35,9 @@ pub struct ModuleResolver {
   search_paths: Vec<PathBuf>,
   /// Cache: stringified module path -> resolved source.
   cache: HashMap<String, ResolvedModule>,
+  /// Canonical path -> text read in place of the file on disk
+  /// (an editor's unsaved buffers).
+  overlay: HashMap<PathBuf, Arc<str>>,
 }
 
 impl ModuleResolver {
@@ -42,9 +46,37 @@ impl ModuleResolver {
     Self {
       search_paths,
       cache: HashMap::default(),
+      overlay: HashMap::default(),
     }
   }
 
+  /// Reads the files in `overlay` — keyed by canonical path —
+  /// from it rather than from disk.
+  pub fn set_overlay(&mut self, overlay: HashMap<PathBuf, Arc<str>>) {
+    self.overlay = overlay;
+    self.cache.clear();
+  }
+
+  /// The text of the file at `path`: its overlay entry, else
+  /// its content on disk.
+  pub fn read(&self, path: &Path) -> Option<String> {
+    Self::read_source(&self.overlay, path)
+  }
+
+  fn read_source(
+    overlay: &HashMap<PathBuf, Arc<str>>,
+    path: &Path,
+  ) -> Option<String> {
+    if !overlay.is_empty()
+      && let Ok(canonical) = std::fs::canonicalize(path)
+      && let Some(text) = overlay.get(&canonical)
+    {
+      return Some(text.to_string());
+    }
+
+    std::fs::read_to_string(path).ok()
+  }
+
   /// The search-path roots used for module resolution.
   /// Exposed so callers (zo-compiler's implicit-pack rule)
   /// can tell "directly under a root" from "nested in a
@@ -89,7 +121,9 @@ impl ModuleResolver {
 
     for search_path in &self.search_paths {
       // Try direct path: {search_path}/{seg0}/{seg1}/...zo
-      if let Some(resolved) = Self::try_resolve(search_path, &names, None) {
+      if let Some(resolved) =
+        Self::try_resolve(&self.overlay, search_path, &names, None)
+      {
         self.cache.insert(key.clone(), resolved);
 
         return self.cache.get(&key);
@@ -101,7 +135,7 @@ impl ModuleResolver {
         && let Some(dir_name) = search_path.file_name()
         && dir_name == names[0]
         && let Some(resolved) =
-          Self::try_resolve(search_path, &names[1..], None)
+          Self::try_resolve(&self.overlay, search_path, &names[1..], None)
       {
         self.cache.insert(key.clone(), resolved);
 
@@ -115,9 +149,12 @@ impl ModuleResolver {
         let last = names.last().unwrap().to_string();
         let parent = &names[..names.len() - 1];
 
-        if let Some(resolved) =
-          Self::try_resolve(search_path, parent, Some(last.clone()))
-        {
+        if let Some(resolved) = Self::try_resolve(
+          &self.overlay,
+          search_path,
+          parent,
+          Some(last.clone()),
+        ) {
           self.cache.insert(key.clone(), resolved);
 
           return self.cache.get(&key);
@@ -127,8 +164,12 @@ impl ModuleResolver {
         if !parent.is_empty()
           && let Some(dir_name) = search_path.file_name()
           && dir_name == parent[0]
-          && let Some(resolved) =
-            Self::try_resolve(search_path, &parent[1..], Some(last))
+          && let Some(resolved) = Self::try_resolve(
+            &self.overlay,
+            search_path,
+            &parent[1..],
+            Some(last),
+          )
         {
           self.cache.insert(key.clone(), resolved);
 
@@ -145,6 +186,7 @@ impl ModuleResolver {
   /// no `lib.zo` body; folder enumeration for `::*` loads is
   /// driven by [`Self::resolve_folder_entries`].
   fn try_resolve(
+    overlay: &HashMap<PathBuf, Arc<str>>,
     base: &Path,
     names: &[&str],
     selective: Option<String>,
@@ -162,7 +204,7 @@ impl ModuleResolver {
     let zo_path = file_path.with_extension("zo");
 
     if zo_path.is_file() {
-      return Self::read_module(&zo_path, selective);
+      return Self::read_module(overlay, &zo_path, selective);
     }
 
     None
@@ -232,16 +274,15 @@ impl ModuleResolver {
 
   /// Reads a .zo file into a ResolvedModule.
   fn read_module(
+    overlay: &HashMap<PathBuf, Arc<str>>,
     path: &Path,
     selective_symbol: Option<String>,
   ) -> Option<ResolvedModule> {
-    std::fs::read_to_string(path)
-      .ok()
-      .map(|source| ResolvedModule {
-        path: path.to_path_buf(),
-        source,
-        selective_symbol,
-      })
+    Self::read_source(overlay, path).map(|source| ResolvedModule {
+      path: path.to_path_buf(),
+      source,
+      selective_symbol,
+    })
   }
 
   /// Builds a cache key from interned symbols.
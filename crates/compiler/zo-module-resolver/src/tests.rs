@@ -12,8 +12,8 @@
 //! pipeline.
 
 use crate::exports::{
-  ExportedGenericBody, ExportedLiteral, ExportedTreeSlice, ModuleHarvest,
-  splice_generic_bodies,
+  AbstractDef, ExportedGenericBody, ExportedLiteral, ExportedTreeSlice,
+  ModuleHarvest, abstract_lineage, splice_generic_bodies,
 };
 
 use zo_interner::Interner;
@@ -312,6 +312,7 @@ fn extract_exports_round_trips_abstract_impls() {
       generic_bodies: Vec::new(),
       component_bodies: Vec::new(),
       abstract_impls: src_impls,
+      abstract_defs: rustc_hash::FxHashMap::default(),
     },
     None,
     &interner,
@@ -363,6 +364,7 @@ fn extract_exports_filters_abstract_impls_on_selective_load() {
       generic_bodies: Vec::new(),
       component_bodies: Vec::new(),
       abstract_impls: src_impls,
+      abstract_defs: rustc_hash::FxHashMap::default(),
     },
     Some("Point"),
     &interner,
@@ -375,3 +377,35 @@ fn extract_exports_filters_abstract_impls_on_selective_load() {
   );
   assert!(exports.abstract_impls.contains_key(&(eq_sym, point_sym)),);
 }
+
+/// `abstract Ord: Eq + Hash` over `Eq: Partial` and `Hash:
+/// Partial` — the diamond's shared `Partial` comes once,
+/// supers ahead of the abstracts extending them. A cycle
+/// through `Partial: Ord` ends instead of recursing.
+#[test]
+fn abstract_lineage_lists_supers_first_and_once() {
+  let mut interner = Interner::new();
+  let [ord, eq, hash, partial] =
+    ["Ord", "Eq", "Hash", "Partial"].map(|name| interner.intern(name));
+
+  let def = |supers: &[_]| AbstractDef {
+    methods: Vec::new(),
+    supers: supers.to_vec(),
+    dyn_safe: true,
+    defaults: Vec::new(),
+    span: Span::ZERO,
+  };
+
+  let mut defs = rustc_hash::FxHashMap::default();
+  defs.insert(ord, def(&[eq, hash]));
+  defs.insert(eq, def(&[partial]));
+  defs.insert(hash, def(&[partial]));
+  defs.insert(partial, def(&[]));
+
+  assert_eq!(abstract_lineage(&defs, ord), [partial, eq, hash, ord]);
+
+  defs.insert(partial, def(&[ord]));
+
+  assert_eq!(abstract_lineage(&defs, ord), [partial, eq, hash, ord]);
+  assert_eq!(abstract_lineage(&defs, partial), [eq, hash, ord, partial]);
+}
@@ -3,4 +3,7 @@ mod ownership;
 #[cfg(test)]
 mod tests;
 
-pub use ownership::Ownership;
+pub use ownership::{
+  DropDecision, DropFact, Elision, MoveFact, MoveKind, Ownership,
+  OwnershipTrace,
+};
@@ -26,6 +26,15 @@
 //! - moved on some-but-not-all paths → `ConditionalMove` error,
 //! - otherwise → KEEP, lowered to `Load Local; Call <dtor>` so
 //!   codegen reuses the ordinary manual-free path.
+//!
+//! A capturing closure's env is released by the
+//! `Insn::ClosureEnvFree` after the call it was built for. A
+//! call that hands the env to a runtime taking ownership of it
+//! — the user-data slot of an `ffi` marked `%% owns_env.`,
+//! directly or through the params of the functions that
+//! forward it there — keeps it alive past its return; the
+//! runtime frees it, so that release is ELIDED. Any other
+//! `ffi` only borrows the env for the call.
 
 use zo_error::{Error, ErrorKind};
 use zo_interner::{Interner, Symbol};
@@ -62,6 +71,78 @@ enum DtorSlot {
   Ambiguous,
 }
 
+/// Which trigger moved a binding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MoveKind {
+  /// The receiver of a consuming (`own self`) call.
+  Consume,
+  /// A whole-value copy into another binding.
+  Copy,
+  /// Returned out of the function.
+  Return,
+  /// Captured into a closure's environment.
+  Capture,
+}
+
+/// One move fact: `binding` is moved at `span`.
+#[derive(Clone, Copy, Debug)]
+pub struct MoveFact {
+  /// The function the move happens in.
+  pub function: Symbol,
+  /// The moved binding.
+  pub binding: Symbol,
+  /// The trigger.
+  pub kind: MoveKind,
+  /// The move site.
+  pub span: Span,
+}
+
+/// Why a scope-exit drop was elided.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Elision {
+  /// The binding aliases a value it does not own.
+  Borrowed,
+  /// The binding was already flagged as used after a move.
+  Reported,
+  /// The type has no unique destructor.
+  NoDestructor,
+  /// Moved on every path reaching the drop.
+  Moved,
+  /// Moved on some paths only — reported as `ConditionalMove`.
+  Conditional,
+}
+
+/// The fate of one scope-exit drop.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DropDecision {
+  /// Lowered to a call of the destructor `dtor`.
+  Keep { dtor: Symbol },
+  /// Removed.
+  Elide(Elision),
+}
+
+/// One drop decision: `binding`'s drop at `span`.
+#[derive(Clone, Copy, Debug)]
+pub struct DropFact {
+  /// The function the drop sits in.
+  pub function: Symbol,
+  /// The dropped binding.
+  pub binding: Symbol,
+  /// The scope exit.
+  pub span: Span,
+  /// What the pass did with it.
+  pub decision: DropDecision,
+}
+
+/// Every move fact and drop decision of one run, in program
+/// order — recorded only under [`Ownership::with_trace`]
+/// (`--emit=ownership`).
+#[derive(Debug, Default)]
+pub struct OwnershipTrace {
+  pub moves: Vec<MoveFact>,
+  pub drops: Vec<DropFact>,
+}
+
 /// Per-function move facts, gathered in one scan and then
 /// consumed by the dataflow and the reporting/elision replay.
 #[derive(Default)]
@@ -70,6 +151,9 @@ struct Facts {
   bit_of: HashMap<Symbol, usize>,
   /// Instruction index → the binding it moves.
   move_set_at: HashMap<usize, Symbol>,
+  /// Instruction index → the trigger of the move recorded
+  /// there.
+  move_kind_at: HashMap<usize, MoveKind>,
   /// The subset of `move_set_at` that are consuming calls —
   /// drives `DoubleFree` (vs `UseAfterMove`).
   consume_call_at: HashMap<usize, Symbol>,
@@ -94,6 +178,20 @@ impl Facts {
 
     *self.bit_of.entry(sym).or_insert(next)
   }
+
+  /// Record that instruction `idx` moves `sym`.
+  fn record_move(
+    &mut self,
+    idx: usize,
+    sym: Symbol,
+    kind: MoveKind,
+    span: Span,
+  ) {
+    self.bit_for(sym);
+    self.move_set_at.insert(idx, sym);
+    self.move_kind_at.insert(idx, kind);
+    self.move_span.entry(sym).or_insert(span);
+  }
 }
 
 /// The intrinsic data of one `Insn::Drop` under decision.
@@ -108,13 +206,60 @@ struct DropPoint {
 enum DropAction {
   /// Remove the marker — already freed, moved, or no
   /// destructor exists.
-  Elide,
+  Elide(Elision),
   /// Lower to `Load Local(local); Call dtor(loaded)`.
   Lower {
     local: Symbol,
     ty_id: TyId,
     dtor: Dtor,
   },
+  /// Remove a `ClosureEnvFree` — its env escaped to the
+  /// runtime, which frees it.
+  Escaped,
+}
+
+/// A function as a call names it: `(name, callee_pack)`.
+type FunKey = (Symbol, Option<Symbol>);
+
+/// Where closure envs leave for the runtime, which frees them
+/// itself once the closure has run.
+#[derive(Default)]
+struct EnvEscapes {
+  /// The `ffi`s that take ownership of the env passed in
+  /// their user-data slot, keyed to that slot's arg index.
+  ffis: HashMap<FunKey, u32>,
+  /// `(function, param)` pairs whose argument is passed on to
+  /// an owning `ffi`, a `TaskSpawn` or another escaping param.
+  params: HashSet<(FunKey, u32)>,
+}
+
+impl EnvEscapes {
+  /// The values `insn` passes to an escaping position.
+  fn escaping_args(&self, insn: &Insn) -> Vec<ValueId> {
+    match insn {
+      Insn::Call {
+        name,
+        callee_pack,
+        args,
+        ..
+      } => {
+        let callee = (*name, *callee_pack);
+        let slot = self.ffis.get(&callee).copied();
+
+        args
+          .iter()
+          .enumerate()
+          .filter(|(j, _)| {
+            slot == Some(*j as u32)
+              || self.params.contains(&(callee, *j as u32))
+          })
+          .map(|(_, arg)| *arg)
+          .collect()
+      }
+      Insn::TaskSpawn { args, .. } => args.clone(),
+      _ => Vec::new(),
+    }
+  }
 }
 
 /// The move-checking + drop-elaboration pass over a whole SIR
@@ -123,6 +268,8 @@ pub struct Ownership<'a> {
   sir: &'a mut Sir,
   interner: &'a Interner,
   ty: &'a TyChecker,
+  /// Move facts and drop decisions, when tracing.
+  trace: Option<OwnershipTrace>,
 }
 
 impl<'a> Ownership<'a> {
@@ -132,7 +279,25 @@ impl<'a> Ownership<'a> {
     interner: &'a Interner,
     ty: &'a TyChecker,
   ) -> Self {
-    Self { sir, interner, ty }
+    Self {
+      sir,
+      interner,
+      ty,
+      trace: None,
+    }
+  }
+
+  /// Records every move fact and drop decision while checking,
+  /// for [`Self::into_trace`].
+  pub fn with_trace(mut self) -> Self {
+    self.trace = Some(OwnershipTrace::default());
+    self
+  }
+
+  /// The recorded trace — `None` unless built
+  /// [`Self::with_trace`].
+  pub fn into_trace(self) -> Option<OwnershipTrace> {
+    self.trace
   }
 
   /// Checks every function body, reports move violations, and
@@ -149,11 +314,13 @@ impl<'a> Ownership<'a> {
     }
 
     let (kinds, dtors) = self.self_kinds_and_destructors();
+    let escapes = self.env_escapes();
 
     // Drop actions keyed by ORIGINAL instruction index. The
     // analysis below indexes into `self.sir.instructions`
     // unchanged; the single compaction at the end applies them.
     let mut actions: HashMap<usize, DropAction> = HashMap::default();
+    let mut trace = self.trace.take();
 
     let n = self.sir.instructions.len();
     let mut i = 0;
@@ -173,13 +340,59 @@ impl<'a> Ownership<'a> {
         end += 1;
       }
 
-      self.check_fn(start, end, &kinds, &dtors, &mut actions);
+      self.check_fn(start, end, &kinds, &dtors, &mut actions, trace.as_mut());
+      self.elide_escaped_env_frees(start, end, &escapes, &mut actions);
+
+      if let Some(trace) = trace.as_mut() {
+        self.trace_drops(start, end, &actions, trace);
+      }
+
       i = end;
     }
 
+    self.trace = trace;
     self.elaborate(actions);
   }
 
+  /// Appends the drop decisions of the function `[start, end)`
+  /// to `trace`.
+  fn trace_drops(
+    &self,
+    start: usize,
+    end: usize,
+    actions: &HashMap<usize, DropAction>,
+    trace: &mut OwnershipTrace,
+  ) {
+    let Insn::FunDef { name: function, .. } = self.sir.instructions[start]
+    else {
+      return;
+    };
+
+    for idx in start..end {
+      let (Some(action), Insn::Drop { local, .. }) =
+        (actions.get(&idx), &self.sir.instructions[idx])
+      else {
+        continue;
+      };
+
+      let decision = match action {
+        DropAction::Elide(elision) => DropDecision::Elide(*elision),
+        DropAction::Lower { dtor, .. } => {
+          DropDecision::Keep { dtor: dtor.name }
+        }
+        // Only ever recorded for a `ClosureEnvFree`.
+        DropAction::Escaped => continue,
+      };
+
+      trace.drops.push(DropFact {
+        function,
+        binding: *local,
+        span: self.sir.spans[idx],
+        decision,
+      });
+    }
+  }
+
   /// One pass over the instruction stream yielding both:
   /// - `kinds`: mangled function name → receiver mode, for
   ///   matching a `Call` against a consuming (`own self`)
@@ -241,6 +454,94 @@ impl<'a> Ownership<'a> {
     (kinds, dtors)
   }
 
+  /// Where the program hands closure envs to the runtime: its
+  /// owning `ffi`s, and every `(function, param)` whose argument is
+  /// passed on to an escaping position. Solved to a fixed point
+  /// — a forwarding chain can run in any order through the
+  /// merged program.
+  fn env_escapes(&self) -> EnvEscapes {
+    let insns = &self.sir.instructions;
+    let mut escapes = EnvEscapes::default();
+    let mut bodies: Vec<(FunKey, usize, usize)> = Vec::new();
+
+    for (idx, insn) in insns.iter().enumerate() {
+      if let Insn::FunDef {
+        name,
+        owning_pack,
+        env_slot,
+        ..
+      } = insn
+      {
+        if let Some(slot) = env_slot.filter(|slot| slot.owned) {
+          escapes.ffis.insert((*name, *owning_pack), slot.param);
+        }
+
+        if let Some(last) = bodies.last_mut() {
+          last.2 = idx;
+        }
+
+        bodies.push(((*name, *owning_pack), idx + 1, insns.len()));
+      }
+    }
+
+    loop {
+      let before = escapes.params.len();
+
+      for &(fun, start, end) in &bodies {
+        let mut param_of: HashMap<ValueId, u32> = HashMap::default();
+
+        for insn in &insns[start..end] {
+          if let Insn::Load {
+            dst,
+            src: LoadSource::Param(p),
+            ..
+          } = insn
+          {
+            param_of.insert(*dst, *p);
+          }
+        }
+
+        for insn in &insns[start..end] {
+          for arg in escapes.escaping_args(insn) {
+            if let Some(&p) = param_of.get(&arg) {
+              escapes.params.insert((fun, p));
+            }
+          }
+        }
+      }
+
+      if escapes.params.len() == before {
+        return escapes;
+      }
+    }
+  }
+
+  /// Marks each `ClosureEnvFree` in `[start, end)` whose env a
+  /// call hands to the runtime as `Escaped`.
+  fn elide_escaped_env_frees(
+    &self,
+    start: usize,
+    end: usize,
+    escapes: &EnvEscapes,
+    actions: &mut HashMap<usize, DropAction>,
+  ) {
+    let insns = &self.sir.instructions;
+
+    for idx in start..end {
+      let Insn::ClosureEnvFree { env } = &insns[idx] else {
+        continue;
+      };
+
+      let escaped = insns[start..end]
+        .iter()
+        .any(|insn| escapes.escaping_args(insn).contains(env));
+
+      if escaped {
+        actions.insert(idx, DropAction::Escaped);
+      }
+    }
+  }
+
   /// Resolve the destructor for a dropped value's type, or
   /// `None` when the type has no name (array / tuple / …) or
   /// no unique destructor.
@@ -264,7 +565,8 @@ impl<'a> Ownership<'a> {
 
   /// Move-checks one function body `[start, end)`, reporting
   /// violations and recording a `DropAction` for every
-  /// `Insn::Drop` it contains.
+  /// `Insn::Drop` it contains. Move facts land in `trace` when
+  /// tracing.
   fn check_fn(
     &self,
     start: usize,
@@ -272,6 +574,7 @@ impl<'a> Ownership<'a> {
     kinds: &HashMap<Symbol, SelfKind>,
     dtors: &HashMap<String, DtorSlot>,
     actions: &mut HashMap<usize, DropAction>,
+    trace: Option<&mut OwnershipTrace>,
   ) {
     let insns = &self.sir.instructions;
     let spans = &self.sir.spans;
@@ -328,10 +631,8 @@ impl<'a> Ownership<'a> {
             ..
           } = &insns[def_idx]
           {
-            facts.bit_for(*sym);
-            facts.move_set_at.insert(idx, *sym);
+            facts.record_move(idx, *sym, MoveKind::Consume, spans[idx]);
             facts.consume_call_at.insert(idx, *sym);
-            facts.move_span.entry(*sym).or_insert(spans[idx]);
             facts.consume_recv_loads.insert(def_idx);
           }
         }
@@ -351,13 +652,19 @@ impl<'a> Ownership<'a> {
             && src != dst
           {
             if owned.contains(src) {
-              facts.bit_for(*src);
-              facts.move_set_at.insert(def_idx, *src);
-              facts.move_span.entry(*src).or_insert(spans[def_idx]);
+              facts.record_move(def_idx, *src, MoveKind::Copy, spans[def_idx]);
             } else {
               facts.borrowed.insert(*dst);
             }
           }
+
+          // `… := s[lo..hi]` binds a view into `s`'s bytes — it
+          // aliases its parent and frees nothing of its own.
+          if let Some(&def_idx) = def_site.get(value)
+            && matches!(insns[def_idx], Insn::StrSlice { .. })
+          {
+            facts.borrowed.insert(*dst);
+          }
         }
 
         // `return v` moves the returned owned binding out.
@@ -369,9 +676,28 @@ impl<'a> Ownership<'a> {
             } = &insns[def_idx]
             && owned.contains(src)
           {
-            facts.bit_for(*src);
-            facts.move_set_at.insert(def_idx, *src);
-            facts.move_span.entry(*src).or_insert(spans[def_idx]);
+            facts.record_move(def_idx, *src, MoveKind::Return, spans[def_idx]);
+          }
+        }
+
+        // A capturing closure moves each owned capture into its
+        // heap environment — the closure now owns the value.
+        Insn::ClosureEnv { captures, .. } => {
+          for capture in captures {
+            if let Some(&def_idx) = def_site.get(capture)
+              && let Insn::Load {
+                src: LoadSource::Local(src),
+                ..
+              } = &insns[def_idx]
+              && owned.contains(src)
+            {
+              facts.record_move(
+                def_idx,
+                *src,
+                MoveKind::Capture,
+                spans[def_idx],
+              );
+            }
           }
         }
 
@@ -387,6 +713,23 @@ impl<'a> Ownership<'a> {
       }
     }
 
+    if let Some(trace) = trace
+      && let Insn::FunDef { name: function, .. } = insns[start]
+    {
+      let mut moves = facts.move_kind_at.iter().collect::<Vec<_>>();
+
+      moves.sort_unstable_by_key(|(idx, _)| **idx);
+
+      trace
+        .moves
+        .extend(moves.into_iter().map(|(idx, kind)| MoveFact {
+          function,
+          binding: facts.move_set_at[idx],
+          kind: *kind,
+          span: spans[*idx],
+        }));
+    }
+
     // Nothing affine and no drops to elaborate.
     if facts.bit_of.is_empty() && !has_drop {
       return;
@@ -562,12 +905,16 @@ impl<'a> Ownership<'a> {
     // already flagged as used-after-move / double-freed, has
     // no live drop to lower — and re-flagging it as a
     // conditional free would just be noise.
-    if facts.borrowed.contains(&local) || reported.contains(&local) {
-      return DropAction::Elide;
+    if facts.borrowed.contains(&local) {
+      return DropAction::Elide(Elision::Borrowed);
+    }
+
+    if reported.contains(&local) {
+      return DropAction::Elide(Elision::Reported);
     }
 
     let Some(dtor) = self.dtor_of(ty_id, dtors) else {
-      return DropAction::Elide;
+      return DropAction::Elide(Elision::NoDestructor);
     };
 
     let bit = facts.bit_of.get(&local).copied();
@@ -575,13 +922,13 @@ impl<'a> Ownership<'a> {
     let moved_some = bit.is_some_and(|b| union_cur.test(b));
 
     if moved_all {
-      DropAction::Elide
+      DropAction::Elide(Elision::Moved)
     } else if moved_some {
       // Freed on some paths but not all — a single static drop
       // would double-free or leak depending on the path.
       report_error(Error::new(ErrorKind::ConditionalMove, span));
 
-      DropAction::Elide
+      DropAction::Elide(Elision::Conditional)
     } else {
       DropAction::Lower { local, ty_id, dtor }
     }
@@ -607,7 +954,7 @@ impl<'a> Ownership<'a> {
       let span = old_spans[i];
 
       match actions.get(&i) {
-        Some(DropAction::Elide) => {}
+        Some(DropAction::Elide(_) | DropAction::Escaped) => {}
         Some(DropAction::Lower { local, ty_id, dtor }) => {
           let loaded = ValueId(next_value);
           next_value += 1;
@@ -644,6 +991,8 @@ impl<'a> Ownership<'a> {
       }
     }
 
+    Sir::fixup_fundef_offsets(&mut insns);
+
     self.sir.instructions = insns;
     self.sir.spans = spans;
     self.sir.next_value_id = next_value;
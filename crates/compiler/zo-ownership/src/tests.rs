@@ -1,13 +1,13 @@
-use crate::Ownership;
+use crate::{DropDecision, Elision, MoveKind, Ownership};
 
 use zo_error::ErrorKind;
 use zo_interner::{Interner, Symbol};
 use zo_reporter::collect_errors;
 use zo_sir::{Insn, LoadSource, Sir};
 use zo_span::Span;
-use zo_ty::{SelfKind, TyId};
+use zo_ty::{SelfKind, Ty, TyId};
 use zo_ty_checker::TyChecker;
-use zo_value::{FunctionKind, Pubness, ValueId};
+use zo_value::{EnvSlot, FunctionKind, Pubness, ValueId};
 
 fn make_sir(instructions: Vec<Insn>) -> Sir {
   let next_value_id = instructions.len() as u32;
@@ -39,6 +39,7 @@ fn fundef(name: Symbol, self_kind: SelfKind) -> Insn {
     pubness: Pubness::No,
     self_kind,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -231,3 +232,228 @@ fn non_consuming_calls_never_move() {
 
   assert!(run(&mut sir, &interner).is_empty());
 }
+
+#[test]
+fn trace_records_moves_and_drop_decisions() {
+  let mut interner = Interner::new();
+  let free = interner.intern("Vec::free");
+  let caller = interner.intern("caller");
+  let v = interner.intern("v");
+  let n = interner.intern("n");
+
+  let mut sir = make_sir(vec![
+    fundef(free, SelfKind::Consume),
+    ret(),
+    fundef(caller, SelfKind::None),
+    load(0, v),
+    call(1, free, vec![0]),
+    Insn::Drop {
+      local: n,
+      ty_id: TyId(1),
+    },
+    ret(),
+  ]);
+
+  let _ = collect_errors();
+  let ty = TyChecker::new();
+  let mut ownership = Ownership::new(&mut sir, &interner, &ty).with_trace();
+
+  ownership.check();
+
+  let trace = ownership.into_trace().expect("tracing was enabled");
+
+  assert_eq!(trace.moves.len(), 1);
+  assert_eq!(trace.moves[0].function, caller);
+  assert_eq!(trace.moves[0].binding, v);
+  assert_eq!(trace.moves[0].kind, MoveKind::Consume);
+
+  // A primitive has no destructor — the drop elides.
+  assert_eq!(trace.drops.len(), 1);
+  assert_eq!(trace.drops[0].binding, n);
+  assert_eq!(
+    trace.drops[0].decision,
+    DropDecision::Elide(Elision::NoDestructor)
+  );
+  assert!(collect_errors().is_empty());
+}
+
+#[test]
+fn str_slice_binding_aliases_its_parent() {
+  let mut interner = Interner::new();
+  let main = interner.intern("main");
+  let s = interner.intern("s");
+  let t = interner.intern("t");
+
+  let mut sir = make_sir(vec![
+    fundef(main, SelfKind::None),
+    load(0, s),
+    Insn::ConstInt {
+      dst: ValueId(1),
+      value: 0,
+      ty_id: TyId(8),
+    },
+    Insn::ConstInt {
+      dst: ValueId(2),
+      value: 2,
+      ty_id: TyId(8),
+    },
+    Insn::StrSlice {
+      dst: ValueId(3),
+      src: ValueId(0),
+      lo: ValueId(1),
+      hi: ValueId(2),
+      ty_id: TyId(4),
+    },
+    store(t, 3),
+    Insn::Drop {
+      local: t,
+      ty_id: TyId(4),
+    },
+    ret(),
+  ]);
+
+  let _ = collect_errors();
+  let ty = TyChecker::new();
+  let mut ownership = Ownership::new(&mut sir, &interner, &ty).with_trace();
+
+  ownership.check();
+
+  let trace = ownership.into_trace().expect("tracing was enabled");
+
+  // The view borrows `s`'s bytes — its drop elides as a
+  // borrow and `s` is not moved.
+  assert!(trace.moves.is_empty());
+  assert_eq!(
+    trace.drops[0].decision,
+    DropDecision::Elide(Elision::Borrowed)
+  );
+  assert!(collect_errors().is_empty());
+}
+
+#[test]
+fn capture_moves_owned_binding_into_closure_env() {
+  let mut interner = Interner::new();
+  let free = interner.intern("Buf::free");
+  let buf = interner.intern("Buf");
+  let main = interner.intern("main");
+  let b = interner.intern("b");
+
+  let mut ty = TyChecker::new();
+  let sid = ty.ty_table.intern_struct(buf, &[]);
+  let buf_ty = ty.intern_ty(Ty::Struct(sid));
+
+  let mut sir = make_sir(vec![
+    fundef(free, SelfKind::Consume),
+    ret(),
+    fundef(main, SelfKind::None),
+    load(0, b),
+    Insn::ClosureEnv {
+      dst: ValueId(1),
+      drop: ValueId(2),
+      captures: vec![ValueId(0)],
+    },
+    Insn::Drop {
+      local: b,
+      ty_id: buf_ty,
+    },
+    ret(),
+  ]);
+
+  let _ = collect_errors();
+  let mut ownership = Ownership::new(&mut sir, &interner, &ty).with_trace();
+
+  ownership.check();
+
+  let trace = ownership.into_trace().expect("tracing was enabled");
+
+  // The environment owns `b` now — the scope-exit drop elides.
+  assert_eq!(trace.moves.len(), 1);
+  assert_eq!(trace.moves[0].binding, b);
+  assert_eq!(trace.moves[0].kind, MoveKind::Capture);
+  assert_eq!(trace.drops[0].decision, DropDecision::Elide(Elision::Moved));
+  assert!(collect_errors().is_empty());
+}
+
+#[test]
+fn env_handed_to_the_runtime_is_not_freed_by_the_caller() {
+  let mut interner = Interner::new();
+  let pool_spawn = interner.intern("pool_spawn");
+  let on_event = interner.intern("on_event");
+  let forward = interner.intern("forward");
+  let call_it = interner.intern("call_it");
+  let main = interner.intern("main");
+
+  let ty = TyChecker::new();
+
+  let ffi = |name: Symbol, owned: bool| Insn::FunDef {
+    name,
+    params: vec![],
+    return_ty: TyId(1),
+    body_start: 0,
+    kind: FunctionKind::Intrinsic,
+    pubness: Pubness::No,
+    self_kind: SelfKind::None,
+    link_name: None,
+    env_slot: Some(EnvSlot { param: 0, owned }),
+    owning_pack: None,
+    span: Span::ZERO,
+    is_test: false,
+  };
+
+  let env = |dst: u32| Insn::ClosureEnv {
+    dst: ValueId(dst),
+    drop: ValueId(0),
+    captures: vec![],
+  };
+
+  let free = |value: u32| Insn::ClosureEnvFree {
+    env: ValueId(value),
+  };
+
+  let mut sir = make_sir(vec![
+    ffi(pool_spawn, true),
+    // `on_event` only borrows its env for the call.
+    ffi(on_event, false),
+    // `forward` passes its env param on to the owning ffi —
+    // the runtime frees it once the task has run.
+    fundef(forward, SelfKind::None),
+    Insn::Load {
+      dst: ValueId(1),
+      src: LoadSource::Param(0),
+      ty_id: TyId(1),
+    },
+    call(2, pool_spawn, vec![1]),
+    ret(),
+    fundef(call_it, SelfKind::None),
+    ret(),
+    fundef(main, SelfKind::None),
+    env(3),
+    call(4, forward, vec![3]),
+    free(3),
+    env(5),
+    call(6, call_it, vec![5]),
+    free(5),
+    env(7),
+    call(8, on_event, vec![7]),
+    free(7),
+    ret(),
+  ]);
+
+  let _ = collect_errors();
+
+  Ownership::new(&mut sir, &interner, &ty).check();
+
+  let frees = sir
+    .instructions
+    .iter()
+    .filter_map(|insn| match insn {
+      Insn::ClosureEnvFree { env } => Some(*env),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+
+  // Only the envs of the calls that borrow them are the
+  // caller's to release.
+  assert_eq!(frees, [ValueId(5), ValueId(7)]);
+  assert!(collect_errors().is_empty());
+}
@@ -377,6 +377,7 @@ impl<'a> Parser<'a> {
       }
       Token::Match => self.handle_match_keyword(),
       Token::Return => self.handle_return_keyword(),
+      Token::Defer => self.handle_defer_keyword(),
 
       // Structured-concurrency keywords.
       // `nursery { body }` — introducer for a task scope;
@@ -1104,6 +1105,7 @@ impl<'a> Parser<'a> {
         // Check if this was a template interpolation
         let was_template_interpolation =
           introducer.state == ParserState::TemplateMode;
+        let block_idx = introducer.node_index;
 
         // Close the block introducer BEFORE emitting RBrace
         // This ensures RBrace is a sibling, not a child
@@ -1139,6 +1141,13 @@ impl<'a> Parser<'a> {
             // Supervise is complete after its body
             // block — same cascade hook as nursery.
             self.close_introducer();
+          } else if parent.token == Token::Defer
+            && parent.children_start == block_idx
+          {
+            // `defer { … }` is complete after its block. A
+            // block nested in `defer expr;` (a closure body,
+            // a block argument) leaves it for the `;`.
+            self.close_introducer();
           } else if parent.token == Token::When {
             // Ternary ends at block boundary
             self.close_introducer();
@@ -1253,18 +1262,10 @@ impl<'a> Parser<'a> {
         return;
       }
 
-      // `abstract Name : Parent { ... }` — colon under
-      // an `Abstract` introducer with no `LBrace` yet is
-      // the unique signature for inheritance syntax.
-      // Inheritance would force chained vtable lookups;
-      // flat single-level abstracts are the design.
-      if let Some(introducer) = self.introducer_stack.last()
-        && introducer.token == Token::Abstract
-      {
-        self.error(ErrorKind::AbstractInheritanceUnsupported);
-      }
-
-      // Otherwise treat as regular token
+      // Otherwise treat as regular token — this includes
+      // the colon of `abstract Ord: Eq { ... }`, whose
+      // super-abstracts the executor reads back off the
+      // header.
       self.handle_operand(Token::Colon);
     } else {
       // Otherwise treat as regular token
@@ -1426,7 +1427,8 @@ impl<'a> Parser<'a> {
         | Token::Ffi
         | Token::Type
         | Token::Group
-        | Token::Spawn => {
+        | Token::Spawn
+        | Token::Defer => {
           self.close_introducer();
           break;
         }
@@ -1877,6 +1879,31 @@ impl<'a> Parser<'a> {
     self.state = ParserState::Expression;
   }
 
+  /// `defer stmt;` / `defer { … }` — scope-exit cleanup.
+  /// The statement form closes on `;` via the cascade in
+  /// `handle_semicolon`; the block form closes with its
+  /// block in `handle_rbrace_closer`. The executor records
+  /// the body and replays it at every exit of the
+  /// enclosing scope.
+  fn handle_defer_keyword(&mut self) {
+    self.flush_expr();
+
+    let node_index = self.emit_node(Token::Defer);
+
+    self.introducer_stack.push(Introducer {
+      state: self.state,
+      token: Token::Defer,
+      node_index,
+      children_start: self.tree.nodes.len() as u32,
+    });
+
+    // The block form parses as a normal block; the
+    // statement form is an expression statement.
+    if self.peek() != Some(Token::LBrace) {
+      self.state = ParserState::Expression;
+    }
+  }
+
   /// `nursery { body }` — structured-concurrency scope.
   /// No condition, no header — the following `{` opens the
   /// body block via the normal LBrace introducer; the
@@ -2018,6 +2045,8 @@ impl<'a> Parser<'a> {
   ///
   /// - `%% name.`              — single tag.
   /// - `%% name(arg).`         — call-style.
+  /// - `%% name(a-b, c).`      — call-style with several
+  ///   args; an ident arg may be a kebab-case chain.
   /// - `%% name = literal.`    — key/value
   ///   (mirrors Rust's `#[link_name = "X"]`).
   /// - `%% n1 = v1, n2, n3(a), .` — multiple fields per
@@ -2025,7 +2054,7 @@ impl<'a> Parser<'a> {
   ///
   /// Emits one `Token::Attribute` node with each field's
   /// tokens as children: per field
-  /// `Ident [+ Eq, literal | LParen, value, RParen]`,
+  /// `Ident [+ Eq, literal | LParen, args, RParen]`,
   /// `Comma` separators between fields, terminating
   /// `Dot`. The executor walks children to extract every
   /// `(name, value)` pair into `pending_attributes`.
@@ -2117,24 +2146,19 @@ impl<'a> Parser<'a> {
         self.pos += 1;
         self.emit_node(Token::LParen);
 
-        let val_idx = self.pos + 1;
-
-        if val_idx >= self.tokens.kinds.len() {
-          self.error_at(ErrorKind::ExpectedAttributeValue, val_idx);
-          return false;
-        }
+        loop {
+          if !self.parse_attribute_arg() {
+            return false;
+          }
 
-        let val_kind = self.tokens.kinds[val_idx];
+          if self.peek() != Some(Token::Comma) {
+            break;
+          }
 
-        if !matches!(val_kind, Token::Ident | Token::String | Token::RawString)
-        {
-          self.error_at(ErrorKind::ExpectedAttributeValue, val_idx);
-          return false;
+          self.pos += 1;
+          self.emit_node(Token::Comma);
         }
 
-        self.pos += 1;
-        self.emit_node(val_kind);
-
         if self.peek() != Some(Token::RParen) {
           self.error_at(ErrorKind::ExpectedRParen, self.pos + 1);
           return false;
@@ -2149,6 +2173,41 @@ impl<'a> Parser<'a> {
     true
   }
 
+  /// Parse one argument of a call-style field: a string, or
+  /// an ident chain joined by `-` so kebab-case lint ids read
+  /// as written — `%% allow(unused-variable).`. Each piece is
+  /// emitted as its own node; the executor stitches them back.
+  fn parse_attribute_arg(&mut self) -> bool {
+    let val_idx = self.pos + 1;
+
+    if val_idx >= self.tokens.kinds.len() {
+      self.error_at(ErrorKind::ExpectedAttributeValue, val_idx);
+      return false;
+    }
+
+    let val_kind = self.tokens.kinds[val_idx];
+
+    if !matches!(val_kind, Token::Ident | Token::String | Token::RawString) {
+      self.error_at(ErrorKind::ExpectedAttributeValue, val_idx);
+      return false;
+    }
+
+    self.pos += 1;
+    self.emit_node(val_kind);
+
+    while val_kind == Token::Ident
+      && self.peek() == Some(Token::Minus)
+      && self.tokens.kinds.get(self.pos + 2) == Some(&Token::Ident)
+    {
+      self.pos += 1;
+      self.emit_node(Token::Minus);
+      self.pos += 1;
+      self.emit_node(Token::Ident);
+    }
+
+    true
+  }
+
   fn handle_directive(&mut self) {
     // Directives follow pattern: #identifier expression
     // Examples: #run foobar(), #render <>, #inline
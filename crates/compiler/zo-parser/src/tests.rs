@@ -17,9 +17,9 @@ use crate::tests::common::assert_nodes_stream;
 
 use zo_interner::Symbol;
 use zo_token::Token::{
-  Abstract, Apply, Arrow, As, BoolType, CharType, Colon, ColonEq, Comma, Dot,
-  DotDot, DotDotEq, Ellipsis, Else, Eq, False, FloatType, For, Fun, Gt, Ident,
-  If, Imu, Int, IntType, LBrace, LBracket, LParen, Lt, Minus, Mut, Plus,
+  Abstract, Apply, Arrow, As, BoolType, CharType, Colon, ColonEq, Comma, Defer,
+  Dot, DotDot, DotDotEq, Ellipsis, Else, Eq, False, FloatType, For, Fun, Gt,
+  Ident, If, Imu, Int, IntType, LBrace, LBracket, LParen, Lt, Minus, Mut, Plus,
   RBrace, RBracket, RParen, Return, S32Type, SelfLower, Semicolon, Star,
   StrType, String, True, While,
 };
@@ -278,6 +278,60 @@ fn test_return_empty() {
   );
 }
 
+#[test]
+fn test_defer_statement() {
+  assert_nodes_stream(
+    r#"
+      fun main() {
+        defer close(f);
+      }
+    "#,
+    &[
+      (Fun, None),
+      (Ident, Some(NodeValue::TextRange(11, 4))), // "main"
+      (LParen, None),
+      (RParen, None),
+      (LBrace, None),
+      (Defer, None),
+      (Ident, Some(NodeValue::TextRange(34, 5))), // "close"
+      (LParen, None),
+      (Ident, Some(NodeValue::TextRange(40, 1))), // "f"
+      (RParen, None),
+      (Semicolon, None),
+      (RBrace, None),
+    ],
+  );
+}
+
+#[test]
+fn test_defer_block() {
+  assert_nodes_stream(
+    r#"
+      fun main() {
+        defer {
+          close(f);
+        }
+      }
+    "#,
+    &[
+      (Fun, None),
+      (Ident, Some(NodeValue::TextRange(11, 4))), // "main"
+      (LParen, None),
+      (RParen, None),
+      (LBrace, None),
+      (Defer, None),
+      (LBrace, None),
+      (Ident, Some(NodeValue::TextRange(46, 5))), // "close"
+      (LParen, None),
+      (Ident, Some(NodeValue::TextRange(52, 1))), // "f"
+      (RParen, None),
+      (Semicolon, None),
+      (RBrace, None),
+      (RBrace, None),
+    ],
+  );
+}
+
 #[test]
 fn test_imu_declaration_with_type() {
   assert_nodes_stream(
@@ -178,3 +178,36 @@ fn test_attribute_stack_above_one_item() {
     ],
   );
 }
+
+#[test]
+fn test_attribute_call_style_kebab_list() {
+  // `%% allow(unused-variable, unused-function).` — a
+  // call-style field with several args, each a kebab-case
+  // ident chain. Every piece is its own node.
+  assert_nodes_stream(
+    r#"
+      %% allow(unused-variable, unused-function).
+      fun main() {}
+    "#,
+    &[
+      (Attribute, None),
+      (Ident, Some(NodeValue::Symbol(Symbol(0)))), // "allow"
+      (LParen, None),
+      (Ident, Some(NodeValue::Symbol(Symbol(0)))), // "unused"
+      (Minus, None),
+      (Ident, Some(NodeValue::Symbol(Symbol(0)))), // "variable"
+      (Comma, None),
+      (Ident, Some(NodeValue::Symbol(Symbol(0)))), // "unused"
+      (Minus, None),
+      (Ident, Some(NodeValue::Symbol(Symbol(0)))), // "function"
+      (RParen, None),
+      (Dot, None),
+      (Fun, None),
+      (Ident, Some(NodeValue::Symbol(Symbol(0)))), // "main"
+      (LParen, None),
+      (RParen, None),
+      (LBrace, None),
+      (RBrace, None),
+    ],
+  );
+}
@@ -2,12 +2,18 @@ use crate::printee::Printee;
 
 use zo_buffer::Buffer;
 use zo_codegen_backend::{Artifact, Target};
-use zo_interner::Interner;
+use zo_interner::{Interner, Symbol};
+use zo_liveness::{BitVec, Cfg, analyze, compute_value_ids};
+use zo_ownership::{DropDecision, Elision, MoveKind, OwnershipTrace};
 use zo_sir::{BinOp, Insn, LoadSource, NurseryKind, Sir, SpawnKind, UnOp};
-use zo_token::{Token, TokenBuffer};
+use zo_span::Span;
+use zo_token::{Base, Token, TokenBuffer};
 use zo_tree::Tree;
-use zo_ty::{Mutability, Ty, type_name};
-use zo_value::Pubness;
+use zo_ty::{Annotation, IntWidth, Mutability, SelfKind, Ty, TyId, type_name};
+use zo_ty_checker::TyChecker;
+use zo_value::{FunctionKind, Pubness, ValueId};
+
+use std::collections::BTreeMap;
 
 /// Represents a [`PrettyPrinter`] instance.
 pub struct PrettyPrinter {
@@ -131,70 +137,175 @@ impl PrettyPrinter {
     self.buffer.newline();
   }
 
-  pub fn format_sir(&mut self, sir: &Sir, interner: &Interner, tys: &[Ty]) {
+  /// Writes the SIR of a program in its textual form — the
+  /// grammar `zo_sir_parser::parse` reads back. Every field a
+  /// backend consumes is printed, types spelled by name, so a
+  /// dump can be edited and rebuilt with `zo build foo.sir`.
+  ///
+  /// `Nop`s print nothing; spans and `body_start` are
+  /// positional and recomputed by the parser. The side tables
+  /// keyed by value — int display bases, `Vec` element types —
+  /// close the dump as `!base` / `!elem` lines.
+  pub fn format_sir(&mut self, sir: &Sir, interner: &Interner, ty: &TyChecker) {
     self.sir_header();
+    self.sir_insns(&sir.instructions, interner, ty);
+
+    let bases = sir.int_bases.iter().collect::<BTreeMap<_, _>>();
+
+    for (value, base) in bases {
+      let base = match base {
+        Base::Binary => "bin",
+        Base::Octal => "oct",
+        Base::Decimal => "dec",
+        Base::Hexadecimal => "hex",
+      };
+
+      self.buffer.str(&format!("!base %{value} {base}\n"));
+    }
+
+    let elems = sir.vec_elem_tys.iter().collect::<BTreeMap<_, _>>();
+
+    for (value, elem_ty) in elems {
+      let elem = Self::ty_label(interner, ty, *elem_ty);
+
+      self.buffer.str(&format!("!elem %{value} : {elem}\n"));
+    }
+  }
+
+  /// Writes a run of instructions, one line each, opening a
+  /// `@name(..) -> ty:` section at every `FunDef`.
+  fn sir_insns(&mut self, insns: &[Insn], interner: &Interner, ty: &TyChecker) {
+    let name = |symbol: Symbol| sir_name(interner.get(symbol));
+    let qualified = |pack: &Option<Symbol>, symbol: Symbol| match pack {
+      Some(pack) => format!("{}::{}", name(*pack), name(symbol)),
+      None => name(symbol),
+    };
+    let ty_of = |ty_id: TyId| Self::ty_label(interner, ty, ty_id);
+    let values = |values: &[ValueId]| {
+      values
+        .iter()
+        .map(|v| format!("%{v}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    };
+    let vis = |pubness: &Pubness| {
+      if *pubness == Pubness::Yes { "pub " } else { "" }
+    };
+    let owned = |owner: &Option<Symbol>| match owner {
+      Some(owner) => format!(" owner {}", name(*owner)),
+      None => String::new(),
+    };
 
     // Track function definitions and bodies
     // let mut current_function: Option<String> = None;
     let mut in_function_body = false;
 
-    for insn in sir.instructions.iter() {
+    for insn in insns.iter() {
       match insn {
         Insn::FunDef {
-          name, owning_pack, ..
+          name: fun_name,
+          params,
+          return_ty,
+          kind,
+          pubness,
+          self_kind,
+          link_name,
+          owning_pack,
+          is_test,
+          env_slot,
+          ..
         } => {
           if in_function_body {
             self.buffer.newline();
           }
 
-          let label = match owning_pack {
-            Some(pack) => {
-              format!("{}::{}", interner.get(*pack), interner.get(*name))
+          let params = params
+            .iter()
+            .map(|(param, ty_id)| {
+              format!("{}: {}", name(*param), ty_of(*ty_id))
+            })
+            .collect::<Vec<_>>();
+
+          let mut label = format!(
+            "{}({}) -> {}",
+            qualified(owning_pack, *fun_name),
+            params.join(", "),
+            ty_of(*return_ty)
+          );
+
+          if *pubness == Pubness::Yes {
+            label.push_str(" pub");
+          }
+
+          if *is_test {
+            label.push_str(" test");
+          }
+
+          match kind {
+            FunctionKind::UserDefined => {}
+            FunctionKind::Intrinsic => label.push_str(" intrinsic"),
+            FunctionKind::Closure { capture_count } => {
+              label.push_str(&format!(" closure({capture_count})"));
             }
-            None => interner.get(*name).to_string(),
-          };
+          }
+
+          match self_kind {
+            SelfKind::None => {}
+            SelfKind::Read => label.push_str(" self(read)"),
+            SelfKind::Write => label.push_str(" self(write)"),
+            SelfKind::Consume => label.push_str(" self(own)"),
+          }
+
+          if let Some(link_name) = link_name {
+            label.push_str(&format!(" link({})", name(*link_name)));
+          }
+
+          if let Some(slot) = env_slot {
+            let own = if slot.owned { ", own" } else { "" };
+
+            label.push_str(&format!(" user_data({}{own})", slot.param));
+          }
 
           self.sir_function(&label);
 
           in_function_body = true;
         }
-        Insn::Return { value, .. } => {
+        Insn::Return { value, ty_id } => {
           if let Some(v) = value {
-            let return_value = format!("ret %{v}");
+            let return_value = format!("ret %{v} : {}", ty_of(*ty_id));
             self.sir_instruction(&return_value);
           } else {
-            self.sir_instruction("ret void");
+            self.sir_instruction(&format!("ret void : {}", ty_of(*ty_id)));
           }
         }
         Insn::ConstInt { dst, value, ty_id } => {
-          let int =
-            format!("%{dst} = const {value} : {}", type_name(tys, *ty_id));
+          let int = format!("%{dst} = const {value} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&int);
         }
         Insn::ConstFloat { dst, value, ty_id } => {
-          let float =
-            format!("%{dst} = const {value} : {}", type_name(tys, *ty_id));
+          let float = format!("%{dst} = const {value} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&float);
         }
         Insn::ConstBool { dst, value, ty_id } => {
-          let boolean =
-            format!("%{dst} = const {value} : {}", type_name(tys, *ty_id));
+          let boolean = format!("%{dst} = const {value} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&boolean);
         }
         Insn::ConstString { dst, symbol, ty_id } => {
           let content = interner.get(*symbol);
-          let string = format!(
-            "%{dst} = const \"{content}\" : {}",
-            type_name(tys, *ty_id)
-          );
+          let string =
+            format!("%{dst} = const {content:?} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&string);
         }
         Insn::BinOp {
-          dst, op, lhs, rhs, ..
+          dst,
+          op,
+          lhs,
+          rhs,
+          ty_id,
         } => {
           let op = match op {
             BinOp::Add => "add",
@@ -218,63 +329,75 @@ impl PrettyPrinter {
             BinOp::Concat => "concat",
           };
 
-          let binop = format!("%{dst} = {op} %{lhs}, %{rhs}");
+          let binop =
+            format!("%{dst} = {op} %{lhs}, %{rhs} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&binop);
         }
-        Insn::UnOp { dst, op, rhs, .. } => {
+        Insn::UnOp {
+          dst,
+          op,
+          rhs,
+          ty_id,
+        } => {
           let op = match op {
             UnOp::Neg => "neg",
             UnOp::Not => "not",
             UnOp::BitNot => "bitnot",
           };
 
-          let unop = format!("%{dst} = {op} %{rhs}");
+          let unop = format!("%{dst} = {op} %{rhs} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&unop);
         }
         Insn::VarDef {
-          name,
+          name: var,
+          ty_id,
           init,
           mutability,
-          ..
+          pubness,
         } => {
-          let name = interner.get(*name);
-
           let mutability = match mutability {
             Mutability::No => "imu",
             Mutability::Yes => "mut",
           };
 
-          if let Some(value) = init {
-            let var = format!("{mutability} {name} = %{value}");
-            self.sir_instruction(&var);
-          } else {
-            let var = format!("{mutability} {name} = undef");
-            self.sir_instruction(&var);
-          }
+          let init = match init {
+            Some(value) => format!("%{value}"),
+            None => "undef".to_string(),
+          };
+
+          let var = format!(
+            "{}{mutability} {} : {} = {init}",
+            vis(pubness),
+            name(*var),
+            ty_of(*ty_id)
+          );
+
+          self.sir_instruction(&var);
         }
-        Insn::Store { name, value, .. } => {
-          let name = interner.get(*name);
-          let store = format!("store {name}, %{value}");
+        Insn::Store {
+          name: var,
+          value,
+          ty_id,
+        } => {
+          let store =
+            format!("store {}, %{value} : {}", name(*var), ty_of(*ty_id));
 
           self.sir_instruction(&store);
         }
-        Insn::Drop { local, .. } => {
-          let local = interner.get(*local);
-          let drop = format!("drop {local}");
+        Insn::Drop { local, ty_id } => {
+          let drop = format!("drop {} : {}", name(*local), ty_of(*ty_id));
 
           self.sir_instruction(&drop);
         }
-        Insn::Load { dst, src, .. } => {
+        Insn::Load { dst, src, ty_id } => {
           let load = match src {
             LoadSource::Param(idx) => {
-              format!("%{dst} = load param[{idx}]")
+              format!("%{dst} = load param[{idx}] : {}", ty_of(*ty_id))
             }
             LoadSource::Local(sym) => {
-              let name = interner.get(*sym);
-
-              format!("%{dst} = load local[{name}]")
+              format!("%{dst} = load local[{}] : {}", name(*sym), ty_of(*ty_id))
             }
           };
 
@@ -282,18 +405,17 @@ impl PrettyPrinter {
         }
         Insn::Call {
           dst,
-          name,
+          name: callee,
           callee_pack,
           args,
-          ..
+          ty_id,
         } => {
-          let name = interner.get(*name);
-          let args = args.iter().map(|v| format!("%{v}")).collect::<Vec<_>>();
-          let qualified = match callee_pack {
-            Some(pack) => format!("{}::{}", interner.get(*pack), name),
-            None => name.to_string(),
-          };
-          let call = format!("%{dst} = call {qualified}({})", args.join(", "));
+          let call = format!(
+            "%{dst} = call {}({}) : {}",
+            qualified(callee_pack, *callee),
+            values(args),
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&call);
         }
@@ -303,10 +425,10 @@ impl PrettyPrinter {
           args,
           ty_id,
         } => {
-          let args = args.iter().map(|v| format!("%{v}")).collect::<Vec<_>>();
           let call = format!(
-            "%{dst} = call_indirect %{callee}({}) : {ty_id:?}",
-            args.join(", ")
+            "%{dst} = call_indirect %{callee}({}) : {}",
+            values(args),
+            ty_of(*ty_id)
           );
 
           self.sir_instruction(&call);
@@ -316,8 +438,7 @@ impl PrettyPrinter {
           kind,
           pubness,
         } => {
-          let path_str =
-            path.iter().map(|s| interner.get(*s)).collect::<Vec<_>>();
+          let path_str = path.iter().map(|s| name(*s)).collect::<Vec<_>>();
 
           let tail = match kind {
             zo_sir::ImportKind::Qualified => String::new(),
@@ -325,7 +446,7 @@ impl PrettyPrinter {
             zo_sir::ImportKind::Selective(items) => {
               let names = items
                 .iter()
-                .map(|s| interner.get(*s))
+                .map(|s| name(*s))
                 .collect::<Vec<_>>()
                 .join(", ");
 
@@ -333,19 +454,16 @@ impl PrettyPrinter {
             }
           };
 
-          let lead = if matches!(pubness, Pubness::Yes) {
-            "pub "
-          } else {
-            ""
-          };
-          let load = format!("{lead}load {}{tail}", path_str.join("::"));
+          let load =
+            format!("{}load {}{tail}", vis(pubness), path_str.join("::"));
 
           self.sir_instruction(&load);
         }
-        Insn::PackDecl { name, pubness } => {
-          let name = interner.get(*name);
-          let vis = if *pubness == Pubness::Yes { "pub " } else { "" };
-          let decl = format!("{vis}pack {name}");
+        Insn::PackDecl {
+          name: pack,
+          pubness,
+        } => {
+          let decl = format!("{}pack {}", vis(pubness), name(*pack));
 
           self.sir_instruction(&decl);
         }
@@ -382,53 +500,89 @@ impl PrettyPrinter {
         Insn::BranchIfNot { cond, target } => {
           self.sir_instruction(&format!("  br_ifnot %{cond}, L{target}"));
         }
-        Insn::ConstDef { name, value, .. } => {
-          let name = interner.get(*name);
-          let def = format!("val {name} = %{value}");
+        Insn::ConstDef {
+          name: constant,
+          ty_id,
+          value,
+          pubness,
+        } => {
+          let def = format!(
+            "{}val {} : {} = %{value}",
+            vis(pubness),
+            name(*constant),
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&def);
         }
-        Insn::Directive { name, value, .. } => {
-          let name = interner.get(*name);
-          let dir = format!("#{name} %{value}");
+        Insn::Directive {
+          name: directive,
+          value,
+          ty_id,
+        } => {
+          let dir =
+            format!("#{} %{value} : {}", name(*directive), ty_of(*ty_id));
 
           self.sir_instruction(&dir);
         }
-        Insn::ArrayLiteral { dst, elements, .. } => {
-          let elems =
-            elements.iter().map(|v| format!("%{v}")).collect::<Vec<_>>();
-
-          let arr = format!("%{dst} = array [{}]", elems.join(", "));
+        Insn::ArrayLiteral {
+          dst,
+          elements,
+          ty_id,
+        } => {
+          let arr = format!(
+            "%{dst} = array [{}] : {}",
+            values(elements),
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&arr);
         }
         Insn::ArrayIndex {
-          dst, array, index, ..
+          dst,
+          array,
+          index,
+          ty_id,
         } => {
-          let ai = format!("%{dst} = index %{array}[%{index}]");
+          let ai =
+            format!("%{dst} = index %{array}[%{index}] : {}", ty_of(*ty_id));
 
           self.sir_instruction(&ai);
         }
-        Insn::ArrayLen { dst, array, .. } => {
-          let al = format!("%{dst} = len %{array}");
+        Insn::ArrayLen { dst, array, ty_id } => {
+          let al = format!("%{dst} = len %{array} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&al);
         }
-        Insn::ArrayPush { array, value, .. } => {
-          let ap = format!("push %{array}, %{value}");
+        Insn::ArrayPush {
+          array,
+          value,
+          ty_id,
+          owner,
+        } => {
+          let ap = format!(
+            "push %{array}, %{value} : {}{}",
+            ty_of(*ty_id),
+            owned(owner)
+          );
 
           self.sir_instruction(&ap);
         }
-        Insn::ArrayPop { dst, array, .. } => {
-          let ap = format!("%{dst} = pop %{array}");
+        Insn::ArrayPop { dst, array, ty_id } => {
+          let ap = format!("%{dst} = pop %{array} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&ap);
         }
-        Insn::TupleLiteral { dst, elements, .. } => {
-          let elems =
-            elements.iter().map(|v| format!("%{v}")).collect::<Vec<_>>();
-
-          let tup = format!("%{dst} = tuple ({})", elems.join(", "));
+        Insn::TupleLiteral {
+          dst,
+          elements,
+          ty_id,
+        } => {
+          let tup = format!(
+            "%{dst} = tuple ({}) : {}",
+            values(elements),
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&tup);
         }
@@ -438,17 +592,21 @@ impl PrettyPrinter {
           index,
           ty_id,
         } => {
-          let ti = format!(
-            "%{dst} = field %{tuple}.{index} : {}",
-            type_name(tys, *ty_id)
-          );
+          let ti =
+            format!("%{dst} = field %{tuple}.{index} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&ti);
         }
         Insn::FieldStore {
-          base, index, value, ..
+          base,
+          index,
+          value,
+          ty_id,
         } => {
-          let fs = format!("store_field %{base}.{index}, %{value}");
+          let fs = format!(
+            "store_field %{base}.{index}, %{value} : {}",
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&fs);
         }
@@ -456,35 +614,45 @@ impl PrettyPrinter {
           array,
           index,
           value,
-          ..
+          ty_id,
+          owner,
         } => {
-          let fs = format!("array_store %{array}[%{index}] = %{value}");
+          let fs = format!(
+            "array_store %{array}[%{index}] = %{value} : {}{}",
+            ty_of(*ty_id),
+            owned(owner)
+          );
 
           self.sir_instruction(&fs);
         }
         Insn::EnumDef {
-          name,
+          name: enum_name,
           variants,
           pubness,
           ..
         } => {
-          let name = interner.get(*name);
-          let vis = if *pubness == Pubness::Yes { "pub " } else { "" };
-
           let vars = variants
             .iter()
             .map(|(n, disc, fields)| {
-              let n = interner.get(*n);
+              let n = name(*n);
 
               if fields.is_empty() {
                 format!("{n} = {disc}")
               } else {
-                format!("{n} = {disc}(...)")
+                let fields =
+                  fields.iter().map(|field| ty_of(*field)).collect::<Vec<_>>();
+
+                format!("{n} = {disc}({})", fields.join(", "))
               }
             })
             .collect::<Vec<_>>();
 
-          let def = format!("{vis}enum_def {name} {{ {} }}", vars.join(", "));
+          let def = format!(
+            "{}enum_def {} {{ {} }}",
+            vis(pubness),
+            name(*enum_name),
+            vars.join(", ")
+          );
 
           self.sir_instruction(&def);
         }
@@ -493,34 +661,43 @@ impl PrettyPrinter {
           enum_name,
           variant,
           fields,
-          ..
+          ty_id,
         } => {
-          let name = interner.get(*enum_name);
-          let fs = fields.iter().map(|v| format!("%{v}")).collect::<Vec<_>>();
+          let enum_name = name(*enum_name);
 
-          let ec = if fs.is_empty() {
-            format!("%{dst} = enum {name}::{variant}")
+          let ec = if fields.is_empty() {
+            format!("%{dst} = enum {enum_name}::{variant} : {}", ty_of(*ty_id))
           } else {
-            format!("%{dst} = enum {name}::{variant}({})", fs.join(", "))
+            format!(
+              "%{dst} = enum {enum_name}::{variant}({}) : {}",
+              values(fields),
+              ty_of(*ty_id)
+            )
           };
 
           self.sir_instruction(&ec);
         }
         Insn::StructDef {
-          name,
+          name: struct_name,
           fields,
           pubness,
           ..
         } => {
-          let name = interner.get(*name);
-          let vis = if *pubness == Pubness::Yes { "pub " } else { "" };
-
           let fs = fields
             .iter()
-            .map(|(n, _, _)| interner.get(*n).to_string())
+            .map(|(n, ty_id, has_default)| {
+              let default = if *has_default { " default" } else { "" };
+
+              format!("{}: {}{default}", name(*n), ty_of(*ty_id))
+            })
             .collect::<Vec<_>>();
 
-          let def = format!("{vis}struct_def {name} {{ {} }}", fs.join(", "));
+          let def = format!(
+            "{}struct_def {} {{ {} }}",
+            vis(pubness),
+            name(*struct_name),
+            fs.join(", ")
+          );
 
           self.sir_instruction(&def);
         }
@@ -528,11 +705,14 @@ impl PrettyPrinter {
           dst,
           struct_name,
           fields,
-          ..
+          ty_id,
         } => {
-          let name = interner.get(*struct_name);
-          let fs = fields.iter().map(|v| format!("%{v}")).collect::<Vec<_>>();
-          let sc = format!("%{dst} = struct {name} {{ {} }}", fs.join(", "));
+          let sc = format!(
+            "%{dst} = struct {} {{ {} }} : {}",
+            name(*struct_name),
+            values(fields),
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&sc);
         }
@@ -559,8 +739,11 @@ impl PrettyPrinter {
           from_ty,
           to_ty,
         } => {
-          let c =
-            format!("%{dst} = cast %{src} ({:?} -> {:?})", from_ty, to_ty,);
+          let c = format!(
+            "%{dst} = cast %{src} ({} -> {})",
+            ty_of(*from_ty),
+            ty_of(*to_ty)
+          );
 
           self.sir_instruction(&c);
         }
@@ -574,8 +757,9 @@ impl PrettyPrinter {
             None => String::from("[]"),
           };
           let c = format!(
-            "arr_ty_def : {:?} -> {prefix}elem {:?}",
-            array_ty, elem_ty,
+            "arr_ty_def : {} -> {prefix}elem {}",
+            ty_of(*array_ty),
+            ty_of(*elem_ty),
           );
 
           self.sir_instruction(&c);
@@ -586,19 +770,21 @@ impl PrettyPrinter {
           val_fmt,
         } => {
           let c = format!(
-            "map_ty_def : {:?} -> key_fmt={key_fmt} val_fmt={val_fmt}",
-            map_ty,
+            "map_ty_def : {} -> key_fmt={key_fmt} val_fmt={val_fmt}",
+            ty_of(*map_ty),
           );
 
           self.sir_instruction(&c);
         }
         Insn::VecTyDef { vec_ty, elem_fmt } => {
-          let c = format!("vec_ty_def : {:?} -> elem_fmt={elem_fmt}", vec_ty,);
+          let c =
+            format!("vec_ty_def : {} -> elem_fmt={elem_fmt}", ty_of(*vec_ty));
 
           self.sir_instruction(&c);
         }
         Insn::SetTyDef { set_ty, key_fmt } => {
-          let c = format!("set_ty_def : {:?} -> key_fmt={key_fmt}", set_ty,);
+          let c =
+            format!("set_ty_def : {} -> key_fmt={key_fmt}", ty_of(*set_ty));
 
           self.sir_instruction(&c);
         }
@@ -610,7 +796,8 @@ impl PrettyPrinter {
           elem_ty,
           capacity,
         } => {
-          let c = format!("%{dst} = chan.new cap={capacity} : {elem_ty:?}");
+          let c =
+            format!("%{dst} = chan.new cap={capacity} : {}", ty_of(*elem_ty));
 
           self.sir_instruction(&c);
         }
@@ -619,7 +806,7 @@ impl PrettyPrinter {
           value,
           ty_id,
         } => {
-          let c = format!("chan.send %{channel}, %{value} : {ty_id:?}");
+          let c = format!("chan.send %{channel}, %{value} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&c);
         }
@@ -628,7 +815,7 @@ impl PrettyPrinter {
           channel,
           ty_id,
         } => {
-          let c = format!("%{dst} = chan.recv %{channel} : {ty_id:?}");
+          let c = format!("%{dst} = chan.recv %{channel} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&c);
         }
@@ -645,31 +832,22 @@ impl PrettyPrinter {
           ty_id,
           kind,
         } => {
-          let name = match callee_pack {
-            Some(p) => {
-              format!("{}::{}", interner.get(*p), interner.get(*callee))
-            }
-            None => interner.get(*callee).to_string(),
-          };
-
-          let args_str = args
-            .iter()
-            .map(|a| format!("%{a}"))
-            .collect::<Vec<_>>()
-            .join(", ");
-
           let kind_str = match kind {
             SpawnKind::Green => "spawn",
             SpawnKind::Thread => "spawn_thread",
           };
 
-          let c =
-            format!("%{dst} = task.{kind_str} {name}({args_str}) : {ty_id:?}");
+          let c = format!(
+            "%{dst} = task.{kind_str} {}({}) : {}",
+            qualified(callee_pack, *callee),
+            values(args),
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&c);
         }
         Insn::TaskAwait { dst, task, ty_id } => {
-          let c = format!("%{dst} = task.await %{task} : {ty_id:?}");
+          let c = format!("%{dst} = task.await %{task} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&c);
         }
@@ -678,14 +856,23 @@ impl PrettyPrinter {
           callee,
           callee_pack,
         } => {
-          let name = match callee_pack {
-            Some(p) => {
-              format!("{}::{}", interner.get(*p), interner.get(*callee))
-            }
-            None => interner.get(*callee).to_string(),
-          };
+          let c =
+            format!("%{dst} = fn.addr {}", qualified(callee_pack, *callee));
 
-          let c = format!("%{dst} = fn.addr {name}");
+          self.sir_instruction(&c);
+        }
+        Insn::ClosureEnv {
+          dst,
+          drop,
+          captures,
+        } => {
+          let c =
+            format!("%{dst} = closure.env %{drop} [{}]", values(captures));
+
+          self.sir_instruction(&c);
+        }
+        Insn::ClosureEnvFree { env } => {
+          let c = format!("closure.free %{env}");
 
           self.sir_instruction(&c);
         }
@@ -708,26 +895,30 @@ impl PrettyPrinter {
           chans,
           elem_ty,
         } => {
-          let chans_str = chans
-            .iter()
-            .map(|c| format!("%{c}"))
-            .collect::<Vec<_>>()
-            .join(", ");
-
-          let c =
-            format!("%{out_which} = select.wait [{chans_str}] : {elem_ty:?}",);
+          let c = format!(
+            "%{out_which} = select.wait [{}] : {}",
+            values(chans),
+            ty_of(*elem_ty)
+          );
 
           self.sir_instruction(&c);
         }
         Insn::SelectRecv {
-          dst, which, ty_id, ..
+          dst,
+          which,
+          ty_id,
+          chans_len,
         } => {
-          let c = format!("%{dst} = select.recv %{which} : {ty_id:?}");
+          let c = format!(
+            "%{dst} = select.recv %{which}, chans={chans_len} : {}",
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&c);
         }
         Insn::TaskCancelled { dst, task, ty_id } => {
-          let c = format!("%{dst} = task.cancelled %{task} : {ty_id:?}");
+          let c =
+            format!("%{dst} = task.cancelled %{task} : {}", ty_of(*ty_id));
 
           self.sir_instruction(&c);
         }
@@ -737,21 +928,34 @@ impl PrettyPrinter {
           self.sir_instruction(&c);
         }
         Insn::StrSlice {
-          dst, src, lo, hi, ..
+          dst,
+          src,
+          lo,
+          hi,
+          ty_id,
         } => {
-          let c = format!("%{dst} = str.slice %{src}[%{lo}..%{hi}]");
+          let c = format!(
+            "%{dst} = str.slice %{src}[%{lo}..%{hi}] : {}",
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&c);
         }
         Insn::ToStr { dst, src, src_ty } => {
-          let c = format!("%{dst} = to_str %{src} : {src_ty:?}");
+          let c = format!("%{dst} = to_str %{src} : {}", ty_of(*src_ty));
 
           self.sir_instruction(&c);
         }
-        Insn::StringFormat { dst, segments, .. } => {
-          let segs: Vec<String> =
-            segments.iter().map(|s| format!("%{s}")).collect();
-          let c = format!("%{dst} = string.format [{}]", segs.join(", "));
+        Insn::StringFormat {
+          dst,
+          segments,
+          ty_id,
+        } => {
+          let c = format!(
+            "%{dst} = string.format [{}] : {}",
+            values(segments),
+            ty_of(*ty_id)
+          );
 
           self.sir_instruction(&c);
         }
@@ -762,7 +966,9 @@ impl PrettyPrinter {
           concrete_ty,
         } => {
           let c = format!(
-            "%{dst} = coerce_to_dyn %{src} : any {abstract_name:?} from {concrete_ty:?}"
+            "%{dst} = coerce_to_dyn %{src} : any {} from {}",
+            name(*abstract_name),
+            ty_of(*concrete_ty)
           );
 
           self.sir_instruction(&c);
@@ -776,13 +982,12 @@ impl PrettyPrinter {
           args,
           ty_id,
         } => {
-          let arg_list = args
-            .iter()
-            .map(|a| format!("%{a}"))
-            .collect::<Vec<_>>()
-            .join(", ");
           let c = format!(
-            "%{dst} = dyn_dispatch %{recv}.{method_name:?}[#{method_index}]({arg_list}) : {ty_id:?} via {abstract_name:?}"
+            "%{dst} = dyn_dispatch %{recv}.{}[#{method_index}]({}) : {} via {}",
+            name(*method_name),
+            values(args),
+            ty_of(*ty_id),
+            name(*abstract_name)
           );
 
           self.sir_instruction(&c);
@@ -792,9 +997,11 @@ impl PrettyPrinter {
 
           self.sir_instruction(&c);
         }
-        Insn::TestRun { callee, .. } => {
-          let name = interner.get(*callee);
-          let c = format!("test.run {name}");
+        Insn::TestRun {
+          callee,
+          callee_pack,
+        } => {
+          let c = format!("test.run {}", qualified(callee_pack, *callee));
 
           self.sir_instruction(&c);
         }
@@ -1162,26 +1369,7 @@ impl PrettyPrinter {
     self.buffer.str(". ");
 
     // Get token name with value if applicable
-    let token_name = if tree.spans.len() > node_idx {
-      let span = tree.spans[node_idx];
-      let start = span.start as usize;
-      let end = start + span.len as usize;
-
-      match node.token {
-        Token::Ident | Token::Int | Token::Float | Token::String => {
-          // Synthetic nodes (template rebalancing, splices) carry
-          // spans outside the source — render a marker instead of
-          // slicing out of bounds.
-          match printee.source.get(start..end) {
-            Some(value) => format!("{:?}({value})", node.token),
-            None => format!("{:?}(<synthetic>)", node.token),
-          }
-        }
-        _ => format!("{:?}", node.token),
-      }
-    } else {
-      format!("{:?}", node.token)
-    };
+    let token_name = Self::node_label(tree, node_idx, printee.source);
 
     self.buffer.str(&token_name);
     self.buffer.newline();
@@ -1214,6 +1402,446 @@ impl PrettyPrinter {
       }
     }
   }
+
+  /// A node's token, with its lexeme for identifiers and
+  /// literals — `Ident(x)`, `Int(42)`, `Plus`.
+  fn node_label(tree: &Tree, node_idx: usize, source: &str) -> String {
+    let node = &tree.nodes[node_idx];
+
+    if tree.spans.len() > node_idx {
+      let span = tree.spans[node_idx];
+      let start = span.start as usize;
+      let end = start + span.len as usize;
+
+      match node.token {
+        Token::Ident | Token::Int | Token::Float | Token::String => {
+          // Synthetic nodes (template rebalancing, splices) carry
+          // spans outside the source — render a marker instead of
+          // slicing out of bounds.
+          match source.get(start..end) {
+            Some(value) => format!("{:?}({value})", node.token),
+            None => format!("{:?}(<synthetic>)", node.token),
+          }
+        }
+        _ => format!("{:?}", node.token),
+      }
+    } else {
+      format!("{:?}", node.token)
+    }
+  }
+
+  /// Writes the typed tree: every node the ty-checker gave a
+  /// type, in tree order, with the type it resolved to.
+  pub fn format_types(
+    &mut self,
+    tree: &Tree,
+    source: &str,
+    annotations: &[Annotation],
+    interner: &Interner,
+    ty: &TyChecker,
+  ) {
+    // Later annotations of a node win — the value it finally
+    // left behind.
+    let typed = annotations
+      .iter()
+      .filter(|a| a.node_idx < tree.nodes.len())
+      .map(|a| (a.node_idx, a.ty_id))
+      .collect::<BTreeMap<_, _>>();
+
+    self.buffer.str("├── TYPED TREE — ");
+    self.buffer.u32(typed.len() as u32);
+    self.buffer.str(" typed nodes.\n");
+    self.buffer.str("│\n");
+
+    for (i, (node_idx, ty_id)) in typed.iter().enumerate() {
+      let label = Self::node_label(tree, *node_idx, source);
+
+      if i == typed.len() - 1 {
+        self.buffer.str("└── ");
+      } else {
+        self.buffer.str("├── ");
+      }
+
+      self.buffer.u32(*node_idx as u32);
+      self.buffer.str(". ");
+      self.buffer.str(&label);
+      self.padding(24 - label.len().min(24));
+      self.buffer.str(" : ");
+      self.buffer.str(&Self::ty_label(interner, ty, *ty_id));
+      self.buffer.newline();
+    }
+  }
+
+  /// The label of a type in the dumps, inference variables
+  /// resolved through the ty-checker's substitutions. An
+  /// unsolved variable reads `?N`; integers read by width,
+  /// `i64`.
+  pub fn ty_label(interner: &Interner, ty: &TyChecker, ty_id: TyId) -> String {
+    Self::label(interner, ty, ty_id, false)
+  }
+
+  /// [`Self::ty_label`], spelled the way a zo source writes
+  /// the type — `int`, `s64` — for hovers, hints and the
+  /// annotations the language server inserts.
+  pub fn source_ty_label(
+    interner: &Interner,
+    ty: &TyChecker,
+    ty_id: TyId,
+  ) -> String {
+    Self::label(interner, ty, ty_id, true)
+  }
+
+  fn label(
+    interner: &Interner,
+    ty: &TyChecker,
+    ty_id: TyId,
+    source: bool,
+  ) -> String {
+    let table = &ty.ty_table;
+
+    match ty.kind_of_ro(ty_id) {
+      Ty::Int { width, .. }
+        if source && let Some(name) = int_keyword(width) =>
+      {
+        name.to_string()
+      }
+      Ty::Struct(sid) => table
+        .struct_ty(sid)
+        .map_or_else(|| "?".to_string(), |s| interner.get(s.name).to_string()),
+      Ty::Enum(eid) => table
+        .enum_ty(eid)
+        .map_or_else(|| "?".to_string(), |e| interner.get(e.name).to_string()),
+      Ty::Array(aid) => match table.array(aid) {
+        Some(arr) => {
+          let elem = Self::label(interner, ty, arr.elem_ty, source);
+
+          match arr.size {
+            Some(size) => format!("[{size}]{elem}"),
+            None => format!("[]{elem}"),
+          }
+        }
+        None => "[]?".to_string(),
+      },
+      Ty::Tuple(tid) => match table.tuple(tid) {
+        Some(tuple) => {
+          let elems = table
+            .tuple_elems(tuple)
+            .iter()
+            .map(|elem| Self::label(interner, ty, *elem, source))
+            .collect::<Vec<_>>();
+
+          format!("({})", elems.join(", "))
+        }
+        None => "(?)".to_string(),
+      },
+      Ty::Fun(fid) => match table.fun(&fid) {
+        Some(fun) => {
+          let params = table
+            .fun_params(fun)
+            .iter()
+            .map(|param| Self::label(interner, ty, *param, source))
+            .collect::<Vec<_>>();
+
+          format!(
+            "Fn({}) -> {}",
+            params.join(", "),
+            Self::label(interner, ty, fun.return_ty, source)
+          )
+        }
+        None => "Fn(?)".to_string(),
+      },
+      Ty::Ref(rid) => match table.reference(rid) {
+        Some(r) => {
+          let inner = Self::label(interner, ty, r.inner_ty, source);
+
+          match r.mutability {
+            Mutability::No => format!("&{inner}"),
+            Mutability::Yes => format!("&mut {inner}"),
+          }
+        }
+        None => "&?".to_string(),
+      },
+      Ty::ChannelTx(elem) => {
+        format!("Tx<{}>", Self::label(interner, ty, elem, source))
+      }
+      Ty::ChannelRx(elem) => {
+        format!("Rx<{}>", Self::label(interner, ty, elem, source))
+      }
+      Ty::Task(ret) => {
+        format!("Task<{}>", Self::label(interner, ty, ret, source))
+      }
+      Ty::Infer(var) => format!("?{}", var.0),
+      Ty::Param(name) => format!("${}", interner.get(name)),
+      Ty::Abstract(name) => interner.get(name).to_string(),
+      Ty::Dyn(name) => format!("any {}", interner.get(name)),
+      other => type_name(&[other], TyId(0)),
+    }
+  }
+
+  /// One Graphviz `digraph` per function, as `(name, dot)`:
+  /// its basic blocks, their instructions and the values live
+  /// on entry to and exit from each block.
+  pub fn cfg_graphs(
+    sir: &Sir,
+    interner: &Interner,
+    ty: &TyChecker,
+  ) -> Vec<(String, Vec<u8>)> {
+    let insns = &sir.instructions;
+    let value_ids = compute_value_ids(insns);
+    let mut graphs = Vec::new();
+    let mut i = 0;
+
+    while i < insns.len() {
+      let Insn::FunDef {
+        name, owning_pack, ..
+      } = &insns[i]
+      else {
+        i += 1;
+        continue;
+      };
+
+      // Body range `[start, end)` — `end` is the next `FunDef`.
+      let start = i;
+      let mut end = i + 1;
+
+      while end < insns.len() && !matches!(insns[end], Insn::FunDef { .. }) {
+        end += 1;
+      }
+
+      let label = match owning_pack {
+        Some(pack) => {
+          format!("{}::{}", interner.get(*pack), interner.get(*name))
+        }
+        None => interner.get(*name).to_string(),
+      };
+
+      let mut pp = PrettyPrinter::new();
+
+      pp.format_cfg(sir, &value_ids, start..end, &label, interner, ty);
+      graphs.push((label, pp.finish()));
+
+      i = end;
+    }
+
+    graphs
+  }
+
+  /// Writes the `digraph` of the function whose instructions
+  /// span `range`.
+  fn format_cfg(
+    &mut self,
+    sir: &Sir,
+    value_ids: &[Option<ValueId>],
+    range: std::ops::Range<usize>,
+    label: &str,
+    interner: &Interner,
+    ty: &TyChecker,
+  ) {
+    let insns = &sir.instructions;
+    let (start, end) = (range.start, range.end);
+    let cfg = Cfg::build(insns, start, end);
+    let live = analyze(insns, start, end, value_ids, sir.next_value_id);
+
+    // Bit index → `ValueId`, to print live sets by value.
+    let mut vids = vec![0; live.vid_map.len()];
+
+    for (vid, bit) in &live.vid_map {
+      vids[*bit as usize] = *vid;
+    }
+
+    self.buffer.str("digraph \"");
+    self.buffer.str(&dot_escape(label));
+    self.buffer.str("\" {\n");
+    self
+      .buffer
+      .str("  node [shape=box, fontname=\"monospace\"];\n");
+
+    for (b, block) in cfg.blocks.iter().enumerate() {
+      // The entry block opens with the `FunDef` itself —
+      // the graph is already named after it.
+      let body_start = if block.start == start {
+        start + 1
+      } else {
+        block.start
+      };
+
+      let mut body = PrettyPrinter::new();
+
+      body.sir_insns(&insns[body_start..block.end], interner, ty);
+
+      let mut text = format!("b{b}\n");
+
+      text.push_str(&live_line(
+        "in:  ",
+        &live.live_in[block.start - start],
+        &vids,
+      ));
+      text.push_str(&body.as_string());
+      text.push_str(&live_line(
+        "out: ",
+        &live.live_out[block.end - 1 - start],
+        &vids,
+      ));
+
+      self.buffer.str("  b");
+      self.buffer.u32(b as u32);
+      self.buffer.str(" [label=\"");
+
+      for line in text.lines() {
+        self.buffer.str(&dot_escape(line));
+        self.buffer.str("\\l");
+      }
+
+      self.buffer.str("\"];\n");
+    }
+
+    for (b, block) in cfg.blocks.iter().enumerate() {
+      for succ in &block.succs {
+        self.buffer.str("  b");
+        self.buffer.u32(b as u32);
+        self.buffer.str(" -> b");
+        self.buffer.u32(*succ as u32);
+        self.buffer.str(";\n");
+      }
+    }
+
+    self.buffer.str("}\n");
+  }
+
+  /// Writes the ownership pass's move facts and the elide/keep
+  /// decision for every scope-exit drop.
+  pub fn format_ownership(
+    &mut self,
+    trace: &OwnershipTrace,
+    interner: &Interner,
+  ) {
+    self.buffer.str("OWNERSHIP FACTS:\n");
+    self.buffer.str("────────────────\n");
+
+    self.buffer.str("moves:\n");
+
+    for fact in &trace.moves {
+      let kind = match fact.kind {
+        MoveKind::Consume => "consume",
+        MoveKind::Copy => "copy",
+        MoveKind::Return => "return",
+        MoveKind::Capture => "capture",
+      };
+
+      self.ownership_row(
+        interner,
+        fact.function,
+        fact.binding,
+        kind,
+        fact.span,
+      );
+    }
+
+    self.buffer.str("drops:\n");
+
+    for fact in &trace.drops {
+      let decision = match fact.decision {
+        DropDecision::Keep { dtor } => {
+          format!("keep -> {}", interner.get(dtor))
+        }
+        DropDecision::Elide(elision) => {
+          let why = match elision {
+            Elision::Borrowed => "borrowed",
+            Elision::Reported => "already reported",
+            Elision::NoDestructor => "no destructor",
+            Elision::Moved => "moved on all paths",
+            Elision::Conditional => "moved on some paths",
+          };
+
+          format!("elide ({why})")
+        }
+      };
+
+      self.ownership_row(
+        interner,
+        fact.function,
+        fact.binding,
+        &decision,
+        fact.span,
+      );
+    }
+  }
+
+  fn ownership_row(
+    &mut self,
+    interner: &Interner,
+    function: Symbol,
+    binding: Symbol,
+    what: &str,
+    span: Span,
+  ) {
+    self.buffer.str("  @");
+    self.buffer.str(interner.get(function));
+    self.buffer.str(": ");
+    self.buffer.str(interner.get(binding));
+    self.buffer.char(b' ');
+    self.buffer.str(what);
+    self.buffer.str(" (");
+    self.buffer.u32(span.start);
+    self.buffer.str("..");
+    self.buffer.u32(span.end());
+    self.buffer.str(")\n");
+  }
+}
+
+/// A symbol as textual SIR spells it — bare when it reads as
+/// an identifier, quoted otherwise (`"Point::new"`), so a `::`
+/// inside a mangled name never reads as a pack path.
+fn sir_name(name: &str) -> String {
+  let mut chars = name.chars();
+  let bare = chars
+    .next()
+    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+  if bare {
+    name.to_string()
+  } else {
+    format!("{name:?}")
+  }
+}
+
+/// Escapes a Graphviz double-quoted string.
+fn dot_escape(text: &str) -> String {
+  text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// One `in:` / `out:` line of a block label — the live values,
+/// `-` when none.
+fn live_line(prefix: &str, set: &BitVec, vids: &[u32]) -> String {
+  let live = vids
+    .iter()
+    .enumerate()
+    .filter(|(bit, _)| set.test(*bit))
+    .map(|(_, vid)| format!("%{vid}"))
+    .collect::<Vec<_>>();
+
+  if live.is_empty() {
+    format!("{prefix}-\n")
+  } else {
+    format!("{prefix}{}\n", live.join(" "))
+  }
+}
+
+/// The keyword a zo source spells an integer width with —
+/// `int` for `s32`. The target-sized width has none.
+fn int_keyword(width: IntWidth) -> Option<&'static str> {
+  match width {
+    IntWidth::S8 => Some("s8"),
+    IntWidth::S16 => Some("s16"),
+    IntWidth::S32 => Some("int"),
+    IntWidth::S64 => Some("s64"),
+    IntWidth::U8 => Some("u8"),
+    IntWidth::U16 => Some("u16"),
+    IntWidth::U32 => Some("uint"),
+    IntWidth::U64 => Some("u64"),
+    IntWidth::Arch => None,
+  }
 }
 
 impl Default for PrettyPrinter {
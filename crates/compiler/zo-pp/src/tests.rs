@@ -2,7 +2,8 @@ use crate::PrettyPrinter;
 
 use zo_interner::Interner;
 use zo_sir::{Insn, Sir};
-use zo_ty::{FloatWidth, IntWidth, Ty, TyId};
+use zo_ty::{FloatWidth, IntWidth, Ty};
+use zo_ty_checker::TyChecker;
 use zo_value::ValueId;
 
 /// The SIR dump prints each constant's real type, resolved from
@@ -11,46 +12,45 @@ use zo_value::ValueId;
 /// whole debugging session.
 #[test]
 fn const_types_resolve_from_ty_id_not_hardcoded() {
-  let tys = [
-    Ty::Float(FloatWidth::F64),
-    Ty::Int {
-      signed: true,
-      width: IntWidth::S64,
-    },
-    Ty::Float(FloatWidth::F32),
-    Ty::Int {
-      signed: false,
-      width: IntWidth::U8,
-    },
-  ];
+  let mut ty = TyChecker::new();
+  let f64_ty = ty.intern_ty(Ty::Float(FloatWidth::F64));
+  let s64_ty = ty.intern_ty(Ty::Int {
+    signed: true,
+    width: IntWidth::S64,
+  });
+  let f32_ty = ty.intern_ty(Ty::Float(FloatWidth::F32));
+  let u8_ty = ty.intern_ty(Ty::Int {
+    signed: false,
+    width: IntWidth::U8,
+  });
 
   let mut sir = Sir::new();
 
   sir.emit(Insn::ConstFloat {
     dst: ValueId(0),
     value: 1.5,
-    ty_id: TyId(0),
+    ty_id: f64_ty,
   });
   sir.emit(Insn::ConstInt {
     dst: ValueId(1),
     value: 42,
-    ty_id: TyId(1),
+    ty_id: s64_ty,
   });
   sir.emit(Insn::ConstFloat {
     dst: ValueId(2),
     value: 0.25,
-    ty_id: TyId(2),
+    ty_id: f32_ty,
   });
   sir.emit(Insn::ConstInt {
     dst: ValueId(3),
     value: 7,
-    ty_id: TyId(3),
+    ty_id: u8_ty,
   });
 
   let interner = Interner::new();
   let mut pp = PrettyPrinter::new();
 
-  pp.format_sir(&sir, &interner, &tys);
+  pp.format_sir(&sir, &interner, &ty);
 
   let out = String::from_utf8(pp.finish()).unwrap();
 
@@ -67,3 +67,69 @@ fn const_types_resolve_from_ty_id_not_hardcoded() {
   // The hardcoded-`f32`-for-every-float bug must stay dead.
   assert!(!out.contains("1.5 : f32"), "f64 must not read f32: {out}");
 }
+
+/// `--emit=cfg` builds one digraph per function: a node per
+/// basic block carrying its live-in / live-out values and one
+/// edge per control-flow successor.
+#[test]
+fn cfg_dump_is_graphviz_with_live_sets() {
+  use zo_span::Span;
+  use zo_ty::SelfKind;
+  use zo_value::{FunctionKind, Pubness};
+
+  let mut interner = Interner::new();
+  let main = interner.intern("main");
+  let mut ty = TyChecker::new();
+  let bool_ty = ty.bool_type();
+  let mut sir = Sir::new();
+
+  for insn in [
+    Insn::FunDef {
+      name: main,
+      params: vec![],
+      return_ty: bool_ty,
+      body_start: 0,
+      kind: FunctionKind::UserDefined,
+      pubness: Pubness::No,
+      self_kind: SelfKind::None,
+      link_name: None,
+      env_slot: None,
+      owning_pack: None,
+      span: Span::ZERO,
+      is_test: false,
+    },
+    Insn::ConstBool {
+      dst: ValueId(0),
+      value: true,
+      ty_id: bool_ty,
+    },
+    Insn::BranchIfNot {
+      cond: ValueId(0),
+      target: 1,
+    },
+    Insn::Jump { target: 1 },
+    Insn::Label { id: 1 },
+    Insn::Return {
+      value: Some(ValueId(0)),
+      ty_id: bool_ty,
+    },
+  ] {
+    sir.emit(insn);
+  }
+
+  let graphs = PrettyPrinter::cfg_graphs(&sir, &interner, &ty);
+
+  assert_eq!(graphs.len(), 1);
+  assert_eq!(graphs[0].0, "main");
+
+  let out = String::from_utf8(graphs[0].1.clone()).unwrap();
+
+  assert!(out.starts_with("digraph \"main\" {"), "{out}");
+  assert!(out.contains("b0 -> b1;"), "{out}");
+  assert!(out.contains("b0 -> b2;"), "{out}");
+  assert!(out.contains("b1 -> b2;"), "{out}");
+  // `%0` flows from the entry block into the return.
+  assert!(out.contains("out: %0"), "{out}");
+  assert!(out.contains("in:  %0"), "{out}");
+  assert!(out.contains("ret %0"), "{out}");
+}
@@ -336,8 +336,10 @@ pub fn allocate_function(ctx: &AllocCtx<'_>, result: &mut RegAlloc) {
     // `ArrayPush` itself may also call `_realloc` when
     // capacity is exhausted. Either makes the function
     // non-leaf and forces FP/LR save in the prologue.
+    // A closure environment is laid out the same way.
     if matches!(insn, Insn::ArrayLiteral { .. })
       || matches!(insn, Insn::ArrayPush { .. })
+      || matches!(insn, Insn::ClosureEnv { .. })
     {
       has_calls = true;
     }
@@ -354,6 +356,7 @@ pub fn allocate_function(ctx: &AllocCtx<'_>, result: &mut RegAlloc) {
         | Insn::ChannelSend { .. }
         | Insn::ChannelRecv { .. }
         | Insn::ChannelClose { .. }
+        | Insn::ClosureEnvFree { .. }
         | Insn::TaskSpawn { .. }
         | Insn::TaskAwait { .. }
         | Insn::TaskCancelled { .. }
@@ -379,7 +382,7 @@ pub fn allocate_function(ctx: &AllocCtx<'_>, result: &mut RegAlloc) {
     }
 
     // `Insn::BinOp` on `Str` operands lowers to runtime
-    // calls — `_memcmp` for `Eq`/`Neq`, `zo_str_concat`
+    // calls — `_zo_str_eq` for `Eq`/`Neq`, `zo_str_concat`
     // for `Concat` (see arm codegen). Without this gate,
     // the leaf-frame skips the caller-save reserve and
     // the emitted spills overwrite the function's own
@@ -736,6 +739,7 @@ pub fn allocate_function(ctx: &AllocCtx<'_>, result: &mut RegAlloc) {
           "HashMap::get" => struct_slots += 4,
           "HashMap::contains_key" => struct_slots += 1,
           "HashMap::remove" => struct_slots += 4,
+          "MapKeys::key_at" => struct_slots += 3,
 
           // Vec apply-method scratch budgets. Mirror the
           // bumps in `emit_vec_*` exactly — mismatched
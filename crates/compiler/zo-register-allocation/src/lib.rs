@@ -638,6 +638,22 @@ pub fn flat_struct_slots_of(
 
       Some(total)
     }
+    Ty::Tuple(tid) => {
+      // Same layout as a struct: one slot per element, then
+      // each struct element's recursive cost.
+      let tuple = ty_table.tuple(tid)?;
+      let elems = ty_table.tuple_elems(tuple);
+
+      let mut total = elems.len() as u32;
+
+      for &elem_ty in elems {
+        if matches!(resolve_ty(tys, elem_ty), Ty::Struct(_)) {
+          total += flat_struct_slots_of(elem_ty, tys, ty_table)?;
+        }
+      }
+
+      Some(total)
+    }
     Ty::Enum(eid) => {
       // Layout for an enum returned across the call
       // boundary:
@@ -694,7 +710,8 @@ pub fn flat_struct_slots_of(
 
 /// Slots a struct-returning fn claims in its caller's frame.
 ///
-/// @note — `None` when `return_ty` isn't a struct, or when
+/// @note — `None` when `return_ty` isn't a struct, enum or
+/// tuple, or when
 /// the type-view is absent / the struct id is missing.
 /// Drives `build_struct_return_map`'s registration filter.
 fn struct_return_slots(
@@ -704,7 +721,9 @@ fn struct_return_slots(
   let (tys, tt) = type_view?;
 
   match resolve_ty(tys, return_ty) {
-    Ty::Struct(_) | Ty::Enum(_) => flat_struct_slots_of(return_ty, tys, tt),
+    Ty::Struct(_) | Ty::Enum(_) | Ty::Tuple(_) => {
+      flat_struct_slots_of(return_ty, tys, tt)
+    }
     _ => None,
   }
 }
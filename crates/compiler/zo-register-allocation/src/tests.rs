@@ -50,6 +50,7 @@ fn fundef(
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
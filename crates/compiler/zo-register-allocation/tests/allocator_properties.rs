@@ -78,6 +78,7 @@ fn fundef(interner: &mut Interner, params: usize) -> Insn {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
@@ -1,7 +1,8 @@
 use crate::collector;
 use crate::collector::Detail;
 
-use zo_error::{Error, ErrorKind};
+use zo_error::lint::LintLevels;
+use zo_error::{Error, ErrorKind, Severity};
 
 use std::collections::HashMap;
 
@@ -78,6 +79,10 @@ pub struct ErrorAggregator {
   /// names, a name suggestion). The renderer looks it up to
   /// enrich the diagnostic. Empty for errors with no detail.
   details: HashMap<Error, Detail>,
+  /// Severity overrides keyed by the `Error` they apply to —
+  /// a lint promoted by `deny`. Errors absent from the map
+  /// keep the severity their kind implies.
+  severities: HashMap<Error, Severity>,
 }
 
 impl ErrorAggregator {
@@ -92,6 +97,58 @@ impl ErrorAggregator {
     self.group_errors_by_phase(errors);
   }
 
+  /// Adds errors resolved against configured lint levels:
+  /// allowed lints are dropped, and a lint whose level
+  /// changes its severity records the override the renderers
+  /// read through [`Self::severity_of`].
+  pub fn add_errors_with_levels(
+    &mut self,
+    errors: &[Error],
+    levels: &LintLevels,
+  ) {
+    if levels.is_default() {
+      self.add_errors(errors);
+
+      return;
+    }
+
+    let mut kept = Vec::with_capacity(errors.len());
+
+    for error in errors {
+      let Some(severity) = levels.severity(error) else {
+        continue;
+      };
+
+      if severity != error.severity() {
+        self.severities.insert(*error, severity);
+      }
+
+      kept.push(*error);
+    }
+
+    self.group_errors_by_phase(&kept);
+  }
+
+  /// The severity an error is reported with — its kind's
+  /// default unless a lint level overrode it.
+  pub fn severity_of(&self, error: &Error) -> Severity {
+    self
+      .severities
+      .get(error)
+      .copied()
+      .unwrap_or_else(|| error.severity())
+  }
+
+  /// Whether any collected error is reported as a hard error,
+  /// lint overrides included — the signal that the build fails.
+  pub fn has_hard_errors(&self) -> bool {
+    self
+      .phase_errors
+      .iter()
+      .flat_map(|p| &p.errors)
+      .any(|e| self.severity_of(e) == Severity::Error)
+  }
+
   /// Registers dynamic detail so the renderer can enrich the
   /// matching diagnostics.
   pub fn add_details(&mut self, details: &[(Error, Detail)]) {
@@ -138,7 +195,8 @@ impl ErrorAggregator {
         | ErrorKind::InvalidTemplateToken
         | ErrorKind::UnmatchedOpeningDelimiter
         | ErrorKind::UnmatchedClosingDelimiter
-        | ErrorKind::MismatchedDelimiter => {
+        | ErrorKind::MismatchedDelimiter
+        | ErrorKind::InvalidFormatSpec => {
           tokenizer_errors.push(*error);
         }
 
@@ -156,6 +214,8 @@ impl ErrorAggregator {
         | ErrorKind::ExpectedTemplate
         | ErrorKind::MismatchedTags
         | ErrorKind::ExpectedAttributeValue
+        | ErrorKind::MalformedSir
+        | ErrorKind::UnknownSirType
         | ErrorKind::ExpectedClosureBody
         | ErrorKind::ExpectedToken
         | ErrorKind::ParserInfiniteLoop
@@ -218,6 +278,7 @@ impl ErrorAggregator {
         | ErrorKind::EventOnComponent
         | ErrorKind::StatementInTemplate
         | ErrorKind::PackDotAccess
+        | ErrorKind::UnknownLint
         | ErrorKind::UninitializedVariable
         | ErrorKind::InvalidSelfReference
         | ErrorKind::InvalidTypeAnnotation => {
@@ -312,6 +373,7 @@ impl ErrorAggregator {
     self.phase_errors.clear();
     self.phase_errors.shrink_to_fit();
     self.details.clear();
+    self.severities.clear();
   }
 
   /// Returns a summary of errors by phase.
@@ -0,0 +1,480 @@
+//! Applying fix-its to source text — the engine behind
+//! `--fix` / `--fix-dry-run`.
+//!
+//! The machine renderers only *describe* edits; this module
+//! performs them. Everything here is pure string work over one
+//! file's source: collect the edits a compile produced, drop
+//! the ones that overlap, splice the rest in, and render the
+//! before/after pair as a unified diff. Re-analysis and the
+//! fixpoint loop live in the driver — it owns the compiler.
+//!
+//! Only the literal [`FixIt`](crate::fixes::FixIt)s from
+//! [`fixes_for`] marked machine-applicable are applied. A `Suggestion` / `Rename`
+//! detail is a guess (the closest in-scope name, a
+//! convention-correct spelling of a declaration whose uses
+//! stay untouched) — good enough to offer, not to apply
+//! unattended.
+
+use crate::aggregator::ErrorAggregator;
+use crate::fixes::fixes_for;
+use crate::locate::fix_span;
+
+use std::ops::Range;
+
+/// Number of unchanged lines shown around each diff hunk —
+/// the `diff -u` / `git diff` default.
+const DIFF_CONTEXT: usize = 3;
+
+/// One concrete edit against a file: replace the byte range
+/// `[start, end)` with `text`. An insert is a zero-length
+/// range, a delete an empty `text`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edit {
+  /// Byte offset the edit starts at.
+  pub start: u32,
+  /// Byte offset the edit ends at (exclusive).
+  pub end: u32,
+  /// Replacement text.
+  pub text: &'static str,
+}
+
+impl Edit {
+  /// The edited byte range.
+  #[inline]
+  pub fn range(&self) -> Range<usize> {
+    self.start as usize..self.end as usize
+  }
+
+  /// Whether two edits touch the same bytes. Two inserts at
+  /// the same point overlap too — their relative order would
+  /// be arbitrary.
+  #[inline]
+  fn overlaps(&self, other: &Self) -> bool {
+    (self.start < other.end && other.start < self.end)
+      || self.start == other.start
+  }
+}
+
+/// The outcome of splicing a batch of edits into a source.
+#[derive(Debug)]
+pub struct Applied {
+  /// The edited source.
+  pub source: String,
+  /// Edits spliced in.
+  pub applied: usize,
+  /// Edits dropped because they overlapped an earlier one.
+  /// The next analysis pass re-derives them against the new
+  /// source when they still apply.
+  pub deferred: usize,
+}
+
+/// The edits a compile produced for file `file_id` (an index
+/// into the compiler's file table, `0` being the entry file).
+/// Takes the most-preferred fix-it of every diagnostic that
+/// has one, when it is machine-applicable, in span order.
+pub fn edits_for(aggregator: &ErrorAggregator, file_id: usize) -> Vec<Edit> {
+  let mut edits = Vec::new();
+
+  for phase_errors in aggregator.errors() {
+    for error in &phase_errors.errors {
+      if error.file_id().map_or(0, |id| id as usize) != file_id {
+        continue;
+      }
+
+      let Some(fix) = fixes_for(error.kind())
+        .first()
+        .filter(|fix| fix.machine_applicable)
+      else {
+        continue;
+      };
+
+      let span = error.span();
+      let (start, end) = fix_span(fix.kind, span.start, span.end());
+
+      edits.push(Edit {
+        start,
+        end,
+        text: fix.text,
+      });
+    }
+  }
+
+  edits.sort_by_key(|edit| (edit.start, edit.end));
+  edits.dedup();
+  edits
+}
+
+/// Splices `edits` into `source`. Edits are taken in span
+/// order; one that overlaps an already accepted edit, or that
+/// falls outside the source or off a char boundary, is
+/// deferred rather than applied.
+pub fn apply(source: &str, edits: &[Edit]) -> Applied {
+  let mut accepted: Vec<&Edit> = Vec::with_capacity(edits.len());
+  let mut deferred = 0;
+
+  for edit in edits {
+    let in_bounds = edit.start <= edit.end
+      && source.is_char_boundary(edit.start as usize)
+      && source.is_char_boundary(edit.end as usize);
+
+    if !in_bounds || accepted.iter().any(|prev| prev.overlaps(edit)) {
+      deferred += 1;
+      continue;
+    }
+
+    accepted.push(edit);
+  }
+
+  accepted.sort_by_key(|edit| (edit.start, edit.end));
+
+  let mut out = String::with_capacity(source.len() + 16 * accepted.len());
+  let mut cursor = 0;
+
+  for edit in &accepted {
+    out.push_str(&source[cursor..edit.start as usize]);
+    out.push_str(edit.text);
+    cursor = edit.end as usize;
+  }
+
+  out.push_str(&source[cursor..]);
+
+  Applied {
+    source: out,
+    applied: accepted.len(),
+    deferred,
+  }
+}
+
+/// Byte ranges of unresolved merge conflicts — from a
+/// `<<<<<<<` marker line through its closing `>>>>>>>` line.
+/// An unterminated conflict runs to the end of the source.
+pub fn conflict_regions(source: &str) -> Vec<Range<usize>> {
+  let mut regions = Vec::new();
+  let mut open = None;
+  let mut offset = 0;
+
+  for line in source.split_inclusive('\n') {
+    if line.starts_with("<<<<<<<") && open.is_none() {
+      open = Some(offset);
+    } else if line.starts_with(">>>>>>>")
+      && let Some(start) = open.take()
+    {
+      regions.push(start..offset + line.len());
+    }
+
+    offset += line.len();
+  }
+
+  if let Some(start) = open {
+    regions.push(start..source.len());
+  }
+
+  regions
+}
+
+/// Whether any edit lands inside (or at the edge of) one of
+/// the conflict `regions`. Such a file is refused outright —
+/// a fix-it computed against half of a conflict is not one
+/// the author asked for.
+pub fn touches_conflict(edits: &[Edit], regions: &[Range<usize>]) -> bool {
+  edits.iter().any(|edit| {
+    let range = edit.range();
+
+    regions
+      .iter()
+      .any(|region| range.start <= region.end && region.start <= range.end)
+  })
+}
+
+/// Renders `old` → `new` as a unified diff with `---` / `+++`
+/// headers naming `path`. Returns an empty string when the two
+/// are identical.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+  if old == new {
+    return String::new();
+  }
+
+  let old_lines = old.split_inclusive('\n').collect::<Vec<_>>();
+  let new_lines = new.split_inclusive('\n').collect::<Vec<_>>();
+  let ops = diff_lines(&old_lines, &new_lines);
+  let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+
+  for hunk in hunks(&ops) {
+    let slice = &ops[hunk];
+    let old_start = slice.first().map_or(0, |op| op.old_index());
+    let new_start = slice.first().map_or(0, |op| op.new_index());
+    let old_len = slice
+      .iter()
+      .filter(|op| !matches!(op, Op::Insert(..)))
+      .count();
+    let new_len = slice
+      .iter()
+      .filter(|op| !matches!(op, Op::Delete(..)))
+      .count();
+
+    out.push_str(&format!(
+      "@@ -{} +{} @@\n",
+      hunk_range(old_start, old_len),
+      hunk_range(new_start, new_len),
+    ));
+
+    for op in slice {
+      let (sign, line) = match *op {
+        Op::Equal(i, _) => (' ', old_lines[i]),
+        Op::Delete(i, _) => ('-', old_lines[i]),
+        Op::Insert(_, j) => ('+', new_lines[j]),
+      };
+
+      out.push(sign);
+      out.push_str(line);
+
+      if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+      }
+    }
+  }
+
+  out
+}
+
+/// One line-level diff operation, carrying the line indices
+/// in the old and the new text it sits at.
+#[derive(Clone, Copy, Debug)]
+enum Op {
+  Equal(usize, usize),
+  Delete(usize, usize),
+  Insert(usize, usize),
+}
+
+impl Op {
+  fn old_index(self) -> usize {
+    match self {
+      Self::Equal(i, _) | Self::Delete(i, _) | Self::Insert(i, _) => i,
+    }
+  }
+
+  fn new_index(self) -> usize {
+    match self {
+      Self::Equal(_, j) | Self::Delete(_, j) | Self::Insert(_, j) => j,
+    }
+  }
+}
+
+/// Line diff by Myers' O(ND) algorithm. Fix-it batches change
+/// a handful of lines, so `D` stays tiny and this is linear in
+/// practice even on large files.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Op> {
+  let n = old.len() as isize;
+  let m = new.len() as isize;
+  let max = (n + m) as usize;
+  let offset = max as isize + 1;
+  let mut v = vec![0isize; 2 * max + 3];
+  let mut trace = Vec::new();
+
+  'search: for d in 0..=max as isize {
+    trace.push(v.clone());
+
+    for k in (-d..=d).step_by(2) {
+      let idx = (k + offset) as usize;
+      let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+        v[idx + 1]
+      } else {
+        v[idx - 1] + 1
+      };
+      let mut y = x - k;
+
+      while x < n && y < m && old[x as usize] == new[y as usize] {
+        x += 1;
+        y += 1;
+      }
+
+      v[idx] = x;
+
+      if x >= n && y >= m {
+        break 'search;
+      }
+    }
+  }
+
+  // Walk the trace backwards to recover the edit script.
+  let mut ops = Vec::with_capacity(max);
+  let (mut x, mut y) = (n, m);
+
+  for (d, v) in trace.iter().enumerate().rev() {
+    let d = d as isize;
+    let k = x - y;
+    let idx = (k + offset) as usize;
+    let prev_k = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+      k + 1
+    } else {
+      k - 1
+    };
+    let prev_x = v[(prev_k + offset) as usize];
+    let prev_y = prev_x - prev_k;
+
+    while x > prev_x && y > prev_y {
+      x -= 1;
+      y -= 1;
+      ops.push(Op::Equal(x as usize, y as usize));
+    }
+
+    if d > 0 {
+      if x == prev_x {
+        ops.push(Op::Insert(x as usize, prev_y as usize));
+      } else {
+        ops.push(Op::Delete(prev_x as usize, y as usize));
+      }
+    }
+
+    x = prev_x;
+    y = prev_y;
+  }
+
+  ops.reverse();
+  ops
+}
+
+/// Groups the edit script into hunks: every run of changes
+/// plus up to [`DIFF_CONTEXT`] equal lines on each side, with
+/// runs closer than twice the context merged into one hunk.
+fn hunks(ops: &[Op]) -> Vec<Range<usize>> {
+  let mut hunks: Vec<Range<usize>> = Vec::new();
+
+  for (i, op) in ops.iter().enumerate() {
+    if matches!(op, Op::Equal(..)) {
+      continue;
+    }
+
+    let start = i.saturating_sub(DIFF_CONTEXT);
+    let end = (i + 1 + DIFF_CONTEXT).min(ops.len());
+
+    match hunks.last_mut() {
+      Some(last) if start <= last.end => last.end = end,
+      _ => hunks.push(start..end),
+    }
+  }
+
+  hunks
+}
+
+/// A `start,len` hunk header range. Lines are 1-indexed; an
+/// empty range names the line *before* it, per the format.
+fn hunk_range(start: usize, len: usize) -> String {
+  match len {
+    0 => format!("{start},0"),
+    1 => format!("{}", start + 1),
+    _ => format!("{},{len}", start + 1),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use zo_error::{Error, ErrorKind};
+  use zo_span::Span;
+
+  fn insert(at: u32, text: &'static str) -> Edit {
+    Edit {
+      start: at,
+      end: at,
+      text,
+    }
+  }
+
+  #[test]
+  fn edits_come_from_the_first_fix_it_per_diagnostic() {
+    let mut agg = ErrorAggregator::new();
+
+    agg.add_errors(&[
+      Error::new(ErrorKind::ExpectedSemicolon, Span::new(9, 1)),
+      // Offered, but not machine-applicable.
+      Error::new(ErrorKind::ImmutableVariable, Span::new(4, 4)),
+      // No fix-it for a type mismatch.
+      Error::new(ErrorKind::TypeMismatch, Span::new(0, 1)),
+      // Another file.
+      Error::with_file(ErrorKind::ExpectedComma, Span::new(2, 1), 1),
+    ]);
+
+    assert_eq!(edits_for(&agg, 0), vec![insert(9, ";")]);
+    assert_eq!(edits_for(&agg, 1), vec![insert(2, ",")]);
+  }
+
+  #[test]
+  fn apply_splices_in_span_order() {
+    let applied = apply("imu x = 1\n", &[insert(4, "mut "), insert(9, ";")]);
+
+    assert_eq!(applied.source, "imu mut x = 1;\n");
+    assert_eq!(applied.applied, 2);
+    assert_eq!(applied.deferred, 0);
+  }
+
+  #[test]
+  fn overlapping_edits_are_deferred() {
+    let edits = [
+      Edit {
+        start: 0,
+        end: 3,
+        text: "abc",
+      },
+      Edit {
+        start: 2,
+        end: 4,
+        text: "z",
+      },
+      insert(5, ";"),
+      insert(5, ","),
+    ];
+    let applied = apply("0123456", &edits);
+
+    assert_eq!(applied.source, "abc34;56");
+    assert_eq!(applied.applied, 2);
+    assert_eq!(applied.deferred, 2);
+  }
+
+  #[test]
+  fn out_of_bounds_edits_are_deferred() {
+    let applied = apply("é", &[insert(1, "x"), insert(9, "y")]);
+
+    assert_eq!(applied.source, "é");
+    assert_eq!(applied.deferred, 2);
+  }
+
+  #[test]
+  fn conflict_regions_cover_marker_blocks() {
+    let source = "a\n<<<<<<< HEAD\nb\n=======\nc\n>>>>>>> topic\nd\n";
+    let regions = conflict_regions(source);
+
+    assert_eq!(regions, vec![2..41]);
+    assert!(touches_conflict(&[insert(15, ";")], &regions));
+    assert!(!touches_conflict(&[insert(0, ";")], &regions));
+    assert!(!touches_conflict(&[insert(43, ";")], &regions));
+  }
+
+  #[test]
+  fn unified_diff_renders_hunks_with_context() {
+    let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+    let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n";
+    let diff = unified_diff("foo.zo", old, new);
+
+    assert_eq!(
+      diff,
+      "--- a/foo.zo\n+++ b/foo.zo\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n",
+    );
+  }
+
+  #[test]
+  fn unified_diff_marks_missing_trailing_newline() {
+    let diff = unified_diff("f.zo", "a", "a;");
+
+    assert_eq!(
+      diff,
+      "--- a/f.zo\n+++ b/f.zo\n@@ -1 +1 @@\n-a\n\\ No newline at end of file\n+a;\n\\ No newline at end of file\n",
+    );
+  }
+
+  #[test]
+  fn identical_sources_diff_to_nothing() {
+    assert_eq!(unified_diff("f.zo", "a\n", "a\n"), "");
+  }
+}
@@ -1,3 +1,4 @@
+use zo_error::lint::LintScope;
 use zo_error::{Error, Severity};
 use zo_span::Span;
 
@@ -69,6 +70,30 @@ pub enum Detail {
   /// so it's discarded. Carries the value's type (`found`).
   /// Primary caret on the value, secondary on the function name.
   DiscardedValue { found: Box<str> },
+  /// The values a `match` leaves uncovered, as patterns —
+  /// `(None, false)` — and the byte offset of its closing
+  /// `}`, where the fix inserts one arm per pattern.
+  MissingArms {
+    patterns: Box<[Box<str>]>,
+    rbrace: u32,
+  },
+  /// A type lacks an `apply` a bound calls for. Carries the
+  /// type, the abstract it doesn't apply, and the bound that
+  /// implies it when that abstract is one of the bound's
+  /// supers (`Eq` for `Ord: Eq`).
+  MissingImpl {
+    ty: Box<str>,
+    abstract_name: Box<str>,
+    required_by: Option<Box<str>>,
+  },
+  /// A derive attribute meets a field it can't synthesize
+  /// for. Carries the attribute (`eq`, `serialize`), the
+  /// field's name and its type.
+  DeriveField {
+    derive: Box<str>,
+    field: Box<str>,
+    ty: Box<str>,
+  },
 }
 
 impl Detail {
@@ -82,6 +107,7 @@ impl Detail {
       Detail::ArgType { .. }
         | Detail::ReturnType { .. }
         | Detail::DiscardedValue { .. }
+        | Detail::MissingImpl { .. }
     )
   }
 
@@ -103,6 +129,20 @@ impl Detail {
       Detail::ArgCount {
         expected, given, ..
       } => format!("expected {expected} arguments, found {given}"),
+      Detail::MissingArms { patterns, .. } => not_covered(patterns),
+      Detail::MissingImpl {
+        ty,
+        abstract_name,
+        required_by,
+      } => match required_by {
+        Some(bound) => format!(
+          "`{ty}` does not apply `{abstract_name}`, which `{bound}` requires"
+        ),
+        None => format!("`{ty}` does not apply `{abstract_name}`"),
+      },
+      Detail::DeriveField { derive, field, ty } => {
+        format!("`{derive}` can't be derived for `{field}: {ty}`")
+      }
       Detail::Suggestion(_) | Detail::Rename(_) | Detail::Cycle(_) => {
         return None;
       }
@@ -145,11 +185,37 @@ impl Detail {
       Detail::DiscardedValue { found } => Some(format!(
         "declare `-> {found}` to return it, or drop the value"
       )),
+      Detail::MissingArms { patterns, .. } => Some(match &**patterns {
+        [pattern] if &**pattern == "_" => "add a `_` wildcard arm".to_owned(),
+        [pattern] => format!("add an arm for `{pattern}`, or a `_` wildcard"),
+        _ => "add an arm for each, or a `_` wildcard".to_owned(),
+      }),
+      Detail::MissingImpl {
+        ty, abstract_name, ..
+      } => Some(format!("add an `apply {abstract_name} for {ty}` block")),
+      Detail::DeriveField { derive, .. } => Some(format!(
+        "drop `{derive}` from the attribute and write the method by hand"
+      )),
       Detail::Types(_) => None,
     }
   }
 }
 
+/// `` pattern `A` not covered ``, `` patterns `A`, `B` and `C`
+/// not covered `` — past three, the rest only by count.
+fn not_covered(patterns: &[Box<str>]) -> String {
+  match patterns {
+    [] => "some values not covered".to_owned(),
+    [a] => format!("pattern `{a}` not covered"),
+    [a, b] => format!("patterns `{a}` and `{b}` not covered"),
+    [a, b, c] => format!("patterns `{a}`, `{b}` and `{c}` not covered"),
+    [a, b, c, rest @ ..] => format!(
+      "patterns `{a}`, `{b}`, `{c}` and {} more not covered",
+      rest.len()
+    ),
+  }
+}
+
 /// Thread-local error reporter with fixed-size buffer.
 /// This provides zero-allocation error collection during compilation.
 pub struct ThreadLocalReporter {
@@ -161,6 +227,10 @@ pub struct ThreadLocalReporter {
   /// annotates. A `Vec` (not a `HashMap`) so `new` stays
   /// `const`; lookups happen only on the cold render path.
   details: Vec<(Error, Detail)>,
+  /// `%%allow(..)` / `%%warn(..)` / `%%deny(..)` scopes seen
+  /// while analyzing. Drained next to the diagnostics they
+  /// govern; resolution happens at report time.
+  lint_scopes: Vec<LintScope>,
 }
 
 impl ThreadLocalReporter {
@@ -171,6 +241,7 @@ impl ThreadLocalReporter {
         MAX_ERRORS],
       count: 0,
       details: Vec::new(),
+      lint_scopes: Vec::new(),
     }
   }
 
@@ -231,6 +302,7 @@ impl ThreadLocalReporter {
   pub fn clear(&mut self) {
     self.count = 0;
     self.details.clear();
+    self.lint_scopes.clear();
   }
 
   /// Returns true if the buffer is full.
@@ -256,6 +328,16 @@ impl ThreadLocalReporter {
 
     (errors, details)
   }
+
+  /// Records a lint-level attribute scope.
+  pub fn report_lint_scope(&mut self, scope: LintScope) {
+    self.lint_scopes.push(scope);
+  }
+
+  /// Drains the recorded lint-level scopes.
+  pub fn drain_lint_scopes(&mut self) -> Vec<LintScope> {
+    std::mem::take(&mut self.lint_scopes)
+  }
 }
 
 impl Default for ThreadLocalReporter {
@@ -331,3 +413,15 @@ pub fn collect_errors() -> Vec<Error> {
 pub fn collect_diagnostics() -> (Vec<Error>, Vec<(Error, Detail)>) {
   REPORTER.with(|reporter| reporter.borrow_mut().drain_with_details())
 }
+
+/// Records a `%%allow(..)`-style scope on the thread-local
+/// reporter. The executor calls this once per lint named by
+/// a level attribute.
+pub fn report_lint_scope(scope: LintScope) {
+  REPORTER.with(|reporter| reporter.borrow_mut().report_lint_scope(scope))
+}
+
+/// Collects the lint-level scopes recorded on this thread.
+pub fn collect_lint_scopes() -> Vec<LintScope> {
+  REPORTER.with(|reporter| reporter.borrow_mut().drain_lint_scopes())
+}
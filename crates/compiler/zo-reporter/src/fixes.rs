@@ -74,6 +74,10 @@ pub struct FixIt {
   /// Surfaced verbatim in the JSON `description` field for
   /// IDE quick-fix menus. Should fit on one screen line.
   pub description: &'static str,
+  /// Whether the edit is correct wherever the diagnostic
+  /// fires, so `--fix` may apply it unattended. One that only
+  /// points the way is offered, never applied.
+  pub machine_applicable: bool,
 }
 
 /// Suggested fixes for an `ErrorKind`. Empty slice means
@@ -90,10 +94,15 @@ pub struct FixIt {
 pub fn fixes_for(kind: ErrorKind) -> &'static [FixIt] {
   match kind {
     // --- Mutability ---
+    //
+    // The primary span sits on the assignment, not on the
+    // declaration the `mut` belongs to — the insert shows the
+    // way but breaks the code if spliced in as is.
     ErrorKind::ImmutableVariable => &[FixIt {
       kind: FixKind::Insert,
       text: "mut ",
       description: "Declare the variable as mutable with `mut`",
+      machine_applicable: false,
     }],
 
     // --- Entry point ---
@@ -101,6 +110,7 @@ pub fn fixes_for(kind: ErrorKind) -> &'static [FixIt] {
       kind: FixKind::Insert,
       text: "\nfun main() {\n}\n",
       description: "Add an empty `main` entry point",
+      machine_applicable: true,
     }],
 
     // --- Missing punctuation: parser ExpectedX variants
@@ -110,56 +120,69 @@ pub fn fixes_for(kind: ErrorKind) -> &'static [FixIt] {
       kind: FixKind::Insert,
       text: ";",
       description: "Add a semicolon to end the statement",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedComma => &[FixIt {
       kind: FixKind::Insert,
       text: ",",
       description: "Add a comma to separate items",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedColon => &[FixIt {
       kind: FixKind::Insert,
       text: ":",
       description: "Add a colon",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedAssignment => &[FixIt {
       kind: FixKind::Insert,
       text: "=",
       description: "Add `=` to assign a value",
+      machine_applicable: true,
     }],
+    // The primary span sits on the `:` written in its place
+    // (`fun f(): int`), so the arrow replaces it.
     ErrorKind::ExpectedArrow => &[FixIt {
-      kind: FixKind::Insert,
+      kind: FixKind::Replace,
       text: "->",
-      description: "Add `->` for the return type",
+      description: "Replace `:` with `->` for the return type",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedLParen => &[FixIt {
       kind: FixKind::Insert,
       text: "(",
       description: "Add the opening `(`",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedRParen => &[FixIt {
       kind: FixKind::Insert,
       text: ")",
       description: "Add the closing `)`",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedLBrace => &[FixIt {
       kind: FixKind::Insert,
       text: "{",
       description: "Add the opening `{`",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedRBrace => &[FixIt {
       kind: FixKind::Insert,
       text: "}",
       description: "Add the closing `}`",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedLBracket => &[FixIt {
       kind: FixKind::Insert,
       text: "[",
       description: "Add the opening `[`",
+      machine_applicable: true,
     }],
     ErrorKind::ExpectedRBracket => &[FixIt {
       kind: FixKind::Insert,
       text: "]",
       description: "Add the closing `]`",
+      machine_applicable: true,
     }],
 
     // --- Val/imu ---
@@ -168,11 +191,13 @@ pub fn fixes_for(kind: ErrorKind) -> &'static [FixIt] {
     // span sits on the `:=` token, so a literal replace
     // with `=` makes the declaration well-formed (modulo
     // the still-required type annotation — that's the
-    // user's prose to supply, not ours to invent).
+    // user's prose to supply, not ours to invent, so the
+    // edit alone doesn't compile).
     ErrorKind::ValRequiresTypeAnnotation => &[FixIt {
       kind: FixKind::Replace,
       text: "=",
       description: "Replace `:=` with `=` (val forbids inference)",
+      machine_applicable: false,
     }],
 
     // --- Conditions ---
@@ -189,6 +214,53 @@ pub fn fixes_for(kind: ErrorKind) -> &'static [FixIt] {
   }
 }
 
+/// The insert that adds one `pattern => {},` arm per missing
+/// pattern to a `match`: right after its last arm, indented
+/// like its arms. `body` is the offset just past the match's
+/// `{`, `rbrace` that of its `}`. Returns the offset to insert
+/// at and the text — the `Detail::MissingArms` fix, shared by
+/// every renderer and the language server.
+pub fn missing_arms_fix(
+  source: &str,
+  body: usize,
+  rbrace: usize,
+  patterns: &[impl AsRef<str>],
+) -> (u32, String) {
+  let rbrace = rbrace.min(source.len());
+  let body = body.min(rbrace);
+  let indent = arm_indent(source, body, rbrace);
+  let before = source[..rbrace].trim_end();
+  let separator = match before.chars().next_back() {
+    Some(',' | '{' | '}') => "",
+    _ => ",",
+  };
+
+  let mut text = separator.to_string();
+
+  for pattern in patterns {
+    text.push_str(&format!("\n{indent}{} => {{}},", pattern.as_ref()));
+  }
+
+  (before.len() as u32, text)
+}
+
+/// The indentation of the first arm between `body` and
+/// `rbrace` — two spaces past the `}`'s own when there's none.
+fn arm_indent(source: &str, body: usize, rbrace: usize) -> String {
+  source[body..rbrace]
+    .lines()
+    .skip(1)
+    .find(|line| !line.trim().is_empty())
+    .map(|line| line[..line.len() - line.trim_start().len()].to_string())
+    .unwrap_or_else(|| {
+      let line = source[..rbrace].rfind('\n').map_or(0, |at| at + 1);
+      let indent = source[line..].len()
+        - source[line..].trim_start_matches([' ', '\t']).len();
+
+      format!("{}  ", &source[line..line + indent])
+    })
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -234,6 +306,20 @@ mod tests {
     assert!(fixes_for(ErrorKind::UnexpectedCharacter).is_empty());
   }
 
+  #[test]
+  fn missing_arms_fix_appends_indented_arms() {
+    let source = "match c {\n    Color::Red => 1\n  }";
+    let (at, text) = missing_arms_fix(
+      source,
+      source.find('{').unwrap() + 1,
+      source.rfind('}').unwrap(),
+      &["Color::Green", "Color::Blue"],
+    );
+
+    assert_eq!(&source[..at as usize], "match c {\n    Color::Red => 1");
+    assert_eq!(text, ",\n    Color::Green => {},\n    Color::Blue => {},");
+  }
+
   #[test]
   fn fix_kind_wire_strings_are_stable() {
     // Frozen — bumping `$schema` is the only legitimate
@@ -5,7 +5,9 @@
 //! stream structured diagnostics on stdout for an agent or an
 //! IDE. The two machine formats are isomorphic — same fields,
 //! same identity, one carried as NDJSON, the other as a single
-//! well-formed XML document.
+//! well-formed XML document. `Sarif` sits beside them for
+//! code-scanning dashboards: the same diagnostics, shaped by
+//! the SARIF 2.1.0 standard instead of zo's own schema.
 
 /// Schema version shared by the machine diagnostic formats
 /// (JSON and XML). Bump on any incompatible shape change — a
@@ -26,6 +28,10 @@ pub enum DiagnosticFormat {
   /// One well-formed `<diagnostics>` document on stdout, for
   /// agents that prefer XML's explicit structural boundaries.
   Xml,
+  /// One SARIF 2.1.0 log on stdout, for code-scanning
+  /// dashboards. Versioned by the standard, not by
+  /// [`DIAGNOSTIC_SCHEMA_VERSION`].
+  Sarif,
 }
 
 impl DiagnosticFormat {
@@ -35,6 +41,6 @@ impl DiagnosticFormat {
   /// (the profiler summary, stray prints).
   #[inline]
   pub fn is_machine(self) -> bool {
-    matches!(self, Self::Json | Self::Xml)
+    matches!(self, Self::Json | Self::Xml | Self::Sarif)
   }
 }
@@ -62,12 +62,12 @@
 
 use crate::aggregator::{ErrorAggregator, Phase};
 use crate::collector::Detail;
-use crate::fixes::{FixIt, FixKind, fixes_for};
+use crate::fixes::{FixIt, FixKind, fixes_for, missing_arms_fix};
 use crate::format::DIAGNOSTIC_SCHEMA_VERSION as SCHEMA_VERSION;
 use crate::locate::{extract_snippet, file_for_error, fix_span, line_col_pair};
 use crate::render::{error_message, error_note};
 
-use zo_error::Error;
+use zo_error::{Error, Severity};
 use zo_span::Span;
 
 use serde_json::{Map, Value, json};
@@ -96,6 +96,7 @@ pub fn to_json<W: Write>(
       let obj = encode(
         error,
         phase_errors.phase,
+        aggregator.severity_of(error),
         source,
         &filename,
         snippet_context,
@@ -138,6 +139,7 @@ pub fn to_stdout(
 fn encode(
   error: &Error,
   phase: Phase,
+  severity: Severity,
   source: &str,
   filename: &str,
   snippet_context: usize,
@@ -165,7 +167,7 @@ fn encode(
   obj.insert("$schema".into(), json!(SCHEMA_VERSION));
   obj.insert("id".into(), json!(kind.id()));
   obj.insert("code".into(), json!(format!("E{:04}", kind.code())));
-  obj.insert("severity".into(), json!(error.severity().as_str()));
+  obj.insert("severity".into(), json!(severity.as_str()));
   obj.insert("phase".into(), json!(phase.as_str()));
   obj.insert("message".into(), json!(error_message(kind)));
   obj.insert("fixes".into(), fixes);
@@ -229,6 +231,46 @@ fn encode(
     Some(Detail::Cycle(chain)) => {
       obj.insert("cycle".into(), json!(&**chain));
     }
+    Some(Detail::MissingArms { patterns, rbrace }) => {
+      let patterns = patterns.iter().map(|p| &**p).collect::<Vec<_>>();
+
+      obj.insert("missing_patterns".into(), json!(patterns));
+
+      // Append a machine-applicable fix: one arm per missing
+      // pattern, after the match's last arm.
+      let (at, text) = missing_arms_fix(
+        source,
+        span.end() as usize,
+        *rbrace as usize,
+        &patterns,
+      );
+
+      if let Some(Value::Array(fixes)) = obj.get_mut("fixes") {
+        fixes.push(json!({
+          "kind":        FixKind::Insert.as_str(),
+          "text":        text,
+          "description": "add the missing arms",
+          "span":        span_json(filename, at, at),
+        }));
+      }
+    }
+    Some(Detail::MissingImpl {
+      ty,
+      abstract_name,
+      required_by,
+    }) => {
+      obj.insert("found_type".into(), json!(&**ty));
+      obj.insert("missing_abstract".into(), json!(&**abstract_name));
+
+      if let Some(bound) = required_by {
+        obj.insert("required_by".into(), json!(&**bound));
+      }
+    }
+    Some(Detail::DeriveField { derive, field, ty }) => {
+      obj.insert("derive".into(), json!(&**derive));
+      obj.insert("field".into(), json!(&**field));
+      obj.insert("found_type".into(), json!(&**ty));
+    }
     Some(Detail::ArgCount {
       callee,
       expected,
@@ -406,6 +448,37 @@ mod tests {
     assert!(v.get("secondary").is_none());
   }
 
+  #[test]
+  fn lint_levels_drive_severity() {
+    use zo_error::lint::{Level, LintLevels};
+
+    let source = "fun main() { imu x: int = 1; }";
+    let unused = Error::new(ErrorKind::UnusedVariable, Span::new(4, 4));
+    let snake = Error::new(ErrorKind::NonSnakeCaseName, Span::new(17, 1));
+    let mut levels = LintLevels::new();
+
+    levels.set(ErrorKind::UnusedVariable, Level::Deny);
+    levels.set(ErrorKind::NonSnakeCaseName, Level::Allow);
+
+    let mut agg = ErrorAggregator::new();
+
+    agg.add_errors_with_levels(&[unused, snake], &levels);
+
+    let mut buf = Vec::new();
+
+    to_json(&agg, &files("foo.zo", source), 0, &mut buf).unwrap();
+
+    let out = String::from_utf8(buf).unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    let v: Value = serde_json::from_str(lines[0]).unwrap();
+
+    // The allowed lint is dropped; the denied one is an error.
+    assert_eq!(lines.len(), 1);
+    assert_eq!(v["id"], json!("unused-variable"));
+    assert_eq!(v["severity"], json!("error"));
+    assert!(agg.has_hard_errors());
+  }
+
   #[test]
   fn type_mismatch_emits_secondary_span() {
     // `1 + true`: primary caret on `true` (byte 4..8), the
@@ -451,7 +524,15 @@ mod tests {
       secondary: "int".into(),
     });
 
-    let v = encode(&err, Phase::Analyzer, source, "foo.zo", 0, Some(&detail));
+    let v = encode(
+      &err,
+      Phase::Analyzer,
+      err.severity(),
+      source,
+      "foo.zo",
+      0,
+      Some(&detail),
+    );
 
     assert_eq!(v["primary_type"], json!("bool"));
     assert_eq!(v["secondary_type"], json!("int"));
@@ -464,7 +545,15 @@ mod tests {
     let err = Error::new(ErrorKind::UndefinedVariable, Span::new(7, 4));
     let detail = Detail::Suggestion("count".into());
 
-    let v = encode(&err, Phase::Analyzer, source, "foo.zo", 0, Some(&detail));
+    let v = encode(
+      &err,
+      Phase::Analyzer,
+      err.severity(),
+      source,
+      "foo.zo",
+      0,
+      Some(&detail),
+    );
 
     assert_eq!(v["suggestion"], json!("count"));
 
@@ -487,7 +576,15 @@ mod tests {
       signature: "add(a: int, b: int) -> int".into(),
     };
 
-    let v = encode(&err, Phase::Analyzer, source, "foo.zo", 0, Some(&detail));
+    let v = encode(
+      &err,
+      Phase::Analyzer,
+      err.severity(),
+      source,
+      "foo.zo",
+      0,
+      Some(&detail),
+    );
 
     assert_eq!(v["callee"], json!("add"));
     assert_eq!(v["expected_count"], json!(2));
@@ -506,7 +603,15 @@ mod tests {
       signature: "greet(name: str) -> str".into(),
     };
 
-    let v = encode(&err, Phase::Analyzer, source, "foo.zo", 0, Some(&detail));
+    let v = encode(
+      &err,
+      Phase::Analyzer,
+      err.severity(),
+      source,
+      "foo.zo",
+      0,
+      Some(&detail),
+    );
 
     assert_eq!(v["callee"], json!("greet"));
     assert_eq!(v["primary_type"], json!("int"));
@@ -529,7 +634,15 @@ mod tests {
       expected: "int".into(),
     };
 
-    let v = encode(&err, Phase::Analyzer, source, "foo.zo", 0, Some(&detail));
+    let v = encode(
+      &err,
+      Phase::Analyzer,
+      err.severity(),
+      source,
+      "foo.zo",
+      0,
+      Some(&detail),
+    );
 
     assert_eq!(v["found_type"], json!("unit"));
     assert_eq!(v["expected_type"], json!("int"));
@@ -551,7 +664,15 @@ mod tests {
       found: "int".into(),
     };
 
-    let v = encode(&err, Phase::Analyzer, source, "foo.zo", 0, Some(&detail));
+    let v = encode(
+      &err,
+      Phase::Analyzer,
+      err.severity(),
+      source,
+      "foo.zo",
+      0,
+      Some(&detail),
+    );
 
     assert_eq!(v["found_type"], json!("int"));
     assert_eq!(v["expected_type"], json!("unit"));
@@ -1,4 +1,5 @@
 pub mod aggregator;
+pub mod apply;
 pub mod collector;
 pub mod color;
 pub mod fixes;
@@ -8,6 +9,7 @@ pub mod locate;
 pub mod rationale;
 pub mod render;
 mod reporter;
+pub mod sarif;
 pub mod xml;
 
 #[cfg(test)]
@@ -16,9 +18,9 @@ mod tests;
 pub use aggregator::{ErrorAggregator, Phase, PhaseErrors};
 pub use collector::{
   Detail, TyNames, clear_errors, collect_diagnostics, collect_errors,
-  error_count, report_error, report_error_with_detail,
+  collect_lint_scopes, error_count, report_error, report_error_with_detail,
   report_error_with_rename, report_error_with_suggestion,
-  report_error_with_types, total_count, warning_count,
+  report_error_with_types, report_lint_scope, total_count, warning_count,
 };
 pub use format::DiagnosticFormat;
 pub use render::{ErrorRenderer, RenderConfig, render_errors_to_stderr};
@@ -15,20 +15,14 @@ use crate::fixes::FixKind;
 
 use zo_error::Error;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Resolves an error's source text and display filename.
 pub(crate) fn file_for_error<'a>(
   error: &Error,
   files: &'a [(PathBuf, String)],
 ) -> (&'a str, String) {
-  let idx = error
-    .file_id()
-    .map(|id| id as usize)
-    .unwrap_or(0)
-    .min(files.len().saturating_sub(1));
-
-  let (path, source) = &files[idx];
+  let (path, source) = &files[file_index(error, files)];
 
   let filename = path
     .file_name()
@@ -38,6 +32,30 @@ pub(crate) fn file_for_error<'a>(
   (source.as_str(), filename)
 }
 
+/// Resolves an error's source text and the path as handed to
+/// the compiler, rather than the basename. Code-scanning
+/// consumers (SARIF) resolve it against the checkout root, so
+/// the directories matter.
+pub(crate) fn artifact_for_error<'a>(
+  error: &Error,
+  files: &'a [(PathBuf, String)],
+) -> (&'a str, &'a Path) {
+  let (path, source) = &files[file_index(error, files)];
+
+  (source.as_str(), path.as_path())
+}
+
+/// Index into the file table for an error. Falls back to the
+/// entry file (index 0) when the error carries no file id,
+/// and clamps an out-of-range id to the last file.
+fn file_index(error: &Error, files: &[(PathBuf, String)]) -> usize {
+  error
+    .file_id()
+    .map(|id| id as usize)
+    .unwrap_or(0)
+    .min(files.len().saturating_sub(1))
+}
+
 /// 1-indexed `(line, column)` for a byte position in
 /// `source`. Counts `\n` for line and UTF-8 chars (not
 /// bytes) on the current line for column — `é` advances
@@ -228,11 +246,7 @@ fn line_indices_for_range(
 /// * `Insert` → zero-length point at `span_start`.
 /// * `Replace` / `Delete` → the full `[span_start, span_end)`
 ///   range.
-pub(crate) fn fix_span(
-  kind: FixKind,
-  span_start: u32,
-  span_end: u32,
-) -> (u32, u32) {
+pub fn fix_span(kind: FixKind, span_start: u32, span_end: u32) -> (u32, u32) {
   match kind {
     FixKind::Insert => (span_start, span_start),
     FixKind::Replace | FixKind::Delete => (span_start, span_end),
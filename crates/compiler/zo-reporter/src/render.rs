@@ -92,6 +92,7 @@ impl ErrorRenderer {
 
         self.render_error(
           error,
+          aggregator.severity_of(error),
           source,
           &filename,
           aggregator.detail_for(error),
@@ -114,6 +115,7 @@ impl ErrorRenderer {
   fn render_error(
     &self,
     error: &Error,
+    severity: Severity,
     source: &str,
     filename: &str,
     detail: Option<&Detail>,
@@ -128,7 +130,7 @@ impl ErrorRenderer {
     // rationale. The headline message and the primary caret
     // share the color so the claim and its pointer read as
     // one unit.
-    let (kind_word, primary_color) = match error.severity() {
+    let (kind_word, primary_color) = match severity {
       Severity::Error => ("Error", Color::Red),
       Severity::Warning => ("Warning", Color::Yellow),
       Severity::Note => ("Note", Color::Blue),
@@ -294,10 +296,11 @@ fn char_offset_for_byte(source: &str, byte_pos: usize) -> usize {
 }
 
 /// Returns the main error message for a given error kind.
-/// Shared between the human renderer and `render_json` so
-/// the JSON `message` field and the human Error line stay
-/// in lockstep — single source of truth per variant.
-pub(crate) fn error_message(kind: ErrorKind) -> &'static str {
+/// Shared between the human renderer, `render_json` and the
+/// language server so the JSON `message` field, the human
+/// Error line and the editor squiggle stay in lockstep —
+/// single source of truth per variant.
+pub fn error_message(kind: ErrorKind) -> &'static str {
   match kind {
     // Tokenizer errors
     ErrorKind::UnexpectedCharacter => "Unexpected character",
@@ -414,6 +417,15 @@ pub(crate) fn error_message(kind: ErrorKind) -> &'static str {
       "Statement inside a template interpolation"
     }
     ErrorKind::PackDotAccess => "Pack item reached with `.` instead of `::`",
+    ErrorKind::UnknownLint => "Unknown lint",
+    ErrorKind::MalformedSir => "Malformed SIR instruction",
+    ErrorKind::UnknownSirType => "Unknown type in SIR",
+    ErrorKind::InvalidFormatSpec => "Invalid format spec",
+    ErrorKind::FormatSpecTypeMismatch => {
+      "Format spec does not apply to this type"
+    }
+    ErrorKind::ControlFlowInDefer => "Control flow out of a `defer` body",
+    ErrorKind::FixNotApplied => "Fix-its not applied",
     ErrorKind::UninitializedVariable => "Uninitialized variable",
     ErrorKind::InvalidSelfReference => "Invalid `self` reference",
     ErrorKind::InvalidTypeAnnotation => "Invalid type annotation",
@@ -467,13 +479,11 @@ pub(crate) fn error_message(kind: ErrorKind) -> &'static str {
     // Entry-point errors.
     ErrorKind::MissingMainFunction => "`main` function not found",
 
-    // String slice errors (compile-time only in v1).
+    // String slice errors.
     ErrorKind::StrSliceRequiresConstBounds => {
       "String slice bounds must be compile-time constants"
     }
-    ErrorKind::StrSliceRequiresStr => {
-      "String slice requires a compile-time string receiver"
-    }
+    ErrorKind::StrSliceRequiresStr => "String slice requires a `str` receiver",
     ErrorKind::StrSliceOutOfBounds => "String slice range is out of bounds",
     ErrorKind::StrSliceInvalidRange => "String slice `lo` must be <= `hi`",
 
@@ -486,7 +496,7 @@ pub(crate) fn error_message(kind: ErrorKind) -> &'static str {
       "`channel(N)` capacity must be an integer literal"
     }
     ErrorKind::CapturingClosureAsFnPointer => {
-      "a capturing closure cannot be used as a function pointer"
+      "a capturing closure cannot become a bare function pointer"
     }
 
     // Repeat-array literal errors.
@@ -500,8 +510,8 @@ pub(crate) fn error_message(kind: ErrorKind) -> &'static str {
       "`[v...n]` count must be an integer literal"
     }
 
-    // `%% serialize.` / `%% deserialize.` derive errors.
-    ErrorKind::DeriveUnsupportedField => "field type cannot be derived to JSON",
+    // `%% serialize.`, `%% eq.`, ... derive errors.
+    ErrorKind::DeriveUnsupportedField => "field type cannot be derived",
 
     ErrorKind::UnsupportedGenericLiteral => {
       "interpolated string / regex literals not yet supported in cross-module \
@@ -522,8 +532,8 @@ pub(crate) fn error_message(kind: ErrorKind) -> &'static str {
        winner"
     }
     ErrorKind::BoundNotSatisfied => {
-      "a generic call site's concrete type does not satisfy the \
-       abstract bound declared on the generic parameter"
+      "a concrete type does not satisfy the abstract bound declared \
+       on a generic parameter or required by an operator"
     }
     ErrorKind::AbstractInheritanceUnsupported => {
       "abstract inheritance (`abstract X : Y`) is not supported — \
@@ -614,6 +624,15 @@ fn error_label(kind: ErrorKind) -> &'static str {
       "this is a statement, not a template expression"
     }
     ErrorKind::PackDotAccess => "a pack is not a value — use `::` here",
+    ErrorKind::UnknownLint => "no lint by this name",
+    ErrorKind::MalformedSir => "this line does not follow the SIR grammar",
+    ErrorKind::UnknownSirType => "no type by this name",
+    ErrorKind::InvalidFormatSpec => "this interpolation has a malformed spec",
+    ErrorKind::FormatSpecTypeMismatch => {
+      "this spec can't format the interpolated value"
+    }
+    ErrorKind::ControlFlowInDefer => "this leaves a `defer` body",
+    ErrorKind::FixNotApplied => "this file was left as it was",
     ErrorKind::UninitializedVariable => "used before initialization",
     ErrorKind::InvalidSelfReference => "`self` used outside of `apply` block",
     ErrorKind::InvalidTypeAnnotation => "invalid type here",
@@ -646,7 +665,7 @@ fn error_label(kind: ErrorKind) -> &'static str {
     ErrorKind::AwaitOnNonTask => "this is not a `Task<T>`",
     ErrorKind::ChannelCapacityNotLiteral => "expected an integer literal here",
     ErrorKind::CapturingClosureAsFnPointer => {
-      "this closure captures a variable"
+      "this closure's captures would be lost here"
     }
 
     // FFI / `#link` errors.
@@ -679,9 +698,8 @@ fn error_label(kind: ErrorKind) -> &'static str {
   }
 }
 
-/// Returns a help message for the error.
 /// Label for the secondary span (e.g., the opening delimiter).
-fn secondary_label(kind: ErrorKind) -> &'static str {
+pub fn secondary_label(kind: ErrorKind) -> &'static str {
   match kind {
     ErrorKind::MismatchedDelimiter => "opened here",
     ErrorKind::UnmatchedOpeningDelimiter => {
@@ -696,6 +714,7 @@ fn secondary_label(kind: ErrorKind) -> &'static str {
   }
 }
 
+/// Returns a help message for the error.
 fn error_help(kind: ErrorKind) -> Option<&'static str> {
   match kind {
     ErrorKind::UnterminatedString => {
@@ -723,6 +742,30 @@ fn error_help(kind: ErrorKind) -> Option<&'static str> {
     ErrorKind::PackDotAccess => {
       Some("Reach a pack's items with the `::` path, e.g. `pack::item()`")
     }
+    ErrorKind::UnknownLint => Some(
+      "Only warnings have a level — name one by its kebab-case id, \
+       e.g. `%% allow(unused-variable).`",
+    ),
+    ErrorKind::MalformedSir => Some(
+      "Write instructions the way `zo build --emit=sir` prints them; \
+       templates, stylesheets and `link` have no textual form",
+    ),
+    ErrorKind::UnknownSirType => Some(
+      "Use a primitive (`i32`, `str`, ...) or declare the type with a \
+       `struct_def` / `enum_def` line",
+    ),
+    ErrorKind::InvalidFormatSpec => Some(
+      "Write the spec as `[[fill]align][+][#][0][width][.precision][kind]`, \
+       e.g. `{price:.2}`, `{id:06}`, `{addr:#x}` or `{name:>10}`",
+    ),
+    ErrorKind::FormatSpecTypeMismatch => Some(
+      "`x`, `X`, `b` and `o` take integers, `.precision` takes floats, \
+       `+` and `0` take numbers; fill, align and width take any value",
+    ),
+    ErrorKind::ControlFlowInDefer => Some(
+      "A deferred body runs while its scope is already exiting — move the \
+       `return`, `break`, `continue` or `?` out of the `defer`",
+    ),
     ErrorKind::EventOnComponent => Some(
       "Declare a function parameter on the component (e.g. `on_click: \
        Fn() -> unit`), wire it inside the body with \
@@ -796,7 +839,7 @@ fn error_help(kind: ErrorKind) -> Option<&'static str> {
       "Write the buffer size as a literal, e.g. `channel(4)`. Variable references are post-MVP",
     ),
     ErrorKind::CapturingClosureAsFnPointer => Some(
-      "Pass a top-level function or a closure that captures nothing — a bare function pointer carries no captured environment",
+      "Pass the closure straight to a `fun`'s `Fn(...)` parameter or to an `ffi` callback whose user-data slot is declared with `%% user_data(env).`, or use a top-level function or a closure that captures nothing",
     ),
 
     ErrorKind::MissingMainFunction => Some(
@@ -32,6 +32,27 @@ impl Reporter {
   pub fn details(&self) -> &[(Error, Detail)] {
     &self.details
   }
+
+  /// Rewrites the file id of every error (and its detail)
+  /// collected at or after the `(errors, details)` marks — used
+  /// when several analyses share one file table. An untagged
+  /// error belongs to its analysis' entry file, `remap(0)`.
+  pub fn remap_files(
+    &mut self,
+    (errors, details): (usize, usize),
+    remap: impl Fn(u16) -> u16,
+  ) {
+    let retag =
+      |error: &Error| error.tagged(remap(error.file_id().unwrap_or(0)));
+
+    for error in &mut self.errors[errors..] {
+      *error = retag(error);
+    }
+
+    for (error, _) in &mut self.details[details..] {
+      *error = retag(error);
+    }
+  }
 }
 
 impl Default for Reporter {
@@ -0,0 +1,659 @@
+//! SARIF 2.1.0 renderer for code-scanning consumers.
+//!
+//! Emits one SARIF log on stdout — a single JSON document
+//! holding one `run`. Where the JSON and XML renderers speak
+//! zo's own schema, SARIF is the OASIS interchange format
+//! that code-scanning dashboards ingest, so the shape here is
+//! dictated by the standard rather than by us.
+//!
+//! ## Mapping
+//!
+//! | zo                       | SARIF                                 |
+//! |--------------------------|---------------------------------------|
+//! | id registry              | `tool.driver.rules[]`                 |
+//! | `kind.id()`              | `result.ruleId` (+ `ruleIndex`)       |
+//! | `E{code}`                | `rule.properties.code`                |
+//! | `Severity`               | `result.level` / `defaultConfiguration.level` |
+//! | primary span             | `locations[0].physicalLocation`       |
+//! | secondary span           | `relatedLocations[0]`                 |
+//! | `FixIt` / suggestion     | `fixes[].artifactChanges[]`           |
+//! | phase                    | `result.properties.phase`             |
+//!
+//! The `rules` array is generated from the whole id registry
+//! (`zo_error::id_registry::kinds`), not only the kinds that
+//! fired, so `ruleIndex` is the kind's stable position in the
+//! registry and a dashboard sees the same rule table on every
+//! run.
+//!
+//! Regions carry both the 1-indexed line/column geometry from
+//! [`crate::locate`] and the raw byte offsets. Columns count
+//! Unicode scalars, declared once on the run as
+//! `columnKind: "unicodeCodePoints"`. A fix's deleted region
+//! is expressed in bytes only — the edit is programmatic, the
+//! same split the JSON renderer makes between diagnostic and
+//! fix-it spans.
+//!
+//! Artifact locations are URIs, not raw paths. A relative path
+//! is resolved against the `%SRCROOT%` base — declared in the
+//! run's `originalUriBaseIds` as the working directory — and an
+//! absolute one becomes a `file:///` URL. Both are
+//! percent-encoded, so `C:\x\foo.zo` doesn't read as scheme
+//! `C:` and a space or `%` survives the round trip.
+
+use crate::aggregator::{ErrorAggregator, Phase};
+use crate::collector::Detail;
+use crate::fixes::{FixIt, fixes_for, missing_arms_fix};
+use crate::locate::{artifact_for_error, fix_span, line_col_pair};
+use crate::render::{error_message, error_note};
+
+use zo_error::{Error, ErrorKind, Severity, id_registry};
+use zo_span::Span;
+
+use serde_json::{Map, Value, json};
+
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// SARIF specification version this renderer targets.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// JSON schema URI advertised in the log's `$schema` field.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The `uriBaseId` relative artifact paths resolve against.
+pub const SRCROOT: &str = "%SRCROOT%";
+
+/// Renders every diagnostic in `aggregator` as one SARIF log
+/// on the given writer. Like XML, SARIF is a single document,
+/// so it is built whole and written once.
+///
+/// Unlike the other renderers an empty aggregator still
+/// produces a complete log with an empty `results` array — a
+/// clean scan is a result in its own right for a dashboard.
+pub fn to_sarif<W: Write>(
+  aggregator: &ErrorAggregator,
+  files: &[(PathBuf, String)],
+  out: &mut W,
+) -> io::Result<()> {
+  let log = encode_log(aggregator, files);
+
+  serde_json::to_writer_pretty(&mut *out, &log)?;
+  writeln!(out)?;
+  out.flush()
+}
+
+/// Convenience: emit the SARIF log to stdout. Mirrors
+/// `json::to_stdout` / `xml::to_stdout` so the driver
+/// dispatches on `--format` with one uniform branch.
+pub fn to_stdout(
+  aggregator: &ErrorAggregator,
+  files: &[(PathBuf, String)],
+) -> io::Result<()> {
+  let stdout = io::stdout();
+  let mut handle = stdout.lock();
+
+  to_sarif(aggregator, files, &mut handle)
+}
+
+/// Build the top-level `sarifLog` object: schema, version and
+/// the single run carrying the tool descriptor and results.
+fn encode_log(
+  aggregator: &ErrorAggregator,
+  files: &[(PathBuf, String)],
+) -> Value {
+  let mut results = Vec::new();
+
+  for phase_errors in aggregator.errors() {
+    for error in &phase_errors.errors {
+      results.push(encode_result(
+        error,
+        phase_errors.phase,
+        aggregator.severity_of(error),
+        files,
+        aggregator.detail_for(error),
+      ));
+    }
+  }
+
+  let mut base_ids = Map::with_capacity(1);
+
+  // Without a readable working directory the base stays
+  // undefined, which SARIF leaves to the consumer to supply.
+  if let Ok(cwd) = std::env::current_dir() {
+    let mut root = file_url(&cwd);
+
+    if !root.ends_with('/') {
+      root.push('/');
+    }
+
+    base_ids.insert(SRCROOT.into(), json!({ "uri": root }));
+  }
+
+  json!({
+    "$schema": SARIF_SCHEMA,
+    "version": SARIF_VERSION,
+    "runs": [{
+      "originalUriBaseIds": Value::Object(base_ids),
+      "tool": {
+        "driver": {
+          "name":           "zo",
+          "version":        env!("CARGO_PKG_VERSION"),
+          "informationUri": "https://github.com/invisageable/zo",
+          "rules":          encode_rules(),
+        }
+      },
+      "columnKind": "unicodeCodePoints",
+      "results":    results,
+    }],
+  })
+}
+
+/// One `reportingDescriptor` per registered `ErrorKind`, in
+/// registry order — the index of a rule in this array is the
+/// `ruleIndex` every result points back at.
+fn encode_rules() -> Vec<Value> {
+  id_registry::kinds().map(encode_rule).collect()
+}
+
+/// The `reportingDescriptor` for one kind. The kebab-case id
+/// doubles as the rule `id` and `name`; the display code
+/// travels in `properties` since SARIF has no slot for it.
+fn encode_rule(kind: ErrorKind) -> Value {
+  let mut rule = Map::with_capacity(6);
+
+  rule.insert("id".into(), json!(kind.id()));
+  rule.insert("name".into(), json!(kind.id()));
+  rule.insert(
+    "shortDescription".into(),
+    json!({ "text": error_message(kind) }),
+  );
+
+  if let Some(note) = error_note(kind) {
+    rule.insert("fullDescription".into(), json!({ "text": note }));
+  }
+
+  rule.insert(
+    "defaultConfiguration".into(),
+    json!({ "level": level(zo_error::severity(kind)) }),
+  );
+  rule.insert(
+    "properties".into(),
+    json!({ "code": format!("E{:04}", kind.code()) }),
+  );
+
+  Value::Object(rule)
+}
+
+/// Build the `result` object for one diagnostic. Field order
+/// is fixed (identity → level → message → locations →
+/// related → fixes → properties) so the byte output is
+/// deterministic, as for the other machine formats.
+fn encode_result(
+  error: &Error,
+  phase: Phase,
+  severity: Severity,
+  files: &[(PathBuf, String)],
+  detail: Option<&Detail>,
+) -> Value {
+  let kind = error.kind();
+  let span = error.span();
+  let (source, path) = artifact_for_error(error, files);
+  let artifact = artifact_location(path);
+
+  let mut result = Map::with_capacity(8);
+
+  result.insert("ruleId".into(), json!(kind.id()));
+  result.insert("ruleIndex".into(), json!(kind as u16));
+  result.insert("level".into(), json!(level(severity)));
+  result.insert(
+    "message".into(),
+    json!({ "text": result_message(kind, detail) }),
+  );
+  result.insert(
+    "locations".into(),
+    json!([{ "physicalLocation": physical_location(&artifact, span, source) }]),
+  );
+
+  // The conflicting value in a type mismatch (the green
+  // secondary in the human render).
+  if let Some(secondary) = error.secondary_span() {
+    result.insert(
+      "relatedLocations".into(),
+      json!([{
+        "id":               0,
+        "physicalLocation": physical_location(&artifact, secondary, source),
+      }]),
+    );
+  }
+
+  let fixes = encode_fixes(fixes_for(kind), &artifact, source, span, detail);
+
+  if !fixes.is_empty() {
+    result.insert("fixes".into(), Value::Array(fixes));
+  }
+
+  result.insert(
+    "properties".into(),
+    json!({
+      "code":  format!("E{:04}", kind.code()),
+      "phase": phase.as_str(),
+    }),
+  );
+
+  Value::Object(result)
+}
+
+/// The result's message text. The per-kind message is the
+/// base; a name suggestion, the patterns a `match` leaves
+/// uncovered, the `apply` a bound misses or the field a
+/// derive can't handle are appended in prose since SARIF
+/// results carry no structured detail slot the dashboards
+/// render.
+fn result_message(kind: ErrorKind, detail: Option<&Detail>) -> String {
+  let message = error_message(kind);
+
+  match detail {
+    Some(Detail::Suggestion(name)) => {
+      format!("{message} (did you mean `{name}`?)")
+    }
+    Some(Detail::Rename(name)) => format!("{message} (rename to `{name}`)"),
+    Some(
+      detail @ (Detail::MissingArms { .. }
+      | Detail::MissingImpl { .. }
+      | Detail::DeriveField { .. }),
+    ) => match detail.primary_label() {
+      Some(label) => format!("{message} ({label})"),
+      None => message.to_string(),
+    },
+    _ => message.to_string(),
+  }
+}
+
+/// The `artifactLocation` for `path`: a `file:///` URL when it
+/// is absolute, else a relative reference on [`SRCROOT`].
+fn artifact_location(path: &Path) -> Value {
+  let path = path.to_string_lossy().replace('\\', "/");
+
+  if is_absolute(&path) {
+    return json!({ "uri": file_url(Path::new(&path)) });
+  }
+
+  let relative = path.trim_start_matches("./");
+
+  json!({
+    "uri":       percent_encode(relative, false),
+    "uriBaseId": SRCROOT,
+  })
+}
+
+/// `true` for a `/`-rooted path or one starting with a drive
+/// letter (`C:/`). Decided on the spelling rather than the
+/// host, so a log renders the same on every platform.
+fn is_absolute(path: &str) -> bool {
+  let bytes = path.as_bytes();
+
+  path.starts_with('/')
+    || (bytes.len() >= 3
+      && bytes[0].is_ascii_alphabetic()
+      && bytes[1] == b':'
+      && bytes[2] == b'/')
+}
+
+/// A `file:///` URL for an absolute path. A drive letter gets
+/// the extra leading `/` (`file:///C:/x`).
+fn file_url(path: &Path) -> String {
+  let path = path.to_string_lossy().replace('\\', "/");
+  let rooted = if path.starts_with('/') { "" } else { "/" };
+
+  format!("file://{rooted}{}", percent_encode(&path, true))
+}
+
+/// Percent-encodes `path` byte by byte, keeping the unreserved
+/// set and `/`. A `:` is kept only when `colon` is set — in a
+/// relative reference it would make the first segment a scheme.
+fn percent_encode(path: &str, colon: bool) -> String {
+  let mut out = String::with_capacity(path.len());
+
+  for byte in path.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => out.push(byte as char),
+      b'-' | b'.' | b'_' | b'~' | b'/' => out.push(byte as char),
+      b':' if colon => out.push(':'),
+      _ => out.push_str(&format!("%{byte:02X}")),
+    }
+  }
+
+  out
+}
+
+/// A `physicalLocation`: the artifact location plus a region
+/// with 1-indexed line/column bounds and raw byte offsets.
+fn physical_location(artifact: &Value, span: Span, source: &str) -> Value {
+  let byte_start = (span.start as usize).min(source.len());
+  let byte_end = (span.end() as usize).min(source.len());
+  let ((start_line, start_column), (end_line, end_column)) =
+    line_col_pair(source, byte_start, byte_end);
+
+  json!({
+    "artifactLocation": artifact,
+    "region": {
+      "startLine":   start_line,
+      "startColumn": start_column,
+      "endLine":     end_line,
+      "endColumn":   end_column,
+      "byteOffset":  span.start,
+      "byteLength":  span.len,
+    },
+  })
+}
+
+/// Encode the fix-its as SARIF `fix` objects, one
+/// `artifactChange` with one `replacement` each. Insert,
+/// replace and delete all collapse onto SARIF's single
+/// replacement shape: a deleted region (zero-length for an
+/// insert) plus inserted content (absent for a delete). A
+/// `Suggestion` / `Rename` detail appends one extra replace
+/// fix and a `MissingArms` detail one insert, matching the
+/// JSON and XML encoders.
+fn encode_fixes(
+  fixes: &[FixIt],
+  artifact: &Value,
+  source: &str,
+  span: Span,
+  detail: Option<&Detail>,
+) -> Vec<Value> {
+  let mut entries = Vec::with_capacity(fixes.len() + 1);
+
+  for fix in fixes {
+    let (start, end) = fix_span(fix.kind, span.start, span.end());
+
+    entries.push(fix_json(artifact, start, end, fix.text, fix.description));
+  }
+
+  match detail {
+    Some(Detail::Suggestion(name)) => entries.push(fix_json(
+      artifact,
+      span.start,
+      span.end(),
+      name,
+      &format!("replace with `{name}`"),
+    )),
+    Some(Detail::Rename(name)) => entries.push(fix_json(
+      artifact,
+      span.start,
+      span.end(),
+      name,
+      &format!("rename to `{name}`"),
+    )),
+    Some(Detail::MissingArms { patterns, rbrace }) => {
+      let (at, text) = missing_arms_fix(
+        source,
+        span.end() as usize,
+        *rbrace as usize,
+        patterns,
+      );
+
+      entries.push(fix_json(artifact, at, at, &text, "add the missing arms"));
+    }
+    _ => {}
+  }
+
+  entries
+}
+
+/// One SARIF `fix`: a description and a single replacement of
+/// the byte range `[start, end)` with `text`.
+fn fix_json(
+  artifact: &Value,
+  start: u32,
+  end: u32,
+  text: &str,
+  description: &str,
+) -> Value {
+  let mut replacement = Map::with_capacity(2);
+
+  replacement.insert(
+    "deletedRegion".into(),
+    json!({
+      "byteOffset": start,
+      "byteLength": end.saturating_sub(start),
+    }),
+  );
+
+  if !text.is_empty() {
+    replacement.insert("insertedContent".into(), json!({ "text": text }));
+  }
+
+  json!({
+    "description": { "text": description },
+    "artifactChanges": [{
+      "artifactLocation": artifact,
+      "replacements":     [Value::Object(replacement)],
+    }],
+  })
+}
+
+/// SARIF `level` for a zo severity. SARIF's closed set is
+/// `none | note | warning | error`; zo never emits `none`.
+#[inline]
+const fn level(severity: Severity) -> &'static str {
+  match severity {
+    Severity::Error => "error",
+    Severity::Warning => "warning",
+    Severity::Note => "note",
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::collector::TyNames;
+
+  fn files(name: &str, source: &str) -> Vec<(PathBuf, String)> {
+    vec![(PathBuf::from(name), source.to_string())]
+  }
+
+  fn render(agg: &ErrorAggregator, name: &str, source: &str) -> Value {
+    let mut buf = Vec::new();
+
+    to_sarif(agg, &files(name, source), &mut buf).unwrap();
+    serde_json::from_slice(&buf).unwrap()
+  }
+
+  fn aggregate(error: Error) -> ErrorAggregator {
+    let mut agg = ErrorAggregator::new();
+
+    agg.add_errors(&[error]);
+    agg
+  }
+
+  #[test]
+  fn empty_aggregator_renders_complete_log() {
+    let log = render(&ErrorAggregator::new(), "foo.zo", "");
+
+    assert_eq!(log["version"], json!("2.1.0"));
+    assert_eq!(log["$schema"], json!(SARIF_SCHEMA));
+    assert_eq!(log["runs"].as_array().unwrap().len(), 1);
+    assert_eq!(log["runs"][0]["tool"]["driver"]["name"], json!("zo"));
+    assert_eq!(log["runs"][0]["results"], json!([]));
+  }
+
+  #[test]
+  fn rules_cover_the_whole_registry_in_order() {
+    let log = render(&ErrorAggregator::new(), "foo.zo", "");
+    let rules = log["runs"][0]["tool"]["driver"]["rules"]
+      .as_array()
+      .unwrap();
+
+    assert_eq!(rules.len(), ErrorKind::LAST as usize + 1);
+
+    for kind in id_registry::kinds() {
+      let rule = &rules[kind as usize];
+
+      assert_eq!(rule["id"], json!(kind.id()));
+      assert_eq!(
+        rule["properties"]["code"],
+        json!(format!("E{:04}", kind.code()))
+      );
+    }
+
+    let unused = &rules[ErrorKind::UnusedVariable as usize];
+
+    assert_eq!(unused["defaultConfiguration"]["level"], json!("warning"));
+  }
+
+  #[test]
+  fn result_points_at_its_rule_and_location() {
+    let source = "fun a() {}\nimu name = 1";
+    let err = Error::new(ErrorKind::ImmutableVariable, Span::new(15, 4));
+    let log = render(&aggregate(err), "src/foo.zo", source);
+    let result = &log["runs"][0]["results"][0];
+
+    assert_eq!(result["ruleId"], json!("immutable-variable"));
+    assert_eq!(
+      result["ruleIndex"],
+      json!(ErrorKind::ImmutableVariable as u16)
+    );
+    assert_eq!(result["level"], json!("error"));
+    assert_eq!(result["properties"]["phase"], json!("analyzer"));
+
+    let location = &result["locations"][0]["physicalLocation"];
+
+    // The artifact keeps its directories, unlike the basename
+    // the JSON renderer shows.
+    assert_eq!(location["artifactLocation"]["uri"], json!("src/foo.zo"));
+    assert_eq!(location["artifactLocation"]["uriBaseId"], json!(SRCROOT));
+    assert_eq!(location["region"]["startLine"], json!(2));
+    assert_eq!(location["region"]["startColumn"], json!(5));
+    assert_eq!(location["region"]["endColumn"], json!(9));
+    assert_eq!(location["region"]["byteOffset"], json!(15));
+    assert_eq!(location["region"]["byteLength"], json!(4));
+  }
+
+  #[test]
+  fn relative_paths_resolve_against_srcroot() {
+    let err = Error::new(ErrorKind::UnusedVariable, Span::new(0, 1));
+    let log = render(&aggregate(err), "./my src/50%.zo", "x");
+    let run = &log["runs"][0];
+    let artifact = &run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"];
+    let root = run["originalUriBaseIds"][SRCROOT]["uri"].as_str().unwrap();
+
+    assert_eq!(artifact["uri"], json!("my%20src/50%25.zo"));
+    assert_eq!(artifact["uriBaseId"], json!(SRCROOT));
+    assert!(root.starts_with("file:///") && root.ends_with('/'));
+  }
+
+  #[test]
+  fn absolute_paths_become_file_urls() {
+    let err = Error::new(ErrorKind::UnusedVariable, Span::new(0, 1));
+    let log = render(&aggregate(err), "/home/me/my src/foo.zo", "x");
+    let artifact = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]
+      ["artifactLocation"];
+
+    assert_eq!(artifact["uri"], json!("file:///home/me/my%20src/foo.zo"));
+    assert!(artifact.get("uriBaseId").is_none());
+
+    let log = render(&aggregate(err), "C:\\x\\foo.zo", "x");
+    let artifact = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]
+      ["artifactLocation"];
+
+    assert_eq!(artifact["uri"], json!("file:///C:/x/foo.zo"));
+  }
+
+  #[test]
+  fn warnings_map_to_warning_level() {
+    let err = Error::new(ErrorKind::UnusedVariable, Span::new(0, 1));
+    let log = render(&aggregate(err), "foo.zo", "x");
+
+    assert_eq!(log["runs"][0]["results"][0]["level"], json!("warning"));
+  }
+
+  #[test]
+  fn insert_fix_becomes_zero_length_replacement() {
+    let source = "imu name = 1";
+    let err = Error::new(ErrorKind::ImmutableVariable, Span::new(4, 4));
+    let log = render(&aggregate(err), "foo.zo", source);
+    let fix = &log["runs"][0]["results"][0]["fixes"][0];
+    let change = &fix["artifactChanges"][0];
+    let replacement = &change["replacements"][0];
+
+    assert_eq!(change["artifactLocation"]["uri"], json!("foo.zo"));
+    assert_eq!(replacement["deletedRegion"]["byteOffset"], json!(4));
+    assert_eq!(replacement["deletedRegion"]["byteLength"], json!(0));
+    assert_eq!(replacement["insertedContent"]["text"], json!("mut "));
+  }
+
+  #[test]
+  fn suggestion_becomes_replace_fix() {
+    let source = "showln(cont)";
+    let err = Error::new(ErrorKind::UndefinedVariable, Span::new(7, 4));
+    let mut agg = ErrorAggregator::new();
+
+    agg.add_errors(&[err]);
+    agg.add_details(&[(err, Detail::Suggestion("count".into()))]);
+
+    let log = render(&agg, "foo.zo", source);
+    let result = &log["runs"][0]["results"][0];
+    let replacement =
+      &result["fixes"][0]["artifactChanges"][0]["replacements"][0];
+
+    assert!(
+      result["message"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("`count`")
+    );
+    assert_eq!(replacement["deletedRegion"]["byteOffset"], json!(7));
+    assert_eq!(replacement["deletedRegion"]["byteLength"], json!(4));
+    assert_eq!(replacement["insertedContent"]["text"], json!("count"));
+  }
+
+  #[test]
+  fn secondary_span_becomes_related_location() {
+    let source = "1 + true";
+    let err = Error::with_secondary(
+      ErrorKind::TypeMismatch,
+      Span::new(4, 4),
+      Span::new(0, 1),
+    );
+    let mut agg = ErrorAggregator::new();
+
+    agg.add_errors(&[err]);
+    agg.add_details(&[(
+      err,
+      Detail::Types(TyNames {
+        primary: "bool".into(),
+        secondary: "int".into(),
+      }),
+    )]);
+
+    let log = render(&agg, "foo.zo", source);
+    let result = &log["runs"][0]["results"][0];
+    let related = &result["relatedLocations"][0]["physicalLocation"];
+
+    assert_eq!(related["region"]["byteOffset"], json!(0));
+    assert_eq!(related["region"]["startColumn"], json!(1));
+    // No fix-it exists for a type mismatch — the key is omitted.
+    assert!(result.get("fixes").is_none());
+  }
+
+  #[test]
+  fn same_input_produces_byte_identical_output() {
+    let source = "fun helper() {}\n";
+    let err_a = Error::new(ErrorKind::MissingMainFunction, Span::new(16, 0));
+    let err_b = Error::new(ErrorKind::ImmutableVariable, Span::new(4, 6));
+    let mut agg = ErrorAggregator::new();
+
+    agg.add_errors(&[err_a, err_b]);
+
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+
+    to_sarif(&agg, &files("foo.zo", source), &mut first).unwrap();
+    to_sarif(&agg, &files("foo.zo", source), &mut second).unwrap();
+
+    assert_eq!(first, second, "SARIF renderer must be byte-deterministic");
+  }
+}
@@ -55,13 +55,13 @@
 
 use crate::aggregator::{ErrorAggregator, Phase};
 use crate::collector::Detail;
-use crate::fixes::{FixIt, fixes_for};
+use crate::fixes::{FixIt, FixKind, fixes_for, missing_arms_fix};
 use crate::format::DIAGNOSTIC_SCHEMA_VERSION;
 use crate::locate::{extract_snippet, file_for_error, fix_span, line_col_pair};
 use crate::render::{error_message, error_note};
 
 use zo_buffer::Buffer;
-use zo_error::Error;
+use zo_error::{Error, Severity};
 use zo_span::Span;
 
 use std::io;
@@ -93,14 +93,12 @@ pub fn to_xml<W: Write>(
 
   for phase_errors in aggregator.errors() {
     for error in &phase_errors.errors {
-      let (source, filename) = file_for_error(error, files);
-
       encode_diagnostic(
         &mut buf,
         error,
         phase_errors.phase,
-        source,
-        &filename,
+        aggregator.severity_of(error),
+        files,
         snippet_context,
         aggregator.detail_for(error),
       );
@@ -136,11 +134,13 @@ fn encode_diagnostic(
   buf: &mut Buffer,
   error: &Error,
   phase: Phase,
-  source: &str,
-  filename: &str,
+  severity: Severity,
+  files: &[(PathBuf, String)],
   snippet_context: usize,
   detail: Option<&Detail>,
 ) {
+  let (source, filename) = file_for_error(error, files);
+  let filename = filename.as_str();
   let kind = error.kind();
   let span = error.span();
   let byte_start = (span.start as usize).min(source.len());
@@ -160,13 +160,13 @@ fn encode_diagnostic(
   buf.str("<diagnostic");
   str_attr(buf, "id", kind.id());
   str_attr(buf, "code", &format!("E{:04}", kind.code()));
-  str_attr(buf, "severity", error.severity().as_str());
+  str_attr(buf, "severity", severity.as_str());
   str_attr(buf, "phase", phase.as_str());
   buf.str(">");
   buf.newline();
 
   text_element(buf, 2, "message", error_message(kind));
-  encode_fixes(buf, fixes_for(kind), filename, span, detail);
+  encode_fixes(buf, fixes_for(kind), filename, source, span, detail);
   encode_notes(buf, note);
   encode_snippet(buf, &snippet);
   span_element(buf, 2, "span", filename, span, source);
@@ -188,22 +188,38 @@ fn encode_diagnostic(
 /// Emit the `<fixes>` block. Always present — self-closing
 /// when empty. A `Detail::Suggestion` appends one extra
 /// replace fix (replace the undefined name with the closest
-/// in-scope name), matching the JSON encoder.
+/// in-scope name) and a `Detail::MissingArms` one insert (the
+/// missing `match` arms), matching the JSON encoder.
 fn encode_fixes(
   buf: &mut Buffer,
   fixes: &[FixIt],
   filename: &str,
+  source: &str,
   span: Span,
   detail: Option<&Detail>,
 ) {
-  let suggestion = match detail {
-    Some(Detail::Suggestion(name)) | Some(Detail::Rename(name)) => {
-      Some(&**name)
+  // The detail's fix: its edit range, text and description.
+  let extra = match detail {
+    Some(Detail::Suggestion(name)) | Some(Detail::Rename(name)) => Some((
+      span.start,
+      span.end(),
+      name.to_string(),
+      format!("replace with `{name}`"),
+    )),
+    Some(Detail::MissingArms { patterns, rbrace }) => {
+      let (at, text) = missing_arms_fix(
+        source,
+        span.end() as usize,
+        *rbrace as usize,
+        patterns,
+      );
+
+      Some((at, at, text, "add the missing arms".to_owned()))
     }
     _ => None,
   };
 
-  if fixes.is_empty() && suggestion.is_none() {
+  if fixes.is_empty() && extra.is_none() {
     indent(buf, 2);
     buf.str("<fixes/>");
     buf.newline();
@@ -227,16 +243,21 @@ fn encode_fixes(
     );
   }
 
-  // The suggestion's replace fix spans the offending name.
-  if let Some(name) = suggestion {
+  if let Some((start, end, text, description)) = extra {
+    let kind = if start == end {
+      FixKind::Insert
+    } else {
+      FixKind::Replace
+    };
+
     fix_element(
       buf,
-      "replace",
+      kind.as_str(),
       filename,
-      span.start,
-      span.end(),
-      name,
-      &format!("replace with `{name}`"),
+      start,
+      end,
+      &text,
+      &description,
     );
   }
 
@@ -362,6 +383,28 @@ fn encode_detail(buf: &mut Buffer, detail: Option<&Detail>) {
     Some(Detail::Cycle(chain)) => {
       text_element(buf, 2, "cycle", chain);
     }
+    Some(Detail::MissingArms { patterns, .. }) => {
+      for pattern in patterns {
+        text_element(buf, 2, "missing_pattern", pattern);
+      }
+    }
+    Some(Detail::MissingImpl {
+      ty,
+      abstract_name,
+      required_by,
+    }) => {
+      text_element(buf, 2, "found_type", ty);
+      text_element(buf, 2, "missing_abstract", abstract_name);
+
+      if let Some(bound) = required_by {
+        text_element(buf, 2, "required_by", bound);
+      }
+    }
+    Some(Detail::DeriveField { derive, field, ty }) => {
+      text_element(buf, 2, "derive", derive);
+      text_element(buf, 2, "field", field);
+      text_element(buf, 2, "found_type", ty);
+    }
     Some(Detail::ArgCount {
       callee,
       expected,
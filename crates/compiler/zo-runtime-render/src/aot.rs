@@ -95,11 +95,21 @@ fn encode_length_prefixed(bytes: &[u8]) -> Vec<u8> {
 
 /// Read the length-prefix at `ptr` and return the borrowed
 /// bytes (without the trailing nul). Caller guarantees
-/// `ptr` points to a `[len: u64][bytes][null]` buffer.
+/// `ptr` points to a `[len: u64][bytes][null]` buffer, or
+/// to a slice view — `[len | 1 << 63][payload ptr]`.
 unsafe fn read_length_prefixed(ptr: *const u8) -> &'static [u8] {
-  let len = unsafe { (ptr as *const u64).read_unaligned() } as usize;
+  const VIEW_BIT: u64 = 1 << 63;
 
-  unsafe { slice::from_raw_parts(ptr.add(8), len) }
+  let word = unsafe { (ptr as *const u64).read_unaligned() };
+  let len = (word & !VIEW_BIT) as usize;
+
+  let payload = if word & VIEW_BIT != 0 {
+    unsafe { (ptr.add(8) as *const *const u8).read_unaligned() }
+  } else {
+    unsafe { ptr.add(8) }
+  };
+
+  unsafe { slice::from_raw_parts(payload, len) }
 }
 
 /// One reactive text binding: replace `commands[cmd_idx]`
@@ -526,6 +526,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -621,6 +622,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -792,6 +794,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -926,6 +929,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -1028,6 +1032,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -1193,6 +1198,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -1247,6 +1253,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -1270,6 +1277,7 @@ mod tests {
       pubness: Pubness::No,
       self_kind: SelfKind::None,
       link_name: None,
+      env_slot: None,
       owning_pack: None,
       span: Span::ZERO,
       is_test: false,
@@ -58,7 +58,7 @@ extern "C-unwind" fn noop_entry() {}
 fn bench_zotask_construct_drop(c: &mut Criterion) {
   c.bench_function("zotask_construct_drop", |b| {
     b.iter(|| {
-      let task = ZoTask::new_green_standalone(noop_entry);
+      let task = ZoTask::new_green_standalone(noop_entry, 0);
 
       black_box(task);
     });
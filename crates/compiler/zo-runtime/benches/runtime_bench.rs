@@ -40,7 +40,7 @@ fn run_fan_out(n_tasks: usize, n_workers: usize) {
   let pool = Pool::new(n_workers);
 
   for _ in 0..n_tasks {
-    pool.spawn(fan_out_increment);
+    pool.spawn(fan_out_increment, 0);
   }
 
   pool.wait_idle();
@@ -0,0 +1,90 @@
+//! Capturing-closure environments.
+//!
+//! A capturing closure that crosses a `Fn(...)` parameter
+//! lowers to a `(fn_ptr, env_ptr)` pair. `env_ptr` addresses a
+//! `malloc`'d block laid out as an array of captures, preceded
+//! by one hidden word: the closure's `__env_drop` glue (or `0`).
+//! The glue drops the captures that own a destructor; this
+//! module frees the block around it.
+
+/// Drop glue of one closure — runs the destructors of its
+/// owned captures, reading them from `env`.
+type DropGlue = unsafe extern "C-unwind" fn(u64);
+
+/// Release a closure environment: run its drop glue, then free
+/// the block. Called after the env's last use — by compiled
+/// code once the callee it was built for returns, and by the
+/// task shim once a pool task that carried it has run. A null
+/// `env` (a plain function's pair) is a no-op.
+///
+/// # Safety
+///
+/// `env` must be null or an environment built by a
+/// `ClosureEnv`, not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_closure_env_free(env: u64) {
+  if env == 0 {
+    return;
+  }
+
+  let block = (env as *mut u64).wrapping_sub(1);
+  let glue = unsafe { *block };
+
+  if glue != 0 {
+    // SAFETY: a non-zero header word is the address of the
+    // closure's drop glue, stored there by `ClosureEnv`.
+    let glue =
+      unsafe { std::mem::transmute::<*const (), DropGlue>(glue as *const ()) };
+
+    unsafe { glue(env) };
+  }
+
+  unsafe { libc::free(block.cast()) };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::sync::atomic::{AtomicU64, Ordering};
+
+  static DROPPED: AtomicU64 = AtomicU64::new(0);
+
+  extern "C-unwind" fn record_drop(env: u64) {
+    // The one capture sits past the array's `len` word.
+    let capture = unsafe { *(env as *const u64).add(1) };
+
+    DROPPED.store(capture, Ordering::SeqCst);
+  }
+
+  /// Builds `[glue][len = 1][capture]` and returns the address
+  /// of `len`, the shape a `ClosureEnv` hands out.
+  fn env_with(glue: u64, capture: u64) -> u64 {
+    unsafe {
+      let block = libc::malloc(3 * 8) as *mut u64;
+
+      *block = glue;
+      *block.add(1) = 1;
+      *block.add(2) = capture;
+
+      block.add(1) as u64
+    }
+  }
+
+  #[test]
+  fn free_runs_the_drop_glue_over_the_captures() {
+    let env = env_with(record_drop as *const () as u64, 42);
+
+    unsafe { zo_closure_env_free(env) };
+
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 42);
+  }
+
+  #[test]
+  fn free_without_glue_or_env_is_a_plain_release() {
+    unsafe {
+      zo_closure_env_free(env_with(0, 7));
+      zo_closure_env_free(0);
+    }
+  }
+}
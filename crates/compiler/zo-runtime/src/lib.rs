@@ -9,6 +9,7 @@ pub mod assert;
 pub mod base64;
 pub mod bufio;
 pub mod channel;
+pub mod closure;
 pub mod ctxsw;
 pub mod env;
 pub mod file;
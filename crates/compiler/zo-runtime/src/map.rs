@@ -6,7 +6,7 @@
 //! on insert when the matching key isn't present
 //! earlier in the probe chain.
 //!
-//! Three key kinds (`KeyKind`):
+//! Four key kinds (`KeyKind`):
 //!
 //! - `Prim` — raw bytes at `key_ptr` are the key.
 //!   Covers `int` (any width), `char`, `bool`. The
@@ -16,9 +16,12 @@
 //!   layout `[len: u64][bytes][null]`). Hash hits the
 //!   payload bytes, not the pointer — different heap
 //!   copies of the same string hash and compare equal.
-//! - `Tuple` — reserved. Not implemented in MVP. The
-//!   executor / codegen rejects tuple keys with a
-//!   compile-time error today.
+//! - `Tuple` — `key_ptr` points at `key_sz` payload
+//!   bytes, one word per element; the bytes are the key.
+//! - `Derived` — a `%% hash.` struct laid out like a
+//!   `Tuple`, hashed and compared through the struct's
+//!   own `hash` / `eq` (passed to `_zo_map_new`), so two
+//!   copies of the same `str` field key the same entry.
 //!
 //! Storage: each slot owns boxed `Vec<u8>` for key and
 //! value. Heap-per-entry is the same trade documented
@@ -26,7 +29,7 @@
 //! inline byte arenas once a benchmark shows it
 //! matters.
 
-use crate::str::str_bytes;
+use crate::str::{alloc_str, str_bytes};
 
 const FNV_OFFSET_BASIS: u64 = 14695981039346656037;
 const FNV_PRIME: u64 = 1099511628211;
@@ -44,6 +47,7 @@ pub enum KeyKind {
   Prim = 0,
   Str = 1,
   Tuple = 2,
+  Derived = 3,
 }
 
 impl KeyKind {
@@ -52,11 +56,20 @@ impl KeyKind {
       0 => KeyKind::Prim,
       1 => KeyKind::Str,
       2 => KeyKind::Tuple,
+      3 => KeyKind::Derived,
       _ => KeyKind::Prim,
     }
   }
 }
 
+/// A `Derived` key's `hash(self) -> int`, called with a
+/// pointer to the struct's payload. `int` is 32 bits wide.
+pub type KeyHashFn = unsafe extern "C" fn(*const u8) -> u32;
+
+/// A `Derived` key's `eq(self, other: Self) -> bool`, called
+/// with pointers to both payloads.
+pub type KeyEqFn = unsafe extern "C" fn(*const u8, *const u8) -> bool;
+
 /// Per-side scalar format identifier the codegen passes
 /// to `zo_map_show`. The discriminants are part of the
 /// runtime/codegen ABI: the executor derives this enum
@@ -215,6 +228,8 @@ pub struct ZoMap {
   key_sz: usize,
   val_sz: usize,
   key_kind: KeyKind,
+  /// The struct's `hash` / `eq` for a `Derived` key.
+  key_fns: Option<(KeyHashFn, KeyEqFn)>,
   len: usize,
   tombstones: usize,
 }
@@ -228,6 +243,7 @@ impl ZoMap {
       key_sz,
       val_sz,
       key_kind,
+      key_fns: None,
       len: 0,
       tombstones: 0,
     }
@@ -270,7 +286,10 @@ impl ZoMap {
   unsafe fn hash_key(&self, key_ptr: *const u8) -> u64 {
     let bytes = unsafe { self.key_payload(key_ptr) };
 
-    Self::hash_bytes(bytes)
+    match self.key_fns {
+      Some((hash, _)) => unsafe { hash(bytes.as_ptr()) as u64 },
+      None => Self::hash_bytes(bytes),
+    }
   }
 
   /// Resolve `key_ptr` to the actual payload byte slice the
@@ -295,7 +314,7 @@ impl ZoMap {
 
         unsafe { str_bytes(header) }
       }
-      KeyKind::Tuple => {
+      KeyKind::Tuple | KeyKind::Derived => {
         let payload = unsafe { *(key_ptr as *const *const u8) };
 
         unsafe { std::slice::from_raw_parts(payload, self.key_sz) }
@@ -317,13 +336,19 @@ impl ZoMap {
   /// Compare the key stored in a slot against an
   /// incoming `key_ptr`. The slot stores the payload
   /// bytes for str keys, so the comparison is byte-wise
-  /// regardless of kind.
+  /// for every kind but `Derived`, which asks the struct's
+  /// `eq`.
   ///
   /// # Safety
   ///
   /// Same contract as `hash_key`.
   unsafe fn key_eq(&self, slot_key: &[u8], key_ptr: *const u8) -> bool {
-    slot_key == unsafe { self.key_payload(key_ptr) }
+    let payload = unsafe { self.key_payload(key_ptr) };
+
+    match self.key_fns {
+      Some((_, eq)) => unsafe { eq(slot_key.as_ptr(), payload.as_ptr()) },
+      None => slot_key == payload,
+    }
   }
 
   /// Locate either the slot holding `key` or the first
@@ -405,16 +430,26 @@ impl ZoMap {
 /// # Safety
 ///
 /// `key_kind` must be a valid `KeyKind` discriminant
-/// (0 / 1 / 2). The caller's `key_sz` and `val_sz`
-/// must match what every subsequent op assumes.
+/// (0 / 1 / 2 / 3). The caller's `key_sz` and `val_sz`
+/// must match what every subsequent op assumes. A
+/// `Derived` map needs both `hash_fn` and `eq_fn`, and
+/// they must accept `key_sz`-byte payloads; the other
+/// kinds ignore them.
 #[unsafe(no_mangle)]
 pub unsafe extern "C-unwind" fn zo_map_new(
   key_kind: u8,
   key_sz: usize,
   val_sz: usize,
   cap: usize,
+  hash_fn: Option<KeyHashFn>,
+  eq_fn: Option<KeyEqFn>,
 ) -> *mut ZoMap {
-  let map = ZoMap::new(KeyKind::from_u8(key_kind), key_sz, val_sz, cap);
+  let key_kind = KeyKind::from_u8(key_kind);
+  let mut map = ZoMap::new(key_kind, key_sz, val_sz, cap);
+
+  if key_kind == KeyKind::Derived {
+    map.key_fns = hash_fn.zip(eq_fn);
+  }
 
   Box::into_raw(Box::new(map))
 }
@@ -568,6 +603,65 @@ pub unsafe extern "C-unwind" fn zo_map_remove(
   }
 }
 
+/// Index of the first live slot at or after `from`, or `-1`
+/// past the last one. Slots are walked in bucket order, so
+/// keys come out in no particular order. Backs `MapKeys`.
+///
+/// # Safety
+///
+/// `map` must be live.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_map_next_slot(
+  map: *mut ZoMap,
+  from: usize,
+) -> isize {
+  let m = unsafe { &*map };
+
+  m.slots
+    .iter()
+    .enumerate()
+    .skip(from)
+    .find(|(_, slot)| matches!(slot, Slot::Occupied { .. }))
+    .map_or(-1, |(idx, _)| idx as isize)
+}
+
+/// Copies the key in slot `slot` into `key_out` — in the form
+/// the call sites pass keys in: the raw bytes of a `Prim` key,
+/// a fresh `str` header pointer for a `Str` key, a pointer to
+/// the stored payload for a `Tuple` or `Derived` key. Returns
+/// `false` when the slot isn't live.
+///
+/// # Safety
+///
+/// `map` must be live; `key_out` must point at a writable
+/// key slot (`key_sz` bytes, or one pointer).
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_map_key_at(
+  map: *mut ZoMap,
+  slot: usize,
+  key_out: *mut u8,
+) -> bool {
+  let m = unsafe { &*map };
+
+  let Some(Slot::Occupied { key, .. }) = m.slots.get(slot) else {
+    return false;
+  };
+
+  match m.key_kind {
+    KeyKind::Prim => unsafe {
+      std::ptr::copy_nonoverlapping(key.as_ptr(), key_out, m.key_sz);
+    },
+    KeyKind::Str => unsafe {
+      *(key_out as *mut *const u8) = alloc_str(key);
+    },
+    KeyKind::Tuple | KeyKind::Derived => unsafe {
+      *(key_out as *mut *const u8) = key.as_ptr();
+    },
+  }
+
+  true
+}
+
 /// Number of live entries in the map.
 ///
 /// # Safety
@@ -622,11 +716,51 @@ pub unsafe extern "C-unwind" fn zo_map_show(
   val_fmt: u8,
 ) {
   let m = unsafe { &*map };
+  let mut out: Vec<u8> = Vec::with_capacity(64);
+
+  format_map(m, key_fmt, val_fmt, &mut out);
+
+  unsafe {
+    libc::write(fd as i32, out.as_ptr() as *const _, out.len());
+  }
+}
+
+thread_local! {
+  /// Backs the string `zo_map_debug` hands out.
+  static DEBUG_OUT: std::cell::RefCell<Vec<u8>> =
+    const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Formats the map as `zo_map_show` prints it and returns it
+/// as a NUL-terminated string. Not called by compiled code —
+/// `zo-dap` calls it through the debugger to show a `HashMap`
+/// variable by its entries. The string stays valid until the
+/// next call on the same thread.
+///
+/// # Safety
+///
+/// `map` must be a live pointer from `_zo_map_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_map_debug(
+  map: *mut ZoMap,
+  key_fmt: u8,
+  val_fmt: u8,
+) -> *const u8 {
+  let m = unsafe { &*map };
+
+  DEBUG_OUT.with_borrow_mut(|out| {
+    out.clear();
+    format_map(m, key_fmt, val_fmt, out);
+    out.push(0);
+    out.as_ptr()
+  })
+}
+
+/// Appends `{k0: v0, k1: v1}` to `out`.
+fn format_map(m: &ZoMap, key_fmt: u8, val_fmt: u8, out: &mut Vec<u8>) {
   let kf = MapFmt::from_u8(key_fmt);
   let vf = MapFmt::from_u8(val_fmt);
 
-  let mut out: Vec<u8> = Vec::with_capacity(64);
-
   out.push(b'{');
 
   let mut first = true;
@@ -639,17 +773,13 @@ pub unsafe extern "C-unwind" fn zo_map_show(
 
       first = false;
 
-      kf.format_bytes(key, false, &mut out);
+      kf.format_bytes(key, false, out);
       out.extend_from_slice(b": ");
-      vf.format_bytes(val, true, &mut out);
+      vf.format_bytes(val, true, out);
     }
   }
 
   out.push(b'}');
-
-  unsafe {
-    libc::write(fd as i32, out.as_ptr() as *const _, out.len());
-  }
 }
 
 /// Pretty-print a `HashSet` as `{k0, k1, ...}`. Same
@@ -712,8 +842,9 @@ mod tests {
 
   #[test]
   fn empty_map_has_zero_len() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
 
     assert_eq!(unsafe { zo_map_len(map) }, 0);
 
@@ -724,8 +855,9 @@ mod tests {
 
   #[test]
   fn insert_then_get_round_trips_int_keys() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
 
     for i in 0i32..32 {
       let k = i.to_le_bytes();
@@ -755,8 +887,9 @@ mod tests {
 
   #[test]
   fn overwrite_same_key_keeps_len_constant() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
 
     let k = 7i32.to_le_bytes();
 
@@ -787,7 +920,7 @@ mod tests {
     // Force a tight initial capacity (clamped to
     // INITIAL_CAPACITY internally) and exercise the
     // probe chain.
-    let map = unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, 1) };
+    let map = unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, 1, None, None) };
 
     for i in 0i32..200 {
       let k = i.to_le_bytes();
@@ -813,8 +946,9 @@ mod tests {
 
   #[test]
   fn remove_then_reinsert_reuses_tombstone() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
 
     let k = 42i32.to_le_bytes();
     let v = 1000i32.to_le_bytes();
@@ -852,8 +986,9 @@ mod tests {
 
   #[test]
   fn contains_distinguishes_present_and_absent() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
 
     let k = 5i32.to_le_bytes();
 
@@ -876,7 +1011,9 @@ mod tests {
 
   #[test]
   fn str_keys_hash_by_content_not_pointer() {
-    let map = unsafe { zo_map_new(KeyKind::Str as u8, 8, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Str as u8, 8, 4, INITIAL_CAPACITY, None, None)
+    };
 
     let k1 = make_str(b"hello");
     let k2 = make_str(b"hello");
@@ -907,9 +1044,122 @@ mod tests {
     }
   }
 
+  /// A two-word key whose identity is its first word only.
+  unsafe extern "C" fn first_word_hash(key: *const u8) -> u32 {
+    unsafe { *(key as *const u64) as u32 }
+  }
+
+  unsafe extern "C" fn first_word_eq(a: *const u8, b: *const u8) -> bool {
+    unsafe { *(a as *const u64) == *(b as *const u64) }
+  }
+
+  #[test]
+  fn derived_keys_hash_and_compare_through_their_fns() {
+    let map = unsafe {
+      zo_map_new(
+        KeyKind::Derived as u8,
+        16,
+        4,
+        INITIAL_CAPACITY,
+        Some(first_word_hash),
+        Some(first_word_eq),
+      )
+    };
+
+    let k1 = [7u64, 1];
+    let k2 = [7u64, 2];
+    let p1 = k1.as_ptr() as *const u8;
+    let p2 = k2.as_ptr() as *const u8;
+    let p1_slot = (&p1) as *const *const u8 as *const u8;
+    let p2_slot = (&p2) as *const *const u8 as *const u8;
+
+    unsafe {
+      zo_map_insert(map, p1_slot, 1i32.to_le_bytes().as_ptr());
+      zo_map_insert(map, p2_slot, 2i32.to_le_bytes().as_ptr());
+    }
+
+    // Byte-unequal but `eq`-equal: the second insert
+    // overwrote the first.
+    let mut out = [0u8; 4];
+
+    assert_eq!(unsafe { zo_map_len(map) }, 1);
+    assert!(unsafe { zo_map_get(map, p1_slot, out.as_mut_ptr()) });
+    assert_eq!(i32::from_le_bytes(out), 2);
+
+    unsafe {
+      zo_map_free(map);
+    }
+  }
+
+  #[test]
+  fn slots_walk_every_live_key_once() {
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
+
+    for i in 0i32..40 {
+      unsafe {
+        zo_map_insert(map, i.to_le_bytes().as_ptr(), i.to_le_bytes().as_ptr());
+      }
+    }
+
+    let mut k = 3i32.to_le_bytes();
+    let mut out = [0u8; 4];
+
+    unsafe {
+      zo_map_remove(map, k.as_ptr(), out.as_mut_ptr());
+    }
+
+    let mut keys = Vec::new();
+    let mut slot = unsafe { zo_map_next_slot(map, 0) };
+
+    while slot >= 0 {
+      assert!(unsafe { zo_map_key_at(map, slot as usize, k.as_mut_ptr()) });
+
+      keys.push(i32::from_le_bytes(k));
+      slot = unsafe { zo_map_next_slot(map, slot as usize + 1) };
+    }
+
+    keys.sort_unstable();
+
+    assert_eq!(keys, (0..40).filter(|i| *i != 3).collect::<Vec<_>>());
+    assert!(!unsafe { zo_map_key_at(map, usize::MAX, k.as_mut_ptr()) });
+
+    unsafe {
+      zo_map_free(map);
+    }
+  }
+
+  #[test]
+  fn key_at_hands_str_keys_back_as_fresh_strs() {
+    let map = unsafe {
+      zo_map_new(KeyKind::Str as u8, 8, 4, INITIAL_CAPACITY, None, None)
+    };
+    let key = make_str(b"hello");
+    let ptr = key.as_ptr();
+    let slot = (&ptr) as *const *const u8 as *const u8;
+
+    unsafe {
+      zo_map_insert(map, slot, 1i32.to_le_bytes().as_ptr());
+    }
+
+    let live = unsafe { zo_map_next_slot(map, 0) };
+    let mut out: *const u8 = std::ptr::null();
+
+    assert!(unsafe {
+      zo_map_key_at(map, live as usize, (&mut out) as *mut *const u8 as *mut u8)
+    });
+    assert_eq!(unsafe { str_bytes(out) }, b"hello");
+    assert_eq!(unsafe { zo_map_next_slot(map, live as usize + 1) }, -1);
+
+    unsafe {
+      zo_map_free(map);
+    }
+  }
+
   #[test]
   fn distinct_str_keys_dont_collide_under_grow() {
-    let map = unsafe { zo_map_new(KeyKind::Str as u8, 8, 4, 1) };
+    let map = unsafe { zo_map_new(KeyKind::Str as u8, 8, 4, 1, None, None) };
 
     let bytes = [
       &b"alpha"[..],
@@ -1004,8 +1254,9 @@ mod tests {
 
   #[test]
   fn show_int_int_emits_braces_and_entry_count() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
 
     for (k, v) in [(1i32, 100i32), (2, 200), (3, 300)] {
       unsafe {
@@ -1035,8 +1286,9 @@ mod tests {
 
   #[test]
   fn show_empty_emits_just_braces() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 4, INITIAL_CAPACITY, None, None)
+    };
 
     let bytes = capture_fd(|fd| unsafe {
       zo_map_show(map, fd, MapFmt::Int as u8, MapFmt::Int as u8);
@@ -1051,8 +1303,9 @@ mod tests {
 
   #[test]
   fn show_bool_value_uses_true_false() {
-    let map =
-      unsafe { zo_map_new(KeyKind::Prim as u8, 4, 1, INITIAL_CAPACITY) };
+    let map = unsafe {
+      zo_map_new(KeyKind::Prim as u8, 4, 1, INITIAL_CAPACITY, None, None)
+    };
 
     let k = 7i32.to_le_bytes();
     let v_true = [1u8];
@@ -99,14 +99,20 @@ pub unsafe extern "C-unwind" fn zo_mem_write_u8(dst: *mut u8, value: u8) {
   }
 }
 
-/// Read one byte at `src`.
+/// Read one byte at `src`, zero-extended to the `int` the
+/// zo declaration returns — a `u8` return leaves the upper
+/// bits of the register unspecified.
 ///
 /// # Safety
 ///
 /// `src` must point at one readable byte.
 #[unsafe(no_mangle)]
-pub unsafe extern "C-unwind" fn zo_mem_read_u8(src: *const u8) -> u8 {
-  if src.is_null() { 0 } else { unsafe { *src } }
+pub unsafe extern "C-unwind" fn zo_mem_read_u8(src: *const u8) -> i32 {
+  if src.is_null() {
+    0
+  } else {
+    i32::from(unsafe { *src })
+  }
 }
 
 /// Write an `f64` (8 bytes, native endian) at `dst`.
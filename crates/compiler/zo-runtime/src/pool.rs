@@ -158,45 +158,16 @@ impl Pool {
     }
   }
 
-  /// Submit a zero-arg task.
-  pub fn spawn(&self, callee: extern "C-unwind" fn()) {
-    self.enqueue(Box::into_raw(ZoTask::new_green_standalone(callee)));
-  }
-
-  /// Submit a 1-arg task. `arg0` is replayed into the
-  /// callee by the task shim on first dispatch.
-  pub fn spawn_1(&self, callee: extern "C-unwind" fn(u64), arg0: u64) {
-    self.enqueue(Box::into_raw(ZoTask::new_green_standalone_1(callee, arg0)));
-  }
-
-  /// Submit a 2-arg task.
-  pub fn spawn_2(
-    &self,
-    callee: extern "C-unwind" fn(u64, u64),
-    arg0: u64,
-    arg1: u64,
-  ) {
-    self.enqueue(Box::into_raw(ZoTask::new_green_standalone_2(
-      callee, arg0, arg1,
-    )));
-  }
-
-  /// Submit a 3-arg task — the green-task argument ceiling.
-  pub fn spawn_3(
-    &self,
-    callee: extern "C-unwind" fn(u64, u64, u64),
-    arg0: u64,
-    arg1: u64,
-    arg2: u64,
-  ) {
-    self.enqueue(Box::into_raw(ZoTask::new_green_standalone_3(
-      callee, arg0, arg1, arg2,
-    )));
+  /// Submit a zero-arg task. `env` is the closure
+  /// environment word, handed to the callee after its
+  /// arguments (`0` for a plain function).
+  pub fn spawn(&self, callee: extern "C-unwind" fn(), env: u64) {
+    self.enqueue(Box::into_raw(ZoTask::new_green_standalone(callee, env)));
   }
 
   /// Place a freshly-built task on the emptiest worker's
   /// shared queue, bump the pending count, and wake that
-  /// worker. Shared by every `spawn*` arity.
+  /// worker.
   fn enqueue(&self, task: *mut ZoTask) {
     self.pending.fetch_add(1, Ordering::SeqCst);
 
@@ -402,73 +373,26 @@ pub unsafe extern "C-unwind" fn zo_pool_new(n: i32) -> i64 {
   Box::into_raw(pool) as i64
 }
 
-/// Submit a zero-arg function to the pool.
+/// Submit a zero-arg function to the pool. `(callee, env)`
+/// is a lowered `Fn()` value: a capturing closure's
+/// trampoline and its environment, or a plain function's
+/// zero-capture trampoline and `0`. The callee receives `env`
+/// after its arguments.
 ///
 /// # Safety
 ///
-/// `handle` must be a live pool from `zo_pool_new`.
+/// `handle` must be a live pool from `zo_pool_new`. A
+/// non-zero `env` passes to the task, which frees it once it
+/// has run.
 #[unsafe(no_mangle)]
 pub unsafe extern "C-unwind" fn zo_pool_spawn(
   handle: i64,
   callee: extern "C-unwind" fn(),
+  env: u64,
 ) {
   let pool = unsafe { &*(handle as *const Pool) };
 
-  pool.spawn(callee);
-}
-
-/// Submit a 1-arg function to the pool. `arg0` rides as a
-/// raw word and is replayed into the callee by the task
-/// shim (e.g. a shared buffer handle or a band index).
-///
-/// # Safety
-///
-/// `handle` must be a live pool from `zo_pool_new`.
-#[unsafe(no_mangle)]
-pub unsafe extern "C-unwind" fn zo_pool_spawn_1(
-  handle: i64,
-  callee: extern "C-unwind" fn(u64),
-  arg0: u64,
-) {
-  let pool = unsafe { &*(handle as *const Pool) };
-
-  pool.spawn_1(callee, arg0);
-}
-
-/// Submit a 2-arg function to the pool.
-///
-/// # Safety
-///
-/// `handle` must be a live pool from `zo_pool_new`.
-#[unsafe(no_mangle)]
-pub unsafe extern "C-unwind" fn zo_pool_spawn_2(
-  handle: i64,
-  callee: extern "C-unwind" fn(u64, u64),
-  arg0: u64,
-  arg1: u64,
-) {
-  let pool = unsafe { &*(handle as *const Pool) };
-
-  pool.spawn_2(callee, arg0, arg1);
-}
-
-/// Submit a 3-arg function to the pool — the green-task
-/// argument ceiling.
-///
-/// # Safety
-///
-/// `handle` must be a live pool from `zo_pool_new`.
-#[unsafe(no_mangle)]
-pub unsafe extern "C-unwind" fn zo_pool_spawn_3(
-  handle: i64,
-  callee: extern "C-unwind" fn(u64, u64, u64),
-  arg0: u64,
-  arg1: u64,
-  arg2: u64,
-) {
-  let pool = unsafe { &*(handle as *const Pool) };
-
-  pool.spawn_3(callee, arg0, arg1, arg2);
+  pool.spawn(callee, env);
 }
 
 /// Block until all submitted tasks complete.
@@ -538,7 +462,7 @@ mod tests {
     let pool = Pool::new(4);
 
     for _ in 0..1_000 {
-      pool.spawn(inc);
+      pool.spawn(inc, 0);
     }
 
     pool.wait_idle();
@@ -565,7 +489,7 @@ mod tests {
     let pool = Pool::new(1);
 
     for _ in 0..100 {
-      pool.spawn(inc);
+      pool.spawn(inc, 0);
     }
 
     pool.wait_idle();
@@ -593,7 +517,7 @@ mod tests {
     let pool = Pool::new(8);
 
     for _ in 0..10_000 {
-      pool.spawn(inc);
+      pool.spawn(inc, 0);
     }
 
     pool.wait_idle();
@@ -602,4 +526,51 @@ mod tests {
 
     pool.shutdown();
   }
+
+  #[test]
+  fn pool_frees_a_task_env_once_the_task_has_run() {
+    // A capturing closure submitted as a task hands its
+    // environment to the pool; the task shim releases it —
+    // drop glue first — after the trampoline returns.
+    static RAN: AtomicU32 = AtomicU32::new(0);
+    static DROPPED: AtomicU32 = AtomicU32::new(0);
+
+    extern "C-unwind" fn trampoline(env: u64) {
+      assert_ne!(env, 0);
+
+      RAN.fetch_add(1, Ordering::SeqCst);
+    }
+
+    extern "C-unwind" fn drop_glue(_env: u64) {
+      DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let pool = Pool::new(2);
+
+    for _ in 0..8 {
+      // `[drop_glue][len = 0]`, addressed past the glue word.
+      let env = unsafe {
+        let block = libc::malloc(2 * 8) as *mut u64;
+
+        *block = drop_glue as *const () as u64;
+        *block.add(1) = 0;
+
+        block.add(1) as u64
+      };
+      let callee = unsafe {
+        std::mem::transmute::<extern "C-unwind" fn(u64), extern "C-unwind" fn()>(
+          trampoline,
+        )
+      };
+
+      pool.spawn(callee, env);
+    }
+
+    pool.wait_idle();
+
+    assert_eq!(RAN.load(Ordering::SeqCst), 8);
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 8);
+
+    pool.shutdown();
+  }
 }
@@ -1,4 +1,4 @@
-//! Runtime string operations — slicing.
+//! Runtime string operations — slicing and formatting.
 //!
 //! Zo's `str` at runtime is a pointer to a length-
 //! prefixed blob: `[len: u64][bytes...][null]`. The
@@ -14,20 +14,49 @@
 //! same layout, allocated as `Box<[u8]>` and leaked
 //! for the process lifetime. A proper free path waits
 //! on reference counting or GC.
+//!
+//! A slice does not copy: [`zo_str_slice`] returns a
+//! view — a 16-byte header `[len | STR_VIEW_BIT][ptr]`
+//! whose `ptr` points into the parent's payload. The
+//! top bit of the length word tells the two layouts
+//! apart, so every reader goes through [`str_len`] and
+//! [`str_bytes`] (or the codegen's inline equivalent).
+//! A view carries no trailing null — C callers ask
+//! [`zo_str_cstr`] for one.
+//!
+//! Interpolation specs (`{price:.2}`, `{id:06}`) land on
+//! [`zo_fmt_int`], [`zo_fmt_uint`], [`zo_fmt_float`] and
+//! [`zo_fmt_str`], each taking the value and the spec
+//! packed into one `u64`.
+
+/// Tag bit set in the length word of a view header.
+pub const STR_VIEW_BIT: u64 = 1 << 63;
+
+/// Whether `ptr` is a view header rather than an owned
+/// str.
+///
+/// # Safety
+///
+/// Same contract as [`str_len`].
+#[inline]
+pub unsafe fn str_is_view(ptr: *const u8) -> bool {
+  unsafe { (ptr as *const u64).read_unaligned() & STR_VIEW_BIT != 0 }
+}
 
 /// Reads the `u64` length prefix at offset 0 of a
-/// runtime `str` pointer.
+/// runtime `str` pointer, with the view tag masked off.
 ///
 /// # Safety
 ///
 /// `ptr` must point at a valid zo str header —
 /// 8 bytes of little-endian length followed by at
-/// least that many bytes of payload.
+/// least that many bytes of payload, or by a view's
+/// payload pointer.
 #[inline]
 pub unsafe fn str_len(ptr: *const u8) -> usize {
   let len_bytes = unsafe { std::slice::from_raw_parts(ptr, 8) };
 
-  u64::from_le_bytes(len_bytes.try_into().unwrap()) as usize
+  (u64::from_le_bytes(len_bytes.try_into().unwrap()) & !STR_VIEW_BIT) as usize
 }
 
 /// Borrows the byte payload of a runtime `str`, not
@@ -40,7 +69,13 @@ pub unsafe fn str_len(ptr: *const u8) -> usize {
 pub unsafe fn str_bytes<'a>(ptr: *const u8) -> &'a [u8] {
   let len = unsafe { str_len(ptr) };
 
-  unsafe { std::slice::from_raw_parts(ptr.add(8), len) }
+  let payload = if unsafe { str_is_view(ptr) } {
+    unsafe { (ptr.add(8) as *const *const u8).read_unaligned() }
+  } else {
+    unsafe { ptr.add(8) }
+  };
+
+  unsafe { std::slice::from_raw_parts(payload, len) }
 }
 
 /// Allocate a fresh heap-backed zo `str` of `len` bytes,
@@ -173,6 +208,174 @@ pub unsafe extern "C-unwind" fn zo_char_to_str(c: u32) -> *const u8 {
   alloc_str(encoded.as_bytes())
 }
 
+/// A `{name:spec}` format spec decoded from the `u64` the
+/// executor packs with `FormatSpec::pack` in `zo-token` —
+/// bits `0..21` fill, `21..23` align (0 default, 1 left,
+/// 2 center, 3 right), `23` sign, `24` alternate, `25` zero,
+/// `26..29` kind (0 display, 1 `x`, 2 `X`, 3 `b`, 4 `o`),
+/// `29` precision present, `32..48` width, `48..64`
+/// precision.
+struct Spec {
+  fill: char,
+  align: u64,
+  sign: bool,
+  alternate: bool,
+  zero: bool,
+  kind: u64,
+  width: usize,
+  precision: Option<usize>,
+}
+
+impl Spec {
+  fn unpack(bits: u64) -> Self {
+    Self {
+      fill: char::from_u32((bits & 0x1F_FFFF) as u32).unwrap_or(' '),
+      align: (bits >> 21) & 0b11,
+      sign: bits >> 23 & 1 != 0,
+      alternate: bits >> 24 & 1 != 0,
+      zero: bits >> 25 & 1 != 0,
+      kind: (bits >> 26) & 0b111,
+      width: ((bits >> 32) & 0xFFFF) as usize,
+      precision: (bits >> 29 & 1 != 0).then_some((bits >> 48) as usize),
+    }
+  }
+
+  /// Pads `body` with the fill char up to the width. `numeric`
+  /// picks the default alignment — numbers lean right, text
+  /// leans left.
+  fn pad(&self, body: String, numeric: bool) -> String {
+    let len = body.chars().count();
+
+    if len >= self.width {
+      return body;
+    }
+
+    let gap = self.width - len;
+    let (before, after) = match (self.align, numeric) {
+      (1, _) | (0, false) => (0, gap),
+      (2, _) => (gap / 2, gap - gap / 2),
+      _ => (gap, 0),
+    };
+
+    let mut out =
+      String::with_capacity(body.len() + gap * self.fill.len_utf8());
+
+    out.extend(std::iter::repeat_n(self.fill, before));
+    out.push_str(&body);
+    out.extend(std::iter::repeat_n(self.fill, after));
+
+    out
+  }
+
+  /// Assembles a number from its sign, radix prefix and
+  /// digits. `0` zero-pads between the prefix and the digits
+  /// and overrides fill and align, the way Rust's does.
+  fn number(&self, negative: bool, prefix: &str, digits: &str) -> String {
+    let sign = if negative {
+      "-"
+    } else if self.sign {
+      "+"
+    } else {
+      ""
+    };
+
+    let len = sign.len() + prefix.len() + digits.len();
+
+    if self.zero && len < self.width {
+      let zeros = "0".repeat(self.width - len);
+
+      return format!("{sign}{prefix}{zeros}{digits}");
+    }
+
+    self.pad(format!("{sign}{prefix}{digits}"), true)
+  }
+
+  /// Writes an unsigned magnitude in the spec's radix.
+  fn integer(&self, negative: bool, magnitude: u64) -> String {
+    let (prefix, digits) = match self.kind {
+      1 => ("0x", format!("{magnitude:x}")),
+      2 => ("0x", format!("{magnitude:X}")),
+      3 => ("0b", format!("{magnitude:b}")),
+      4 => ("0o", format!("{magnitude:o}")),
+      _ => ("", format!("{magnitude}")),
+    };
+
+    let prefix = if self.alternate { prefix } else { "" };
+
+    self.number(negative, prefix, &digits)
+  }
+}
+
+/// Formats a signed integer through a packed `{name:spec}`.
+/// The radix kinds write the two's complement of a negative
+/// value, as Rust's `{:x}` does.
+///
+/// # Safety
+///
+/// No preconditions — `n` and `spec` are plain scalars.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_fmt_int(n: i64, spec: u64) -> *const u8 {
+  let spec = Spec::unpack(spec);
+  let formatted = if spec.kind == 0 {
+    spec.integer(n < 0, n.unsigned_abs())
+  } else {
+    spec.integer(false, n as u64)
+  };
+
+  alloc_str(formatted.as_bytes())
+}
+
+/// Formats an unsigned integer through a packed
+/// `{name:spec}`.
+///
+/// # Safety
+///
+/// No preconditions — `n` and `spec` are plain scalars.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_fmt_uint(n: u64, spec: u64) -> *const u8 {
+  let formatted = Spec::unpack(spec).integer(false, n);
+
+  alloc_str(formatted.as_bytes())
+}
+
+/// Formats a float through a packed `{name:spec}` — a
+/// precision rounds to that many decimals, otherwise the
+/// digits match `zo_float_to_str`.
+///
+/// # Safety
+///
+/// No preconditions — `f` and `spec` are plain scalars.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_fmt_float(f: f64, spec: u64) -> *const u8 {
+  let spec = Spec::unpack(spec);
+  let magnitude = f.abs();
+  let digits = match spec.precision {
+    Some(precision) => format!("{magnitude:.precision$}"),
+    None => format!("{magnitude}"),
+  };
+
+  let formatted = spec.number(f.is_sign_negative() && !f.is_nan(), "", &digits);
+
+  alloc_str(formatted.as_bytes())
+}
+
+/// Pads a zo `str` through a packed `{name:spec}` — only
+/// fill, align and width apply to text.
+///
+/// # Safety
+///
+/// `s` must point at a live zo str header.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_fmt_str(
+  s: *const u8,
+  spec: u64,
+) -> *const u8 {
+  let text = String::from_utf8_lossy(unsafe { str_bytes(s) }).into_owned();
+  let formatted = Spec::unpack(spec).pad(text, false);
+
+  alloc_str(formatted.as_bytes())
+}
+
 /// Concatenate N zo strs into a single heap-allocated str.
 ///
 /// @note — single allocation regardless of segment count.
@@ -258,23 +461,77 @@ pub unsafe extern "C" fn zo_cstr_from_str(
     return std::ptr::null();
   }
 
-  let len = unsafe { str_len(s) };
-  let payload = unsafe { s.add(8) };
-  let bytes = unsafe { std::slice::from_raw_parts(payload, len) };
+  let bytes = unsafe { str_bytes(s) };
 
   if bytes.contains(&0) {
     return std::ptr::null();
   }
 
-  payload as *const std::os::raw::c_char
+  unsafe { zo_str_cstr(s) }
+}
+
+/// Address of the first payload byte of `s`. Backs
+/// `core::c::zo_str_data`, which `String` copies from.
+///
+/// # Safety
+///
+/// `s` must point at a valid zo str header.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zo_str_data(s: *const u8) -> *const u8 {
+  unsafe { str_bytes(s) }.as_ptr()
+}
+
+/// Return a NUL-terminated pointer to the payload of `s`.
+///
+/// @note — an owned str already ends in a NUL, so its
+/// post-prefix pointer is returned as is. A view stops
+/// mid-parent, so its bytes are copied into a fresh
+/// owned str first. Codegen calls this before handing a
+/// `str` to a libc path argument.
+///
+/// # Safety
+///
+/// `s` must point at a valid zo str header.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn zo_str_cstr(
+  s: *const u8,
+) -> *const std::os::raw::c_char {
+  let owned = if unsafe { str_is_view(s) } {
+    alloc_str(unsafe { str_bytes(s) })
+  } else {
+    s
+  };
+
+  unsafe { owned.add(8) as *const std::os::raw::c_char }
 }
 
-/// Slice `src[lo..hi]` — produce a fresh heap str
-/// containing the bytes of `src` from index `lo`
-/// (inclusive) to `hi` (exclusive).
+/// Byte-wise equality of two zo strs, `1` when equal.
+///
+/// @note — codegen calls this for `==` / `!=` on `str`
+/// so owned strs and views compare by content alike.
+///
+/// # Safety
 ///
-/// Aborts on out-of-range bounds or `lo > hi`. A
-/// richer diagnostic surface waits on zo's panic
+/// `lhs` and `rhs` must both point at live zo str
+/// headers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn zo_str_eq(
+  lhs: *const u8,
+  rhs: *const u8,
+) -> u64 {
+  (unsafe { str_bytes(lhs) } == unsafe { str_bytes(rhs) }) as u64
+}
+
+/// Slice `src[lo..hi]` — produce a view of the bytes of
+/// `src` from index `lo` (inclusive) to `hi` (exclusive).
+///
+/// @note — the view borrows `src`'s payload, no bytes
+/// are copied. Slicing a view points straight at the
+/// root owner, so views never chain.
+///
+/// Aborts on out-of-range bounds, `lo > hi`, or a
+/// bound that splits a UTF-8 sequence. A richer
+/// diagnostic surface waits on zo's panic
 /// infrastructure; for now we fail loud so bugs
 /// don't silently produce wrong results.
 ///
@@ -298,7 +555,31 @@ pub unsafe extern "C-unwind" fn zo_str_slice(
     );
   }
 
-  alloc_str(&src_bytes[lo..hi])
+  for index in [lo, hi] {
+    if !is_char_boundary(src_bytes, index) {
+      panic!("zo_str_slice: byte index {index} is not a char boundary");
+    }
+  }
+
+  alloc_view(&src_bytes[lo..hi])
+}
+
+/// Whether `index` starts a UTF-8 sequence of `bytes` —
+/// or sits at either end of it.
+fn is_char_boundary(bytes: &[u8], index: usize) -> bool {
+  index == 0
+    || index == bytes.len()
+    || bytes.get(index).is_some_and(|&b| (b as i8) >= -0x40)
+}
+
+/// Allocate a view header over `payload`. Leaked like
+/// [`alloc_str_with`]; the payload stays owned by its
+/// parent.
+fn alloc_view(payload: &[u8]) -> *const u8 {
+  let header =
+    Box::new([payload.len() as u64 | STR_VIEW_BIT, payload.as_ptr() as u64]);
+
+  Box::leak(header).as_ptr() as *const u8
 }
 
 /// Replace every non-overlapping occurrence of `needle` in
@@ -410,7 +691,7 @@ mod tests {
   }
 
   #[test]
-  fn slice_reads_len_and_copies_bytes() {
+  fn slice_reads_len_and_bytes() {
     let src = make_str(b"hello, world");
     let sliced = unsafe { zo_str_slice(src.as_ptr(), 7, 12) };
 
@@ -439,6 +720,178 @@ mod tests {
     }
   }
 
+  #[test]
+  fn slice_is_a_view_into_the_parent() {
+    let src = make_str(b"hello, world");
+    let sliced = unsafe { zo_str_slice(src.as_ptr(), 7, 12) };
+
+    assert!(unsafe { str_is_view(sliced) });
+    assert_eq!(unsafe { str_bytes(sliced) }.as_ptr(), src[8 + 7..].as_ptr());
+  }
+
+  #[test]
+  fn slice_of_a_view_points_at_the_root() {
+    let src = make_str(b"hello, world");
+    let outer = unsafe { zo_str_slice(src.as_ptr(), 7, 12) };
+    let inner = unsafe { zo_str_slice(outer, 1, 3) };
+
+    assert_eq!(unsafe { str_bytes(inner) }, b"or");
+    assert_eq!(unsafe { str_bytes(inner) }.as_ptr(), src[8 + 8..].as_ptr());
+  }
+
+  #[test]
+  #[should_panic(expected = "byte index 2 is not a char boundary")]
+  fn slice_inside_a_char_panics() {
+    // `é` is two bytes, at 1..3.
+    let src = make_str("hé!".as_bytes());
+
+    unsafe {
+      zo_str_slice(src.as_ptr(), 0, 2);
+    }
+  }
+
+  #[test]
+  fn slice_at_char_boundaries_keeps_multibyte_chars() {
+    let src = make_str("hé!".as_bytes());
+    let sliced = unsafe { zo_str_slice(src.as_ptr(), 1, 3) };
+
+    assert_eq!(unsafe { str_bytes(sliced) }, "é".as_bytes());
+  }
+
+  #[test]
+  fn cstr_of_a_view_is_nul_terminated() {
+    let src = make_str(b"abcdef");
+    let sliced = unsafe { zo_str_slice(src.as_ptr(), 1, 3) };
+    let cstr = unsafe { std::ffi::CStr::from_ptr(zo_str_cstr(sliced)) };
+
+    assert_eq!(cstr.to_bytes(), b"bc");
+  }
+
+  #[test]
+  fn eq_compares_views_and_owned_by_content() {
+    let src = make_str(b"abcabc");
+    let owned = make_str(b"abc");
+    let sliced = unsafe { zo_str_slice(src.as_ptr(), 3, 6) };
+
+    assert_eq!(unsafe { zo_str_eq(sliced, owned.as_ptr()) }, 1);
+    assert_eq!(unsafe { zo_str_eq(src.as_ptr(), owned.as_ptr()) }, 0);
+  }
+
+  /// Packs a spec the way `FormatSpec::pack` does, for
+  /// the `zo_fmt_*` tests. `flags` carries the sign,
+  /// alternate and zero bits (23, 24, 25) pre-shifted.
+  fn spec(
+    fill: char,
+    align: u64,
+    kind: u64,
+    width: u64,
+    precision: Option<u64>,
+    flags: u64,
+  ) -> u64 {
+    fill as u64
+      | align << 21
+      | flags
+      | kind << 26
+      | (precision.is_some() as u64) << 29
+      | width << 32
+      | precision.unwrap_or(0) << 48
+  }
+
+  fn fmt_text(ptr: *const u8) -> String {
+    String::from_utf8(unsafe { str_bytes(ptr) }.to_vec()).unwrap()
+  }
+
+  #[test]
+  fn fmt_float_rounds_to_precision() {
+    let two = spec(' ', 0, 0, 0, Some(2), 0);
+
+    assert_eq!(fmt_text(unsafe { zo_fmt_float(9.456, two) }), "9.46");
+    assert_eq!(fmt_text(unsafe { zo_fmt_float(-0.5, two) }), "-0.50");
+  }
+
+  #[test]
+  fn fmt_int_zero_pads_after_the_sign() {
+    let zero_six = spec(' ', 0, 0, 6, None, 1 << 25);
+
+    assert_eq!(fmt_text(unsafe { zo_fmt_int(42, zero_six) }), "000042");
+    assert_eq!(fmt_text(unsafe { zo_fmt_int(-42, zero_six) }), "-00042");
+  }
+
+  #[test]
+  fn fmt_int_writes_radix_kinds() {
+    let alt_hex = spec(' ', 0, 1, 0, None, 1 << 24);
+    let upper_hex = spec(' ', 0, 2, 0, None, 0);
+    let alt_bin_zero = spec(' ', 0, 3, 10, None, 1 << 24 | 1 << 25);
+
+    assert_eq!(fmt_text(unsafe { zo_fmt_int(255, alt_hex) }), "0xff");
+    assert_eq!(fmt_text(unsafe { zo_fmt_int(255, upper_hex) }), "FF");
+    assert_eq!(
+      fmt_text(unsafe { zo_fmt_int(5, alt_bin_zero) }),
+      "0b00000101"
+    );
+    assert_eq!(
+      fmt_text(unsafe { zo_fmt_int(-1, upper_hex) }),
+      "FFFFFFFFFFFFFFFF"
+    );
+  }
+
+  #[test]
+  fn fmt_uint_keeps_the_full_range() {
+    let plain = spec(' ', 0, 0, 0, None, 0);
+
+    assert_eq!(
+      fmt_text(unsafe { zo_fmt_uint(u64::MAX, plain) }),
+      u64::MAX.to_string()
+    );
+  }
+
+  #[test]
+  fn fmt_sign_marks_non_negative_numbers() {
+    let plus = spec(' ', 0, 0, 0, None, 1 << 23);
+
+    assert_eq!(fmt_text(unsafe { zo_fmt_int(5, plus) }), "+5");
+    assert_eq!(fmt_text(unsafe { zo_fmt_int(-5, plus) }), "-5");
+  }
+
+  #[test]
+  fn fmt_aligns_numbers_right_and_text_left_by_default() {
+    let width = spec(' ', 0, 0, 4, None, 0);
+    let text = make_str(b"ab");
+
+    assert_eq!(fmt_text(unsafe { zo_fmt_int(7, width) }), "   7");
+    assert_eq!(
+      fmt_text(unsafe { zo_fmt_str(text.as_ptr(), width) }),
+      "ab  "
+    );
+  }
+
+  #[test]
+  fn fmt_str_fills_and_centers() {
+    let text = make_str(b"ab");
+    let center = spec('*', 2, 0, 7, None, 0);
+    let right = spec('-', 3, 0, 5, None, 0);
+
+    assert_eq!(
+      fmt_text(unsafe { zo_fmt_str(text.as_ptr(), center) }),
+      "**ab***"
+    );
+    assert_eq!(
+      fmt_text(unsafe { zo_fmt_str(text.as_ptr(), right) }),
+      "---ab"
+    );
+  }
+
+  #[test]
+  fn fmt_width_counts_chars_not_bytes() {
+    let text = make_str("héé".as_bytes());
+    let width = spec(' ', 3, 0, 5, None, 0);
+
+    assert_eq!(
+      fmt_text(unsafe { zo_fmt_str(text.as_ptr(), width) }),
+      "  héé"
+    );
+  }
+
   #[test]
   fn count_occurrences_disjoint() {
     assert_eq!(count_occurrences(b"abababab", b"ab"), 4);
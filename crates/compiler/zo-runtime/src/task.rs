@@ -22,6 +22,7 @@
 //! resolve against — compiled programs don't know
 //! whether they run on green tasks or OS threads.
 
+use crate::closure::zo_closure_env_free;
 use crate::ctxsw::{Context, ctx_switch};
 use crate::scheduler;
 use crate::stack::TaskStack;
@@ -122,6 +123,13 @@ pub struct ZoTask {
   user_arg0: u64,
   user_arg1: u64,
   user_arg2: u64,
+  /// Closure environment word, passed to the callee after
+  /// its arguments — a capturing closure's trampoline reads
+  /// its captures from it. `0` for a plain function, whose
+  /// zero-capture trampoline never looks. The task is the
+  /// env's last use, so the shim frees it once the callee
+  /// returns. Green-only.
+  user_env: u64,
   /// Return value captured by the task shim when the
   /// user callee completes normally. Read back by
   /// `zo_task_await` and returned through X0. For
@@ -155,49 +163,17 @@ impl ZoTask {
   /// cost-decomposition microbenches in `tests/` can
   /// build tasks without touching the thread-local
   /// run queue.
-  pub fn new_green_standalone(user_entry: extern "C-unwind" fn()) -> Box<Self> {
-    Self::new_green(user_entry)
-  }
-
-  /// Standalone 1-arg green task — same as
-  /// [`new_green_standalone`](Self::new_green_standalone)
-  /// but the shim replays `arg0` into the callee. Used by
-  /// the pool to dispatch a task that carries one argument
-  /// (e.g. a shared buffer handle) without touching the
-  /// thread-local run queue.
-  pub fn new_green_standalone_1(
-    user_entry: extern "C-unwind" fn(u64),
-    arg0: u64,
-  ) -> Box<Self> {
-    Self::new_green_1(user_entry, arg0)
-  }
-
-  /// Standalone 2-arg green task. See
-  /// [`new_green_standalone_1`](Self::new_green_standalone_1).
-  pub fn new_green_standalone_2(
-    user_entry: extern "C-unwind" fn(u64, u64),
-    arg0: u64,
-    arg1: u64,
+  pub fn new_green_standalone(
+    user_entry: extern "C-unwind" fn(),
+    env: u64,
   ) -> Box<Self> {
-    Self::new_green_2(user_entry, arg0, arg1)
-  }
-
-  /// Standalone 3-arg green task — the green-task argument
-  /// ceiling. See
-  /// [`new_green_standalone_1`](Self::new_green_standalone_1).
-  pub fn new_green_standalone_3(
-    user_entry: extern "C-unwind" fn(u64, u64, u64),
-    arg0: u64,
-    arg1: u64,
-    arg2: u64,
-  ) -> Box<Self> {
-    Self::new_green_3(user_entry, arg0, arg1, arg2)
+    Self::new_green(user_entry, env)
   }
 
   /// Allocate a new green task. The task is `Ready`
   /// and its Context bootstraps into [`task_shim`] on
   /// the first `ctx_switch` into it.
-  fn new_green(user_entry: extern "C-unwind" fn()) -> Box<Self> {
+  fn new_green(user_entry: extern "C-unwind" fn(), env: u64) -> Box<Self> {
     let stack = TaskStack::reserve();
     let stack_top = stack.top();
 
@@ -212,6 +188,7 @@ impl ZoTask {
       user_arg0: 0,
       user_arg1: 0,
       user_arg2: 0,
+      user_env: env,
       ret_value: 0,
       threaded: None,
     });
@@ -235,6 +212,7 @@ impl ZoTask {
   fn new_green_1(
     user_entry: extern "C-unwind" fn(u64),
     arg0: u64,
+    env: u64,
   ) -> Box<Self> {
     let stack = TaskStack::reserve();
     let stack_top = stack.top();
@@ -250,6 +228,7 @@ impl ZoTask {
       user_arg0: arg0,
       user_arg1: 0,
       user_arg2: 0,
+      user_env: env,
       ret_value: 0,
       threaded: None,
     });
@@ -268,6 +247,7 @@ impl ZoTask {
     user_entry: extern "C-unwind" fn(u64, u64),
     arg0: u64,
     arg1: u64,
+    env: u64,
   ) -> Box<Self> {
     let stack = TaskStack::reserve();
     let stack_top = stack.top();
@@ -283,6 +263,7 @@ impl ZoTask {
       user_arg0: arg0,
       user_arg1: arg1,
       user_arg2: 0,
+      user_env: env,
       ret_value: 0,
       threaded: None,
     });
@@ -302,6 +283,7 @@ impl ZoTask {
     arg0: u64,
     arg1: u64,
     arg2: u64,
+    env: u64,
   ) -> Box<Self> {
     let stack = TaskStack::reserve();
     let stack_top = stack.top();
@@ -317,6 +299,7 @@ impl ZoTask {
       user_arg0: arg0,
       user_arg1: arg1,
       user_arg2: arg2,
+      user_env: env,
       ret_value: 0,
       threaded: None,
     });
@@ -347,6 +330,7 @@ impl ZoTask {
       user_arg0: 0,
       user_arg1: 0,
       user_arg2: 0,
+      user_env: 0,
       ret_value: 0,
       threaded: Some(Box::new(ThreadedData {
         join: Mutex::new(None),
@@ -401,9 +385,10 @@ extern "C-unwind" fn task_shim(task_addr: u64) {
   // scheduler drops it only after the `Dead`
   // transition is consumed by `await_task`.
   let task = task_addr as *mut ZoTask;
-  let user_entry_addr = unsafe { (*task).user_entry_addr };
+  let (user_entry_addr, env) =
+    unsafe { ((*task).user_entry_addr, (*task).user_env) };
 
-  // Transmute the user callee as `fn() -> u64`. Zo
+  // Transmute the user callee as `fn(env) -> u64`. Zo
   // callees compile with the arm64 C ABI where a
   // return value is left in X0 regardless of the
   // static `unit` / `int` / pointer distinction —
@@ -415,15 +400,19 @@ extern "C-unwind" fn task_shim(task_addr: u64) {
   // ABI allows narrowing the return type between the
   // declared and the transmuted signatures provided
   // the caller only reads the returned X0 bits.
-  let user_entry: extern "C-unwind" fn() -> u64 = unsafe {
-    std::mem::transmute::<*const (), extern "C-unwind" fn() -> u64>(
+  // Every green callee takes the env word last: the compiler
+  // hands the runtime a closure's `__env` trampoline, or a
+  // zero-capture one wrapping a plain function, so the
+  // transmuted signature is the callee's own.
+  let user_entry: extern "C-unwind" fn(u64) -> u64 = unsafe {
+    std::mem::transmute::<*const (), extern "C-unwind" fn(u64) -> u64>(
       user_entry_addr as *const (),
     )
   };
 
   // `catch_unwind` needs a `FnOnce()` — wrap so the
   // `u64` return threads through the `Ok` arm.
-  let result = catch_unwind(AssertUnwindSafe(|| user_entry()));
+  let result = catch_unwind(AssertUnwindSafe(|| user_entry(env)));
 
   // SAFETY: same task pointer as above, still live.
   unsafe {
@@ -436,6 +425,8 @@ extern "C-unwind" fn task_shim(task_addr: u64) {
         (*task).outcome = TaskOutcome::Panicked;
       }
     }
+
+    zo_closure_env_free(env);
   }
 
   exit_current();
@@ -448,20 +439,20 @@ extern "C-unwind" fn task_shim_1(task_addr: u64) {
   let task = task_addr as *mut ZoTask;
   // SAFETY: `task_addr` carries a live `*mut ZoTask` —
   // the box outlives this shim's call frame.
-  let (user_entry_addr, arg0) =
-    unsafe { ((*task).user_entry_addr, (*task).user_arg0) };
+  let (user_entry_addr, arg0, env) =
+    unsafe { ((*task).user_entry_addr, (*task).user_arg0, (*task).user_env) };
 
   // SAFETY: `user_entry_addr` was built from a valid
   // `extern "C-unwind" fn(u64)` pointer; the arm64 ABI
   // makes reading X0 as `u64` safe for any return
   // width the callee emits (see `task_shim`).
-  let user_entry: extern "C-unwind" fn(u64) -> u64 = unsafe {
-    std::mem::transmute::<*const (), extern "C-unwind" fn(u64) -> u64>(
+  let user_entry: extern "C-unwind" fn(u64, u64) -> u64 = unsafe {
+    std::mem::transmute::<*const (), extern "C-unwind" fn(u64, u64) -> u64>(
       user_entry_addr as *const (),
     )
   };
 
-  let result = catch_unwind(AssertUnwindSafe(|| user_entry(arg0)));
+  let result = catch_unwind(AssertUnwindSafe(|| user_entry(arg0, env)));
 
   unsafe {
     match result {
@@ -473,6 +464,8 @@ extern "C-unwind" fn task_shim_1(task_addr: u64) {
         (*task).outcome = TaskOutcome::Panicked;
       }
     }
+
+    zo_closure_env_free(env);
   }
 
   exit_current();
@@ -481,21 +474,22 @@ extern "C-unwind" fn task_shim_1(task_addr: u64) {
 /// 2-arg shim — same pattern as `task_shim_1`.
 extern "C-unwind" fn task_shim_2(task_addr: u64) {
   let task = task_addr as *mut ZoTask;
-  let (user_entry_addr, arg0, arg1) = unsafe {
+  let (user_entry_addr, arg0, arg1, env) = unsafe {
     (
       (*task).user_entry_addr,
       (*task).user_arg0,
       (*task).user_arg1,
+      (*task).user_env,
     )
   };
 
-  let user_entry: extern "C-unwind" fn(u64, u64) -> u64 = unsafe {
-    std::mem::transmute::<*const (), extern "C-unwind" fn(u64, u64) -> u64>(
+  let user_entry: extern "C-unwind" fn(u64, u64, u64) -> u64 = unsafe {
+    std::mem::transmute::<*const (), extern "C-unwind" fn(u64, u64, u64) -> u64>(
       user_entry_addr as *const (),
     )
   };
 
-  let result = catch_unwind(AssertUnwindSafe(|| user_entry(arg0, arg1)));
+  let result = catch_unwind(AssertUnwindSafe(|| user_entry(arg0, arg1, env)));
 
   unsafe {
     match result {
@@ -507,6 +501,8 @@ extern "C-unwind" fn task_shim_2(task_addr: u64) {
         (*task).outcome = TaskOutcome::Panicked;
       }
     }
+
+    zo_closure_env_free(env);
   }
 
   exit_current();
@@ -515,22 +511,25 @@ extern "C-unwind" fn task_shim_2(task_addr: u64) {
 /// 3-arg shim — same pattern as `task_shim_1`.
 extern "C-unwind" fn task_shim_3(task_addr: u64) {
   let task = task_addr as *mut ZoTask;
-  let (user_entry_addr, arg0, arg1, arg2) = unsafe {
+  let (user_entry_addr, arg0, arg1, arg2, env) = unsafe {
     (
       (*task).user_entry_addr,
       (*task).user_arg0,
       (*task).user_arg1,
       (*task).user_arg2,
+      (*task).user_env,
     )
   };
 
-  let user_entry: extern "C-unwind" fn(u64, u64, u64) -> u64 = unsafe {
-    std::mem::transmute::<*const (), extern "C-unwind" fn(u64, u64, u64) -> u64>(
-      user_entry_addr as *const (),
-    )
+  let user_entry: extern "C-unwind" fn(u64, u64, u64, u64) -> u64 = unsafe {
+    std::mem::transmute::<
+      *const (),
+      extern "C-unwind" fn(u64, u64, u64, u64) -> u64,
+    >(user_entry_addr as *const ())
   };
 
-  let result = catch_unwind(AssertUnwindSafe(|| user_entry(arg0, arg1, arg2)));
+  let result =
+    catch_unwind(AssertUnwindSafe(|| user_entry(arg0, arg1, arg2, env)));
 
   unsafe {
     match result {
@@ -542,6 +541,8 @@ extern "C-unwind" fn task_shim_3(task_addr: u64) {
         (*task).outcome = TaskOutcome::Panicked;
       }
     }
+
+    zo_closure_env_free(env);
   }
 
   exit_current();
@@ -616,7 +617,7 @@ fn exit_current() -> ! {
 /// returned handle is consumed by [`await_task`] or
 /// equivalent.
 pub unsafe fn spawn(user_entry: extern "C-unwind" fn()) -> *mut ZoTask {
-  let task = Box::into_raw(ZoTask::new_green(user_entry));
+  let task = Box::into_raw(ZoTask::new_green(user_entry, 0));
 
   scheduler::with(|s| s.enqueue(task));
 
@@ -635,7 +636,7 @@ pub unsafe fn spawn_1(
   user_entry: extern "C-unwind" fn(u64),
   arg0: u64,
 ) -> *mut ZoTask {
-  let task = Box::into_raw(ZoTask::new_green_1(user_entry, arg0));
+  let task = Box::into_raw(ZoTask::new_green_1(user_entry, arg0, 0));
 
   scheduler::with(|s| s.enqueue(task));
 
@@ -653,7 +654,7 @@ pub unsafe fn spawn_2(
   arg0: u64,
   arg1: u64,
 ) -> *mut ZoTask {
-  let task = Box::into_raw(ZoTask::new_green_2(user_entry, arg0, arg1));
+  let task = Box::into_raw(ZoTask::new_green_2(user_entry, arg0, arg1, 0));
 
   scheduler::with(|s| s.enqueue(task));
 
@@ -672,7 +673,8 @@ pub unsafe fn spawn_3(
   arg1: u64,
   arg2: u64,
 ) -> *mut ZoTask {
-  let task = Box::into_raw(ZoTask::new_green_3(user_entry, arg0, arg1, arg2));
+  let task =
+    Box::into_raw(ZoTask::new_green_3(user_entry, arg0, arg1, arg2, 0));
 
   scheduler::with(|s| s.enqueue(task));
 
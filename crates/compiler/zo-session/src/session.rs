@@ -4,6 +4,7 @@ use zo_ty_checker::TyChecker;
 /// Shared compilation state that lives for the entire compilation pipeline.
 /// Owns the interner (symbol table) and type checker so all stages —
 /// tokenizer, parser, executor, codegen — share a single namespace.
+#[derive(Clone)]
 pub struct Session {
   pub interner: Interner,
   pub ty_checker: TyChecker,
@@ -0,0 +1,6 @@
+mod parser;
+
+#[cfg(test)]
+mod tests;
+
+pub use parser::SirParser;
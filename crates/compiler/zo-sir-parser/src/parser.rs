@@ -0,0 +1,1654 @@
+//! Textual SIR — reads back what `--emit=sir` prints.
+//!
+//! The pretty printer writes one instruction per line, every
+//! field a backend consumes spelled out and every type written
+//! by name (`i32`, `[]str`, `(i32, bool)`, `Fn(i32) -> unit`,
+//! `&mut Point`, `Tx<i32>`). This parser is its inverse: a dump
+//! parses into a [`Sir`] that prints back byte for byte, so a
+//! backend can be exercised from a hand-edited `.sir` fixture
+//! without going through the front end.
+//!
+//! - `@name(a: T, ..) -> R [pub] [test] [intrinsic] [closure(N)]
+//!   [self(read|write|own)] [link(sym)]:` opens a function.
+//! - `%N = <mnemonic> ..` defines a value, `L<N>:` a label.
+//! - `!base %N hex` / `!elem %N : T` restore the side tables.
+//! - `-- ..` is a comment, blank lines are skipped.
+//!
+//! Symbols are interned into the caller's [`Interner`]; types
+//! resolve through the caller's [`TyChecker`]. `struct_def` /
+//! `enum_def` lines are registered before anything else parses,
+//! so a type may be named before its definition line.
+//!
+//! Spans point at each instruction's line in the `.sir` source
+//! and `body_start` is recomputed from the `FunDef` position.
+//! Templates, stylesheets and `link` blocks have no textual
+//! form and are rejected.
+
+use zo_error::{Error, ErrorKind};
+use zo_interner::{Interner, Symbol};
+use zo_sir::{
+  BinOp, ImportKind, Insn, LoadSource, NurseryKind, Sir, SpawnKind, UnOp,
+};
+use zo_span::Span;
+use zo_token::Base;
+use zo_ty::{FloatWidth, IntWidth, Mutability, SelfKind, Ty, TyId};
+use zo_ty_checker::TyChecker;
+use zo_value::{EnvSlot, FunctionKind, Pubness, ValueId};
+
+/// The title line `format_sir` opens a dump with.
+const HEADER: &str = "SIR INSTRUCTION STREAM:";
+
+/// Represents a [`SirParser`] instance.
+pub struct SirParser<'a> {
+  /// The textual SIR.
+  source: &'a str,
+  /// Where names and string constants are interned.
+  interner: &'a mut Interner,
+  /// Where type names resolve.
+  ty: &'a mut TyChecker,
+  /// One past the highest value id seen.
+  next_value_id: u32,
+  /// One past the highest label id seen.
+  next_label_id: u32,
+}
+
+impl<'a> SirParser<'a> {
+  /// Creates a new [`SirParser`] instance.
+  pub fn new(
+    source: &'a str,
+    interner: &'a mut Interner,
+    ty: &'a mut TyChecker,
+  ) -> Self {
+    Self {
+      source,
+      interner,
+      ty,
+      next_value_id: 0,
+      next_label_id: 0,
+    }
+  }
+
+  /// Parses the source into a [`Sir`], or the first line that
+  /// does not follow the grammar.
+  pub fn parse(mut self) -> Result<Sir, Error> {
+    let source = self.source;
+    let lines = Self::lines(source);
+
+    self.declare_types(&lines);
+
+    let mut sir = Sir::new();
+
+    for &(start, text) in &lines {
+      let mut line = Line::new(text, start);
+
+      if line.eat("!") {
+        self.side_table(&mut line, &mut sir)?;
+        line.finish()?;
+
+        continue;
+      }
+
+      let insn = self.insn(&mut line)?;
+
+      line.finish()?;
+
+      sir.instructions.push(insn);
+      sir.spans.push(line.whole());
+    }
+
+    for (idx, insn) in sir.instructions.iter_mut().enumerate() {
+      if let Insn::FunDef {
+        kind, body_start, ..
+      } = insn
+      {
+        // Intrinsics have no body — `0` is the sentinel the
+        // backends expect.
+        *body_start = match kind {
+          FunctionKind::Intrinsic => 0,
+          _ => idx as u32 + 1,
+        };
+      }
+    }
+
+    sir.next_value_id = self.next_value_id;
+    sir.next_label_id = self.next_label_id;
+
+    Ok(sir)
+  }
+
+  /// The meaningful lines of `source` with their byte offsets,
+  /// trimmed — the dump header, comments and blanks dropped.
+  fn lines(source: &str) -> Vec<(u32, &str)> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+
+    for raw in source.split_inclusive('\n') {
+      let text = raw.trim_end();
+      let trimmed = text.trim_start();
+      let start = offset + (text.len() - trimmed.len());
+
+      offset += raw.len();
+
+      if trimmed.is_empty()
+        || trimmed.starts_with("--")
+        || trimmed == HEADER
+        || trimmed.chars().all(|c| c == '─')
+      {
+        continue;
+      }
+
+      lines.push((start as u32, trimmed));
+    }
+
+    lines
+  }
+
+  /// Registers every `struct_def` / `enum_def` name up front so
+  /// types resolve regardless of line order. Malformed lines
+  /// are left for the main pass to report.
+  fn declare_types(&mut self, lines: &[(u32, &str)]) {
+    for &(start, text) in lines {
+      let mut line = Line::new(text, start);
+
+      line.eat_word("pub");
+
+      let is_struct = line.eat_word("struct_def");
+      let is_enum = !is_struct && line.eat_word("enum_def");
+
+      if !(is_struct || is_enum) {
+        continue;
+      }
+
+      let Ok(name) = self.name(&mut line) else {
+        continue;
+      };
+
+      if is_struct {
+        self.ty.ty_table.register_forward_struct(name);
+      } else {
+        self.ty.ty_table.intern_enum(name, &[]);
+      }
+    }
+  }
+
+  /// Parses one `!base` / `!elem` side-table line.
+  fn side_table(
+    &mut self,
+    line: &mut Line,
+    sir: &mut Sir,
+  ) -> Result<(), Error> {
+    if line.eat_word("base") {
+      let value = self.value(line)?;
+      let base = match line.word() {
+        Some("bin") => Base::Binary,
+        Some("oct") => Base::Octal,
+        Some("dec") => Base::Decimal,
+        Some("hex") => Base::Hexadecimal,
+        _ => return Err(line.error()),
+      };
+
+      sir.int_bases.insert(value.0, base);
+
+      Ok(())
+    } else if line.eat_word("elem") {
+      let value = self.value(line)?;
+
+      line.expect(":")?;
+
+      let elem_ty = self.ty(line)?;
+
+      sir.vec_elem_tys.insert(value.0, elem_ty);
+
+      Ok(())
+    } else {
+      Err(line.error())
+    }
+  }
+
+  /// Parses one instruction line.
+  fn insn(&mut self, line: &mut Line) -> Result<Insn, Error> {
+    if line.eat("@") {
+      return self.fun_def(line);
+    }
+
+    if line.eat("#") {
+      let name = self.name(line)?;
+      let value = self.value(line)?;
+      let ty_id = self.typed(line)?;
+
+      return Ok(Insn::Directive { name, value, ty_id });
+    }
+
+    if line.peek() == Some('%') {
+      let dst = self.value(line)?;
+
+      line.expect("=")?;
+
+      return self.value_insn(line, dst);
+    }
+
+    let pubness = match line.eat_word("pub") {
+      true => Pubness::Yes,
+      false => Pubness::No,
+    };
+
+    let mnemonic = line.mnemonic().ok_or_else(|| line.error())?;
+
+    match mnemonic.as_str() {
+      "imu" | "mut" => {
+        let mutability = match mnemonic.as_str() {
+          "imu" => Mutability::No,
+          _ => Mutability::Yes,
+        };
+        let name = self.name(line)?;
+        let ty_id = self.typed(line)?;
+
+        line.expect("=")?;
+
+        let init = match line.eat_word("undef") {
+          true => None,
+          false => Some(self.value(line)?),
+        };
+
+        Ok(Insn::VarDef {
+          name,
+          ty_id,
+          init,
+          mutability,
+          pubness,
+        })
+      }
+      "val" => {
+        let name = self.name(line)?;
+        let ty_id = self.typed(line)?;
+
+        line.expect("=")?;
+
+        let value = self.value(line)?;
+
+        Ok(Insn::ConstDef {
+          name,
+          ty_id,
+          value,
+          pubness,
+        })
+      }
+      "load" => self.module_load(line, pubness),
+      "pack" => Ok(Insn::PackDecl {
+        name: self.name(line)?,
+        pubness,
+      }),
+      "struct_def" => self.struct_def(line, pubness),
+      "enum_def" => self.enum_def(line, pubness),
+      _ if pubness == Pubness::Yes => Err(line.error()),
+      "ret" => {
+        let value = match line.eat_word("void") {
+          true => None,
+          false => Some(self.value(line)?),
+        };
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::Return { value, ty_id })
+      }
+      "jmp" => Ok(Insn::Jump {
+        target: self.label(line)?,
+      }),
+      "br_ifnot" => {
+        let cond = self.value(line)?;
+
+        line.expect(",")?;
+
+        let target = self.label(line)?;
+
+        Ok(Insn::BranchIfNot { cond, target })
+      }
+      "store" => {
+        let name = self.name(line)?;
+
+        line.expect(",")?;
+
+        let value = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::Store { name, value, ty_id })
+      }
+      "drop" => {
+        let local = self.name(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::Drop { local, ty_id })
+      }
+      "push" => {
+        let array = self.value(line)?;
+
+        line.expect(",")?;
+
+        let value = self.value(line)?;
+        let ty_id = self.typed(line)?;
+        let owner = self.owner(line)?;
+
+        Ok(Insn::ArrayPush {
+          array,
+          value,
+          ty_id,
+          owner,
+        })
+      }
+      "store_field" => {
+        let base = self.value(line)?;
+
+        line.expect(".")?;
+
+        let index = line.uint()?;
+
+        line.expect(",")?;
+
+        let value = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::FieldStore {
+          base,
+          index,
+          value,
+          ty_id,
+        })
+      }
+      "array_store" => {
+        let array = self.value(line)?;
+
+        line.expect("[")?;
+
+        let index = self.value(line)?;
+
+        line.expect("]")?;
+        line.expect("=")?;
+
+        let value = self.value(line)?;
+        let ty_id = self.typed(line)?;
+        let owner = self.owner(line)?;
+
+        Ok(Insn::ArrayStore {
+          array,
+          index,
+          value,
+          ty_id,
+          owner,
+        })
+      }
+      "arr_ty_def" => {
+        let array_ty = self.typed(line)?;
+
+        line.expect("->")?;
+        line.expect("[")?;
+
+        let size = match line.eat("]") {
+          true => None,
+          false => {
+            let size = line.uint()?;
+
+            line.expect("]")?;
+
+            Some(size)
+          }
+        };
+
+        line.expect_word("elem")?;
+
+        let elem_ty = self.ty(line)?;
+
+        Ok(Insn::ArrayTyDef {
+          array_ty,
+          elem_ty,
+          size,
+        })
+      }
+      "map_ty_def" => {
+        let map_ty = self.typed(line)?;
+
+        line.expect("->")?;
+
+        let key_fmt = line.field("key_fmt")?;
+        let val_fmt = line.field("val_fmt")?;
+
+        Ok(Insn::MapTyDef {
+          map_ty,
+          key_fmt,
+          val_fmt,
+        })
+      }
+      "vec_ty_def" => {
+        let vec_ty = self.typed(line)?;
+
+        line.expect("->")?;
+
+        let elem_fmt = line.field("elem_fmt")?;
+
+        Ok(Insn::VecTyDef { vec_ty, elem_fmt })
+      }
+      "set_ty_def" => {
+        let set_ty = self.typed(line)?;
+
+        line.expect("->")?;
+
+        let key_fmt = line.field("key_fmt")?;
+
+        Ok(Insn::SetTyDef { set_ty, key_fmt })
+      }
+      "chan.send" => {
+        let channel = self.value(line)?;
+
+        line.expect(",")?;
+
+        let value = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::ChannelSend {
+          channel,
+          value,
+          ty_id,
+        })
+      }
+      "chan.close" => Ok(Insn::ChannelClose {
+        channel: self.value(line)?,
+      }),
+      "closure.free" => Ok(Insn::ClosureEnvFree {
+        env: self.value(line)?,
+      }),
+      "nursery.begin" | "supervise.begin" => {
+        let kind = match mnemonic.as_str() {
+          "nursery.begin" => NurseryKind::Scoped,
+          _ => NurseryKind::Supervised,
+        };
+        let label = self.label(line)?;
+
+        Ok(Insn::NurseryBegin { label, kind })
+      }
+      "nursery.end" => Ok(Insn::NurseryEnd {
+        label: self.label(line)?,
+      }),
+      "task.cancel" => Ok(Insn::TaskCancel {
+        task: self.value(line)?,
+      }),
+      "test.begin" => Ok(Insn::TestBegin {
+        count: line.field("count")?,
+      }),
+      "test.run" => {
+        let (callee_pack, callee) = self.qualified(line)?;
+
+        Ok(Insn::TestRun {
+          callee,
+          callee_pack,
+        })
+      }
+      "test.summary" => Ok(Insn::TestSummary),
+      label if label.starts_with('L') && line.eat(":") => {
+        let id: u32 = label[1..].parse().map_err(|_| line.error())?;
+
+        self.next_label_id = self.next_label_id.max(id.saturating_add(1));
+
+        Ok(Insn::Label { id })
+      }
+      _ => Err(line.error()),
+    }
+  }
+
+  /// Parses the right-hand side of `%dst = ..`.
+  fn value_insn(
+    &mut self,
+    line: &mut Line,
+    dst: ValueId,
+  ) -> Result<Insn, Error> {
+    let mnemonic = line.mnemonic().ok_or_else(|| line.error())?;
+
+    if let Some(op) = binop(&mnemonic) {
+      let lhs = self.value(line)?;
+
+      line.expect(",")?;
+
+      let rhs = self.value(line)?;
+      let ty_id = self.typed(line)?;
+
+      return Ok(Insn::BinOp {
+        dst,
+        op,
+        lhs,
+        rhs,
+        ty_id,
+      });
+    }
+
+    if let Some(op) = unop(&mnemonic) {
+      let rhs = self.value(line)?;
+      let ty_id = self.typed(line)?;
+
+      return Ok(Insn::UnOp {
+        dst,
+        op,
+        rhs,
+        ty_id,
+      });
+    }
+
+    match mnemonic.as_str() {
+      "const" => self.constant(line, dst),
+      "load" => {
+        let src = if line.eat_word("param") {
+          line.expect("[")?;
+
+          let idx = line.uint()?;
+
+          line.expect("]")?;
+
+          LoadSource::Param(idx)
+        } else {
+          line.expect_word("local")?;
+          line.expect("[")?;
+
+          let name = self.name(line)?;
+
+          line.expect("]")?;
+
+          LoadSource::Local(name)
+        };
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::Load { dst, src, ty_id })
+      }
+      "call" => {
+        let (callee_pack, name) = self.qualified(line)?;
+        let args = self.values(line, "(", ")")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::Call {
+          dst,
+          name,
+          callee_pack,
+          args,
+          ty_id,
+        })
+      }
+      "call_indirect" => {
+        let callee = self.value(line)?;
+        let args = self.values(line, "(", ")")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::CallIndirect {
+          dst,
+          callee,
+          args,
+          ty_id,
+        })
+      }
+      "array" => {
+        let elements = self.values(line, "[", "]")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::ArrayLiteral {
+          dst,
+          elements,
+          ty_id,
+        })
+      }
+      "index" => {
+        let array = self.value(line)?;
+
+        line.expect("[")?;
+
+        let index = self.value(line)?;
+
+        line.expect("]")?;
+
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::ArrayIndex {
+          dst,
+          array,
+          index,
+          ty_id,
+        })
+      }
+      "len" => {
+        let array = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::ArrayLen { dst, array, ty_id })
+      }
+      "pop" => {
+        let array = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::ArrayPop { dst, array, ty_id })
+      }
+      "tuple" => {
+        let elements = self.values(line, "(", ")")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::TupleLiteral {
+          dst,
+          elements,
+          ty_id,
+        })
+      }
+      "field" => {
+        let tuple = self.value(line)?;
+
+        line.expect(".")?;
+
+        let index = line.uint()?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::TupleIndex {
+          dst,
+          tuple,
+          index,
+          ty_id,
+        })
+      }
+      "enum" => {
+        let enum_name = self.name(line)?;
+
+        line.expect("::")?;
+
+        let variant = line.uint()?;
+        let fields = match line.peek() {
+          Some('(') => self.values(line, "(", ")")?,
+          _ => Vec::new(),
+        };
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::EnumConstruct {
+          dst,
+          enum_name,
+          variant,
+          fields,
+          ty_id,
+        })
+      }
+      "struct" => {
+        let struct_name = self.name(line)?;
+        let fields = self.values(line, "{", "}")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::StructConstruct {
+          dst,
+          struct_name,
+          fields,
+          ty_id,
+        })
+      }
+      "cast" => {
+        let src = self.value(line)?;
+
+        line.expect("(")?;
+
+        let from_ty = self.ty(line)?;
+
+        line.expect("->")?;
+
+        let to_ty = self.ty(line)?;
+
+        line.expect(")")?;
+
+        Ok(Insn::Cast {
+          dst,
+          src,
+          from_ty,
+          to_ty,
+        })
+      }
+      "chan.new" => {
+        let capacity = line.field("cap")?;
+        let elem_ty = self.typed(line)?;
+
+        Ok(Insn::ChannelCreate {
+          dst,
+          elem_ty,
+          capacity,
+        })
+      }
+      "chan.recv" => {
+        let channel = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::ChannelRecv {
+          dst,
+          channel,
+          ty_id,
+        })
+      }
+      "task.spawn" | "task.spawn_thread" => {
+        let kind = match mnemonic.as_str() {
+          "task.spawn" => SpawnKind::Green,
+          _ => SpawnKind::Thread,
+        };
+        let (callee_pack, callee) = self.qualified(line)?;
+        let args = self.values(line, "(", ")")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::TaskSpawn {
+          dst,
+          callee,
+          callee_pack,
+          args,
+          ty_id,
+          kind,
+        })
+      }
+      "task.await" => {
+        let task = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::TaskAwait { dst, task, ty_id })
+      }
+      "task.cancelled" => {
+        let task = self.value(line)?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::TaskCancelled { dst, task, ty_id })
+      }
+      "fn.addr" => {
+        let (callee_pack, callee) = self.qualified(line)?;
+
+        Ok(Insn::FnAddr {
+          dst,
+          callee,
+          callee_pack,
+        })
+      }
+      "closure.env" => {
+        let drop = self.value(line)?;
+        let captures = self.values(line, "[", "]")?;
+
+        Ok(Insn::ClosureEnv {
+          dst,
+          drop,
+          captures,
+        })
+      }
+      "select.wait" => {
+        let chans = self.values(line, "[", "]")?;
+        let elem_ty = self.typed(line)?;
+
+        Ok(Insn::SelectWait {
+          out_which: dst,
+          chans,
+          elem_ty,
+        })
+      }
+      "select.recv" => {
+        let which = self.value(line)?;
+
+        line.expect(",")?;
+
+        let chans_len = line.field("chans")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::SelectRecv {
+          dst,
+          which,
+          ty_id,
+          chans_len,
+        })
+      }
+      "str.slice" => {
+        let src = self.value(line)?;
+
+        line.expect("[")?;
+
+        let lo = self.value(line)?;
+
+        line.expect("..")?;
+
+        let hi = self.value(line)?;
+
+        line.expect("]")?;
+
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::StrSlice {
+          dst,
+          src,
+          lo,
+          hi,
+          ty_id,
+        })
+      }
+      "to_str" => {
+        let src = self.value(line)?;
+        let src_ty = self.typed(line)?;
+
+        Ok(Insn::ToStr { dst, src, src_ty })
+      }
+      "string.format" => {
+        let segments = self.values(line, "[", "]")?;
+        let ty_id = self.typed(line)?;
+
+        Ok(Insn::StringFormat {
+          dst,
+          segments,
+          ty_id,
+        })
+      }
+      "coerce_to_dyn" => {
+        let src = self.value(line)?;
+
+        line.expect(":")?;
+        line.expect_word("any")?;
+
+        let abstract_name = self.name(line)?;
+
+        line.expect_word("from")?;
+
+        let concrete_ty = self.ty(line)?;
+
+        Ok(Insn::CoerceToDyn {
+          dst,
+          src,
+          abstract_name,
+          concrete_ty,
+        })
+      }
+      "dyn_dispatch" => {
+        let recv = self.value(line)?;
+
+        line.expect(".")?;
+
+        let method_name = self.name(line)?;
+
+        line.expect("[")?;
+        line.expect("#")?;
+
+        let method_index = line.uint()?;
+
+        line.expect("]")?;
+
+        let args = self.values(line, "(", ")")?;
+        let ty_id = self.typed(line)?;
+
+        line.expect_word("via")?;
+
+        let abstract_name = self.name(line)?;
+
+        Ok(Insn::DynDispatch {
+          dst,
+          recv,
+          method_index,
+          abstract_name,
+          method_name,
+          args,
+          ty_id,
+        })
+      }
+      _ => Err(line.error()),
+    }
+  }
+
+  /// `@[pack::]name(a: T, ..) -> R attrs:`.
+  fn fun_def(&mut self, line: &mut Line) -> Result<Insn, Error> {
+    let (owning_pack, name) = self.qualified(line)?;
+    let mut params = Vec::new();
+
+    line.expect("(")?;
+
+    while !line.eat(")") {
+      if !params.is_empty() {
+        line.expect(",")?;
+      }
+
+      let param = self.name(line)?;
+      let ty_id = self.typed(line)?;
+
+      params.push((param, ty_id));
+    }
+
+    line.expect("->")?;
+
+    let return_ty = self.ty(line)?;
+    let mut pubness = Pubness::No;
+    let mut is_test = false;
+    let mut kind = FunctionKind::UserDefined;
+    let mut self_kind = SelfKind::None;
+    let mut link_name = None;
+    let mut env_slot = None;
+
+    while !line.eat(":") {
+      match line.word() {
+        Some("pub") => pubness = Pubness::Yes,
+        Some("test") => is_test = true,
+        Some("intrinsic") => kind = FunctionKind::Intrinsic,
+        Some("closure") => {
+          line.expect("(")?;
+
+          let capture_count = line.uint()?;
+
+          line.expect(")")?;
+
+          kind = FunctionKind::Closure { capture_count };
+        }
+        Some("self") => {
+          line.expect("(")?;
+
+          self_kind = match line.word() {
+            Some("read") => SelfKind::Read,
+            Some("write") => SelfKind::Write,
+            Some("own") => SelfKind::Consume,
+            _ => return Err(line.error()),
+          };
+
+          line.expect(")")?;
+        }
+        Some("link") => {
+          line.expect("(")?;
+
+          link_name = Some(self.name(line)?);
+
+          line.expect(")")?;
+        }
+        Some("user_data") => {
+          line.expect("(")?;
+
+          let param = line.uint()?;
+          let owned = line.eat(",");
+
+          if owned {
+            line.expect_word("own")?;
+          }
+
+          line.expect(")")?;
+
+          env_slot = Some(EnvSlot { param, owned });
+        }
+        _ => return Err(line.error()),
+      }
+    }
+
+    Ok(Insn::FunDef {
+      name,
+      params,
+      return_ty,
+      body_start: 0,
+      kind,
+      pubness,
+      self_kind,
+      link_name,
+      owning_pack,
+      span: line.whole(),
+      is_test,
+      env_slot,
+    })
+  }
+
+  /// `const <literal> : T` — the literal's shape picks the
+  /// instruction, a float type turns a bare number into a
+  /// `ConstFloat`.
+  fn constant(&mut self, line: &mut Line, dst: ValueId) -> Result<Insn, Error> {
+    if line.peek() == Some('"') {
+      let text = line.string()?;
+      let symbol = self.interner.intern(&text);
+      let ty_id = self.typed(line)?;
+
+      return Ok(Insn::ConstString { dst, symbol, ty_id });
+    }
+
+    if let Some(value) = line.eat_bool() {
+      let ty_id = self.typed(line)?;
+
+      return Ok(Insn::ConstBool { dst, value, ty_id });
+    }
+
+    let literal = line.number().ok_or_else(|| line.error())?;
+    let ty_id = self.typed(line)?;
+
+    if matches!(self.ty.kind_of_ro(ty_id), Ty::Float(_)) {
+      let value = literal.parse().map_err(|_| line.error())?;
+
+      Ok(Insn::ConstFloat { dst, value, ty_id })
+    } else {
+      let value = literal.parse().map_err(|_| line.error())?;
+
+      Ok(Insn::ConstInt { dst, value, ty_id })
+    }
+  }
+
+  /// `load a::b`, `load a::b::*` or `load a::b::(x, y)`.
+  fn module_load(
+    &mut self,
+    line: &mut Line,
+    pubness: Pubness,
+  ) -> Result<Insn, Error> {
+    let mut path = vec![self.name(line)?];
+    let mut kind = ImportKind::Qualified;
+
+    while line.eat("::") {
+      if line.eat("*") {
+        kind = ImportKind::Glob;
+
+        break;
+      }
+
+      if line.eat("(") {
+        let mut items = Vec::new();
+
+        while !line.eat(")") {
+          if !items.is_empty() {
+            line.expect(",")?;
+          }
+
+          items.push(self.name(line)?);
+        }
+
+        kind = ImportKind::Selective(items);
+
+        break;
+      }
+
+      path.push(self.name(line)?);
+    }
+
+    Ok(Insn::ModuleLoad {
+      path,
+      kind,
+      pubness,
+    })
+  }
+
+  /// `struct_def Name { a: T, b: T default }`.
+  fn struct_def(
+    &mut self,
+    line: &mut Line,
+    pubness: Pubness,
+  ) -> Result<Insn, Error> {
+    let name = self.name(line)?;
+    let mut fields = Vec::new();
+
+    line.expect("{")?;
+
+    while !line.eat("}") {
+      if !fields.is_empty() {
+        line.expect(",")?;
+      }
+
+      let field = self.name(line)?;
+      let ty_id = self.typed(line)?;
+      let has_default = line.eat_word("default");
+
+      fields.push((field, ty_id, has_default));
+    }
+
+    let sid = self.ty.ty_table.intern_struct(name, &fields);
+    let ty_id = self.ty.intern_ty(Ty::Struct(sid));
+
+    Ok(Insn::StructDef {
+      name,
+      ty_id,
+      fields,
+      pubness,
+    })
+  }
+
+  /// `enum_def Name { A = 0, B = 1(T, ..) }`.
+  fn enum_def(
+    &mut self,
+    line: &mut Line,
+    pubness: Pubness,
+  ) -> Result<Insn, Error> {
+    let name = self.name(line)?;
+    let mut variants = Vec::new();
+
+    line.expect("{")?;
+
+    while !line.eat("}") {
+      if !variants.is_empty() {
+        line.expect(",")?;
+      }
+
+      let variant = self.name(line)?;
+
+      line.expect("=")?;
+
+      let discriminant = line.uint()?;
+      let mut fields = Vec::new();
+
+      if line.eat("(") {
+        while !line.eat(")") {
+          if !fields.is_empty() {
+            line.expect(",")?;
+          }
+
+          fields.push(self.ty(line)?);
+        }
+      }
+
+      variants.push((variant, discriminant, fields));
+    }
+
+    let eid = self.ty.ty_table.intern_enum(name, &variants);
+    let ty_id = self.ty.intern_ty(Ty::Enum(eid));
+
+    Ok(Insn::EnumDef {
+      name,
+      ty_id,
+      variants,
+      pubness,
+    })
+  }
+
+  /// A type name: primitives, `[]T`, `[N]T`, `(T, ..)`,
+  /// `Fn(T, ..) -> R`, `&T`, `&mut T`, `Tx<T>`, `Rx<T>`,
+  /// `Task<T>`, `$T`, `any Name`, or a declared struct / enum.
+  fn ty(&mut self, line: &mut Line) -> Result<TyId, Error> {
+    line.skip_ws();
+
+    let start = line.pos;
+
+    if line.eat("[") {
+      let size = match line.eat("]") {
+        true => None,
+        false => {
+          let size = line.uint()?;
+
+          line.expect("]")?;
+
+          Some(size)
+        }
+      };
+      let elem_ty = self.ty(line)?;
+      let aid = self.ty.ty_table.intern_array(elem_ty, size);
+
+      return Ok(self.ty.intern_ty(Ty::Array(aid)));
+    }
+
+    if line.eat("(") {
+      let elems = self.tys(line, ")")?;
+      let tid = self.ty.ty_table.intern_tuple(elems);
+
+      return Ok(self.ty.intern_ty(Ty::Tuple(tid)));
+    }
+
+    if line.eat("&") {
+      let mutability = match line.eat_word("mut") {
+        true => Mutability::Yes,
+        false => Mutability::No,
+      };
+      let inner_ty = self.ty(line)?;
+      let rid = self.ty.ty_table.intern_ref(mutability, inner_ty);
+
+      return Ok(self.ty.intern_ty(Ty::Ref(rid)));
+    }
+
+    if line.eat("$") {
+      let name = self.name(line)?;
+
+      return Ok(self.ty.intern_ty(Ty::Param(name)));
+    }
+
+    if line.peek() == Some('"') {
+      let name = self.name(line)?;
+
+      return self.named_ty(line, name, start);
+    }
+
+    let word = line.word().ok_or_else(|| line.error())?;
+
+    let primitive = match word {
+      "i8" => Some(int(true, IntWidth::S8)),
+      "i16" => Some(int(true, IntWidth::S16)),
+      "i32" => Some(int(true, IntWidth::S32)),
+      "i64" => Some(int(true, IntWidth::S64)),
+      "isize" => Some(int(true, IntWidth::Arch)),
+      "u8" => Some(int(false, IntWidth::U8)),
+      "u16" => Some(int(false, IntWidth::U16)),
+      "u32" => Some(int(false, IntWidth::U32)),
+      "u64" => Some(int(false, IntWidth::U64)),
+      "usize" => Some(int(false, IntWidth::Arch)),
+      "f32" => Some(Ty::Float(FloatWidth::F32)),
+      "f64" => Some(Ty::Float(FloatWidth::F64)),
+      "fsize" => Some(Ty::Float(FloatWidth::Arch)),
+      "bool" => Some(Ty::Bool),
+      "char" => Some(Ty::Char),
+      "str" => Some(Ty::Str),
+      "bytes" => Some(Ty::Bytes),
+      "unit" => Some(Ty::Unit),
+      "Error" => Some(Ty::Error),
+      "Template" => Some(Ty::Template),
+      "Type" => Some(Ty::Type),
+      "Unknown" => Some(Ty::Unknown),
+      _ => None,
+    };
+
+    if let Some(primitive) = primitive {
+      return Ok(self.ty.intern_ty(primitive));
+    }
+
+    if word == "Fn" && line.eat("(") {
+      let params = self.tys(line, ")")?;
+
+      line.expect("->")?;
+
+      let return_ty = self.ty(line)?;
+      let fid = self.ty.ty_table.intern_fun(params, return_ty);
+
+      return Ok(self.ty.intern_ty(Ty::Fun(fid)));
+    }
+
+    if matches!(word, "Tx" | "Rx" | "Task") && line.eat("<") {
+      let inner = self.ty(line)?;
+
+      line.expect(">")?;
+
+      return Ok(match word {
+        "Tx" => self.ty.channel_tx_type(inner),
+        "Rx" => self.ty.channel_rx_type(inner),
+        _ => self.ty.task_type(inner),
+      });
+    }
+
+    if word == "any" {
+      let name = self.name(line)?;
+
+      return Ok(self.ty.intern_ty(Ty::Dyn(name)));
+    }
+
+    let name = self.interner.intern(word);
+
+    self.named_ty(line, name, start)
+  }
+
+  /// A struct or enum declared by the file.
+  fn named_ty(
+    &mut self,
+    line: &Line,
+    name: Symbol,
+    start: usize,
+  ) -> Result<TyId, Error> {
+    let table = &self.ty.ty_table;
+
+    let ty = if let Some(&sid) = table.struct_intern_lookup(name) {
+      Ty::Struct(sid)
+    } else if let Some(&eid) = table.enum_intern_lookup(name) {
+      Ty::Enum(eid)
+    } else {
+      return Err(Error::new(
+        ErrorKind::UnknownSirType,
+        line.span(start, line.pos),
+      ));
+    };
+
+    Ok(self.ty.intern_ty(ty))
+  }
+
+  /// Comma-separated types up to `close`.
+  fn tys(&mut self, line: &mut Line, close: &str) -> Result<Vec<TyId>, Error> {
+    let mut tys = Vec::new();
+
+    while !line.eat(close) {
+      if !tys.is_empty() {
+        line.expect(",")?;
+      }
+
+      tys.push(self.ty(line)?);
+    }
+
+    Ok(tys)
+  }
+
+  /// `: T`.
+  fn typed(&mut self, line: &mut Line) -> Result<TyId, Error> {
+    line.expect(":")?;
+
+    self.ty(line)
+  }
+
+  /// An optional trailing `owner name`.
+  fn owner(&mut self, line: &mut Line) -> Result<Option<Symbol>, Error> {
+    match line.eat_word("owner") {
+      true => Ok(Some(self.name(line)?)),
+      false => Ok(None),
+    }
+  }
+
+  /// A bare or quoted name, interned.
+  fn name(&mut self, line: &mut Line) -> Result<Symbol, Error> {
+    line.skip_ws();
+
+    let name = match line.peek() {
+      Some('"') => line.string()?,
+      _ => line.word().ok_or_else(|| line.error())?.to_string(),
+    };
+
+    Ok(self.interner.intern(&name))
+  }
+
+  /// `name` or `pack::name`.
+  fn qualified(
+    &mut self,
+    line: &mut Line,
+  ) -> Result<(Option<Symbol>, Symbol), Error> {
+    let first = self.name(line)?;
+
+    match line.eat("::") {
+      true => Ok((Some(first), self.name(line)?)),
+      false => Ok((None, first)),
+    }
+  }
+
+  /// `%N`.
+  fn value(&mut self, line: &mut Line) -> Result<ValueId, Error> {
+    line.expect("%")?;
+
+    let id: u32 = line.uint()?;
+
+    self.next_value_id = self.next_value_id.max(id.saturating_add(1));
+
+    Ok(ValueId(id))
+  }
+
+  /// Comma-separated values between `open` and `close`.
+  fn values(
+    &mut self,
+    line: &mut Line,
+    open: &str,
+    close: &str,
+  ) -> Result<Vec<ValueId>, Error> {
+    let mut values = Vec::new();
+
+    line.expect(open)?;
+
+    while !line.eat(close) {
+      if !values.is_empty() {
+        line.expect(",")?;
+      }
+
+      values.push(self.value(line)?);
+    }
+
+    Ok(values)
+  }
+
+  /// `L<N>`.
+  fn label(&mut self, line: &mut Line) -> Result<u32, Error> {
+    line.skip_ws();
+
+    let id = line
+      .word()
+      .and_then(|word| word.strip_prefix('L'))
+      .and_then(|id| id.parse::<u32>().ok())
+      .ok_or_else(|| line.error())?;
+
+    self.next_label_id = self.next_label_id.max(id.saturating_add(1));
+
+    Ok(id)
+  }
+}
+
+/// A cursor over one trimmed line.
+struct Line<'a> {
+  /// The line, trimmed.
+  text: &'a str,
+  /// Byte offset of `text` in the source.
+  base: u32,
+  /// Current byte offset in `text`.
+  pos: usize,
+}
+
+impl<'a> Line<'a> {
+  fn new(text: &'a str, base: u32) -> Self {
+    Self { text, base, pos: 0 }
+  }
+
+  fn rest(&self) -> &'a str {
+    &self.text[self.pos..]
+  }
+
+  fn skip_ws(&mut self) {
+    let rest = self.rest();
+
+    self.pos += rest.len() - rest.trim_start().len();
+  }
+
+  fn peek(&mut self) -> Option<char> {
+    self.skip_ws();
+    self.rest().chars().next()
+  }
+
+  /// Consumes the punctuation `token` when it comes next.
+  fn eat(&mut self, token: &str) -> bool {
+    self.skip_ws();
+
+    if self.rest().starts_with(token) {
+      self.pos += token.len();
+
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, token: &str) -> Result<(), Error> {
+    match self.eat(token) {
+      true => Ok(()),
+      false => Err(self.error()),
+    }
+  }
+
+  /// Consumes `word` when the next word is exactly it.
+  fn eat_word(&mut self, word: &str) -> bool {
+    let pos = self.pos;
+
+    match self.word() {
+      Some(next) if next == word => true,
+      _ => {
+        self.pos = pos;
+
+        false
+      }
+    }
+  }
+
+  fn expect_word(&mut self, word: &str) -> Result<(), Error> {
+    match self.eat_word(word) {
+      true => Ok(()),
+      false => Err(self.error()),
+    }
+  }
+
+  fn eat_bool(&mut self) -> Option<bool> {
+    if self.eat_word("true") {
+      Some(true)
+    } else if self.eat_word("false") {
+      Some(false)
+    } else {
+      None
+    }
+  }
+
+  /// An identifier-shaped word: letters, digits, `_` and `$`.
+  fn word(&mut self) -> Option<&'a str> {
+    self.skip_ws();
+
+    let rest = self.rest();
+    let len = rest
+      .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+      .unwrap_or(rest.len());
+
+    if len == 0 {
+      return None;
+    }
+
+    self.pos += len;
+
+    Some(&rest[..len])
+  }
+
+  /// A dotted mnemonic — `call`, `chan.new`, `task.spawn_thread`.
+  fn mnemonic(&mut self) -> Option<String> {
+    let mut mnemonic = self.word()?.to_string();
+
+    while self.rest().starts_with('.') {
+      self.pos += 1;
+
+      mnemonic.push('.');
+      mnemonic.push_str(self.word()?);
+    }
+
+    Some(mnemonic)
+  }
+
+  /// A numeric literal — everything up to the next blank.
+  fn number(&mut self) -> Option<&'a str> {
+    self.skip_ws();
+
+    let rest = self.rest();
+    let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+
+    if len == 0 {
+      return None;
+    }
+
+    self.pos += len;
+
+    Some(&rest[..len])
+  }
+
+  fn uint<T: std::str::FromStr>(&mut self) -> Result<T, Error> {
+    self.skip_ws();
+
+    let rest = self.rest();
+    let len = rest
+      .find(|c: char| !c.is_ascii_digit())
+      .unwrap_or(rest.len());
+
+    let value = rest[..len].parse().map_err(|_| self.error())?;
+
+    self.pos += len;
+
+    Ok(value)
+  }
+
+  /// `name=N`.
+  fn field<T: std::str::FromStr>(&mut self, name: &str) -> Result<T, Error> {
+    self.expect_word(name)?;
+    self.expect("=")?;
+    self.uint()
+  }
+
+  /// A double-quoted string with Rust escapes, as `{:?}` prints.
+  fn string(&mut self) -> Result<String, Error> {
+    self.expect("\"")?;
+
+    let start = self.pos;
+    let mut text = String::new();
+    let mut chars = self.rest().char_indices();
+
+    while let Some((i, c)) = chars.next() {
+      match c {
+        '"' => {
+          self.pos += i + 1;
+
+          return Ok(text);
+        }
+        '\\' => {
+          let escaped = match chars.next().map(|(_, c)| c) {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('\'') => '\'',
+            Some('u') => {
+              let digits = chars
+                .by_ref()
+                .map(|(_, c)| c)
+                .skip(1)
+                .take_while(|c| *c != '}')
+                .collect::<String>();
+
+              u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| self.error())?
+            }
+            _ => return Err(self.error()),
+          };
+
+          text.push(escaped);
+        }
+        c => text.push(c),
+      }
+    }
+
+    Err(Error::new(
+      ErrorKind::MalformedSir,
+      self.span(start, self.text.len()),
+    ))
+  }
+
+  /// Fails unless the whole line was consumed.
+  fn finish(&mut self) -> Result<(), Error> {
+    self.skip_ws();
+
+    match self.rest().is_empty() {
+      true => Ok(()),
+      false => Err(self.error()),
+    }
+  }
+
+  fn span(&self, start: usize, end: usize) -> Span {
+    let len = end.saturating_sub(start).min(u16::MAX as usize) as u16;
+
+    Span::new(self.base + start as u32, len)
+  }
+
+  /// The whole line.
+  fn whole(&self) -> Span {
+    self.span(0, self.text.len())
+  }
+
+  /// A `MalformedSir` error at the current word.
+  fn error(&self) -> Error {
+    let rest = self.rest();
+    let len = rest.find(char::is_whitespace).unwrap_or(rest.len()).max(1);
+    let end = (self.pos + len).min(self.text.len());
+
+    Error::new(ErrorKind::MalformedSir, self.span(self.pos, end))
+  }
+}
+
+fn int(signed: bool, width: IntWidth) -> Ty {
+  Ty::Int { signed, width }
+}
+
+fn binop(mnemonic: &str) -> Option<BinOp> {
+  Some(match mnemonic {
+    "add" => BinOp::Add,
+    "sub" => BinOp::Sub,
+    "mul" => BinOp::Mul,
+    "div" => BinOp::Div,
+    "rem" => BinOp::Rem,
+    "eq" => BinOp::Eq,
+    "neq" => BinOp::Neq,
+    "lt" => BinOp::Lt,
+    "lte" => BinOp::Lte,
+    "gt" => BinOp::Gt,
+    "gte" => BinOp::Gte,
+    "and" => BinOp::And,
+    "or" => BinOp::Or,
+    "bitand" => BinOp::BitAnd,
+    "bitor" => BinOp::BitOr,
+    "bitxor" => BinOp::BitXor,
+    "shl" => BinOp::Shl,
+    "shr" => BinOp::Shr,
+    "concat" => BinOp::Concat,
+    _ => return None,
+  })
+}
+
+fn unop(mnemonic: &str) -> Option<UnOp> {
+  Some(match mnemonic {
+    "neg" => UnOp::Neg,
+    "not" => UnOp::Not,
+    "bitnot" => UnOp::BitNot,
+    _ => return None,
+  })
+}
@@ -0,0 +1,273 @@
+use crate::SirParser;
+
+use zo_error::{Error, ErrorKind};
+use zo_executor::Executor;
+use zo_interner::Interner;
+use zo_parser::Parser;
+use zo_pp::PrettyPrinter;
+use zo_sir::{Insn, Sir};
+use zo_tokenizer::Tokenizer;
+use zo_ty_checker::TyChecker;
+
+use std::path::Path;
+
+fn print(sir: &Sir, interner: &Interner, ty: &TyChecker) -> String {
+  let mut pp = PrettyPrinter::new();
+
+  pp.format_sir(sir, interner, ty);
+
+  String::from_utf8(pp.finish()).unwrap()
+}
+
+fn parse(source: &str) -> Result<(Sir, Interner, TyChecker), Error> {
+  let mut interner = Interner::new();
+  let mut ty = TyChecker::new();
+  let sir = SirParser::new(source, &mut interner, &mut ty).parse()?;
+
+  Ok((sir, interner, ty))
+}
+
+/// Parses `text` and prints it back.
+fn reprint(text: &str) -> String {
+  let (sir, interner, ty) =
+    parse(text).unwrap_or_else(|error| panic!("{error:?} in:\n{text}"));
+
+  print(&sir, &interner, &ty)
+}
+
+/// Source → SIR → text → SIR → text: the two dumps match.
+fn assert_round_trip(source: &str) {
+  let mut interner = Interner::new();
+  let tokenization = Tokenizer::new(source, &mut interner).tokenize();
+  let parsing = Parser::new(&tokenization, source).parse();
+  let mut ty = TyChecker::new();
+  let sir = Executor::new(
+    &parsing.tree,
+    &mut interner,
+    &tokenization.literals,
+    &mut ty,
+  )
+  .execute()
+  .sir;
+
+  let text = print(&sir, &interner, &ty);
+
+  assert_eq!(reprint(&text), text);
+}
+
+fn error_kind(source: &str) -> ErrorKind {
+  match parse(source) {
+    Ok(_) => panic!("expected an error for:\n{source}"),
+    Err(error) => error.kind(),
+  }
+}
+
+#[test]
+fn handwritten_dump_round_trips_exactly() {
+  let text = r#"SIR INSTRUCTION STREAM:
+───────────────────────
+  pub struct_def Point { x: i32, y: i32 default }
+  enum_def Shape { Dot = 0, Circle = 1(f64), Rect = 2(Point, [2]f64) }
+@"Point::sum"(self: Point) -> i32 self(read):
+  %0 = load param[0] : Point
+  %1 = field %0.0 : i32
+  %2 = field %0.1 : i32
+  %3 = add %1, %2 : i32
+  ret %3 : i32
+
+@main() -> unit pub:
+  %4 = const 1 : i32
+  %5 = const 2.5 : f64
+  %6 = const true : bool
+  %7 = const "say \"hi\"\n" : str
+  %8 = struct Point { %4, %4 } : Point
+  %9 = call "Point::sum"(%8) : i32
+  %10 = enum Shape::1(%5) : Shape
+  mut total : i32 = %9
+  imu name : str = undef
+  store name, %7 : str
+  %11 = neg %4 : i32
+  %12 = lt %11, %9 : bool
+  %13 = cast %4 (i32 -> f64)
+  %14 = array [%4, %9] : []i32
+  %15 = index %14[%4] : i32
+  %16 = len %14 : u64
+  push %14, %15 : i32 owner total
+  array_store %14[%4] = %15 : i32
+  %17 = tuple (%4, %6) : (i32, bool)
+  store_field %8.1, %4 : i32
+  %18 = fn.addr "Point::sum"
+  %19 = call_indirect %18(%8) : i32
+  %20 = call io::showln(%7) : unit
+  %22 = const 0 : i64
+  %23 = closure.env %22 [%4, %9]
+  closure.free %23
+  L0:
+    br_ifnot %12, L1
+    jmp L0
+  L1:
+  drop total : i32
+  ret void : unit
+
+@add_one(x: i32) -> i32 closure(1):
+  %21 = load param[0] : i32
+  ret %21 : i32
+
+@puts(s: str) -> i32 intrinsic link(puts):
+
+@on_event(tag: i32, env: i64) -> unit intrinsic user_data(1):
+
+@hand_off(tag: i32, env: i64) -> unit intrinsic user_data(1, own):
+
+!base %4 hex
+!elem %9 : Point
+"#;
+
+  assert_eq!(reprint(text), text);
+}
+
+#[test]
+fn concurrency_dump_round_trips_exactly() {
+  let text = r#"SIR INSTRUCTION STREAM:
+───────────────────────
+@worker(n: i32) -> i32:
+  %0 = load param[0] : i32
+  ret %0 : i32
+
+@main() -> unit:
+  nursery.begin L0
+  %1 = chan.new cap=4 : i32
+  %2 = field %1.0 : Tx<i32>
+  %3 = field %1.1 : Rx<i32>
+  %4 = const 7 : i32
+  chan.send %2, %4 : i32
+  %5 = chan.recv %3 : i32
+  %6 = task.spawn worker(%5) : Task<i32>
+  %7 = task.await %6 : i32
+  %8 = select.wait [%3, %3] : i32
+  %9 = select.recv %8, chans=2 : i32
+  chan.close %2
+  nursery.end L0
+  ret void : unit
+
+"#;
+
+  assert_eq!(reprint(text), text);
+}
+
+#[test]
+fn executor_output_round_trips() {
+  assert_round_trip(
+    r#"
+fun fib(n: int) -> int {
+  if n < 2 { return n; }
+  fib(n - 1) + fib(n - 2)
+}
+
+fun main() {
+  mut total: int = 0;
+  for i := 0..10 {
+    total += fib(i);
+  }
+  showln(total);
+}
+"#,
+  );
+
+  assert_round_trip(
+    r#"
+struct Point { x: int, y: int }
+
+enum Shape { Dot, Circle(float) }
+
+fun main() {
+  imu p := Point { x: 1, y: 2 };
+  imu s := Shape::Circle(1.5);
+  imu xs: []int = [p.x, p.y];
+  imu greeting := "hello \"zo\"\n";
+  showln(xs[0]);
+  showln(greeting);
+}
+"#,
+  );
+}
+
+#[test]
+fn types_resolve_before_their_definition_line() {
+  let (sir, interner, ty) = parse(
+    "@main() -> unit:\n  imu p : Point = undef\n  ret void : unit\nstruct_def Point { x: i32 }\n",
+  )
+  .unwrap();
+
+  let Insn::VarDef { ty_id, .. } = &sir.instructions[1] else {
+    panic!("expected a VarDef");
+  };
+  let Insn::StructDef { ty_id: point, .. } = &sir.instructions[3] else {
+    panic!("expected a StructDef");
+  };
+
+  assert_eq!(ty_id, point);
+  assert!(print(&sir, &interner, &ty).contains("imu p : Point = undef"));
+}
+
+#[test]
+fn parser_recomputes_positions() {
+  let (sir, ..) =
+    parse("@main() -> unit:\n  %3 = const 1 : i32\nL4:\n  ret void : unit\n")
+      .unwrap();
+
+  assert!(matches!(
+    sir.instructions[0],
+    Insn::FunDef { body_start: 1, .. }
+  ));
+  assert_eq!(sir.next_value_id, 4);
+  assert_eq!(sir.next_label_id, 5);
+  assert_eq!(sir.spans.len(), sir.instructions.len());
+  // `%3 = const 1 : i32` starts at byte 19, past its indent.
+  assert_eq!(sir.spans[1].start, 19);
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+  assert_eq!(
+    error_kind("@main() -> unit:\n  %0 = frob %1\n"),
+    ErrorKind::MalformedSir
+  );
+  assert_eq!(
+    error_kind("@main() -> unit:\n  ret void : unit trailing\n"),
+    ErrorKind::MalformedSir
+  );
+  assert_eq!(
+    error_kind("@main() -> unit:\n  template #0 <fragment>\n"),
+    ErrorKind::MalformedSir
+  );
+  assert_eq!(
+    error_kind("@main() -> Nowhere:\n"),
+    ErrorKind::UnknownSirType
+  );
+}
+
+/// Every fixture under `zo-tests/programming/sir` parses, and
+/// its canonical form is a fixed point of parse + print.
+#[test]
+fn sir_fixtures_parse() {
+  let dir =
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../zo-tests/programming/sir");
+
+  let mut fixtures = std::fs::read_dir(&dir)
+    .unwrap()
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .filter(|path| path.extension().is_some_and(|ext| ext == "sir"))
+    .collect::<Vec<_>>();
+
+  fixtures.sort();
+
+  assert!(!fixtures.is_empty(), "no .sir fixtures in {dir:?}");
+
+  for fixture in fixtures {
+    let source = std::fs::read_to_string(&fixture).unwrap();
+    let canonical = reprint(&source);
+
+    assert_eq!(reprint(&canonical), canonical, "{fixture:?}");
+  }
+}
@@ -35,6 +35,7 @@ fn make_binop_stream(n: u32) -> Vec<Insn> {
     pubness: Pubness::No,
     self_kind: SelfKind::None,
     link_name: None,
+    env_slot: None,
     owning_pack: None,
     span: Span::ZERO,
     is_test: false,
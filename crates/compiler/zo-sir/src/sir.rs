@@ -5,7 +5,7 @@ use zo_ty::Mutability;
 use zo_ty::SelfKind;
 use zo_ty::TyId;
 use zo_ui_protocol::{Attr, StyleScope, UiCommand};
-use zo_value::{FunctionKind, Pubness, ValueId};
+use zo_value::{EnvSlot, FunctionKind, Pubness, ValueId};
 
 /// Reactive bindings carried by `Insn::Template`. Split by
 /// target kind so the runtime can dispatch patches without
@@ -527,6 +527,20 @@ impl Sir {
       }
     }
   }
+
+  /// Resets each `FunDef`'s `body_start` to the index right
+  /// after it. Every pass that drains or inserts instructions
+  /// (DCE, inlining, drop elaboration) calls this once it is
+  /// done — a stale `body_start` can point past the next
+  /// `FunDef`, and the CLIF backend then reads a live body as
+  /// empty and imports the function instead of defining it.
+  pub fn fixup_fundef_offsets(instructions: &mut [Insn]) {
+    for (idx, insn) in instructions.iter_mut().enumerate() {
+      if let Insn::FunDef { body_start, .. } = insn {
+        *body_start = (idx + 1) as u32;
+      }
+    }
+  }
 }
 
 impl Insn {
@@ -560,6 +574,7 @@ impl Insn {
       | Insn::ChannelCreate { dst, .. }
       | Insn::ChannelRecv { dst, .. }
       | Insn::FnAddr { dst, .. }
+      | Insn::ClosureEnv { dst, .. }
       | Insn::TaskSpawn { dst, .. }
       | Insn::TaskAwait { dst, .. }
       | Insn::TaskCancelled { dst, .. }
@@ -707,6 +722,16 @@ impl Insn {
       }
       Insn::ChannelClose { channel } => f(*channel),
       Insn::FnAddr { dst, .. } => f(*dst),
+      Insn::ClosureEnv {
+        dst,
+        drop,
+        captures,
+      } => {
+        f(*dst);
+        f(*drop);
+        captures.iter().for_each(|v| f(*v));
+      }
+      Insn::ClosureEnvFree { env } => f(*env),
       Insn::TaskSpawn { dst, args, .. } => {
         f(*dst);
         args.iter().for_each(|v| f(*v));
@@ -900,6 +925,16 @@ impl Insn {
       // produced address `dst` participates in the value
       // namespace.
       Insn::FnAddr { dst, .. } => f(dst),
+      Insn::ClosureEnv {
+        dst,
+        drop,
+        captures,
+      } => {
+        f(dst);
+        f(drop);
+        captures.iter_mut().for_each(&mut *f);
+      }
+      Insn::ClosureEnvFree { env } => f(env),
       Insn::TaskSpawn { dst, args, .. } => {
         f(dst);
         args.iter_mut().for_each(&mut *f);
@@ -1071,8 +1106,11 @@ impl Insn {
       | Insn::TestRun { .. }
       | Insn::TestSummary
       // `FnAddr`'s result is always a code pointer (`s64`),
-      // so it carries no substitutable `TyId`.
+      // and `ClosureEnv`'s a data pointer, so neither carries
+      // a substitutable `TyId`.
       | Insn::FnAddr { .. }
+      | Insn::ClosureEnv { .. }
+      | Insn::ClosureEnvFree { .. }
       | Insn::Nop => {}
       Insn::CoerceToDyn { concrete_ty, .. } => f(concrete_ty),
       Insn::DynDispatch { ty_id, .. } => f(ty_id),
@@ -1184,6 +1222,9 @@ pub enum Insn {
     span: Span,
     /// `true` when declared with the `test` modifier.
     is_test: bool,
+    /// The user-data slot of an `ffi` callback, from a
+    /// `%% user_data(param).` attribute.
+    env_slot: Option<EnvSlot>,
   },
   /// Return from function
   Return {
@@ -1554,6 +1595,33 @@ pub enum Insn {
     /// disambiguated. Mirrors `TaskSpawn.callee_pack`.
     callee_pack: Option<Symbol>,
   },
+  /// Heap-allocate a capturing closure's environment: one
+  /// 8-byte slot per captured value, in capture order, laid
+  /// out like a `[]s64` array so the closure's `__env`
+  /// trampoline reads slot `i` back with `ArrayIndex`. `dst`
+  /// is the array's address (`s64`), passed alongside the
+  /// trampoline's `FnAddr` as the `(fn_ptr, env_ptr)` pair a
+  /// `Fn(...)` parameter lowers to. The values are moved in
+  /// — the block outlives the creating frame, so a pool
+  /// worker or task can run the closure after it returns.
+  ///
+  /// `drop` is the address of the closure's `__env_drop`
+  /// glue (or `0` when no capture owns a destructor). It sits
+  /// in the word just below `dst`, where the runtime's
+  /// `zo_closure_env_free` finds it, whichever backend laid
+  /// out the array header.
+  ClosureEnv {
+    dst: ValueId,
+    drop: ValueId,
+    captures: Vec<ValueId>,
+  },
+  /// Release a `ClosureEnv` once its closure has run for the
+  /// last time: run its `drop` glue over the captures, then
+  /// free the block. Lowered to a `zo_closure_env_free` call.
+  /// Emitted after the call the env was built for; the
+  /// ownership pass elides it when that call hands the env
+  /// to the runtime (a pool task), which frees it itself.
+  ClosureEnvFree { env: ValueId },
   TaskSpawn {
     dst: ValueId,
     callee: Symbol,
@@ -1620,9 +1688,12 @@ pub enum Insn {
   /// cancellation to have any observable effect.
   TaskCancel { task: ValueId },
   /// Runtime string slice `src[lo..hi]`. Lowered to
-  /// `BL _zo_str_slice(src, lo, hi)`; returns a fresh
-  /// heap-backed `str`. Emitted by the executor when
-  /// the bounds are not compile-time constants
+  /// `BL _zo_str_slice(src, lo, hi)`; returns a view
+  /// `str` borrowing `src`'s bytes, and panics on a
+  /// bound past the end or inside a UTF-8 sequence.
+  /// `..=` and open bounds arrive pre-lowered (`hi + 1`,
+  /// `0`, `len src`). Emitted by the executor when the
+  /// bounds are not compile-time constants
   /// (compile-time bounds still fold to `ConstString`).
   StrSlice {
     dst: ValueId,
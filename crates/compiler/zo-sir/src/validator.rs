@@ -536,6 +536,8 @@ fn check_insn(insn: &Insn, idx: usize, ctx: &mut ValidatorCtx<'_>) {
     // `FnAddr` carries no `TyId` (its result is always a
     // code pointer), so there is nothing to placeholder-check.
     Insn::FnAddr { .. } => {}
+    // Nor does `ClosureEnv` — its result is a data pointer.
+    Insn::ClosureEnv { .. } | Insn::ClosureEnvFree { .. } => {}
   }
 }
 
@@ -715,6 +717,7 @@ mod tests {
         pubness: zo_value::Pubness::No,
         self_kind: SelfKind::None,
         link_name: None,
+        env_slot: None,
         owning_pack: None,
         span: Span::ZERO,
         is_test: false,
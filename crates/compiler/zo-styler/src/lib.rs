@@ -2,7 +2,7 @@
 ///
 /// Tailwind-inspired short names that expand to full CSS
 /// properties. Stored as a static lookup — no allocation.
-const PROPERTIES: &[(&str, &str)] = &[
+pub const PROPERTIES: &[(&str, &str)] = &[
   // dimensions.
   ("w", "width"),
   ("h", "height"),
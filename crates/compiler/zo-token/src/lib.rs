@@ -1,6 +1,6 @@
 mod token;
 
 pub use token::{
-  Base, InterpSegment, LiteralStore, LiteralStoreBaseline, Token, TokenBuffer,
-  TokenId,
+  Align, Base, FormatKind, FormatSpec, InterpSegment, LiteralStore,
+  LiteralStoreBaseline, Token, TokenBuffer, TokenId,
 };
@@ -69,6 +69,7 @@ pub enum Token {
   Group,
   And,
   Return,
+  Defer,
   Break,
   Continue,
   Match,
@@ -358,6 +359,7 @@ impl Token {
           | Self::When
           | Self::Loop
           | Self::Match
+          | Self::Defer
           | Self::Break
           | Self::Continue
           | Self::And
@@ -518,6 +520,107 @@ pub enum InterpSegment {
   Literal(Symbol),
   /// Variable name inside {}.
   Variable(Symbol),
+  /// Variable name with a format spec — `{name:spec}`.
+  Formatted(Symbol, FormatSpec),
+}
+
+/// A parsed `{name:spec}` format specifier, following the
+/// `[[fill]align][+][#][0][width][.precision][kind]` grammar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct FormatSpec {
+  /// Padding char, `' '` unless a fill precedes the align.
+  pub fill: char,
+  /// `<`, `^` or `>`. `None` keeps the per-type default —
+  /// numbers right-aligned, everything else left-aligned.
+  pub align: Option<Align>,
+  /// `+` — print the sign of non-negative numbers too.
+  pub sign: bool,
+  /// `#` — prefix `0x` / `0b` / `0o` in the radix kinds.
+  pub alternate: bool,
+  /// `0` — pad numbers with zeros after the sign and prefix.
+  pub zero: bool,
+  /// Minimum width in chars.
+  pub width: u16,
+  /// Digits after the decimal point, for floats.
+  pub precision: Option<u16>,
+  /// How the value itself is written.
+  pub kind: FormatKind,
+}
+
+impl FormatSpec {
+  /// Packs the spec into the `u64` the runtime's `zo_fmt_*`
+  /// functions decode — bits `0..21` fill, `21..23` align,
+  /// `23` sign, `24` alternate, `25` zero, `26..29` kind,
+  /// `29` precision present, `32..48` width and `48..64`
+  /// precision.
+  pub fn pack(self) -> u64 {
+    let align = match self.align {
+      None => 0,
+      Some(Align::Left) => 1,
+      Some(Align::Center) => 2,
+      Some(Align::Right) => 3,
+    };
+
+    let kind = match self.kind {
+      FormatKind::Display => 0,
+      FormatKind::LowerHex => 1,
+      FormatKind::UpperHex => 2,
+      FormatKind::Binary => 3,
+      FormatKind::Octal => 4,
+    };
+
+    (self.fill as u64)
+      | (align << 21)
+      | ((self.sign as u64) << 23)
+      | ((self.alternate as u64) << 24)
+      | ((self.zero as u64) << 25)
+      | (kind << 26)
+      | ((self.precision.is_some() as u64) << 29)
+      | ((self.width as u64) << 32)
+      | ((self.precision.unwrap_or(0) as u64) << 48)
+  }
+}
+
+impl Default for FormatSpec {
+  fn default() -> Self {
+    Self {
+      fill: ' ',
+      align: None,
+      sign: false,
+      alternate: false,
+      zero: false,
+      width: 0,
+      precision: None,
+      kind: FormatKind::Display,
+    }
+  }
+}
+
+/// Alignment of a formatted value inside its width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Align {
+  /// `<`.
+  Left,
+  /// `^`.
+  Center,
+  /// `>`.
+  Right,
+}
+
+/// How a formatted value is written — the trailing letter
+/// of a spec.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum FormatKind {
+  /// No letter — the type's usual form.
+  Display,
+  /// `x`.
+  LowerHex,
+  /// `X`.
+  UpperHex,
+  /// `b`.
+  Binary,
+  /// `o`.
+  Octal,
 }
 
 /// Display base for an integer literal — how `showln` prints
@@ -215,7 +215,7 @@ fn test_control_flow_keywords() {
   assert_tokens_stream(
     r#"
       loop while for if else when
-      break continue return match
+      break continue return defer match
     "#,
     &[
       (Token::Loop, "loop"),
@@ -227,6 +227,7 @@ fn test_control_flow_keywords() {
       (Token::Break, "break"),
       (Token::Continue, "continue"),
       (Token::Return, "return"),
+      (Token::Defer, "defer"),
       (Token::Match, "match"),
       (Token::Eof, ""),
     ],
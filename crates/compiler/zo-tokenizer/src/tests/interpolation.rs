@@ -1,8 +1,8 @@
 use crate::Tokenizer;
-use crate::tokenizer::is_valid_ident;
+use crate::tokenizer::{is_valid_ident, parse_format_spec};
 
 use zo_interner::Interner;
-use zo_token::{InterpSegment, Token};
+use zo_token::{Align, FormatKind, FormatSpec, InterpSegment, Token};
 
 use proptest::prelude::*;
 use proptest::test_runner::{Config, FileFailurePersistence};
@@ -114,12 +114,65 @@ fn reconstruct(segments: &[InterpSegment], interner: &Interner) -> String {
         out.push_str(interner.get(*sym));
         out.push('}');
       }
+      InterpSegment::Formatted(sym, spec) => {
+        out.push('{');
+        out.push_str(interner.get(*sym));
+        out.push(':');
+        out.push_str(&spec_text(spec));
+        out.push('}');
+      }
     }
   }
 
   out
 }
 
+/// Renders a spec back in its canonical `[[fill]align][+][#][0][width]
+/// [.precision][kind]` order.
+fn spec_text(spec: &FormatSpec) -> String {
+  let mut out = String::new();
+
+  if let Some(align) = spec.align {
+    out.push(spec.fill);
+    out.push(match align {
+      Align::Left => '<',
+      Align::Center => '^',
+      Align::Right => '>',
+    });
+  }
+
+  if spec.sign {
+    out.push('+');
+  }
+
+  if spec.alternate {
+    out.push('#');
+  }
+
+  if spec.zero {
+    out.push('0');
+  }
+
+  if spec.width > 0 {
+    out.push_str(&spec.width.to_string());
+  }
+
+  if let Some(precision) = spec.precision {
+    out.push('.');
+    out.push_str(&precision.to_string());
+  }
+
+  out.push_str(match spec.kind {
+    FormatKind::Display => "",
+    FormatKind::LowerHex => "x",
+    FormatKind::UpperHex => "X",
+    FormatKind::Binary => "b",
+    FormatKind::Octal => "o",
+  });
+
+  out
+}
+
 fn roundtrip(content: &str) -> bool {
   let quoted = format!("\"{content}\"");
   let mut interner = Interner::new();
@@ -386,3 +439,90 @@ fn backslash_in_braces_not_variable() {
     );
   }
 }
+
+// -------------------------------------------------------
+// Format specs — `{name:spec}`.
+// -------------------------------------------------------
+
+fn canonical_spec_strategy() -> impl Strategy<Value = String> {
+  "([*_ ][<^>])?[+]?#?0?([1-9][0-9]{0,2})?([.](0|[1-9][0-9]?))?[xXbo]?"
+}
+
+proptest! {
+  #![proptest_config(Config {
+    failure_persistence: Some(Box::new(
+      FileFailurePersistence::Off,
+    )),
+    cases: 1000,
+    ..Config::default()
+  })]
+
+  #[test]
+  fn roundtrip_formatted_variable(
+    prefix in safe_literal_strategy(),
+    var in ident_strategy(),
+    spec in canonical_spec_strategy(),
+    suffix in safe_literal_strategy(),
+  ) {
+    let content = format!("{prefix}{{{var}:{spec}}}{suffix}");
+
+    prop_assert!(
+      roundtrip(&content),
+      "roundtrip failed for: {:?}", content
+    );
+  }
+}
+
+#[test]
+fn formatted_variable_segment() {
+  let segments = segments_for("total: {price:.2}");
+
+  assert_eq!(segments.len(), 2);
+
+  let InterpSegment::Formatted(_, spec) = segments[1] else {
+    panic!("expected Formatted, got {:?}", segments[1]);
+  };
+
+  assert_eq!(spec.precision, Some(2));
+  assert_eq!(spec.kind, FormatKind::Display);
+}
+
+#[test]
+fn parse_spec_fill_align_width() {
+  let spec = parse_format_spec("*^10").unwrap();
+
+  assert_eq!(spec.fill, '*');
+  assert_eq!(spec.align, Some(Align::Center));
+  assert_eq!(spec.width, 10);
+}
+
+#[test]
+fn parse_spec_bare_align_keeps_space_fill() {
+  let spec = parse_format_spec(">8").unwrap();
+
+  assert_eq!(spec.fill, ' ');
+  assert_eq!(spec.align, Some(Align::Right));
+  assert_eq!(spec.width, 8);
+}
+
+#[test]
+fn parse_spec_zero_pad_and_radix() {
+  let spec = parse_format_spec("#010x").unwrap();
+
+  assert!(spec.alternate);
+  assert!(spec.zero);
+  assert_eq!(spec.width, 10);
+  assert_eq!(spec.kind, FormatKind::LowerHex);
+}
+
+#[test]
+fn parse_spec_empty_is_default() {
+  assert_eq!(parse_format_spec(""), Some(FormatSpec::default()));
+}
+
+#[test]
+fn parse_spec_rejects_malformed() {
+  for spec in ["q", ".", "5.x2", "x5", "99999999", "+-", "<<<"] {
+    assert_eq!(parse_format_spec(spec), None, "accepted {spec:?}");
+  }
+}
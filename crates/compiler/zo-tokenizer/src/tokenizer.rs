@@ -2,7 +2,10 @@ use zo_error::{Error, ErrorKind};
 use zo_interner::Interner;
 use zo_reporter::report_error;
 use zo_span::Span;
-use zo_token::{Base, InterpSegment, LiteralStore, Token, TokenBuffer};
+use zo_token::{
+  Align, Base, FormatKind, FormatSpec, InterpSegment, LiteralStore, Token,
+  TokenBuffer,
+};
 
 use serde::Serialize;
 
@@ -509,6 +512,7 @@ impl<'a> Tokenizer<'a> {
         4 if bytes == b"test" => Token::Test,
         5 if bytes == b"while" => Token::While,
         5 if bytes == b"break" => Token::Break,
+        5 if bytes == b"defer" => Token::Defer,
         5 if bytes == b"false" => Token::False,
         5 if bytes == b"match" => Token::Match,
         5 if bytes == b"apply" => Token::Apply,
@@ -1698,7 +1702,13 @@ impl<'a> Tokenizer<'a> {
 
       if has_interpolation {
         // Parse segments and store in side table.
-        let interp_id = self.parse_interp_segments(string_content);
+        let interp_id = self.parse_interp_segments(
+          string_content,
+          Span {
+            start: start as u32,
+            len,
+          },
+        );
         // Also intern the full string for the tree node.
         let symbol = self.interner.intern(string_content);
         let str_id = self.literals.push_string_symbol(symbol);
@@ -1737,8 +1747,9 @@ impl<'a> Tokenizer<'a> {
   }
 
   /// Parses interpolation segments from string content.
-  /// Returns the interp_ranges index.
-  fn parse_interp_segments(&mut self, content: &str) -> u32 {
+  /// Returns the interp_ranges index. `span` covers the whole
+  /// string literal and anchors a malformed format spec.
+  fn parse_interp_segments(&mut self, content: &str, span: Span) -> u32 {
     let bytes = content.as_bytes();
     let mut segments: Vec<InterpSegment> = Vec::new();
     let mut lit_start = 0;
@@ -1782,17 +1793,36 @@ impl<'a> Tokenizer<'a> {
         }
 
         if i < bytes.len() {
-          let var_name = &content[var_start..i];
+          let inner = &content[var_start..i];
+          let (var_name, spec) = match inner.split_once(':') {
+            Some((name, spec)) => (name, Some(parse_format_spec(spec))),
+            None => (inner, None),
+          };
 
-          if is_valid_ident(var_name) {
-            let sym = self.interner.intern(var_name);
+          match (is_valid_ident(var_name), spec) {
+            (true, None) => {
+              let sym = self.interner.intern(var_name);
 
-            segments.push(InterpSegment::Variable(sym));
-          } else {
-            let lit = &content[var_start - 1..=i];
-            let sym = self.interner.intern(lit);
+              segments.push(InterpSegment::Variable(sym));
+            }
+            (true, Some(Some(spec))) => {
+              let sym = self.interner.intern(var_name);
+
+              segments.push(InterpSegment::Formatted(sym, spec));
+            }
+            (is_ident, spec) => {
+              // `{name:spec}` whose spec strays from the
+              // grammar: the user meant a spec, so say so —
+              // the text itself stays literal.
+              if is_ident && spec.is_some() {
+                report_error(Error::new(ErrorKind::InvalidFormatSpec, span));
+              }
+
+              let lit = &content[var_start - 1..=i];
+              let sym = self.interner.intern(lit);
 
-            segments.push(InterpSegment::Literal(sym));
+              segments.push(InterpSegment::Literal(sym));
+            }
           }
 
           i += 1; // skip }
@@ -2053,12 +2083,84 @@ impl<'a> Tokenizer<'a> {
   }
 }
 
-/// Returns the UTF-8 byte length of the codepoint whose
-/// lead byte is `b`. ASCII (< 0x80) and stray continuation
-/// bytes (0x80..0xC0) both fall back to 1 so the tokenizer
-/// still advances instead of looping — malformed UTF-8 is
-/// reported upstream as `UnexpectedCharacter` at its own
-/// site.
+/// Parses the spec after the `:` of a `{name:spec}`
+/// interpolation — `[[fill]align][+][#][0][width]
+/// [.precision][kind]`. `None` when the spec strays from
+/// that grammar.
+pub(crate) fn parse_format_spec(spec: &str) -> Option<FormatSpec> {
+  let align_of = |ch: char| match ch {
+    '<' => Some(Align::Left),
+    '^' => Some(Align::Center),
+    '>' => Some(Align::Right),
+    _ => None,
+  };
+
+  let chars = spec.chars().collect::<Vec<_>>();
+  let mut out = FormatSpec::default();
+  let mut i = 0;
+
+  if let Some(align) = chars.get(1).copied().and_then(align_of) {
+    out.fill = chars[0];
+    out.align = Some(align);
+    i = 2;
+  } else if let Some(align) = chars.first().copied().and_then(align_of) {
+    out.align = Some(align);
+    i = 1;
+  }
+
+  if chars.get(i) == Some(&'+') {
+    out.sign = true;
+    i += 1;
+  }
+
+  if chars.get(i) == Some(&'#') {
+    out.alternate = true;
+    i += 1;
+  }
+
+  if chars.get(i) == Some(&'0') {
+    out.zero = true;
+    i += 1;
+  }
+
+  let digits = |i: &mut usize| {
+    let start = *i;
+
+    while chars.get(*i).is_some_and(char::is_ascii_digit) {
+      *i += 1;
+    }
+
+    chars[start..*i].iter().collect::<String>()
+  };
+
+  let width = digits(&mut i);
+
+  if !width.is_empty() {
+    out.width = width.parse().ok()?;
+  }
+
+  if chars.get(i) == Some(&'.') {
+    i += 1;
+
+    out.precision = Some(digits(&mut i).parse().ok()?);
+  }
+
+  out.kind = match chars.get(i) {
+    None => FormatKind::Display,
+    Some('x') => FormatKind::LowerHex,
+    Some('X') => FormatKind::UpperHex,
+    Some('b') => FormatKind::Binary,
+    Some('o') => FormatKind::Octal,
+    Some(_) => return None,
+  };
+
+  if out.kind != FormatKind::Display {
+    i += 1;
+  }
+
+  (i == chars.len()).then_some(out)
+}
+
 pub(crate) fn is_valid_ident(s: &str) -> bool {
   let bytes = s.as_bytes();
 
@@ -2069,6 +2171,12 @@ pub(crate) fn is_valid_ident(s: &str) -> bool {
       .all(|b| b.is_ascii_alphanumeric() || *b == b'_')
 }
 
+/// Returns the UTF-8 byte length of the codepoint whose
+/// lead byte is `b`. ASCII (< 0x80) and stray continuation
+/// bytes (0x80..0xC0) both fall back to 1 so the tokenizer
+/// still advances instead of looping — malformed UTF-8 is
+/// reported upstream as `UnexpectedCharacter` at its own
+/// site.
 #[inline(always)]
 fn utf8_cp_len(b: u8) -> usize {
   if b < 0xC0 {
@@ -237,7 +237,7 @@ fn is_keyword(kind: Token) -> bool {
     Token::Fun | Token::Fn | Token::Mut | Token::Imu
     | Token::If | Token::Else | Token::While | Token::For
     | Token::Loop | Token::Pack | Token::Load | Token::Type
-    | Token::Struct | Token::Enum | Token::Return | Token::Break
+    | Token::Struct | Token::Enum | Token::Return | Token::Defer | Token::Break
     | Token::Continue | Token::Match | Token::When | Token::As
     | Token::Is | Token::True | Token::False | Token::Pub
     | Token::Val | Token::Ffi | Token::Abstract | Token::Apply
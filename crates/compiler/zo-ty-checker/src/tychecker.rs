@@ -20,7 +20,7 @@ pub struct TyScheme {
 
 /// Undo log entry for O(1) scope push / O(k) scope pop.
 /// Tracks what changed so we can restore on pop_scope.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum UndoEntry {
   /// Key was freshly inserted (remove on undo).
   Insert(Symbol),
@@ -39,6 +39,7 @@ pub struct TyCheckerBaseline {
 }
 
 /// Type checker implementing Hindley-Milner W algorithm
+#[derive(Clone)]
 pub struct TyChecker {
   /// Counter for fresh type IDs (for all types in tys)
   next_ty_id: u32,
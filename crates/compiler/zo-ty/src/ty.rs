@@ -275,7 +275,7 @@ pub struct FragmentTyId(pub u32);
 
 /// Type table using structure of arrays for cache-friendly access.
 /// This stores all compound types encountered during execution.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct TyTable {
   /// Array types stored contiguously.
   pub array_types: Vec<ArrayTy>,
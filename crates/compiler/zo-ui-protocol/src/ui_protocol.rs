@@ -147,6 +147,14 @@ pub enum ElementTag {
 }
 
 impl ElementTag {
+  /// Every enumerated tag name, in declaration order — what
+  /// an editor offers after `<`.
+  pub const NAMES: &'static [&'static str] = &[
+    "div", "section", "main", "article", "aside", "header", "footer", "nav",
+    "form", "ul", "ol", "li", "span", "h1", "h2", "h3", "h4", "h5", "h6", "p",
+    "img", "button", "input", "textarea", "select", "option",
+  ];
+
   /// Canonical HTML tag name.
   pub fn as_str(&self) -> &str {
     match self {
@@ -345,6 +353,11 @@ pub enum EventKind {
 }
 
 impl EventKind {
+  /// Every event name accepted after `@` in markup.
+  pub const NAMES: &'static [&'static str] = &[
+    "click", "hover", "change", "input", "focus", "blur", "submit",
+  ];
+
   /// Parse an event name (the bit after `@` in markup, or
   /// the IPC frame prefix from the web bridge) into an
   /// `EventKind`. Single source of truth for the
@@ -599,6 +612,20 @@ mod set_attr_tests {
     assert_eq!(EventKind::from_name("nope"), None);
   }
 
+  #[test]
+  fn name_tables_cover_enumerated_variants() {
+    for name in ElementTag::NAMES {
+      let tag = ElementTag::from_name(name).unwrap();
+
+      assert!(!matches!(tag, ElementTag::Custom(_)), "{name}");
+      assert_eq!(tag.as_str(), *name);
+    }
+
+    for name in EventKind::NAMES {
+      assert!(EventKind::from_name(name).is_some(), "{name}");
+    }
+  }
+
   #[test]
   fn event_kind_payload_classification() {
     // Payload-bearing kinds carry the input's text.
@@ -1,6 +1,6 @@
 mod value;
 
 pub use value::{
-  AutoDrop, CaptureInfo, ClosureValue, FunDef, FunctionKind, Local, LocalKind,
-  Pubness, Value, ValueId, ValueStorage,
+  AutoDrop, CaptureInfo, ClosureValue, EnvSlot, FunDef, FunctionKind, Local,
+  LocalKind, Pubness, Value, ValueId, ValueStorage,
 };
@@ -250,6 +250,20 @@ pub enum FunctionKind {
   },
 }
 
+/// An `ffi` callback's user-data slot, declared with
+/// `%% user_data(param).`: the C `void *` the callback is
+/// invoked with, filled with the callback's env word at the
+/// call site. `owned` (`%% owns_env.`) marks a runtime that
+/// frees the env itself once the callback has run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnvSlot {
+  /// Index of the slot in the `ffi`'s params — right after
+  /// its `Fn(...)` callback.
+  pub param: u32,
+  /// Whether the runtime takes ownership of the env.
+  pub owned: bool,
+}
+
 /// Represents a [`LocalKind`] — parameter vs local
 /// variable.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -318,6 +332,8 @@ pub struct FunDef {
   pub span: Span,
   /// `true` when declared with the `test` modifier.
   pub is_test: bool,
+  /// The user-data slot of an `ffi` callback, if declared.
+  pub env_slot: Option<EnvSlot>,
 }
 
 /// Represents a [`Local`] variable entry instance.
@@ -75,6 +75,7 @@ impl<'src> Parser<'src> {
     let mut version = None;
     let mut authors = Vec::new();
     let mut license = None;
+    let mut lints = Vec::new();
     let mut entry_point = None;
     let mut source_dir = None;
     let mut binary_name = None;
@@ -127,6 +128,9 @@ impl<'src> Parser<'src> {
             .consume_number("Expected number value for 'optimization_level'")?;
           optimization_level = value as u8;
         }
+        "lints" => {
+          lints = self.parse_lint_table()?;
+        }
         "debug_symbols" => {
           let value =
             self.consume_bool("Expected boolean value for 'debug_symbols'")?;
@@ -167,6 +171,7 @@ impl<'src> Parser<'src> {
       debug_symbols,
       authors,
       license,
+      lints,
     })
   }
 
@@ -215,6 +220,43 @@ impl<'src> Parser<'src> {
     Ok(values)
   }
 
+  /// Parse a lint table like `(unused-variable: deny, ...)`.
+  /// Keys are kebab-case lint ids, values a bare level.
+  fn parse_lint_table(&mut self) -> Result<Vec<(String, String)>, StageError> {
+    self.consume(TokenKind::LeftParen, "Expected '(' to open 'lints'")?;
+
+    let mut lints = Vec::new();
+
+    while !self.check(TokenKind::RightParen) && !self.is_at_end() {
+      let id = self
+        .consume(TokenKind::Identifier, "Expected lint name")?
+        .lexeme(self.source)
+        .to_string();
+
+      self.consume(TokenKind::Colon, "Expected ':' after lint name")?;
+
+      let level = self
+        .consume(TokenKind::Identifier, "Expected 'allow', 'warn' or 'deny'")?
+        .lexeme(self.source);
+
+      if !matches!(level, "allow" | "warn" | "deny") {
+        return Err(self.error(&format!(
+          "Unknown lint level '{level}', expected 'allow', 'warn' or 'deny'"
+        )));
+      }
+
+      lints.push((id, level.to_string()));
+
+      if !self.check(TokenKind::RightParen) {
+        self.consume(TokenKind::Comma, "Expected ',' or ')'")?;
+      }
+    }
+
+    self.consume(TokenKind::RightParen, "Expected ')' to close 'lints'")?;
+
+    Ok(lints)
+  }
+
   /// Consume a string token and return its value.
   fn consume_string(&mut self, error_msg: &str) -> Result<String, StageError> {
     let token = self.consume(TokenKind::String, error_msg)?;
@@ -455,4 +497,38 @@ mod tests {
     assert_eq!(config.version.minor, 0);
     assert_eq!(config.version.patch, 0);
   }
+
+  #[test]
+  fn test_parse_lint_table() {
+    let source = r#"
+@pack = (
+  name: "linted",
+  lints: (
+    unused-variable: deny,
+    non-snake-case-name: allow,
+  ),
+)
+"#;
+
+    let config = parse_config(source).unwrap();
+    assert_eq!(
+      config.lints,
+      vec![
+        ("unused-variable".to_string(), "deny".to_string()),
+        ("non-snake-case-name".to_string(), "allow".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_error_unknown_lint_level() {
+    let source = r#"
+@pack = (
+  name: "linted",
+  lints: (unused-variable: forbid),
+)
+"#;
+
+    assert!(parse_config(source).is_err());
+  }
 }
@@ -5,6 +5,7 @@ use fret_types::{BuildContext, Stage, StageError};
 
 use zo_codegen_backend::Target as ZoTarget;
 use zo_compiler::orchestrator::Orchestrator;
+use zo_error::lint::{self, Level, LintLevels};
 
 use hashbrown::HashMap;
 
@@ -67,6 +68,9 @@ impl Stage for CompileStage {
     };
 
     let mut orchestrator = Orchestrator::new();
+
+    orchestrator.set_lint_levels(lint_levels(&ctx.config.lints)?);
+
     let result = orchestrator.compile_batch(
       source_map,
       zo_target,
@@ -100,6 +104,26 @@ impl Stage for CompileStage {
   }
 }
 
+/// Builds the compiler's lint levels from the `fret.oz`
+/// `lints` table. Later entries win; an id that isn't a lint
+/// is a config error.
+fn lint_levels(lints: &[(String, String)]) -> Result<LintLevels, StageError> {
+  let mut levels = LintLevels::new();
+
+  for (id, level) in lints {
+    let kind = lint::lookup(id).ok_or_else(|| {
+      StageError::ConfigParse(format!("Unknown lint '{id}' in 'lints'"))
+    })?;
+    let level = Level::parse(level).ok_or_else(|| {
+      StageError::ConfigParse(format!("Unknown lint level '{level}'"))
+    })?;
+
+    levels.set(kind, level);
+  }
+
+  Ok(levels)
+}
+
 /// Converts fret Target to zo_codegen_backend Target.
 fn convert_target(target: fret_types::Target) -> ZoTarget {
   match target {
@@ -138,4 +162,18 @@ mod tests {
       ZoTarget::Arm64AppleDarwin
     );
   }
+
+  #[test]
+  fn test_lint_levels_from_table() {
+    let table = [("unused-variable".to_string(), "deny".to_string())];
+
+    assert!(lint_levels(&table).is_ok());
+
+    let unknown = [("type-mismatch".to_string(), "allow".to_string())];
+
+    assert!(matches!(
+      lint_levels(&unknown),
+      Err(StageError::ConfigParse(_))
+    ));
+  }
 }
@@ -81,6 +81,11 @@ pub struct ProjectConfig {
   pub authors: Vec<String>,
   /// Project license
   pub license: Option<String>,
+  /// Project-wide lint levels from the `lints: (...)` table —
+  /// `(lint id, level)` pairs in file order, the level one of
+  /// `allow`, `warn` or `deny`. The compiler flags and the
+  /// source attributes override them.
+  pub lints: Vec<(String, String)>,
 }
 
 /// Semantic version (`major.minor.patch`).